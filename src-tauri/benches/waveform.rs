@@ -0,0 +1,48 @@
+//! Waveform peak generation benchmarks
+//!
+//! Run with `cargo bench --bench waveform`. The trim editor asks for 400
+//! peaks; long files have to stay fast enough that opening it feels instant.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sonic_deck::{generate_peaks, AudioData};
+use std::hint::black_box;
+
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Peaks requested by the trim editor
+const EDITOR_PEAKS: usize = 400;
+
+/// Stereo 440 Hz tone of `minutes` length
+fn stereo_tone(minutes: usize) -> AudioData {
+    let frames = minutes * 60 * SAMPLE_RATE as usize;
+    let samples = (0..frames)
+        .flat_map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let sample = (t * 440.0 * std::f32::consts::TAU).sin() * 0.5;
+            [sample, -sample]
+        })
+        .collect();
+    AudioData {
+        samples,
+        sample_rate: SAMPLE_RATE,
+        channels: 2,
+    }
+}
+
+fn bench_generate_peaks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate_peaks");
+    group.sample_size(20);
+    for minutes in [1, 10] {
+        let audio = stereo_tone(minutes);
+        group.throughput(Throughput::Elements(audio.samples.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}min", minutes)),
+            &audio,
+            |b, audio| b.iter(|| generate_peaks(black_box(audio), EDITOR_PEAKS)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate_peaks);
+criterion_main!(benches);
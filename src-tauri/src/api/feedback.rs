@@ -0,0 +1,123 @@
+//! Trigger feedback for external surfaces
+//!
+//! Sound start/stop and hotkey events are emitted as the Tauri event
+//! `trigger-feedback` and fanned out to every API subscriber (Stream Deck
+//! plugins, OpenRGB scripts, ...) so keys can light up while a sound plays.
+
+use serde::Serialize;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use tauri::Emitter;
+use tracing::error;
+
+use super::spectator;
+use crate::hotkeys::HotkeyAction;
+use crate::stats::now_ms;
+
+/// Subscribers receiving serialized feedback events
+static SUBSCRIBERS: Mutex<Vec<Sender<String>>> = Mutex::new(Vec::new());
+
+/// What happened
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerFeedback {
+    /// A sound became audible
+    SoundStarted {
+        sound_id: String,
+        playback_id: String,
+    },
+    /// A sound finished or was stopped
+    SoundStopped {
+        sound_id: String,
+        playback_id: String,
+    },
+    /// A global hotkey was pressed
+    HotkeyFired {
+        hotkey: String,
+        sound_id: Option<String>,
+        action: Option<HotkeyAction>,
+    },
+}
+
+/// Feedback event with timestamp (payload of `trigger-feedback`)
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedbackEvent {
+    #[serde(flatten)]
+    pub feedback: TriggerFeedback,
+    /// Unix time in milliseconds
+    pub timestamp_ms: u64,
+}
+
+/// Receive every feedback event published from now on (as JSON)
+pub fn subscribe() -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.push(tx);
+    }
+    rx
+}
+
+/// Emit a feedback event to the frontend, all API subscribers and the
+/// spectator view
+pub fn publish(app_handle: &tauri::AppHandle, feedback: TriggerFeedback) {
+    let event = FeedbackEvent {
+        feedback,
+        timestamp_ms: now_ms(),
+    };
+
+    if let Err(e) = app_handle.emit("trigger-feedback", &event) {
+        error!("Failed to emit trigger feedback event: {}", e);
+    }
+    broadcast(&event);
+    spectator::record(app_handle, &event);
+}
+
+/// Send an event to all subscribers, dropping disconnected ones
+fn broadcast(event: &FeedbackEvent) {
+    let Ok(json) = serde_json::to_string(event) else {
+        return;
+    };
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.retain(|tx| tx.send(json.clone()).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_serialization() {
+        let event = FeedbackEvent {
+            feedback: TriggerFeedback::SoundStarted {
+                sound_id: "s1".to_string(),
+                playback_id: "playback_1".to_string(),
+            },
+            timestamp_ms: 42,
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"sound_started\""));
+        assert!(json.contains("\"sound_id\":\"s1\""));
+        assert!(json.contains("\"timestamp_ms\":42"));
+    }
+
+    #[test]
+    fn test_broadcast_reaches_subscribers_and_drops_closed() {
+        let rx = subscribe();
+        let closed = subscribe();
+        drop(closed);
+
+        broadcast(&FeedbackEvent {
+            feedback: TriggerFeedback::HotkeyFired {
+                hotkey: "Ctrl+F1".to_string(),
+                sound_id: None,
+                action: Some(HotkeyAction::ToggleNormalization),
+            },
+            timestamp_ms: 0,
+        });
+
+        let json = rx.try_recv().unwrap();
+        assert!(json.contains("\"action\":\"toggle_normalization\""));
+    }
+}
@@ -0,0 +1,18 @@
+//! Local API for external control surfaces
+//!
+//! Serves on 127.0.0.1 when enabled in settings:
+//! - `GET /events` - server-sent event stream of trigger feedback
+//! - `GET /spectate` - read-only event stream of now-playing, recent plays
+//!   and output levels
+//! - `GET /now-playing` - current spectator view as JSON
+//!
+//! Once tokens exist, requests need one granting the route's capability
+//! (see `api_security`).
+
+mod feedback;
+mod server;
+mod spectator;
+
+pub use feedback::{publish, TriggerFeedback};
+pub use server::{running_port, start, stop};
+pub use spectator::publish_levels;
@@ -0,0 +1,449 @@
+//! Minimal HTTP server for the local API
+//!
+//! Listens on 127.0.0.1 only. Each connection gets its own thread, up to
+//! `MAX_CONNECTIONS` at a time; event streams use server-sent events
+//! (`text/event-stream`), which browsers, Node and most scripting languages
+//! can consume without extra libraries.
+//!
+//! Requests must name the server itself in `Host` (`127.0.0.1:<port>` or
+//! `localhost:<port>`), which defeats DNS rebinding, and responses carry no
+//! CORS headers, so websites open in a browser can't read the API.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use super::{feedback, spectator};
+use crate::api_security::{self, Capability};
+
+/// How often the accept loop checks for a stop request
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time a client gets to send its request head
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Interval between keep-alive comments on idle event streams
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Upper bound for request head size
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Connections served at once; further ones get 503 and are closed
+const MAX_CONNECTIONS: usize = 32;
+
+/// Event stream endpoints, which also take the token as `?token=` since
+/// `EventSource` can't send an `Authorization` header
+const QUERY_TOKEN_PATHS: [&str; 2] = ["/events", "/spectate"];
+
+/// Parsed request head
+#[derive(Debug)]
+struct Request {
+    method: String,
+    /// Path without query
+    path: String,
+    /// Token from `Authorization: Bearer`, or from the `token` query parameter
+    /// on `QUERY_TOKEN_PATHS`
+    token: Option<String>,
+    /// `Host` header value
+    host: Option<String>,
+}
+
+/// Count of connections being served
+#[derive(Clone, Default)]
+struct ConnectionLimit {
+    active: Arc<AtomicUsize>,
+}
+
+impl ConnectionLimit {
+    /// Take a slot, or None if `max` connections are already being served
+    fn try_acquire(&self, max: usize) -> Option<ConnectionSlot> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < max).then_some(active + 1)
+            })
+            .ok()?;
+        Some(ConnectionSlot {
+            active: self.active.clone(),
+        })
+    }
+}
+
+/// Slot of a served connection, released when dropped
+struct ConnectionSlot {
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Stop signal and port of the running server
+static SERVER: Mutex<Option<(u16, Arc<AtomicBool>)>> = Mutex::new(None);
+
+/// Start the API server on `port`, replacing a running one
+pub fn start(port: u16) -> Result<(), String> {
+    stop();
+
+    let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+        .map_err(|e| format!("Failed to bind API server to port {}: {}", port, e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure API server: {}", e))?;
+
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    if let Ok(mut server) = SERVER.lock() {
+        *server = Some((port, stop_signal.clone()));
+    }
+
+    thread::spawn(move || accept_loop(listener, port, stop_signal));
+    info!("API server listening on 127.0.0.1:{}", port);
+    Ok(())
+}
+
+/// Stop the API server (open event streams close on their next write)
+pub fn stop() {
+    let server = SERVER.lock().ok().and_then(|mut s| s.take());
+    if let Some((port, stop_signal)) = server {
+        stop_signal.store(true, Ordering::Relaxed);
+        info!("API server on port {} stopped", port);
+    }
+}
+
+/// Port of the running server
+pub fn running_port() -> Option<u16> {
+    SERVER
+        .lock()
+        .ok()
+        .and_then(|s| s.as_ref().map(|(port, _)| *port))
+}
+
+fn accept_loop(listener: TcpListener, port: u16, stop: Arc<AtomicBool>) {
+    let connections = ConnectionLimit::default();
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, addr)) => {
+                debug!("API connection from {}", addr);
+                let Some(slot) = connections.try_acquire(MAX_CONNECTIONS) else {
+                    warn!("API connection from {} refused, too many connections", addr);
+                    let _ = refuse_connection(&stream);
+                    continue;
+                };
+                let stop = stop.clone();
+                thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(e) = handle_connection(stream, port, &stop) {
+                        debug!("API connection from {} closed: {}", addr, e);
+                    }
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(e) => {
+                error!("API server accept failed: {}", e);
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Answer 503 without reading the request
+fn refuse_connection(stream: &TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    write_response(stream, "503 Service Unavailable", "Too many connections")
+}
+
+fn handle_connection(stream: TcpStream, port: u16, stop: &AtomicBool) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+
+    let Some(request) = read_request_head(&stream)? else {
+        return write_response(&stream, "400 Bad Request", "Malformed request");
+    };
+    if !request
+        .host
+        .as_deref()
+        .is_some_and(|host| is_own_host(host, port))
+    {
+        warn!("API request for host {:?} rejected", request.host);
+        return write_response(&stream, "421 Misdirected Request", "Unknown host");
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/events") => {
+            if authorize(&stream, &request, Capability::Events)? {
+                stream_feedback(stream, stop)?;
+            }
+            Ok(())
+        }
+        ("GET", "/spectate") => {
+            if authorize(&stream, &request, Capability::Spectate)? {
+                stream_spectator(stream, stop)?;
+            }
+            Ok(())
+        }
+        ("GET", "/now-playing") => {
+            if authorize(&stream, &request, Capability::Spectate)? {
+                let json =
+                    serde_json::to_string(&spectator::snapshot()).map_err(std::io::Error::other)?;
+                write_json_response(&stream, &json)?;
+            }
+            Ok(())
+        }
+        ("GET", _) => write_response(&stream, "404 Not Found", "Not found"),
+        _ => write_response(&stream, "405 Method Not Allowed", "Method not allowed"),
+    }
+}
+
+/// Check the request's token, answering with 401/403 if it is rejected
+fn authorize(
+    stream: &TcpStream,
+    request: &Request,
+    capability: Capability,
+) -> std::io::Result<bool> {
+    let name = format!("{} {}", request.method, request.path);
+    match api_security::authorize(request.token.as_deref(), capability, &name) {
+        Ok(()) => Ok(true),
+        Err(denial) => {
+            write_response(stream, denial.status(), "Access denied")?;
+            Ok(false)
+        }
+    }
+}
+
+/// Read the request head (request line and headers)
+fn read_request_head(stream: &TcpStream) -> std::io::Result<Option<Request>> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut token = None;
+    let mut host = None;
+    let mut head_len = request_line.len();
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line)?;
+        head_len += read;
+        if read == 0 || line == "\r\n" || line == "\n" || head_len > MAX_REQUEST_HEAD {
+            break;
+        }
+        if let Some(bearer) = bearer_token(&line) {
+            token = Some(bearer);
+        }
+        if let Some(value) = header_value(&line, "host") {
+            host = Some(value.to_string());
+        }
+    }
+
+    Ok(
+        parse_request_line(&request_line).map(|(method, path)| Request {
+            token: token.or_else(|| {
+                QUERY_TOKEN_PATHS
+                    .contains(&path.as_str())
+                    .then(|| query_token(&request_line))
+                    .flatten()
+            }),
+            method,
+            path,
+            host,
+        }),
+    )
+}
+
+/// Parse `GET /path?query HTTP/1.1` into method and path
+fn parse_request_line(line: &str) -> Option<(String, String)> {
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    if !parts.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    let path = target.split('?').next().unwrap_or(target);
+    Some((method.to_string(), path.to_string()))
+}
+
+/// `token` query parameter of the request line
+fn query_token(request_line: &str) -> Option<String> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
+/// Value of a header line if it is the header `name` (case-insensitive)
+fn header_value<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    let (header_name, value) = header.split_once(':')?;
+    header_name
+        .trim()
+        .eq_ignore_ascii_case(name)
+        .then(|| value.trim())
+}
+
+/// Whether `host` names this server (`127.0.0.1:<port>` or `localhost:<port>`)
+///
+/// Anything else means the request was sent for another site, e.g. a page
+/// whose domain was rebound to 127.0.0.1.
+fn is_own_host(host: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    (name == "127.0.0.1" || name.eq_ignore_ascii_case("localhost")) && host_port.parse() == Ok(port)
+}
+
+/// Token of an `Authorization: Bearer <token>` header line
+fn bearer_token(header: &str) -> Option<String> {
+    let value = header_value(header, "authorization")?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then(|| token.to_string())
+}
+
+fn write_response(mut stream: &TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn write_json_response(mut stream: &TcpStream, json: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        json.len(),
+        json
+    )
+}
+
+/// Stream trigger feedback as server-sent events until the client leaves
+fn stream_feedback(mut stream: TcpStream, stop: &AtomicBool) -> std::io::Result<()> {
+    let events = feedback::subscribe();
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+          Connection: keep-alive\r\n\r\n",
+    )?;
+
+    while !stop.load(Ordering::Relaxed) {
+        match events.recv_timeout(KEEPALIVE_INTERVAL) {
+            Ok(json) => stream.write_all(sse_frame("trigger-feedback", &json).as_bytes())?,
+            Err(RecvTimeoutError::Timeout) => stream.write_all(b": keep-alive\n\n")?,
+            Err(RecvTimeoutError::Disconnected) => {
+                warn!("Feedback channel closed");
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Stream the spectator view and levels as server-sent events
+///
+/// Starts with the current view, then sends `now-playing` on every change
+/// and `levels` while the spectrum analyzer runs.
+fn stream_spectator(mut stream: TcpStream, stop: &AtomicBool) -> std::io::Result<()> {
+    let events = spectator::subscribe();
+    let view = serde_json::to_string(&spectator::snapshot()).map_err(std::io::Error::other)?;
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+          Connection: keep-alive\r\n\r\n",
+    )?;
+    stream.write_all(sse_frame("now-playing", &view).as_bytes())?;
+
+    while !stop.load(Ordering::Relaxed) {
+        match events.recv_timeout(KEEPALIVE_INTERVAL) {
+            Ok((event, json)) => stream.write_all(sse_frame(event, &json).as_bytes())?,
+            Err(RecvTimeoutError::Timeout) => stream.write_all(b": keep-alive\n\n")?,
+            Err(RecvTimeoutError::Disconnected) => {
+                warn!("Spectator channel closed");
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Format one server-sent event
+fn sse_frame(event: &str, data: &str) -> String {
+    format!("event: {}\ndata: {}\n\n", event, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_line() {
+        assert_eq!(
+            parse_request_line("GET /events?x=1 HTTP/1.1\r\n"),
+            Some(("GET".to_string(), "/events".to_string()))
+        );
+        assert_eq!(parse_request_line("GET /events\r\n"), None);
+        assert_eq!(parse_request_line(""), None);
+    }
+
+    #[test]
+    fn test_request_tokens() {
+        assert_eq!(
+            query_token("GET /events?x=1&token=sdk_abc HTTP/1.1\r\n").as_deref(),
+            Some("sdk_abc")
+        );
+        assert_eq!(query_token("GET /events?token= HTTP/1.1\r\n"), None);
+        assert_eq!(query_token("GET /events HTTP/1.1\r\n"), None);
+
+        assert_eq!(
+            bearer_token("authorization: Bearer sdk_abc\r\n").as_deref(),
+            Some("sdk_abc")
+        );
+        assert_eq!(bearer_token("Authorization: Basic dXNlcg==\r\n"), None);
+        assert_eq!(bearer_token("Host: 127.0.0.1\r\n"), None);
+    }
+
+    #[test]
+    fn test_is_own_host() {
+        assert!(is_own_host("127.0.0.1:8787", 8787));
+        assert!(is_own_host("localhost:8787", 8787));
+        assert!(is_own_host("LocalHost:8787", 8787));
+
+        assert!(!is_own_host("127.0.0.1:8788", 8787));
+        assert!(!is_own_host("127.0.0.1", 8787));
+        assert!(!is_own_host("evil.example:8787", 8787));
+        assert!(!is_own_host("127.0.0.1.evil.example:8787", 8787));
+        assert_eq!(
+            header_value("host: localhost:8787\r\n", "host"),
+            Some("localhost:8787")
+        );
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let connections = ConnectionLimit::default();
+        let first = connections.try_acquire(2).unwrap();
+        let _second = connections.try_acquire(2).unwrap();
+        assert!(connections.try_acquire(2).is_none());
+
+        drop(first);
+        assert!(connections.try_acquire(2).is_some());
+    }
+
+    #[test]
+    fn test_sse_frame() {
+        assert_eq!(
+            sse_frame("trigger-feedback", "{\"a\":1}"),
+            "event: trigger-feedback\ndata: {\"a\":1}\n\n"
+        );
+    }
+}
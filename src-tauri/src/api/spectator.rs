@@ -0,0 +1,219 @@
+//! Read-only spectator view
+//!
+//! Tracks what is playing and what played recently, and fans snapshots and
+//! output levels out to spectator subscribers (moderator dashboards, "now
+//! playing" overlays). Spectators only watch: the `spectate` capability
+//! grants nothing that plays, stops or edits anything.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use super::feedback::{FeedbackEvent, TriggerFeedback};
+use crate::AppState;
+
+/// Recent plays kept for spectators
+const MAX_RECENT_PLAYS: usize = 20;
+
+/// Current view (shared by all spectators)
+static VIEW: Mutex<SpectatorView> = Mutex::new(SpectatorView::new());
+
+/// Subscribers receiving (event name, JSON payload) pairs
+static SUBSCRIBERS: Mutex<Vec<Sender<(&'static str, String)>>> = Mutex::new(Vec::new());
+
+/// A sound that started playing
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpectatedPlay {
+    pub playback_id: String,
+    pub sound_id: String,
+    /// None if the sound was deleted meanwhile
+    pub sound_name: Option<String>,
+    /// Unix time in milliseconds
+    pub started_ms: u64,
+}
+
+/// What spectators see (payload of `now-playing`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpectatorView {
+    /// Sounds playing right now, oldest first
+    pub now_playing: Vec<SpectatedPlay>,
+    /// Last started sounds, newest first (including the playing ones)
+    pub recent: VecDeque<SpectatedPlay>,
+}
+
+/// Monitor output levels (payload of `levels`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpectatorLevels {
+    /// Loudest band (0.0 = silent, 1.0 = full scale)
+    pub level: f32,
+    /// Spectrum analyzer bands from low to high frequency
+    pub bands: Vec<f32>,
+}
+
+impl SpectatorView {
+    const fn new() -> Self {
+        Self {
+            now_playing: Vec::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Apply a feedback event; true if the view changed
+    fn apply(&mut self, event: &FeedbackEvent, sound_name: Option<String>) -> bool {
+        match &event.feedback {
+            TriggerFeedback::SoundStarted {
+                sound_id,
+                playback_id,
+            } => {
+                let play = SpectatedPlay {
+                    playback_id: playback_id.clone(),
+                    sound_id: sound_id.clone(),
+                    sound_name,
+                    started_ms: event.timestamp_ms,
+                };
+                self.now_playing.push(play.clone());
+                self.recent.push_front(play);
+                self.recent.truncate(MAX_RECENT_PLAYS);
+                true
+            }
+            TriggerFeedback::SoundStopped { playback_id, .. } => {
+                let before = self.now_playing.len();
+                self.now_playing.retain(|p| &p.playback_id != playback_id);
+                self.now_playing.len() != before
+            }
+            TriggerFeedback::HotkeyFired { .. } => false,
+        }
+    }
+}
+
+/// Current spectator view
+pub fn snapshot() -> SpectatorView {
+    VIEW.lock()
+        .map(|view| view.clone())
+        .unwrap_or_else(|_| SpectatorView::new())
+}
+
+/// Receive view changes and levels from now on
+pub fn subscribe() -> Receiver<(&'static str, String)> {
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.push(tx);
+    }
+    rx
+}
+
+/// Update the view from a feedback event and notify spectators
+pub(super) fn record(app_handle: &tauri::AppHandle, event: &FeedbackEvent) {
+    let sound_name = match &event.feedback {
+        TriggerFeedback::SoundStarted { sound_id, .. } => app_handle
+            .state::<AppState>()
+            .read_sounds()
+            .sounds
+            .iter()
+            .find(|s| s.id.as_str() == sound_id)
+            .map(|s| s.name.clone()),
+        _ => None,
+    };
+
+    let view = {
+        let Ok(mut view) = VIEW.lock() else {
+            return;
+        };
+        if !view.apply(event, sound_name) {
+            return;
+        }
+        view.clone()
+    };
+    broadcast("now-playing", &view);
+}
+
+/// Pass spectrum analyzer bands on to spectators
+pub fn publish_levels(bands: &[f32]) {
+    broadcast(
+        "levels",
+        &SpectatorLevels {
+            level: bands.iter().cloned().fold(0.0, f32::max),
+            bands: bands.to_vec(),
+        },
+    );
+}
+
+/// Send a payload to all subscribers, dropping disconnected ones
+fn broadcast(event: &'static str, payload: &impl Serialize) {
+    let Ok(json) = serde_json::to_string(payload) else {
+        return;
+    };
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.retain(|tx| tx.send((event, json.clone())).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(feedback: TriggerFeedback, timestamp_ms: u64) -> FeedbackEvent {
+        FeedbackEvent {
+            feedback,
+            timestamp_ms,
+        }
+    }
+
+    fn started(playback: &str, sound: &str, timestamp_ms: u64) -> FeedbackEvent {
+        event(
+            TriggerFeedback::SoundStarted {
+                sound_id: sound.to_string(),
+                playback_id: playback.to_string(),
+            },
+            timestamp_ms,
+        )
+    }
+
+    #[test]
+    fn test_view_tracks_now_playing_and_recent() {
+        let mut view = SpectatorView::new();
+        assert!(view.apply(&started("playback_1", "s1", 10), Some("Horn".to_string())));
+        assert!(view.apply(&started("playback_2", "s2", 20), None));
+        assert_eq!(view.now_playing.len(), 2);
+        assert_eq!(view.now_playing[0].sound_name.as_deref(), Some("Horn"));
+
+        let stopped = event(
+            TriggerFeedback::SoundStopped {
+                sound_id: "s1".to_string(),
+                playback_id: "playback_1".to_string(),
+            },
+            30,
+        );
+        assert!(view.apply(&stopped, None));
+        assert!(!view.apply(&stopped, None));
+        assert_eq!(view.now_playing.len(), 1);
+        assert_eq!(view.now_playing[0].playback_id, "playback_2");
+
+        // Newest first, stopped plays stay in the history
+        let recent: Vec<&str> = view.recent.iter().map(|p| p.sound_id.as_str()).collect();
+        assert_eq!(recent, ["s2", "s1"]);
+        assert_eq!(view.recent[1].started_ms, 10);
+
+        let hotkey = event(
+            TriggerFeedback::HotkeyFired {
+                hotkey: "Ctrl+F1".to_string(),
+                sound_id: None,
+                action: None,
+            },
+            40,
+        );
+        assert!(!view.apply(&hotkey, None));
+    }
+
+    #[test]
+    fn test_recent_plays_are_capped() {
+        let mut view = SpectatorView::new();
+        for i in 0..MAX_RECENT_PLAYS + 5 {
+            view.apply(&started(&format!("playback_{}", i), "s1", i as u64), None);
+        }
+        assert_eq!(view.recent.len(), MAX_RECENT_PLAYS);
+        assert_eq!(view.recent[0].started_ms, (MAX_RECENT_PLAYS + 4) as u64);
+    }
+}
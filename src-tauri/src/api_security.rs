@@ -0,0 +1,324 @@
+//! Access tokens and capabilities for the local API
+//!
+//! External clients authenticate with a token, sent as
+//! `Authorization: Bearer <token>`. The event stream endpoints (`/events`,
+//! `/spectate`) also take `?token=<token>`, since `EventSource` can't set
+//! headers. Each token grants a set of capabilities, so e.g. a Stream Deck
+//! profile can play sounds without being able to edit the library. While no
+//! token exists the API rejects every request, unless open local access is
+//! turned on (`AppSettings::api_open_local_access`).
+//!
+//! Every authorized or denied request is kept in an in-memory audit log and
+//! written to the `api_audit` log target.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::stats::now_ms;
+
+/// Prefix of generated token secrets (makes leaked tokens easy to recognize)
+const TOKEN_PREFIX: &str = "sdk_";
+
+/// Audit entries kept in memory
+const MAX_AUDIT_ENTRIES: usize = 500;
+
+/// Tokens the API server accepts (mirrors `AppSettings::api_tokens`)
+static TOKENS: RwLock<Vec<ApiToken>> = RwLock::new(Vec::new());
+
+/// Whether requests without a token are allowed while no token exists
+/// (mirrors `AppSettings::api_open_local_access`)
+static OPEN_LOCAL_ACCESS: AtomicBool = AtomicBool::new(false);
+
+/// Most recent requests, oldest first
+static AUDIT_LOG: Mutex<VecDeque<AuditEntry>> = Mutex::new(VecDeque::new());
+
+/// What a token may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Subscribe to trigger feedback (`GET /events`)
+    Events,
+    /// Watch now-playing, recent plays and levels (`GET /spectate`,
+    /// `GET /now-playing`), without any control
+    Spectate,
+    /// Play sounds
+    Play,
+    /// Stop playback
+    Stop,
+    /// Add, edit or delete sounds and categories
+    EditLibrary,
+    /// Change settings
+    EditSettings,
+}
+
+/// Access token for an external client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// Stable identifier (shown in the audit log instead of the secret)
+    pub id: String,
+    /// Label, e.g. "Stream Deck"
+    pub name: String,
+    /// Secret the client presents
+    pub secret: String,
+    /// Capabilities granted to the token
+    pub capabilities: Vec<Capability>,
+    /// Unix time in milliseconds
+    pub created_at_ms: u64,
+}
+
+impl ApiToken {
+    /// Create a token with a fresh random secret
+    pub fn generate(name: String, capabilities: Vec<Capability>) -> Self {
+        Self {
+            id: format!("token_{:016x}", random_u64()),
+            name,
+            secret: format!(
+                "{}{:016x}{:016x}{:016x}{:016x}",
+                TOKEN_PREFIX,
+                random_u64(),
+                random_u64(),
+                random_u64(),
+                random_u64()
+            ),
+            capabilities,
+            created_at_ms: now_ms(),
+        }
+    }
+
+    pub fn allows(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+}
+
+/// Why a request was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Denial {
+    /// The request carried no token (and the API isn't open)
+    MissingToken,
+    /// The presented token is unknown (or was revoked)
+    InvalidToken,
+    /// The token doesn't grant the capability
+    MissingCapability,
+}
+
+impl Denial {
+    /// HTTP status line for the rejection
+    pub fn status(self) -> &'static str {
+        match self {
+            Denial::MissingToken | Denial::InvalidToken => "401 Unauthorized",
+            Denial::MissingCapability => "403 Forbidden",
+        }
+    }
+}
+
+/// One API request in the audit log
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    /// Unix time in milliseconds
+    pub timestamp_ms: u64,
+    /// Token used (None while the API is open or if the token was unknown)
+    pub token_id: Option<String>,
+    pub token_name: Option<String>,
+    /// Request, e.g. "GET /events"
+    pub request: String,
+    pub capability: Capability,
+    pub allowed: bool,
+    pub denial: Option<Denial>,
+}
+
+/// Replace the tokens the server accepts
+pub fn set_tokens(tokens: Vec<ApiToken>) {
+    if let Ok(mut current) = TOKENS.write() {
+        *current = tokens;
+    }
+}
+
+/// Allow or reject requests without a token while no token exists
+pub fn set_open_local_access(open: bool) {
+    OPEN_LOCAL_ACCESS.store(open, Ordering::Relaxed);
+}
+
+/// Check a request needing `capability` and record it in the audit log
+pub fn authorize(
+    presented: Option<&str>,
+    capability: Capability,
+    request: &str,
+) -> Result<(), Denial> {
+    let tokens = TOKENS.read().unwrap_or_else(|e| e.into_inner());
+    let open_local_access = OPEN_LOCAL_ACCESS.load(Ordering::Relaxed);
+    let result = check(&tokens, open_local_access, presented, capability);
+
+    let (token_id, token_name) = match result {
+        Ok(Some(token)) | Err((Some(token), _)) => {
+            (Some(token.id.clone()), Some(token.name.clone()))
+        }
+        _ => (None, None),
+    };
+    let denial = result.err().map(|(_, denial)| denial);
+
+    if let Some(denial) = denial {
+        warn!(
+            target: "api_audit",
+            "Denied {} for token {:?}: {:?}", request, token_name, denial
+        );
+    } else {
+        info!(target: "api_audit", "Allowed {} for token {:?}", request, token_name);
+    }
+
+    record(AuditEntry {
+        timestamp_ms: now_ms(),
+        token_id,
+        token_name,
+        request: request.to_string(),
+        capability,
+        allowed: denial.is_none(),
+        denial,
+    });
+
+    denial.map_or(Ok(()), Err)
+}
+
+/// Recent audit entries, newest first (optionally only those of one token)
+pub fn audit_log(token_id: Option<&str>) -> Vec<AuditEntry> {
+    let Ok(log) = AUDIT_LOG.lock() else {
+        return Vec::new();
+    };
+    log.iter()
+        .rev()
+        .filter(|entry| token_id.is_none() || entry.token_id.as_deref() == token_id)
+        .cloned()
+        .collect()
+}
+
+/// Find the token granting `capability`
+///
+/// Without configured tokens every request is rejected, or allowed
+/// (`Ok(None)`) with `open_local_access`.
+fn check<'a>(
+    tokens: &'a [ApiToken],
+    open_local_access: bool,
+    presented: Option<&str>,
+    capability: Capability,
+) -> Result<Option<&'a ApiToken>, (Option<&'a ApiToken>, Denial)> {
+    if tokens.is_empty() && open_local_access {
+        return Ok(None);
+    }
+    let Some(presented) = presented else {
+        return Err((None, Denial::MissingToken));
+    };
+    let Some(token) = tokens
+        .iter()
+        .find(|t| constant_time_eq(t.secret.as_bytes(), presented.as_bytes()))
+    else {
+        return Err((None, Denial::InvalidToken));
+    };
+    if token.allows(capability) {
+        Ok(Some(token))
+    } else {
+        Err((Some(token), Denial::MissingCapability))
+    }
+}
+
+fn record(entry: AuditEntry) {
+    if let Ok(mut log) = AUDIT_LOG.lock() {
+        if log.len() == MAX_AUDIT_ENTRIES {
+            log.pop_front();
+        }
+        log.push_back(entry);
+    }
+}
+
+/// Compare secrets without exiting early on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Random 64 bits from the OS random source
+///
+/// Panics if the OS can't provide randomness; a guessable secret would be
+/// worse than failing to create the token.
+fn random_u64() -> u64 {
+    getrandom::u64().expect("OS random source unavailable")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_only() -> ApiToken {
+        ApiToken::generate("Stream Deck".to_string(), vec![Capability::Play])
+    }
+
+    #[test]
+    fn test_generated_tokens_are_unique() {
+        let a = play_only();
+        let b = play_only();
+        assert!(a.secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(a.secret.len(), TOKEN_PREFIX.len() + 64);
+        assert_ne!(a.secret, b.secret);
+        assert_ne!(a.id, b.id);
+    }
+
+    #[test]
+    fn test_check_without_tokens_is_closed() {
+        assert_eq!(
+            check(&[], false, None, Capability::Events),
+            Err((None, Denial::MissingToken))
+        );
+        assert_eq!(
+            check(&[], false, Some("sdk_any"), Capability::Events),
+            Err((None, Denial::InvalidToken))
+        );
+    }
+
+    #[test]
+    fn test_check_open_local_access() {
+        assert_eq!(check(&[], true, None, Capability::EditLibrary), Ok(None));
+
+        // Creating a token closes the API again
+        let tokens = [play_only()];
+        assert_eq!(
+            check(&tokens, true, None, Capability::Play),
+            Err((None, Denial::MissingToken))
+        );
+    }
+
+    #[test]
+    fn test_check_capabilities() {
+        let token = play_only();
+        let tokens = [token.clone()];
+
+        assert_eq!(
+            check(&tokens, false, Some(&token.secret), Capability::Play),
+            Ok(Some(&token))
+        );
+        assert_eq!(
+            check(&tokens, false, Some(&token.secret), Capability::EditLibrary),
+            Err((Some(&token), Denial::MissingCapability))
+        );
+        assert_eq!(
+            check(&tokens, false, None, Capability::Play),
+            Err((None, Denial::MissingToken))
+        );
+        assert_eq!(
+            check(&tokens, false, Some("sdk_wrong"), Capability::Play),
+            Err((None, Denial::InvalidToken))
+        );
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
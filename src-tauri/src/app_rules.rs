@@ -0,0 +1,388 @@
+//! Per-application rules
+//!
+//! While rules are enabled, the process owning the foreground window is
+//! checked whenever Windows reports a foreground change (a WinEvent hook, no
+//! polling) and whenever the rules change. The first rule naming it switches
+//! to the rule's profile and/or category, so hotkeys that would clash with
+//! another app are only registered while that app is focused. Once no rule
+//! matches any more, the profile that was active before the first switch comes
+//! back. Focusing SonicDeck itself changes nothing, so rules can be edited
+//! while they apply.
+//!
+//! Reading the foreground window needs the Windows API; on other platforms
+//! rules never match.
+
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tracing::{error, info, warn};
+
+use crate::profiles;
+use crate::sounds::CategoryId;
+use crate::AppState;
+
+/// Wakes the watcher thread (None until it runs)
+static WAKE: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+/// What to switch to while an application is in the foreground
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppRule {
+    /// Executable name, e.g. `obs64.exe` (case-insensitive, `.exe` optional)
+    pub process_name: String,
+    /// Profile to switch to, `None` keeps the current one
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Category to show, from the library of the profile switched to
+    #[serde(default)]
+    pub category_id: Option<CategoryId>,
+}
+
+/// Payload of `app-rule-changed`
+#[derive(Debug, Clone, Serialize)]
+pub struct AppRuleChanged {
+    /// Executable in the foreground
+    pub process: String,
+    /// Rule now applied, `None` when the last one stopped matching
+    pub rule: Option<AppRule>,
+}
+
+/// Switch to make after the foreground application changed
+#[derive(Debug, Clone, PartialEq)]
+struct Transition {
+    /// Profile to switch to, if it isn't the active one
+    profile: Option<String>,
+    /// Rule now applied
+    rule: Option<AppRule>,
+}
+
+/// Executable name without directory and `.exe`, lowercased
+fn normalize_process_name(name: &str) -> String {
+    let name = Path::new(name.trim())
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_string(),
+        None => name,
+    }
+}
+
+/// First rule naming `process`
+pub fn find_rule<'a>(rules: &'a [AppRule], process: &str) -> Option<&'a AppRule> {
+    let process = normalize_process_name(process);
+    if process.is_empty() {
+        return None;
+    }
+    rules
+        .iter()
+        .find(|rule| normalize_process_name(&rule.process_name) == process)
+}
+
+/// Applied rule and the profile to return to
+#[derive(Debug, Default)]
+struct Tracker {
+    current: Option<AppRule>,
+    /// Profile active before the first rule switched away from it
+    fallback: Option<String>,
+}
+
+impl Tracker {
+    /// Decide what to switch to now that `matched` applies to the foreground
+    /// application; `None` if nothing changes
+    fn update(&mut self, matched: Option<&AppRule>, active_profile: &str) -> Option<Transition> {
+        if matched == self.current.as_ref() {
+            return None;
+        }
+        let transition = match matched {
+            Some(rule) => {
+                if self.fallback.is_none() {
+                    self.fallback = Some(active_profile.to_string());
+                }
+                Transition {
+                    profile: rule.profile.clone(),
+                    rule: Some(rule.clone()),
+                }
+            }
+            None => Transition {
+                profile: self.fallback.take(),
+                rule: None,
+            },
+        };
+        self.current = matched.cloned();
+        Some(Transition {
+            profile: transition.profile.filter(|p| p != active_profile),
+            ..transition
+        })
+    }
+}
+
+/// Spawn the threads applying the rules in the settings
+///
+/// Does nothing on platforms without a foreground window API.
+pub fn spawn_watcher(app: tauri::AppHandle) {
+    if !cfg!(windows) {
+        return;
+    }
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut wake) = WAKE.lock() {
+        *wake = Some(tx);
+    }
+    let hook = std::thread::Builder::new()
+        .name("app-rules-foreground".to_string())
+        .spawn(foreground::watch);
+    if let Err(e) = hook {
+        error!("Failed to start foreground watcher thread: {}", e);
+        return;
+    }
+    let spawned = std::thread::Builder::new()
+        .name("app-rules".to_string())
+        .spawn(move || {
+            let own_pid = std::process::id();
+            let mut tracker = Tracker::default();
+            let mut process = String::new();
+            while rx.recv().is_ok() {
+                let state = app.state::<AppState>();
+                let active = {
+                    let settings = state.read_settings();
+                    settings.app_rules_enabled && !settings.app_rules.is_empty()
+                };
+                // Without rules nothing can match, so the foreground window
+                // isn't even looked at; a rule still applied is undone
+                let matched = if active {
+                    let Some((pid, foreground)) = foreground_process() else {
+                        continue;
+                    };
+                    if pid == own_pid {
+                        continue;
+                    }
+                    process = foreground;
+                    find_rule(&state.read_settings().app_rules, &process).cloned()
+                } else {
+                    None
+                };
+                if let Some(transition) = tracker.update(matched.as_ref(), &profiles::active()) {
+                    apply(&app, &process, transition);
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start app rules thread: {}", e);
+    }
+}
+
+/// Re-check the foreground application against changed rules
+pub fn rules_changed() {
+    wake();
+}
+
+fn wake() {
+    if let Ok(wake) = WAKE.lock() {
+        if let Some(tx) = wake.as_ref() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// Switch profiles and tell the frontend which category to show
+fn apply(app: &tauri::AppHandle, process: &str, transition: Transition) {
+    if let Some(profile) = &transition.profile {
+        info!("Switching to profile '{}' for {}", profile, process);
+        if let Err(e) = profiles::switch(app, profile) {
+            warn!("App rule for {} can't switch profiles: {}", process, e);
+        }
+    }
+
+    // Only point the frontend at categories the loaded library has
+    let mut rule = transition.rule;
+    if let Some(rule) = rule.as_mut() {
+        if let Some(category_id) = &rule.category_id {
+            let state = app.state::<AppState>();
+            let library = state.read_sounds();
+            if !library.categories.iter().any(|c| &c.id == category_id) {
+                warn!(
+                    "App rule for {} names a category the library doesn't have",
+                    process
+                );
+                rule.category_id = None;
+            }
+        }
+    }
+
+    let payload = AppRuleChanged {
+        process: process.to_string(),
+        rule,
+    };
+    if let Err(e) = app.emit("app-rule-changed", &payload) {
+        error!("Failed to emit app-rule-changed: {}", e);
+    }
+}
+
+/// Process ID and executable name of the foreground window's process
+#[cfg(windows)]
+fn foreground_process() -> Option<(u32, String)> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    // SAFETY: the handle is closed before returning and the buffer outlives
+    // the call writing into it
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.is_invalid() {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, Some(&mut pid));
+        if pid == 0 {
+            return None;
+        }
+
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        let name = Path::new(&path).file_name()?.to_str()?.to_string();
+        Some((pid, name))
+    }
+}
+
+#[cfg(not(windows))]
+fn foreground_process() -> Option<(u32, String)> {
+    None
+}
+
+#[cfg(windows)]
+mod foreground {
+    use tracing::error;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Accessibility::{SetWinEventHook, UnhookWinEvent, HWINEVENTHOOK};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DispatchMessageW, GetMessageW, TranslateMessage, EVENT_SYSTEM_FOREGROUND, MSG,
+        WINEVENT_OUTOFCONTEXT,
+    };
+
+    unsafe extern "system" fn on_foreground(
+        _hook: HWINEVENTHOOK,
+        _event: u32,
+        _window: HWND,
+        _object: i32,
+        _child: i32,
+        _thread: u32,
+        _time: u32,
+    ) {
+        super::wake();
+    }
+
+    /// Wake the watcher on every foreground window change; runs the hook's
+    /// message loop for as long as the app runs
+    pub fn watch() {
+        // SAFETY: the out-of-context hook delivers its callbacks through this
+        // thread's message loop, and is removed on the thread that set it;
+        // `msg` outlives every call reading or writing it
+        unsafe {
+            let hook = SetWinEventHook(
+                EVENT_SYSTEM_FOREGROUND,
+                EVENT_SYSTEM_FOREGROUND,
+                None,
+                Some(on_foreground),
+                0,
+                0,
+                WINEVENT_OUTOFCONTEXT,
+            );
+            if hook.is_invalid() {
+                error!("Failed to hook foreground window changes");
+                return;
+            }
+
+            let mut msg = MSG::default();
+            // GetMessageW returns -1 on failure and 0 on WM_QUIT
+            while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+            let _ = UnhookWinEvent(hook);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod foreground {
+    /// Foreground changes are Windows-only
+    pub fn watch() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(process_name: &str, profile: Option<&str>) -> AppRule {
+        AppRule {
+            process_name: process_name.to_string(),
+            profile: profile.map(str::to_string),
+            category_id: None,
+        }
+    }
+
+    #[test]
+    fn test_find_rule_ignores_case_and_extension() {
+        let rules = vec![rule("obs64.exe", Some("Streaming")), rule("Game", None)];
+
+        assert_eq!(find_rule(&rules, "OBS64.EXE"), Some(&rules[0]));
+        assert_eq!(find_rule(&rules, "obs64"), Some(&rules[0]));
+        assert_eq!(find_rule(&rules, "game.exe"), Some(&rules[1]));
+        assert_eq!(find_rule(&rules, "discord.exe"), None);
+        assert_eq!(find_rule(&rules, ""), None);
+    }
+
+    #[test]
+    fn test_tracker_returns_to_previous_profile() {
+        let obs = rule("obs64.exe", Some("Streaming"));
+        let mut tracker = Tracker::default();
+
+        // Nothing applied, nothing matches
+        assert_eq!(tracker.update(None, "default"), None);
+
+        let switch = tracker.update(Some(&obs), "default").unwrap();
+        assert_eq!(switch.profile.as_deref(), Some("Streaming"));
+        // Same rule again: no switch
+        assert_eq!(tracker.update(Some(&obs), "Streaming"), None);
+
+        let back = tracker.update(None, "Streaming").unwrap();
+        assert_eq!(back.profile.as_deref(), Some("default"));
+        assert_eq!(back.rule, None);
+        assert_eq!(tracker.update(None, "default"), None);
+    }
+
+    #[test]
+    fn test_tracker_keeps_first_fallback_across_rules() {
+        let obs = rule("obs64.exe", Some("Streaming"));
+        let game = rule("game.exe", None);
+        let mut tracker = Tracker::default();
+
+        tracker.update(Some(&obs), "default");
+        // A rule without a profile stays on the active one
+        let switch = tracker.update(Some(&game), "Streaming").unwrap();
+        assert_eq!(switch.profile, None);
+        assert_eq!(switch.rule, Some(game));
+
+        let back = tracker.update(None, "Streaming").unwrap();
+        assert_eq!(back.profile.as_deref(), Some("default"));
+    }
+}
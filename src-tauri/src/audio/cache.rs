@@ -1,16 +1,22 @@
 //! Audio caching system
 //!
 //! LRU memory cache for decoded audio data to avoid redundant decoding.
+//!
+//! A second level holds sounds already converted to an output device's
+//! sample rate, keyed by (file, rate), so repeated playback on the same
+//! devices needs no per-sample interpolation. Converted buffers share the
+//! memory budget and are evicted before decoded ones (they can be rebuilt
+//! from them).
 
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Instant, SystemTime};
 
 use lru::LruCache;
 use tracing::debug;
 
-use super::decode::decode_audio_file;
+use super::decode::{decode_audio_file_cancellable, CancelToken};
 use super::{AudioData, AudioError};
 
 /// Estimated bytes per sample (f32 = 4 bytes)
@@ -39,6 +45,8 @@ pub struct AudioCache {
     max_bytes: usize,
     /// Track file modification times for invalidation
     file_times: HashMap<String, SystemTime>,
+    /// Decoded audio converted to an output rate, keyed by (file, rate)
+    resampled: LruCache<(String, u32), Arc<AudioData>>,
 }
 
 impl AudioCache {
@@ -57,6 +65,7 @@ impl AudioCache {
                 DEFAULT_MAX_CACHE_BYTES
             },
             file_times: HashMap::new(),
+            resampled: LruCache::unbounded(),
         }
     }
 
@@ -82,6 +91,20 @@ impl AudioCache {
     /// Evict entries until we have enough space for new entry
     fn make_space(&mut self, needed_bytes: usize) {
         while self.current_bytes + needed_bytes > self.max_bytes {
+            // Converted buffers go first, they're only a shortcut
+            if let Some((key, audio_data)) = self.resampled.pop_lru() {
+                self.current_bytes = self
+                    .current_bytes
+                    .saturating_sub(Self::estimate_size(&audio_data));
+                debug!(
+                    cache = "eviction",
+                    evicted_path = %key.0,
+                    sample_rate = key.1,
+                    "Resampled cache eviction (LRU)"
+                );
+                continue;
+            }
+
             // LRU pop removes least recently used entry
             if let Some((path, entry)) = self.cache.pop_lru() {
                 self.current_bytes = self.current_bytes.saturating_sub(entry.size_bytes);
@@ -102,34 +125,22 @@ impl AudioCache {
     }
 
     /// Get cached audio or decode and cache
-    pub fn get_or_decode(&mut self, file_path: &str) -> Result<Arc<AudioData>, AudioError> {
-        let start = Instant::now();
+    ///
+    /// The lock is only held for the lookup and the insert, so a long decode
+    /// doesn't block playback or other decodes.
+    pub fn load(cache: &Mutex<Self>, file_path: &str) -> Result<Arc<AudioData>, AudioError> {
+        Self::load_cancellable(cache, file_path, &CancelToken::new())
+    }
 
-        // Check if we have a valid cached version
-        if let Some(entry) = self.cache.get(file_path) {
-            if Self::is_cache_valid(entry, file_path) {
-                // Cache hit - return the cached data
-                let duration_us = start.elapsed().as_micros();
-                debug!(
-                    cache = "hit",
-                    file_path = %file_path,
-                    duration_us = duration_us,
-                    "Audio cache hit"
-                );
-                return Ok(entry.audio_data.clone());
-            } else {
-                // Cache invalid - remove it (will be replaced below)
-                if let Some(removed) = self.cache.pop(file_path) {
-                    self.current_bytes = self.current_bytes.saturating_sub(removed.size_bytes);
-                    self.file_times.remove(file_path);
-                    debug!(
-                        cache = "invalidated",
-                        file_path = %file_path,
-                        freed_bytes = removed.size_bytes,
-                        "Cache entry invalidated (file changed)"
-                    );
-                }
-            }
+    /// Like `load`, but a miss can be aborted through `cancel` (nothing is
+    /// cached then)
+    pub fn load_cancellable(
+        cache: &Mutex<Self>,
+        file_path: &str,
+        cancel: &CancelToken,
+    ) -> Result<Arc<AudioData>, AudioError> {
+        if let Some(audio_data) = cache.lock().unwrap().get(file_path) {
+            return Ok(audio_data);
         }
 
         // Cache miss - decode the file
@@ -138,8 +149,64 @@ impl AudioCache {
             file_path = %file_path,
             "Cache miss, decoding audio"
         );
-        let audio_data = decode_audio_file(file_path)?;
-        let audio_data = Arc::new(audio_data);
+        let start = Instant::now();
+        let audio_data = Arc::new(decode_audio_file_cancellable(file_path, cancel)?);
+        let audio_data = cache.lock().unwrap().insert(file_path, audio_data);
+
+        let duration_ms = start.elapsed().as_millis();
+        debug!(
+            cache = "stored",
+            file_path = %file_path,
+            duration_ms = duration_ms,
+            "Audio decoded and cached"
+        );
+
+        Ok(audio_data)
+    }
+
+    /// Cached audio of `file_path`, if still valid
+    ///
+    /// An entry whose file changed since it was cached is dropped.
+    pub fn get(&mut self, file_path: &str) -> Option<Arc<AudioData>> {
+        let start = Instant::now();
+
+        // Check if we have a valid cached version
+        let entry = self.cache.get(file_path)?;
+        if Self::is_cache_valid(entry, file_path) {
+            // Cache hit - return the cached data
+            let duration_us = start.elapsed().as_micros();
+            debug!(
+                cache = "hit",
+                file_path = %file_path,
+                duration_us = duration_us,
+                "Audio cache hit"
+            );
+            return Some(entry.audio_data.clone());
+        }
+
+        // Cache invalid - remove it
+        self.remove_resampled(file_path);
+        if let Some(removed) = self.cache.pop(file_path) {
+            self.current_bytes = self.current_bytes.saturating_sub(removed.size_bytes);
+            self.file_times.remove(file_path);
+            debug!(
+                cache = "invalidated",
+                file_path = %file_path,
+                freed_bytes = removed.size_bytes,
+                "Cache entry invalidated (file changed)"
+            );
+        }
+        None
+    }
+
+    /// Store decoded audio of `file_path`
+    ///
+    /// If another decode of the same file got there first, its entry is kept
+    /// and returned instead, so both callers share one buffer.
+    pub fn insert(&mut self, file_path: &str, audio_data: Arc<AudioData>) -> Arc<AudioData> {
+        if let Some(existing) = self.get(file_path) {
+            return existing;
+        }
 
         // Calculate size and make space if needed
         let size_bytes = Self::estimate_size(&audio_data);
@@ -163,21 +230,80 @@ impl AudioCache {
             self.file_times.insert(file_path.to_string(), time);
         }
 
-        let duration_ms = start.elapsed().as_millis();
+        audio_data
+    }
+
+    /// Whether `file_path` is cached (without counting as a use)
+    pub fn contains(&self, file_path: &str) -> bool {
+        self.cache.contains(file_path)
+    }
+
+    /// Cached audio of `file_path` converted to `sample_rate`
+    ///
+    /// Only served while the decoded entry is still valid.
+    pub fn get_resampled(&mut self, file_path: &str, sample_rate: u32) -> Option<Arc<AudioData>> {
+        let valid = self
+            .cache
+            .peek(file_path)
+            .is_some_and(|entry| Self::is_cache_valid(entry, file_path));
+        if !valid {
+            self.remove_resampled(file_path);
+            return None;
+        }
+
+        let hit = self
+            .resampled
+            .get(&(file_path.to_string(), sample_rate))
+            .cloned();
         debug!(
-            cache = "stored",
+            cache = if hit.is_some() { "hit" } else { "miss" },
             file_path = %file_path,
-            size_bytes = size_bytes,
-            duration_ms = duration_ms,
-            "Audio decoded and cached"
+            sample_rate = sample_rate,
+            "Resampled cache lookup"
         );
+        hit
+    }
 
-        Ok(audio_data)
+    /// Store audio of `file_path` converted to its `sample_rate`
+    ///
+    /// Ignored if the file isn't cached (decoded) anymore.
+    pub fn insert_resampled(&mut self, file_path: &str, audio_data: Arc<AudioData>) {
+        if !self.cache.contains(file_path) {
+            return;
+        }
+        let key = (file_path.to_string(), audio_data.sample_rate);
+        let size_bytes = Self::estimate_size(&audio_data);
+        if let Some(replaced) = self.resampled.pop(&key) {
+            self.current_bytes = self
+                .current_bytes
+                .saturating_sub(Self::estimate_size(&replaced));
+        }
+        self.make_space(size_bytes);
+        self.resampled.put(key, audio_data);
+        self.current_bytes += size_bytes;
+    }
+
+    /// Drop the converted buffers of a file
+    fn remove_resampled(&mut self, file_path: &str) {
+        let keys: Vec<_> = self
+            .resampled
+            .iter()
+            .filter(|(key, _)| key.0 == file_path)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            if let Some(audio_data) = self.resampled.pop(&key) {
+                self.current_bytes = self
+                    .current_bytes
+                    .saturating_sub(Self::estimate_size(&audio_data));
+            }
+        }
     }
 
     /// Clear the entire cache
     pub fn clear(&mut self) {
         self.cache.clear();
+        self.resampled.clear();
         self.file_times.clear();
         self.current_bytes = 0;
     }
@@ -186,6 +312,7 @@ impl AudioCache {
     pub fn stats(&self) -> CacheStats {
         CacheStats {
             entries: self.cache.len(),
+            resampled_entries: self.resampled.len(),
             memory_bytes: self.current_bytes,
             max_memory_bytes: self.max_bytes,
             memory_mb: self.current_bytes / (1024 * 1024),
@@ -195,6 +322,7 @@ impl AudioCache {
 
     /// Invalidate a specific file from cache
     pub fn invalidate(&mut self, file_path: &str) {
+        self.remove_resampled(file_path);
         if let Some(entry) = self.cache.pop(file_path) {
             self.current_bytes = self.current_bytes.saturating_sub(entry.size_bytes);
             self.file_times.remove(file_path);
@@ -294,6 +422,59 @@ mod tests {
         assert_eq!(cache.cache.len(), 0);
     }
 
+    #[test]
+    fn test_resampled_entries_follow_decoded_entry() {
+        let mut cache = AudioCache::new(100);
+        let entry = CacheEntry {
+            audio_data: Arc::new(create_test_audio(1000)),
+            file_modified: None,
+            size_bytes: 4000,
+        };
+        cache.cache.put("test.mp3".to_string(), entry);
+        cache.current_bytes = 4000;
+
+        let converted = Arc::new(AudioData {
+            samples: vec![0.0; 1100],
+            sample_rate: 44100,
+            channels: 2,
+        });
+        cache.insert_resampled("test.mp3", converted);
+        // Files that aren't cached don't get conversions
+        cache.insert_resampled("other.mp3", Arc::new(create_test_audio(10)));
+        assert_eq!(cache.stats().resampled_entries, 1);
+        assert_eq!(cache.current_bytes, 4000 + 1100 * BYTES_PER_SAMPLE);
+
+        cache.invalidate("test.mp3");
+        assert_eq!(cache.stats().resampled_entries, 0);
+        assert_eq!(cache.current_bytes, 0);
+    }
+
+    #[test]
+    fn test_make_space_evicts_resampled_first() {
+        let mut cache = AudioCache::new(1); // 1 MB max
+        let entry = CacheEntry {
+            audio_data: Arc::new(create_test_audio(500 * 1024 / BYTES_PER_SAMPLE)),
+            file_modified: None,
+            size_bytes: 500 * 1024,
+        };
+        cache.cache.put("first.mp3".to_string(), entry);
+        cache.current_bytes = 500 * 1024;
+        cache.insert_resampled(
+            "first.mp3",
+            Arc::new(AudioData {
+                samples: vec![0.0; 400 * 1024 / BYTES_PER_SAMPLE],
+                sample_rate: 44100,
+                channels: 2,
+            }),
+        );
+
+        cache.make_space(200 * 1024);
+
+        assert_eq!(cache.stats().resampled_entries, 0);
+        assert!(cache.cache.get("first.mp3").is_some());
+        assert_eq!(cache.current_bytes, 500 * 1024);
+    }
+
     #[test]
     fn test_make_space_eviction() {
         let mut cache = AudioCache::new(1); // 1 MB max
@@ -325,6 +506,23 @@ mod tests {
         assert!(cache.current_bytes <= one_mb);
         assert!(cache.cache.get("first.mp3").is_none());
     }
+
+    #[test]
+    fn test_insert_keeps_entry_of_earlier_decode() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.mp3");
+        std::fs::write(&path, b"audio").unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut cache = AudioCache::new(100);
+        let first = cache.insert(path, Arc::new(create_test_audio(1000)));
+        // A second decode finishing later shares the first buffer
+        let second = cache.insert(path, Arc::new(create_test_audio(1000)));
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(&cache.get(path).unwrap(), &first));
+        assert_eq!(cache.current_bytes, 1000 * BYTES_PER_SAMPLE);
+    }
 }
 
 /// Cache statistics for monitoring
@@ -332,6 +530,8 @@ mod tests {
 pub struct CacheStats {
     /// Number of cached entries
     pub entries: usize,
+    /// Number of cached conversions to an output rate
+    pub resampled_entries: usize,
     /// Current memory usage in bytes
     pub memory_bytes: usize,
     /// Maximum memory limit in bytes
@@ -1,90 +1,113 @@
 //! Audio decoding using Symphonia
 //!
-//! Supports MP3, WAV, OGG Vorbis, and MP4/M4A formats.
+//! Supports MP3, WAV, FLAC, OGG Vorbis/Opus, and MP4/M4A formats.
 
 use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
-use symphonia::core::audio::SampleBuffer;
-use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::audio::{Channels, SampleBuffer, SignalSpec};
+use symphonia::core::codecs::{self, CodecParameters, CodecRegistry, CodecType, DecoderOptions};
 use symphonia::core::errors::Error as SymphoniaError;
-use symphonia::core::formats::FormatOptions;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
 use symphonia::core::io::MediaSourceStream;
-use symphonia::core::meta::MetadataOptions;
+use symphonia::core::meta::{MetadataOptions, MetadataRevision, StandardTagKey};
 use symphonia::core::probe::Hint;
 use tracing::{debug, warn};
 
+use super::downmix::downmix_to_stereo;
+use super::faults::{take_fault, Fault};
+use super::opus::OpusDecoder;
 use super::{AudioData, AudioError};
 
-/// Decode an audio file to raw PCM samples
-pub fn decode_audio_file(file_path: &str) -> Result<AudioData, AudioError> {
-    let start = Instant::now();
-    debug!(file_path = %file_path, "Starting audio decode");
+/// Symphonia's codecs plus Opus
+fn codecs() -> &'static CodecRegistry {
+    static CODECS: OnceLock<CodecRegistry> = OnceLock::new();
+    CODECS.get_or_init(|| {
+        let mut registry = CodecRegistry::new();
+        symphonia::default::register_enabled_codecs(&mut registry);
+        registry.register_all::<OpusDecoder>();
+        registry
+    })
+}
+
+/// Shared flag for aborting a running decode
+///
+/// Clones share the flag, so the playback side keeps one clone and the
+/// decoder checks the other between packets.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
 
-    let file = File::open(file_path)?;
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    let media_source = MediaSourceStream::new(Box::new(file), Default::default());
+    /// Ask the decode to stop at the next packet
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
 
-    let mut hint = Hint::new();
-    if let Some(ext) = std::path::Path::new(file_path).extension() {
-        hint.with_extension(ext.to_str().unwrap_or(""));
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
     }
+}
 
-    let probed = symphonia::default::get_probe()
-        .format(
-            &hint,
-            media_source,
-            &FormatOptions::default(),
-            &MetadataOptions::default(),
-        )
-        .map_err(|e| AudioError::ProbeFormat(e.to_string()))?;
+/// Decode an audio file to raw PCM samples
+pub fn decode_audio_file(file_path: &str) -> Result<AudioData, AudioError> {
+    decode_audio_file_cancellable(file_path, &CancelToken::new())
+}
 
-    let mut format = probed.format;
-    let track = format.default_track().ok_or(AudioError::NoTracks)?;
+/// Decode an audio file, stopping early with `AudioError::Cancelled` once
+/// `cancel` is set
+pub fn decode_audio_file_cancellable(
+    file_path: &str,
+    cancel: &CancelToken,
+) -> Result<AudioData, AudioError> {
+    let start = Instant::now();
+    debug!(file_path = %file_path, "Starting audio decode");
+
+    if take_fault(Fault::DecodeFailure) {
+        return Err(AudioError::Decode("Injected failure".to_string()));
+    }
 
-    let track_id = track.id;
-    let mut decoder = symphonia::default::get_codecs()
-        .make(&track.codec_params, &DecoderOptions::default())
-        .map_err(|e| AudioError::DecoderCreation(e.to_string()))?;
+    let mut track = OpenTrack::open(file_path)?;
 
     let mut samples = Vec::new();
     let mut sample_rate = 48000;
     let mut channels = 2;
+    let mut layout = Channels::FRONT_LEFT | Channels::FRONT_RIGHT;
 
     loop {
-        let packet = match format.next_packet() {
-            Ok(packet) => packet,
-            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
-                break
-            }
-            Err(e) => return Err(AudioError::PacketRead(e.to_string())),
-        };
-
-        if packet.track_id() != track_id {
-            continue;
+        if cancel.is_cancelled() {
+            debug!(file_path = %file_path, "Audio decode cancelled");
+            return Err(AudioError::Cancelled);
         }
 
-        match decoder.decode(&packet) {
-            Ok(decoded) => {
-                let spec = *decoded.spec();
-                sample_rate = spec.rate;
-                channels = spec.channels.count() as u16;
-
-                let mut sample_buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
-                sample_buf.copy_interleaved_ref(decoded);
-                samples.extend_from_slice(sample_buf.samples());
-            }
-            Err(SymphoniaError::DecodeError(err)) => {
-                warn!("Decode error (continuing): {}", err);
-                continue;
-            }
-            Err(e) => return Err(AudioError::Decode(e.to_string())),
-        }
+        let Some(spec) = track.next_block(&mut samples)? else {
+            break;
+        };
+        sample_rate = spec.rate;
+        channels = spec.channels.count() as u16;
+        layout = spec.channels;
     }
 
     if samples.is_empty() {
         return Err(AudioError::NoData);
     }
 
+    // Playback maps channels by index, so surround layouts become stereo
+    if channels > 2 {
+        debug!(
+            file_path = %file_path,
+            layout = ?layout,
+            "Downmixing surround audio to stereo"
+        );
+        samples = downmix_to_stereo(&samples, layout);
+        channels = 2;
+    }
+
     let duration_ms = start.elapsed().as_millis();
     let duration_secs = samples.len() as f64 / (sample_rate as f64 * channels as f64);
     debug!(
@@ -104,11 +127,316 @@ pub fn decode_audio_file(file_path: &str) -> Result<AudioData, AudioError> {
     })
 }
 
+/// Information read from a file's headers, without decoding it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileMetadata {
+    /// None if the container doesn't state the length
+    pub duration_ms: Option<u64>,
+    /// Title tag (ID3, Vorbis comment, MP4 atom)
+    pub title: Option<String>,
+}
+
+/// Read duration and title of an audio file
+///
+/// Fails like decoding would for files that aren't playable (unknown
+/// format, unsupported codec, DRM).
+pub fn read_metadata(file_path: &str) -> Result<FileMetadata, AudioError> {
+    let track = OpenTrack::open(file_path)?;
+    let params = track.codec_params();
+    let duration_ms = params
+        .n_frames
+        .zip(params.sample_rate)
+        .map(|(frames, rate)| frames * 1000 / rate.max(1) as u64);
+    Ok(FileMetadata {
+        duration_ms,
+        title: track.title,
+    })
+}
+
+/// Title tag of a metadata revision, if it isn't blank
+fn title_tag(revision: &MetadataRevision) -> Option<String> {
+    revision
+        .tags()
+        .iter()
+        .find(|tag| tag.std_key == Some(StandardTagKey::TrackTitle))
+        .map(|tag| tag.value.to_string().trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// An opened file's audio track with its decoder
+pub(super) struct OpenTrack {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn codecs::Decoder>,
+    track_id: u32,
+    title: Option<String>,
+}
+
+impl OpenTrack {
+    /// Probe `file_path` and set up a decoder for its audio track
+    pub(super) fn open(file_path: &str) -> Result<Self, AudioError> {
+        let file = File::open(file_path)?;
+
+        let media_source = MediaSourceStream::new(Box::new(file), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(ext) = std::path::Path::new(file_path).extension() {
+            hint.with_extension(ext.to_str().unwrap_or(""));
+        }
+
+        let mut probed = symphonia::default::get_probe()
+            .format(
+                &hint,
+                media_source,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .map_err(|e| {
+                if is_drm_protected_mp4(file_path) {
+                    AudioError::DrmProtected
+                } else {
+                    AudioError::ProbeFormat(e.to_string())
+                }
+            })?;
+
+        // Tags may sit in front of the container (ID3v2) or inside it
+        let title = probed
+            .metadata
+            .get()
+            .and_then(|metadata| metadata.current().and_then(title_tag))
+            .or_else(|| probed.format.metadata().current().and_then(title_tag));
+
+        let format = probed.format;
+
+        // Prefer the first track with a recognized codec over the container default
+        let track = format
+            .tracks()
+            .iter()
+            .find(|t| t.codec_params.codec != codecs::CODEC_TYPE_NULL)
+            .or_else(|| format.default_track())
+            .ok_or(AudioError::NoTracks)?;
+
+        let track_id = track.id;
+        let codec_type = track.codec_params.codec;
+        let decoder = codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| {
+                if codec_type == codecs::CODEC_TYPE_NULL && is_drm_protected_mp4(file_path) {
+                    AudioError::DrmProtected
+                } else if codecs().get_codec(codec_type).is_none() {
+                    AudioError::UnsupportedCodec {
+                        codec: codec_display_name(codec_type),
+                    }
+                } else {
+                    AudioError::DecoderCreation(e.to_string())
+                }
+            })?;
+
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+            title,
+        })
+    }
+
+    /// Codec parameters of the track (length and rate, if the container has them)
+    pub(super) fn codec_params(&self) -> &CodecParameters {
+        self.decoder.codec_params()
+    }
+
+    /// Move to source frame `frame`
+    ///
+    /// Uses the container's seek index when timestamps count frames, otherwise
+    /// decodes from the start. Returns the frames still to drop after
+    /// decoding on.
+    pub(super) fn seek_to_frame(&mut self, frame: u64) -> usize {
+        let params = self.codec_params();
+        let frame_based = matches!(
+            (params.time_base, params.sample_rate),
+            (Some(tb), Some(rate)) if tb.numer == 1 && tb.denom == rate
+        );
+        if frame == 0 || !frame_based {
+            return frame as usize;
+        }
+
+        let seeked = self.format.seek(
+            SeekMode::Accurate,
+            SeekTo::TimeStamp {
+                ts: frame,
+                track_id: self.track_id,
+            },
+        );
+        match seeked {
+            Ok(seeked) => {
+                self.decoder.reset();
+                seeked.required_ts.saturating_sub(seeked.actual_ts) as usize
+            }
+            Err(e) => {
+                debug!("Seek failed, decoding from the start: {}", e);
+                frame as usize
+            }
+        }
+    }
+
+    /// Decode the next packet, appending its interleaved samples to `samples`
+    ///
+    /// Returns the packet's signal spec, or None at the end of the track.
+    /// Corrupt packets are skipped.
+    pub(super) fn next_block(
+        &mut self,
+        samples: &mut Vec<f32>,
+    ) -> Result<Option<SignalSpec>, AudioError> {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(e))
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    return Ok(None)
+                }
+                Err(e) => return Err(AudioError::PacketRead(e.to_string())),
+            };
+
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    let spec = *decoded.spec();
+                    let mut sample_buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                    sample_buf.copy_interleaved_ref(decoded);
+                    samples.extend_from_slice(sample_buf.samples());
+                    return Ok(Some(spec));
+                }
+                Err(SymphoniaError::DecodeError(err)) => {
+                    warn!("Decode error (continuing): {}", err);
+                    continue;
+                }
+                Err(e) => return Err(AudioError::Decode(e.to_string())),
+            }
+        }
+    }
+}
+
+/// Sample entry / box types that only appear in protected MP4 files
+/// (FairPlay `drms`/`drmi`, CENC `enca`, protection scheme info `sinf`)
+const DRM_ATOM_TYPES: [&[u8; 4]; 4] = [b"drms", b"drmi", b"enca", b"sinf"];
+
+/// Upper bound on how much of the `moov` box is scanned for DRM markers
+const MAX_MOOV_SCAN_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Check whether an MP4/M4A file carries encrypted (DRM) sample entries
+///
+/// Walks the top-level boxes to locate `moov` and scans it for protection
+/// boxes. Returns false for anything that isn't an ISO base media file.
+fn is_drm_protected_mp4(file_path: &str) -> bool {
+    let Ok(mut file) = File::open(file_path) else {
+        return false;
+    };
+    let file_len = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+    let mut offset = 0u64;
+    let mut first_box = true;
+
+    while file_len.saturating_sub(offset) >= 8 {
+        let mut header = [0u8; 8];
+        if file.seek(SeekFrom::Start(offset)).is_err() || file.read_exact(&mut header).is_err() {
+            return false;
+        }
+
+        let box_type = &header[4..8];
+        if first_box && box_type != b"ftyp" {
+            return false;
+        }
+        first_box = false;
+
+        let mut header_len = 8u64;
+        let mut size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        if size == 1 {
+            // 64-bit "largesize" follows the type
+            let mut large = [0u8; 8];
+            if file.read_exact(&mut large).is_err() {
+                return false;
+            }
+            size = u64::from_be_bytes(large);
+            header_len = 16;
+        } else if size == 0 {
+            // Box extends to end of file
+            size = file_len - offset;
+        }
+
+        if size < header_len {
+            return false;
+        }
+
+        if box_type == b"moov" {
+            let body_len = (size - header_len).min(MAX_MOOV_SCAN_BYTES) as usize;
+            let mut body = vec![0u8; body_len];
+            if file.read_exact(&mut body).is_err() {
+                return false;
+            }
+            return contains_drm_atom(&body);
+        }
+
+        // A bogus largesize must end the scan rather than wrap around
+        let Some(next) = offset.checked_add(size) else {
+            return false;
+        };
+        offset = next;
+    }
+
+    false
+}
+
+/// Search a box payload for a plausibly-sized DRM box header
+fn contains_drm_atom(data: &[u8]) -> bool {
+    data.windows(8).enumerate().any(|(i, header)| {
+        let is_drm_type = DRM_ATOM_TYPES.iter().any(|t| &header[4..8] == *t);
+        let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        is_drm_type && size >= 8 && i + size <= data.len()
+    })
+}
+
+/// Human-readable name for codecs Symphonia can identify but not decode
+fn codec_display_name(codec: CodecType) -> String {
+    let name = match codec {
+        codecs::CODEC_TYPE_NULL => "unknown",
+        codecs::CODEC_TYPE_ALAC => "ALAC",
+        codecs::CODEC_TYPE_SPEEX => "Speex",
+        codecs::CODEC_TYPE_WMA => "WMA",
+        codecs::CODEC_TYPE_EAC3 => "E-AC-3",
+        codecs::CODEC_TYPE_AC4 => "AC-4",
+        codecs::CODEC_TYPE_DCA => "DTS",
+        codecs::CODEC_TYPE_MUSEPACK => "Musepack",
+        codecs::CODEC_TYPE_WAVPACK => "WavPack",
+        codecs::CODEC_TYPE_MONKEYS_AUDIO => "Monkey's Audio",
+        codecs::CODEC_TYPE_TTA => "TTA",
+        codecs::CODEC_TYPE_ATRAC1
+        | codecs::CODEC_TYPE_ATRAC3
+        | codecs::CODEC_TYPE_ATRAC3PLUS
+        | codecs::CODEC_TYPE_ATRAC9 => "ATRAC",
+        codecs::CODEC_TYPE_MP1 => "MPEG-1 Layer I",
+        codecs::CODEC_TYPE_MP2 => "MPEG-1 Layer II",
+        other => return format!("codec {}", other),
+    };
+    name.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
     use std::path::PathBuf;
 
+    /// Build an MP4 box (size + type + payload)
+    fn mp4_box(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(box_type);
+        out.extend_from_slice(payload);
+        out
+    }
+
     fn get_fixture_path(filename: &str) -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
@@ -137,6 +465,107 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ========== Cancellation tests ==========
+
+    #[test]
+    fn test_decode_cancelled() {
+        let path = get_fixture_path("test_mono.mp3");
+        let cancel = CancelToken::new();
+        cancel.cancel();
+
+        let err = decode_audio_file_cancellable(path.to_str().unwrap(), &cancel).unwrap_err();
+        assert!(
+            matches!(err, AudioError::Cancelled),
+            "Expected Cancelled, got {:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_cancel_token_shared_between_clones() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    // ========== DRM / unsupported codec detection tests ==========
+
+    #[test]
+    fn test_contains_drm_atom() {
+        let enca = mp4_box(b"enca", &[0u8; 28]);
+        let stsd = mp4_box(b"stsd", &enca);
+        assert!(contains_drm_atom(&stsd));
+
+        let mp4a = mp4_box(b"mp4a", &[0u8; 28]);
+        assert!(!contains_drm_atom(&mp4_box(b"stsd", &mp4a)));
+    }
+
+    #[test]
+    fn test_contains_drm_atom_ignores_implausible_size() {
+        // "drms" appearing inside text data without a valid box size
+        let mut data = vec![0xFF, 0xFF, 0xFF, 0xFF];
+        data.extend_from_slice(b"drms");
+        assert!(!contains_drm_atom(&data));
+    }
+
+    #[test]
+    fn test_decode_drm_protected_m4a() {
+        let sinf = mp4_box(b"sinf", &mp4_box(b"frma", b"mp4a"));
+        let drms = mp4_box(b"drms", &[vec![0u8; 28], sinf].concat());
+        let moov = mp4_box(b"moov", &mp4_box(b"trak", &mp4_box(b"stsd", &drms)));
+        let ftyp = mp4_box(b"ftyp", b"M4P \0\0\0\0M4P mp42isom");
+
+        let mut file = tempfile::Builder::new().suffix(".m4p").tempfile().unwrap();
+        file.write_all(&[ftyp, moov].concat()).unwrap();
+
+        let path = file.path().to_str().unwrap().to_string();
+        assert!(is_drm_protected_mp4(&path));
+
+        let err = decode_audio_file(&path).unwrap_err();
+        assert!(
+            matches!(err, AudioError::DrmProtected),
+            "Expected DrmProtected, got {:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_huge_largesize_box_is_not_drm_protected() {
+        let ftyp = mp4_box(b"ftyp", b"M4A \0\0\0\0M4A mp42isom");
+        // size 1: a 64-bit largesize follows, here one that overflows the offset
+        let mut free = 1u32.to_be_bytes().to_vec();
+        free.extend_from_slice(b"free");
+        free.extend_from_slice(&u64::MAX.to_be_bytes());
+        let moov = mp4_box(b"moov", &mp4_box(b"drms", &[0u8; 28]));
+
+        let mut file = tempfile::Builder::new().suffix(".m4a").tempfile().unwrap();
+        file.write_all(&[ftyp, free, moov].concat()).unwrap();
+
+        assert!(!is_drm_protected_mp4(file.path().to_str().unwrap()));
+    }
+
+    #[test]
+    fn test_regular_m4a_is_not_drm_protected() {
+        let path = get_fixture_path("test_stereo.m4a");
+        assert!(!is_drm_protected_mp4(path.to_str().unwrap()));
+    }
+
+    #[test]
+    fn test_non_mp4_is_not_drm_protected() {
+        let path = get_fixture_path("test_mono.mp3");
+        assert!(!is_drm_protected_mp4(path.to_str().unwrap()));
+    }
+
+    #[test]
+    fn test_codec_display_name() {
+        assert_eq!(codec_display_name(codecs::CODEC_TYPE_ALAC), "ALAC");
+        assert_eq!(codec_display_name(codecs::CODEC_TYPE_NULL), "unknown");
+        assert!(codec_display_name(codecs::decl_codec_type(b"xyz")).starts_with("codec 0x"));
+    }
+
     // ========== MP3 format tests ==========
 
     #[test]
@@ -233,6 +662,41 @@ mod tests {
         );
     }
 
+    // ========== FLAC format tests ==========
+
+    #[test]
+    fn test_decode_flac_fixture() {
+        let path = get_fixture_path("test_stereo.flac");
+        let result = decode_audio_file(path.to_str().unwrap());
+
+        assert!(result.is_ok(), "Failed to decode FLAC: {:?}", result.err());
+
+        let audio = result.unwrap();
+        assert_eq!(audio.sample_rate, 44100, "FLAC should be 44.1kHz");
+        assert_eq!(audio.channels, 2, "FLAC should be stereo");
+        // Lossless: exactly 1 second, both channels carry the same sine
+        assert_eq!(audio.samples.len(), 88200);
+        assert!(audio.samples.chunks_exact(2).all(|f| f[0] == f[1]));
+        let peak = audio.samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!((peak - 0.5).abs() < 0.01, "Unexpected peak {}", peak);
+    }
+
+    // ========== OGG/Opus format tests ==========
+
+    #[test]
+    fn test_decode_opus_fixture() {
+        let path = get_fixture_path("test_stereo.opus");
+        let result = decode_audio_file(path.to_str().unwrap());
+
+        assert!(result.is_ok(), "Failed to decode Opus: {:?}", result.err());
+
+        let audio = result.unwrap();
+        assert_eq!(audio.sample_rate, 48000, "Opus should be 48kHz");
+        assert_eq!(audio.channels, 2, "Opus should be stereo");
+        // 1 second (50 frames of 20 ms) minus the 312 frame pre-skip
+        assert_eq!(audio.samples.len(), (48000 - 312) * 2);
+    }
+
     // ========== Sample data validation tests ==========
 
     #[test]
@@ -262,6 +726,8 @@ mod tests {
             ("test_mono.mp3", 44100, 1),
             ("test_stereo.ogg", 48000, 2),
             ("test_stereo.m4a", 48000, 2),
+            ("test_stereo.flac", 44100, 2),
+            ("test_stereo.opus", 48000, 2),
         ];
 
         for (filename, expected_rate, expected_channels) in formats {
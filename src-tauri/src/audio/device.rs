@@ -1,11 +1,37 @@
 //! Audio device enumeration
+//!
+//! Output devices get stable IDs (host, endpoint ID and name, see
+//! `DeviceId`), resolved to the host's current device list whenever a stream
+//! is opened. A saved ID whose endpoint is gone (e.g. after a driver
+//! reinstall) resolves to a device of the same name. Older IDs are positions
+//! in the device list, which shift when a device appears or disappears before
+//! another one; the last enumerated list is kept to map those to the same
+//! device names after a rescan.
+//!
+//! The Windows endpoint list behind the stable IDs is cached, so resolving a
+//! device when a sound plays doesn't enumerate COM endpoints. It is refreshed
+//! with every enumeration (`enumerate_devices`, which the device watcher and
+//! `rescan_devices` run).
+
+use std::sync::Mutex;
 
 use cpal::traits::{DeviceTrait, HostTrait};
 
 use super::{AudioDevice, AudioError, DeviceId};
 
+/// Devices returned by the last enumeration
+static KNOWN_DEVICES: Mutex<Vec<AudioDevice>> = Mutex::new(Vec::new());
+
+/// Render endpoints (name, endpoint ID) as of the last enumeration, None
+/// before the first one
+static ENDPOINTS: Mutex<Option<Vec<(String, String)>>> = Mutex::new(None);
+
 /// Lists all available output audio devices on the system
+///
+/// The list is remembered for `reconcile_device_id`, and the endpoint IDs
+/// behind it for `output_device_ids`.
 pub fn enumerate_devices() -> Result<Vec<AudioDevice>, AudioError> {
+    refresh_endpoints();
     let host = cpal::default_host();
     let mut devices = Vec::new();
 
@@ -15,13 +41,14 @@ pub fn enumerate_devices() -> Result<Vec<AudioDevice>, AudioError> {
         .and_then(|d| d.name().ok())
         .unwrap_or_default();
 
-    let output_devices = host
+    let output_devices: Vec<_> = host
         .output_devices()
-        .map_err(|e| AudioError::DeviceEnumeration(e.to_string()))?;
+        .map_err(|e| AudioError::DeviceEnumeration(e.to_string()))?
+        .collect();
+    let ids = output_device_ids(&output_devices);
 
-    for (index, device) in output_devices.enumerate() {
+    for (device, device_id) in output_devices.iter().zip(ids) {
         if let Ok(name) = device.name() {
-            let device_id = DeviceId::from_index(index);
             let is_default = name == default_name;
 
             devices.push(AudioDevice {
@@ -36,5 +63,372 @@ pub fn enumerate_devices() -> Result<Vec<AudioDevice>, AudioError> {
         return Err(AudioError::NoDevices);
     }
 
+    if let Ok(mut known) = KNOWN_DEVICES.lock() {
+        *known = devices.clone();
+    }
     Ok(devices)
 }
+
+/// Devices returned by the last enumeration (empty before the first one)
+pub fn known_devices() -> Vec<AudioDevice> {
+    KNOWN_DEVICES
+        .lock()
+        .map(|known| known.clone())
+        .unwrap_or_default()
+}
+
+/// New ID of the device `saved` referred to, if it moved
+///
+/// A position ID follows its device's name from `previous` to `current`, a
+/// stable ID whose endpoint is gone moves to a device of the same name.
+/// Returns None if the device kept its ID, is gone, or was never seen.
+pub fn reconcile_device_id(
+    saved: &DeviceId,
+    previous: &[AudioDevice],
+    current: &[AudioDevice],
+) -> Option<DeviceId> {
+    let name = match saved.name() {
+        Some(name) => name,
+        None => previous.iter().find(|d| &d.id == saved)?.name.as_str(),
+    };
+    if current.iter().any(|d| &d.id == saved && d.name == name) {
+        return None;
+    }
+    current
+        .iter()
+        .find(|d| d.name == name)
+        .map(|d| d.id.clone())
+}
+
+/// Stable IDs of the host's output devices, in the same order
+pub fn output_device_ids(devices: &[cpal::Device]) -> Vec<DeviceId> {
+    let names: Vec<String> = devices
+        .iter()
+        .map(|device| device.name().unwrap_or_default())
+        .collect();
+    stable_ids(&host_name(), &names, &cached_endpoints())
+}
+
+/// Re-read the render endpoints behind the stable IDs
+fn refresh_endpoints() {
+    let endpoints = endpoint_ids();
+    if let Ok(mut cached) = ENDPOINTS.lock() {
+        *cached = Some(endpoints);
+    }
+}
+
+/// Render endpoints from the last enumeration, read now if there was none
+fn cached_endpoints() -> Vec<(String, String)> {
+    let Ok(mut cached) = ENDPOINTS.lock() else {
+        return endpoint_ids();
+    };
+    cached.get_or_insert_with(endpoint_ids).clone()
+}
+
+/// Stable IDs for devices named `names`
+///
+/// Each device takes the next unused endpoint (name, endpoint ID) of its
+/// name; devices without one are keyed by their position among devices of
+/// the same name.
+fn stable_ids(host: &str, names: &[String], endpoints: &[(String, String)]) -> Vec<DeviceId> {
+    let mut used = vec![false; endpoints.len()];
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let endpoint = endpoints
+                .iter()
+                .enumerate()
+                .position(|(i, (endpoint_name, _))| !used[i] && endpoint_name == name);
+            let key = match endpoint {
+                Some(i) => {
+                    used[i] = true;
+                    endpoints[i].1.clone()
+                }
+                None => names[..index]
+                    .iter()
+                    .filter(|earlier| *earlier == name)
+                    .count()
+                    .to_string(),
+            };
+            DeviceId::stable(host, &key, name)
+        })
+        .collect()
+}
+
+/// Position of the device `saved` refers to in `current` (stable IDs of the
+/// host's devices, see `output_device_ids`)
+///
+/// Position IDs resolve by position, stable IDs by ID and then by name.
+pub fn resolve_device_index(saved: &DeviceId, current: &[DeviceId]) -> Option<usize> {
+    if let Ok(index) = saved.index() {
+        return (index < current.len()).then_some(index);
+    }
+    current.iter().position(|id| id == saved).or_else(|| {
+        let name = saved.name()?;
+        current.iter().position(|id| id.name() == Some(name))
+    })
+}
+
+/// The device `saved` stands for: the system default output (at
+/// `default_index` in `current`) for the system default pseudo-device,
+/// else `saved` itself
+pub fn follow_system_default(
+    saved: &DeviceId,
+    current: &[DeviceId],
+    default_index: Option<usize>,
+) -> DeviceId {
+    if !saved.is_system_default() {
+        return saved.clone();
+    }
+    default_index
+        .and_then(|index| current.get(index))
+        .cloned()
+        .unwrap_or_else(|| saved.clone())
+}
+
+/// Look up an output device by ID
+pub fn find_output_device(device_id: &DeviceId) -> Result<cpal::Device, AudioError> {
+    let host = cpal::default_host();
+    if device_id.is_system_default() {
+        return host
+            .default_output_device()
+            .ok_or_else(|| AudioError::DeviceNotFound(device_id.to_string()));
+    }
+    let mut devices: Vec<_> = host
+        .output_devices()
+        .map_err(|e| AudioError::DeviceEnumeration(e.to_string()))?
+        .collect();
+    let ids = output_device_ids(&devices);
+    let index = resolve_device_index(device_id, &ids)
+        .ok_or_else(|| AudioError::DeviceNotFound(device_id.to_string()))?;
+    Ok(devices.swap_remove(index))
+}
+
+/// Active render endpoints as (friendly name, endpoint ID), in the order
+/// Windows lists them
+#[cfg(windows)]
+fn endpoint_ids() -> Vec<(String, String)> {
+    use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+    use windows::Win32::Media::Audio::{
+        eRender, IMMDeviceEnumerator, MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+    };
+    use windows::Win32::System::Com::StructuredStorage::PropVariantToStringAlloc;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL,
+        COINIT_MULTITHREADED, STGM_READ,
+    };
+
+    /// COM error: already initialized with different threading mode
+    const RPC_E_CHANGED_MODE: i32 = 0x80010106u32 as i32;
+
+    // SAFETY: COM is initialized on this thread before any COM call and only
+    // uninitialized if this call initialized it; the interfaces are dropped
+    // (released) inside the closure, before that. The strings COM allocates
+    // are copied and then freed with CoTaskMemFree exactly once.
+    unsafe {
+        let hr = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let we_initialized_com = hr.is_ok();
+        if hr.is_err() && hr != windows::core::HRESULT(RPC_E_CHANGED_MODE) {
+            tracing::warn!("Failed to initialize COM for endpoint IDs: {:?}", hr);
+            return Vec::new();
+        }
+
+        let result = (|| -> windows::core::Result<Vec<(String, String)>> {
+            let enumerator: IMMDeviceEnumerator =
+                CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let collection = enumerator.EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)?;
+            let mut endpoints = Vec::new();
+            for i in 0..collection.GetCount()? {
+                let device = collection.Item(i)?;
+                let name_prop = device
+                    .OpenPropertyStore(STGM_READ)?
+                    .GetValue(&PKEY_Device_FriendlyName)?;
+                let name_ptr = PropVariantToStringAlloc(&name_prop)?;
+                let name = name_ptr.to_string().unwrap_or_default();
+                CoTaskMemFree(Some(name_ptr.0 as *const _));
+                let id_ptr = device.GetId()?;
+                let id = id_ptr.to_string().unwrap_or_default();
+                CoTaskMemFree(Some(id_ptr.0 as *const _));
+                endpoints.push((name, id));
+            }
+            Ok(endpoints)
+        })();
+
+        if we_initialized_com {
+            CoUninitialize();
+        }
+
+        result.unwrap_or_else(|e| {
+            tracing::warn!("Failed to list endpoint IDs: {}", e);
+            Vec::new()
+        })
+    }
+}
+
+/// Endpoint IDs are a Windows concept; elsewhere devices are keyed by name
+#[cfg(not(windows))]
+fn endpoint_ids() -> Vec<(String, String)> {
+    Vec::new()
+}
+
+/// Name of the audio host API in use (e.g. "WASAPI")
+pub fn host_name() -> String {
+    cpal::default_host().id().name().to_string()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(index: usize, name: &str) -> AudioDevice {
+        AudioDevice {
+            id: DeviceId::from_index(index),
+            name: name.to_string(),
+            is_default: false,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_follows_shifted_device() {
+        let previous = [device(0, "Speakers"), device(1, "CABLE Input")];
+        // A headset connected and was listed first
+        let current = [
+            device(0, "Headset"),
+            device(1, "Speakers"),
+            device(2, "CABLE Input"),
+        ];
+
+        assert_eq!(
+            reconcile_device_id(&DeviceId::from_index(1), &previous, &current),
+            Some(DeviceId::from_index(2))
+        );
+    }
+
+    #[test]
+    fn test_reconcile_keeps_unmoved_or_unknown_devices() {
+        let previous = [device(0, "Speakers"), device(1, "CABLE Input")];
+        let current = [device(0, "Speakers")];
+
+        // Unchanged
+        assert_eq!(
+            reconcile_device_id(&DeviceId::from_index(0), &previous, &current),
+            None
+        );
+        // Gone (the fallback handles it)
+        assert_eq!(
+            reconcile_device_id(&DeviceId::from_index(1), &previous, &current),
+            None
+        );
+        // Never enumerated
+        assert_eq!(
+            reconcile_device_id(&DeviceId::from_index(5), &previous, &current),
+            None
+        );
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_stable_ids_use_endpoints_then_name_position() {
+        let endpoints = [
+            ("Headset".to_string(), "{guid-a}".to_string()),
+            ("Speakers".to_string(), "{guid-b}".to_string()),
+            ("Headset".to_string(), "{guid-c}".to_string()),
+        ];
+        let ids = stable_ids(
+            "WASAPI",
+            &names(&["Speakers", "Headset", "Headset", "Monitor"]),
+            &endpoints,
+        );
+        assert_eq!(
+            ids,
+            [
+                DeviceId::stable("wasapi", "{guid-b}", "Speakers"),
+                DeviceId::stable("wasapi", "{guid-a}", "Headset"),
+                DeviceId::stable("wasapi", "{guid-c}", "Headset"),
+                DeviceId::stable("wasapi", "0", "Monitor"),
+            ]
+        );
+
+        // Without endpoint IDs, same-named devices are told apart by position
+        let ids = stable_ids("ALSA", &names(&["Headset", "Headset"]), &[]);
+        assert_eq!(ids[0].as_str(), "alsa:0:Headset");
+        assert_eq!(ids[1].as_str(), "alsa:1:Headset");
+    }
+
+    #[test]
+    fn test_resolve_stable_id_ignores_order() {
+        let speakers = DeviceId::stable("wasapi", "{guid-b}", "Speakers");
+        // A headset connected and was listed first
+        let current = [
+            DeviceId::stable("wasapi", "{guid-a}", "Headset"),
+            speakers.clone(),
+        ];
+        assert_eq!(resolve_device_index(&speakers, &current), Some(1));
+
+        // The endpoint changed (driver reinstall): matched by name
+        let reinstalled = [DeviceId::stable("wasapi", "{guid-z}", "Speakers")];
+        assert_eq!(resolve_device_index(&speakers, &reinstalled), Some(0));
+
+        let unplugged = [DeviceId::stable("wasapi", "{guid-a}", "Headset")];
+        assert_eq!(resolve_device_index(&speakers, &unplugged), None);
+    }
+
+    #[test]
+    fn test_resolve_index_id_by_position() {
+        let current = [
+            DeviceId::stable("wasapi", "{guid-a}", "Headset"),
+            DeviceId::stable("wasapi", "{guid-b}", "Speakers"),
+        ];
+        assert_eq!(
+            resolve_device_index(&DeviceId::from_index(1), &current),
+            Some(1)
+        );
+        assert_eq!(
+            resolve_device_index(&DeviceId::from_index(2), &current),
+            None
+        );
+    }
+
+    #[test]
+    fn test_follow_system_default() {
+        let current = [
+            DeviceId::stable("wasapi", "{guid-a}", "Headset"),
+            DeviceId::stable("wasapi", "{guid-b}", "Speakers"),
+        ];
+        let default = DeviceId::system_default();
+        assert_eq!(
+            follow_system_default(&default, &current, Some(1)),
+            current[1]
+        );
+        // No default output: left for resolving to report it missing
+        assert_eq!(follow_system_default(&default, &current, None), default);
+        // Other IDs stay pinned to their device
+        assert_eq!(
+            follow_system_default(&current[0], &current, Some(1)),
+            current[0]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_moves_stable_id_to_same_name() {
+        let saved = DeviceId::stable("wasapi", "{guid-b}", "Speakers");
+        let current = [AudioDevice {
+            id: DeviceId::stable("wasapi", "{guid-z}", "Speakers"),
+            name: "Speakers".to_string(),
+            is_default: false,
+        }];
+        assert_eq!(
+            reconcile_device_id(&saved, &[], &current),
+            Some(current[0].id.clone())
+        );
+        assert_eq!(reconcile_device_id(&current[0].id, &[], &current), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! Audio engine diagnostics
+//!
+//! Keeps a bounded history of recent stream errors (cpal error callbacks and
+//! failed stream builds) for the engine status report.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use crate::stats::now_ms;
+
+/// Number of stream errors kept in history
+const MAX_RECENT_STREAM_ERRORS: usize = 20;
+
+/// Global history of recent stream errors, newest last
+static RECENT_STREAM_ERRORS: Mutex<VecDeque<StreamErrorRecord>> = Mutex::new(VecDeque::new());
+
+/// A stream error reported by a device
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamErrorRecord {
+    /// Device name the error came from
+    pub device: String,
+    /// Error message
+    pub message: String,
+    /// When the error occurred (Unix time in milliseconds)
+    pub timestamp_ms: u64,
+}
+
+/// Record a stream error, evicting the oldest entry when full
+pub fn record_stream_error(device: &str, message: &str) {
+    crate::recap::record_error();
+    let timestamp_ms = now_ms();
+
+    let Ok(mut errors) = RECENT_STREAM_ERRORS.lock() else {
+        return;
+    };
+    push_bounded(
+        &mut errors,
+        StreamErrorRecord {
+            device: device.to_string(),
+            message: message.to_string(),
+            timestamp_ms,
+        },
+    );
+}
+
+/// Recent stream errors, oldest first
+pub fn recent_stream_errors() -> Vec<StreamErrorRecord> {
+    RECENT_STREAM_ERRORS
+        .lock()
+        .map(|errors| errors.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn push_bounded(errors: &mut VecDeque<StreamErrorRecord>, record: StreamErrorRecord) {
+    if errors.len() >= MAX_RECENT_STREAM_ERRORS {
+        errors.pop_front();
+    }
+    errors.push_back(record);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(message: &str) -> StreamErrorRecord {
+        StreamErrorRecord {
+            device: "Speakers".to_string(),
+            message: message.to_string(),
+            timestamp_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_push_bounded_evicts_oldest() {
+        let mut errors = VecDeque::new();
+        for i in 0..MAX_RECENT_STREAM_ERRORS + 5 {
+            push_bounded(&mut errors, record(&i.to_string()));
+        }
+
+        assert_eq!(errors.len(), MAX_RECENT_STREAM_ERRORS);
+        assert_eq!(errors.front().unwrap().message, "5");
+        assert_eq!(
+            errors.back().unwrap().message,
+            (MAX_RECENT_STREAM_ERRORS + 4).to_string()
+        );
+    }
+
+    #[test]
+    fn test_record_stream_error_is_listed() {
+        record_stream_error("Headphones", "device unplugged");
+
+        let errors = recent_stream_errors();
+        assert!(errors
+            .iter()
+            .any(|e| e.device == "Headphones" && e.message == "device unplugged"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Surround to stereo downmix
+//!
+//! Decoded buffers are interleaved in symphonia's channel order (the bit
+//! order of `Channels`), while playback maps source channels onto device
+//! channels by index. A 5.1 file would therefore put its centre channel on
+//! the third speaker (silent on stereo outputs) and its LFE on the fourth.
+//! Surround sources are folded down to stereo at decode time instead, using
+//! ITU-R BS.775 style coefficients.
+
+use symphonia::core::audio::Channels;
+
+/// Weight of centre and surround channels in the stereo mix (-3 dB)
+const SURROUND_GAIN: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Weight of the LFE channel in each stereo side (-10 dB)
+///
+/// The LFE carries a +10 dB playback gain in surround mastering, so mixing
+/// it in at -10 dB keeps the bass of effects like explosions without
+/// muddying the mix.
+const LFE_GAIN: f32 = 0.316;
+
+/// Left/right weights of a single speaker position
+fn stereo_weights(channel: Channels) -> (f32, f32) {
+    const LEFT: Channels = Channels::FRONT_LEFT_CENTRE
+        .union(Channels::REAR_LEFT)
+        .union(Channels::SIDE_LEFT)
+        .union(Channels::REAR_LEFT_CENTRE)
+        .union(Channels::FRONT_LEFT_WIDE)
+        .union(Channels::FRONT_LEFT_HIGH)
+        .union(Channels::TOP_FRONT_LEFT)
+        .union(Channels::TOP_REAR_LEFT);
+    const RIGHT: Channels = Channels::FRONT_RIGHT_CENTRE
+        .union(Channels::REAR_RIGHT)
+        .union(Channels::SIDE_RIGHT)
+        .union(Channels::REAR_RIGHT_CENTRE)
+        .union(Channels::FRONT_RIGHT_WIDE)
+        .union(Channels::FRONT_RIGHT_HIGH)
+        .union(Channels::TOP_FRONT_RIGHT)
+        .union(Channels::TOP_REAR_RIGHT);
+    const LFE: Channels = Channels::LFE1.union(Channels::LFE2);
+
+    if channel == Channels::FRONT_LEFT {
+        (1.0, 0.0)
+    } else if channel == Channels::FRONT_RIGHT {
+        (0.0, 1.0)
+    } else if LEFT.contains(channel) {
+        (SURROUND_GAIN, 0.0)
+    } else if RIGHT.contains(channel) {
+        (0.0, SURROUND_GAIN)
+    } else if LFE.contains(channel) {
+        (LFE_GAIN, LFE_GAIN)
+    } else {
+        // Centre positions (front, rear, top) and anything unnamed
+        (SURROUND_GAIN, SURROUND_GAIN)
+    }
+}
+
+/// Fold interleaved `samples` in `layout` down to interleaved stereo
+///
+/// The result is scaled down if the fold-down would clip, so loud surround
+/// mixes keep their balance instead of distorting.
+pub fn downmix_to_stereo(samples: &[f32], layout: Channels) -> Vec<f32> {
+    let weights: Vec<(f32, f32)> = layout.iter().map(stereo_weights).collect();
+    if weights.is_empty() {
+        return samples.to_vec();
+    }
+
+    let mut stereo = Vec::with_capacity(samples.len() / weights.len() * 2);
+    let mut peak = 0.0f32;
+    for frame in samples.chunks_exact(weights.len()) {
+        let (mut left, mut right) = (0.0f32, 0.0f32);
+        for (sample, (l, r)) in frame.iter().zip(&weights) {
+            left += sample * l;
+            right += sample * r;
+        }
+        peak = peak.max(left.abs()).max(right.abs());
+        stereo.push(left);
+        stereo.push(right);
+    }
+
+    if peak > 1.0 {
+        let scale = 1.0 / peak;
+        stereo.iter_mut().for_each(|s| *s *= scale);
+    }
+    stereo
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 5.1 in symphonia's order: FL, FR, FC, LFE, RL, RR
+    fn layout_5_1() -> Channels {
+        Channels::FRONT_LEFT
+            | Channels::FRONT_RIGHT
+            | Channels::FRONT_CENTRE
+            | Channels::LFE1
+            | Channels::REAR_LEFT
+            | Channels::REAR_RIGHT
+    }
+
+    fn frame(values: [f32; 6]) -> Vec<f32> {
+        downmix_to_stereo(&values, layout_5_1())
+    }
+
+    #[test]
+    fn test_front_channels_pass_through() {
+        assert_eq!(frame([0.5, -0.25, 0.0, 0.0, 0.0, 0.0]), vec![0.5, -0.25]);
+    }
+
+    #[test]
+    fn test_centre_goes_to_both_sides() {
+        let stereo = frame([0.0, 0.0, 0.5, 0.0, 0.0, 0.0]);
+        assert!((stereo[0] - 0.5 * SURROUND_GAIN).abs() < 1e-6);
+        assert_eq!(stereo[0], stereo[1]);
+    }
+
+    #[test]
+    fn test_surrounds_stay_on_their_side() {
+        let stereo = frame([0.0, 0.0, 0.0, 0.0, 0.5, 0.0]);
+        assert!(stereo[0] > 0.0);
+        assert_eq!(stereo[1], 0.0);
+    }
+
+    #[test]
+    fn test_lfe_is_attenuated() {
+        let stereo = frame([0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(stereo, vec![LFE_GAIN, LFE_GAIN]);
+    }
+
+    #[test]
+    fn test_loud_mix_is_scaled_instead_of_clipping() {
+        let stereo = downmix_to_stereo(
+            &[[1.0, 1.0, 1.0, 1.0, 1.0, 1.0], [0.5; 6]].concat(),
+            layout_5_1(),
+        );
+        assert_eq!(stereo.len(), 4);
+        assert!((stereo[0] - 1.0).abs() < 1e-6);
+        assert!((stereo[2] - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,191 @@
+//! Playback gain envelope
+//!
+//! Fades and A/B routing gains follow the playback position, so stream
+//! callbacks apply them at their cursor (once per buffer) instead of a thread
+//! stepping the volume. The envelope's clock is the sound's own time in
+//! source frames: it stands still while paused and follows what was actually
+//! played.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use super::routing::OutputRouting;
+use super::DeviceRole;
+
+/// Gain of a playback output over time, on top of its volume
+pub struct PlaybackEnvelope {
+    /// Source frames per millisecond
+    frames_per_ms: f64,
+    /// Frame the clock starts at
+    start_frame: f64,
+    /// Clock time at `start_frame` (ms); an extended playback's clock keeps
+    /// running across its repeats
+    start_ms: f64,
+    /// Fade-in from the clock's zero (ms, 0 = none)
+    fade_in_ms: f64,
+    /// Routing and the output whose gain is taken from it
+    routing: Option<(OutputRouting, DeviceRole)>,
+    /// Frame the fade-out starts at (`f64` bits, infinite until requested)
+    fade_out_start: AtomicU64,
+    /// Length of the fade-out in frames (`f64` bits)
+    fade_out_frames: AtomicU64,
+    /// Frames between progress signals (0 = none)
+    progress_frames: f64,
+}
+
+impl PlaybackEnvelope {
+    /// Envelope of a sound at `sample_rate`, its clock starting at `start_frame`
+    pub fn new(sample_rate: u32, start_frame: usize) -> Self {
+        Self {
+            frames_per_ms: sample_rate as f64 / 1000.0,
+            start_frame: start_frame as f64,
+            start_ms: 0.0,
+            fade_in_ms: 0.0,
+            routing: None,
+            fade_out_start: AtomicU64::new(f64::INFINITY.to_bits()),
+            fade_out_frames: AtomicU64::new(0.0f64.to_bits()),
+            progress_frames: 0.0,
+        }
+    }
+
+    /// Start the clock at `elapsed_ms` instead of zero
+    pub fn starting_at(mut self, elapsed_ms: u64) -> Self {
+        self.start_ms = elapsed_ms as f64;
+        self
+    }
+
+    /// Fade in over `duration` from the clock's zero
+    pub fn with_fade_in(mut self, duration: Duration) -> Self {
+        self.fade_in_ms = duration.as_secs_f64() * 1000.0;
+        self
+    }
+
+    /// Follow the gain `routing` gives `role`
+    pub fn with_routing(mut self, routing: OutputRouting, role: DeviceRole) -> Self {
+        self.routing = Some((routing, role));
+        self
+    }
+
+    /// Signal progress every `interval` of played sound
+    pub fn with_progress(mut self, interval: Duration) -> Self {
+        self.progress_frames = interval.as_secs_f64() * 1000.0 * self.frames_per_ms;
+        self
+    }
+
+    /// Clock time (ms) at `frame`
+    fn elapsed_ms(&self, frame: f64) -> f64 {
+        self.start_ms + (frame - self.start_frame).max(0.0) / self.frames_per_ms
+    }
+
+    /// Multiplier of the volume setting at `frame`
+    ///
+    /// Fades scale the setting, routing gains are linear (like
+    /// `volume_with_gain`).
+    pub(super) fn gain(&self, frame: f64) -> f32 {
+        let elapsed_ms = self.elapsed_ms(frame);
+        let fade_in = if self.fade_in_ms > 0.0 {
+            (elapsed_ms / self.fade_in_ms).min(1.0)
+        } else {
+            1.0
+        };
+        let fade_out = 1.0 - self.fade_out_progress(frame);
+        let routing = match self.routing {
+            Some((routing, role)) => {
+                let (monitor, broadcast) = routing.gains(elapsed_ms as u64);
+                let gain = match role {
+                    DeviceRole::Monitor => monitor,
+                    DeviceRole::Broadcast => broadcast,
+                };
+                gain * gain
+            }
+            None => 1.0,
+        };
+        (fade_in * fade_out) as f32 * routing
+    }
+
+    /// Fade out over `duration`, starting at `frame`
+    pub(super) fn fade_out(&self, frame: f64, duration: Duration) {
+        let frames = duration.as_secs_f64() * 1000.0 * self.frames_per_ms;
+        self.fade_out_frames
+            .store(frames.to_bits(), Ordering::Relaxed);
+        self.fade_out_start
+            .store(frame.to_bits(), Ordering::Release);
+    }
+
+    /// How far the fade-out got at `frame` (0.0 before it starts, 1.0 once
+    /// silent)
+    pub(super) fn fade_out_progress(&self, frame: f64) -> f64 {
+        let start = f64::from_bits(self.fade_out_start.load(Ordering::Acquire));
+        if frame <= start {
+            return 0.0;
+        }
+        let frames = f64::from_bits(self.fade_out_frames.load(Ordering::Relaxed));
+        if frames <= 0.0 {
+            1.0
+        } else {
+            ((frame - start) / frames).min(1.0)
+        }
+    }
+
+    /// Whether a progress signal is due after playing from `from` to `to`
+    pub(super) fn progress_due(&self, from: f64, to: f64) -> bool {
+        let interval = |frame: f64| ((frame - self.start_frame) / self.progress_frames).floor();
+        self.progress_frames > 0.0 && interval(to) > interval(from)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fade_in_follows_position() {
+        let envelope = PlaybackEnvelope::new(1000, 100).with_fade_in(Duration::from_millis(10));
+        assert_eq!(envelope.gain(100.0), 0.0);
+        assert_eq!(envelope.gain(105.0), 0.5);
+        assert_eq!(envelope.gain(200.0), 1.0);
+
+        // A repeat's clock has passed the fade-in
+        let repeat = PlaybackEnvelope::new(1000, 100)
+            .with_fade_in(Duration::from_millis(10))
+            .starting_at(500);
+        assert_eq!(repeat.gain(100.0), 1.0);
+    }
+
+    #[test]
+    fn test_fade_out_from_requested_frame() {
+        let envelope = PlaybackEnvelope::new(1000, 0);
+        assert_eq!(envelope.fade_out_progress(1_000_000.0), 0.0);
+
+        envelope.fade_out(50.0, Duration::from_millis(20));
+        assert_eq!(envelope.gain(50.0), 1.0);
+        assert_eq!(envelope.gain(60.0), 0.5);
+        assert_eq!(envelope.fade_out_progress(70.0), 1.0);
+        assert_eq!(envelope.gain(80.0), 0.0);
+    }
+
+    #[test]
+    fn test_routing_gain_is_linear() {
+        let routing = OutputRouting::Alternate { segment_ms: 1000 };
+        let monitor = PlaybackEnvelope::new(1000, 0).with_routing(routing, DeviceRole::Monitor);
+        let broadcast = PlaybackEnvelope::new(1000, 0).with_routing(routing, DeviceRole::Broadcast);
+        assert_eq!((monitor.gain(500.0), broadcast.gain(500.0)), (1.0, 0.0));
+        // Halfway through the switch fade: half the amplitude, a quarter of
+        // the setting
+        assert_eq!((monitor.gain(1010.0), broadcast.gain(1010.0)), (0.25, 0.25));
+        assert_eq!((monitor.gain(1500.0), broadcast.gain(1500.0)), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_progress_due_once_per_interval() {
+        let envelope = PlaybackEnvelope::new(1000, 10).with_progress(Duration::from_millis(50));
+        assert!(!envelope.progress_due(10.0, 59.0));
+        assert!(envelope.progress_due(59.0, 61.0));
+        assert!(!envelope.progress_due(61.0, 100.0));
+        assert!(!PlaybackEnvelope::new(1000, 0).progress_due(0.0, 1000.0));
+    }
+}
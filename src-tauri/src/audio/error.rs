@@ -26,6 +26,12 @@ pub enum AudioError {
     #[error("No audio data decoded")]
     NoData,
 
+    #[error("File is DRM-protected and cannot be played")]
+    DrmProtected,
+
+    #[error("Unsupported audio codec: {codec}")]
+    UnsupportedCodec { codec: String },
+
     #[error("Failed to enumerate audio devices: {0}")]
     DeviceEnumeration(String),
 
@@ -49,6 +55,9 @@ pub enum AudioError {
 
     #[error("Device not found: {0}")]
     DeviceNotFound(String),
+
+    #[error("Decoding was cancelled")]
+    Cancelled,
 }
 
 /// Convert AudioError to String for Tauri commands
@@ -63,7 +72,7 @@ mod tests {
     use super::*;
     use std::io::{Error as IoError, ErrorKind};
 
-    // ========== Display trait tests for all 15 error variants ==========
+    // ========== Display trait tests for all 18 error variants ==========
 
     #[test]
     fn test_display_file_open() {
@@ -120,6 +129,23 @@ mod tests {
         assert!(msg.contains("No audio data decoded"));
     }
 
+    #[test]
+    fn test_display_drm_protected() {
+        let err = AudioError::DrmProtected;
+        let msg = err.to_string();
+        assert!(msg.contains("DRM-protected"));
+    }
+
+    #[test]
+    fn test_display_unsupported_codec() {
+        let err = AudioError::UnsupportedCodec {
+            codec: "ALAC".to_string(),
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("Unsupported audio codec"));
+        assert!(msg.contains("ALAC"));
+    }
+
     #[test]
     fn test_display_device_enumeration() {
         let err = AudioError::DeviceEnumeration("access denied".to_string());
@@ -182,6 +208,12 @@ mod tests {
         assert!(msg.contains("Speakers (High Definition Audio)"));
     }
 
+    #[test]
+    fn test_display_cancelled() {
+        let err = AudioError::Cancelled;
+        assert_eq!(err.to_string(), "Decoding was cancelled");
+    }
+
     // ========== From<io::Error> conversion test ==========
 
     #[test]
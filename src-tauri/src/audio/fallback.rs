@@ -0,0 +1,321 @@
+//! Fallback to the system default device
+//!
+//! When a saved monitor/broadcast device no longer resolves, playback uses
+//! the system default output instead. The saved choice stays in settings and
+//! is used again as soon as the device is back.
+
+use std::sync::Mutex;
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+
+use super::device::{follow_system_default, output_device_ids, resolve_device_index};
+use super::{AudioError, DeviceId};
+
+/// Roles currently on fallback
+static TRACKER: Mutex<FallbackTracker> = Mutex::new(FallbackTracker::new());
+
+/// Which output a device is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceRole {
+    Monitor,
+    Broadcast,
+}
+
+/// Device actually used for a role (index into the host's output devices)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolvedDevice {
+    /// The saved device
+    Saved(usize),
+    /// The system default, because the saved device is missing
+    Fallback(usize),
+}
+
+impl ResolvedDevice {
+    pub fn index(self) -> usize {
+        match self {
+            ResolvedDevice::Saved(index) | ResolvedDevice::Fallback(index) => index,
+        }
+    }
+}
+
+/// A role switched to or back from the fallback device
+/// (payload of the `device-fallback` event)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeviceFallback {
+    pub role: DeviceRole,
+    /// Saved device that is missing (or has returned)
+    pub original_device_id: DeviceId,
+    /// Device used instead (None once the original is restored)
+    pub fallback_device_id: Option<DeviceId>,
+    /// True when the original device is back in use
+    pub restored: bool,
+}
+
+/// Resolve a saved device against the output devices' IDs (see
+/// `output_device_ids`)
+///
+/// Falls back to `default_index` when the saved ID doesn't resolve; pass
+/// `None` to disable the fallback.
+pub fn resolve_device(
+    saved: &DeviceId,
+    devices: &[DeviceId],
+    default_index: Option<usize>,
+) -> Result<ResolvedDevice, AudioError> {
+    match resolve_device_index(saved, devices) {
+        Some(index) => Ok(ResolvedDevice::Saved(index)),
+        None => resolve_missing_device(saved, devices, default_index),
+    }
+}
+
+/// Resolve a saved device that is missing (the fallback, if enabled)
+pub fn resolve_missing_device(
+    saved: &DeviceId,
+    devices: &[DeviceId],
+    default_index: Option<usize>,
+) -> Result<ResolvedDevice, AudioError> {
+    default_index
+        .filter(|index| *index < devices.len())
+        .map(ResolvedDevice::Fallback)
+        .ok_or_else(|| AudioError::DeviceNotFound(saved.to_string()))
+}
+
+/// Index of the system default output in `devices`
+pub fn default_device_index(host: &cpal::Host, devices: &[cpal::Device]) -> Option<usize> {
+    let default_name = host.default_output_device()?.name().ok()?;
+    devices
+        .iter()
+        .position(|d| d.name().ok().as_deref() == Some(default_name.as_str()))
+}
+
+/// Record how a role was resolved against `devices`; returns a change to
+/// report, if any
+pub fn record_resolution(
+    role: DeviceRole,
+    saved: &DeviceId,
+    resolved: ResolvedDevice,
+    devices: &[DeviceId],
+) -> Option<DeviceFallback> {
+    TRACKER
+        .lock()
+        .ok()
+        .and_then(|mut tracker| tracker.update(role, saved, resolved, devices))
+}
+
+/// Roles currently playing on the fallback device
+pub fn active_fallbacks() -> Vec<DeviceFallback> {
+    TRACKER
+        .lock()
+        .map(|tracker| tracker.active())
+        .unwrap_or_default()
+}
+
+/// Check the saved devices against the current device list (e.g. at startup)
+pub fn check_saved_devices(
+    monitor: Option<&DeviceId>,
+    broadcast: Option<&DeviceId>,
+) -> Result<Vec<DeviceFallback>, AudioError> {
+    let host = cpal::default_host();
+    let devices: Vec<_> = host
+        .output_devices()
+        .map_err(|e| AudioError::DeviceEnumeration(e.to_string()))?
+        .collect();
+    let default_index = default_device_index(&host, &devices);
+    let ids = output_device_ids(&devices);
+
+    let changes = [
+        (DeviceRole::Monitor, monitor),
+        (DeviceRole::Broadcast, broadcast),
+    ]
+    .into_iter()
+    .filter_map(|(role, saved)| {
+        let saved = follow_system_default(saved?, &ids, default_index);
+        let resolved = resolve_device(&saved, &ids, default_index).ok()?;
+        record_resolution(role, &saved, resolved, &ids)
+    })
+    .collect();
+    Ok(changes)
+}
+
+/// Original device and fallback per role
+#[derive(Debug)]
+struct FallbackTracker {
+    monitor: Option<(DeviceId, DeviceId)>,
+    broadcast: Option<(DeviceId, DeviceId)>,
+}
+
+impl FallbackTracker {
+    const fn new() -> Self {
+        Self {
+            monitor: None,
+            broadcast: None,
+        }
+    }
+
+    fn slot(&mut self, role: DeviceRole) -> &mut Option<(DeviceId, DeviceId)> {
+        match role {
+            DeviceRole::Monitor => &mut self.monitor,
+            DeviceRole::Broadcast => &mut self.broadcast,
+        }
+    }
+
+    fn update(
+        &mut self,
+        role: DeviceRole,
+        saved: &DeviceId,
+        resolved: ResolvedDevice,
+        devices: &[DeviceId],
+    ) -> Option<DeviceFallback> {
+        let slot = self.slot(role);
+        match resolved {
+            ResolvedDevice::Fallback(index) => {
+                let fallback = devices.get(index)?.clone();
+                if slot.as_ref() == Some(&(saved.clone(), fallback.clone())) {
+                    return None;
+                }
+                *slot = Some((saved.clone(), fallback.clone()));
+                Some(DeviceFallback {
+                    role,
+                    original_device_id: saved.clone(),
+                    fallback_device_id: Some(fallback),
+                    restored: false,
+                })
+            }
+            ResolvedDevice::Saved(_) => {
+                // Only report a restore for the device that was missing
+                let (original, _) = slot.take()?;
+                (original == *saved).then(|| DeviceFallback {
+                    role,
+                    original_device_id: original,
+                    fallback_device_id: None,
+                    restored: true,
+                })
+            }
+        }
+    }
+
+    fn active(&self) -> Vec<DeviceFallback> {
+        [
+            (DeviceRole::Monitor, &self.monitor),
+            (DeviceRole::Broadcast, &self.broadcast),
+        ]
+        .into_iter()
+        .filter_map(|(role, slot)| {
+            let (original, fallback) = slot.as_ref()?;
+            Some(DeviceFallback {
+                role,
+                original_device_id: original.clone(),
+                fallback_device_id: Some(fallback.clone()),
+                restored: false,
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn devices(count: usize) -> Vec<DeviceId> {
+        (0..count).map(DeviceId::from_index).collect()
+    }
+
+    #[test]
+    fn test_resolve_device_saved() {
+        let resolved = resolve_device(&DeviceId::from_index(1), &devices(3), Some(0)).unwrap();
+        assert_eq!(resolved, ResolvedDevice::Saved(1));
+    }
+
+    #[test]
+    fn test_resolve_device_falls_back_to_default() {
+        let resolved = resolve_device(&DeviceId::from_index(5), &devices(3), Some(2)).unwrap();
+        assert_eq!(resolved, ResolvedDevice::Fallback(2));
+        assert_eq!(resolved.index(), 2);
+    }
+
+    #[test]
+    fn test_resolve_device_without_fallback() {
+        assert!(resolve_device(&DeviceId::from_index(5), &devices(3), None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_stable_device() {
+        let speakers = DeviceId::stable("wasapi", "{guid-b}", "Speakers");
+        let current = [
+            DeviceId::stable("wasapi", "{guid-a}", "Headset"),
+            speakers.clone(),
+        ];
+        assert_eq!(
+            resolve_device(&speakers, &current, Some(0)).unwrap(),
+            ResolvedDevice::Saved(1)
+        );
+        assert_eq!(
+            resolve_device(&speakers, &current[..1], Some(0)).unwrap(),
+            ResolvedDevice::Fallback(0)
+        );
+    }
+
+    #[test]
+    fn test_tracker_reports_fallback_once() {
+        let mut tracker = FallbackTracker::new();
+        let saved = DeviceId::from_index(5);
+
+        let change = tracker
+            .update(
+                DeviceRole::Monitor,
+                &saved,
+                ResolvedDevice::Fallback(0),
+                &devices(6),
+            )
+            .unwrap();
+        assert_eq!(change.fallback_device_id, Some(DeviceId::from_index(0)));
+        assert!(!change.restored);
+
+        // Same fallback again: nothing new to report
+        assert!(tracker
+            .update(
+                DeviceRole::Monitor,
+                &saved,
+                ResolvedDevice::Fallback(0),
+                &devices(6)
+            )
+            .is_none());
+        assert_eq!(tracker.active().len(), 1);
+    }
+
+    #[test]
+    fn test_tracker_reports_restore() {
+        let mut tracker = FallbackTracker::new();
+        let saved = DeviceId::from_index(5);
+        tracker.update(
+            DeviceRole::Broadcast,
+            &saved,
+            ResolvedDevice::Fallback(0),
+            &devices(6),
+        );
+
+        let change = tracker
+            .update(
+                DeviceRole::Broadcast,
+                &saved,
+                ResolvedDevice::Saved(5),
+                &devices(6),
+            )
+            .unwrap();
+        assert!(change.restored);
+        assert_eq!(change.original_device_id, saved);
+        assert!(tracker.active().is_empty());
+
+        // No fallback active: resolving the saved device is not a change
+        assert!(tracker
+            .update(
+                DeviceRole::Broadcast,
+                &saved,
+                ResolvedDevice::Saved(5),
+                &devices(6)
+            )
+            .is_none());
+    }
+}
@@ -0,0 +1,83 @@
+//! Failure injection for exercising audio error handling
+//!
+//! Debug builds can arm simulated failures (see `inject_audio_fault`). The
+//! next playbacks hit them where a real failure would happen, so the
+//! frontend's error handling and the recovery paths (device fallback, error
+//! events, cleanup) can be tested without unplugging hardware. Each armed
+//! injection fires once.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// A simulated audio failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fault {
+    /// Both saved output devices look unplugged while resolving them
+    DeviceLoss,
+    /// Decoding the sound's file fails
+    DecodeFailure,
+    /// Building an output stream fails
+    StreamBuildFailure,
+}
+
+impl Fault {
+    pub const ALL: [Fault; 3] = [
+        Fault::DeviceLoss,
+        Fault::DecodeFailure,
+        Fault::StreamBuildFailure,
+    ];
+
+    fn slot(self) -> &'static AtomicU32 {
+        &ARMED[self as usize]
+    }
+}
+
+/// Pending injections per fault (indexed by `Fault as usize`)
+static ARMED: [AtomicU32; 3] = [AtomicU32::new(0), AtomicU32::new(0), AtomicU32::new(0)];
+
+/// Make the next `count` occurrences of `fault`'s operation fail
+pub fn arm_fault(fault: Fault, count: u32) {
+    fault.slot().fetch_add(count, Ordering::Relaxed);
+}
+
+/// Consume one pending injection of `fault`; true if the operation must fail
+pub fn take_fault(fault: Fault) -> bool {
+    fault
+        .slot()
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .is_ok()
+}
+
+/// Disarm every fault
+pub fn clear_faults() {
+    for fault in Fault::ALL {
+        fault.slot().store(0, Ordering::Relaxed);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Other tests run real decodes and streams, so only one test arms faults
+    // and it leaves nothing armed
+    #[test]
+    fn test_armed_faults_fire_once_each() {
+        assert!(!take_fault(Fault::StreamBuildFailure));
+
+        arm_fault(Fault::StreamBuildFailure, 2);
+        assert!(take_fault(Fault::StreamBuildFailure));
+        assert!(take_fault(Fault::StreamBuildFailure));
+        assert!(!take_fault(Fault::StreamBuildFailure));
+
+        arm_fault(Fault::DeviceLoss, 1);
+        clear_faults();
+        assert!(!take_fault(Fault::DeviceLoss));
+    }
+}
@@ -0,0 +1,136 @@
+//! Minimal FFT utilities for audio analysis
+//!
+//! Provides an in-place radix-2 FFT and window helpers used by the
+//! spectrogram generator. Kept dependency-free because the transforms
+//! run on short frames only.
+
+use std::f32::consts::PI;
+
+/// In-place iterative radix-2 FFT
+///
+/// `re` and `im` must have the same power-of-two length.
+pub(crate) fn fft_in_place(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    debug_assert_eq!(n, im.len());
+    debug_assert!(n.is_power_of_two());
+
+    if n <= 1 {
+        return;
+    }
+
+    // Bit-reversal permutation
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    // Butterflies
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        let (w_im, w_re) = angle.sin_cos();
+        for start in (0..n).step_by(len) {
+            let mut cur_re = 1.0f32;
+            let mut cur_im = 0.0f32;
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Hann window coefficients of the given length
+pub(crate) fn hann_window(len: usize) -> Vec<f32> {
+    if len <= 1 {
+        return vec![1.0; len];
+    }
+    (0..len)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / (len - 1) as f32).cos())
+        .collect()
+}
+
+/// Magnitude spectrum (bins 0..=n/2) of a real-valued frame
+///
+/// The frame length must be a power of two.
+pub(crate) fn magnitude_spectrum(frame: &[f32]) -> Vec<f32> {
+    let mut re = frame.to_vec();
+    let mut im = vec![0.0f32; frame.len()];
+    fft_in_place(&mut re, &mut im);
+
+    (0..=frame.len() / 2)
+        .map(|k| (re[k] * re[k] + im[k] * im[k]).sqrt())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fft_impulse_is_flat() {
+        let mut re = vec![0.0f32; 8];
+        let mut im = vec![0.0f32; 8];
+        re[0] = 1.0;
+        fft_in_place(&mut re, &mut im);
+
+        for k in 0..8 {
+            assert!((re[k] - 1.0).abs() < 1e-6);
+            assert!(im[k].abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_fft_sine_peak_bin() {
+        let n = 64;
+        let frame: Vec<f32> = (0..n)
+            .map(|i| (2.0 * PI * 4.0 * i as f32 / n as f32).sin())
+            .collect();
+        let mags = magnitude_spectrum(&frame);
+
+        assert_eq!(mags.len(), n / 2 + 1);
+        let peak_bin = mags
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(i, _)| i)
+            .unwrap();
+        assert_eq!(peak_bin, 4);
+        assert!((mags[4] - n as f32 / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_hann_window_shape() {
+        let w = hann_window(5);
+        assert_eq!(w.len(), 5);
+        assert!(w[0].abs() < 1e-6);
+        assert!((w[2] - 1.0).abs() < 1e-6);
+        assert!(w[4].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hann_window_degenerate() {
+        assert!(hann_window(0).is_empty());
+        assert_eq!(hann_window(1), vec![1.0]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Gain staging simulation
+//!
+//! Mirrors the playback gain chain (per-sound volume -> loudness
+//! normalization -> sqrt volume curve -> master) to predict output peaks
+//! without playing anything.
+
+use serde::Serialize;
+
+use super::playback::{calculate_scaled_volume, volume_with_gain};
+use super::waveform::abs_peak;
+use super::AudioData;
+
+/// Output peaks above this clip
+pub const CLIP_THRESHOLD_DBFS: f32 = 0.0;
+/// Output peaks below this are barely audible over voice chat
+pub const INAUDIBLE_THRESHOLD_DBFS: f32 = -45.0;
+/// Files peaking below this hold nothing but silence (or dither)
+pub const SILENCE_THRESHOLD_DBFS: f32 = -70.0;
+
+/// Expected outcome at the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GainVerdict {
+    Ok,
+    Clipping,
+    Inaudible,
+}
+
+/// Gain contributed by each stage (dB, None = silence)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GainChain {
+    /// Volume setting the sound plays at (per-sound or default, 0.0-1.0)
+    pub volume: f32,
+    /// Loudness normalization gain (0 dB when disabled)
+    pub normalization_gain_db: f32,
+    /// Volume curve incl. base attenuation, applied to the normalized volume
+    pub curve_gain_db: Option<f32>,
+    pub master_gain_db: f32,
+    /// Sum of all stages
+    pub total_gain_db: Option<f32>,
+}
+
+/// Linear amplitude to dB (None for silence)
+pub fn to_db(linear: f32) -> Option<f32> {
+    (linear > 0.0).then(|| 20.0 * linear.log10())
+}
+
+/// dB to linear amplitude
+pub fn from_db(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Simulate the gain chain for one sound
+///
+/// `normalization_gain` and `master_gain` are linear factors.
+pub fn simulate_chain(volume: f32, normalization_gain: f32, master_gain: f32) -> GainChain {
+    let volume = volume.clamp(0.0, 1.0);
+    let amplitude = calculate_scaled_volume(volume_with_gain(volume, normalization_gain));
+    let total = amplitude * master_gain;
+
+    GainChain {
+        volume,
+        normalization_gain_db: to_db(normalization_gain).unwrap_or(0.0),
+        curve_gain_db: to_db(calculate_scaled_volume(volume)),
+        master_gain_db: to_db(master_gain).unwrap_or(0.0),
+        total_gain_db: to_db(total),
+    }
+}
+
+/// Absolute sample peak within an optional trim range
+pub fn sample_peak(audio: &AudioData, trim_start_ms: Option<u64>, trim_end_ms: Option<u64>) -> f32 {
+    let channels = audio.channels.max(1) as usize;
+    let frames = audio.samples.len() / channels;
+    let to_frame = |ms: u64| ((ms * audio.sample_rate as u64 / 1000) as usize).min(frames);
+
+    let start = trim_start_ms.map_or(0, to_frame);
+    let end = trim_end_ms.map_or(frames, to_frame).max(start);
+
+    abs_peak(&audio.samples[start * channels..end * channels])
+}
+
+/// Whether a whole file peaks below `SILENCE_THRESHOLD_DBFS`
+pub fn is_silent(audio: &AudioData) -> bool {
+    to_db(sample_peak(audio, None, None)).is_none_or(|db| db < SILENCE_THRESHOLD_DBFS)
+}
+
+/// Classify an expected output peak
+pub fn verdict(output_peak_dbfs: Option<f32>) -> GainVerdict {
+    match output_peak_dbfs {
+        Some(peak) if peak > CLIP_THRESHOLD_DBFS => GainVerdict::Clipping,
+        Some(peak) if peak >= INAUDIBLE_THRESHOLD_DBFS => GainVerdict::Ok,
+        _ => GainVerdict::Inaudible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_without_normalization() {
+        let chain = simulate_chain(1.0, 1.0, 1.0);
+        assert_eq!(chain.normalization_gain_db, 0.0);
+        // Full volume is 20% amplitude = -14 dB
+        let total = chain.total_gain_db.unwrap();
+        assert!((total - 20.0 * 0.2f32.log10()).abs() < 0.01);
+        assert_eq!(chain.curve_gain_db, chain.total_gain_db);
+    }
+
+    #[test]
+    fn test_chain_stages_add_up() {
+        let chain = simulate_chain(0.5, 2.0, 0.5);
+        let sum = chain.curve_gain_db.unwrap() + chain.normalization_gain_db + chain.master_gain_db;
+        assert!((chain.total_gain_db.unwrap() - sum).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_from_db_inverts_to_db() {
+        assert_eq!(from_db(0.0), 1.0);
+        assert!((from_db(-6.0) - 0.501).abs() < 0.001);
+        assert!((to_db(from_db(-12.5)).unwrap() + 12.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_chain_muted() {
+        let chain = simulate_chain(0.0, 1.0, 1.0);
+        assert_eq!(chain.total_gain_db, None);
+        assert_eq!(chain.curve_gain_db, None);
+    }
+
+    #[test]
+    fn test_sample_peak_respects_trim() {
+        let mut samples = vec![0.1; 2000];
+        samples[1500] = -0.9;
+        let audio = AudioData {
+            samples,
+            sample_rate: 1000,
+            channels: 1,
+        };
+
+        assert!((sample_peak(&audio, None, None) - 0.9).abs() < 0.0001);
+        assert!((sample_peak(&audio, None, Some(1000)) - 0.1).abs() < 0.0001);
+        assert!((sample_peak(&audio, Some(1400), Some(1600)) - 0.9).abs() < 0.0001);
+        assert_eq!(sample_peak(&audio, Some(5000), None), 0.0);
+    }
+
+    #[test]
+    fn test_is_silent() {
+        let mut audio = AudioData {
+            samples: vec![0.0; 1000],
+            sample_rate: 1000,
+            channels: 1,
+        };
+        assert!(is_silent(&audio));
+
+        // Dither-level noise is still silence, a single audible click is not
+        audio.samples[10] = from_db(-80.0);
+        assert!(is_silent(&audio));
+        audio.samples[500] = from_db(-40.0);
+        assert!(!is_silent(&audio));
+    }
+
+    #[test]
+    fn test_verdict_thresholds() {
+        assert_eq!(verdict(Some(1.5)), GainVerdict::Clipping);
+        assert_eq!(verdict(Some(-12.0)), GainVerdict::Ok);
+        assert_eq!(verdict(Some(-60.0)), GainVerdict::Inaudible);
+        assert_eq!(verdict(None), GainVerdict::Inaudible);
+    }
+}
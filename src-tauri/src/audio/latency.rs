@@ -0,0 +1,328 @@
+//! Output latency measurement
+//!
+//! Opens a stream on a device with the buffer sizes playback uses, plays a
+//! short click and reads the timing the host reports in the callbacks: the
+//! frames per callback (the app's own buffering) and how far each callback
+//! runs ahead of its samples reaching the device (the device's latency).
+//! The last result is kept for the engine status.
+
+use std::f32::consts::TAU;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use cpal::{BufferSize, Device, StreamConfig};
+use serde::Serialize;
+use tracing::{debug, info};
+
+use super::playback::buffer_sizes;
+use super::{AudioError, DeviceId};
+use crate::stats::now_ms;
+
+/// How long the measurement stream runs
+const MEASURE_DURATION: Duration = Duration::from_millis(300);
+
+/// Length of the test click
+const CLICK_MS: f32 = 3.0;
+
+/// Frequency of the test click
+const CLICK_HZ: f32 = 1_000.0;
+
+/// Peak level of the test click
+const CLICK_LEVEL: f32 = 0.25;
+
+/// Last measurement (see `last_latency_measurement`)
+static LAST_MEASUREMENT: Mutex<Option<LatencyReport>> = Mutex::new(None);
+
+/// Measured output latency of a device
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyReport {
+    pub device_id: DeviceId,
+    pub device_name: String,
+    pub sample_rate: u32,
+    /// Buffer size the stream was opened with ("Fixed(256)" or "Default")
+    pub buffer_size: String,
+    /// Frames per callback the device asked for (largest seen)
+    pub callback_frames: u32,
+    /// The app's buffering: one callback of audio (ms)
+    pub buffer_ms: f64,
+    /// Time from a callback to its first sample playing, as reported by the
+    /// host (ms, None if it doesn't report it)
+    pub device_latency_ms: Option<f64>,
+    /// Buffering plus device latency, roughly trigger to sound (ms)
+    pub total_ms: f64,
+    /// When it was measured (Unix time in milliseconds)
+    pub measured_at_ms: u64,
+}
+
+/// Timing collected by the measurement stream's callback
+#[derive(Default)]
+struct CallbackTimings {
+    callbacks: AtomicU32,
+    max_frames: AtomicU32,
+    /// Sum of the reported latencies (µs)
+    latency_us: AtomicU64,
+    /// Callbacks that reported a latency
+    latency_count: AtomicU32,
+}
+
+impl CallbackTimings {
+    fn record(&self, frames: usize, info: &cpal::OutputCallbackInfo) {
+        self.callbacks.fetch_add(1, Ordering::Relaxed);
+        self.max_frames.fetch_max(frames as u32, Ordering::Relaxed);
+        let timestamp = info.timestamp();
+        if let Some(latency) = timestamp.playback.duration_since(&timestamp.callback) {
+            self.latency_us
+                .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+            self.latency_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Average reported latency
+    fn device_latency(&self) -> Option<Duration> {
+        let count = self.latency_count.load(Ordering::Relaxed);
+        (count > 0)
+            .then(|| Duration::from_micros(self.latency_us.load(Ordering::Relaxed) / count as u64))
+    }
+}
+
+/// Play a click on `device` and report its output latency
+///
+/// Blocks for the length of the measurement; the result is also kept for
+/// `last_latency_measurement`.
+pub fn measure_output_latency(
+    device: &Device,
+    device_id: DeviceId,
+    small_buffers: bool,
+) -> Result<LatencyReport, AudioError> {
+    let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+    let supported_config = device
+        .default_output_config()
+        .map_err(|e| AudioError::DeviceConfig(e.to_string()))?;
+    let sample_rate = supported_config.sample_rate().0;
+    let timings = Arc::new(CallbackTimings::default());
+
+    // Same buffer sizes as playback, then the device default
+    let configs = buffer_sizes(small_buffers)
+        .map(BufferSize::Fixed)
+        .chain([BufferSize::Default]);
+    let mut last_error = None;
+    let mut opened = None;
+    for buffer_size in configs {
+        let config = StreamConfig {
+            channels: supported_config.channels(),
+            sample_rate: supported_config.sample_rate(),
+            buffer_size,
+        };
+        match build_probe_stream(device, supported_config.sample_format(), &config, &timings) {
+            Ok(stream) => {
+                opened = Some((stream, buffer_size));
+                break;
+            }
+            Err(e) => {
+                debug!(buffer_size = ?buffer_size, error = %e, "Latency probe stream failed");
+                last_error = Some(e);
+            }
+        }
+    }
+    let Some((stream, buffer_size)) = opened else {
+        return Err(last_error.unwrap_or(AudioError::UnsupportedFormat));
+    };
+
+    stream
+        .play()
+        .map_err(|e| AudioError::StreamStart(e.to_string()))?;
+    thread::sleep(MEASURE_DURATION);
+    drop(stream);
+
+    if timings.callbacks.load(Ordering::Relaxed) == 0 {
+        return Err(AudioError::StreamStart(format!(
+            "No audio callbacks within {}ms",
+            MEASURE_DURATION.as_millis()
+        )));
+    }
+
+    let report = latency_report(
+        device_id,
+        device_name,
+        sample_rate,
+        match buffer_size {
+            BufferSize::Fixed(size) => format!("Fixed({})", size),
+            BufferSize::Default => "Default".to_string(),
+        },
+        timings.max_frames.load(Ordering::Relaxed),
+        timings.device_latency(),
+    );
+    info!(
+        device = %report.device_name,
+        buffer_size = %report.buffer_size,
+        callback_frames = report.callback_frames,
+        device_latency_ms = ?report.device_latency_ms,
+        total_ms = format!("{:.1}", report.total_ms),
+        "Output latency measured"
+    );
+
+    if let Ok(mut last) = LAST_MEASUREMENT.lock() {
+        *last = Some(report.clone());
+    }
+    Ok(report)
+}
+
+/// Result of the last `measure_output_latency`, if any
+pub fn last_latency_measurement() -> Option<LatencyReport> {
+    LAST_MEASUREMENT.lock().ok()?.clone()
+}
+
+fn latency_report(
+    device_id: DeviceId,
+    device_name: String,
+    sample_rate: u32,
+    buffer_size: String,
+    callback_frames: u32,
+    device_latency: Option<Duration>,
+) -> LatencyReport {
+    let buffer_ms = callback_frames as f64 * 1000.0 / sample_rate.max(1) as f64;
+    let device_latency_ms = device_latency.map(|latency| latency.as_secs_f64() * 1000.0);
+    LatencyReport {
+        device_id,
+        device_name,
+        sample_rate,
+        buffer_size,
+        callback_frames,
+        buffer_ms,
+        device_latency_ms,
+        total_ms: buffer_ms + device_latency_ms.unwrap_or(0.0),
+        measured_at_ms: now_ms(),
+    }
+}
+
+/// Build the measurement stream: the click, then silence
+fn build_probe_stream(
+    device: &Device,
+    sample_format: cpal::SampleFormat,
+    config: &StreamConfig,
+    timings: &Arc<CallbackTimings>,
+) -> Result<cpal::Stream, AudioError> {
+    let channels = config.channels.max(1) as usize;
+    let sample_rate = config.sample_rate.0;
+    let timings = timings.clone();
+    let mut frame = 0;
+    let on_error = |err: cpal::StreamError| debug!("Latency probe stream error: {}", err);
+
+    match sample_format {
+        cpal::SampleFormat::F32 => device.build_output_stream(
+            config,
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                timings.record(data.len() / channels, info);
+                write_click(data, channels, sample_rate, &mut frame, |v| v);
+            },
+            on_error,
+            None,
+        ),
+        cpal::SampleFormat::I16 => device.build_output_stream(
+            config,
+            move |data: &mut [i16], info: &cpal::OutputCallbackInfo| {
+                timings.record(data.len() / channels, info);
+                write_click(data, channels, sample_rate, &mut frame, |v| {
+                    (v * 32767.0) as i16
+                });
+            },
+            on_error,
+            None,
+        ),
+        cpal::SampleFormat::U16 => device.build_output_stream(
+            config,
+            move |data: &mut [u16], info: &cpal::OutputCallbackInfo| {
+                timings.record(data.len() / channels, info);
+                write_click(data, channels, sample_rate, &mut frame, |v| {
+                    ((v + 1.0) * 32767.5) as u16
+                });
+            },
+            on_error,
+            None,
+        ),
+        _ => return Err(AudioError::UnsupportedFormat),
+    }
+    .map_err(|e| AudioError::StreamBuild(e.to_string()))
+}
+
+/// Write the click's next frames to every channel, `frame` counting the
+/// frames written so far
+fn write_click<T: Copy>(
+    output: &mut [T],
+    channels: usize,
+    sample_rate: u32,
+    frame: &mut usize,
+    to_sample: impl Fn(f32) -> T,
+) {
+    for samples in output.chunks_mut(channels) {
+        samples.fill(to_sample(click_sample(*frame, sample_rate)));
+        *frame += 1;
+    }
+}
+
+/// Sample of a short decaying tone burst, silence after `CLICK_MS`
+fn click_sample(frame: usize, sample_rate: u32) -> f32 {
+    let rate = sample_rate.max(1) as f32;
+    let t = frame as f32 / rate;
+    if t * 1000.0 >= CLICK_MS {
+        return 0.0;
+    }
+    let decay = 1.0 - t * 1000.0 / CLICK_MS;
+    (TAU * CLICK_HZ * t).sin() * decay * CLICK_LEVEL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_is_short_and_quiet() {
+        let click: Vec<f32> = (0..480).map(|i| click_sample(i, 48_000)).collect();
+        // 3ms at 48kHz
+        assert!(click[..144].iter().any(|&s| s.abs() > 0.1));
+        assert!(click.iter().all(|&s| s.abs() <= CLICK_LEVEL));
+        assert!(click[144..].iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_write_click_fills_every_channel() {
+        let mut output = [9.0f32; 8];
+        let mut frame = 10;
+        write_click(&mut output, 2, 48_000, &mut frame, |v| v);
+        assert_eq!(frame, 14);
+        for (i, pair) in output.chunks(2).enumerate() {
+            assert_eq!(pair[0], pair[1]);
+            assert_eq!(pair[0], click_sample(10 + i, 48_000));
+        }
+    }
+
+    #[test]
+    fn test_latency_report_adds_buffer_and_device_latency() {
+        let report = latency_report(
+            DeviceId::from_index(0),
+            "Speakers".to_string(),
+            48_000,
+            "Fixed(256)".to_string(),
+            480,
+            Some(Duration::from_millis(12)),
+        );
+        assert!((report.buffer_ms - 10.0).abs() < 1e-9);
+        assert_eq!(report.device_latency_ms, Some(12.0));
+        assert!((report.total_ms - 22.0).abs() < 1e-9);
+
+        // Hosts without timestamps only report the buffering
+        let report = latency_report(
+            DeviceId::from_index(0),
+            "Speakers".to_string(),
+            48_000,
+            "Default".to_string(),
+            480,
+            None,
+        );
+        assert_eq!(report.device_latency_ms, None);
+        assert!((report.total_ms - 10.0).abs() < 1e-9);
+    }
+}
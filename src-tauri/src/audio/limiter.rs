@@ -0,0 +1,142 @@
+//! Output limiter
+//!
+//! Loudness normalization, device trims and boosted volumes can push a sound
+//! past full scale, which the device then clips. The limiter looks a
+//! millisecond ahead and lowers the gain before a peak arrives, so the output
+//! stays under `CEILING` without the crackle of a hard clip.
+
+/// Highest output sample (-1 dBFS, headroom for peaks between samples)
+pub const CEILING: f32 = 0.891;
+
+/// How long the gain takes to recover after a peak (time constant)
+const RELEASE_MS: f32 = 80.0;
+
+/// Look-ahead limiter for one stream's interleaved output
+pub struct Limiter {
+    channels: usize,
+    /// Frames still to be played, `lookahead` of them (interleaved)
+    delay: Vec<f32>,
+    /// Frame slot of `delay` that is played next
+    pos: usize,
+    lookahead: usize,
+    /// Lowest gain a frame in the look-ahead window needs
+    held_gain: f32,
+    /// Frames until `held_gain` was played out
+    hold: usize,
+    gain: f32,
+    attack: f32,
+    release: f32,
+}
+
+impl Limiter {
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let channels = channels.max(1);
+        let lookahead = (sample_rate as usize / 1000).max(1);
+        let release_frames = RELEASE_MS / 1000.0 * sample_rate as f32;
+        Self {
+            channels,
+            delay: vec![0.0; lookahead * channels],
+            pos: 0,
+            lookahead,
+            held_gain: 1.0,
+            hold: 0,
+            gain: 1.0,
+            // Within 1% of the needed gain once the peak is played
+            attack: 1.0 - (-5.0 / lookahead as f32).exp(),
+            release: 1.0 - (-1.0 / release_frames.max(1.0)).exp(),
+        }
+    }
+
+    /// Limit interleaved frames in place; they come out delayed by the
+    /// look-ahead (about 1 ms)
+    pub fn process(&mut self, buffer: &mut [f32]) {
+        for frame in buffer.chunks_exact_mut(self.channels) {
+            let needed = needed_gain(frame);
+            if needed <= self.held_gain || self.hold == 0 {
+                self.held_gain = needed;
+                self.hold = self.lookahead;
+            } else {
+                self.hold -= 1;
+            }
+            let coef = if self.held_gain < self.gain {
+                self.attack
+            } else {
+                self.release
+            };
+            self.gain += (self.held_gain - self.gain) * coef;
+
+            let start = self.pos * self.channels;
+            let delayed = &mut self.delay[start..start + self.channels];
+            // The envelope stops short of the needed gain (and the hold can
+            // miss a peak right behind another), never let a sample through
+            let gain = self.gain.min(needed_gain(delayed));
+            for (sample, delayed) in frame.iter_mut().zip(delayed.iter_mut()) {
+                let input = *sample;
+                *sample = *delayed * gain;
+                *delayed = input;
+            }
+            self.pos = (self.pos + 1) % self.lookahead;
+        }
+    }
+}
+
+/// Gain that brings a frame's peak down to the ceiling
+fn needed_gain(frame: &[f32]) -> f32 {
+    let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    if peak > CEILING {
+        CEILING / peak
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frames: usize, amplitude: f32) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let s = amplitude * (i as f32 * 2.0 * std::f32::consts::PI * 440.0 / 48000.0).sin();
+                [s, s]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quiet_audio_passes_delayed() {
+        let mut limiter = Limiter::new(48000, 2);
+        let input = sine(1000, 0.5);
+        let mut output = input.clone();
+        limiter.process(&mut output);
+
+        // 48 frames of look-ahead
+        assert!(output[..96].iter().all(|s| *s == 0.0));
+        assert_eq!(output[96..], input[..input.len() - 96]);
+    }
+
+    #[test]
+    fn test_loud_audio_stays_under_ceiling() {
+        let mut limiter = Limiter::new(48000, 2);
+        let mut output = sine(4800, 2.0);
+        limiter.process(&mut output);
+
+        let peak = output.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(peak <= CEILING + 1e-6);
+        // Limited, not silenced
+        assert!(peak > CEILING * 0.9);
+    }
+
+    #[test]
+    fn test_gain_recovers_after_a_peak() {
+        let mut limiter = Limiter::new(48000, 2);
+        let mut burst = sine(480, 4.0);
+        limiter.process(&mut burst);
+
+        let mut quiet = sine(48000, 0.5);
+        limiter.process(&mut quiet);
+        let tail = &quiet[quiet.len() - 960..];
+        let peak = tail.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!((peak - 0.5).abs() < 0.01);
+    }
+}
@@ -0,0 +1,283 @@
+//! Integrated loudness measurement (ITU-R BS.1770 / EBU R128)
+//!
+//! Measures the gated integrated loudness of decoded audio in LUFS so
+//! playback can be normalized to a common target level.
+
+use std::time::Instant;
+use tracing::debug;
+
+use super::AudioData;
+
+/// Gating block length (400 ms) and hop (75% overlap)
+const BLOCK_MS: u64 = 400;
+const HOP_MS: u64 = 100;
+
+/// Absolute gating threshold (LUFS)
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Relative gating threshold below the ungated loudness (LU)
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Maximum boost applied by normalization (dB), protects against
+/// amplifying near-silent files into noise
+pub const MAX_NORMALIZATION_BOOST_DB: f32 = 12.0;
+
+/// Second-order IIR filter (direct form I)
+#[derive(Clone, Copy)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b0: b[0] / a[0],
+            b1: b[1] / a[0],
+            b2: b[2] / a[0],
+            a1: a[1] / a[0],
+            a2: a[2] / a[0],
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    /// K-weighting stage 1: high shelf modelling the acoustic effect of the head
+    ///
+    /// Bilinear-transform design that reproduces the BS.1770 reference
+    /// coefficients at 48 kHz and adapts them to other sample rates.
+    fn high_shelf(sample_rate: f64) -> Self {
+        let gain_db = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let fc = 1_681.974_450_955_533;
+
+        let k = (std::f64::consts::PI * fc / sample_rate).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+
+        Self::new(
+            [
+                vh + vb * k / q + k * k,
+                2.0 * (k * k - vh),
+                vh - vb * k / q + k * k,
+            ],
+            [
+                1.0 + k / q + k * k,
+                2.0 * (k * k - 1.0),
+                1.0 - k / q + k * k,
+            ],
+        )
+    }
+
+    /// K-weighting stage 2: RLB high-pass
+    fn high_pass(sample_rate: f64) -> Self {
+        let q = 0.500_327_037_323_877_3;
+        let fc = 38.135_470_876_024_44;
+
+        let k = (std::f64::consts::PI * fc / sample_rate).tan();
+
+        Self::new(
+            [1.0, -2.0, 1.0],
+            [
+                1.0 + k / q + k * k,
+                2.0 * (k * k - 1.0),
+                1.0 - k / q + k * k,
+            ],
+        )
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Measure gated integrated loudness in LUFS
+///
+/// Returns `None` for empty or silent audio (every block below the
+/// absolute gate). All channels are weighted equally, which matches
+/// BS.1770 for mono and stereo material.
+pub fn integrated_loudness(audio_data: &AudioData) -> Option<f32> {
+    let start = Instant::now();
+    let channels = audio_data.channels.max(1) as usize;
+    let total_frames = audio_data.samples.len() / channels;
+    if total_frames == 0 || audio_data.sample_rate == 0 {
+        return None;
+    }
+
+    let sample_rate = audio_data.sample_rate as f64;
+    let block_frames = ((BLOCK_MS * audio_data.sample_rate as u64 / 1000) as usize).max(1);
+    let hop_frames = ((HOP_MS * audio_data.sample_rate as u64 / 1000) as usize).max(1);
+
+    // K-weighted squared samples summed across channels, per hop
+    let mut filters: Vec<(Biquad, Biquad)> = (0..channels)
+        .map(|_| {
+            (
+                Biquad::high_shelf(sample_rate),
+                Biquad::high_pass(sample_rate),
+            )
+        })
+        .collect();
+    let num_hops = total_frames.div_ceil(hop_frames);
+    let mut hop_energy = vec![0.0f64; num_hops];
+
+    for (frame_idx, frame) in audio_data.samples.chunks_exact(channels).enumerate() {
+        let mut energy = 0.0;
+        for (sample, (shelf, pass)) in frame.iter().zip(filters.iter_mut()) {
+            let weighted = pass.process(shelf.process(*sample as f64));
+            energy += weighted * weighted;
+        }
+        hop_energy[frame_idx / hop_frames] += energy;
+    }
+
+    // Mean-square power of each complete 400 ms block (short files get one block)
+    let hops_per_block = block_frames / hop_frames;
+    let complete_hops = total_frames / hop_frames;
+    let block_powers: Vec<f64> = if complete_hops < hops_per_block {
+        vec![hop_energy.iter().sum::<f64>() / total_frames as f64]
+    } else {
+        hop_energy[..complete_hops]
+            .windows(hops_per_block)
+            .map(|w| w.iter().sum::<f64>() / block_frames as f64)
+            .collect()
+    };
+
+    let loudness = |power: f64| -0.691 + 10.0 * power.log10();
+
+    // Absolute gate
+    let gated: Vec<f64> = block_powers
+        .into_iter()
+        .filter(|p| *p > 0.0 && loudness(*p) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if gated.is_empty() {
+        return None;
+    }
+
+    // Relative gate
+    let ungated_mean = gated.iter().sum::<f64>() / gated.len() as f64;
+    let relative_threshold = loudness(ungated_mean) + RELATIVE_GATE_LU;
+    let (sum, count) = gated
+        .iter()
+        .filter(|p| loudness(**p) > relative_threshold)
+        .fold((0.0, 0usize), |(sum, count), p| (sum + p, count + 1));
+    if count == 0 {
+        return None;
+    }
+
+    let lufs = loudness(sum / count as f64) as f32;
+
+    debug!(
+        duration_ms = start.elapsed().as_millis() as u64,
+        lufs = lufs,
+        "Loudness measurement complete"
+    );
+
+    Some(lufs)
+}
+
+/// Linear gain that brings audio measured at `measured_lufs` to `target_lufs`
+///
+/// Boost is capped at `MAX_NORMALIZATION_BOOST_DB`; unmeasurable (silent)
+/// audio is left untouched.
+pub fn normalization_gain(measured_lufs: Option<f32>, target_lufs: f32) -> f32 {
+    match measured_lufs {
+        Some(measured) => {
+            let gain_db = (target_lufs - measured).min(MAX_NORMALIZATION_BOOST_DB);
+            10f32.powf(gain_db / 20.0)
+        }
+        None => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn create_sine(freq: f32, amplitude: f32, seconds: f32, channels: u16) -> AudioData {
+        let sample_rate = 48000;
+        let frames = (sample_rate as f32 * seconds) as usize;
+        let samples = (0..frames)
+            .flat_map(|i| {
+                let s = amplitude * (2.0 * PI * freq * i as f32 / sample_rate as f32).sin();
+                std::iter::repeat_n(s, channels as usize)
+            })
+            .collect();
+        AudioData {
+            samples,
+            sample_rate,
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_loudness_reference_sine_stereo() {
+        // EBU Tech 3341: a 1 kHz sine at -23 dBFS on both channels reads -23 LUFS
+        let amplitude = 10f32.powf(-23.0 / 20.0);
+        let audio = create_sine(1000.0, amplitude, 5.0, 2);
+        let lufs = integrated_loudness(&audio).unwrap();
+        assert!((lufs - -23.0).abs() < 0.1, "got {}", lufs);
+    }
+
+    #[test]
+    fn test_loudness_tracks_level() {
+        // -20 dBFS is 20 LU quieter
+        let loud = integrated_loudness(&create_sine(1000.0, 1.0, 3.0, 2)).unwrap();
+        let quiet = integrated_loudness(&create_sine(1000.0, 0.1, 3.0, 2)).unwrap();
+        assert!(((loud - quiet) - 20.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_loudness_short_clip() {
+        // Shorter than one gating block still measures
+        let audio = create_sine(1000.0, 0.5, 0.2, 1);
+        assert!(integrated_loudness(&audio).is_some());
+    }
+
+    #[test]
+    fn test_loudness_silence_and_empty() {
+        let silent = AudioData {
+            samples: vec![0.0; 96000],
+            sample_rate: 48000,
+            channels: 2,
+        };
+        assert_eq!(integrated_loudness(&silent), None);
+
+        let empty = AudioData {
+            samples: vec![],
+            sample_rate: 48000,
+            channels: 2,
+        };
+        assert_eq!(integrated_loudness(&empty), None);
+    }
+
+    #[test]
+    fn test_normalization_gain() {
+        // 6 dB too loud -> halve amplitude (approximately)
+        let gain = normalization_gain(Some(-8.0), -14.0);
+        assert!((gain - 0.501).abs() < 0.01);
+
+        // Boost is capped
+        let gain = normalization_gain(Some(-60.0), -14.0);
+        let max = 10f32.powf(MAX_NORMALIZATION_BOOST_DB / 20.0);
+        assert!((gain - max).abs() < 1e-4);
+
+        assert_eq!(normalization_gain(None, -14.0), 1.0);
+    }
+}
@@ -2,11 +2,37 @@
 //!
 //! Manages active playbacks with thread-safe stop signaling and audio caching.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::mpsc::Sender;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 
 use super::cache::{AudioCache, CacheStats};
+use super::decode::CancelToken;
+use super::mic_duck::MicDuck;
+use super::playback::PlaybackSignal;
+use super::pool::{PoolStatus, WorkerPool};
+use super::queue::PlaybackQueue;
+use super::waveform::{PeakPyramid, WaveformData};
+use super::waveform_cache::WaveformCache;
+use super::AudioError;
+use crate::card_assets::FileStamp;
+
+/// Decode workers (decoding is CPU-bound, a few threads are enough)
+const DECODE_WORKERS: usize = 4;
+
+/// Playbacks running at once; further triggers wait for a free slot
+pub const MAX_CONCURRENT_PLAYBACKS: usize = 32;
+
+/// Start order of a playback (the counter in its ID)
+fn playback_number(playback_id: &str) -> u64 {
+    playback_id
+        .strip_prefix("playback_")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
 
 /// State of an active sound playback
 #[derive(Clone, Debug)]
@@ -31,14 +57,46 @@ impl SoundState {
 
 /// Manages audio playback state, active streams, and audio cache
 pub struct AudioManager {
-    /// Stop signals for active playbacks (send () to stop)
-    stop_senders: Arc<Mutex<HashMap<String, Sender<()>>>>,
+    /// Signals to the threads of active playbacks (send `Stop` to stop)
+    stop_senders: Arc<Mutex<HashMap<String, Sender<PlaybackSignal>>>>,
     /// Counter for generating unique playback IDs
     playback_counter: Arc<Mutex<u64>>,
     /// LRU cache for decoded audio data
     cache: Arc<Mutex<AudioCache>>,
+    /// Peak pyramids and waveforms per file
+    waveforms: Arc<Mutex<WaveformCache>>,
     /// Active sound_id -> SoundState mapping for policy enforcement
     active_sounds: Arc<Mutex<HashMap<String, SoundState>>>,
+    /// Measured integrated loudness (LUFS) per file path, `None` if silent
+    loudness: Arc<Mutex<HashMap<String, Option<f32>>>>,
+    /// Playbacks that should play once more when they reach their end
+    extensions: Arc<Mutex<HashSet<String>>>,
+    /// Cancellation tokens of playbacks that are still decoding
+    decode_tokens: Arc<Mutex<HashMap<String, CancelToken>>>,
+    /// Workers decoding sounds for playback and preloading
+    decode_pool: Arc<WorkerPool>,
+    /// Workers running playback streams until a sound ends
+    playback_pool: Arc<WorkerPool>,
+    /// Exclusive group -> sound IDs started in it
+    exclusive_groups: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    /// Sounds waiting to play one after another (queue mode)
+    queue: Arc<Mutex<PlaybackQueue>>,
+    /// Effective master volume (`f32` bits), read by every stream's callback:
+    /// the master volume setting times the duck gain
+    master_volume: Arc<AtomicU32>,
+    /// Master volume setting (`f32` bits)
+    master_setting: Arc<AtomicU32>,
+    /// Gain of held duck hotkeys (`f32` bits, 1.0 while not ducked)
+    duck_gain: Arc<AtomicU32>,
+    /// Routed microphone gain, lowered while sounds play (mic ducking)
+    mic_duck: Arc<MicDuck>,
+    /// Volume of each playback, owned by its playback thread (entries die
+    /// with the thread)
+    playback_volumes: Arc<Mutex<HashMap<String, Weak<AtomicU32>>>>,
+    /// Paused flag of each playback, owned by its playback thread like volumes
+    paused_flags: Arc<Mutex<HashMap<String, Weak<AtomicBool>>>>,
+    /// Playback ID -> triggers (Queue policy) waiting for it to end
+    follow_ups: Arc<Mutex<HashMap<String, u32>>>,
 }
 
 impl AudioManager {
@@ -47,7 +105,22 @@ impl AudioManager {
             stop_senders: Arc::new(Mutex::new(HashMap::new())),
             playback_counter: Arc::new(Mutex::new(0)),
             cache: Arc::new(Mutex::new(AudioCache::default())),
+            waveforms: Arc::new(Mutex::new(WaveformCache::default())),
             active_sounds: Arc::new(Mutex::new(HashMap::new())),
+            loudness: Arc::new(Mutex::new(HashMap::new())),
+            extensions: Arc::new(Mutex::new(HashSet::new())),
+            decode_tokens: Arc::new(Mutex::new(HashMap::new())),
+            decode_pool: Arc::new(WorkerPool::new("decode", DECODE_WORKERS)),
+            playback_pool: Arc::new(WorkerPool::new("playback", MAX_CONCURRENT_PLAYBACKS)),
+            exclusive_groups: Arc::new(Mutex::new(HashMap::new())),
+            queue: Arc::new(Mutex::new(PlaybackQueue::default())),
+            master_volume: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            master_setting: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            duck_gain: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            mic_duck: Arc::new(MicDuck::default()),
+            playback_volumes: Arc::new(Mutex::new(HashMap::new())),
+            paused_flags: Arc::new(Mutex::new(HashMap::new())),
+            follow_ups: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -57,7 +130,22 @@ impl AudioManager {
             stop_senders: Arc::new(Mutex::new(HashMap::new())),
             playback_counter: Arc::new(Mutex::new(0)),
             cache: Arc::new(Mutex::new(AudioCache::new(max_memory_mb))),
+            waveforms: Arc::new(Mutex::new(WaveformCache::default())),
             active_sounds: Arc::new(Mutex::new(HashMap::new())),
+            loudness: Arc::new(Mutex::new(HashMap::new())),
+            extensions: Arc::new(Mutex::new(HashSet::new())),
+            decode_tokens: Arc::new(Mutex::new(HashMap::new())),
+            decode_pool: Arc::new(WorkerPool::new("decode", DECODE_WORKERS)),
+            playback_pool: Arc::new(WorkerPool::new("playback", MAX_CONCURRENT_PLAYBACKS)),
+            exclusive_groups: Arc::new(Mutex::new(HashMap::new())),
+            queue: Arc::new(Mutex::new(PlaybackQueue::default())),
+            master_volume: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            master_setting: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            duck_gain: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            mic_duck: Arc::new(MicDuck::default()),
+            playback_volumes: Arc::new(Mutex::new(HashMap::new())),
+            paused_flags: Arc::new(Mutex::new(HashMap::new())),
+            follow_ups: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -66,9 +154,204 @@ impl AudioManager {
         self.cache.clone()
     }
 
-    /// Clear the audio cache
+    /// Clear the audio cache (and cached loudness measurements and waveforms)
     pub fn clear_cache(&self) {
         self.cache.lock().unwrap().clear();
+        self.loudness.lock().unwrap().clear();
+        self.waveforms.lock().unwrap().clear();
+    }
+
+    /// Keep waveform peaks in `dir` across restarts
+    pub fn set_waveform_dir(&self, dir: PathBuf) {
+        self.waveforms.lock().unwrap().set_dir(dir);
+    }
+
+    /// Waveform of a file with `num_peaks` peaks
+    ///
+    /// Answered from the waveform cache; a file is only decoded when it has
+    /// no peak pyramid yet.
+    pub fn waveform(&self, file_path: &str, num_peaks: usize) -> Result<WaveformData, AudioError> {
+        let Some(stamp) = FileStamp::of(Path::new(file_path)) else {
+            return Ok(self.peak_pyramid(file_path, None)?.peaks(num_peaks));
+        };
+        if let Some(waveform) = self
+            .waveforms
+            .lock()
+            .unwrap()
+            .response(file_path, num_peaks, stamp)
+        {
+            return Ok(waveform);
+        }
+        let waveform = self.peak_pyramid(file_path, Some(stamp))?.peaks(num_peaks);
+        self.waveforms.lock().unwrap().insert_response(
+            file_path,
+            num_peaks,
+            stamp,
+            waveform.clone(),
+        );
+        Ok(waveform)
+    }
+
+    /// Waveform of the part of a file between `start_ms` and `end_ms`
+    /// (zooming), at the scale of the whole file
+    pub fn waveform_range(
+        &self,
+        file_path: &str,
+        start_ms: u64,
+        end_ms: u64,
+        num_peaks: usize,
+    ) -> Result<WaveformData, AudioError> {
+        let stamp = FileStamp::of(Path::new(file_path));
+        Ok(self
+            .peak_pyramid(file_path, stamp)?
+            .peaks_in_range(start_ms, end_ms, num_peaks))
+    }
+
+    /// Peak pyramid of a file, decoding and scanning it if it isn't cached
+    /// (files that can't be stamped aren't cached)
+    fn peak_pyramid(
+        &self,
+        file_path: &str,
+        stamp: Option<FileStamp>,
+    ) -> Result<Arc<PeakPyramid>, AudioError> {
+        if let Some(stamp) = stamp {
+            if let Some(pyramid) = self.waveforms.lock().unwrap().pyramid(file_path, stamp) {
+                return Ok(pyramid);
+            }
+        }
+        // Decoded outside the waveform lock, other files stay answerable
+        let audio_data = AudioCache::load(&self.cache, file_path)?;
+        let pyramid = PeakPyramid::from_audio(&audio_data);
+        Ok(match stamp {
+            Some(stamp) => self
+                .waveforms
+                .lock()
+                .unwrap()
+                .insert_pyramid(file_path, stamp, pyramid),
+            None => Arc::new(pyramid),
+        })
+    }
+
+    /// Get a clone of the loudness cache Arc for use in playback threads
+    pub fn get_loudness_cache(&self) -> Arc<Mutex<HashMap<String, Option<f32>>>> {
+        self.loudness.clone()
+    }
+
+    /// Get the master volume shared with the playback streams
+    pub fn get_master_volume(&self) -> Arc<AtomicU32> {
+        self.master_volume.clone()
+    }
+
+    /// Current master volume setting (0.0-1.0, without any duck)
+    pub fn master_volume(&self) -> f32 {
+        f32::from_bits(self.master_setting.load(Ordering::Relaxed))
+    }
+
+    /// Change the master volume; playing sounds follow right away
+    pub fn set_master_volume(&self, volume: f32) {
+        self.master_setting
+            .store(volume.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+        self.update_master_level();
+    }
+
+    /// Lower every sound by `db` on top of the master volume (0 lifts the duck)
+    pub fn set_duck(&self, db: u32) {
+        let gain = 10f32.powf(-(db as f32) / 20.0);
+        self.duck_gain.store(gain.to_bits(), Ordering::Relaxed);
+        self.update_master_level();
+    }
+
+    /// Lower the routed microphone by `db` while sounds play (`None`: off)
+    pub fn set_mic_duck(&self, db: Option<f32>) {
+        self.mic_duck.set_depth(db);
+    }
+
+    /// Get the mic ducking state playbacks hold while they play
+    pub fn get_mic_duck(&self) -> Arc<MicDuck> {
+        self.mic_duck.clone()
+    }
+
+    /// Target gain of the routed microphone, read by its CABLE Input stream
+    pub fn mic_duck_gain(&self) -> Arc<AtomicU32> {
+        self.mic_duck.gain()
+    }
+
+    /// Store the effective master volume the streams read
+    fn update_master_level(&self) {
+        let setting = f32::from_bits(self.master_setting.load(Ordering::Relaxed));
+        let duck = f32::from_bits(self.duck_gain.load(Ordering::Relaxed));
+        self.master_volume
+            .store((setting * duck).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Make a playback's volume adjustable with `set_playback_volume`
+    pub fn register_playback_volume(&self, playback_id: &str, volume: &Arc<AtomicU32>) {
+        let mut volumes = self.playback_volumes.lock().unwrap();
+        volumes.retain(|_, v| v.strong_count() > 0);
+        volumes.insert(playback_id.to_string(), Arc::downgrade(volume));
+    }
+
+    /// Change the volume of a running playback; false if it already ended
+    pub fn set_playback_volume(&self, playback_id: &str, volume: f32) -> bool {
+        let volumes = self.playback_volumes.lock().unwrap();
+        match volumes.get(playback_id).and_then(Weak::upgrade) {
+            Some(state) => {
+                state.store(volume.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+                self.notify_playback(playback_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Make a playback pausable with `set_paused`
+    pub fn register_paused_flag(&self, playback_id: &str, paused: &Arc<AtomicBool>) {
+        let mut flags = self.paused_flags.lock().unwrap();
+        flags.retain(|_, f| f.strong_count() > 0);
+        flags.insert(playback_id.to_string(), Arc::downgrade(paused));
+    }
+
+    /// Pause or resume a running playback; false if it already ended
+    pub fn set_paused(&self, playback_id: &str, paused: bool) -> bool {
+        let flags = self.paused_flags.lock().unwrap();
+        match flags.get(playback_id).and_then(Weak::upgrade) {
+            Some(flag) => {
+                flag.store(paused, Ordering::Release);
+                self.notify_playback(playback_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Tell a playback's thread its volume or pause state changed
+    fn notify_playback(&self, playback_id: &str) {
+        if let Some(sender) = self.stop_senders.lock().unwrap().get(playback_id) {
+            let _ = sender.send(PlaybackSignal::Changed);
+        }
+    }
+
+    /// Tell every playback's thread to recompute its output levels (the
+    /// broadcast mute changed)
+    pub fn refresh_playbacks(&self) {
+        for sender in self.stop_senders.lock().unwrap().values() {
+            let _ = sender.send(PlaybackSignal::Changed);
+        }
+    }
+
+    /// Get the decode pool (decoding and preloading)
+    pub fn get_decode_pool(&self) -> Arc<WorkerPool> {
+        self.decode_pool.clone()
+    }
+
+    /// Get the playback pool (one job per playing sound)
+    pub fn get_playback_pool(&self) -> Arc<WorkerPool> {
+        self.playback_pool.clone()
+    }
+
+    /// Load of the decode and playback pools
+    pub fn pool_status(&self) -> (PoolStatus, PoolStatus) {
+        (self.decode_pool.status(), self.playback_pool.status())
     }
 
     /// Get cache statistics
@@ -84,39 +367,118 @@ impl AudioManager {
     }
 
     /// Register a stop sender for a playback
-    pub fn register_playback(&self, playback_id: String, sender: Sender<()>) {
+    pub fn register_playback(&self, playback_id: String, sender: Sender<PlaybackSignal>) {
         let mut senders = self.stop_senders.lock().unwrap();
         senders.insert(playback_id, sender);
     }
 
     /// Unregister a playback (called when playback completes)
-    #[allow(dead_code)]
     pub fn unregister_playback(&self, playback_id: &str) {
         let mut senders = self.stop_senders.lock().unwrap();
         senders.remove(playback_id);
     }
 
-    /// Stop all active playbacks
+    /// Stop all active playbacks (and abort their decodes), emptying the queue
     pub fn stop_all(&self) {
+        self.queue.lock().unwrap().reset();
+        self.follow_ups.lock().unwrap().clear();
+        for (_, token) in self.decode_tokens.lock().unwrap().drain() {
+            token.cancel();
+        }
         let mut senders = self.stop_senders.lock().unwrap();
         for (_, sender) in senders.drain() {
-            let _ = sender.send(()); // Ignore errors if thread already stopped
+            let _ = sender.send(PlaybackSignal::Stop); // Ignore errors if thread already stopped
         }
     }
 
-    /// Signal a specific playback to stop
+    /// Signal a specific playback to stop (aborts its decode if still running)
     pub fn signal_stop(&self, playback_id: &str) -> bool {
+        self.cancel_decode(playback_id);
         let mut senders = self.stop_senders.lock().unwrap();
         if let Some(sender) = senders.remove(playback_id) {
-            let _ = sender.send(());
+            let _ = sender.send(PlaybackSignal::Stop);
             true
         } else {
             false
         }
     }
 
+    /// Fade a playback out over `duration` and stop it (a restart replaced
+    /// it); false if it already ended
+    pub fn signal_fade_out(&self, playback_id: &str, duration: Duration) -> bool {
+        let mut senders = self.stop_senders.lock().unwrap();
+        match senders.remove(playback_id) {
+            Some(sender) => sender.send(PlaybackSignal::FadeOut(duration)).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Stop the oldest playbacks so a new one leaves at most `max` running
+    /// (voice stealing)
+    ///
+    /// `ending` is a playback that stops anyway (a restart's old playback)
+    /// and doesn't count. Returns the stolen playback IDs, oldest first.
+    pub fn steal_voices(&self, max: usize, ending: Option<&str>) -> Vec<String> {
+        let stolen: Vec<String> = {
+            let senders = self.stop_senders.lock().unwrap();
+            let mut running: Vec<&String> = senders
+                .keys()
+                .filter(|id| Some(id.as_str()) != ending)
+                .collect();
+            running.sort_by_key(|id| playback_number(id));
+            let excess = (running.len() + 1).saturating_sub(max.max(1));
+            running.into_iter().take(excess).cloned().collect()
+        };
+        for playback_id in &stolen {
+            self.signal_stop(playback_id);
+        }
+        stolen
+    }
+
+    /// Create the cancellation token for a playback's decode
+    pub fn register_decode(&self, playback_id: &str) -> CancelToken {
+        let token = CancelToken::new();
+        self.decode_tokens
+            .lock()
+            .unwrap()
+            .insert(playback_id.to_string(), token.clone());
+        token
+    }
+
+    /// Abort a playback's decode; returns false if it isn't decoding
+    pub fn cancel_decode(&self, playback_id: &str) -> bool {
+        match self.decode_tokens.lock().unwrap().remove(playback_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get a clone of the decode tokens Arc (decode jobs remove their token
+    /// once decoding is done)
+    pub fn get_decode_tokens(&self) -> Arc<Mutex<HashMap<String, CancelToken>>> {
+        self.decode_tokens.clone()
+    }
+
+    /// Number of playbacks currently decoding or playing
+    pub fn active_playback_count(&self) -> usize {
+        self.stop_senders.lock().unwrap().len()
+    }
+
+    /// Number of sounds currently audible (streams open)
+    pub fn playing_sound_count(&self) -> usize {
+        self.active_sounds
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|state| matches!(state, SoundState::Playing { .. }))
+            .count()
+    }
+
     /// Get a clone of the stop_senders Arc for use in spawned threads
-    pub fn get_stop_senders(&self) -> Arc<Mutex<HashMap<String, Sender<()>>>> {
+    pub fn get_stop_senders(&self) -> Arc<Mutex<HashMap<String, Sender<PlaybackSignal>>>> {
         self.stop_senders.clone()
     }
 
@@ -139,6 +501,74 @@ impl AudioManager {
             .insert(sound_id, SoundState::Decoding { playback_id });
     }
 
+    /// Request that a playback plays once more when it reaches its end.
+    ///
+    /// Repeated requests before the end don't stack, so mashing a hotkey
+    /// keeps a looped ambience alive without piling up repeats.
+    pub fn request_extension(&self, playback_id: &str) {
+        self.extensions
+            .lock()
+            .unwrap()
+            .insert(playback_id.to_string());
+    }
+
+    /// Queue `count` more plays of a playback's sound for after it ended
+    ///
+    /// Unlike extensions these stack: every trigger plays once more.
+    pub fn add_follow_ups(&self, playback_id: &str, count: u32) {
+        *self
+            .follow_ups
+            .lock()
+            .unwrap()
+            .entry(playback_id.to_string())
+            .or_default() += count;
+    }
+
+    /// Take the plays queued for after a playback (0 if none)
+    pub fn take_follow_ups(&self, playback_id: &str) -> u32 {
+        self.follow_ups
+            .lock()
+            .unwrap()
+            .remove(playback_id)
+            .unwrap_or(0)
+    }
+
+    /// Get a clone of the extension requests Arc for use in playback threads
+    pub fn get_extensions(&self) -> Arc<Mutex<HashSet<String>>> {
+        self.extensions.clone()
+    }
+
+    /// Make `sound_id` the only active sound of an exclusive group.
+    ///
+    /// Signals the other active sounds of the group to stop and returns
+    /// their playback IDs. Called when a grouped sound is triggered.
+    pub fn claim_exclusive_group(&self, group: &str, sound_id: &str) -> Vec<String> {
+        let mut groups = self.exclusive_groups.lock().unwrap();
+        let members = groups.entry(group.to_string()).or_default();
+
+        let others: Vec<String> = {
+            let active = self.active_sounds.lock().unwrap();
+            members
+                .iter()
+                .filter(|id| id.as_str() != sound_id)
+                .filter_map(|id| active.get(id).map(|s| s.playback_id().to_string()))
+                .collect()
+        };
+        members.clear();
+        members.insert(sound_id.to_string());
+        drop(groups);
+
+        for playback_id in &others {
+            self.signal_stop(playback_id);
+        }
+        others
+    }
+
+    /// Get a clone of the playback queue Arc
+    pub fn get_queue(&self) -> Arc<Mutex<PlaybackQueue>> {
+        self.queue.clone()
+    }
+
     /// Returns a thread-safe reference to the active sounds map.
     ///
     /// Used by playback threads to update sound state (Decoding -> Playing)
@@ -195,10 +625,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_active_counts() {
+        let manager = AudioManager::new();
+        let (tx1, _rx1) = mpsc::channel();
+        let (tx2, _rx2) = mpsc::channel();
+        manager.register_playback("playback_1".to_string(), tx1);
+        manager.register_playback("playback_2".to_string(), tx2);
+
+        manager.register_sound_decoding("a".to_string(), "playback_1".to_string());
+        manager.get_active_sounds().lock().unwrap().insert(
+            "b".to_string(),
+            SoundState::Playing {
+                playback_id: "playback_2".to_string(),
+                started_at: std::time::Instant::now(),
+            },
+        );
+
+        assert_eq!(manager.active_playback_count(), 2);
+        assert_eq!(manager.playing_sound_count(), 1);
+    }
+
     #[test]
     fn test_register_and_signal_stop() {
         let manager = AudioManager::new();
-        let (tx, rx) = mpsc::channel::<()>();
+        let (tx, rx) = mpsc::channel::<PlaybackSignal>();
 
         manager.register_playback("playback_1".to_string(), tx);
 
@@ -210,6 +661,19 @@ mod tests {
         assert!(!manager.signal_stop("playback_1"));
     }
 
+    #[test]
+    fn test_signal_fade_out_replaces_stop() {
+        let manager = AudioManager::new();
+        let (tx, rx) = mpsc::channel();
+        manager.register_playback("playback_1".to_string(), tx);
+
+        let fade = Duration::from_millis(200);
+        assert!(manager.signal_fade_out("playback_1", fade));
+        assert_eq!(rx.try_recv(), Ok(PlaybackSignal::FadeOut(fade)));
+        assert_eq!(manager.active_playback_count(), 0);
+        assert!(!manager.signal_fade_out("playback_1", fade));
+    }
+
     #[test]
     fn test_signal_stop_nonexistent() {
         let manager = AudioManager::new();
@@ -219,8 +683,8 @@ mod tests {
     #[test]
     fn test_stop_all() {
         let manager = AudioManager::new();
-        let (tx1, rx1) = mpsc::channel::<()>();
-        let (tx2, rx2) = mpsc::channel::<()>();
+        let (tx1, rx1) = mpsc::channel::<PlaybackSignal>();
+        let (tx2, rx2) = mpsc::channel::<PlaybackSignal>();
 
         manager.register_playback("playback_1".to_string(), tx1);
         manager.register_playback("playback_2".to_string(), tx2);
@@ -235,6 +699,51 @@ mod tests {
         assert_eq!(manager.stop_senders.lock().unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_stop_all_empties_queue() {
+        let manager = AudioManager::new();
+        manager
+            .get_queue()
+            .lock()
+            .unwrap()
+            .push("sound_1".to_string());
+
+        manager.stop_all();
+
+        assert!(manager
+            .get_queue()
+            .lock()
+            .unwrap()
+            .snapshot()
+            .pending
+            .is_empty());
+    }
+
+    #[test]
+    fn test_signal_stop_cancels_decode() {
+        let manager = AudioManager::new();
+        let (tx, _rx) = mpsc::channel::<PlaybackSignal>();
+        manager.register_playback("playback_1".to_string(), tx);
+        let token = manager.register_decode("playback_1");
+
+        assert!(manager.signal_stop("playback_1"));
+        assert!(token.is_cancelled());
+        assert!(!manager.cancel_decode("playback_1"));
+    }
+
+    #[test]
+    fn test_stop_all_cancels_decodes() {
+        let manager = AudioManager::new();
+        let token1 = manager.register_decode("playback_1");
+        let token2 = manager.register_decode("playback_2");
+
+        manager.stop_all();
+
+        assert!(token1.is_cancelled());
+        assert!(token2.is_cancelled());
+        assert!(manager.get_decode_tokens().lock().unwrap().is_empty());
+    }
+
     #[test]
     fn test_sound_state_decoding() {
         let manager = AudioManager::new();
@@ -275,6 +784,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_claim_exclusive_group() {
+        let manager = AudioManager::new();
+        let (tx1, rx1) = mpsc::channel::<PlaybackSignal>();
+        let (tx2, rx2) = mpsc::channel::<PlaybackSignal>();
+        manager.register_playback("playback_1".to_string(), tx1);
+        manager.register_playback("playback_2".to_string(), tx2);
+
+        manager.register_sound_decoding("bed_a".to_string(), "playback_1".to_string());
+        assert!(manager.claim_exclusive_group("Music", "bed_a").is_empty());
+
+        // Another sound of the group stops the first one
+        manager.register_sound_decoding("bed_b".to_string(), "playback_2".to_string());
+        assert_eq!(
+            manager.claim_exclusive_group("Music", "bed_b"),
+            ["playback_1"]
+        );
+        assert!(rx1.try_recv().is_ok());
+
+        // Other groups are unaffected
+        manager.register_sound_decoding("sfx".to_string(), "playback_3".to_string());
+        assert!(manager.claim_exclusive_group("Effects", "sfx").is_empty());
+        assert!(rx2.try_recv().is_err());
+    }
+
     #[test]
     fn test_sound_state_nonexistent() {
         let manager = AudioManager::new();
@@ -295,6 +829,120 @@ mod tests {
         assert_eq!(playing.playback_id(), "pb_2");
     }
 
+    #[test]
+    fn test_request_extension_does_not_stack() {
+        let manager = AudioManager::new();
+
+        manager.request_extension("playback_1");
+        manager.request_extension("playback_1");
+
+        let extensions = manager.get_extensions();
+        assert!(extensions.lock().unwrap().remove("playback_1"));
+        assert!(!extensions.lock().unwrap().remove("playback_1"));
+    }
+
+    #[test]
+    fn test_steal_voices_stops_oldest() {
+        let manager = AudioManager::new();
+        let mut receivers = Vec::new();
+        for _ in 0..11 {
+            let (tx, rx) = std::sync::mpsc::channel();
+            manager.register_playback(manager.next_playback_id(), tx);
+            receivers.push(rx);
+        }
+
+        // Room for one more within 10: playback_1 and playback_2 go
+        // (playback_10 sorts after playback_9)
+        let stolen = manager.steal_voices(10, None);
+        assert_eq!(stolen, vec!["playback_1", "playback_2"]);
+        assert!(receivers[0].try_recv().is_ok());
+        assert!(receivers[2].try_recv().is_err());
+        assert_eq!(manager.active_playback_count(), 9);
+
+        // A playback that ends anyway doesn't count
+        assert!(manager.steal_voices(9, Some("playback_3")).is_empty());
+        assert_eq!(manager.steal_voices(9, None), vec!["playback_3"]);
+    }
+
+    #[test]
+    fn test_follow_ups_stack_and_are_taken_once() {
+        let manager = AudioManager::new();
+        assert_eq!(manager.take_follow_ups("playback_1"), 0);
+
+        manager.add_follow_ups("playback_1", 1);
+        manager.add_follow_ups("playback_1", 1);
+        assert_eq!(manager.take_follow_ups("playback_1"), 2);
+        assert_eq!(manager.take_follow_ups("playback_1"), 0);
+
+        manager.add_follow_ups("playback_2", 3);
+        manager.stop_all();
+        assert_eq!(manager.take_follow_ups("playback_2"), 0);
+    }
+
+    #[test]
+    fn test_master_volume_is_shared_and_clamped() {
+        let manager = AudioManager::new();
+        assert_eq!(manager.master_volume(), 1.0);
+
+        let shared = manager.get_master_volume();
+        manager.set_master_volume(0.25);
+        assert_eq!(f32::from_bits(shared.load(Ordering::Relaxed)), 0.25);
+
+        manager.set_master_volume(3.0);
+        assert_eq!(manager.master_volume(), 1.0);
+    }
+
+    #[test]
+    fn test_duck_lowers_level_without_changing_setting() {
+        let manager = AudioManager::new();
+        let shared = manager.get_master_volume();
+        manager.set_master_volume(0.5);
+
+        manager.set_duck(20);
+        assert!((f32::from_bits(shared.load(Ordering::Relaxed)) - 0.05).abs() < 1e-6);
+        assert_eq!(manager.master_volume(), 0.5);
+
+        // Volume changes while ducked stay ducked
+        manager.set_master_volume(1.0);
+        assert!((f32::from_bits(shared.load(Ordering::Relaxed)) - 0.1).abs() < 1e-6);
+
+        manager.set_duck(0);
+        assert_eq!(f32::from_bits(shared.load(Ordering::Relaxed)), 1.0);
+    }
+
+    #[test]
+    fn test_playback_volume_follows_playback_lifetime() {
+        let manager = AudioManager::new();
+        let volume = Arc::new(AtomicU32::new(0.5f32.to_bits()));
+        manager.register_playback_volume("playback_1", &volume);
+
+        assert!(manager.set_playback_volume("playback_1", 1.5));
+        assert_eq!(f32::from_bits(volume.load(Ordering::Relaxed)), 1.0);
+        assert!(!manager.set_playback_volume("playback_2", 0.3));
+
+        // Once the playback thread drops its volume the playback is gone
+        drop(volume);
+        assert!(!manager.set_playback_volume("playback_1", 0.3));
+    }
+
+    #[test]
+    fn test_paused_flag_follows_playback_lifetime() {
+        let manager = AudioManager::new();
+        let paused = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        manager.register_paused_flag("playback_1", &paused);
+        manager.register_playback("playback_1".to_string(), tx);
+
+        assert!(manager.set_paused("playback_1", true));
+        assert!(paused.load(Ordering::Acquire));
+        // The playback's thread is woken to pause its streams
+        assert_eq!(rx.try_recv(), Ok(PlaybackSignal::Changed));
+        assert!(!manager.set_paused("playback_2", true));
+
+        drop(paused);
+        assert!(!manager.set_paused("playback_1", false));
+    }
+
     #[test]
     fn test_cache_clear() {
         let manager = AudioManager::new();
@@ -0,0 +1,106 @@
+//! Microphone ducking while sounds play
+//!
+//! Each playback holds a `Broadcasting` guard while its streams run. As long
+//! as one is held, the shared target gain drops to the duck depth; the
+//! routed microphone's CABLE Input stream reads it and moves toward it with
+//! its attack and release times.
+
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Sounds playing to the broadcast output and the microphone gain that
+/// results
+pub struct MicDuck {
+    /// Playbacks holding a `Broadcasting` guard
+    playing: AtomicUsize,
+    /// Gain while sounds play (`f32` bits, 1.0 = ducking off)
+    ducked_gain: AtomicU32,
+    /// Target gain of the routed microphone (`f32` bits)
+    gain: Arc<AtomicU32>,
+}
+
+impl Default for MicDuck {
+    fn default() -> Self {
+        Self {
+            playing: AtomicUsize::new(0),
+            ducked_gain: AtomicU32::new(1.0f32.to_bits()),
+            gain: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+        }
+    }
+}
+
+impl MicDuck {
+    /// Lower the microphone by `db` while sounds play (`None` turns it off)
+    pub fn set_depth(&self, db: Option<f32>) {
+        let gain = db.map_or(1.0, |db| 10f32.powf(-db.abs() / 20.0));
+        self.ducked_gain.store(gain.to_bits(), Ordering::Relaxed);
+        self.update();
+    }
+
+    /// Target gain shared with the microphone routing
+    pub fn gain(&self) -> Arc<AtomicU32> {
+        self.gain.clone()
+    }
+
+    /// Duck the microphone until the returned guard is dropped
+    pub fn hold(self: &Arc<Self>) -> Broadcasting {
+        self.playing.fetch_add(1, Ordering::AcqRel);
+        self.update();
+        Broadcasting(self.clone())
+    }
+
+    fn update(&self) {
+        let gain = if self.playing.load(Ordering::Acquire) > 0 {
+            self.ducked_gain.load(Ordering::Relaxed)
+        } else {
+            1.0f32.to_bits()
+        };
+        self.gain.store(gain, Ordering::Relaxed);
+    }
+}
+
+/// Held by a playback while it sends to the broadcast output
+pub struct Broadcasting(Arc<MicDuck>);
+
+impl Drop for Broadcasting {
+    fn drop(&mut self) {
+        self.0.playing.fetch_sub(1, Ordering::AcqRel);
+        self.0.update();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(duck: &MicDuck) -> f32 {
+        f32::from_bits(duck.gain().load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn test_ducks_while_any_sound_plays() {
+        let duck = Arc::new(MicDuck::default());
+        duck.set_depth(Some(20.0));
+        assert_eq!(target(&duck), 1.0);
+
+        let first = duck.hold();
+        let second = duck.hold();
+        assert!((target(&duck) - 0.1).abs() < 1e-6);
+        drop(first);
+        assert!((target(&duck) - 0.1).abs() < 1e-6);
+        drop(second);
+        assert_eq!(target(&duck), 1.0);
+    }
+
+    #[test]
+    fn test_depth_changes_apply_to_playing_sounds() {
+        let duck = Arc::new(MicDuck::default());
+        let _playing = duck.hold();
+        assert_eq!(target(&duck), 1.0);
+
+        duck.set_depth(Some(6.0));
+        assert!((target(&duck) - 0.501).abs() < 1e-3);
+        duck.set_depth(None);
+        assert_eq!(target(&duck), 1.0);
+    }
+}
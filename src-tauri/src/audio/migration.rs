@@ -0,0 +1,200 @@
+//! Migration of saved device IDs across restarts
+//!
+//! Older configs saved positions in the host's device list (`device_N`),
+//! which point at another device once Windows lists the devices in a
+//! different order. These are moved to the stable ID (see `DeviceId`) of
+//! their device: pinned IDs by the name they were pinned to, IDs without a
+//! pin (configs from before pinning) by enumeration order, once. Stable IDs
+//! whose endpoint is gone move to a device of the same name. Every saved ID
+//! is pinned to its device's name, and IDs that can't be matched are
+//! reported so the user can pick the device again instead of sounds
+//! silently going to the wrong output.
+
+use std::collections::HashMap;
+
+use super::{AudioDevice, DeviceId};
+
+/// Migrates saved device IDs against the current device list
+///
+/// Run every saved ID through `migrate`, then store `finish`'s pins.
+pub struct DeviceIdMigrator<'a> {
+    /// Pins saved with the IDs (device ID -> device name)
+    saved_names: &'a HashMap<DeviceId, String>,
+    devices: &'a [AudioDevice],
+    /// Pins of the migrated IDs
+    names: HashMap<DeviceId, String>,
+    /// Unpinned IDs that don't match a listed device
+    unresolved: Vec<DeviceId>,
+}
+
+impl<'a> DeviceIdMigrator<'a> {
+    pub fn new(saved_names: &'a HashMap<DeviceId, String>, devices: &'a [AudioDevice]) -> Self {
+        Self {
+            saved_names,
+            devices,
+            names: HashMap::new(),
+            unresolved: Vec::new(),
+        }
+    }
+
+    /// Migrate one saved ID; true if it changed
+    pub fn migrate(&mut self, device_id: &mut Option<DeviceId>) -> bool {
+        let Some(saved) = device_id.as_ref().filter(|id| !id.is_system_default()) else {
+            return false;
+        };
+
+        let name = match (self.saved_names.get(saved), saved.index()) {
+            (Some(name), _) => name.as_str(),
+            (None, Err(_)) => saved.name().unwrap_or_default(),
+            // Unpinned position: trust the enumeration order this once
+            (None, Ok(index)) => match self.devices.get(index) {
+                Some(device) => device.name.as_str(),
+                None => {
+                    tracing::warn!("Saved device {} matches no output device", saved);
+                    if !self.unresolved.contains(saved) {
+                        self.unresolved.push(saved.clone());
+                    }
+                    return false;
+                }
+            },
+        };
+
+        if self.devices.iter().any(|d| &d.id == saved) {
+            self.names.insert(saved.clone(), name.to_string());
+            return false;
+        }
+        match self.devices.iter().find(|d| d.name == name) {
+            Some(device) => {
+                tracing::info!("Device '{}' moved from {} to {}", name, saved, device.id);
+                self.names.insert(device.id.clone(), name.to_string());
+                *device_id = Some(device.id.clone());
+                true
+            }
+            // Unplugged: kept for when it's back, the fallback covers it
+            // meanwhile
+            None => {
+                self.names.insert(saved.clone(), name.to_string());
+                false
+            }
+        }
+    }
+
+    /// Pins to save with the migrated IDs, and the IDs that couldn't be matched
+    pub fn finish(self) -> (HashMap<DeviceId, String>, Vec<DeviceId>) {
+        (self.names, self.unresolved)
+    }
+}
+
+/// Pin a device ID the user just selected to its device's name
+pub fn pin_device_name(
+    names: &mut HashMap<DeviceId, String>,
+    device_id: &DeviceId,
+    devices: &[AudioDevice],
+) {
+    if let Some(device) = devices.iter().find(|d| &d.id == device_id) {
+        names.insert(device_id.clone(), device.name.clone());
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable(endpoint: &str, name: &str) -> DeviceId {
+        DeviceId::stable("wasapi", endpoint, name)
+    }
+
+    fn device(endpoint: &str, name: &str) -> AudioDevice {
+        AudioDevice {
+            id: stable(endpoint, name),
+            name: name.to_string(),
+            is_default: false,
+        }
+    }
+
+    #[test]
+    fn test_unpinned_ids_are_migrated_by_enumeration_order() {
+        let devices = [device("{a}", "Speakers"), device("{b}", "CABLE Input")];
+        let saved = HashMap::new();
+        let mut migrator = DeviceIdMigrator::new(&saved, &devices);
+
+        let mut monitor = Some(DeviceId::from_index(0));
+        let mut broadcast = Some(DeviceId::from_index(4));
+        assert!(migrator.migrate(&mut monitor));
+        assert!(!migrator.migrate(&mut broadcast));
+        assert!(!migrator.migrate(&mut None));
+        assert_eq!(monitor, Some(stable("{a}", "Speakers")));
+
+        let (names, unresolved) = migrator.finish();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[&stable("{a}", "Speakers")], "Speakers");
+        assert_eq!(unresolved, vec![DeviceId::from_index(4)]);
+        // Left for the user to re-select
+        assert_eq!(broadcast, Some(DeviceId::from_index(4)));
+    }
+
+    #[test]
+    fn test_pinned_ids_follow_their_device() {
+        // A headset was listed first since the last run
+        let devices = [
+            device("{h}", "Headset"),
+            device("{a}", "Speakers"),
+            device("{b}", "CABLE Input"),
+        ];
+        let saved = HashMap::from([
+            (DeviceId::from_index(0), "Speakers".to_string()),
+            (DeviceId::from_index(1), "CABLE Input".to_string()),
+            (DeviceId::from_index(3), "USB Interface".to_string()),
+        ]);
+        let mut migrator = DeviceIdMigrator::new(&saved, &devices);
+
+        let mut monitor = Some(DeviceId::from_index(0));
+        let mut broadcast = Some(DeviceId::from_index(1));
+        let mut unplugged = Some(DeviceId::from_index(3));
+        assert!(migrator.migrate(&mut monitor));
+        assert!(migrator.migrate(&mut broadcast));
+        assert!(!migrator.migrate(&mut unplugged));
+        assert_eq!(monitor, Some(stable("{a}", "Speakers")));
+        assert_eq!(broadcast, Some(stable("{b}", "CABLE Input")));
+
+        // The same saved ID elsewhere (e.g. a profile) moves the same way
+        let mut profile_monitor = Some(DeviceId::from_index(0));
+        assert!(migrator.migrate(&mut profile_monitor));
+        assert_eq!(profile_monitor, monitor);
+
+        let (names, unresolved) = migrator.finish();
+        assert_eq!(names[&stable("{a}", "Speakers")], "Speakers");
+        assert_eq!(names[&stable("{b}", "CABLE Input")], "CABLE Input");
+        assert_eq!(names[&DeviceId::from_index(3)], "USB Interface");
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_stable_ids_follow_their_name() {
+        // Speakers were reinstalled under a new endpoint ID
+        let devices = [device("{h}", "Headset"), device("{new}", "Speakers")];
+        let saved = HashMap::new();
+        let mut migrator = DeviceIdMigrator::new(&saved, &devices);
+
+        let mut monitor = Some(stable("{old}", "Speakers"));
+        let mut broadcast = Some(stable("{h}", "Headset"));
+        let mut unplugged = Some(stable("{usb}", "USB Interface"));
+        let mut system_default = Some(DeviceId::system_default());
+        assert!(migrator.migrate(&mut monitor));
+        assert!(!migrator.migrate(&mut broadcast));
+        assert!(!migrator.migrate(&mut unplugged));
+        assert!(!migrator.migrate(&mut system_default));
+        assert_eq!(monitor, Some(stable("{new}", "Speakers")));
+        assert_eq!(unplugged, Some(stable("{usb}", "USB Interface")));
+
+        let (names, unresolved) = migrator.finish();
+        assert_eq!(names[&stable("{h}", "Headset")], "Headset");
+        assert_eq!(names[&stable("{usb}", "USB Interface")], "USB Interface");
+        assert!(!names.contains_key(&DeviceId::system_default()));
+        assert!(unresolved.is_empty());
+    }
+}
@@ -5,21 +5,96 @@
 mod cache;
 mod decode;
 mod device;
+mod diagnostics;
+mod downmix;
+mod envelope;
 mod error;
+mod fallback;
+mod faults;
+mod fft;
+mod gain;
+mod latency;
+mod limiter;
+mod loudness;
 mod manager;
+mod mic_duck;
+mod migration;
+mod onsets;
+mod opus;
+mod pitch;
 mod playback;
+mod pool;
+mod queue;
+mod render;
+mod resample;
+mod routing;
+mod spectrogram;
+mod spectrum;
+mod stream;
+mod stretch;
+mod tone;
 mod waveform;
+mod waveform_cache;
 
-pub use cache::CacheStats;
-pub use device::enumerate_devices;
+pub use cache::{AudioCache, CacheStats};
+pub use decode::{decode_audio_file, read_metadata, CancelToken, FileMetadata};
+pub use device::{
+    enumerate_devices, find_output_device, follow_system_default, host_name, known_devices,
+    output_device_ids, reconcile_device_id, resolve_device_index,
+};
+pub use diagnostics::{recent_stream_errors, StreamErrorRecord};
+pub use envelope::PlaybackEnvelope;
 pub use error::AudioError;
-pub use manager::{AudioManager, SoundState};
-pub use playback::create_playback_stream;
+pub use fallback::{
+    active_fallbacks, check_saved_devices, default_device_index, record_resolution, resolve_device,
+    resolve_missing_device, DeviceFallback, DeviceRole,
+};
+pub use faults::{arm_fault, clear_faults, take_fault, Fault};
+pub(crate) use fft::fft_in_place;
+pub use gain::{
+    from_db, is_silent, sample_peak, simulate_chain, to_db, verdict, GainChain, GainVerdict,
+};
+pub use latency::{last_latency_measurement, measure_output_latency, LatencyReport};
+pub use limiter::Limiter;
+pub use loudness::{integrated_loudness, normalization_gain};
+pub use manager::{AudioManager, SoundState, MAX_CONCURRENT_PLAYBACKS};
+pub use migration::{pin_device_name, DeviceIdMigrator};
+pub use onsets::detect_onsets;
+pub use opus::encode_ogg_opus;
+pub use pitch::{is_neutral, shift_pitch_and_speed, MAX_PITCH_SEMITONES, MAX_SPEED, MIN_SPEED};
+pub use playback::{
+    create_playback_stream, output_sample_rate, volume_with_gain, MixRenderer, PlaybackCursor,
+    PlaybackSignal, PlaybackSource,
+};
+pub use pool::PoolStatus;
+pub use queue::{PlaybackQueue, QueueSnapshot};
+pub use render::{render_region, write_wav, WavWriter};
+pub use resample::resample;
+pub use routing::{OutputRouting, MIN_SEGMENT_MS};
+pub use spectrogram::{
+    generate_spectrogram, SpectrogramData, MAX_SPECTROGRAM_HEIGHT, MAX_SPECTROGRAM_WIDTH,
+};
+pub use spectrum::{start_analyzer, stop_analyzer};
+pub use stream::{stream_audio_file, streaming_info, StreamInfo, StreamRing};
+pub use stretch::fit_to_duration;
+pub use tone::{test_tone, TEST_TONE_DURATION};
 pub use waveform::{generate_peaks, WaveformData};
 
 use serde::{Deserialize, Serialize};
 
+/// ID of the pseudo-device following the system default output
+const SYSTEM_DEFAULT_ID: &str = "system_default";
+
 /// Type-safe device identifier
+///
+/// Output devices are identified by host, a stable key and name
+/// (`wasapi:{endpoint ID}:Speakers`), so an ID keeps pointing at its device
+/// when the host lists devices in a different order. The key is the
+/// endpoint ID where the host has one, else the device's position among
+/// devices of the same name. Older IDs are positions in the host's device
+/// list (`device_N`); they still resolve by position until migrated.
+/// `system_default` is a pseudo-device following the system's default
+/// output.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct DeviceId(String);
@@ -30,6 +105,16 @@ impl DeviceId {
         Self(format!("device_{}", index))
     }
 
+    /// The pseudo-device following the system default output
+    pub fn system_default() -> Self {
+        Self(SYSTEM_DEFAULT_ID.to_string())
+    }
+
+    /// Create a stable device ID
+    pub fn stable(host: &str, key: &str, name: &str) -> Self {
+        Self(format!("{}:{}:{}", host.to_lowercase(), key, name))
+    }
+
     /// Parse the device index from the ID
     pub fn index(&self) -> Result<usize, AudioError> {
         self.0
@@ -38,6 +123,25 @@ impl DeviceId {
             .ok_or_else(|| AudioError::InvalidDeviceId(self.0.clone()))
     }
 
+    /// Whether this is a position in the device list rather than a stable ID
+    pub fn is_index(&self) -> bool {
+        self.index().is_ok()
+    }
+
+    /// Whether this follows the system default output
+    pub fn is_system_default(&self) -> bool {
+        self.0 == SYSTEM_DEFAULT_ID
+    }
+
+    /// Device name of a stable ID
+    pub fn name(&self) -> Option<&str> {
+        let mut parts = self.0.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(_), Some(_), Some(name)) => Some(name),
+            _ => None,
+        }
+    }
+
     /// Get the raw string ID
     pub fn as_str(&self) -> &str {
         &self.0
@@ -121,6 +225,30 @@ mod tests {
         assert!(id.index().is_err());
     }
 
+    #[test]
+    fn test_stable_device_id() {
+        let id = DeviceId::stable("WASAPI", "{0.0.0.00000000}.{1234}", "Speakers: Realtek");
+        assert_eq!(
+            id.as_str(),
+            "wasapi:{0.0.0.00000000}.{1234}:Speakers: Realtek"
+        );
+        assert_eq!(id.name(), Some("Speakers: Realtek"));
+        assert!(!id.is_index());
+        assert!(id.index().is_err());
+
+        assert!(DeviceId::from_index(2).is_index());
+        assert_eq!(DeviceId::from_index(2).name(), None);
+    }
+
+    #[test]
+    fn test_system_default_device_id() {
+        let id = DeviceId::system_default();
+        assert!(id.is_system_default());
+        assert!(!id.is_index());
+        assert_eq!(id.name(), None);
+        assert!(!DeviceId::from_index(0).is_system_default());
+    }
+
     #[test]
     fn test_device_id_display() {
         let id = DeviceId::from_index(7);
@@ -0,0 +1,172 @@
+//! Transient onset detection
+//!
+//! Finds the start of hits and words with spectral flux: the summed increase
+//! of FFT magnitudes from one frame to the next peaks where new energy
+//! appears. Peaks above a moving-average threshold become onsets, which the
+//! trim editor offers as snap points.
+
+use std::time::Instant;
+use tracing::debug;
+
+use super::fft::{hann_window, magnitude_spectrum};
+use super::AudioData;
+
+/// Analysis frame size and hop (samples at the file's rate)
+const FRAME_SIZE: usize = 1024;
+const HOP_SIZE: usize = 256;
+
+/// Frames on each side averaged for the adaptive threshold
+const THRESHOLD_RADIUS: usize = 8;
+
+/// How far flux must exceed the local average to count as an onset
+const THRESHOLD_RATIO: f32 = 1.5;
+
+/// Minimum flux relative to the loudest frame (ignores noise-floor flicker)
+const MIN_RELATIVE_FLUX: f32 = 0.05;
+
+/// Minimum distance between two onsets
+const MIN_ONSET_GAP_MS: u64 = 50;
+
+/// Onset timestamps in milliseconds from the start of the file, ascending
+pub fn detect_onsets(audio_data: &AudioData) -> Vec<u64> {
+    let start = Instant::now();
+    let flux = spectral_flux(audio_data);
+    let hop_ms = HOP_SIZE as f64 * 1000.0 / audio_data.sample_rate.max(1) as f64;
+    let onsets: Vec<u64> = pick_peaks(&flux, hop_ms);
+
+    debug!(
+        duration_ms = start.elapsed().as_millis() as u64,
+        frames = flux.len(),
+        onsets = onsets.len(),
+        "Onset detection complete"
+    );
+    onsets
+}
+
+/// Half-wave rectified spectral flux per hop (mono mixdown)
+///
+/// Entry `i` describes the frame starting at `i * HOP_SIZE`.
+fn spectral_flux(audio_data: &AudioData) -> Vec<f32> {
+    let channels = audio_data.channels.max(1) as usize;
+    let mono: Vec<f32> = audio_data
+        .samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+    if mono.len() < FRAME_SIZE {
+        return Vec::new();
+    }
+
+    let window = hann_window(FRAME_SIZE);
+    let mut frame = vec![0.0f32; FRAME_SIZE];
+    let mut previous: Option<Vec<f32>> = None;
+    let mut flux = Vec::with_capacity(mono.len() / HOP_SIZE);
+
+    for frame_start in (0..=mono.len() - FRAME_SIZE).step_by(HOP_SIZE) {
+        for (i, slot) in frame.iter_mut().enumerate() {
+            *slot = mono[frame_start + i] * window[i];
+        }
+        let mags = magnitude_spectrum(&frame);
+
+        let value = previous.as_ref().map_or(0.0, |prev| {
+            mags.iter()
+                .zip(prev)
+                .map(|(m, p)| (m - p).max(0.0))
+                .sum::<f32>()
+        });
+        flux.push(value);
+        previous = Some(mags);
+    }
+    flux
+}
+
+/// Local maxima of `flux` above an adaptive threshold, as timestamps
+fn pick_peaks(flux: &[f32], hop_ms: f64) -> Vec<u64> {
+    let max_flux = flux.iter().cloned().fold(0.0f32, f32::max);
+    if max_flux <= 0.0 {
+        return Vec::new();
+    }
+    let floor = max_flux * MIN_RELATIVE_FLUX;
+
+    let mut onsets: Vec<u64> = Vec::new();
+    for i in 1..flux.len().saturating_sub(1) {
+        let value = flux[i];
+        if value < floor || value < flux[i - 1] || value <= flux[i + 1] {
+            continue;
+        }
+
+        let from = i.saturating_sub(THRESHOLD_RADIUS);
+        let to = (i + THRESHOLD_RADIUS + 1).min(flux.len());
+        let average = flux[from..to].iter().sum::<f32>() / (to - from) as f32;
+        if value < average * THRESHOLD_RATIO {
+            continue;
+        }
+
+        // The flux frame spans the transient; its start is where the hit begins
+        let time_ms = (i as f64 * hop_ms) as u64;
+        if onsets
+            .last()
+            .is_some_and(|last| time_ms < last + MIN_ONSET_GAP_MS)
+        {
+            continue;
+        }
+        onsets.push(time_ms);
+    }
+    onsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    /// Silence with short decaying tone bursts at the given times
+    fn bursts(times_ms: &[u64], sample_rate: u32, total_ms: u64) -> AudioData {
+        let total = (total_ms * sample_rate as u64 / 1000) as usize;
+        let mut samples = vec![0.0f32; total];
+        for &time_ms in times_ms {
+            let start = (time_ms * sample_rate as u64 / 1000) as usize;
+            for i in 0..(sample_rate as usize / 10) {
+                if let Some(sample) = samples.get_mut(start + i) {
+                    let t = i as f32 / sample_rate as f32;
+                    *sample = (2.0 * PI * 440.0 * t).sin() * (-t * 40.0).exp();
+                }
+            }
+        }
+        AudioData {
+            samples,
+            sample_rate,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_detects_bursts() {
+        let expected = [200, 700, 1300];
+        let onsets = detect_onsets(&bursts(&expected, 44_100, 2000));
+
+        assert_eq!(onsets.len(), expected.len(), "onsets: {:?}", onsets);
+        for (onset, expected) in onsets.iter().zip(expected) {
+            // Within one analysis frame of the true start
+            assert!(
+                onset.abs_diff(expected) <= 25,
+                "onset {} vs {}",
+                onset,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_silence_and_short_audio() {
+        assert!(detect_onsets(&bursts(&[], 44_100, 1000)).is_empty());
+        assert!(detect_onsets(&bursts(&[0], 44_100, 10)).is_empty());
+    }
+
+    #[test]
+    fn test_pick_peaks_min_gap() {
+        let flux = [0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        // 10 ms hops: the second peak is only 20 ms after the first
+        assert_eq!(pick_peaks(&flux, 10.0), vec![10, 80]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Opus decoding and encoding
+//!
+//! Symphonia demuxes Ogg Opus but ships no Opus decoder. `OpusDecoder`
+//! plugs libopus (via the `opus` crate) into Symphonia's codec registry, so
+//! Opus goes through the same decode loop as every other format.
+//! `encode_ogg_opus` goes the other way for recorded clips, writing the Ogg
+//! pages itself since Symphonia has no muxers.
+
+use std::sync::Mutex;
+
+use symphonia::core::audio::{
+    AsAudioBufferRef, AudioBuffer, AudioBufferRef, Channels, Signal, SignalSpec,
+};
+use symphonia::core::codecs::{
+    CodecDescriptor, CodecParameters, Decoder, DecoderOptions, FinalizeResult, CODEC_TYPE_OPUS,
+};
+use symphonia::core::errors::{decode_error, unsupported_error, Result};
+use symphonia::core::formats::Packet;
+use symphonia::core::support_codec;
+use tracing::warn;
+
+use super::AudioData;
+
+/// Opus always decodes at 48 kHz
+const OPUS_RATE: u32 = 48_000;
+
+/// Frames in the longest Opus packet (120 ms)
+const MAX_PACKET_FRAMES: usize = 5_760;
+
+/// Frames per encoded packet (20 ms)
+const ENCODE_FRAMES: usize = 960;
+
+/// Largest packet libopus is asked to produce
+const MAX_PACKET_BYTES: usize = 4_000;
+
+/// Audio packets are flushed to a page once they reach this size
+const PAGE_TARGET_BYTES: usize = 4_096;
+
+/// Ogg page header flags
+const PAGE_FIRST: u8 = 0x02;
+const PAGE_LAST: u8 = 0x04;
+
+/// Ogg stream serial number (files hold a single stream)
+const STREAM_SERIAL: u32 = 0x536F_6E44;
+
+/// Symphonia decoder for mono and stereo Opus streams
+pub struct OpusDecoder {
+    params: CodecParameters,
+    /// libopus state is Send but not Sync, Symphonia decoders must be both
+    decoder: Mutex<opus::Decoder>,
+    channels: usize,
+    /// Leading frames still to drop (the stream's pre-skip)
+    skip: usize,
+    /// Interleaved output of the last packet
+    pcm: Vec<f32>,
+    buf: AudioBuffer<f32>,
+}
+
+impl Decoder for OpusDecoder {
+    fn try_new(params: &CodecParameters, _options: &DecoderOptions) -> Result<Self> {
+        let (opus_channels, layout) = match params.channels.map(|c| c.count()) {
+            Some(1) => (opus::Channels::Mono, Channels::FRONT_LEFT),
+            Some(2) => (
+                opus::Channels::Stereo,
+                Channels::FRONT_LEFT | Channels::FRONT_RIGHT,
+            ),
+            // Surround Opus needs libopus' multistream decoder
+            _ => return unsupported_error("opus: only mono and stereo are supported"),
+        };
+        let decoder = opus::Decoder::new(OPUS_RATE, opus_channels).map_err(|e| {
+            warn!("Failed to create Opus decoder: {}", e);
+            symphonia::core::errors::Error::Unsupported("opus: decoder creation failed")
+        })?;
+        let channels = layout.count();
+
+        Ok(Self {
+            params: params.clone(),
+            decoder: Mutex::new(decoder),
+            channels,
+            skip: params.delay.unwrap_or(0) as usize,
+            pcm: vec![0.0; MAX_PACKET_FRAMES * channels],
+            buf: AudioBuffer::new(MAX_PACKET_FRAMES as u64, SignalSpec::new(OPUS_RATE, layout)),
+        })
+    }
+
+    fn supported_codecs() -> &'static [CodecDescriptor] {
+        &[support_codec!(CODEC_TYPE_OPUS, "opus", "Opus")]
+    }
+
+    fn reset(&mut self) {
+        let decoder = self.decoder.get_mut().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = decoder.reset_state() {
+            warn!("Failed to reset Opus decoder: {}", e);
+        }
+    }
+
+    fn codec_params(&self) -> &CodecParameters {
+        &self.params
+    }
+
+    fn decode(&mut self, packet: &Packet) -> Result<AudioBufferRef<'_>> {
+        let decoder = self.decoder.get_mut().unwrap_or_else(|e| e.into_inner());
+        let frames = match decoder.decode_float(packet.buf(), &mut self.pcm, false) {
+            Ok(frames) => frames,
+            Err(e) => {
+                warn!("Opus packet decode failed: {}", e);
+                return decode_error("opus: invalid packet");
+            }
+        };
+
+        let skip = self.skip.min(frames);
+        self.skip -= skip;
+
+        self.buf.clear();
+        self.buf.render_reserved(Some(frames - skip));
+        for channel in 0..self.channels {
+            let pcm = self.pcm[skip * self.channels..frames * self.channels]
+                .iter()
+                .skip(channel)
+                .step_by(self.channels);
+            for (out, sample) in self.buf.chan_mut(channel).iter_mut().zip(pcm) {
+                *out = *sample;
+            }
+        }
+        Ok(self.buf.as_audio_buffer_ref())
+    }
+
+    fn finalize(&mut self) -> FinalizeResult {
+        FinalizeResult::default()
+    }
+
+    fn last_decoded(&self) -> AudioBufferRef<'_> {
+        self.buf.as_audio_buffer_ref()
+    }
+}
+
+/// Encode 48 kHz mono or stereo audio as an Ogg Opus file
+pub fn encode_ogg_opus(audio: &AudioData) -> std::result::Result<Vec<u8>, String> {
+    if audio.sample_rate != OPUS_RATE {
+        return Err(format!(
+            "Opus encoding needs {} Hz audio, got {} Hz",
+            OPUS_RATE, audio.sample_rate
+        ));
+    }
+    let opus_channels = match audio.channels {
+        1 => opus::Channels::Mono,
+        2 => opus::Channels::Stereo,
+        n => {
+            return Err(format!(
+                "Opus encoding supports mono and stereo, not {} channels",
+                n
+            ))
+        }
+    };
+    let mut encoder = opus::Encoder::new(OPUS_RATE, opus_channels, opus::Application::Audio)
+        .map_err(|e| format!("Failed to create Opus encoder: {}", e))?;
+    let pre_skip = encoder
+        .get_lookahead()
+        .map_err(|e| format!("Failed to query Opus encoder: {}", e))?
+        .clamp(0, u16::MAX as i32) as usize;
+
+    let channels = audio.channels as usize;
+    let frames = audio.samples.len() / channels;
+    let mut ogg = OggWriter::default();
+    ogg.write_page(
+        &[opus_head(audio.channels as u8, pre_skip as u16)],
+        0,
+        PAGE_FIRST,
+    );
+    ogg.write_page(&[opus_tags()], 0, 0);
+
+    // The encoder lags by its lookahead, so encode that much silence past
+    // the end to get the last samples out
+    let packet_count = (frames + pre_skip).div_ceil(ENCODE_FRAMES).max(1);
+    let mut pcm = vec![0.0f32; ENCODE_FRAMES * channels];
+    let mut encoded = vec![0u8; MAX_PACKET_BYTES];
+    let mut packets: Vec<Vec<u8>> = Vec::new();
+    let mut segments = 0;
+    for index in 0..packet_count {
+        let start = (index * pcm.len()).min(audio.samples.len());
+        let end = (start + pcm.len()).min(audio.samples.len());
+        pcm.fill(0.0);
+        pcm[..end - start].copy_from_slice(&audio.samples[start..end]);
+        let len = encoder
+            .encode_float(&pcm, &mut encoded)
+            .map_err(|e| format!("Opus encoding failed: {}", e))?;
+
+        // A page holds at most 255 lacing values
+        let packet_segments = len / 255 + 1;
+        if segments + packet_segments > 255 {
+            ogg.write_page(&packets, (index * ENCODE_FRAMES) as u64, 0);
+            packets.clear();
+            segments = 0;
+        }
+        packets.push(encoded[..len].to_vec());
+        segments += packet_segments;
+
+        let is_last = index + 1 == packet_count;
+        let page_bytes: usize = packets.iter().map(Vec::len).sum();
+        if is_last {
+            // The last granule position marks where the audio ends, so
+            // players drop the padding
+            ogg.write_page(&packets, (pre_skip + frames) as u64, PAGE_LAST);
+        } else if page_bytes >= PAGE_TARGET_BYTES {
+            ogg.write_page(&packets, ((index + 1) * ENCODE_FRAMES) as u64, 0);
+            packets.clear();
+            segments = 0;
+        }
+    }
+    Ok(ogg.bytes)
+}
+
+/// Identification header (RFC 7845, section 5.1)
+fn opus_head(channels: u8, pre_skip: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(channels);
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&OPUS_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // mono/stereo channel mapping
+    head
+}
+
+/// Comment header without comments
+fn opus_tags() -> Vec<u8> {
+    let vendor = b"SonicDeck";
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor);
+    tags.extend_from_slice(&0u32.to_le_bytes());
+    tags
+}
+
+/// Writes the pages of a single Ogg stream
+#[derive(Default)]
+struct OggWriter {
+    bytes: Vec<u8>,
+    sequence: u32,
+}
+
+impl OggWriter {
+    /// Append a page holding `packets`, which must fit in 255 lacing values
+    fn write_page(&mut self, packets: &[Vec<u8>], granule_position: u64, flags: u8) {
+        let start = self.bytes.len();
+        let mut lacing = Vec::new();
+        for packet in packets {
+            lacing.extend(std::iter::repeat_n(255u8, packet.len() / 255));
+            lacing.push((packet.len() % 255) as u8);
+        }
+
+        self.bytes.extend_from_slice(b"OggS");
+        self.bytes.push(0); // version
+        self.bytes.push(flags);
+        self.bytes
+            .extend_from_slice(&granule_position.to_le_bytes());
+        self.bytes.extend_from_slice(&STREAM_SERIAL.to_le_bytes());
+        self.bytes.extend_from_slice(&self.sequence.to_le_bytes());
+        self.bytes.extend_from_slice(&[0; 4]); // checksum, filled in below
+        self.bytes.push(lacing.len() as u8);
+        self.bytes.extend_from_slice(&lacing);
+        for packet in packets {
+            self.bytes.extend_from_slice(packet);
+        }
+
+        let checksum = ogg_crc(&self.bytes[start..]);
+        self.bytes[start + 22..start + 26].copy_from_slice(&checksum.to_le_bytes());
+        self.sequence += 1;
+    }
+}
+
+/// Ogg page checksum (CRC-32, polynomial 0x04C11DB7, no reflection)
+fn ogg_crc(data: &[u8]) -> u32 {
+    let mut crc = 0u32;
+    for &byte in data {
+        crc ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(channels: u16, frames: usize) -> AudioData {
+        let samples = (0..frames * channels as usize)
+            .map(|i| ((i / channels as usize) as f32 * 0.05).sin() * 0.5)
+            .collect();
+        AudioData {
+            samples,
+            sample_rate: OPUS_RATE,
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_encode_rejects_unsupported_audio() {
+        let mut audio = tone(2, 100);
+        audio.sample_rate = 44_100;
+        assert!(encode_ogg_opus(&audio).is_err());
+        assert!(encode_ogg_opus(&tone(6, 100)).is_err());
+    }
+
+    #[test]
+    fn test_encoded_file_decodes_to_same_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.ogg");
+        let frames = 48_000 / 2 + 123;
+        std::fs::write(&path, encode_ogg_opus(&tone(2, frames)).unwrap()).unwrap();
+
+        let decoded = crate::audio::decode_audio_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(decoded.sample_rate, OPUS_RATE);
+        assert_eq!(decoded.channels, 2);
+        let decoded_frames = decoded.samples.len() / 2;
+        // At most the padding of the last packet is left over
+        assert!(decoded_frames >= frames, "{} < {}", decoded_frames, frames);
+        assert!(decoded_frames < frames + ENCODE_FRAMES);
+    }
+}
@@ -0,0 +1,137 @@
+//! Pitch shift and playback speed
+//!
+//! Speed works like a tape running faster or slower: the sound is resampled,
+//! so its pitch follows. The pitch shift keeps the length: the sound is
+//! time-stretched (WSOLA) by the pitch ratio first, then resampled back to
+//! its original length, which moves every frequency by that ratio. Both
+//! share the one resampling pass.
+
+use super::resample::resample;
+use super::stretch::{stretch_to_frames, trimmed_range};
+use super::AudioData;
+
+/// Largest pitch shift either way (one octave)
+pub const MAX_PITCH_SEMITONES: f32 = 12.0;
+
+/// Slowest playback speed
+pub const MIN_SPEED: f32 = 0.5;
+
+/// Fastest playback speed
+pub const MAX_SPEED: f32 = 2.0;
+
+/// Whether a pitch shift and speed leave a sound as it is
+pub fn is_neutral(pitch_semitones: f32, speed: f32) -> bool {
+    pitch_semitones == 0.0 && speed == 1.0
+}
+
+/// The `start_ms`..`end_ms` range of `audio`, shifted by `pitch_semitones`
+/// and played at `speed`
+///
+/// Both are clamped to the supported ranges; the result lasts the range's
+/// length divided by the speed.
+pub fn shift_pitch_and_speed(
+    audio: &AudioData,
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+    pitch_semitones: f32,
+    speed: f32,
+) -> AudioData {
+    let pitch_semitones = pitch_semitones.clamp(-MAX_PITCH_SEMITONES, MAX_PITCH_SEMITONES);
+    let speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    let channels = audio.channels.max(1) as usize;
+    let range = trimmed_range(audio, start_ms, end_ms);
+
+    let pitch_ratio = 2f64.powf(pitch_semitones as f64 / 12.0);
+    let stretched = if pitch_semitones == 0.0 {
+        range.to_vec()
+    } else {
+        let frames = range.len() / channels;
+        let target_frames = (frames as f64 * pitch_ratio).round() as usize;
+        stretch_to_frames(range, channels, audio.sample_rate, target_frames)
+    };
+
+    // Played `rate_ratio` times as fast: read it as if it had been recorded
+    // at a higher rate and convert back
+    let rate_ratio = pitch_ratio * speed as f64;
+    let source = AudioData {
+        samples: stretched,
+        sample_rate: (audio.sample_rate as f64 * rate_ratio).round() as u32,
+        channels: audio.channels,
+    };
+    let mut shifted = resample(&source, audio.sample_rate);
+    shifted.sample_rate = audio.sample_rate;
+    shifted
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 44_100;
+
+    fn sine(freq: f32, frames: usize) -> AudioData {
+        AudioData {
+            samples: (0..frames)
+                .map(|i| (std::f32::consts::TAU * freq * i as f32 / RATE as f32).sin() * 0.5)
+                .collect(),
+            sample_rate: RATE,
+            channels: 1,
+        }
+    }
+
+    /// Zero crossings per second, away from the edges
+    fn crossing_rate(samples: &[f32]) -> f32 {
+        let middle = &samples[samples.len() / 8..samples.len() * 7 / 8];
+        let crossings = middle
+            .windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count();
+        crossings as f32 / (middle.len() as f32 / RATE as f32)
+    }
+
+    #[test]
+    fn test_pitch_shift_keeps_length() {
+        let input = sine(440.0, RATE as usize / 2);
+        let up = shift_pitch_and_speed(&input, None, None, 12.0, 1.0);
+
+        let frames_ratio = up.samples.len() as f32 / input.samples.len() as f32;
+        assert!((frames_ratio - 1.0).abs() < 0.01, "ratio {}", frames_ratio);
+        assert_eq!(up.sample_rate, RATE);
+        // An octave up doubles the frequency
+        let pitch_ratio = crossing_rate(&up.samples) / crossing_rate(&input.samples);
+        assert!((pitch_ratio - 2.0).abs() < 0.1, "ratio {}", pitch_ratio);
+    }
+
+    #[test]
+    fn test_speed_changes_length_and_pitch() {
+        let input = sine(440.0, RATE as usize / 2);
+        let fast = shift_pitch_and_speed(&input, None, None, 0.0, 2.0);
+
+        let frames_ratio = fast.samples.len() as f32 / input.samples.len() as f32;
+        assert!((frames_ratio - 0.5).abs() < 0.01, "ratio {}", frames_ratio);
+        let pitch_ratio = crossing_rate(&fast.samples) / crossing_rate(&input.samples);
+        assert!((pitch_ratio - 2.0).abs() < 0.1, "ratio {}", pitch_ratio);
+
+        // An octave down at double speed: shorter, same pitch
+        let same_pitch = shift_pitch_and_speed(&input, None, None, -12.0, 2.0);
+        let pitch_ratio = crossing_rate(&same_pitch.samples) / crossing_rate(&input.samples);
+        assert!((pitch_ratio - 1.0).abs() < 0.05, "ratio {}", pitch_ratio);
+    }
+
+    #[test]
+    fn test_values_are_clamped_and_trim_applies() {
+        let input = sine(440.0, RATE as usize);
+        // 250 ms at 4x is clamped to 2x: 125 ms
+        let out = shift_pitch_and_speed(&input, Some(250), Some(500), 0.0, 4.0);
+        let expected = RATE as usize / 8;
+        assert!((out.samples.len() as i64 - expected as i64).abs() <= 2);
+
+        assert!(is_neutral(0.0, 1.0));
+        assert!(!is_neutral(0.5, 1.0));
+        assert!(!is_neutral(0.0, 1.5));
+    }
+}
@@ -1,32 +1,243 @@
 //! Audio playback stream creation and sample writing
 //!
-//! Handles cpal stream creation with sample rate conversion using linear interpolation.
+//! Handles cpal stream creation with sample rate conversion: linear interpolation
+//! in the callback, or a buffer converted up front (see `resample`). Streams
+//! play a decoded sound or read a long one from its decoder's ring (see
+//! `stream`), optionally through a limiter (see `limiter`). A `MixRenderer`
+//! renders the same way into a buffer, for outputs mixed into another stream.
 
 use cpal::traits::{DeviceTrait, StreamTrait};
 use cpal::{BufferSize, Device, SampleRate, Stream, StreamConfig};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, trace, warn};
 
+use super::diagnostics::record_stream_error;
+use super::envelope::PlaybackEnvelope;
+use super::faults::{take_fault, Fault};
+use super::limiter::Limiter;
+use super::spectrum;
+use super::stream::StreamRing;
 use super::{AudioData, AudioError};
 
 /// Preferred buffer size for low-latency playback.
 /// 256 samples @ 48kHz = ~5.3ms latency per buffer.
 const PREFERRED_BUFFER_SIZE: u32 = 256;
 
+/// Buffer sizes tried first with smaller buffers on (64 samples @ 48kHz =
+/// ~1.3ms per buffer)
+///
+/// This only shrinks the app's buffers. cpal opens WASAPI in shared,
+/// event-driven mode, and Windows doesn't deliver events faster than its
+/// engine period (usually 10ms) there, so the gain is largest on drivers
+/// that run with shorter periods. Exclusive mode is not available through
+/// cpal.
+const SMALL_BUFFER_SIZES: [u32; 2] = [64, 128];
+
+/// Message to a playback's thread, which waits for these instead of polling
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackSignal {
+    /// Stop right away
+    Stop,
+    /// A stream played its last frame, or faded out
+    Finished,
+    /// The stream played on by its progress interval
+    Progress,
+    /// Fade out over the duration, then stop (a restart replaced the playback)
+    FadeOut(Duration),
+    /// Volume, pause state or broadcast mute changed
+    Changed,
+}
+
+/// Playback position of a stream, advanced by its audio callback
+///
+/// The position is in source frames (fractional while resampling), so it
+/// reflects what was actually handed to the device rather than wall time.
+/// Once the callback reaches the end frame (or an envelope's fade-out ends)
+/// the cursor is marked finished and `Finished` is signalled (once). With an
+/// envelope, the stream also applies its gain and signals its progress.
+pub struct PlaybackCursor {
+    /// Current source frame, stored as `f64` bits
+    frame: AtomicU64,
+    finished: AtomicBool,
+    signals: Sender<PlaybackSignal>,
+    envelope: Option<PlaybackEnvelope>,
+}
+
+impl PlaybackCursor {
+    pub fn new(start_frame: usize, signals: Sender<PlaybackSignal>) -> Arc<Self> {
+        Self::build(start_frame, signals, None)
+    }
+
+    /// Cursor whose stream follows `envelope`
+    pub fn with_envelope(
+        start_frame: usize,
+        signals: Sender<PlaybackSignal>,
+        envelope: PlaybackEnvelope,
+    ) -> Arc<Self> {
+        Self::build(start_frame, signals, Some(envelope))
+    }
+
+    fn build(
+        start_frame: usize,
+        signals: Sender<PlaybackSignal>,
+        envelope: Option<PlaybackEnvelope>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            frame: AtomicU64::new((start_frame as f64).to_bits()),
+            finished: AtomicBool::new(false),
+            signals,
+            envelope,
+        })
+    }
+
+    /// Source frame the stream has played up to
+    pub fn frame(&self) -> f64 {
+        f64::from_bits(self.frame.load(Ordering::Acquire))
+    }
+
+    /// Whether the stream has played its last frame
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+
+    /// Multiplier of the stream's volume at the current position (1.0
+    /// without an envelope)
+    fn gain(&self) -> f32 {
+        self.envelope
+            .as_ref()
+            .map_or(1.0, |envelope| envelope.gain(self.frame()))
+    }
+
+    /// Fade the stream out over `duration` from its current position; it
+    /// finishes once silent (no effect without an envelope)
+    pub fn fade_out(&self, duration: Duration) {
+        if let Some(envelope) = &self.envelope {
+            envelope.fade_out(self.frame(), duration);
+        }
+    }
+
+    /// Store the position after a callback, signalling completion at `end`
+    fn advance(&self, frame: f64, end: f64) {
+        let previous = f64::from_bits(self.frame.swap(frame.to_bits(), Ordering::AcqRel));
+        let faded_out = self
+            .envelope
+            .as_ref()
+            .is_some_and(|envelope| envelope.fade_out_progress(frame) >= 1.0);
+        // The owner may already be gone (stopped playback)
+        if frame >= end || faded_out {
+            if !self.finished.swap(true, Ordering::AcqRel) {
+                let _ = self.signals.send(PlaybackSignal::Finished);
+            }
+        } else if self
+            .envelope
+            .as_ref()
+            .is_some_and(|envelope| envelope.progress_due(previous, frame))
+        {
+            let _ = self.signals.send(PlaybackSignal::Progress);
+        }
+    }
+}
+
+/// What a stream plays
+#[derive(Clone)]
+pub enum PlaybackSource {
+    /// Decoded sound, plus a copy already resampled to the device's rate
+    /// (used instead of linear interpolation if the rate matches)
+    Decoded {
+        audio_data: Arc<AudioData>,
+        converted: Option<Arc<AudioData>>,
+    },
+    /// Long sound read from its decoder's ring while it decodes
+    Streamed {
+        ring: Arc<StreamRing>,
+        sample_rate: u32,
+    },
+}
+
+impl PlaybackSource {
+    fn sample_rate(&self) -> u32 {
+        match self {
+            Self::Decoded { audio_data, .. } => audio_data.sample_rate,
+            Self::Streamed { sample_rate, .. } => *sample_rate,
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        match self {
+            Self::Decoded { audio_data, .. } => audio_data.channels,
+            Self::Streamed { ring, .. } => ring.channels(),
+        }
+    }
+
+    /// Keep a converted buffer only if it matches the output's rate
+    fn for_output_rate(self, output_rate: u32) -> Self {
+        match self {
+            Self::Decoded {
+                audio_data,
+                converted,
+            } => Self::Decoded {
+                converted: converted.filter(|c| {
+                    c.sample_rate == output_rate && c.sample_rate != audio_data.sample_rate
+                }),
+                audio_data,
+            },
+            streamed => streamed,
+        }
+    }
+
+    /// Last frame to play (a streamed sound's decoder stops at the end frame
+    /// itself)
+    fn end_index(&self, end_frame: Option<usize>) -> usize {
+        match self {
+            Self::Decoded { audio_data, .. } => {
+                let max_frames = audio_data.samples.len() / audio_data.channels as usize;
+                end_frame.unwrap_or(max_frames)
+            }
+            Self::Streamed { .. } => usize::MAX,
+        }
+    }
+}
+
+/// Sample rate the device's streams run at
+pub fn output_sample_rate(device: &Device) -> Result<u32, AudioError> {
+    device
+        .default_output_config()
+        .map(|config| config.sample_rate().0)
+        .map_err(|e| AudioError::DeviceConfig(e.to_string()))
+}
+
 /// Create and start a playback stream on a specific device
+///
+/// Playback starts at the cursor's frame and advances it (a streamed source
+/// must start decoding at that frame). The stream plays at `volume` scaled by
+/// `master_volume` (both shared `f32` bits, read without locking).
+/// `tap_spectrum` feeds the stream's output to the spectrum analyzer (monitor
+/// streams only), `limit` passes it through a limiter so boosted sounds don't
+/// clip. `small_buffers` tries buffers smaller than the preferred size first.
+#[allow(clippy::too_many_arguments)]
 pub fn create_playback_stream(
     device: &Device,
-    audio_data: Arc<AudioData>,
-    volume: Arc<Mutex<f32>>,
-    start_frame: Option<usize>,
+    source: PlaybackSource,
+    volume: Arc<AtomicU32>,
+    master_volume: Arc<AtomicU32>,
+    cursor: Arc<PlaybackCursor>,
     end_frame: Option<usize>,
+    tap_spectrum: bool,
+    limit: bool,
+    small_buffers: bool,
 ) -> Result<Stream, AudioError> {
     let start = Instant::now();
     let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
 
     debug!(device = %device_name, "Creating playback stream");
 
+    if take_fault(Fault::StreamBuildFailure) {
+        return Err(AudioError::StreamBuild("Injected failure".to_string()));
+    }
+
     let supported_config = device
         .default_output_config()
         .map_err(|e| AudioError::DeviceConfig(e.to_string()))?;
@@ -43,31 +254,37 @@ pub fn create_playback_stream(
     };
 
     // Log channel mapping for multi-channel devices
-    if channels > audio_data.channels as usize {
+    if channels > source.channels() as usize {
         warn!(
             "Device has {} output channels, audio has {} channels - extra channels will be silent",
-            channels, audio_data.channels
+            channels,
+            source.channels()
         );
     }
 
-    // Initialize sample index to start_frame (or 0)
-    let start_idx = start_frame.unwrap_or(0) as f64;
-    let sample_index = Arc::new(Mutex::new(start_idx));
-
     // Calculate end frame (or use full length)
-    let max_frames = audio_data.samples.len() / audio_data.channels as usize;
-    let end_idx = end_frame.unwrap_or(max_frames);
-    let end_frame_arc = Arc::new(end_idx);
+    let end_frame_arc = Arc::new(source.end_index(end_frame));
 
     // Calculate sample rate ratio for resampling
-    let rate_ratio = audio_data.sample_rate as f64 / output_sample_rate as f64;
+    let source_rate = source.sample_rate();
+    let rate_ratio = source_rate as f64 / output_sample_rate as f64;
+
+    // A converted buffer only helps if it matches the device's rate
+    let source = source.for_output_rate(output_sample_rate);
 
     // Log if resampling is occurring (quality impact)
-    if audio_data.sample_rate != output_sample_rate {
+    if source_rate != output_sample_rate {
         info!(
-            audio_sample_rate = audio_data.sample_rate,
+            audio_sample_rate = source_rate,
             output_sample_rate = output_sample_rate,
             rate_ratio = format!("{:.4}", rate_ratio),
+            band_limited = matches!(
+                source,
+                PlaybackSource::Decoded {
+                    converted: Some(_),
+                    ..
+                }
+            ),
             "Sample rate conversion active"
         );
     }
@@ -78,17 +295,23 @@ pub fn create_playback_stream(
         sample_format,
         &stream_config,
         &supported_config,
-        audio_data,
-        sample_index,
+        source,
+        cursor,
         volume,
+        master_volume,
         end_frame_arc,
         channels,
         rate_ratio,
-    )?;
+        tap_spectrum,
+        limit,
+        small_buffers,
+    )
+    .inspect_err(|e| record_stream_error(&device_name, &e.to_string()))?;
 
     stream
         .play()
-        .map_err(|e| AudioError::StreamStart(e.to_string()))?;
+        .map_err(|e| AudioError::StreamStart(e.to_string()))
+        .inspect_err(|e| record_stream_error(&device_name, &e.to_string()))?;
 
     let duration_ms = start.elapsed().as_millis();
     info!(
@@ -104,14 +327,84 @@ pub fn create_playback_stream(
     Ok(stream)
 }
 
+/// Renders a playback into buffers of another stream instead of a device
+/// stream of its own (the soundboard mixed into the routed microphone)
+///
+/// Renders like a device stream's callback: at `volume` times the master
+/// volume, advancing the cursor, without a limiter (the stream mixing it in
+/// limits the sum).
+pub struct MixRenderer {
+    reader: SourceReader,
+    cursor: Arc<PlaybackCursor>,
+    volume: Arc<AtomicU32>,
+    master_volume: Arc<AtomicU32>,
+    end_frame: usize,
+    channels: usize,
+    rate_ratio: f64,
+}
+
+impl MixRenderer {
+    pub fn new(
+        source: PlaybackSource,
+        volume: Arc<AtomicU32>,
+        master_volume: Arc<AtomicU32>,
+        cursor: Arc<PlaybackCursor>,
+        end_frame: Option<usize>,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Self {
+        let end_frame = source.end_index(end_frame);
+        let rate_ratio = source.sample_rate() as f64 / sample_rate as f64;
+        Self {
+            reader: SourceReader::new(source.for_output_rate(sample_rate)),
+            cursor,
+            volume,
+            master_volume,
+            end_frame,
+            channels: channels as usize,
+            rate_ratio,
+        }
+    }
+
+    /// Render the next samples into `output` (interleaved, overwriting it)
+    pub fn render(&mut self, output: &mut [f32]) {
+        let vol =
+            shared_level(&self.volume) * shared_level(&self.master_volume) * self.cursor.gain();
+        render_f32(
+            output,
+            &mut self.reader,
+            &self.cursor,
+            vol,
+            self.channels,
+            self.rate_ratio,
+            self.end_frame,
+        );
+    }
+}
+
 /// Buffer size options for fallback strategy
 const FALLBACK_BUFFER_SIZES: [u32; 3] = [256, 512, 1024];
 
+/// Frames of the f32 buffer integer output is limited in (the largest fixed
+/// buffer size; bigger device buffers are rendered in several chunks)
+const SCRATCH_FRAMES: usize = 1024;
+
+/// Fixed buffer sizes output streams try, in order, before the device default
+pub(super) fn buffer_sizes(small_buffers: bool) -> impl Iterator<Item = u32> {
+    let small_sizes = if small_buffers {
+        &SMALL_BUFFER_SIZES[..]
+    } else {
+        &[]
+    };
+    small_sizes.iter().chain(&FALLBACK_BUFFER_SIZES).copied()
+}
+
 /// Build output stream with fallback to larger buffer sizes or default config.
 ///
 /// Attempts to create a low-latency audio stream by trying multiple buffer sizes
-/// in sequence: 256 → 512 → 1024 samples. If all fixed buffer sizes fail, falls
-/// back to the device's default configuration.
+/// in sequence: 256 → 512 → 1024 samples (64 → 128 first with smaller
+/// buffers on). If all fixed buffer sizes fail, falls back to the device's default
+/// configuration.
 ///
 /// # Arguments
 ///
@@ -119,12 +412,16 @@ const FALLBACK_BUFFER_SIZES: [u32; 3] = [256, 512, 1024];
 /// * `sample_format` - Sample format (F32, I16, or U16)
 /// * `low_latency_config` - Preferred low-latency stream configuration
 /// * `default_config` - Device's default configuration (fallback)
-/// * `audio_data` - Decoded audio samples
-/// * `sample_index` - Current playback position
-/// * `volume` - Playback volume (0.0-1.0)
+/// * `source` - Decoded or streamed audio
+/// * `cursor` - Playback position, advanced by the callback
+/// * `volume` - Playback volume (`f32` bits, 0.0-1.0)
+/// * `master_volume` - Master volume (`f32` bits), applied on top
 /// * `end_frame` - End frame for trimmed playback
 /// * `channels` - Number of output channels
 /// * `rate_ratio` - Sample rate conversion ratio
+/// * `tap_spectrum` - Feed the output to the spectrum analyzer
+/// * `limit` - Pass the output through a limiter
+/// * `small_buffers` - Try the smaller buffer sizes first
 ///
 /// # Returns
 ///
@@ -141,15 +438,25 @@ fn build_stream_with_fallback(
     sample_format: cpal::SampleFormat,
     low_latency_config: &StreamConfig,
     default_config: &cpal::SupportedStreamConfig,
-    audio_data: Arc<AudioData>,
-    sample_index: Arc<Mutex<f64>>,
-    volume: Arc<Mutex<f32>>,
+    source: PlaybackSource,
+    cursor: Arc<PlaybackCursor>,
+    volume: Arc<AtomicU32>,
+    master_volume: Arc<AtomicU32>,
     end_frame: Arc<usize>,
     channels: usize,
     rate_ratio: f64,
+    tap_spectrum: bool,
+    limit: bool,
+    small_buffers: bool,
 ) -> Result<(Stream, String), AudioError> {
+    let preferred = if small_buffers {
+        SMALL_BUFFER_SIZES[0]
+    } else {
+        PREFERRED_BUFFER_SIZE
+    };
+
     // Try each buffer size in order
-    for &buffer_size in &FALLBACK_BUFFER_SIZES {
+    for buffer_size in buffer_sizes(small_buffers) {
         let config = StreamConfig {
             channels: low_latency_config.channels,
             sample_rate: low_latency_config.sample_rate,
@@ -160,18 +467,21 @@ fn build_stream_with_fallback(
             device,
             sample_format,
             &config,
-            audio_data.clone(),
-            sample_index.clone(),
+            source.clone(),
+            cursor.clone(),
             volume.clone(),
+            master_volume.clone(),
             end_frame.clone(),
             channels,
             rate_ratio,
+            tap_spectrum,
+            limit,
         ) {
             Ok(stream) => {
-                if buffer_size != PREFERRED_BUFFER_SIZE {
+                if buffer_size != preferred {
                     warn!(
                         buffer_size = buffer_size,
-                        preferred = PREFERRED_BUFFER_SIZE,
+                        preferred = preferred,
                         "Using fallback buffer size (preferred size not supported by device)"
                     );
                 }
@@ -194,12 +504,15 @@ fn build_stream_with_fallback(
         device,
         sample_format,
         &default_config.clone().into(),
-        audio_data,
-        sample_index,
+        source,
+        cursor,
         volume,
+        master_volume,
         end_frame,
         channels,
         rate_ratio,
+        tap_spectrum,
+        limit,
     )?;
 
     Ok((stream, "Default".to_string()))
@@ -216,12 +529,15 @@ fn build_stream_with_fallback(
 /// * `device` - The audio output device
 /// * `sample_format` - Sample format to use (F32, I16, or U16)
 /// * `config` - Stream configuration (sample rate, channels, buffer size)
-/// * `audio_data` - Decoded audio samples
-/// * `sample_index` - Current playback position (shared, mutable)
-/// * `volume` - Playback volume (shared, mutable, 0.0-1.0)
+/// * `source` - Decoded or streamed audio
+/// * `cursor` - Playback position, advanced by the callback
+/// * `volume` - Playback volume (shared `f32` bits, mutable, 0.0-1.0)
+/// * `master_volume` - Master volume (`f32` bits), applied on top
 /// * `end_frame` - End frame for trimmed playback
 /// * `channels` - Number of output channels
 /// * `rate_ratio` - Sample rate conversion ratio
+/// * `tap_spectrum` - Feed the output to the spectrum analyzer
+/// * `limit` - Pass the output through a limiter
 ///
 /// # Returns
 ///
@@ -232,21 +548,36 @@ fn build_stream_with_fallback(
 /// # Audio Processing
 ///
 /// The audio callback performs:
-/// - Linear interpolation for sample rate conversion
+/// - Reading the decoded sound or its decoder's ring
+/// - Sample rate conversion (converted buffer or linear interpolation)
 /// - Volume scaling with square root curve
 /// - Multi-channel mapping (silences extra output channels)
+/// - Limiting (integer formats are then rendered as f32 first)
 #[allow(clippy::too_many_arguments)]
 fn try_build_stream(
     device: &Device,
     sample_format: cpal::SampleFormat,
     config: &StreamConfig,
-    audio_data: Arc<AudioData>,
-    sample_index: Arc<Mutex<f64>>,
-    volume: Arc<Mutex<f32>>,
+    source: PlaybackSource,
+    cursor: Arc<PlaybackCursor>,
+    volume: Arc<AtomicU32>,
+    master_volume: Arc<AtomicU32>,
     end_frame: Arc<usize>,
     channels: usize,
     rate_ratio: f64,
+    tap_spectrum: bool,
+    limit: bool,
 ) -> Result<Stream, AudioError> {
+    let sample_rate = config.sample_rate.0;
+    let mut reader = SourceReader::new(source);
+    let mut limiter = limit.then(|| Limiter::new(sample_rate, channels));
+    // f32 rendering of integer output while limiting, allocated here so the
+    // callback never allocates (larger buffers are rendered in chunks)
+    let mut scratch = if limit && sample_format != cpal::SampleFormat::F32 {
+        vec![0.0f32; SCRATCH_FRAMES * channels]
+    } else {
+        Vec::new()
+    };
     trace!(
         sample_format = ?sample_format,
         buffer_size = ?config.buffer_size,
@@ -260,18 +591,24 @@ fn try_build_stream(
             .build_output_stream(
                 config,
                 move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                    let vol = *volume.lock().unwrap();
-                    write_audio_f32(
+                    let vol = shared_level(&volume) * shared_level(&master_volume) * cursor.gain();
+                    render_f32(
                         data,
-                        &audio_data,
-                        &sample_index,
+                        &mut reader,
+                        &cursor,
                         vol,
                         channels,
                         rate_ratio,
                         *end_frame,
                     );
+                    if let Some(limiter) = &mut limiter {
+                        limiter.process(data);
+                    }
+                    if tap_spectrum {
+                        spectrum::feed(data, channels, sample_rate, |s| s);
+                    }
                 },
-                |err| error!("Stream error: {}", err),
+                stream_error_callback(device),
                 None,
             )
             .map_err(|e| AudioError::StreamBuild(e.to_string())),
@@ -279,18 +616,52 @@ fn try_build_stream(
             .build_output_stream(
                 config,
                 move |data: &mut [i16], _: &cpal::OutputCallbackInfo| {
-                    let vol = *volume.lock().unwrap();
-                    write_audio_i16(
-                        data,
-                        &audio_data,
-                        &sample_index,
-                        vol,
-                        channels,
-                        rate_ratio,
-                        *end_frame,
-                    );
+                    let vol = shared_level(&volume) * shared_level(&master_volume) * cursor.gain();
+                    if let Some(limiter) = &mut limiter {
+                        render_limited(
+                            data,
+                            &mut scratch,
+                            limiter,
+                            &mut reader,
+                            &cursor,
+                            vol,
+                            channels,
+                            rate_ratio,
+                            *end_frame,
+                            |value| (value * 32767.0) as i16,
+                        );
+                    } else {
+                        match &mut reader {
+                            SourceReader::Decoded {
+                                audio_data,
+                                converted,
+                            } => write_audio_i16(
+                                data,
+                                audio_data,
+                                converted.as_deref(),
+                                &cursor,
+                                vol,
+                                channels,
+                                rate_ratio,
+                                *end_frame,
+                            ),
+                            SourceReader::Streamed(frames) => write_streamed(
+                                data,
+                                frames,
+                                &cursor,
+                                vol,
+                                channels,
+                                rate_ratio,
+                                0,
+                                |v| (v * 32767.0) as i16,
+                            ),
+                        }
+                    }
+                    if tap_spectrum {
+                        spectrum::feed(data, channels, sample_rate, |s| s as f32 / 32767.0);
+                    }
                 },
-                |err| error!("Stream error: {}", err),
+                stream_error_callback(device),
                 None,
             )
             .map_err(|e| AudioError::StreamBuild(e.to_string())),
@@ -298,18 +669,52 @@ fn try_build_stream(
             .build_output_stream(
                 config,
                 move |data: &mut [u16], _: &cpal::OutputCallbackInfo| {
-                    let vol = *volume.lock().unwrap();
-                    write_audio_u16(
-                        data,
-                        &audio_data,
-                        &sample_index,
-                        vol,
-                        channels,
-                        rate_ratio,
-                        *end_frame,
-                    );
+                    let vol = shared_level(&volume) * shared_level(&master_volume) * cursor.gain();
+                    if let Some(limiter) = &mut limiter {
+                        render_limited(
+                            data,
+                            &mut scratch,
+                            limiter,
+                            &mut reader,
+                            &cursor,
+                            vol,
+                            channels,
+                            rate_ratio,
+                            *end_frame,
+                            |value| ((value + 1.0) * 32767.5) as u16,
+                        );
+                    } else {
+                        match &mut reader {
+                            SourceReader::Decoded {
+                                audio_data,
+                                converted,
+                            } => write_audio_u16(
+                                data,
+                                audio_data,
+                                converted.as_deref(),
+                                &cursor,
+                                vol,
+                                channels,
+                                rate_ratio,
+                                *end_frame,
+                            ),
+                            SourceReader::Streamed(frames) => write_streamed(
+                                data,
+                                frames,
+                                &cursor,
+                                vol,
+                                channels,
+                                rate_ratio,
+                                32768,
+                                |v| ((v + 1.0) * 32767.5) as u16,
+                            ),
+                        }
+                    }
+                    if tap_spectrum {
+                        spectrum::feed(data, channels, sample_rate, |s| s as f32 / 32767.5 - 1.0);
+                    }
                 },
-                |err| error!("Stream error: {}", err),
+                stream_error_callback(device),
                 None,
             )
             .map_err(|e| AudioError::StreamBuild(e.to_string())),
@@ -320,17 +725,102 @@ fn try_build_stream(
     Ok(stream)
 }
 
-/// Write audio data to f32 output buffer with resampling (linear interpolation)
+/// Error callback for a device's stream: logs and records for diagnostics
+fn stream_error_callback(device: &Device) -> impl FnMut(cpal::StreamError) + Send + 'static {
+    let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+    move |err| {
+        error!("Stream error: {}", err);
+        record_stream_error(&device_name, &err.to_string());
+    }
+}
+
+/// Render through the limiter into integer output
+///
+/// Each chunk of `output` is rendered into `scratch` as f32, limited and then
+/// converted, so `scratch` never has to grow on the audio thread.
+#[allow(clippy::too_many_arguments)]
+fn render_limited<T>(
+    output: &mut [T],
+    scratch: &mut [f32],
+    limiter: &mut Limiter,
+    reader: &mut SourceReader,
+    cursor: &PlaybackCursor,
+    volume: f32,
+    output_channels: usize,
+    rate_ratio: f64,
+    end_frame: usize,
+    convert: impl Fn(f32) -> T,
+) {
+    for chunk in output.chunks_mut(scratch.len().max(output_channels)) {
+        let Some(rendered) = scratch.get_mut(..chunk.len()) else {
+            return;
+        };
+        render_f32(
+            rendered,
+            reader,
+            cursor,
+            volume,
+            output_channels,
+            rate_ratio,
+            end_frame,
+        );
+        limiter.process(rendered);
+        for (sample, value) in chunk.iter_mut().zip(rendered.iter()) {
+            *sample = convert(*value);
+        }
+    }
+}
+
+/// Write a decoded or streamed sound to an f32 output buffer
+fn render_f32(
+    output: &mut [f32],
+    reader: &mut SourceReader,
+    cursor: &PlaybackCursor,
+    volume: f32,
+    output_channels: usize,
+    rate_ratio: f64,
+    end_frame: usize,
+) {
+    match reader {
+        SourceReader::Decoded {
+            audio_data,
+            converted,
+        } => write_audio_f32(
+            output,
+            audio_data,
+            converted.as_deref(),
+            cursor,
+            volume,
+            output_channels,
+            rate_ratio,
+            end_frame,
+        ),
+        SourceReader::Streamed(frames) => write_streamed(
+            output,
+            frames,
+            cursor,
+            volume,
+            output_channels,
+            rate_ratio,
+            0.0,
+            |v| v,
+        ),
+    }
+}
+
+/// Write audio data to f32 output buffer with resampling
+#[allow(clippy::too_many_arguments)]
 fn write_audio_f32(
     output: &mut [f32],
     audio_data: &AudioData,
-    sample_index: &Arc<Mutex<f64>>,
+    converted: Option<&AudioData>,
+    cursor: &PlaybackCursor,
     volume: f32,
     output_channels: usize,
     rate_ratio: f64,
     end_frame: usize,
 ) {
-    let mut index = sample_index.lock().unwrap();
+    let mut index = cursor.frame();
     let input_channels = audio_data.channels as usize;
     let max_frame = end_frame.min(audio_data.samples.len() / input_channels) as f64;
 
@@ -339,7 +829,7 @@ fn write_audio_f32(
     let scaled_volume = volume.sqrt() * 0.2;
 
     for frame in output.chunks_mut(output_channels) {
-        if *index >= max_frame - 1.0 {
+        if index >= max_frame - 1.0 {
             // End of audio - silence
             for sample in frame.iter_mut() {
                 *sample = 0.0;
@@ -347,10 +837,6 @@ fn write_audio_f32(
             continue;
         }
 
-        // Linear interpolation between samples
-        let frame_idx = *index as usize;
-        let frac = *index - frame_idx as f64; // Fractional part for interpolation
-
         for (ch, sample) in frame.iter_mut().enumerate() {
             // Only map audio to channels that exist in input
             // Extra output channels (e.g., center, LFE, surround in 5.1/7.1) get silence
@@ -360,36 +846,28 @@ fn write_audio_f32(
                 continue;
             }
 
-            let idx1 = frame_idx * input_channels + ch;
-            let idx2 = (frame_idx + 1) * input_channels + ch;
-
-            if idx2 < audio_data.samples.len() {
-                // Linear interpolation: value = sample1 + (sample2 - sample1) * frac
-                let sample1 = audio_data.samples[idx1];
-                let sample2 = audio_data.samples[idx2];
-                *sample = (sample1 + (sample2 - sample1) * frac as f32) * scaled_volume;
-            } else if idx1 < audio_data.samples.len() {
-                *sample = audio_data.samples[idx1] * scaled_volume;
-            } else {
-                *sample = 0.0;
-            }
+            *sample = sample_at(audio_data, converted, index, rate_ratio, ch) * scaled_volume;
         }
 
-        *index += rate_ratio;
+        index += rate_ratio;
     }
+
+    cursor.advance(index, max_frame - 1.0);
 }
 
-/// Write audio data to i16 output buffer with resampling (linear interpolation)
+/// Write audio data to i16 output buffer with resampling
+#[allow(clippy::too_many_arguments)]
 fn write_audio_i16(
     output: &mut [i16],
     audio_data: &AudioData,
-    sample_index: &Arc<Mutex<f64>>,
+    converted: Option<&AudioData>,
+    cursor: &PlaybackCursor,
     volume: f32,
     output_channels: usize,
     rate_ratio: f64,
     end_frame: usize,
 ) {
-    let mut index = sample_index.lock().unwrap();
+    let mut index = cursor.frame();
     let input_channels = audio_data.channels as usize;
     let max_frame = end_frame.min(audio_data.samples.len() / input_channels) as f64;
 
@@ -398,7 +876,7 @@ fn write_audio_i16(
     let scaled_volume = volume.sqrt() * 0.2;
 
     for frame in output.chunks_mut(output_channels) {
-        if *index >= max_frame - 1.0 {
+        if index >= max_frame - 1.0 {
             // End of audio - silence
             for sample in frame.iter_mut() {
                 *sample = 0;
@@ -406,10 +884,6 @@ fn write_audio_i16(
             continue;
         }
 
-        // Linear interpolation between samples
-        let frame_idx = *index as usize;
-        let frac = *index - frame_idx as f64;
-
         for (ch, sample) in frame.iter_mut().enumerate() {
             // Only map audio to channels that exist in input
             // Extra output channels (e.g., center, LFE, surround in 5.1/7.1) get silence
@@ -419,36 +893,29 @@ fn write_audio_i16(
                 continue;
             }
 
-            let idx1 = frame_idx * input_channels + ch;
-            let idx2 = (frame_idx + 1) * input_channels + ch;
-
-            let value = if idx2 < audio_data.samples.len() {
-                let sample1 = audio_data.samples[idx1];
-                let sample2 = audio_data.samples[idx2];
-                (sample1 + (sample2 - sample1) * frac as f32) * scaled_volume
-            } else if idx1 < audio_data.samples.len() {
-                audio_data.samples[idx1] * scaled_volume
-            } else {
-                0.0
-            };
+            let value = sample_at(audio_data, converted, index, rate_ratio, ch) * scaled_volume;
             *sample = (value * 32767.0) as i16;
         }
 
-        *index += rate_ratio;
+        index += rate_ratio;
     }
+
+    cursor.advance(index, max_frame - 1.0);
 }
 
-/// Write audio data to u16 output buffer with resampling (linear interpolation)
+/// Write audio data to u16 output buffer with resampling
+#[allow(clippy::too_many_arguments)]
 fn write_audio_u16(
     output: &mut [u16],
     audio_data: &AudioData,
-    sample_index: &Arc<Mutex<f64>>,
+    converted: Option<&AudioData>,
+    cursor: &PlaybackCursor,
     volume: f32,
     output_channels: usize,
     rate_ratio: f64,
     end_frame: usize,
 ) {
-    let mut index = sample_index.lock().unwrap();
+    let mut index = cursor.frame();
     let input_channels = audio_data.channels as usize;
     let max_frame = end_frame.min(audio_data.samples.len() / input_channels) as f64;
 
@@ -457,7 +924,7 @@ fn write_audio_u16(
     let scaled_volume = volume.sqrt() * 0.2;
 
     for frame in output.chunks_mut(output_channels) {
-        if *index >= max_frame - 1.0 {
+        if index >= max_frame - 1.0 {
             // End of audio - silence
             for sample in frame.iter_mut() {
                 *sample = 32768;
@@ -465,10 +932,6 @@ fn write_audio_u16(
             continue;
         }
 
-        // Linear interpolation between samples
-        let frame_idx = *index as usize;
-        let frac = *index - frame_idx as f64;
-
         for (ch, sample) in frame.iter_mut().enumerate() {
             // Only map audio to channels that exist in input
             // Extra output channels (e.g., center, LFE, surround in 5.1/7.1) get silence
@@ -478,22 +941,184 @@ fn write_audio_u16(
                 continue;
             }
 
-            let idx1 = frame_idx * input_channels + ch;
-            let idx2 = (frame_idx + 1) * input_channels + ch;
+            let value = sample_at(audio_data, converted, index, rate_ratio, ch) * scaled_volume;
+            *sample = ((value + 1.0) * 32767.5) as u16;
+        }
+
+        index += rate_ratio;
+    }
+
+    cursor.advance(index, max_frame - 1.0);
+}
 
-            let value = if idx2 < audio_data.samples.len() {
-                let sample1 = audio_data.samples[idx1];
-                let sample2 = audio_data.samples[idx2];
-                (sample1 + (sample2 - sample1) * frac as f32) * scaled_volume
-            } else if idx1 < audio_data.samples.len() {
-                audio_data.samples[idx1] * scaled_volume
+/// Callback-side state of a `PlaybackSource`
+enum SourceReader {
+    Decoded {
+        audio_data: Arc<AudioData>,
+        converted: Option<Arc<AudioData>>,
+    },
+    Streamed(StreamFrames),
+}
+
+impl SourceReader {
+    fn new(source: PlaybackSource) -> Self {
+        match source {
+            PlaybackSource::Decoded {
+                audio_data,
+                converted,
+            } => Self::Decoded {
+                audio_data,
+                converted,
+            },
+            PlaybackSource::Streamed { ring, .. } => Self::Streamed(StreamFrames {
+                ring,
+                frame: 0,
+                current: [0.0; 2],
+                next: [0.0; 2],
+                loaded: 0,
+            }),
+        }
+    }
+}
+
+/// Outcome of moving a streamed sound's read position
+enum Fetch {
+    Ready,
+    /// The decoder lags behind
+    Underrun,
+    /// The decoder is done and everything was played
+    Ended,
+}
+
+/// The two source frames around a streamed sound's read position
+///
+/// Frames are taken from the ring as the position passes them, so linear
+/// interpolation works as on a decoded sound.
+struct StreamFrames {
+    ring: Arc<StreamRing>,
+    /// Source frame of `current`
+    frame: usize,
+    current: [f32; 2],
+    next: [f32; 2],
+    /// Frames held (0-2)
+    loaded: usize,
+}
+
+impl StreamFrames {
+    /// Hold frames `index` and `index + 1`
+    fn fetch(&mut self, index: usize) -> Fetch {
+        let channels = self.ring.channels() as usize;
+        let mut frame = [0.0f32; 2];
+        while self.loaded < 2 || self.frame < index {
+            if self.loaded == 2 {
+                self.current = self.next;
+                self.frame += 1;
+                self.loaded = 1;
+            }
+            if !self.ring.pop(&mut frame[..channels]) {
+                return if self.ring.is_drained() {
+                    Fetch::Ended
+                } else {
+                    Fetch::Underrun
+                };
+            }
+            if self.loaded == 0 {
+                self.current = frame;
             } else {
-                0.0
+                self.next = frame;
+            }
+            self.loaded += 1;
+        }
+        Fetch::Ready
+    }
+}
+
+/// Write a streamed sound to an output buffer with resampling
+///
+/// Plays silence without moving on while the decoder lags behind, and
+/// finishes the cursor once the ring is drained.
+#[allow(clippy::too_many_arguments)]
+fn write_streamed<T: Copy>(
+    output: &mut [T],
+    frames: &mut StreamFrames,
+    cursor: &PlaybackCursor,
+    volume: f32,
+    output_channels: usize,
+    rate_ratio: f64,
+    silence: T,
+    to_sample: impl Fn(f32) -> T,
+) {
+    let mut index = cursor.frame();
+    let input_channels = frames.ring.channels() as usize;
+    let scaled_volume = calculate_scaled_volume(volume);
+    if frames.loaded == 0 {
+        // The decoder starts at the cursor's frame
+        frames.frame = index as usize;
+    }
+    let mut ended = false;
+
+    for frame in output.chunks_mut(output_channels) {
+        match frames.fetch(index as usize) {
+            Fetch::Ready => {}
+            fetch => {
+                ended = matches!(fetch, Fetch::Ended);
+                frame.fill(silence);
+                continue;
+            }
+        }
+
+        let frac = (index - index.floor()) as f32;
+        for (ch, sample) in frame.iter_mut().enumerate() {
+            // Extra output channels get silence (see `write_audio_f32`)
+            *sample = if ch < input_channels {
+                to_sample(lerp_sample(frames.current[ch], frames.next[ch], frac) * scaled_volume)
+            } else {
+                silence
             };
-            *sample = ((value + 1.0) * 32767.5) as u16;
         }
 
-        *index += rate_ratio;
+        index += rate_ratio;
+    }
+
+    cursor.advance(index, if ended { index } else { f64::INFINITY });
+}
+
+/// Current playback or master volume from its shared `f32` bits
+#[inline]
+fn shared_level(volume: &AtomicU32) -> f32 {
+    f32::from_bits(volume.load(Ordering::Relaxed))
+}
+
+/// Sample of `channel` at (fractional) source frame `index`
+///
+/// Reads the nearest frame of the pre-converted buffer when there is one,
+/// otherwise interpolates linearly between source frames.
+#[inline]
+fn sample_at(
+    audio_data: &AudioData,
+    converted: Option<&AudioData>,
+    index: f64,
+    rate_ratio: f64,
+    channel: usize,
+) -> f32 {
+    let channels = audio_data.channels as usize;
+    if let Some(converted) = converted {
+        let frame = (index / rate_ratio).round() as usize;
+        return converted
+            .samples
+            .get(frame * channels + channel)
+            .copied()
+            .unwrap_or(0.0);
+    }
+
+    let frame_idx = index as usize;
+    let frac = (index - frame_idx as f64) as f32;
+    let idx1 = frame_idx * channels + channel;
+    let idx2 = (frame_idx + 1) * channels + channel;
+    match (audio_data.samples.get(idx1), audio_data.samples.get(idx2)) {
+        (Some(&sample1), Some(&sample2)) => lerp_sample(sample1, sample2, frac),
+        (Some(&sample1), None) => sample1,
+        _ => 0.0,
     }
 }
 
@@ -508,11 +1133,19 @@ fn write_audio_u16(
 /// # Returns
 /// Scaled volume value (0.0 to 0.2 range)
 #[inline]
-#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) fn calculate_scaled_volume(volume: f32) -> f32 {
     volume.sqrt() * 0.2
 }
 
+/// Volume setting that plays `gain` times louder (linear) than `volume`
+///
+/// The output level follows `sqrt(volume)` (see `calculate_scaled_volume`),
+/// so a linear gain maps onto the volume setting squared.
+#[inline]
+pub fn volume_with_gain(volume: f32, gain: f32) -> f32 {
+    volume * gain * gain
+}
+
 /// Linear interpolation between two samples.
 ///
 /// # Arguments
@@ -523,7 +1156,6 @@ pub(crate) fn calculate_scaled_volume(volume: f32) -> f32 {
 /// # Returns
 /// Interpolated sample value
 #[inline]
-#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) fn lerp_sample(sample1: f32, sample2: f32, frac: f32) -> f32 {
     sample1 + (sample2 - sample1) * frac
 }
@@ -531,8 +1163,237 @@ pub(crate) fn lerp_sample(sample1: f32, sample2: f32, frac: f32) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::audio::decode_audio_file;
+    use crate::audio::stream::stream_audio_file;
+
+    use std::sync::mpsc;
+
+    fn mono(frames: usize) -> AudioData {
+        AudioData {
+            samples: vec![0.5; frames],
+            sample_rate: 48000,
+            channels: 1,
+        }
+    }
+
+    // Playback cursor tests
+    #[test]
+    fn test_cursor_advances_by_rate_ratio() {
+        let (tx, rx) = mpsc::channel();
+        let cursor = PlaybackCursor::new(10, tx);
+        let mut output = [0.0f32; 8];
+
+        write_audio_f32(&mut output, &mono(100), None, &cursor, 1.0, 2, 0.5, 100);
+
+        // 4 output frames at half the source rate
+        assert!((cursor.frame() - 12.0).abs() < 1e-9);
+        assert!(!cursor.is_finished());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_mix_renderer_follows_rate_and_end() {
+        let (tx, rx) = mpsc::channel();
+        let cursor = PlaybackCursor::new(0, tx);
+        let source = PlaybackSource::Decoded {
+            audio_data: Arc::new(mono(100)),
+            converted: None,
+        };
+        let master = Arc::new(AtomicU32::new(0.5f32.to_bits()));
+        let mut renderer = MixRenderer::new(
+            source,
+            Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            master,
+            cursor.clone(),
+            Some(80),
+            24000,
+            1,
+        );
+
+        // 30 frames at half the source rate cover 60 source frames
+        let mut output = [0.0f32; 30];
+        renderer.render(&mut output);
+        assert!((cursor.frame() - 60.0).abs() < 1e-9);
+        assert!(output[0] > 0.0 && output[0] < 0.5);
+        assert!(!cursor.is_finished());
+
+        renderer.render(&mut output);
+        assert!(cursor.is_finished());
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_cursor_signals_completion_once() {
+        let (tx, rx) = mpsc::channel();
+        let cursor = PlaybackCursor::new(0, tx);
+        let mut output = [1.0f32; 16];
+
+        write_audio_f32(&mut output, &mono(8), None, &cursor, 1.0, 1, 1.0, 4);
+        assert!(cursor.is_finished());
+        assert!(rx.try_recv().is_ok());
+        // Samples past the end frame are silent
+        assert_eq!(output[15], 0.0);
+
+        write_audio_f32(&mut output, &mono(8), None, &cursor, 1.0, 1, 1.0, 4);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_cursor_envelope_signals_progress_and_fade_out() {
+        let (tx, rx) = mpsc::channel();
+        let envelope = PlaybackEnvelope::new(48000, 0).with_progress(Duration::from_millis(1));
+        let cursor = PlaybackCursor::with_envelope(0, tx, envelope);
+        let source = PlaybackSource::Decoded {
+            audio_data: Arc::new(mono(4800)),
+            converted: None,
+        };
+        let mut renderer = MixRenderer::new(
+            source,
+            Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            cursor.clone(),
+            None,
+            48000,
+            1,
+        );
+
+        // 1ms played: one progress signal
+        let mut output = [0.0f32; 48];
+        renderer.render(&mut output);
+        assert_eq!(rx.try_recv(), Ok(PlaybackSignal::Progress));
+        assert!(output[0] > 0.0);
+
+        // Silent and finished once the fade-out ran, long before the end
+        cursor.fade_out(Duration::from_millis(1));
+        renderer.render(&mut output);
+        renderer.render(&mut output);
+        assert_eq!(output[47], 0.0);
+        assert!(cursor.is_finished());
+        let signals: Vec<_> = rx.try_iter().collect();
+        assert_eq!(signals.last(), Some(&PlaybackSignal::Finished));
+    }
+
+    #[test]
+    fn test_render_limited_chunks_large_buffers() {
+        let audio_data = Arc::new(AudioData {
+            samples: (0..1000).map(|i| (i as f32 / 50.0).sin()).collect(),
+            sample_rate: 48000,
+            channels: 1,
+        });
+        let reader = || SourceReader::Decoded {
+            audio_data: audio_data.clone(),
+            converted: None,
+        };
+
+        // Reference: the whole buffer rendered and limited at once
+        let (tx, _rx) = mpsc::channel();
+        let mut expected = vec![0.0f32; 600];
+        let mut limiter = Limiter::new(48000, 2);
+        render_f32(
+            &mut expected,
+            &mut reader(),
+            &PlaybackCursor::new(0, tx.clone()),
+            8.0,
+            2,
+            1.0,
+            1000,
+        );
+        limiter.process(&mut expected);
+
+        // Scratch of 128 frames covers the 300 frames in three chunks
+        let mut scratch = vec![0.0f32; 128 * 2];
+        let mut output = vec![0i16; 600];
+        let cursor = PlaybackCursor::new(0, tx);
+        render_limited(
+            &mut output,
+            &mut scratch,
+            &mut Limiter::new(48000, 2),
+            &mut reader(),
+            &cursor,
+            8.0,
+            2,
+            1.0,
+            1000,
+            |value| (value * 32767.0) as i16,
+        );
+
+        assert!((cursor.frame() - 300.0).abs() < 1e-9);
+        for (sample, value) in output.iter().zip(&expected) {
+            assert_eq!(*sample, (value * 32767.0) as i16);
+        }
+    }
+
+    #[test]
+    fn test_converted_buffer_replaces_interpolation() {
+        let (tx, _rx) = mpsc::channel();
+        let cursor = PlaybackCursor::new(10, tx);
+        // Output runs at twice the source rate: source frame 10 is frame 20
+        let converted = AudioData {
+            samples: (0..200).map(|i| i as f32 / 200.0).collect(),
+            sample_rate: 96000,
+            channels: 1,
+        };
+        let mut output = [0.0f32; 2];
+
+        write_audio_f32(
+            &mut output,
+            &mono(100),
+            Some(&converted),
+            &cursor,
+            1.0,
+            1,
+            0.5,
+            100,
+        );
+
+        let scaled = calculate_scaled_volume(1.0);
+        assert!((output[0] - 0.1 * scaled).abs() < 1e-6);
+        assert!((output[1] - 0.105 * scaled).abs() < 1e-6);
+        assert!((cursor.frame() - 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_streamed_source_plays_to_the_end() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("test_stereo.flac");
+        let path = path.to_str().unwrap();
+        let total_frames = decode_audio_file(path).unwrap().samples.len() / 2;
+        let (sample_rate, rings) = stream_audio_file(path, 0, None, 1).unwrap();
+        let source = PlaybackSource::Streamed {
+            ring: rings[0].clone(),
+            sample_rate,
+        };
+        let SourceReader::Streamed(mut frames) = SourceReader::new(source) else {
+            unreachable!();
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let cursor = PlaybackCursor::new(0, tx);
+        let mut output = [0.0f32; 512];
+        let mut peak = 0.0f32;
+        while !cursor.is_finished() {
+            write_streamed(&mut output, &mut frames, &cursor, 1.0, 2, 1.0, 0.0, |v| v);
+            peak = output.iter().fold(peak, |m, s| m.max(s.abs()));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        // The last frame has nothing to interpolate towards
+        assert!((cursor.frame() - (total_frames - 1) as f64).abs() < 1e-9);
+        assert!(rx.try_recv().is_ok());
+        assert!((peak - 0.5 * calculate_scaled_volume(1.0)).abs() < 0.01);
+    }
 
     // Volume curve tests
+    #[test]
+    fn test_volume_with_gain_scales_output_linearly() {
+        let base = calculate_scaled_volume(0.5);
+        let boosted = calculate_scaled_volume(volume_with_gain(0.5, 2.0));
+        assert!((boosted - base * 2.0).abs() < 0.0001);
+        assert_eq!(volume_with_gain(0.5, 1.0), 0.5);
+    }
+
     #[test]
     fn test_volume_curve_zero() {
         let result = calculate_scaled_volume(0.0);
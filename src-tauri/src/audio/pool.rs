@@ -0,0 +1,206 @@
+//! Bounded worker pools
+//!
+//! Jobs are queued on a channel and run by at most `max_workers` threads.
+//! Workers are spawned on demand (when no worker is idle) and then kept
+//! around, so a burst of triggers can't spawn an unbounded number of OS
+//! threads: excess jobs wait in the queue instead.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::Serialize;
+use tracing::{debug, error};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Worker pool load (for diagnostics)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PoolStatus {
+    /// Worker threads spawned so far
+    pub workers: usize,
+    /// Upper bound for worker threads
+    pub max_workers: usize,
+    /// Workers currently running a job
+    pub busy: usize,
+    /// Jobs waiting for a free worker
+    pub queued: usize,
+}
+
+#[derive(Default)]
+struct Counters {
+    spawned: AtomicUsize,
+    idle: AtomicUsize,
+    queued: AtomicUsize,
+}
+
+/// Fixed-capacity thread pool
+pub struct WorkerPool {
+    name: &'static str,
+    max_workers: usize,
+    sender: Mutex<Sender<Job>>,
+    receiver: Arc<Mutex<Receiver<Job>>>,
+    counters: Arc<Counters>,
+}
+
+impl WorkerPool {
+    /// Create a pool running at most `max_workers` jobs at once
+    pub fn new(name: &'static str, max_workers: usize) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            name,
+            max_workers: max_workers.max(1),
+            sender: Mutex::new(sender),
+            receiver: Arc::new(Mutex::new(receiver)),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    /// Queue a job; it runs as soon as a worker is free
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.counters.queued.fetch_add(1, Ordering::SeqCst);
+        if self.sender.lock().unwrap().send(Box::new(job)).is_err() {
+            // Receiver lives as long as the pool, so this can't happen
+            self.counters.queued.fetch_sub(1, Ordering::SeqCst);
+            error!("{} pool is shut down, job dropped", self.name);
+            return;
+        }
+
+        if self.counters.idle.load(Ordering::SeqCst) == 0 {
+            self.spawn_worker();
+        }
+    }
+
+    /// Current load
+    pub fn status(&self) -> PoolStatus {
+        let workers = self.counters.spawned.load(Ordering::SeqCst);
+        let idle = self.counters.idle.load(Ordering::SeqCst);
+        PoolStatus {
+            workers,
+            max_workers: self.max_workers,
+            busy: workers.saturating_sub(idle),
+            queued: self.counters.queued.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Spawn another worker unless the pool is at capacity
+    fn spawn_worker(&self) {
+        let max_workers = self.max_workers;
+        let Ok(previous) =
+            self.counters
+                .spawned
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                    (n < max_workers).then_some(n + 1)
+                })
+        else {
+            return;
+        };
+
+        let name = self.name;
+        let receiver = self.receiver.clone();
+        let counters = self.counters.clone();
+        let spawned = thread::Builder::new()
+            .name(format!("{}-{}", name, previous))
+            .spawn(move || worker_loop(name, &receiver, &counters));
+
+        match spawned {
+            Ok(_) => debug!("{} pool: started worker {}", name, previous),
+            Err(e) => {
+                self.counters.spawned.fetch_sub(1, Ordering::SeqCst);
+                error!("{} pool: failed to start worker: {}", name, e);
+            }
+        }
+    }
+}
+
+fn worker_loop(name: &str, receiver: &Mutex<Receiver<Job>>, counters: &Counters) {
+    loop {
+        counters.idle.fetch_add(1, Ordering::SeqCst);
+        let job = receiver.lock().unwrap().recv();
+        counters.idle.fetch_sub(1, Ordering::SeqCst);
+
+        let Ok(job) = job else {
+            // Pool dropped
+            counters.spawned.fetch_sub(1, Ordering::SeqCst);
+            return;
+        };
+        counters.queued.fetch_sub(1, Ordering::SeqCst);
+
+        // Keep the worker alive if a job panics
+        if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            error!("{} pool: job panicked", name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_pool_runs_jobs() {
+        let pool = WorkerPool::new("test", 2);
+        let (tx, rx) = mpsc::channel();
+
+        for i in 0..10 {
+            let tx = tx.clone();
+            pool.execute(move || tx.send(i).unwrap());
+        }
+
+        let mut results: Vec<i32> = (0..10)
+            .map(|_| rx.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect();
+        results.sort();
+        assert_eq!(results, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_pool_caps_workers_and_queues() {
+        let pool = WorkerPool::new("test", 2);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Arc::new(Mutex::new(release_rx));
+        let (started_tx, started_rx) = mpsc::channel();
+
+        for _ in 0..4 {
+            let release_rx = release_rx.clone();
+            let started_tx = started_tx.clone();
+            pool.execute(move || {
+                started_tx.send(()).unwrap();
+                let _ = release_rx.lock().unwrap().recv();
+            });
+        }
+
+        // Two jobs start, two wait in the queue
+        for _ in 0..2 {
+            started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        let status = pool.status();
+        assert_eq!(status.workers, 2);
+        assert_eq!(status.queued, 2);
+
+        for _ in 0..4 {
+            release_tx.send(()).unwrap();
+        }
+        for _ in 0..2 {
+            started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pool_survives_panicking_job() {
+        let pool = WorkerPool::new("test", 1);
+        let (tx, rx) = mpsc::channel();
+
+        pool.execute(|| panic!("job failed"));
+        pool.execute(move || tx.send(42).unwrap());
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 42);
+    }
+}
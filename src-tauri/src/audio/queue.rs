@@ -0,0 +1,161 @@
+//! Playback queue
+//!
+//! In queue mode triggered sounds wait for the previous one to end instead of
+//! overlapping. The queue only tracks sound IDs and which playback it started;
+//! starting the next sound is up to the caller (it needs the app state).
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+/// Sound started from the queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Current {
+    sound_id: String,
+    /// None until the playback was started
+    playback_id: Option<String>,
+}
+
+/// Sounds waiting to play, one after another
+#[derive(Debug, Default)]
+pub struct PlaybackQueue {
+    pending: VecDeque<String>,
+    current: Option<Current>,
+}
+
+/// Payload of `queue-updated`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct QueueSnapshot {
+    /// Sound playing from the queue
+    pub current: Option<String>,
+    /// Sounds waiting, next first
+    pub pending: Vec<String>,
+}
+
+impl PlaybackQueue {
+    /// Append a sound; returns true if nothing from the queue is playing,
+    /// so the caller should start the next sound right away
+    pub fn push(&mut self, sound_id: String) -> bool {
+        self.pending.push_back(sound_id);
+        self.current.is_none()
+    }
+
+    /// Take the next sound to play (None if empty or one is still playing)
+    pub fn start_next(&mut self) -> Option<String> {
+        if self.current.is_some() {
+            return None;
+        }
+        let sound_id = self.pending.pop_front()?;
+        self.current = Some(Current {
+            sound_id: sound_id.clone(),
+            playback_id: None,
+        });
+        Some(sound_id)
+    }
+
+    /// Record the playback started for the current sound
+    pub fn set_playback(&mut self, playback_id: String) {
+        if let Some(current) = self.current.as_mut() {
+            current.playback_id = Some(playback_id);
+        }
+    }
+
+    /// Drop the current sound without a playback (it couldn't be started)
+    pub fn abandon_current(&mut self) {
+        self.current = None;
+    }
+
+    /// A playback ended; returns true if it was the queue's current one
+    pub fn finish(&mut self, playback_id: &str) -> bool {
+        let is_current = self
+            .current
+            .as_ref()
+            .is_some_and(|c| c.playback_id.as_deref() == Some(playback_id));
+        if is_current {
+            self.current = None;
+        }
+        is_current
+    }
+
+    /// Playback of the current sound, to stop it when skipping
+    pub fn current_playback(&self) -> Option<&str> {
+        self.current.as_ref()?.playback_id.as_deref()
+    }
+
+    /// Remove the waiting sounds (the current one keeps playing)
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Forget everything, e.g. after stop all
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.current = None;
+    }
+
+    pub fn snapshot(&self) -> QueueSnapshot {
+        QueueSnapshot {
+            current: self.current.as_ref().map(|c| c.sound_id.clone()),
+            pending: self.pending.iter().cloned().collect(),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_plays_sequentially() {
+        let mut queue = PlaybackQueue::default();
+        assert!(queue.push("a".to_string()));
+        assert_eq!(queue.start_next().as_deref(), Some("a"));
+        queue.set_playback("playback_1".to_string());
+
+        // Queued while "a" plays: waits
+        assert!(!queue.push("b".to_string()));
+        assert_eq!(queue.start_next(), None);
+        assert_eq!(
+            queue.snapshot(),
+            QueueSnapshot {
+                current: Some("a".to_string()),
+                pending: vec!["b".to_string()],
+            }
+        );
+
+        // Other playbacks ending don't advance the queue
+        assert!(!queue.finish("playback_7"));
+        assert!(queue.finish("playback_1"));
+        assert_eq!(queue.start_next().as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_clear_keeps_current() {
+        let mut queue = PlaybackQueue::default();
+        queue.push("a".to_string());
+        queue.push("b".to_string());
+        queue.start_next();
+        queue.set_playback("playback_1".to_string());
+
+        queue.clear_pending();
+        assert_eq!(queue.current_playback(), Some("playback_1"));
+        assert!(queue.snapshot().pending.is_empty());
+
+        queue.reset();
+        assert_eq!(queue.snapshot(), QueueSnapshot::default());
+    }
+
+    #[test]
+    fn test_abandoned_sound_frees_the_queue() {
+        let mut queue = PlaybackQueue::default();
+        queue.push("missing".to_string());
+        queue.push("b".to_string());
+        assert_eq!(queue.start_next().as_deref(), Some("missing"));
+        queue.abandon_current();
+        assert_eq!(queue.start_next().as_deref(), Some("b"));
+    }
+}
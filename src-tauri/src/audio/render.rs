@@ -0,0 +1,214 @@
+//! Rendering audio to WAV files
+//!
+//! `WavWriter` streams 16-bit PCM to disk as it arrives (recordings).
+//! `render_region` cuts decoded audio down to a sound's trim region with
+//! fades, so trims can be baked into a file of their own.
+
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use super::AudioData;
+
+/// 16-bit PCM WAV file written as samples arrive; `finish` fills in the
+/// sizes in the header
+pub struct WavWriter {
+    file: BufWriter<File>,
+    data_bytes: u32,
+}
+
+impl WavWriter {
+    pub fn create(path: &Path, channels: u16, sample_rate: u32) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut writer = Self {
+            file: BufWriter::new(file),
+            data_bytes: 0,
+        };
+
+        let block_align = channels * 2;
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&36u32.to_le_bytes()); // filled in by finish
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        header.extend_from_slice(&channels.to_le_bytes());
+        header.extend_from_slice(&sample_rate.to_le_bytes());
+        header.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&0u32.to_le_bytes()); // filled in by finish
+        writer.write_bytes(&header)?;
+        Ok(writer)
+    }
+
+    pub fn write(&mut self, samples: &[f32]) -> Result<(), String> {
+        let bytes: Vec<u8> = samples
+            .iter()
+            .flat_map(|s| ((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+            .collect();
+        self.write_bytes(&bytes)?;
+        self.data_bytes = self.data_bytes.saturating_add(bytes.len() as u32);
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.file
+            .write_all(bytes)
+            .map_err(|e| format!("Failed to write recording: {}", e))
+    }
+
+    pub fn finish(mut self) -> Result<(), String> {
+        let sizes = [(4, 36 + self.data_bytes), (40, self.data_bytes)];
+        for (offset, size) in sizes {
+            self.file
+                .seek(SeekFrom::Start(offset))
+                .and_then(|_| self.file.write_all(&size.to_le_bytes()))
+                .map_err(|e| format!("Failed to finish recording: {}", e))?;
+        }
+        self.file
+            .flush()
+            .map_err(|e| format!("Failed to finish recording: {}", e))
+    }
+}
+
+/// Write `audio` as a 16-bit PCM WAV file
+pub fn write_wav(path: &Path, audio: &AudioData) -> Result<(), String> {
+    let mut writer = WavWriter::create(path, audio.channels, audio.sample_rate)?;
+    writer.write(&audio.samples)?;
+    writer.finish()
+}
+
+/// The part of `audio` between `start_ms` and `end_ms` (whole length if
+/// `None`), faded in and out over the given lengths
+///
+/// Fades longer than the region are shortened to it. Fails if the region
+/// is empty.
+pub fn render_region(
+    audio: &AudioData,
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+    fade_in_ms: u64,
+    fade_out_ms: u64,
+) -> Result<AudioData, String> {
+    let channels = audio.channels.max(1) as usize;
+    let frames = audio.samples.len() / channels;
+    let to_frames = |ms: u64| ((ms * audio.sample_rate as u64 / 1000) as usize).min(frames);
+
+    let start = start_ms.map_or(0, to_frames);
+    let end = end_ms.map_or(frames, to_frames);
+    if start >= end {
+        return Err("Trim region is empty".to_string());
+    }
+    let length = end - start;
+    let fade_in = to_frames(fade_in_ms).min(length);
+    let fade_out = to_frames(fade_out_ms).min(length);
+
+    let mut samples = audio.samples[start * channels..end * channels].to_vec();
+    for (frame, samples) in samples.chunks_exact_mut(channels).enumerate() {
+        let mut gain = 1.0;
+        if frame < fade_in {
+            gain *= frame as f32 / fade_in as f32;
+        }
+        let remaining = length - 1 - frame;
+        if remaining < fade_out {
+            gain *= remaining as f32 / fade_out as f32;
+        }
+        if gain < 1.0 {
+            samples.iter_mut().for_each(|s| *s *= gain);
+        }
+    }
+
+    Ok(AudioData {
+        samples,
+        sample_rate: audio.sample_rate,
+        channels: audio.channels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant(frames: usize) -> AudioData {
+        AudioData {
+            samples: vec![1.0; frames * 2],
+            sample_rate: 1000,
+            channels: 2,
+        }
+    }
+
+    #[test]
+    fn test_wav_writer_produces_playable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.wav");
+        let mut writer = WavWriter::create(&path, 2, 44_100).unwrap();
+        writer.write(&[0.5, -0.5, 0.25, -0.25]).unwrap();
+        writer.write(&[1.5, -1.5]).unwrap();
+        writer.finish().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes.len(), 44 + 12);
+        assert_eq!(&bytes[4..8], &(36u32 + 12).to_le_bytes());
+        assert_eq!(&bytes[40..44], &12u32.to_le_bytes());
+
+        let decoded = crate::audio::decode_audio_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(decoded.sample_rate, 44_100);
+        assert_eq!(decoded.channels, 2);
+        assert_eq!(decoded.samples.len(), 6);
+        assert!((decoded.samples[0] - 0.5).abs() < 0.001);
+        // Out of range samples are clipped
+        assert!((decoded.samples[4] - 1.0).abs() < 0.001);
+        assert!((decoded.samples[5] + 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_render_region_cuts_trim_range() {
+        let mut audio = constant(100);
+        for (i, s) in audio.samples.iter_mut().enumerate() {
+            *s = (i / 2) as f32;
+        }
+
+        let region = render_region(&audio, Some(10), Some(30), 0, 0).unwrap();
+        assert_eq!(region.channels, 2);
+        assert_eq!(region.sample_rate, 1000);
+        assert_eq!(region.samples.len(), 40);
+        assert_eq!(region.samples[0], 10.0);
+        assert_eq!(region.samples[39], 29.0);
+
+        // Open ends and ends past the file
+        assert_eq!(
+            render_region(&audio, None, Some(5_000), 0, 0)
+                .unwrap()
+                .samples
+                .len(),
+            200
+        );
+        assert!(render_region(&audio, Some(50), Some(50), 0, 0).is_err());
+        assert!(render_region(&audio, Some(200), None, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_render_region_fades() {
+        let region = render_region(&constant(100), None, None, 10, 20).unwrap();
+        let left: Vec<f32> = region.samples.iter().step_by(2).copied().collect();
+
+        assert_eq!(left[0], 0.0);
+        assert!((left[5] - 0.5).abs() < 1e-6);
+        assert_eq!(left[10], 1.0);
+        assert_eq!(left[79], 1.0);
+        assert!((left[89] - 0.5).abs() < 1e-6);
+        assert_eq!(left[99], 0.0);
+        // Both channels get the same gain
+        assert_eq!(region.samples[10], region.samples[11]);
+    }
+
+    #[test]
+    fn test_render_region_shortens_long_fades() {
+        let region = render_region(&constant(10), None, None, 1_000, 0).unwrap();
+        assert_eq!(region.samples[0], 0.0);
+        assert!(region.samples[18] < 1.0);
+    }
+}
@@ -0,0 +1,130 @@
+//! Per-trigger output routing
+//!
+//! A trigger normally plays on both outputs. It can instead be limited to
+//! one of them, or alternate between monitor-only (A) and broadcast-only (B)
+//! segments to compare both paths with the same sound, e.g. to check
+//! whether VB-Cable resampling changes how it sounds.
+
+use serde::{Deserialize, Serialize};
+
+use super::DeviceRole;
+
+/// Shortest A/B segment (shorter segments are hard to judge)
+pub const MIN_SEGMENT_MS: u64 = 250;
+
+/// Crossfade at A/B switches, so they don't click or leave a gap
+const SWITCH_FADE_MS: u64 = 20;
+
+/// Which outputs a playback is audible on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum OutputRouting {
+    /// Monitor and broadcast (normal playback)
+    #[default]
+    Both,
+    MonitorOnly,
+    BroadcastOnly,
+    /// Alternate monitor-only and broadcast-only segments, starting with monitor
+    Alternate {
+        segment_ms: u64,
+    },
+}
+
+impl OutputRouting {
+    /// (monitor, broadcast) gain `elapsed_ms` into the playback
+    pub fn gains(self, elapsed_ms: u64) -> (f32, f32) {
+        match self {
+            OutputRouting::Both => (1.0, 1.0),
+            OutputRouting::MonitorOnly => (1.0, 0.0),
+            OutputRouting::BroadcastOnly => (0.0, 1.0),
+            OutputRouting::Alternate { segment_ms } => {
+                let segment_ms = segment_ms.max(MIN_SEGMENT_MS);
+                let segment = elapsed_ms / segment_ms;
+
+                // Fade from the previous segment's output to this one's
+                let fade_in = if segment == 0 {
+                    1.0
+                } else {
+                    ((elapsed_ms % segment_ms) as f32 / SWITCH_FADE_MS as f32).min(1.0)
+                };
+                if segment.is_multiple_of(2) {
+                    (fade_in, 1.0 - fade_in)
+                } else {
+                    (1.0 - fade_in, fade_in)
+                }
+            }
+        }
+    }
+
+    /// Output audible in the segment at `elapsed_ms` (None unless alternating)
+    pub fn segment_output(self, elapsed_ms: u64) -> Option<DeviceRole> {
+        match self {
+            OutputRouting::Alternate { segment_ms } => {
+                if (elapsed_ms / segment_ms.max(MIN_SEGMENT_MS)).is_multiple_of(2) {
+                    Some(DeviceRole::Monitor)
+                } else {
+                    Some(DeviceRole::Broadcast)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_routing_gains() {
+        assert_eq!(OutputRouting::Both.gains(5_000), (1.0, 1.0));
+        assert_eq!(OutputRouting::MonitorOnly.gains(0), (1.0, 0.0));
+        assert_eq!(OutputRouting::BroadcastOnly.gains(0), (0.0, 1.0));
+        assert_eq!(OutputRouting::Both.segment_output(0), None);
+    }
+
+    #[test]
+    fn test_alternate_segments() {
+        let routing = OutputRouting::Alternate { segment_ms: 1_000 };
+
+        // Segment A starts at full level on the monitor
+        assert_eq!(routing.gains(0), (1.0, 0.0));
+        assert_eq!(routing.gains(999), (1.0, 0.0));
+        assert_eq!(routing.segment_output(500), Some(DeviceRole::Monitor));
+
+        // Segment B crossfades over to broadcast
+        assert_eq!(routing.gains(1_000), (1.0, 0.0));
+        assert_eq!(routing.gains(1_010), (0.5, 0.5));
+        assert_eq!(routing.gains(1_020), (0.0, 1.0));
+        assert_eq!(routing.segment_output(1_500), Some(DeviceRole::Broadcast));
+
+        // And back to A
+        assert_eq!(routing.gains(2_500), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_alternate_minimum_segment() {
+        let routing = OutputRouting::Alternate { segment_ms: 0 };
+        assert_eq!(
+            routing.segment_output(MIN_SEGMENT_MS - 1),
+            Some(DeviceRole::Monitor)
+        );
+        assert_eq!(
+            routing.segment_output(MIN_SEGMENT_MS),
+            Some(DeviceRole::Broadcast)
+        );
+    }
+
+    #[test]
+    fn test_routing_serde() {
+        let routing: OutputRouting =
+            serde_json::from_str(r#"{"mode": "alternate", "segment_ms": 2000}"#).unwrap();
+        assert_eq!(routing, OutputRouting::Alternate { segment_ms: 2000 });
+        let routing: OutputRouting = serde_json::from_str(r#"{"mode": "monitor_only"}"#).unwrap();
+        assert_eq!(routing, OutputRouting::MonitorOnly);
+    }
+}
@@ -0,0 +1,229 @@
+//! Spectrogram generation from audio data
+//!
+//! Computes a short-time Fourier transform (STFT) magnitude matrix for the
+//! trim editor's spectrogram view.
+
+use std::time::Instant;
+use tracing::debug;
+
+use super::fft::{hann_window, magnitude_spectrum};
+use super::AudioData;
+
+/// Lower bound of the displayed dynamic range (dBFS)
+const MIN_DB: f32 = -90.0;
+
+/// Smallest and largest FFT frame sizes used for analysis
+const MIN_FFT_SIZE: usize = 256;
+const MAX_FFT_SIZE: usize = 8192;
+
+/// Maximum spectrogram dimensions accepted from the frontend
+pub const MAX_SPECTROGRAM_WIDTH: usize = 4096;
+pub const MAX_SPECTROGRAM_HEIGHT: usize = 1024;
+
+/// Spectrogram data for visualization
+#[derive(Clone, serde::Serialize)]
+pub struct SpectrogramData {
+    /// Number of time columns
+    pub width: usize,
+    /// Number of frequency rows
+    pub height: usize,
+    /// Column-major magnitudes (0-255): `data[col * height + row]`,
+    /// row 0 is the lowest frequency
+    pub data: Vec<u8>,
+    /// Highest frequency represented by the top row (Nyquist, in Hz)
+    pub max_frequency_hz: u32,
+    /// Duration in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Generate a quantized STFT magnitude matrix from audio data
+///
+/// Channels are mixed down to mono, each column is a Hann-windowed frame
+/// centered on its position in the file, and magnitudes are mapped from
+/// -90..0 dBFS onto 0..255.
+pub fn generate_spectrogram(
+    audio_data: &AudioData,
+    width: usize,
+    height: usize,
+) -> SpectrogramData {
+    let start = Instant::now();
+    let channels = audio_data.channels.max(1) as usize;
+    let total_frames = audio_data.samples.len() / channels;
+    let duration_ms = (total_frames as f64 / audio_data.sample_rate as f64 * 1000.0) as u64;
+    let max_frequency_hz = audio_data.sample_rate / 2;
+
+    if total_frames == 0 || width == 0 || height == 0 {
+        return SpectrogramData {
+            width: 0,
+            height: 0,
+            data: vec![],
+            max_frequency_hz,
+            duration_ms,
+        };
+    }
+
+    // Mono mixdown
+    let mono: Vec<f32> = audio_data
+        .samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+
+    // At least two FFT bins per output row so every row has data
+    let fft_size = (height * 2)
+        .next_power_of_two()
+        .clamp(MIN_FFT_SIZE, MAX_FFT_SIZE);
+    let window = hann_window(fft_size);
+    // A full-scale sine produces a peak magnitude of sum(window) / 2
+    let reference = window.iter().sum::<f32>() / 2.0;
+    let num_bins = fft_size / 2 + 1;
+
+    let mut data = Vec::with_capacity(width * height);
+    let mut frame = vec![0.0f32; fft_size];
+
+    for col in 0..width {
+        let center = ((col as f64 + 0.5) * total_frames as f64 / width as f64) as isize;
+        let frame_start = center - (fft_size / 2) as isize;
+
+        for (i, slot) in frame.iter_mut().enumerate() {
+            let idx = frame_start + i as isize;
+            let sample = if idx >= 0 && (idx as usize) < mono.len() {
+                mono[idx as usize]
+            } else {
+                0.0
+            };
+            *slot = sample * window[i];
+        }
+
+        let mags = magnitude_spectrum(&frame);
+
+        for row in 0..height {
+            // Take the loudest bin in this row's frequency band
+            let bin_start = row * num_bins / height;
+            let bin_end = ((row + 1) * num_bins / height).max(bin_start + 1);
+            let magnitude = mags[bin_start..bin_end.min(num_bins)]
+                .iter()
+                .cloned()
+                .fold(0.0f32, f32::max);
+            data.push(quantize_magnitude(magnitude / reference));
+        }
+    }
+
+    debug!(
+        duration_ms = start.elapsed().as_millis() as u64,
+        width = width,
+        height = height,
+        fft_size = fft_size,
+        "Spectrogram generation complete"
+    );
+
+    SpectrogramData {
+        width,
+        height,
+        data,
+        max_frequency_hz,
+        duration_ms,
+    }
+}
+
+/// Map a linear magnitude (1.0 = full scale) onto 0-255
+fn quantize_magnitude(magnitude: f32) -> u8 {
+    if magnitude <= 0.0 {
+        return 0;
+    }
+    let db = 20.0 * magnitude.log10();
+    let normalized = ((db - MIN_DB) / -MIN_DB).clamp(0.0, 1.0);
+    (normalized * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn create_sine(freq: f32, sample_rate: u32, frames: usize, channels: u16) -> AudioData {
+        let samples = (0..frames)
+            .flat_map(|i| {
+                let s = (2.0 * PI * freq * i as f32 / sample_rate as f32).sin();
+                std::iter::repeat_n(s, channels as usize)
+            })
+            .collect();
+        AudioData {
+            samples,
+            sample_rate,
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_spectrogram_empty_data() {
+        let audio = AudioData {
+            samples: vec![],
+            sample_rate: 48000,
+            channels: 2,
+        };
+        let spec = generate_spectrogram(&audio, 100, 64);
+
+        assert!(spec.data.is_empty());
+        assert_eq!(spec.width, 0);
+        assert_eq!(spec.duration_ms, 0);
+    }
+
+    #[test]
+    fn test_spectrogram_zero_dimensions() {
+        let audio = create_sine(1000.0, 48000, 4800, 1);
+        assert!(generate_spectrogram(&audio, 0, 64).data.is_empty());
+        assert!(generate_spectrogram(&audio, 64, 0).data.is_empty());
+    }
+
+    #[test]
+    fn test_spectrogram_dimensions() {
+        let audio = create_sine(1000.0, 48000, 48000, 2);
+        let spec = generate_spectrogram(&audio, 50, 32);
+
+        assert_eq!(spec.width, 50);
+        assert_eq!(spec.height, 32);
+        assert_eq!(spec.data.len(), 50 * 32);
+        assert_eq!(spec.max_frequency_hz, 24000);
+        assert_eq!(spec.duration_ms, 1000);
+    }
+
+    #[test]
+    fn test_spectrogram_sine_energy_in_expected_row() {
+        // 6 kHz at 48 kHz sample rate sits at 1/4 of Nyquist
+        let audio = create_sine(6000.0, 48000, 48000, 1);
+        let height = 16;
+        let spec = generate_spectrogram(&audio, 10, height);
+
+        let col = 5;
+        let column = &spec.data[col * height..(col + 1) * height];
+        let loudest_row = column
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, v)| **v)
+            .map(|(i, _)| i)
+            .unwrap();
+
+        assert_eq!(loudest_row, height / 4);
+        assert!(column[loudest_row] > 240);
+    }
+
+    #[test]
+    fn test_spectrogram_silence_is_zero() {
+        let audio = AudioData {
+            samples: vec![0.0; 9600],
+            sample_rate: 48000,
+            channels: 1,
+        };
+        let spec = generate_spectrogram(&audio, 8, 8);
+        assert!(spec.data.iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_quantize_magnitude_range() {
+        assert_eq!(quantize_magnitude(0.0), 0);
+        assert_eq!(quantize_magnitude(1.0), 255);
+        assert_eq!(quantize_magnitude(2.0), 255);
+        assert_eq!(quantize_magnitude(1e-6), 0);
+    }
+}
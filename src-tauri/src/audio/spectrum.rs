@@ -0,0 +1,312 @@
+//! Live spectrum analyzer for the monitor output
+//!
+//! Monitor stream callbacks copy their (mono mixed) output into a lock-free
+//! ring; an analyzer thread reads the newest samples at a fixed rate and
+//! reduces them to a few log-spaced band magnitudes for the UI visualizer.
+//! Every sound has its own stream, so concurrent sounds land in the ring
+//! block by block, which is close enough to the mix for coarse bands.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::info;
+
+use super::fft::{hann_window, magnitude_spectrum};
+
+/// Number of frequency bands reported to the UI
+pub const SPECTRUM_BANDS: usize = 20;
+
+/// Time between analyzer updates (20 Hz)
+const UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Samples per analysis frame (~43 ms at 48 kHz)
+const FFT_SIZE: usize = 2048;
+
+/// Ring capacity in samples (power of two, a few callbacks' worth)
+const RING_CAPACITY: usize = 8192;
+
+/// Band range (Hz), capped at Nyquist
+const MIN_FREQUENCY_HZ: f32 = 20.0;
+const MAX_FREQUENCY_HZ: f32 = 20_000.0;
+
+/// Lower bound of the reported dynamic range (dBFS)
+const MIN_DB: f32 = -90.0;
+
+// ============================================================================
+// Global State
+// ============================================================================
+
+/// Monitor streams only feed the ring while the analyzer runs
+static TAP_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Output sample rate of the most recent monitor callback
+static TAP_SAMPLE_RATE: AtomicU32 = AtomicU32::new(48_000);
+
+/// Newest monitor output samples
+static TAP: SampleRing = SampleRing::new();
+
+/// Stop signal of the running analyzer thread
+static ANALYZER_STOP: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+/// One analyzer update (payload of `spectrum-update`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpectrumFrame {
+    /// Band magnitudes from low to high frequency (0.0 = silent, 1.0 = full scale)
+    pub bands: Vec<f32>,
+}
+
+// ============================================================================
+// Sample Ring
+// ============================================================================
+
+/// Lossy multi-producer ring of f32 samples (stored as bits in atomics)
+///
+/// Writers reserve a block with a single `fetch_add` and never wait; the
+/// reader copies the newest samples and may see a block that is still being
+/// written, which only shows up as a glitch in one visualizer frame.
+struct SampleRing {
+    samples: [AtomicU32; RING_CAPACITY],
+    write_pos: AtomicUsize,
+}
+
+impl SampleRing {
+    const fn new() -> Self {
+        Self {
+            samples: [const { AtomicU32::new(0) }; RING_CAPACITY],
+            write_pos: AtomicUsize::new(0),
+        }
+    }
+
+    /// Append samples, overwriting the oldest ones
+    fn push(&self, block: impl ExactSizeIterator<Item = f32>) {
+        let start = self.write_pos.fetch_add(block.len(), Ordering::AcqRel);
+        for (i, sample) in block.enumerate() {
+            self.samples[(start + i) % RING_CAPACITY].store(sample.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Copy the newest `out.len()` samples (oldest first)
+    ///
+    /// Returns the write position, which only changes when samples arrive.
+    fn latest(&self, out: &mut [f32]) -> usize {
+        let end = self.write_pos.load(Ordering::Acquire);
+        let len = out.len();
+        for (i, slot) in out.iter_mut().enumerate() {
+            *slot = if end + i >= len {
+                let index = (end + i - len) % RING_CAPACITY;
+                f32::from_bits(self.samples[index].load(Ordering::Relaxed))
+            } else {
+                0.0
+            };
+        }
+        end
+    }
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Feed a monitor stream's output buffer to the analyzer (audio callback)
+///
+/// Does nothing unless the analyzer runs. Frames are mixed to mono from the
+/// first two channels; extra surround channels are silent anyway.
+pub(crate) fn feed<T: Copy>(
+    output: &[T],
+    channels: usize,
+    sample_rate: u32,
+    to_f32: impl Fn(T) -> f32,
+) {
+    if !TAP_ENABLED.load(Ordering::Relaxed) || channels == 0 {
+        return;
+    }
+    TAP_SAMPLE_RATE.store(sample_rate, Ordering::Relaxed);
+
+    let mixed = channels.min(2);
+    TAP.push(
+        output
+            .chunks_exact(channels)
+            .map(|frame| frame[..mixed].iter().map(|s| to_f32(*s)).sum::<f32>() / mixed as f32),
+    );
+}
+
+/// Start (or restart) the analyzer thread
+///
+/// `on_frame` is called from the analyzer thread at up to 20 Hz while sound
+/// plays, and once with silent bands when playback stops.
+pub fn start_analyzer(on_frame: impl Fn(SpectrumFrame) + Send + 'static) {
+    stop_analyzer();
+
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    if let Ok(mut analyzer) = ANALYZER_STOP.lock() {
+        *analyzer = Some(stop_signal.clone());
+    }
+    TAP_ENABLED.store(true, Ordering::Relaxed);
+
+    thread::spawn(move || run_analyzer(&on_frame, &stop_signal));
+    info!("Spectrum analyzer started");
+}
+
+/// Stop the analyzer thread (it exits within one update interval)
+pub fn stop_analyzer() {
+    TAP_ENABLED.store(false, Ordering::Relaxed);
+    let stop_signal = ANALYZER_STOP.lock().ok().and_then(|mut a| a.take());
+    if let Some(stop_signal) = stop_signal {
+        stop_signal.store(true, Ordering::Relaxed);
+        info!("Spectrum analyzer stopped");
+    }
+}
+
+/// Reduce a mono frame to `bands` log-spaced band magnitudes (0.0-1.0)
+///
+/// `frame.len()` must be a power of two. Each band takes its loudest bin,
+/// mapped from -90..0 dBFS onto 0..1.
+pub fn band_magnitudes(frame: &[f32], sample_rate: u32, bands: usize) -> Vec<f32> {
+    let window = hann_window(frame.len());
+    // A full-scale sine produces a peak magnitude of sum(window) / 2
+    let reference = window.iter().sum::<f32>() / 2.0;
+    let windowed: Vec<f32> = frame.iter().zip(&window).map(|(s, w)| s * w).collect();
+    let mags = magnitude_spectrum(&windowed);
+
+    let bin_hz = sample_rate as f32 / frame.len() as f32;
+    let max_hz = MAX_FREQUENCY_HZ.min(sample_rate as f32 / 2.0);
+    let ratio = (max_hz / MIN_FREQUENCY_HZ).powf(1.0 / bands as f32);
+
+    (0..bands)
+        .map(|band| {
+            let low_hz = MIN_FREQUENCY_HZ * ratio.powi(band as i32);
+            let high_hz = low_hz * ratio;
+            let bin_start = ((low_hz / bin_hz) as usize).min(mags.len() - 1);
+            let bin_end = ((high_hz / bin_hz).ceil() as usize).clamp(bin_start + 1, mags.len());
+            let magnitude = mags[bin_start..bin_end]
+                .iter()
+                .cloned()
+                .fold(0.0f32, f32::max);
+            normalize_db(magnitude / reference)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Analyzer
+// ============================================================================
+
+/// Analyzer thread body: analyze the newest samples until stopped
+fn run_analyzer(on_frame: &impl Fn(SpectrumFrame), stop: &AtomicBool) {
+    lower_thread_priority();
+
+    let mut frame = vec![0.0f32; FFT_SIZE];
+    let mut last_write_pos = TAP.latest(&mut frame);
+    let mut silent = true;
+
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(UPDATE_INTERVAL);
+
+        let write_pos = TAP.latest(&mut frame);
+        if write_pos == last_write_pos {
+            // Nothing played since the last update: report silence once
+            if !silent {
+                on_frame(SpectrumFrame {
+                    bands: vec![0.0; SPECTRUM_BANDS],
+                });
+                silent = true;
+            }
+            continue;
+        }
+        last_write_pos = write_pos;
+        silent = false;
+
+        let sample_rate = TAP_SAMPLE_RATE.load(Ordering::Relaxed);
+        on_frame(SpectrumFrame {
+            bands: band_magnitudes(&frame, sample_rate, SPECTRUM_BANDS),
+        });
+    }
+}
+
+/// Keep the analyzer from competing with decode and UI threads
+#[cfg(windows)]
+fn lower_thread_priority() {
+    use windows::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL,
+    };
+
+    // SAFETY: GetCurrentThread returns a pseudo handle valid for this thread
+    if let Err(e) = unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_BELOW_NORMAL) } {
+        tracing::debug!("Failed to lower analyzer thread priority: {}", e);
+    }
+}
+
+#[cfg(not(windows))]
+fn lower_thread_priority() {}
+
+/// Map a linear magnitude (1.0 = full scale) onto 0..1
+fn normalize_db(magnitude: f32) -> f32 {
+    if magnitude <= 0.0 {
+        return 0.0;
+    }
+    let db = 20.0 * magnitude.log10();
+    ((db - MIN_DB) / -MIN_DB).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn sine(freq: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * PI * freq * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_band_magnitudes_peak_band() {
+        let bands = band_magnitudes(&sine(1000.0, 48_000, FFT_SIZE), 48_000, SPECTRUM_BANDS);
+        assert_eq!(bands.len(), SPECTRUM_BANDS);
+
+        let loudest = bands
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .unwrap();
+        // 1 kHz sits in the middle of the 20 Hz - 20 kHz log range
+        assert!((9..=11).contains(&loudest), "loudest band {}", loudest);
+        assert!(bands[loudest] > 0.9);
+        assert!(bands[0] < 0.5);
+    }
+
+    #[test]
+    fn test_band_magnitudes_silence() {
+        let bands = band_magnitudes(&[0.0; 1024], 44_100, 8);
+        assert!(bands.iter().all(|b| *b == 0.0));
+    }
+
+    #[test]
+    fn test_sample_ring_returns_newest() {
+        let ring = SampleRing::new();
+        let mut out = [0.0f32; 4];
+        assert_eq!(ring.latest(&mut out), 0);
+        assert_eq!(out, [0.0; 4]);
+
+        ring.push([1.0, 2.0].into_iter());
+        ring.latest(&mut out);
+        assert_eq!(out, [0.0, 0.0, 1.0, 2.0]);
+
+        ring.push((0..RING_CAPACITY + 3).map(|i| i as f32));
+        let end = ring.latest(&mut out);
+        assert_eq!(end, RING_CAPACITY + 5);
+        let last = (RING_CAPACITY + 2) as f32;
+        assert_eq!(out, [last - 3.0, last - 2.0, last - 1.0, last]);
+    }
+
+    #[test]
+    fn test_normalize_db() {
+        assert_eq!(normalize_db(0.0), 0.0);
+        assert_eq!(normalize_db(1.0), 1.0);
+        assert!((normalize_db(10f32.powf(-45.0 / 20.0)) - 0.5).abs() < 0.001);
+    }
+}
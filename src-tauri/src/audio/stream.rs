@@ -0,0 +1,312 @@
+//! Streaming decode for long files
+//!
+//! Long files (music beds, full songs) aren't decoded up front: a decoder
+//! thread keeps one ring per output stream a couple of seconds ahead of
+//! playback. Playback starts right away, and memory stays at a few seconds
+//! of audio instead of the whole file. Streamed files skip the cache.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use super::decode::OpenTrack;
+use super::downmix::downmix_to_stereo;
+use super::AudioError;
+
+/// Files longer than this are streamed instead of decoded up front
+pub const STREAMING_THRESHOLD_MS: u64 = 5 * 60 * 1000;
+
+/// Audio buffered ahead of each stream
+const RING_SECONDS: usize = 2;
+
+/// Decoder pause while a ring is full
+const FULL_RING_WAIT: Duration = Duration::from_millis(5);
+
+/// Sample rate and length of a file long enough to stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamInfo {
+    pub sample_rate: u32,
+    /// Length in source frames
+    pub frames: u64,
+}
+
+/// Whether `file_path` is long enough to stream
+///
+/// Reads the length from the container; files that don't state it are
+/// decoded up front.
+pub fn streaming_info(file_path: &str) -> Option<StreamInfo> {
+    let track = OpenTrack::open(file_path).ok()?;
+    let params = track.codec_params();
+    let sample_rate = params.sample_rate?;
+    let frames = params.n_frames?;
+    let duration_ms = frames * 1000 / sample_rate.max(1) as u64;
+    (duration_ms > STREAMING_THRESHOLD_MS).then_some(StreamInfo {
+        sample_rate,
+        frames,
+    })
+}
+
+// ============================================================================
+// Ring
+// ============================================================================
+
+/// Single-producer single-consumer ring of f32 samples (stored as bits in
+/// atomics)
+///
+/// The decoder thread pushes, one audio callback pops (for recordings, the
+/// input callback pushes and the writer thread pops). Neither side locks:
+/// the producer only writes free slots and publishes them with `write_pos`,
+/// the consumer only reads published ones and frees them with `read_pos`.
+pub struct StreamRing {
+    samples: Box<[AtomicU32]>,
+    read_pos: AtomicUsize,
+    write_pos: AtomicUsize,
+    /// Set once the decoder pushed its last sample
+    ended: AtomicBool,
+    channels: u16,
+}
+
+impl StreamRing {
+    pub fn new(capacity: usize, channels: u16) -> Self {
+        Self {
+            samples: (0..capacity).map(|_| AtomicU32::new(0)).collect(),
+            read_pos: AtomicUsize::new(0),
+            write_pos: AtomicUsize::new(0),
+            ended: AtomicBool::new(false),
+            channels,
+        }
+    }
+
+    /// Channels per frame
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Append as many samples as fit, returning how many did
+    pub fn push(&self, block: &[f32]) -> usize {
+        let write = self.write_pos.load(Ordering::Relaxed);
+        let read = self.read_pos.load(Ordering::Acquire);
+        let count = block.len().min(self.samples.len() - (write - read));
+        for (i, sample) in block[..count].iter().enumerate() {
+            self.samples[(write + i) % self.samples.len()]
+                .store(sample.to_bits(), Ordering::Relaxed);
+        }
+        self.write_pos.store(write + count, Ordering::Release);
+        count
+    }
+
+    /// Samples buffered and not yet popped
+    pub fn buffered(&self) -> usize {
+        self.write_pos.load(Ordering::Acquire) - self.read_pos.load(Ordering::Relaxed)
+    }
+
+    /// Take exactly `out.len()` samples; false (taking nothing) if fewer are
+    /// buffered
+    pub fn pop(&self, out: &mut [f32]) -> bool {
+        let read = self.read_pos.load(Ordering::Relaxed);
+        let write = self.write_pos.load(Ordering::Acquire);
+        if write - read < out.len() {
+            return false;
+        }
+        for (i, slot) in out.iter_mut().enumerate() {
+            *slot = f32::from_bits(
+                self.samples[(read + i) % self.samples.len()].load(Ordering::Relaxed),
+            );
+        }
+        self.read_pos.store(read + out.len(), Ordering::Release);
+        true
+    }
+
+    /// Whether the decoder is done and everything was played
+    pub fn is_drained(&self) -> bool {
+        self.ended.load(Ordering::Acquire)
+            && self.read_pos.load(Ordering::Relaxed) == self.write_pos.load(Ordering::Acquire)
+    }
+
+    fn finish(&self) {
+        self.ended.store(true, Ordering::Release);
+    }
+}
+
+// ============================================================================
+// Decoder thread
+// ============================================================================
+
+/// Start decoding `file_path` from `start_frame` into `outputs` rings
+///
+/// The first packet is decoded before returning, so errors surface here and
+/// the rate and channel count are known. Decoding stops at `end_frame`, and
+/// early once every ring was dropped (the playback ended).
+pub fn stream_audio_file(
+    file_path: &str,
+    start_frame: usize,
+    end_frame: Option<usize>,
+    outputs: usize,
+) -> Result<(u32, Vec<Arc<StreamRing>>), AudioError> {
+    let mut track = OpenTrack::open(file_path)?;
+    let mut skip = track.seek_to_frame(start_frame as u64);
+
+    let mut block = Vec::new();
+    let spec = track.next_block(&mut block)?.ok_or(AudioError::NoData)?;
+    let layout = spec.channels;
+    let source_channels = layout.count().max(1);
+    // Playback maps channels by index, so surround layouts become stereo
+    let channels = source_channels.min(2);
+    let capacity = spec.rate as usize * channels * RING_SECONDS;
+    let rings: Vec<Arc<StreamRing>> = (0..outputs)
+        .map(|_| Arc::new(StreamRing::new(capacity, channels as u16)))
+        .collect();
+
+    let mut remaining = end_frame.map(|end| end.saturating_sub(start_frame));
+    let thread_rings = rings.clone();
+    let file_path = file_path.to_string();
+    thread::Builder::new()
+        .name("stream-decoder".to_string())
+        .spawn(move || {
+            loop {
+                let mut samples = if source_channels > 2 {
+                    downmix_to_stereo(&block, layout)
+                } else {
+                    std::mem::take(&mut block)
+                };
+
+                let skipped = (skip * channels).min(samples.len());
+                samples.drain(..skipped);
+                skip -= skipped / channels;
+                if let Some(remaining) = &mut remaining {
+                    samples.truncate(*remaining * channels);
+                    *remaining -= samples.len() / channels;
+                }
+
+                if !push_all(&thread_rings, &samples) || remaining == Some(0) {
+                    break;
+                }
+
+                block.clear();
+                match track.next_block(&mut block) {
+                    Ok(Some(_)) => {}
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!(file_path = %file_path, "Streaming decode failed: {}", e);
+                        break;
+                    }
+                }
+            }
+            for ring in &thread_rings {
+                ring.finish();
+            }
+            debug!(file_path = %file_path, "Streaming decode finished");
+        })
+        .map_err(|e| AudioError::Decode(format!("Failed to start decoder thread: {}", e)))?;
+
+    Ok((spec.rate, rings))
+}
+
+/// Push `samples` to every ring still played, waiting while one is full
+///
+/// Returns false once no ring is played anymore.
+fn push_all(rings: &[Arc<StreamRing>], samples: &[f32]) -> bool {
+    let mut pushed = vec![0; rings.len()];
+    loop {
+        let mut listening = false;
+        let mut done = true;
+        for (ring, pushed) in rings.iter().zip(pushed.iter_mut()) {
+            // Only the decoder holds it: the stream is gone
+            if Arc::strong_count(ring) == 1 {
+                continue;
+            }
+            listening = true;
+            *pushed += ring.push(&samples[*pushed..]);
+            done &= *pushed == samples.len();
+        }
+        if !listening {
+            return false;
+        }
+        if done {
+            return true;
+        }
+        thread::sleep(FULL_RING_WAIT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::decode_audio_file;
+    use std::path::PathBuf;
+
+    fn fixture(filename: &str) -> String {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(filename)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Pop everything the decoder produces, frame by frame
+    fn drain(ring: &StreamRing) -> Vec<f32> {
+        let mut samples = Vec::new();
+        let mut frame = vec![0.0; ring.channels() as usize];
+        while !ring.is_drained() {
+            if ring.pop(&mut frame) {
+                samples.extend_from_slice(&frame);
+            } else {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn test_ring_push_pop_wraps() {
+        let ring = StreamRing::new(4, 1);
+        assert_eq!(ring.push(&[1.0, 2.0, 3.0]), 3);
+        assert_eq!(ring.push(&[4.0, 5.0]), 1);
+        assert_eq!(ring.buffered(), 4);
+
+        let mut out = [0.0; 2];
+        assert!(ring.pop(&mut out));
+        assert_eq!(out, [1.0, 2.0]);
+        assert_eq!(ring.push(&[5.0, 6.0]), 2);
+
+        let mut out = [0.0; 4];
+        assert!(ring.pop(&mut out));
+        assert_eq!(out, [3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(ring.buffered(), 0);
+        assert!(!ring.pop(&mut out[..1]));
+        assert!(!ring.is_drained());
+        ring.finish();
+        assert!(ring.is_drained());
+    }
+
+    #[test]
+    fn test_streamed_matches_full_decode() {
+        let path = fixture("test_stereo.flac");
+        let full = decode_audio_file(&path).unwrap();
+
+        let (sample_rate, rings) = stream_audio_file(&path, 0, None, 2).unwrap();
+        assert_eq!(sample_rate, full.sample_rate);
+        for ring in &rings {
+            assert_eq!(ring.channels(), full.channels);
+            assert_eq!(drain(ring), full.samples);
+        }
+    }
+
+    #[test]
+    fn test_streamed_range() {
+        let path = fixture("test_stereo.flac");
+        let full = decode_audio_file(&path).unwrap();
+
+        let (_, rings) = stream_audio_file(&path, 1000, Some(31000), 1).unwrap();
+        assert_eq!(drain(&rings[0]), full.samples[2000..62000]);
+    }
+
+    #[test]
+    fn test_short_files_are_not_streamed() {
+        assert_eq!(streaming_info(&fixture("test_stereo.flac")), None);
+        assert_eq!(streaming_info("/nonexistent/path/audio.mp3"), None);
+    }
+}
@@ -0,0 +1,229 @@
+//! Time stretching (WSOLA)
+//!
+//! Changes how long a sound plays without changing its pitch. Waveform
+//! similarity overlap-add cuts the input into overlapping Hann-windowed
+//! segments, spaces them out (or packs them together) on the output and
+//! shifts each one by up to `SEEK_MS` so it lines up with the waveform the
+//! previous segment left off with, which avoids the phasing of a plain
+//! overlap-add.
+
+use super::AudioData;
+
+/// Largest stretch or compression factor `fit_to_duration` applies
+pub const MAX_STRETCH_FACTOR: f64 = 4.0;
+
+/// Segment length
+const WINDOW_MS: f64 = 30.0;
+
+/// How far a segment may shift from its nominal position to line up
+const SEEK_MS: f64 = 8.0;
+
+/// Every n-th frame of the overlap is compared when lining up segments
+const CORRELATION_STRIDE: usize = 4;
+
+/// Sum of all channels of a frame (0.0 outside the buffer)
+fn mono(samples: &[f32], channels: usize, frame: usize) -> f32 {
+    samples
+        .get(frame * channels..(frame + 1) * channels)
+        .map_or(0.0, |f| f.iter().sum())
+}
+
+/// Stretch interleaved `samples` so they last exactly `target_frames` frames
+pub fn stretch_to_frames(
+    samples: &[f32],
+    channels: usize,
+    sample_rate: u32,
+    target_frames: usize,
+) -> Vec<f32> {
+    let input_frames = samples.len() / channels.max(1);
+    if input_frames == 0 || target_frames == input_frames {
+        let mut out = samples.to_vec();
+        out.resize(target_frames * channels, 0.0);
+        return out;
+    }
+
+    let window = ((WINDOW_MS / 1000.0 * sample_rate as f64) as usize).max(4) & !1;
+    let seek = (SEEK_MS / 1000.0 * sample_rate as f64) as isize;
+    let synthesis_hop = window / 2;
+    let analysis_hop = synthesis_hop as f64 * input_frames as f64 / target_frames as f64;
+    let hann: Vec<f32> = (0..window)
+        .map(|i| {
+            let phase = std::f32::consts::TAU * i as f32 / window as f32;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect();
+
+    let mut out = vec![0.0f32; (target_frames + window) * channels];
+    let mut weights = vec![0.0f32; target_frames + window];
+    let mut previous: Option<usize> = None;
+
+    for k in 0.. {
+        let out_frame = k * synthesis_hop;
+        if out_frame >= target_frames {
+            break;
+        }
+        let nominal = (k as f64 * analysis_hop).round() as isize;
+
+        // Line up with the natural continuation of the previous segment
+        let start = match previous {
+            None => nominal.max(0) as usize,
+            Some(previous) => {
+                let continuation = previous + synthesis_hop;
+                let mut best = (nominal.max(0) as usize, f32::MIN);
+                for delta in -seek..=seek {
+                    let candidate = nominal + delta;
+                    if candidate < 0 || candidate as usize >= input_frames {
+                        continue;
+                    }
+                    let candidate = candidate as usize;
+                    let score: f32 = (0..synthesis_hop)
+                        .step_by(CORRELATION_STRIDE)
+                        .map(|i| {
+                            mono(samples, channels, candidate + i)
+                                * mono(samples, channels, continuation + i)
+                        })
+                        .sum();
+                    if score > best.1 {
+                        best = (candidate, score);
+                    }
+                }
+                best.0
+            }
+        };
+
+        for (i, w) in hann.iter().enumerate() {
+            let Some(frame) = samples.get((start + i) * channels..(start + i + 1) * channels)
+            else {
+                break;
+            };
+            let offset = (out_frame + i) * channels;
+            for (c, sample) in frame.iter().enumerate() {
+                out[offset + c] += sample * w;
+            }
+            weights[out_frame + i] += w;
+        }
+        previous = Some(start);
+    }
+
+    out.truncate(target_frames * channels);
+    for (frame, weight) in out.chunks_exact_mut(channels).zip(&weights) {
+        if *weight > 1e-3 {
+            frame.iter_mut().for_each(|s| *s /= weight);
+        }
+    }
+    out
+}
+
+/// Interleaved samples of the `start_ms`..`end_ms` range of `audio`
+pub(super) fn trimmed_range(
+    audio: &AudioData,
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+) -> &[f32] {
+    let channels = audio.channels.max(1) as usize;
+    let total_frames = audio.samples.len() / channels;
+    let to_frame = |ms: u64| (ms as f64 / 1000.0 * audio.sample_rate as f64) as usize;
+    let end = end_ms.map_or(total_frames, to_frame).min(total_frames);
+    let start = start_ms.map_or(0, to_frame).min(end);
+    &audio.samples[start * channels..end * channels]
+}
+
+/// The `start_ms`..`end_ms` range of `audio` stretched to last `target_ms`
+///
+/// The factor is limited to `MAX_STRETCH_FACTOR` either way, beyond that the
+/// result sounds too smeared or choppy to be useful.
+pub fn fit_to_duration(
+    audio: &AudioData,
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+    target_ms: u64,
+) -> AudioData {
+    let channels = audio.channels.max(1) as usize;
+    let range = trimmed_range(audio, start_ms, end_ms);
+
+    let source_frames = (range.len() / channels) as f64;
+    let target_frames = (target_ms as f64 / 1000.0 * audio.sample_rate as f64)
+        .floor()
+        .clamp(
+            source_frames / MAX_STRETCH_FACTOR,
+            source_frames * MAX_STRETCH_FACTOR,
+        );
+
+    AudioData {
+        samples: stretch_to_frames(range, channels, audio.sample_rate, target_frames as usize),
+        sample_rate: audio.sample_rate,
+        channels: audio.channels,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 44_100;
+
+    fn sine(freq: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|i| (std::f32::consts::TAU * freq * i as f32 / RATE as f32).sin() * 0.5)
+            .collect()
+    }
+
+    /// Zero crossings per second of a mono buffer
+    fn crossing_rate(samples: &[f32]) -> f32 {
+        let crossings = samples
+            .windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count();
+        crossings as f32 / (samples.len() as f32 / RATE as f32)
+    }
+
+    #[test]
+    fn test_output_has_target_length() {
+        let input = sine(440.0, RATE as usize / 4);
+        for target in [RATE as usize / 8, RATE as usize / 2, 12_345] {
+            assert_eq!(stretch_to_frames(&input, 1, RATE, target).len(), target);
+        }
+
+        let stereo: Vec<f32> = input.iter().flat_map(|s| [*s, -*s]).collect();
+        assert_eq!(stretch_to_frames(&stereo, 2, RATE, 1_000).len(), 2_000);
+    }
+
+    #[test]
+    fn test_stretch_keeps_pitch() {
+        let input = sine(441.0, RATE as usize / 2);
+        for target in [RATE as usize, RATE as usize / 4] {
+            let output = stretch_to_frames(&input, 1, RATE, target);
+            // Skip the edges, the first and last segments fade in/out
+            let middle = &output[target / 4..target * 3 / 4];
+            let rate = crossing_rate(middle);
+            assert!((rate - 882.0).abs() < 882.0 * 0.1, "crossing rate {}", rate);
+        }
+    }
+
+    #[test]
+    fn test_same_length_is_unchanged() {
+        let input = sine(440.0, 1_000);
+        assert_eq!(stretch_to_frames(&input, 1, RATE, 1_000), input);
+    }
+
+    #[test]
+    fn test_fit_to_duration_uses_trim_and_limits_factor() {
+        let audio = AudioData {
+            samples: sine(440.0, RATE as usize / 2),
+            sample_rate: RATE,
+            channels: 1,
+        };
+
+        // 200 ms trimmed range fitted to 300 ms
+        let fitted = fit_to_duration(&audio, Some(100), Some(300), 300);
+        assert_eq!(fitted.samples.len(), RATE as usize * 3 / 10);
+
+        // 500 ms can't be squeezed into 10 ms
+        let fitted = fit_to_duration(&audio, None, None, 10);
+        assert_eq!(fitted.samples.len(), RATE as usize / 8);
+    }
+}
@@ -0,0 +1,77 @@
+//! Test tone
+//!
+//! A short sine beep for checking which device an output plays on. It fades
+//! in and out so it doesn't click, and stays well below full scale so a
+//! device turned up loud isn't startling.
+
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use super::AudioData;
+
+/// Length of the test tone
+pub const TEST_TONE_DURATION: Duration = Duration::from_millis(800);
+
+/// Pitch of the test tone (A4)
+const TONE_HZ: f32 = 440.0;
+
+/// Peak level of the test tone (-12 dBFS)
+const TONE_LEVEL: f32 = 0.25;
+
+/// Fade at each end of the tone
+const FADE_MS: f32 = 20.0;
+
+/// Sample rate the tone is generated at (streams resample it as needed)
+const TONE_SAMPLE_RATE: u32 = 48_000;
+
+/// Generate the test tone (mono)
+pub fn test_tone() -> AudioData {
+    let rate = TONE_SAMPLE_RATE as f32;
+    let frames = (TEST_TONE_DURATION.as_secs_f32() * rate) as usize;
+    let fade_frames = FADE_MS / 1000.0 * rate;
+    let samples = (0..frames)
+        .map(|frame| {
+            let edge = frame.min(frames - 1 - frame) as f32;
+            let fade = (edge / fade_frames).min(1.0);
+            (TAU * TONE_HZ * frame as f32 / rate).sin() * TONE_LEVEL * fade
+        })
+        .collect();
+    AudioData {
+        samples,
+        sample_rate: TONE_SAMPLE_RATE,
+        channels: 1,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_length_and_level() {
+        let tone = test_tone();
+        assert_eq!(tone.channels, 1);
+        assert_eq!(tone.samples.len(), 38_400);
+        let peak = tone
+            .samples
+            .iter()
+            .fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!(peak > TONE_LEVEL * 0.99 && peak <= TONE_LEVEL);
+    }
+
+    #[test]
+    fn test_tone_fades_at_both_ends() {
+        let tone = test_tone();
+        let (first, last) = (tone.samples[0], tone.samples[tone.samples.len() - 1]);
+        assert_eq!(first, 0.0);
+        assert_eq!(last, 0.0);
+        // 20ms fade at 48kHz: still quiet after 2ms
+        assert!(tone.samples[..96]
+            .iter()
+            .all(|s| s.abs() < TONE_LEVEL * 0.1));
+    }
+}
@@ -1,12 +1,23 @@
 //! Waveform peak generation from audio data
 //!
-//! Generates amplitude peaks for visualization.
+//! Generates amplitude peaks for visualization. The peak scan runs over
+//! fixed-width chunks so it vectorizes (hour-long files stay quick to draw).
+//!
+//! `PeakPyramid` scans a file once and keeps its peaks at several
+//! resolutions, so any peak count, or a zoomed-in range, is answered from the
+//! pyramid without touching the samples again.
 
 use std::time::Instant;
 use tracing::debug;
 
 use super::AudioData;
 
+/// Running maxima kept side by side by `abs_peak` (one 256-bit register)
+const PEAK_LANES: usize = 8;
+
+/// Most peaks in a pyramid's finest level (bounds its size for long files)
+pub const MAX_PYRAMID_PEAKS: usize = 1 << 14;
+
 /// Waveform data for visualization
 #[derive(Clone, serde::Serialize)]
 pub struct WaveformData {
@@ -36,25 +47,12 @@ pub fn generate_peaks(audio_data: &AudioData, num_peaks: usize) -> WaveformData
     let mut peaks = Vec::with_capacity(num_peaks);
 
     for peak_idx in 0..num_peaks {
-        let start_frame = peak_idx * frames_per_peak;
         let end_frame = ((peak_idx + 1) * frames_per_peak).min(total_frames);
+        let start_frame = (peak_idx * frames_per_peak).min(end_frame);
 
-        let mut max_amplitude: f32 = 0.0;
-
-        // Find the maximum absolute amplitude in this segment
-        for frame in start_frame..end_frame {
-            for ch in 0..channels {
-                let sample_idx = frame * channels + ch;
-                if sample_idx < audio_data.samples.len() {
-                    let amplitude = audio_data.samples[sample_idx].abs();
-                    if amplitude > max_amplitude {
-                        max_amplitude = amplitude;
-                    }
-                }
-            }
-        }
-
-        peaks.push(max_amplitude);
+        // A segment's frames are contiguous, channels interleaved
+        let segment = &audio_data.samples[start_frame * channels..end_frame * channels];
+        peaks.push(abs_peak(segment));
     }
 
     // Normalize peaks to 0.0-1.0 range
@@ -77,6 +75,197 @@ pub fn generate_peaks(audio_data: &AudioData, num_peaks: usize) -> WaveformData
     WaveformData { peaks, duration_ms }
 }
 
+/// Peaks of a file at several resolutions, for zooming without rescanning
+///
+/// The finest level holds the absolute peak of every `frames_per_peak`
+/// frames; each level above is built from the one below it, keeping the
+/// larger of every two neighbours, up to a single peak. Peaks are stored as
+/// measured and normalized to the file's loudest peak when read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeakPyramid {
+    sample_rate: u32,
+    total_frames: usize,
+    frames_per_peak: usize,
+    levels: Vec<Vec<f32>>,
+}
+
+impl PeakPyramid {
+    /// Scan `audio_data` into a pyramid
+    pub fn from_audio(audio_data: &AudioData) -> Self {
+        let start = Instant::now();
+        let channels = (audio_data.channels as usize).max(1);
+        let total_frames = audio_data.samples.len() / channels;
+        let frames_per_peak = total_frames.div_ceil(MAX_PYRAMID_PEAKS).max(1);
+        let finest = audio_data.samples[..total_frames * channels]
+            .chunks(frames_per_peak * channels)
+            .map(abs_peak)
+            .collect();
+
+        let pyramid = Self::build(
+            audio_data.sample_rate,
+            total_frames,
+            frames_per_peak,
+            finest,
+        );
+        debug!(
+            duration_ms = start.elapsed().as_millis(),
+            total_frames = total_frames,
+            levels = pyramid.levels.len(),
+            "Peak pyramid built"
+        );
+        pyramid
+    }
+
+    /// Rebuild a pyramid from its finest level
+    ///
+    /// `None` if the level doesn't fit the frame count.
+    pub fn from_finest(
+        sample_rate: u32,
+        total_frames: usize,
+        frames_per_peak: usize,
+        finest: Vec<f32>,
+    ) -> Option<Self> {
+        if frames_per_peak == 0 || finest.len() != total_frames.div_ceil(frames_per_peak) {
+            return None;
+        }
+        Some(Self::build(
+            sample_rate,
+            total_frames,
+            frames_per_peak,
+            finest,
+        ))
+    }
+
+    /// Stack coarser levels on top of `finest`
+    fn build(
+        sample_rate: u32,
+        total_frames: usize,
+        frames_per_peak: usize,
+        finest: Vec<f32>,
+    ) -> Self {
+        let mut levels = vec![finest];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let coarser = level
+                .chunks(2)
+                .map(|pair| pair.iter().fold(0.0f32, |peak, &p| peak.max(p)))
+                .collect();
+            levels.push(coarser);
+        }
+        Self {
+            sample_rate,
+            total_frames,
+            frames_per_peak,
+            levels,
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn total_frames(&self) -> usize {
+        self.total_frames
+    }
+
+    /// Frames covered by each peak of the finest level
+    pub fn frames_per_peak(&self) -> usize {
+        self.frames_per_peak
+    }
+
+    /// The finest level (enough to rebuild the pyramid)
+    pub fn finest(&self) -> &[f32] {
+        &self.levels[0]
+    }
+
+    /// Length of the file in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        if self.sample_rate == 0 {
+            return 0;
+        }
+        (self.total_frames as f64 / self.sample_rate as f64 * 1000.0) as u64
+    }
+
+    /// `num_peaks` peaks over the whole file
+    pub fn peaks(&self, num_peaks: usize) -> WaveformData {
+        self.peaks_in_range(0, self.duration_ms(), num_peaks)
+    }
+
+    /// `num_peaks` peaks between `start_ms` and `end_ms`
+    ///
+    /// Each peak comes from the coarsest level whose peaks are no wider than
+    /// it. Peaks are normalized to the loudest peak of the whole file, so a
+    /// zoomed-in range is drawn at the same scale as the full waveform;
+    /// `duration_ms` is the file's.
+    pub fn peaks_in_range(&self, start_ms: u64, end_ms: u64, num_peaks: usize) -> WaveformData {
+        let duration_ms = self.duration_ms();
+        let start = self.frame_at(start_ms);
+        let end = self.frame_at(end_ms).max(start);
+        if end == start || num_peaks == 0 {
+            return WaveformData {
+                peaks: vec![],
+                duration_ms,
+            };
+        }
+
+        let frames_per_output = (end - start) as f64 / num_peaks as f64;
+        let mut level = 0;
+        while level + 1 < self.levels.len()
+            && (self.frames_per_peak << (level + 1)) as f64 <= frames_per_output
+        {
+            level += 1;
+        }
+        let bucket = self.frames_per_peak << level;
+        let buckets = &self.levels[level];
+
+        let max_peak = self.levels.last().map_or(0.0, |top| top[0]);
+        let peaks = (0..num_peaks)
+            .map(|i| {
+                let from = start + (i as f64 * frames_per_output) as usize;
+                let to = start + ((i + 1) as f64 * frames_per_output) as usize;
+                let first = (from / bucket).min(buckets.len() - 1);
+                let last = to.min(end).div_ceil(bucket).clamp(first + 1, buckets.len());
+                let peak = buckets[first..last]
+                    .iter()
+                    .fold(0.0f32, |peak, &p| peak.max(p));
+                if max_peak > 0.0 {
+                    peak / max_peak
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        WaveformData { peaks, duration_ms }
+    }
+
+    /// Frame at `ms`, clamped to the file
+    fn frame_at(&self, ms: u64) -> usize {
+        let frame = (ms as f64 / 1000.0 * self.sample_rate as f64) as usize;
+        frame.min(self.total_frames)
+    }
+}
+
+/// Largest absolute sample value (0.0 for no samples)
+///
+/// Keeps `PEAK_LANES` running maxima over fixed-size chunks, which the
+/// compiler turns into SIMD max instructions, and combines them at the end.
+pub(crate) fn abs_peak(samples: &[f32]) -> f32 {
+    let chunks = samples.chunks_exact(PEAK_LANES);
+    let rest = chunks.remainder();
+
+    let mut lanes = [0.0f32; PEAK_LANES];
+    for chunk in chunks {
+        for (lane, sample) in lanes.iter_mut().zip(chunk) {
+            let amplitude = sample.abs();
+            // Branch-free max (NaN samples are ignored)
+            *lane = if amplitude > *lane { amplitude } else { *lane };
+        }
+    }
+
+    let peak = lanes.iter().fold(0.0f32, |peak, &lane| peak.max(lane));
+    rest.iter().fold(peak, |peak, s| peak.max(s.abs()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +346,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_abs_peak_covers_chunks_and_remainder() {
+        assert_eq!(abs_peak(&[]), 0.0);
+        let mut samples = vec![0.25f32; 19];
+        samples[5] = -0.5;
+        assert_eq!(abs_peak(&samples), 0.5);
+        // Past the last full chunk
+        samples[18] = -0.75;
+        assert_eq!(abs_peak(&samples), 0.75);
+        samples[3] = f32::NAN;
+        assert_eq!(abs_peak(&samples), 0.75);
+    }
+
+    #[test]
+    fn test_peaks_match_per_sample_scan() {
+        let samples: Vec<f32> = (0..10_007)
+            .map(|i| ((i * 37 % 101) as f32 - 50.0) / 50.0)
+            .collect();
+        let audio = create_test_audio(samples.clone(), 48000, 2);
+        let waveform = generate_peaks(&audio, 64);
+
+        let frames_per_peak = 10_007 / 2 / 64;
+        let expected: Vec<f32> = samples[..64 * frames_per_peak * 2]
+            .chunks(frames_per_peak * 2)
+            .map(|segment| segment.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+            .collect();
+        let max = expected.iter().cloned().fold(0.0f32, f32::max);
+        for (peak, expected) in waveform.peaks.iter().zip(&expected) {
+            assert!((peak - expected / max).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_pyramid_matches_direct_scan() {
+        let samples: Vec<f32> = (0..8192)
+            .map(|i| ((i * 53 % 97) as f32 - 48.0) / 48.0)
+            .collect();
+        let audio = create_test_audio(samples, 48000, 2);
+        let pyramid = PeakPyramid::from_audio(&audio);
+
+        for num_peaks in [1, 16, 64, 400] {
+            let direct = generate_peaks(&audio, num_peaks);
+            let from_pyramid = pyramid.peaks(num_peaks);
+            assert_eq!(from_pyramid.duration_ms, direct.duration_ms);
+            if 4096 % num_peaks == 0 {
+                assert_eq!(from_pyramid.peaks, direct.peaks);
+            } else {
+                assert_eq!(from_pyramid.peaks.len(), num_peaks);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pyramid_range_keeps_file_scale() {
+        // Quiet first second, one loud sample in the second
+        let mut samples = vec![0.25f32; 2000];
+        samples[1500] = 1.0;
+        let audio = create_test_audio(samples, 1000, 1);
+        let pyramid = PeakPyramid::from_audio(&audio);
+
+        let quiet = pyramid.peaks_in_range(0, 1000, 10);
+        assert_eq!(quiet.duration_ms, 2000);
+        assert!(quiet.peaks.iter().all(|p| (p - 0.25).abs() < 1e-6));
+
+        let zoomed = pyramid.peaks_in_range(1400, 1600, 200);
+        assert_eq!(zoomed.peaks.len(), 200);
+        assert_eq!(zoomed.peaks[100], 1.0);
+        assert!((zoomed.peaks[0] - 0.25).abs() < 1e-6);
+
+        assert!(pyramid.peaks_in_range(1600, 1400, 10).peaks.is_empty());
+        assert!(pyramid.peaks_in_range(5000, 6000, 10).peaks.is_empty());
+    }
+
+    #[test]
+    fn test_pyramid_bounds_long_files() {
+        let frames = MAX_PYRAMID_PEAKS * 3 + 7;
+        let audio = create_test_audio(vec![0.5; frames], 48000, 1);
+        let pyramid = PeakPyramid::from_audio(&audio);
+
+        assert_eq!(pyramid.frames_per_peak(), 4);
+        assert!(pyramid.finest().len() <= MAX_PYRAMID_PEAKS);
+        assert_eq!(pyramid.peaks(400).peaks, vec![1.0; 400]);
+    }
+
+    #[test]
+    fn test_pyramid_rebuilds_from_finest_level() {
+        let samples: Vec<f32> = (0..1000).map(|i| (i as f32 / 1000.0).sin()).collect();
+        let pyramid = PeakPyramid::from_audio(&create_test_audio(samples, 44100, 1));
+
+        let rebuilt = PeakPyramid::from_finest(
+            pyramid.sample_rate(),
+            pyramid.total_frames(),
+            pyramid.frames_per_peak(),
+            pyramid.finest().to_vec(),
+        );
+        assert_eq!(rebuilt.as_ref(), Some(&pyramid));
+        // A level that doesn't fit the frame count
+        assert!(PeakPyramid::from_finest(44100, 1000, 1, vec![0.5; 999]).is_none());
+        assert!(PeakPyramid::from_finest(44100, 1000, 0, vec![]).is_none());
+    }
+
     #[test]
     fn test_duration_calculation() {
         // 48000 samples @ 48kHz mono = 1 second = 1000ms
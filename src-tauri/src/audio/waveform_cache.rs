@@ -0,0 +1,285 @@
+//! Waveform cache
+//!
+//! Peak pyramids are kept per file, in memory and as `.peaks` files in the
+//! app's `waveforms` directory, so a file is decoded and scanned once, not on
+//! every `get_waveform` call; the answers for each (file, peak count) are
+//! kept on top. Entries are invalidated when a file's size or modification
+//! time changes, like the grid card cache.
+
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lru::LruCache;
+use tracing::{debug, warn};
+
+use super::waveform::{PeakPyramid, WaveformData};
+use crate::card_assets::FileStamp;
+
+/// Pyramids kept in memory
+const MAX_PYRAMIDS: usize = 64;
+
+/// Waveform answers kept in memory
+const MAX_RESPONSES: usize = 256;
+
+/// Start of every `.peaks` file
+const PEAKS_MAGIC: &[u8; 4] = b"SDPK";
+
+/// Format of `.peaks` files; others are ignored and rebuilt
+const PEAKS_VERSION: u8 = 1;
+
+/// Pyramids and waveform answers per file
+pub struct WaveformCache {
+    pyramids: LruCache<String, (FileStamp, Arc<PeakPyramid>)>,
+    responses: LruCache<(String, usize), (FileStamp, WaveformData)>,
+    /// Where pyramids are persisted (`None`: memory only)
+    dir: Option<PathBuf>,
+}
+
+impl Default for WaveformCache {
+    fn default() -> Self {
+        Self {
+            pyramids: LruCache::new(NonZeroUsize::new(MAX_PYRAMIDS).unwrap()),
+            responses: LruCache::new(NonZeroUsize::new(MAX_RESPONSES).unwrap()),
+            dir: None,
+        }
+    }
+}
+
+impl WaveformCache {
+    /// Persist pyramids to `dir` from now on
+    pub fn set_dir(&mut self, dir: PathBuf) {
+        self.dir = Some(dir);
+    }
+
+    /// Cached waveform of a file with `num_peaks` peaks
+    pub fn response(
+        &mut self,
+        file_path: &str,
+        num_peaks: usize,
+        stamp: FileStamp,
+    ) -> Option<WaveformData> {
+        self.responses
+            .get(&(file_path.to_string(), num_peaks))
+            .filter(|(cached, _)| *cached == stamp)
+            .map(|(_, waveform)| waveform.clone())
+    }
+
+    pub fn insert_response(
+        &mut self,
+        file_path: &str,
+        num_peaks: usize,
+        stamp: FileStamp,
+        waveform: WaveformData,
+    ) {
+        self.responses
+            .put((file_path.to_string(), num_peaks), (stamp, waveform));
+    }
+
+    /// Pyramid of this version of a file, from memory or disk
+    pub fn pyramid(&mut self, file_path: &str, stamp: FileStamp) -> Option<Arc<PeakPyramid>> {
+        if let Some((cached, pyramid)) = self.pyramids.get(file_path) {
+            if *cached == stamp {
+                return Some(pyramid.clone());
+            }
+        }
+        let path = self.peaks_path(file_path)?;
+        let bytes = std::fs::read(path).ok()?;
+        let pyramid = Arc::new(decode_pyramid(&bytes, file_path, stamp)?);
+        debug!("Loaded peak pyramid of {} from disk", file_path);
+        self.pyramids
+            .put(file_path.to_string(), (stamp, pyramid.clone()));
+        Some(pyramid)
+    }
+
+    /// Keep a freshly built pyramid, writing it to disk
+    pub fn insert_pyramid(
+        &mut self,
+        file_path: &str,
+        stamp: FileStamp,
+        pyramid: PeakPyramid,
+    ) -> Arc<PeakPyramid> {
+        if let Some(path) = self.peaks_path(file_path) {
+            let bytes = encode_pyramid(&pyramid, file_path, stamp);
+            let written = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&path, bytes));
+            if let Err(e) = written {
+                warn!("Failed to save peak pyramid of {}: {}", file_path, e);
+            }
+        }
+        let pyramid = Arc::new(pyramid);
+        self.pyramids
+            .put(file_path.to_string(), (stamp, pyramid.clone()));
+        pyramid
+    }
+
+    /// Forget everything held in memory (`.peaks` files stay)
+    pub fn clear(&mut self) {
+        self.pyramids.clear();
+        self.responses.clear();
+    }
+
+    /// `.peaks` file of a sound file, named after a hash of its path
+    fn peaks_path(&self, file_path: &str) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        Some(dir.join(format!("{:016x}.peaks", path_hash(file_path))))
+    }
+}
+
+/// FNV-1a hash of a path (stable across builds, unlike `DefaultHasher`)
+fn path_hash(file_path: &str) -> u64 {
+    file_path.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// `.peaks` file contents: header, the file it describes, then the finest
+/// level (the coarser ones are rebuilt on load), all little-endian
+fn encode_pyramid(pyramid: &PeakPyramid, file_path: &str, stamp: FileStamp) -> Vec<u8> {
+    let finest = pyramid.finest();
+    let mut bytes = Vec::with_capacity(64 + file_path.len() + finest.len() * 4);
+    bytes.extend_from_slice(PEAKS_MAGIC);
+    bytes.push(PEAKS_VERSION);
+    bytes.extend_from_slice(&stamp.size.to_le_bytes());
+    bytes.extend_from_slice(&stamp.modified_ms.to_le_bytes());
+    bytes.extend_from_slice(&(file_path.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(file_path.as_bytes());
+    bytes.extend_from_slice(&pyramid.sample_rate().to_le_bytes());
+    bytes.extend_from_slice(&(pyramid.total_frames() as u64).to_le_bytes());
+    bytes.extend_from_slice(&(pyramid.frames_per_peak() as u64).to_le_bytes());
+    for peak in finest {
+        bytes.extend_from_slice(&peak.to_le_bytes());
+    }
+    bytes
+}
+
+/// Pyramid from `.peaks` file contents, `None` if they're unreadable or
+/// describe another file or version of it
+fn decode_pyramid(bytes: &[u8], file_path: &str, stamp: FileStamp) -> Option<PeakPyramid> {
+    let mut reader = Reader(bytes);
+    if reader.take(4)? != PEAKS_MAGIC || reader.take(1)?[0] != PEAKS_VERSION {
+        return None;
+    }
+    let saved_stamp = FileStamp {
+        size: reader.u64()?,
+        modified_ms: reader.u64()?,
+    };
+    let path_len = reader.u32()? as usize;
+    if saved_stamp != stamp || reader.take(path_len)? != file_path.as_bytes() {
+        return None;
+    }
+    let sample_rate = reader.u32()?;
+    let total_frames = usize::try_from(reader.u64()?).ok()?;
+    let frames_per_peak = usize::try_from(reader.u64()?).ok()?;
+    let rest = reader.0;
+    if rest.len() % 4 != 0 {
+        return None;
+    }
+    let finest = rest
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    PeakPyramid::from_finest(sample_rate, total_frames, frames_per_peak, finest)
+}
+
+/// Cursor over `.peaks` file contents
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioData;
+
+    fn stamp(size: u64) -> FileStamp {
+        FileStamp {
+            size,
+            modified_ms: 1_700_000_000_000,
+        }
+    }
+
+    fn pyramid() -> PeakPyramid {
+        let samples = (0..3000).map(|i| (i as f32 / 300.0).sin()).collect();
+        PeakPyramid::from_audio(&AudioData {
+            samples,
+            sample_rate: 48000,
+            channels: 2,
+        })
+    }
+
+    #[test]
+    fn test_peaks_file_roundtrip() {
+        let bytes = encode_pyramid(&pyramid(), "/a.mp3", stamp(10));
+
+        assert_eq!(decode_pyramid(&bytes, "/a.mp3", stamp(10)), Some(pyramid()));
+        // Another file, a changed file, a truncated file
+        assert_eq!(decode_pyramid(&bytes, "/b.mp3", stamp(10)), None);
+        assert_eq!(decode_pyramid(&bytes, "/a.mp3", stamp(11)), None);
+        assert_eq!(
+            decode_pyramid(&bytes[..bytes.len() - 4], "/a.mp3", stamp(10)),
+            None
+        );
+        assert_eq!(decode_pyramid(b"SDPK", "/a.mp3", stamp(10)), None);
+    }
+
+    #[test]
+    fn test_pyramid_persists_across_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = WaveformCache::default();
+        cache.set_dir(dir.path().to_path_buf());
+        cache.insert_pyramid("/a.mp3", stamp(10), pyramid());
+
+        // A new cache (app restart) finds it on disk
+        let mut restarted = WaveformCache::default();
+        restarted.set_dir(dir.path().to_path_buf());
+        assert_eq!(
+            restarted.pyramid("/a.mp3", stamp(10)).as_deref(),
+            Some(&pyramid())
+        );
+        assert!(restarted.pyramid("/a.mp3", stamp(11)).is_none());
+    }
+
+    #[test]
+    fn test_changed_file_misses_responses() {
+        let mut cache = WaveformCache::default();
+        let waveform = pyramid().peaks(100);
+        cache.insert_response("/a.mp3", 100, stamp(10), waveform.clone());
+
+        assert_eq!(
+            cache.response("/a.mp3", 100, stamp(10)).map(|w| w.peaks),
+            Some(waveform.peaks)
+        );
+        assert!(cache.response("/a.mp3", 100, stamp(11)).is_none());
+        assert!(cache.response("/a.mp3", 400, stamp(10)).is_none());
+
+        cache.clear();
+        assert!(cache.response("/a.mp3", 100, stamp(10)).is_none());
+    }
+
+    #[test]
+    fn test_path_hash_is_stable() {
+        assert_eq!(path_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(path_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(path_hash("/a.mp3"), path_hash("/b.mp3"));
+    }
+}
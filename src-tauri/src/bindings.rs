@@ -0,0 +1,266 @@
+//! Hotkey binding export
+//!
+//! Lists every bound hotkey with what it triggers, as JSON or CSV, so the
+//! bindings can be mirrored into OBS, Touch Portal or stream overlays. Each
+//! hotkey also comes in OBS's hotkey notation (`OBS_KEY_*` plus modifier
+//! flags, as stored in OBS scene collections).
+
+use serde::{Deserialize, Serialize};
+
+use crate::hotkeys::{self, HotkeyAction, HotkeyMappings};
+use crate::sounds::SoundLibrary;
+
+/// Output format of `export_bindings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingFormat {
+    Json,
+    Csv,
+}
+
+/// A hotkey in OBS's notation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ObsHotkey {
+    pub key: String,
+    pub control: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub command: bool,
+}
+
+/// One bound hotkey
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Binding {
+    /// Canonical hotkey, e.g. "Ctrl+Shift+A"
+    pub hotkey: String,
+    /// "play_sound", "bank_slot" or the action (e.g. "stop_all", "duck")
+    pub action: String,
+    /// Sound played or stopped
+    pub sound_id: Option<String>,
+    pub sound_name: Option<String>,
+    pub category_name: Option<String>,
+    /// Bank slot (1-9), duck attenuation (dB)
+    pub value: Option<u32>,
+    pub obs: ObsHotkey,
+}
+
+/// Translate a canonical hotkey to OBS's notation
+pub fn obs_hotkey(hotkey: &str) -> ObsHotkey {
+    let mut obs = ObsHotkey {
+        key: String::new(),
+        control: false,
+        shift: false,
+        alt: false,
+        command: false,
+    };
+    for part in hotkey.split('+') {
+        match part {
+            "Ctrl" => obs.control = true,
+            "Shift" => obs.shift = true,
+            "Alt" => obs.alt = true,
+            "Super" => obs.command = true,
+            key => {
+                let key = match key.strip_prefix("NumPad") {
+                    Some(rest) => format!("NUM{}", rest),
+                    None => key.to_uppercase(),
+                };
+                obs.key = format!("OBS_KEY_{}", key);
+            }
+        }
+    }
+    obs
+}
+
+/// All bound hotkeys, sorted by hotkey
+pub fn list_bindings(mappings: &HotkeyMappings, library: &SoundLibrary) -> Vec<Binding> {
+    let sound_binding = |hotkey: &str, action: &str, sound_id: &str, value: Option<u32>| {
+        let sound = library.sounds.iter().find(|s| s.id.as_str() == sound_id);
+        let category =
+            sound.and_then(|s| library.categories.iter().find(|c| c.id == s.category_id));
+        Binding {
+            hotkey: hotkey.to_string(),
+            action: action.to_string(),
+            sound_id: Some(sound_id.to_string()),
+            sound_name: sound.map(|s| s.name.clone()),
+            category_name: category.map(|c| c.name.clone()),
+            value,
+            obs: obs_hotkey(hotkey),
+        }
+    };
+    let other_binding = |hotkey: &str, action: &str, value: Option<u32>| Binding {
+        hotkey: hotkey.to_string(),
+        action: action.to_string(),
+        sound_id: None,
+        sound_name: None,
+        category_name: None,
+        value,
+        obs: obs_hotkey(hotkey),
+    };
+
+    let mut bindings: Vec<Binding> = mappings
+        .mappings
+        .iter()
+        .map(|(hotkey, sound_id)| sound_binding(hotkey, "play_sound", sound_id.as_str(), None))
+        .collect();
+
+    for (hotkey, action) in &mappings.actions {
+        bindings.push(match action {
+            HotkeyAction::PlaySound(id) => sound_binding(hotkey, "play_sound", id.as_str(), None),
+            HotkeyAction::StopSound(id) => sound_binding(hotkey, "stop_sound", id.as_str(), None),
+            HotkeyAction::Duck(db) => other_binding(hotkey, "duck", Some(*db)),
+            // Unit actions serialize as their name
+            action => {
+                let name = serde_json::to_value(action)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                other_binding(hotkey, &name, None)
+            }
+        });
+    }
+
+    if let Some(bank) = &mappings.bank {
+        for (slot, hotkey) in hotkeys::bank_hotkeys(bank).iter().enumerate() {
+            bindings.push(other_binding(hotkey, "bank_slot", Some(slot as u32 + 1)));
+        }
+    }
+
+    bindings.sort_by(|a, b| a.hotkey.cmp(&b.hotkey));
+    bindings
+}
+
+/// Render bindings in `format`
+pub fn render(bindings: &[Binding], format: BindingFormat) -> Result<String, String> {
+    match format {
+        BindingFormat::Json => serde_json::to_string_pretty(bindings)
+            .map_err(|e| format!("Failed to serialize bindings: {}", e)),
+        BindingFormat::Csv => Ok(to_csv(bindings)),
+    }
+}
+
+/// CSV with a header row (OBS notation as key plus modifier columns)
+fn to_csv(bindings: &[Binding]) -> String {
+    let mut csv = String::from(
+        "hotkey,action,sound_id,sound_name,category_name,value,obs_key,control,shift,alt,command\n",
+    );
+    for b in bindings {
+        let fields = [
+            b.hotkey.clone(),
+            b.action.clone(),
+            b.sound_id.clone().unwrap_or_default(),
+            b.sound_name.clone().unwrap_or_default(),
+            b.category_name.clone().unwrap_or_default(),
+            b.value.map(|v| v.to_string()).unwrap_or_default(),
+            b.obs.key.clone(),
+            b.obs.control.to_string(),
+            b.obs.shift.to_string(),
+            b.obs.alt.to_string(),
+            b.obs.command.to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hotkeys::HotkeyBank;
+    use crate::sounds::{self, CategoryId};
+
+    fn library_with_sound(name: &str) -> SoundLibrary {
+        let mut library = SoundLibrary::default();
+        let category_id = CategoryId::from_string("default".to_string());
+        sounds::add_sound(
+            &mut library,
+            name.to_string(),
+            "/sounds/horn.mp3".to_string(),
+            category_id,
+            None,
+            None,
+        );
+        library
+    }
+
+    #[test]
+    fn test_obs_hotkey_notation() {
+        assert_eq!(
+            obs_hotkey("Ctrl+Alt+F1"),
+            ObsHotkey {
+                key: "OBS_KEY_F1".to_string(),
+                control: true,
+                shift: false,
+                alt: true,
+                command: false,
+            }
+        );
+        assert_eq!(obs_hotkey("Shift+NumPad3").key, "OBS_KEY_NUM3");
+        assert_eq!(obs_hotkey("Super+a").key, "OBS_KEY_A");
+    }
+
+    #[test]
+    fn test_list_bindings_covers_sounds_actions_and_bank() {
+        let library = library_with_sound("Air Horn, loud");
+        let sound_id = library.sounds[0].id.clone();
+        let mut mappings = HotkeyMappings::default();
+        mappings
+            .mappings
+            .insert("Ctrl+H".to_string(), sound_id.clone());
+        mappings
+            .actions
+            .insert("Ctrl+S".to_string(), HotkeyAction::StopAll);
+        mappings
+            .actions
+            .insert("Ctrl+D".to_string(), HotkeyAction::Duck(12));
+        mappings.bank = Some(HotkeyBank {
+            modifiers: "Alt".to_string(),
+        });
+
+        let bindings = list_bindings(&mappings, &library);
+        assert_eq!(bindings.len(), 3 + hotkeys::BANK_SLOTS);
+
+        let horn = bindings.iter().find(|b| b.hotkey == "Ctrl+H").unwrap();
+        assert_eq!(horn.action, "play_sound");
+        assert_eq!(horn.sound_name.as_deref(), Some("Air Horn, loud"));
+        assert_eq!(horn.sound_id.as_deref(), Some(sound_id.as_str()));
+
+        let stop = bindings.iter().find(|b| b.hotkey == "Ctrl+S").unwrap();
+        assert_eq!(stop.action, "stop_all");
+        let duck = bindings.iter().find(|b| b.hotkey == "Ctrl+D").unwrap();
+        assert_eq!((duck.action.as_str(), duck.value), ("duck", Some(12)));
+        let slot = bindings.iter().find(|b| b.hotkey == "Alt+NumPad2").unwrap();
+        assert_eq!((slot.action.as_str(), slot.value), ("bank_slot", Some(2)));
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        let library = library_with_sound("Air Horn, \"loud\"");
+        let mut mappings = HotkeyMappings::default();
+        mappings
+            .mappings
+            .insert("Ctrl+H".to_string(), library.sounds[0].id.clone());
+
+        let csv = render(&list_bindings(&mappings, &library), BindingFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("hotkey,action,"));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("Ctrl+H,play_sound,"));
+        assert!(row.contains(",\"Air Horn, \"\"loud\"\"\","));
+        assert!(row.ends_with(",OBS_KEY_H,true,false,false,false"));
+    }
+}
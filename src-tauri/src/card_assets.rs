@@ -0,0 +1,302 @@
+//! Card assets for the sound grid
+//!
+//! Every button in the grid shows a tiny waveform, the duration and the icon.
+//! Fetching them one `get_waveform` call per card means hundreds of IPC calls
+//! and decodes when the grid opens; `get_card_assets` answers for many sounds
+//! at once from `card_assets.json`, a sidecar cache keyed by file path and
+//! invalidated when a file's size or modification time changes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use tauri::Manager;
+use tracing::{debug, warn};
+
+use crate::audio;
+use crate::sounds::{Sound, SoundId};
+
+/// Peaks per card waveform
+pub const CARD_PEAKS: usize = 64;
+
+/// Files decoded in parallel when computing missing assets
+const COMPUTE_WORKERS: usize = 4;
+
+/// Loaded sidecar cache (`None` until first use)
+static CACHE: Mutex<Option<CardAssetCache>> = Mutex::new(None);
+
+/// Size and modification time identifying a version of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified_ms: u64,
+}
+
+impl FileStamp {
+    /// Stamp of the file at `path` (`None` if it can't be read)
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let modified_ms = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_millis() as u64;
+        Some(Self {
+            size: metadata.len(),
+            modified_ms,
+        })
+    }
+}
+
+/// Precomputed waveform and duration of one file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedCard {
+    pub stamp: FileStamp,
+    pub peaks: Vec<f32>,
+    pub duration_ms: u64,
+}
+
+/// Cached cards keyed by file path
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CardAssetCache {
+    #[serde(default)]
+    entries: HashMap<String, CachedCard>,
+}
+
+impl CardAssetCache {
+    /// Cached card of a file, if it was computed for this version of it
+    pub fn get(&self, file_path: &str, stamp: FileStamp) -> Option<&CachedCard> {
+        self.entries
+            .get(file_path)
+            .filter(|card| card.stamp == stamp)
+    }
+
+    pub fn insert(&mut self, file_path: String, card: CachedCard) {
+        self.entries.insert(file_path, card);
+    }
+
+    /// Drop cards of files no sound uses anymore; true if any were dropped
+    pub fn retain_paths(&mut self, paths: &HashSet<&str>) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| paths.contains(path.as_str()));
+        self.entries.len() != before
+    }
+}
+
+/// Grid card data of one sound
+#[derive(Debug, Clone, Serialize)]
+pub struct CardAsset {
+    pub sound_id: SoundId,
+    /// `CARD_PEAKS` peak amplitudes (0.0-1.0), empty if the file can't be decoded
+    pub peaks: Vec<f32>,
+    /// Duration of the whole file, `None` if it can't be decoded
+    pub duration_ms: Option<u64>,
+    /// The sound's icon/emoji
+    pub icon: Option<String>,
+}
+
+/// Decode a file and compute its card
+fn compute_card(file_path: &str, stamp: FileStamp) -> Result<CachedCard, String> {
+    let audio_data = audio::decode_audio_file(file_path).map_err(|e| e.to_string())?;
+    let waveform = audio::generate_peaks(&audio_data, CARD_PEAKS);
+    Ok(CachedCard {
+        stamp,
+        peaks: waveform.peaks,
+        duration_ms: waveform.duration_ms,
+    })
+}
+
+/// Compute the cards of `files` on a few worker threads
+fn compute_cards(files: Vec<(String, FileStamp)>) -> Vec<(String, CachedCard)> {
+    if files.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = files.len().div_ceil(COMPUTE_WORKERS);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|(path, stamp)| match compute_card(path, *stamp) {
+                            Ok(card) => Some((path.clone(), card)),
+                            Err(e) => {
+                                warn!("Failed to compute card assets for {}: {}", path, e);
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Card assets of `sounds`, computing (and caching) the missing ones
+pub fn card_assets(app_handle: &tauri::AppHandle, sounds: &[Sound]) -> Vec<CardAsset> {
+    let stamps: HashMap<&str, FileStamp> = sounds
+        .iter()
+        .filter_map(|s| {
+            Some((
+                s.file_path.as_str(),
+                FileStamp::of(Path::new(&s.file_path))?,
+            ))
+        })
+        .collect();
+
+    let mut guard = CACHE.lock().unwrap();
+    let cache = guard.get_or_insert_with(|| load(app_handle));
+
+    let missing: Vec<(String, FileStamp)> = stamps
+        .iter()
+        .filter(|(path, stamp)| cache.get(path, **stamp).is_none())
+        .map(|(path, stamp)| (path.to_string(), *stamp))
+        .collect();
+    if !missing.is_empty() {
+        debug!("Computing card assets for {} files", missing.len());
+        for (path, card) in compute_cards(missing) {
+            cache.insert(path, card);
+        }
+        if let Err(e) = save(cache, app_handle) {
+            warn!("Failed to save card asset cache: {}", e);
+        }
+    }
+
+    sounds
+        .iter()
+        .map(|sound| {
+            let card = stamps
+                .get(sound.file_path.as_str())
+                .and_then(|stamp| cache.get(&sound.file_path, *stamp));
+            CardAsset {
+                sound_id: sound.id.clone(),
+                peaks: card.map(|c| c.peaks.clone()).unwrap_or_default(),
+                duration_ms: card.map(|c| c.duration_ms),
+                icon: sound.icon.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Precompute the cards of the whole library and forget removed files
+pub fn warm_up(app_handle: &tauri::AppHandle) {
+    let sounds = app_handle
+        .state::<crate::AppState>()
+        .read_sounds()
+        .sounds
+        .clone();
+    card_assets(app_handle, &sounds);
+
+    let paths: HashSet<&str> = sounds.iter().map(|s| s.file_path.as_str()).collect();
+    let mut guard = CACHE.lock().unwrap();
+    if let Some(cache) = guard.as_mut() {
+        if cache.retain_paths(&paths) {
+            if let Err(e) = save(cache, app_handle) {
+                warn!("Failed to save card asset cache: {}", e);
+            }
+        }
+    }
+}
+
+/// Get the path to the card asset cache file
+fn get_cache_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    // Ensure directory exists
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data_dir.join("card_assets.json"))
+}
+
+/// Load the cache from disk (empty if missing or unreadable, it's rebuilt)
+fn load(app_handle: &tauri::AppHandle) -> CardAssetCache {
+    let content = get_cache_path(app_handle)
+        .and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string()));
+    match content {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable card asset cache: {}", e);
+            CardAssetCache::default()
+        }),
+        Err(_) => CardAssetCache::default(),
+    }
+}
+
+/// Save the cache to disk (atomic write)
+fn save(cache: &CardAssetCache, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let path = get_cache_path(app_handle)?;
+    let json = serde_json::to_string(cache)
+        .map_err(|e| format!("Failed to serialize card asset cache: {}", e))?;
+    crate::persistence::atomic_write(&path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp(size: u64) -> FileStamp {
+        FileStamp {
+            size,
+            modified_ms: 1_700_000_000_000,
+        }
+    }
+
+    fn card(size: u64) -> CachedCard {
+        CachedCard {
+            stamp: stamp(size),
+            peaks: vec![0.5; CARD_PEAKS],
+            duration_ms: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_changed_file_misses_the_cache() {
+        let mut cache = CardAssetCache::default();
+        cache.insert("/a.mp3".to_string(), card(100));
+
+        assert!(cache.get("/a.mp3", stamp(100)).is_some());
+        assert!(cache.get("/a.mp3", stamp(101)).is_none());
+        assert!(cache.get("/b.mp3", stamp(100)).is_none());
+    }
+
+    #[test]
+    fn test_retain_paths_drops_removed_files() {
+        let mut cache = CardAssetCache::default();
+        cache.insert("/a.mp3".to_string(), card(1));
+        cache.insert("/b.mp3".to_string(), card(2));
+
+        let paths: HashSet<&str> = ["/a.mp3"].into_iter().collect();
+        assert!(cache.retain_paths(&paths));
+        assert!(!cache.retain_paths(&paths));
+        assert!(cache.get("/b.mp3", stamp(2)).is_none());
+    }
+
+    #[test]
+    fn test_cache_serde_roundtrip() {
+        let mut cache = CardAssetCache::default();
+        cache.insert("/a.mp3".to_string(), card(1));
+
+        let json = serde_json::to_string(&cache).unwrap();
+        let restored: CardAssetCache = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get("/a.mp3", stamp(1)), Some(&card(1)));
+    }
+
+    #[test]
+    fn test_file_stamp_of_missing_file() {
+        assert!(FileStamp::of(Path::new("/nonexistent/sound.mp3")).is_none());
+    }
+}
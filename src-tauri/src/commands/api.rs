@@ -0,0 +1,159 @@
+//! Local API server commands
+
+use serde::Serialize;
+use tauri::{Emitter, State};
+
+use crate::api;
+use crate::api_security::{self, ApiToken, AuditEntry, Capability};
+use crate::error::{AppError, ErrorCode};
+use crate::settings::AppSettings;
+use crate::AppState;
+
+/// Local API server state
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiStatus {
+    pub enabled: bool,
+    /// Port the server is listening on (None if not running)
+    pub running_port: Option<u16>,
+}
+
+/// Enable/disable the local API server and optionally change its port
+#[tauri::command]
+pub fn set_api_server(
+    enabled: bool,
+    port: Option<u16>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    if port == Some(0) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "API port must be between 1 and 65535",
+        ));
+    }
+
+    let mut settings = state.read_settings().clone();
+    settings.api_enabled = enabled;
+    if let Some(port) = port {
+        settings.api_port = port;
+    }
+
+    if enabled {
+        api::start(settings.api_port)?;
+    } else {
+        api::stop();
+    }
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Allow local API clients without a token while no token exists
+#[tauri::command]
+pub fn set_api_open_local_access(
+    open: bool,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    let mut settings = state.read_settings().clone();
+    settings.api_open_local_access = open;
+    api_security::set_open_local_access(open);
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    tracing::info!(
+        "Local API access without a token {}",
+        if open { "allowed" } else { "denied" }
+    );
+    Ok(settings)
+}
+
+/// Create an access token for an API client
+///
+/// Returns the token including its secret. From the first token on, the API
+/// rejects requests without a valid token, even with open local access.
+#[tauri::command]
+pub fn create_api_token(
+    name: String,
+    capabilities: Vec<Capability>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<ApiToken, AppError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Token name must not be empty",
+        ));
+    }
+    if capabilities.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "A token needs at least one capability",
+        ));
+    }
+
+    let token = ApiToken::generate(name, capabilities);
+    let mut settings = state.read_settings().clone();
+    settings.api_tokens.push(token.clone());
+    save_tokens(settings, &state, &app_handle)?;
+
+    tracing::info!("Created API token '{}' ({})", token.name, token.id);
+    Ok(token)
+}
+
+/// Revoke an API token (clients using it are rejected from now on)
+#[tauri::command]
+pub fn revoke_api_token(
+    token_id: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    let mut settings = state.read_settings().clone();
+    let before = settings.api_tokens.len();
+    settings.api_tokens.retain(|t| t.id != token_id);
+    if settings.api_tokens.len() == before {
+        return Err(
+            AppError::new(ErrorCode::InvalidRequest, "API token not found")
+                .with_context("token_id", &token_id),
+        );
+    }
+
+    tracing::info!("Revoked API token {}", token_id);
+    save_tokens(settings, &state, &app_handle)
+}
+
+/// Recent API requests, newest first (optionally only those of one token)
+#[tauri::command]
+pub fn get_api_audit_log(token_id: Option<String>) -> Result<Vec<AuditEntry>, AppError> {
+    Ok(api_security::audit_log(token_id.as_deref()))
+}
+
+/// Apply changed tokens to the server and persist them
+fn save_tokens(
+    settings: AppSettings,
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    api_security::set_tokens(settings.api_tokens.clone());
+    state.update_and_save_settings(app_handle, settings.clone())?;
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Get whether the local API server is enabled and running
+#[tauri::command]
+pub fn get_api_status(state: State<'_, AppState>) -> Result<ApiStatus, AppError> {
+    Ok(ApiStatus {
+        enabled: state.read_settings().api_enabled,
+        running_port: api::running_port(),
+    })
+}
@@ -5,20 +5,103 @@
 //! - Dual-output playback
 //! - Playback control (play, stop)
 //! - Audio cache management
-//! - Waveform generation
+//! - Waveform and spectrogram generation
 
-use std::sync::mpsc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
-use std::thread;
 use std::time::{Duration, Instant};
 
-use cpal::traits::HostTrait;
-use tauri::{Emitter, State};
-use tracing::{debug, error, info};
+use cpal::traits::{HostTrait, StreamTrait};
+use tauri::{Emitter, Manager, Runtime, State};
+use tracing::{debug, error, info, warn};
 
+use crate::api::{self, TriggerFeedback};
 use crate::audio::{
-    self, AudioDevice, AudioManager, CacheStats, DeviceId, SoundState, WaveformData,
+    self, AudioCache, AudioData, AudioDevice, AudioManager, CacheStats, DeviceFallback, DeviceId,
+    DeviceRole, OutputRouting, PlaybackSignal, SoundState, SpectrogramData, WaveformData,
+    MAX_CONCURRENT_PLAYBACKS,
 };
+use crate::card_assets::{self, CardAsset};
+use crate::error::{AppError, ErrorCode};
+use crate::i18n;
+use crate::recap;
+use crate::settings::{IdenticalDeviceBehavior, MAX_RESTART_CROSSFADE_MS};
+use crate::sounds::{PlayPolicy, SoundId};
+use crate::vbcable;
+use crate::AppState;
+
+/// How long past its expected end a playback may run before it's ended anyway
+const STREAM_STALL_GRACE: Duration = Duration::from_secs(2);
+
+/// Played time between `playback-progress` events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Output streams with their cursors (monitor first, then broadcast unless
+/// both are the same device)
+type OpenStreams = (Vec<PlaybackOutput>, Vec<Arc<audio::PlaybackCursor>>);
+
+/// A playback output: where it plays, the sound converted to its rate (if
+/// needed) and its level
+type PlannedOutput<'a> = (OutputTarget<'a>, Option<Arc<AudioData>>, Arc<AtomicU32>);
+
+/// Conversion of the sound for each output (None plays the source), and the
+/// rates left to convert in the background
+type Conversions = (Vec<Option<Arc<AudioData>>>, Vec<(u32, Arc<AudioData>)>);
+
+/// Where a playback output plays
+#[derive(Clone, Copy)]
+enum OutputTarget<'a> {
+    Device(&'a cpal::Device),
+    /// Mixed into the routed microphone's CABLE Input stream, at its sample
+    /// rate and channel count
+    MicMix(u32, u16),
+}
+
+/// An opened playback output
+enum PlaybackOutput {
+    Stream(cpal::Stream),
+    MicMix(vbcable::MixHandle),
+}
+
+impl PlaybackOutput {
+    fn set_paused(&self, paused: bool) -> Result<(), String> {
+        match self {
+            Self::Stream(stream) if paused => stream.pause().map_err(|e| e.to_string()),
+            Self::Stream(stream) => stream.play().map_err(|e| e.to_string()),
+            Self::MicMix(handle) => {
+                handle.set_paused(paused);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Audio of a playback
+enum SoundAudio {
+    /// Decoded up front (from the cache)
+    Decoded(Arc<AudioData>),
+    /// Long file decoded while it plays
+    Streamed(audio::StreamInfo),
+}
+
+impl SoundAudio {
+    fn sample_rate(&self) -> u32 {
+        match self {
+            Self::Decoded(audio_data) => audio_data.sample_rate,
+            Self::Streamed(info) => info.sample_rate,
+        }
+    }
+
+    /// Length in source frames
+    fn frames(&self) -> usize {
+        match self {
+            Self::Decoded(audio_data) => audio_data.samples.len() / audio_data.channels as usize,
+            Self::Streamed(info) => info.frames as usize,
+        }
+    }
+}
 
 /// Playback progress event payload
 #[derive(Clone, serde::Serialize)]
@@ -29,10 +112,343 @@ struct PlaybackProgress {
     progress_pct: u8,
 }
 
+/// Payload of `playback-paused`, emitted when a playback pauses or resumes
+#[derive(Clone, serde::Serialize)]
+struct PlaybackPaused {
+    playback_id: String,
+    paused: bool,
+}
+
+/// Why a playback trigger was ignored
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IgnoreReason {
+    /// Sound was retriggered before it played for the minimum time
+    Cooldown,
+    /// Sound is playing and its policy ignores triggers meanwhile
+    AlreadyPlaying,
+}
+
+/// Playback ignored event payload
+#[derive(Clone, serde::Serialize)]
+struct PlaybackIgnored {
+    sound_id: String,
+    reason: IgnoreReason,
+    /// Time until the sound can be triggered again
+    remaining_ms: u64,
+}
+
+/// Emit `playback-ignored` so the UI can give feedback for the dropped trigger
+fn emit_playback_ignored(
+    app_handle: &tauri::AppHandle,
+    sound_id: &str,
+    reason: IgnoreReason,
+    remaining_ms: u64,
+) {
+    let payload = PlaybackIgnored {
+        sound_id: sound_id.to_string(),
+        reason,
+        remaining_ms,
+    };
+    if let Err(e) = app_handle.emit("playback-ignored", payload) {
+        error!("Failed to emit playback ignored event: {}", e);
+    }
+}
+
+/// Sound state reported to the frontend
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundStatus {
+    /// Triggered, audio is being decoded (not audible yet)
+    Decoding,
+    /// Streams are open, the sound is audible
+    Playing,
+    /// Playback ended, was stopped or failed to start
+    Stopped,
+}
+
+/// A/B comparison segment event payload
+#[derive(Clone, serde::Serialize)]
+struct RoutingSegment {
+    playback_id: String,
+    /// Output audible in the new segment
+    output: DeviceRole,
+}
+
+/// Sound state changed event payload
+#[derive(Clone, serde::Serialize)]
+struct SoundStateChanged {
+    sound_id: String,
+    playback_id: String,
+    state: SoundStatus,
+}
+
+/// Emit `sound-state-changed` so buttons can show decode/playing state
+fn emit_sound_state(
+    app_handle: &tauri::AppHandle,
+    sound_id: &str,
+    playback_id: &str,
+    state: SoundStatus,
+) {
+    let payload = SoundStateChanged {
+        sound_id: sound_id.to_string(),
+        playback_id: playback_id.to_string(),
+        state,
+    };
+    if let Err(e) = app_handle.emit("sound-state-changed", payload) {
+        error!("Failed to emit sound state event: {}", e);
+    }
+}
+
+/// Remove a sound from active tracking ONLY if `playback_id` is still its
+/// current playback (a newer playback may have replaced it already)
+fn release_sound(
+    active_sounds: &Mutex<HashMap<String, SoundState>>,
+    sound_id: &str,
+    playback_id: &str,
+) -> bool {
+    let mut sounds = active_sounds.lock().unwrap();
+    match sounds.get(sound_id) {
+        Some(state) if state.playback_id() == playback_id => {
+            sounds.remove(sound_id);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Whether a sound remembers its playback position, and where to resume it
+fn resume_point(
+    app_handle: &tauri::AppHandle,
+    sound_id: &str,
+    trim_start_ms: Option<u64>,
+    trim_end_ms: Option<u64>,
+) -> (bool, Option<u64>) {
+    if sound_id.is_empty() {
+        return (false, None);
+    }
+    let state = app_handle.state::<AppState>();
+    let enabled = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .any(|s| s.id.as_str() == sound_id && s.resume_playback);
+    if !enabled {
+        return (false, None);
+    }
+    let position = state
+        .read_session()
+        .resume_position(sound_id, trim_start_ms, trim_end_ms);
+    (true, position)
+}
+
+/// Linear gains of a sound's monitor and broadcast level trims (1.0 = untrimmed)
+fn device_trims(app_handle: &tauri::AppHandle, sound_id: &str) -> (f32, f32) {
+    if sound_id.is_empty() {
+        return (1.0, 1.0);
+    }
+    let state = app_handle.state::<AppState>();
+    let library = state.read_sounds();
+    let Some(sound) = library.sounds.iter().find(|s| s.id.as_str() == sound_id) else {
+        return (1.0, 1.0);
+    };
+    (
+        sound.monitor_trim_db.map_or(1.0, audio::from_db),
+        sound.broadcast_trim_db.map_or(1.0, audio::from_db),
+    )
+}
+
+/// Devices a sound plays on: its own overrides, else the given global devices
+fn sound_devices(
+    app_handle: &tauri::AppHandle,
+    sound_id: &str,
+    monitor: DeviceId,
+    broadcast: DeviceId,
+) -> (DeviceId, DeviceId) {
+    let state = app_handle.state::<AppState>();
+    let library = state.read_sounds();
+    match library.sounds.iter().find(|s| s.id.as_str() == sound_id) {
+        Some(sound) => (
+            sound.monitor_device_id.clone().unwrap_or(monitor),
+            sound.broadcast_device_id.clone().unwrap_or(broadcast),
+        ),
+        None => (monitor, broadcast),
+    }
+}
+
+/// Duration a sound is set to be stretched to, if any
+fn sound_fit_duration(app_handle: &tauri::AppHandle, sound_id: &str) -> Option<u64> {
+    let state = app_handle.state::<AppState>();
+    let library = state.read_sounds();
+    library
+        .sounds
+        .iter()
+        .find(|s| s.id.as_str() == sound_id)
+        .and_then(|s| s.fit_duration_ms)
+}
+
+/// Pitch shift and speed a sound is set to play with, if it isn't played
+/// as it is
+fn sound_pitch_speed(app_handle: &tauri::AppHandle, sound_id: &str) -> Option<(f32, f32)> {
+    let state = app_handle.state::<AppState>();
+    let library = state.read_sounds();
+    let sound = library.sounds.iter().find(|s| s.id.as_str() == sound_id)?;
+    let pitch = sound.pitch_semitones.unwrap_or(0.0);
+    let speed = sound.speed.unwrap_or(1.0);
+    (!audio::is_neutral(pitch, speed)).then_some((pitch, speed))
+}
+
+/// Loudness stored on a sound by `analyze_library_loudness`, if measured
+/// from `file_path`
+fn sound_lufs(app_handle: &tauri::AppHandle, sound_id: &str, file_path: &str) -> Option<f32> {
+    let state = app_handle.state::<AppState>();
+    let library = state.read_sounds();
+    library
+        .sounds
+        .iter()
+        .find(|s| s.id.as_str() == sound_id && s.file_path == file_path)
+        .and_then(|s| s.lufs)
+}
+
+/// Exclusive group a sound belongs to, if any
+fn exclusive_group(app_handle: &tauri::AppHandle, sound_id: &str) -> Option<String> {
+    let state = app_handle.state::<AppState>();
+    let library = state.read_sounds();
+    library
+        .sounds
+        .iter()
+        .find(|s| s.id.as_str() == sound_id)
+        .and_then(|s| s.group.clone())
+}
+
+/// Emit `device-fallback` when a role switches to or back from the default device
+pub(crate) fn emit_device_fallback(app_handle: &tauri::AppHandle, change: &DeviceFallback) {
+    if change.restored {
+        info!(
+            "{:?} device {} is back, using it again",
+            change.role, change.original_device_id
+        );
+    } else {
+        warn!(
+            "{:?} device {} is missing, falling back to {:?}",
+            change.role, change.original_device_id, change.fallback_device_id
+        );
+    }
+    if let Err(e) = app_handle.emit("device-fallback", change) {
+        error!("Failed to emit device fallback event: {}", e);
+    }
+}
+
+/// Broadcast stream volume (silent while Discord reports the user muted/deafened)
+fn broadcast_volume(volume: f32) -> f32 {
+    if crate::discord::is_broadcast_suppressed() {
+        0.0
+    } else {
+        volume
+    }
+}
+
+/// Look up a file's integrated loudness, measuring and caching it on first use
+pub(super) fn cached_loudness(
+    loudness_cache: &Mutex<HashMap<String, Option<f32>>>,
+    file_path: &str,
+    audio_data: &AudioData,
+) -> Option<f32> {
+    if let Some(lufs) = loudness_cache.lock().unwrap().get(file_path) {
+        return *lufs;
+    }
+    let lufs = audio::integrated_loudness(audio_data);
+    loudness_cache
+        .lock()
+        .unwrap()
+        .insert(file_path.to_string(), lufs);
+    lufs
+}
+
+/// Band-limited conversion of a sound to `sample_rate`
+fn resample_timed(audio_data: &AudioData, sample_rate: u32) -> Arc<AudioData> {
+    let start = Instant::now();
+    let converted = Arc::new(audio::resample(audio_data, sample_rate));
+    debug!(
+        from = audio_data.sample_rate,
+        to = sample_rate,
+        duration_ms = start.elapsed().as_millis(),
+        "Resampled audio"
+    );
+    converted
+}
+
 /// Lists all available output audio devices on the system
 #[tauri::command]
-pub fn list_audio_devices() -> Result<Vec<AudioDevice>, String> {
-    audio::enumerate_devices().map_err(Into::into)
+pub fn list_audio_devices() -> Result<Vec<AudioDevice>, AppError> {
+    Ok(audio::enumerate_devices()?)
+}
+
+/// Re-enumerate output devices (e.g. after a Bluetooth device connected)
+///
+/// Saved device IDs (settings and per-sound overrides) that shifted follow
+/// their device, and the default-device fallback is checked again.
+#[tauri::command]
+pub fn refresh_audio_devices(app_handle: tauri::AppHandle) -> Result<Vec<AudioDevice>, AppError> {
+    rescan_devices(&app_handle)
+}
+
+/// Re-enumerate output devices, following moved saved IDs and checking the
+/// fallback (see `refresh_audio_devices`)
+pub(crate) fn rescan_devices(app_handle: &tauri::AppHandle) -> Result<Vec<AudioDevice>, AppError> {
+    let state = app_handle.state::<AppState>();
+    let previous = audio::known_devices();
+    let devices = audio::enumerate_devices()?;
+    debug!("Rescanned {} output devices", devices.len());
+
+    let mut settings = state.read_settings().clone();
+    let settings_changed = follow_device(&mut settings.monitor_device_id, &previous, &devices)
+        | follow_device(&mut settings.broadcast_device_id, &previous, &devices);
+    if settings_changed {
+        state.update_and_save_settings(app_handle, settings.clone())?;
+        if let Err(e) = app_handle.emit("settings-changed", &settings) {
+            error!("Failed to emit settings changed event: {}", e);
+        }
+    }
+
+    let mut library = state.read_sounds().clone();
+    let mut sounds_changed = false;
+    for sound in &mut library.sounds {
+        sounds_changed |= follow_device(&mut sound.monitor_device_id, &previous, &devices);
+        sounds_changed |= follow_device(&mut sound.broadcast_device_id, &previous, &devices);
+    }
+    if sounds_changed {
+        state.update_and_save_sounds(app_handle, library)?;
+    }
+
+    if settings.device_fallback_enabled {
+        let changes = audio::check_saved_devices(
+            settings.monitor_device_id.as_ref(),
+            settings.broadcast_device_id.as_ref(),
+        )?;
+        for change in &changes {
+            emit_device_fallback(app_handle, change);
+        }
+    }
+
+    Ok(devices)
+}
+
+/// Point a saved device ID at its device's current ID; true if it moved
+fn follow_device(
+    device_id: &mut Option<DeviceId>,
+    previous: &[AudioDevice],
+    current: &[AudioDevice],
+) -> bool {
+    let Some(saved) = device_id.as_ref() else {
+        return false;
+    };
+    let Some(moved) = audio::reconcile_device_id(saved, previous, current) else {
+        return false;
+    };
+    info!("Device {} is now {}", saved, moved);
+    *device_id = Some(moved);
+    true
 }
 
 /// Result of play_dual_output indicating what action was taken
@@ -40,12 +456,168 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>, String> {
 pub struct PlaybackResult {
     /// The playback ID (if playback started)
     pub playback_id: Option<String>,
-    /// Action taken: "started", "restarted", "ignored"
+    /// Action taken: "started", "restarted", "extended", "queued", "ignored"
     pub action: String,
     /// Previous playback ID that was stopped (if restarted)
     pub stopped_playback_id: Option<String>,
 }
 
+/// Minimum time a sound must AUDIBLY play before it can be restarted
+/// Lower = more responsive/snappy, but too low may cause audio glitches
+const MIN_PLAY_TIME_MS: u64 = 15;
+
+/// What the play policy made of a trigger
+enum PolicyDecision {
+    /// Start the new playback, replacing the given one (Restart)
+    Play(Option<String>),
+    /// Handled without a new playback
+    Done(PlaybackResult),
+}
+
+/// Apply the play policy of a sound that is already active, and register the
+/// new playback as the sound's decoding one
+fn apply_policy(
+    manager: &AudioManager,
+    app_handle: &tauri::AppHandle,
+    sound_id: &str,
+    playback_id: &str,
+    play_policy: PlayPolicy,
+) -> PolicyDecision {
+    if sound_id.is_empty() {
+        return PolicyDecision::Play(None);
+    }
+
+    let mut stopped_playback_id = None;
+    if let Some(current_state) = manager.get_sound_state(sound_id) {
+        let current_playback_id = current_state.playback_id().to_string();
+
+        match play_policy {
+            // Keep the current playback and let it play once more
+            PlayPolicy::Extend => {
+                debug!("Extending {} ({})", sound_id, current_playback_id);
+                manager.request_extension(&current_playback_id);
+                return PolicyDecision::Done(PlaybackResult {
+                    playback_id: Some(current_playback_id),
+                    action: "extended".to_string(),
+                    stopped_playback_id: None,
+                });
+            }
+            // Play again as a new playback after the current one
+            PlayPolicy::Queue => {
+                debug!("Queueing {} after {}", sound_id, current_playback_id);
+                manager.add_follow_ups(&current_playback_id, 1);
+                return PolicyDecision::Done(PlaybackResult {
+                    playback_id: Some(current_playback_id),
+                    action: "queued".to_string(),
+                    stopped_playback_id: None,
+                });
+            }
+            PlayPolicy::IgnoreIfPlaying => {
+                debug!("Ignoring trigger for playing {}", sound_id);
+                emit_playback_ignored(app_handle, sound_id, IgnoreReason::AlreadyPlaying, 0);
+                return PolicyDecision::Done(PlaybackResult {
+                    playback_id: None,
+                    action: "ignored".to_string(),
+                    stopped_playback_id: None,
+                });
+            }
+            PlayPolicy::Restart | PlayPolicy::Overlap => {}
+        }
+
+        // Check cooldown only if sound is actually playing (audible)
+        let cooldown_remaining_ms = match &current_state {
+            SoundState::Playing { started_at, .. } => {
+                MIN_PLAY_TIME_MS.saturating_sub(started_at.elapsed().as_millis() as u64)
+            }
+            SoundState::Decoding { .. } => 0, // No cooldown during decoding
+        };
+
+        if cooldown_remaining_ms > 0 {
+            debug!(
+                "Cooldown: Ignoring trigger for {} ({}ms remaining)",
+                sound_id, cooldown_remaining_ms
+            );
+            emit_playback_ignored(
+                app_handle,
+                sound_id,
+                IgnoreReason::Cooldown,
+                cooldown_remaining_ms,
+            );
+            return PolicyDecision::Done(PlaybackResult {
+                playback_id: None,
+                action: "ignored".to_string(),
+                stopped_playback_id: None,
+            });
+        }
+
+        if play_policy == PlayPolicy::Overlap {
+            // The new playback becomes the sound's tracked one, the
+            // current one plays out untracked
+            info!("Overlapping {} with {}", current_playback_id, playback_id);
+        } else {
+            // Restart sound (works for both Decoding and Playing states)
+            info!("Restarting {} (was {})", sound_id, current_playback_id);
+            if matches!(current_state, SoundState::Decoding { .. }) {
+                // The stale decode isn't audible yet, drop it right away
+                manager.signal_stop(&current_playback_id);
+            }
+            stopped_playback_id = Some(current_playback_id);
+        }
+    }
+    manager.register_sound_decoding(sound_id.to_string(), playback_id.to_string());
+    emit_sound_state(app_handle, sound_id, playback_id, SoundStatus::Decoding);
+
+    // Starting a grouped sound stops the others of its group
+    if let Some(group) = exclusive_group(app_handle, sound_id) {
+        for stopped in manager.claim_exclusive_group(&group, sound_id) {
+            info!("Stopped {} for exclusive group '{}'", stopped, group);
+        }
+    }
+    PolicyDecision::Play(stopped_playback_id)
+}
+
+/// Settings a playback reads when it starts
+struct PlaybackSettings {
+    /// Loudness normalization target, if normalization is on
+    normalization_target_lufs: Option<f32>,
+    device_fallback_enabled: bool,
+    high_quality_resampling: bool,
+    resample_cache_enabled: bool,
+    /// Playback limit (voice stealing)
+    max_concurrent_sounds: usize,
+    restart_crossfade_ms: u32,
+    monitor_output_volume: f32,
+    broadcast_output_volume: f32,
+    identical_device_behavior: IdenticalDeviceBehavior,
+    output_limiter_enabled: bool,
+    small_output_buffers: bool,
+    soundboard_mic_mix_enabled: bool,
+}
+
+impl PlaybackSettings {
+    fn read(app_handle: &tauri::AppHandle) -> Self {
+        let state = app_handle.state::<AppState>();
+        let settings = state.read_settings();
+        Self {
+            normalization_target_lufs: settings
+                .normalization_enabled
+                .then_some(settings.normalization_target_lufs),
+            device_fallback_enabled: settings.device_fallback_enabled,
+            high_quality_resampling: settings.high_quality_resampling,
+            resample_cache_enabled: settings.resample_cache_enabled,
+            max_concurrent_sounds: (settings.max_concurrent_sounds as usize)
+                .clamp(1, MAX_CONCURRENT_PLAYBACKS),
+            restart_crossfade_ms: settings.restart_crossfade_ms.min(MAX_RESTART_CROSSFADE_MS),
+            monitor_output_volume: settings.monitor_volume.clamp(0.0, 1.0),
+            broadcast_output_volume: settings.broadcast_volume.clamp(0.0, 1.0),
+            identical_device_behavior: settings.identical_device_behavior,
+            output_limiter_enabled: settings.output_limiter_enabled,
+            small_output_buffers: settings.small_output_buffers,
+            soundboard_mic_mix_enabled: settings.soundboard_mic_mix_enabled,
+        }
+    }
+}
+
 /// Plays an audio file simultaneously to two different output devices
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
@@ -57,11 +629,20 @@ pub fn play_dual_output(
     trim_start_ms: Option<u64>,
     trim_end_ms: Option<u64>,
     sound_id: Option<String>,
+    play_policy: Option<PlayPolicy>,
+    routing: Option<OutputRouting>,
+    fit_duration_ms: Option<u64>,
     manager: State<'_, AudioManager>,
     app_handle: tauri::AppHandle,
-) -> Result<PlaybackResult, String> {
+) -> Result<PlaybackResult, AppError> {
     let volume = volume.clamp(0.0, 1.0);
     let sound_id = sound_id.unwrap_or_default();
+    let play_policy = play_policy.unwrap_or_default();
+    let routing = routing.unwrap_or_default();
+    let fit_duration_ms = fit_duration_ms
+        .or_else(|| sound_fit_duration(&app_handle, &sound_id))
+        .filter(|ms| *ms > 0);
+    let pitch_speed = sound_pitch_speed(&app_handle, &sound_id);
 
     debug!(
         sound_id = %sound_id,
@@ -70,115 +651,365 @@ pub fn play_dual_output(
         "Playback requested"
     );
 
-    // Minimum time a sound must AUDIBLY play before it can be restarted
-    // Lower = more responsive/snappy, but too low may cause audio glitches
-    const MIN_PLAY_TIME_MS: u64 = 15;
-
     // Generate playback ID first
     let playback_id = manager.next_playback_id();
 
     // Check if this sound is already active and apply policy
-    let mut stopped_playback_id: Option<String> = None;
-    if !sound_id.is_empty() {
-        if let Some(current_state) = manager.get_sound_state(&sound_id) {
-            let current_playback_id = current_state.playback_id().to_string();
-
-            // Check cooldown only if sound is actually playing (audible)
-            let should_apply_cooldown = match &current_state {
-                SoundState::Playing { started_at, .. } => {
-                    (started_at.elapsed().as_millis() as u64) < MIN_PLAY_TIME_MS
-                }
-                SoundState::Decoding { .. } => false, // No cooldown during decoding
-            };
+    let stopped_playback_id =
+        match apply_policy(&manager, &app_handle, &sound_id, &playback_id, play_policy) {
+            PolicyDecision::Play(stopped_playback_id) => stopped_playback_id,
+            PolicyDecision::Done(result) => return Ok(result),
+        };
 
-            if should_apply_cooldown {
-                debug!(
-                    "Cooldown: Ignoring trigger for {} (still in cooldown)",
-                    sound_id
-                );
-                return Ok(PlaybackResult {
-                    playback_id: None,
-                    action: "ignored".to_string(),
-                    stopped_playback_id: None,
-                });
-            }
+    // Loudness normalization applies to playbacks started from now on
+    let settings = PlaybackSettings::read(&app_handle);
 
-            // Restart sound (works for both Decoding and Playing states)
-            info!("Restarting {} (was {})", sound_id, current_playback_id);
-            manager.register_sound_decoding(sound_id.clone(), playback_id.clone());
-            stopped_playback_id = Some(current_playback_id);
-        } else {
-            // No current playback, register as decoding
-            manager.register_sound_decoding(sound_id.clone(), playback_id.clone());
-        }
+    // Long sounds can pick up where they were stopped last time (not when
+    // reshaped, positions in the processed buffer don't map to the file)
+    let reshaped = fit_duration_ms.is_some() || pitch_speed.is_some();
+    let (resume_enabled, resume_from_ms) = if reshaped {
+        (false, None)
+    } else {
+        resume_point(&app_handle, &sound_id, trim_start_ms, trim_end_ms)
+    };
+    if let Some(position_ms) = resume_from_ms {
+        debug!("Resuming {} at {}ms", sound_id, position_ms);
     }
+    let device_trims = device_trims(&app_handle, &sound_id);
 
-    // Create stop channel
+    // Sounds can have their own devices; the passed ones are the global devices
+    let device_ids = sound_devices(&app_handle, &sound_id, device_id_1, device_id_2);
+
+    // Create the signal channel (stop requests and changes from the manager,
+    // completion and progress from the streams)
     let (stop_tx, stop_rx) = mpsc::channel();
+    let finished_tx = stop_tx.clone();
+
+    // Make room within the playback limit by stopping the oldest sounds
+    let max_concurrent_sounds = settings.max_concurrent_sounds;
+    for stolen in manager.steal_voices(max_concurrent_sounds, stopped_playback_id.as_deref()) {
+        info!(
+            "Stopped {} to stay within {} sounds",
+            stolen, max_concurrent_sounds
+        );
+    }
 
     // Register the playback
     manager.register_playback(playback_id.clone(), stop_tx);
+    let cancel = manager.register_decode(&playback_id);
 
-    // Create shared volume state for dynamic control
-    let volume_state = Arc::new(Mutex::new(volume));
+    // Create shared volume state for dynamic control (set_playback_volume)
+    let volume_state = Arc::new(AtomicU32::new(volume.to_bits()));
+    manager.register_playback_volume(&playback_id, &volume_state);
+    let paused = Arc::new(AtomicBool::new(false));
+    manager.register_paused_flag(&playback_id, &paused);
 
-    // Clone for the thread
-    let playback_id_clone = playback_id.clone();
-    let manager_inner = manager.get_stop_senders();
-    let active_sounds = manager.get_active_sounds();
-    let cache = manager.get_cache();
-    let sound_id_clone = sound_id.clone();
-    let old_playback_to_stop = stopped_playback_id.clone();
-
-    // Spawn dedicated playback thread (including decoding to avoid blocking UI)
-    thread::spawn(move || {
-        let thread_start = Instant::now();
-
-        // Helper to clean up on early return (before playback starts)
-        let cleanup_early =
-            |manager_inner: &Arc<Mutex<std::collections::HashMap<String, _>>>,
-             active_sounds: &Arc<Mutex<std::collections::HashMap<String, SoundState>>>,
-             playback_id: &str,
-             sound_id: &str| {
-                manager_inner.lock().unwrap().remove(playback_id);
-                if !sound_id.is_empty() {
-                    let mut sounds = active_sounds.lock().unwrap();
-                    // Only remove if this is still our playback
-                    if let Some(state) = sounds.get(sound_id) {
-                        if state.playback_id() == playback_id {
-                            sounds.remove(sound_id);
-                        }
-                    }
-                }
-            };
+    // A restart fades the old playback out while this one fades in
+    let crossfade = (stopped_playback_id.is_some() && settings.restart_crossfade_ms > 0)
+        .then(|| Duration::from_millis(settings.restart_crossfade_ms as u64));
+
+    let job = PlaybackJob {
+        app_handle,
+        playback_id: playback_id.clone(),
+        sound_id,
+        file_path,
+        device_ids,
+        trim_start_ms,
+        trim_end_ms,
+        fit_duration_ms,
+        pitch_speed,
+        resume_enabled,
+        resume_from_ms,
+        device_trims,
+        routing,
+        crossfade,
+        replaces: stopped_playback_id.clone(),
+        settings,
+        volume: volume_state,
+        normalization: 1.0,
+        paused,
+        cancel,
+        stop_rx,
+        finished_tx,
+        started: Instant::now(),
+    };
+
+    // Decode on the decode pool (keeps the UI responsive), then hand over to
+    // the playback pool, which runs the streams until the sound ends
+    manager.get_decode_pool().execute(move || job.decode());
+
+    let action = if stopped_playback_id.is_some() {
+        "restarted"
+    } else {
+        "started"
+    };
+
+    Ok(PlaybackResult {
+        playback_id: Some(playback_id),
+        action: action.to_string(),
+        stopped_playback_id,
+    })
+}
+
+/// A playback on its way through the worker pools: decoded on the decode
+/// pool, then played on the playback pool until the sound ends
+struct PlaybackJob {
+    app_handle: tauri::AppHandle,
+    playback_id: String,
+    sound_id: String,
+    file_path: String,
+    /// Monitor and broadcast device
+    device_ids: (DeviceId, DeviceId),
+    /// Trim range (cleared once a reshaped sound has it cut out)
+    trim_start_ms: Option<u64>,
+    trim_end_ms: Option<u64>,
+    fit_duration_ms: Option<u64>,
+    pitch_speed: Option<(f32, f32)>,
+    /// Whether the sound remembers its position, and where it resumes
+    resume_enabled: bool,
+    resume_from_ms: Option<u64>,
+    /// Monitor and broadcast trim of the sound
+    device_trims: (f32, f32),
+    routing: OutputRouting,
+    /// Fade-in of a restart (the old playback fades out meanwhile)
+    crossfade: Option<Duration>,
+    /// Old playback of the sound this one replaces (Restart)
+    replaces: Option<String>,
+    settings: PlaybackSettings,
+    /// Shared volume (set_playback_volume)
+    volume: Arc<AtomicU32>,
+    /// Loudness normalization gain, known once the sound is decoded
+    normalization: f32,
+    paused: Arc<AtomicBool>,
+    cancel: audio::CancelToken,
+    stop_rx: mpsc::Receiver<PlaybackSignal>,
+    finished_tx: mpsc::Sender<PlaybackSignal>,
+    started: Instant,
+}
+
+/// What a playback's streams are opened from, again for each repeat (Extend)
+struct StreamPlan<'a> {
+    sound_audio: &'a SoundAudio,
+    /// Outputs, monitor first, then broadcast unless both are the same device
+    outputs: Vec<PlannedOutput<'a>>,
+    /// Frame the first play starts at (a remembered position or the trim
+    /// start)
+    first_frame: Option<usize>,
+    /// Trim range in source frames; repeats start at the trim start again
+    start_frame: Option<usize>,
+    end_frame: Option<usize>,
+}
+
+impl StreamPlan<'_> {
+    /// Milliseconds of the sound in `frames` source frames
+    fn frames_to_ms(&self, frames: f64) -> u64 {
+        (frames.max(0.0) / self.sound_audio.sample_rate() as f64 * 1000.0) as u64
+    }
+
+    /// Length of streams opened at `start_frame` (ms)
+    fn length_ms(&self, start_frame: usize) -> u64 {
+        let end = self.end_frame.unwrap_or(self.sound_audio.frames());
+        self.frames_to_ms(end.saturating_sub(start_frame) as f64)
+    }
+}
+
+/// How a playback's streams ended
+struct PlaybackEnd {
+    /// Stopped before the sound played out
+    stopped: bool,
+    /// Position of the monitor stream (ms)
+    position_ms: u64,
+}
+
+impl PlaybackJob {
+    /// Decode the sound, then hand it over to the playback pool
+    fn decode(mut self) {
+        self.started = Instant::now();
+        let Some(sound_audio) = self.load_audio() else {
+            return;
+        };
+        self.normalization = self.normalization_gain(&sound_audio);
+        let sound_audio = self.reshape(sound_audio);
+
+        // Emit event that decoding is complete and playback is starting
+        if let Err(e) = self
+            .app_handle
+            .emit("audio-decode-complete", &self.playback_id)
+        {
+            error!("Failed to emit decode complete event: {}", e);
+        }
+
+        let playback_pool = self.app_handle.state::<AudioManager>().get_playback_pool();
+        playback_pool.execute(move || self.play(sound_audio));
+    }
+
+    /// Stretched or pitched sounds are processed whole before they play
+    fn reshaped(&self) -> bool {
+        self.fit_duration_ms.is_some() || self.pitch_speed.is_some()
+    }
+
+    /// Get the sound from the cache, decode it, or stream it; None once the
+    /// playback is cleaned up (cancelled or failed)
+    fn load_audio(&self) -> Option<SoundAudio> {
+        let manager = self.app_handle.state::<AudioManager>();
+        let cache = manager.get_cache();
+
+        // Long files play while they decode (reshaping needs the whole sound)
+        let streamed = if self.reshaped() || cache.lock().unwrap().contains(&self.file_path) {
+            None
+        } else {
+            audio::streaming_info(&self.file_path)
+        };
 
         // Get audio from cache or decode (cache handles the logic)
-        let audio_data = match cache.lock().unwrap().get_or_decode(&file_path) {
-            Ok(data) => data, // Already Arc<AudioData>
+        let decoded = if self.cancel.is_cancelled() {
+            Err(audio::AudioError::Cancelled)
+        } else if let Some(info) = streamed {
+            debug!(file_path = %self.file_path, frames = info.frames, "Streaming long file");
+            Ok(SoundAudio::Streamed(info))
+        } else {
+            AudioCache::load_cancellable(&cache, &self.file_path, &self.cancel)
+                .map(SoundAudio::Decoded)
+        };
+        manager
+            .get_decode_tokens()
+            .lock()
+            .unwrap()
+            .remove(&self.playback_id);
+
+        match decoded {
+            Ok(sound_audio) => Some(sound_audio),
+            Err(audio::AudioError::Cancelled) => {
+                debug!("Decode for {} cancelled", self.playback_id);
+                self.cleanup_early();
+                None
+            }
             Err(e) => {
                 error!("Failed to decode audio: {}", e);
-                cleanup_early(
-                    &manager_inner,
-                    &active_sounds,
-                    &playback_id_clone,
-                    &sound_id_clone,
-                );
+                recap::record_error();
+                self.cleanup_early();
                 // Emit error event
-                if let Err(emit_err) =
-                    app_handle.emit("audio-decode-error", format!("Failed to decode: {}", e))
-                {
+                let message = i18n::tf("notify.decode_failed", &[("error", &e.to_string())]);
+                if let Err(emit_err) = self.app_handle.emit("audio-decode-error", message) {
                     error!("Failed to emit decode error event: {}", emit_err);
                 }
-                return;
+                None
+            }
+        }
+    }
+
+    /// Gain playing the sound at the normalization target (1.0 without
+    /// normalization)
+    fn normalization_gain(&self, sound_audio: &SoundAudio) -> f32 {
+        let Some(target_lufs) = self.settings.normalization_target_lufs else {
+            return 1.0;
+        };
+        let loudness_cache = self.app_handle.state::<AudioManager>().get_loudness_cache();
+        let stored = sound_lufs(&self.app_handle, &self.sound_id, &self.file_path);
+        let measured = match sound_audio {
+            _ if stored.is_some() => stored,
+            SoundAudio::Decoded(audio_data) => {
+                cached_loudness(&loudness_cache, &self.file_path, audio_data)
+            }
+            // Measuring would decode the whole file, only an earlier
+            // measurement counts
+            SoundAudio::Streamed(_) => loudness_cache
+                .lock()
+                .unwrap()
+                .get(&self.file_path)
+                .copied()
+                .flatten(),
+        };
+        let gain = audio::normalization_gain(measured, target_lufs);
+        debug!(
+            measured_lufs = ?measured,
+            target_lufs = target_lufs,
+            gain = gain,
+            "Loudness normalization applied"
+        );
+        gain
+    }
+
+    /// Fit to duration, then pitch shift and speed; a reshaped sound is the
+    /// processed trim range and plays whole
+    fn reshape(&mut self, sound_audio: SoundAudio) -> SoundAudio {
+        // Fit to duration: the trimmed range is stretched to the target
+        // length and played whole
+        let sound_audio = match (self.fit_duration_ms, sound_audio) {
+            (Some(target_ms), SoundAudio::Decoded(audio_data)) => {
+                let stretch_start = Instant::now();
+                let stretched = audio::fit_to_duration(
+                    &audio_data,
+                    self.trim_start_ms,
+                    self.trim_end_ms,
+                    target_ms,
+                );
+                debug!(
+                    target_ms = target_ms,
+                    duration_ms = stretch_start.elapsed().as_millis() as u64,
+                    "Time-stretched playback"
+                );
+                self.trim_start_ms = None;
+                self.trim_end_ms = None;
+                SoundAudio::Decoded(Arc::new(stretched))
             }
+            (_, sound_audio) => sound_audio,
         };
+        // Pitch shift and speed; a sound fitted to a duration keeps it
+        match (self.pitch_speed, sound_audio) {
+            (Some((pitch, speed)), SoundAudio::Decoded(audio_data)) => {
+                let speed = if self.fit_duration_ms.is_some() {
+                    1.0
+                } else {
+                    speed
+                };
+                let shift_start = Instant::now();
+                let shifted = audio::shift_pitch_and_speed(
+                    &audio_data,
+                    self.trim_start_ms,
+                    self.trim_end_ms,
+                    pitch,
+                    speed,
+                );
+                debug!(
+                    pitch_semitones = pitch,
+                    speed = speed,
+                    duration_ms = shift_start.elapsed().as_millis() as u64,
+                    "Pitch-shifted playback"
+                );
+                self.trim_start_ms = None;
+                self.trim_end_ms = None;
+                SoundAudio::Decoded(Arc::new(shifted))
+            }
+            (_, sound_audio) => sound_audio,
+        }
+    }
 
-        // Emit event that decoding is complete and playback is starting
-        if let Err(e) = app_handle.emit("audio-decode-complete", &playback_id_clone) {
-            error!("Failed to emit decode complete event: {}", e);
+    /// Unregister a playback that ends before its streams play
+    fn cleanup_early(&self) {
+        let manager = self.app_handle.state::<AudioManager>();
+        manager.unregister_playback(&self.playback_id);
+        if !self.sound_id.is_empty()
+            && release_sound(
+                &manager.get_active_sounds(),
+                &self.sound_id,
+                &self.playback_id,
+            )
+        {
+            emit_sound_state(
+                &self.app_handle,
+                &self.sound_id,
+                &self.playback_id,
+                SoundStatus::Stopped,
+            );
         }
+        super::queue::playback_finished(&self.app_handle, &self.playback_id);
+        manager.take_follow_ups(&self.playback_id);
+    }
 
-        // This thread owns the streams - no Send issues!
+    /// Open the streams and run them until the sound ends (on the playback
+    /// pool; this thread owns the streams - no Send issues!)
+    fn play(self, sound_audio: SoundAudio) {
         let host = cpal::default_host();
 
         let enum_start = Instant::now();
@@ -186,12 +1017,8 @@ pub fn play_dual_output(
             Ok(devices) => devices.collect(),
             Err(e) => {
                 error!("Failed to enumerate devices: {}", e);
-                cleanup_early(
-                    &manager_inner,
-                    &active_sounds,
-                    &playback_id_clone,
-                    &sound_id_clone,
-                );
+                recap::record_error();
+                self.cleanup_early();
                 return;
             }
         };
@@ -203,173 +1030,604 @@ pub fn play_dual_output(
             "Device enumeration complete"
         );
 
-        // Parse device indices
-        let (idx1, idx2) = match (device_id_1.index(), device_id_2.index()) {
-            (Ok(i1), Ok(i2)) => (i1, i2),
-            _ => {
-                let error_msg = format!("Invalid device IDs: {} / {}", device_id_1, device_id_2);
-                error!("{}", error_msg);
-                if let Err(e) = app_handle.emit("audio-device-error", error_msg) {
-                    error!("Failed to emit device error event: {}", e);
-                }
-                cleanup_early(
-                    &manager_inner,
-                    &active_sounds,
-                    &playback_id_clone,
-                    &sound_id_clone,
-                );
-                return;
-            }
-        };
-
-        let (Some(device_1), Some(device_2)) = (output_devices.get(idx1), output_devices.get(idx2))
-        else {
-            error!("Devices not found at indices {} and {}", idx1, idx2);
-            cleanup_early(
-                &manager_inner,
-                &active_sounds,
-                &playback_id_clone,
-                &sound_id_clone,
-            );
+        let Some(targets) = self.resolve_outputs(&host, &output_devices) else {
+            self.cleanup_early();
             return;
         };
 
         // Calculate trim frames from milliseconds
-        let sample_rate = audio_data.sample_rate;
-        let start_frame =
-            trim_start_ms.map(|ms| ((ms as f64 / 1000.0) * sample_rate as f64) as usize);
-        let end_frame = trim_end_ms.map(|ms| ((ms as f64 / 1000.0) * sample_rate as f64) as usize);
-
-        // Create streams with shared volume state and trim parameters
-        let stream_1 = match audio::create_playback_stream(
-            device_1,
-            audio_data.clone(),
-            volume_state.clone(),
+        let sample_rate = sound_audio.sample_rate();
+        let to_frame = |ms: u64| ((ms as f64 / 1000.0) * sample_rate as f64) as usize;
+        let start_frame = self.trim_start_ms.map(to_frame);
+
+        // Levels start at the current volume; the streams' envelopes fade a
+        // restart in
+        let (monitor_level, broadcast_level) = self.output_levels();
+        let levels =
+            [monitor_level, broadcast_level].map(|level| Arc::new(AtomicU32::new(level.to_bits())));
+        let (conversions, missing_rates) = self.conversions(&sound_audio, &targets);
+        let plan = StreamPlan {
+            sound_audio: &sound_audio,
+            outputs: targets
+                .into_iter()
+                .zip(conversions)
+                .zip(levels)
+                .map(|((target, converted), level)| (target, converted, level))
+                .collect(),
+            // A resumed playback starts at the remembered position, repeats
+            // (Extend) start at the trim start again
+            first_frame: self.resume_from_ms.map(to_frame).or(start_frame),
             start_frame,
-            end_frame,
-        ) {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to create stream 1: {}", e);
-                cleanup_early(
-                    &manager_inner,
-                    &active_sounds,
-                    &playback_id_clone,
-                    &sound_id_clone,
-                );
-                return;
-            }
+            end_frame: self.trim_end_ms.map(to_frame),
         };
 
-        let stream_2 = match audio::create_playback_stream(
-            device_2,
-            audio_data.clone(),
-            volume_state.clone(),
-            start_frame,
-            end_frame,
-        ) {
-            Ok(s) => s,
+        let opened = match self.start_streams(&plan, plan.first_frame, 0) {
+            Ok(opened) => opened,
             Err(e) => {
-                error!("Failed to create stream 2: {}", e);
-                cleanup_early(
-                    &manager_inner,
-                    &active_sounds,
-                    &playback_id_clone,
-                    &sound_id_clone,
-                );
+                error!("{}", e);
+                self.cleanup_early();
                 return;
             }
         };
 
         // Streams created successfully - NOW the sound is audible!
-        let streams_ready_elapsed = thread_start.elapsed().as_millis();
+        // The routed microphone is ducked until this thread ends
+        let manager = self.app_handle.state::<AudioManager>();
+        let _broadcasting = manager.get_mic_duck().hold();
+        let streams_ready_elapsed = self.started.elapsed().as_millis();
         info!(
-            playback_id = %playback_id_clone,
-            sound_id = %sound_id_clone,
+            playback_id = %self.playback_id,
+            sound_id = %self.sound_id,
             streams_ready_ms = streams_ready_elapsed,
             "Audio streams created and playing"
         );
 
-        // Stop the old playback NOW (seamless transition, no audio gap)
-        if let Some(ref old_id) = old_playback_to_stop {
-            if let Some(sender) = manager_inner.lock().unwrap().remove(old_id) {
-                let _ = sender.send(());
+        // Convert for the cache off the playback path
+        for (rate, audio_data) in missing_rates {
+            let cache = manager.get_cache();
+            let file_path = self.file_path.clone();
+            manager.get_decode_pool().execute(move || {
+                let converted = resample_timed(&audio_data, rate);
+                cache
+                    .lock()
+                    .unwrap()
+                    .insert_resampled(&file_path, converted);
+            });
+        }
+
+        // Stop the old playback NOW (seamless transition, no audio gap),
+        // or fade it out while this one fades in
+        if let Some(ref old_id) = self.replaces {
+            if let Some(duration) = self.crossfade {
+                if manager.signal_fade_out(old_id, duration) {
+                    debug!("Crossfading old playback {} (new one ready)", old_id);
+                }
+            } else if let Some(sender) = manager.get_stop_senders().lock().unwrap().remove(old_id) {
+                let _ = sender.send(PlaybackSignal::Stop);
                 debug!("Stopped old playback {} (new one ready)", old_id);
             }
         }
 
-        // Transition from Decoding to Playing state
-        if !sound_id_clone.is_empty() {
-            let mut sounds = active_sounds.lock().unwrap();
-            if let Some(state) = sounds.get(&sound_id_clone) {
-                // Only update if this is still our playback
-                if state.playback_id() == playback_id_clone {
-                    sounds.insert(
-                        sound_id_clone.clone(),
-                        SoundState::Playing {
-                            playback_id: playback_id_clone.clone(),
-                            started_at: std::time::Instant::now(),
-                        },
-                    );
-                    debug!(
-                        "Sound {} now playing (playback {})",
-                        sound_id_clone, playback_id_clone
-                    );
+        recap::record_play(&self.sound_id);
+        if !self.sound_id.is_empty() {
+            self.app_handle.state::<AppState>().record_play(
+                &self.app_handle,
+                &SoundId::from_string(self.sound_id.clone()),
+            );
+            self.mark_playing();
+        }
+
+        let end = self.run_playback_loop(&plan, opened);
+        self.finish(end);
+    }
+
+    /// Resolve the saved devices to output targets (monitor first, then
+    /// broadcast unless one stream plays on both); None after reporting
+    /// missing devices
+    fn resolve_outputs<'a>(
+        &self,
+        host: &cpal::Host,
+        output_devices: &'a [cpal::Device],
+    ) -> Option<Vec<OutputTarget<'a>>> {
+        // Resolve saved devices, falling back to the system default if missing
+        let device_ids = audio::output_device_ids(output_devices);
+        let system_default = audio::default_device_index(host, output_devices);
+        let default_index = self
+            .settings
+            .device_fallback_enabled
+            .then_some(system_default)
+            .flatten();
+        // "System default" plays on whatever the default output is now
+        let device_id_1 =
+            audio::follow_system_default(&self.device_ids.0, &device_ids, system_default);
+        let device_id_2 =
+            audio::follow_system_default(&self.device_ids.1, &device_ids, system_default);
+        // Injected device loss resolves both as if they were unplugged
+        let resolve = if audio::take_fault(audio::Fault::DeviceLoss) {
+            warn!("Simulating loss of {} / {}", device_id_1, device_id_2);
+            audio::resolve_missing_device
+        } else {
+            audio::resolve_device
+        };
+        let (resolved_1, resolved_2) = match (
+            resolve(&device_id_1, &device_ids, default_index),
+            resolve(&device_id_2, &device_ids, default_index),
+        ) {
+            (Ok(r1), Ok(r2)) => (r1, r2),
+            _ => {
+                error!("Devices not found: {} / {}", device_id_1, device_id_2);
+                recap::record_error();
+                let error_msg = i18n::tf(
+                    "notify.devices_not_found",
+                    &[
+                        ("monitor", &device_id_1.to_string()),
+                        ("broadcast", &device_id_2.to_string()),
+                    ],
+                );
+                if let Err(e) = self.app_handle.emit("audio-device-error", error_msg) {
+                    error!("Failed to emit device error event: {}", e);
                 }
+                return None;
+            }
+        };
+
+        for (role, saved, resolved) in [
+            (DeviceRole::Monitor, &device_id_1, resolved_1),
+            (DeviceRole::Broadcast, &device_id_2, resolved_2),
+        ] {
+            if let Some(change) = audio::record_resolution(role, saved, resolved, &device_ids) {
+                emit_device_fallback(&self.app_handle, &change);
             }
         }
 
-        // Calculate duration (with trim)
-        let total_frames = audio_data.samples.len() / audio_data.channels as usize;
-        let actual_start = start_frame.unwrap_or(0);
-        let actual_end = end_frame.unwrap_or(total_frames);
-        let trimmed_frames = actual_end.saturating_sub(actual_start);
+        let device_1 = &output_devices[resolved_1.index()];
+        let device_2 = &output_devices[resolved_2.index()];
+        // While a microphone is routed, the broadcast output can be mixed
+        // into its CABLE Input stream instead of playing on a device
+        let mic_mix = self
+            .settings
+            .soundboard_mic_mix_enabled
+            .then(vbcable::mix_format)
+            .flatten();
+        let broadcast_target = match mic_mix {
+            Some((rate, channels)) => OutputTarget::MicMix(rate, channels),
+            None => OutputTarget::Device(device_2),
+        };
+        // Two streams on one device phase against each other and double
+        // the level; the monitor stream alone plays the sound there
+        let single_stream = mic_mix.is_none()
+            && resolved_1.index() == resolved_2.index()
+            && self.settings.identical_device_behavior == IdenticalDeviceBehavior::SingleStream;
+        if single_stream {
+            debug!("Monitor and broadcast are the same device, playing one stream");
+            return Some(vec![OutputTarget::Device(device_1)]);
+        }
+        Some(vec![OutputTarget::Device(device_1), broadcast_target])
+    }
 
-        let duration_secs = trimmed_frames as f64 / audio_data.sample_rate as f64;
-        let total_sleep_ms = (duration_secs * 1000.0) as u64;
+    /// Levels of the monitor and broadcast output
+    ///
+    /// Each output follows the shared volume with its output level and the
+    /// sound's device trim; broadcast is silent while Discord mutes it. The
+    /// streams' envelopes add fades and the trigger's routing.
+    fn output_levels(&self) -> (f32, f32) {
+        let volume = f32::from_bits(self.volume.load(Ordering::Relaxed));
+        let volume = audio::volume_with_gain(volume, self.normalization);
+        let (monitor_trim, broadcast_trim) = self.device_trims;
+        (
+            audio::volume_with_gain(volume * self.settings.monitor_output_volume, monitor_trim),
+            broadcast_volume(audio::volume_with_gain(
+                volume * self.settings.broadcast_output_volume,
+                broadcast_trim,
+            )),
+        )
+    }
 
-        // Wait for completion or stop signal, emitting progress events
-        let check_interval = Duration::from_millis(10); // 10ms for fast stop response
-        let progress_interval = 50u64; // Emit progress every 50ms
-        let mut elapsed_ms = 0u64;
-        let mut last_progress_ms = 0u64;
+    /// Conversion of the sound to each target's rate, once per rate (reopened
+    /// streams and same-rate outputs share it)
+    ///
+    /// Cached conversions are reused; without high-quality resampling a
+    /// missing one is left to build in the background for the next play
+    /// (returned with the rate). Streamed sounds are interpolated.
+    fn conversions(&self, sound_audio: &SoundAudio, targets: &[OutputTarget<'_>]) -> Conversions {
+        // Cached conversions are of the file, not of a reshaped copy
+        let resample_cache_enabled = self.settings.resample_cache_enabled && !self.reshaped();
+        let high_quality_resampling = self.settings.high_quality_resampling;
+        let cache = self.app_handle.state::<AudioManager>().get_cache();
 
-        while elapsed_ms < total_sleep_ms {
-            // Check for stop signal
-            if stop_rx.try_recv().is_ok() {
-                break;
+        let mut conversions: HashMap<u32, Arc<AudioData>> = HashMap::new();
+        let mut missing_rates = Vec::new();
+        let mut converted_for = |target: OutputTarget| -> Option<Arc<AudioData>> {
+            let SoundAudio::Decoded(audio_data) = sound_audio else {
+                return None;
+            };
+            if !high_quality_resampling && !resample_cache_enabled {
+                return None;
+            }
+            let rate = match target {
+                OutputTarget::Device(device) => audio::output_sample_rate(device).ok()?,
+                OutputTarget::MicMix(rate, _) => rate,
+            };
+            if rate == audio_data.sample_rate {
+                return None;
             }
+            if let Some(converted) = conversions.get(&rate) {
+                return Some(converted.clone());
+            }
+            let cached = resample_cache_enabled
+                .then(|| cache.lock().unwrap().get_resampled(&self.file_path, rate))
+                .flatten();
+            let converted = match cached {
+                Some(converted) => converted,
+                None if high_quality_resampling => {
+                    let converted = resample_timed(audio_data, rate);
+                    if resample_cache_enabled {
+                        cache
+                            .lock()
+                            .unwrap()
+                            .insert_resampled(&self.file_path, converted.clone());
+                    }
+                    converted
+                }
+                None => {
+                    if !missing_rates.iter().any(|(missing, _)| *missing == rate) {
+                        missing_rates.push((rate, audio_data.clone()));
+                    }
+                    return None;
+                }
+            };
+            conversions.insert(rate, converted.clone());
+            Some(converted)
+        };
+        let converted: Vec<_> = targets
+            .iter()
+            .map(|target| converted_for(*target))
+            .collect();
+        (converted, missing_rates)
+    }
 
-            thread::sleep(check_interval);
-            elapsed_ms += 10;
-
-            // Emit progress event every 50ms (not every 10ms check)
-            if elapsed_ms - last_progress_ms >= progress_interval {
-                last_progress_ms = elapsed_ms;
-                let progress_pct =
-                    ((elapsed_ms as f64 / total_sleep_ms as f64) * 100.0).min(100.0) as u8;
-                if let Err(e) = app_handle.emit(
-                    "playback-progress",
-                    PlaybackProgress {
-                        playback_id: playback_id_clone.clone(),
-                        elapsed_ms,
-                        total_ms: total_sleep_ms,
-                        progress_pct,
+    /// Create streams with shared volume state and trim parameters
+    ///
+    /// Each stream advances its own cursor (monitor first) from the callback,
+    /// following its envelope from `elapsed_ms` into the playback; the
+    /// monitor cursor signals progress. A streamed sound starts a decoder
+    /// feeding all of them.
+    fn start_streams(
+        &self,
+        plan: &StreamPlan<'_>,
+        start_frame: Option<usize>,
+        elapsed_ms: u64,
+    ) -> Result<OpenStreams, String> {
+        let start_frame = start_frame.unwrap_or(0);
+        let sample_rate = plan.sound_audio.sample_rate();
+        let cursors: Vec<_> = (0..plan.outputs.len())
+            .map(|i| {
+                let role = if i == 0 {
+                    DeviceRole::Monitor
+                } else {
+                    DeviceRole::Broadcast
+                };
+                let mut envelope = audio::PlaybackEnvelope::new(sample_rate, start_frame)
+                    .starting_at(elapsed_ms)
+                    .with_routing(self.routing, role);
+                // A crossfading restart fades in (repeats are past it)
+                if let Some(duration) = self.crossfade {
+                    envelope = envelope.with_fade_in(duration);
+                }
+                if i == 0 {
+                    envelope = envelope.with_progress(PROGRESS_INTERVAL);
+                }
+                audio::PlaybackCursor::with_envelope(
+                    start_frame,
+                    self.finished_tx.clone(),
+                    envelope,
+                )
+            })
+            .collect();
+        let sources: Vec<_> = match plan.sound_audio {
+            SoundAudio::Decoded(audio_data) => plan
+                .outputs
+                .iter()
+                .map(|(_, converted, _)| audio::PlaybackSource::Decoded {
+                    audio_data: audio_data.clone(),
+                    converted: converted.clone(),
+                })
+                .collect(),
+            SoundAudio::Streamed(_) => {
+                let (sample_rate, rings) = audio::stream_audio_file(
+                    &self.file_path,
+                    start_frame,
+                    plan.end_frame,
+                    plan.outputs.len(),
+                )
+                .map_err(|e| format!("Failed to stream audio: {}", e))?;
+                rings
+                    .into_iter()
+                    .map(|ring| audio::PlaybackSource::Streamed { ring, sample_rate })
+                    .collect()
+            }
+        };
+        let master_volume = self.app_handle.state::<AudioManager>().get_master_volume();
+        let mut streams = Vec::with_capacity(plan.outputs.len());
+        for (i, ((target, _, volume), source)) in plan.outputs.iter().zip(sources).enumerate() {
+            let output = match *target {
+                OutputTarget::Device(device) => audio::create_playback_stream(
+                    device,
+                    source,
+                    Arc::clone(volume),
+                    master_volume.clone(),
+                    cursors[i].clone(),
+                    plan.end_frame,
+                    i == 0,
+                    self.settings.output_limiter_enabled,
+                    self.settings.small_output_buffers,
+                )
+                .map(PlaybackOutput::Stream)
+                .map_err(|e| e.to_string()),
+                OutputTarget::MicMix(rate, channels) => {
+                    vbcable::add_mix_source(audio::MixRenderer::new(
+                        source,
+                        Arc::clone(volume),
+                        master_volume.clone(),
+                        cursors[i].clone(),
+                        plan.end_frame,
+                        rate,
+                        channels,
+                    ))
+                    .map(PlaybackOutput::MicMix)
+                }
+            }
+            .map_err(|e| format!("Failed to create stream {}: {}", i + 1, e))?;
+            streams.push(output);
+        }
+        Ok((streams, cursors))
+    }
+
+    /// Transition the sound from Decoding to Playing
+    fn mark_playing(&self) {
+        let active_sounds = self.app_handle.state::<AudioManager>().get_active_sounds();
+        let mut sounds = active_sounds.lock().unwrap();
+        let mut now_playing = false;
+        if let Some(state) = sounds.get(&self.sound_id) {
+            // Only update if this is still our playback
+            if state.playback_id() == self.playback_id {
+                sounds.insert(
+                    self.sound_id.clone(),
+                    SoundState::Playing {
+                        playback_id: self.playback_id.clone(),
+                        started_at: std::time::Instant::now(),
                     },
-                ) {
-                    error!("Failed to emit progress event: {}", e);
+                );
+                now_playing = true;
+                debug!(
+                    "Sound {} now playing (playback {})",
+                    self.sound_id, self.playback_id
+                );
+            }
+        }
+        drop(sounds);
+
+        if now_playing {
+            emit_sound_state(
+                &self.app_handle,
+                &self.sound_id,
+                &self.playback_id,
+                SoundStatus::Playing,
+            );
+        }
+
+        api::publish(
+            &self.app_handle,
+            TriggerFeedback::SoundStarted {
+                sound_id: self.sound_id.clone(),
+                playback_id: self.playback_id.clone(),
+            },
+        );
+    }
+
+    /// Follow the playback's signals until its streams end or it's stopped
+    ///
+    /// Sleeps until a signal arrives; while playing, a deadline only catches
+    /// a stream whose device stopped calling back. Progress follows the
+    /// monitor cursor, so it stays in step with what was actually played.
+    fn run_playback_loop(
+        &self,
+        plan: &StreamPlan<'_>,
+        (mut streams, mut cursors): OpenStreams,
+    ) -> PlaybackEnd {
+        let extensions = self.app_handle.state::<AudioManager>().get_extensions();
+        // Length of the current streams
+        let mut segment_start = plan.first_frame.unwrap_or(0);
+        let mut total_ms = plan.length_ms(segment_start);
+        let mut segment_started = Instant::now();
+        let mut stopped = false;
+
+        // Routing time played before the current streams (its clock runs
+        // on across repeats, the cursors restart on extension)
+        let mut routing_offset_ms = 0;
+        let mut routing_output = None;
+        let mut paused_since: Option<Instant> = None;
+        let mut paused_total = Duration::ZERO;
+        let streams_started = Instant::now();
+        // Set once a restart of the sound asks this playback to fade out
+        let mut fading_out = false;
+
+        loop {
+            let signal = if paused_since.is_some() {
+                self.stop_rx
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                let deadline =
+                    segment_started + Duration::from_millis(total_ms) + STREAM_STALL_GRACE;
+                self.stop_rx
+                    .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            };
+            let stalled = match signal {
+                Ok(PlaybackSignal::Stop) | Err(RecvTimeoutError::Disconnected) => {
+                    stopped = true;
+                    break;
+                }
+                Ok(PlaybackSignal::Finished) => false,
+                Err(RecvTimeoutError::Timeout) => {
+                    warn!(
+                        "Playback {} didn't finish in time, ending it",
+                        self.playback_id
+                    );
+                    true
+                }
+                // Replaced by a restart: the streams fade out and finish
+                // (stopped right away when paused, nothing is audible)
+                Ok(PlaybackSignal::FadeOut(duration)) => {
+                    if paused_since.is_some() {
+                        stopped = true;
+                        break;
+                    }
+                    for cursor in &cursors {
+                        cursor.fade_out(duration);
+                    }
+                    fading_out = true;
+                    continue;
+                }
+                Ok(PlaybackSignal::Progress) => {
+                    let elapsed_ms = plan.frames_to_ms(cursors[0].frame() - segment_start as f64);
+                    self.emit_progress(elapsed_ms, total_ms);
+
+                    // Tell the UI which output an A/B comparison is on
+                    let output = self.routing.segment_output(routing_offset_ms + elapsed_ms);
+                    if output != routing_output {
+                        routing_output = output;
+                        if let Some(output) = output {
+                            let payload = RoutingSegment {
+                                playback_id: self.playback_id.clone(),
+                                output,
+                            };
+                            if let Err(e) = self.app_handle.emit("routing-segment", payload) {
+                                error!("Failed to emit routing segment event: {}", e);
+                            }
+                        }
+                    }
+                    continue;
                 }
+                Ok(PlaybackSignal::Changed) => {
+                    // Paused streams stop pulling samples; the stall
+                    // deadline skips the time spent paused
+                    let pause_requested = self.paused.load(Ordering::Acquire);
+                    if pause_requested && fading_out {
+                        stopped = true;
+                        break;
+                    }
+                    if pause_requested != paused_since.is_some() {
+                        for stream in &streams {
+                            if let Err(e) = stream.set_paused(pause_requested) {
+                                warn!("Failed to pause/resume {}: {}", self.playback_id, e);
+                            }
+                        }
+                        match paused_since.take() {
+                            Some(since) => {
+                                segment_started += since.elapsed();
+                                paused_total += since.elapsed();
+                            }
+                            None => paused_since = Some(Instant::now()),
+                        }
+                        let payload = PlaybackPaused {
+                            playback_id: self.playback_id.clone(),
+                            paused: pause_requested,
+                        };
+                        if let Err(e) = self.app_handle.emit("playback-paused", payload) {
+                            error!("Failed to emit playback paused event: {}", e);
+                        }
+                    }
+
+                    let (monitor_level, broadcast_level) = self.output_levels();
+                    for ((_, _, level), value) in
+                        plan.outputs.iter().zip([monitor_level, broadcast_level])
+                    {
+                        level.store(value.to_bits(), Ordering::Relaxed);
+                    }
+                    continue;
+                }
+            };
+
+            if !stalled && !cursors.iter().all(|cursor| cursor.is_finished()) {
+                continue;
+            }
+            if fading_out {
+                stopped = true;
+                break;
+            }
+            // Play once more if the sound was re-triggered with Extend
+            if !extensions.lock().unwrap().remove(&self.playback_id) {
+                break;
             }
+            routing_offset_ms += plan.frames_to_ms(cursors[0].frame() - segment_start as f64);
+            match self.start_streams(plan, plan.start_frame, routing_offset_ms) {
+                Ok((new_streams, new_cursors)) => {
+                    streams = new_streams;
+                    cursors = new_cursors;
+                }
+                Err(e) => {
+                    error!("Failed to extend playback: {}", e);
+                    break;
+                }
+            }
+            debug!("Extended playback {}", self.playback_id);
+            segment_start = plan.start_frame.unwrap_or(0);
+            total_ms = plan.length_ms(segment_start);
+            segment_started = Instant::now();
+        }
+
+        drop(streams);
+        let paused_total = paused_total + paused_since.map_or(Duration::ZERO, |s| s.elapsed());
+        recap::record_playback_time(streams_started.elapsed().saturating_sub(paused_total));
+        PlaybackEnd {
+            stopped,
+            position_ms: plan.frames_to_ms(cursors[0].frame()),
+        }
+    }
+
+    /// Emit `playback-progress` at `elapsed_ms` into the current streams
+    fn emit_progress(&self, elapsed_ms: u64, total_ms: u64) {
+        let progress_pct = if total_ms == 0 {
+            100
+        } else {
+            ((elapsed_ms as f64 / total_ms as f64) * 100.0).min(100.0) as u8
+        };
+        if let Err(e) = self.app_handle.emit(
+            "playback-progress",
+            PlaybackProgress {
+                playback_id: self.playback_id.clone(),
+                elapsed_ms,
+                total_ms,
+                progress_pct,
+            },
+        ) {
+            error!("Failed to emit progress event: {}", e);
         }
+    }
+
+    /// Clean up after the streams ended and hand over to what plays next
+    fn finish(&self, end: PlaybackEnd) {
+        let manager = self.app_handle.state::<AudioManager>();
+        manager
+            .get_extensions()
+            .lock()
+            .unwrap()
+            .remove(&self.playback_id);
 
-        // Clean up
-        drop(stream_1);
-        drop(stream_2);
+        // Remember where a stopped sound was, forget it once it played out
+        if self.resume_enabled {
+            let position_ms = end.stopped.then_some(end.position_ms);
+            self.app_handle
+                .state::<AppState>()
+                .write_session()
+                .record_position(&self.sound_id, position_ms);
+        }
 
-        let total_duration_ms = thread_start.elapsed().as_millis();
+        let total_duration_ms = self.started.elapsed().as_millis();
         debug!(
-            playback_id = %playback_id_clone,
-            sound_id = %sound_id_clone,
+            playback_id = %self.playback_id,
+            sound_id = %self.sound_id,
             total_duration_ms = total_duration_ms,
             "Playback complete"
         );
@@ -377,86 +1635,431 @@ pub fn play_dual_output(
         // Emit playback complete event first, so frontend knows it's done.
         // This prevents race conditions where frontend sends stop_playback
         // just before receiving this event.
-        if let Err(e) = app_handle.emit("playback-complete", &playback_id_clone) {
+        if let Err(e) = self.app_handle.emit("playback-complete", &self.playback_id) {
             error!("Failed to emit playback complete event: {}", e);
         }
 
+        if !self.sound_id.is_empty() {
+            api::publish(
+                &self.app_handle,
+                TriggerFeedback::SoundStopped {
+                    sound_id: self.sound_id.clone(),
+                    playback_id: self.playback_id.clone(),
+                },
+            );
+        }
+
         // Remove from manager last
-        manager_inner.lock().unwrap().remove(&playback_id_clone);
+        manager.unregister_playback(&self.playback_id);
 
         // Remove from active sounds tracking ONLY if this playback is still the current one
         // (prevents race condition when a newer playback has already replaced us)
-        if !sound_id_clone.is_empty() {
-            let mut sounds = active_sounds.lock().unwrap();
-            if let Some(state) = sounds.get(&sound_id_clone) {
-                if state.playback_id() == playback_id_clone {
-                    sounds.remove(&sound_id_clone);
-                }
-            }
+        if !self.sound_id.is_empty()
+            && release_sound(
+                &manager.get_active_sounds(),
+                &self.sound_id,
+                &self.playback_id,
+            )
+        {
+            emit_sound_state(
+                &self.app_handle,
+                &self.sound_id,
+                &self.playback_id,
+                SoundStatus::Stopped,
+            );
         }
-    });
 
-    let action = if stopped_playback_id.is_some() {
-        "restarted"
-    } else {
-        "started"
-    };
+        // A queued sound hands over to the next one
+        super::queue::playback_finished(&self.app_handle, &self.playback_id);
 
-    Ok(PlaybackResult {
-        playback_id: Some(playback_id),
-        action: action.to_string(),
-        stopped_playback_id,
-    })
+        // Triggers queued by the Queue policy play once it played out
+        let follow_ups = manager.take_follow_ups(&self.playback_id);
+        if follow_ups > 0 && !end.stopped {
+            play_follow_up(&self.app_handle, &self.sound_id, follow_ups);
+        }
+    }
+}
+
+/// Play a sound again for a trigger queued with the Queue policy, handing the
+/// remaining triggers on to the new playback
+fn play_follow_up(app_handle: &tauri::AppHandle, sound_id: &str, follow_ups: u32) {
+    debug!(
+        "Playing queued trigger of {} ({} waiting)",
+        sound_id, follow_ups
+    );
+    match super::queue::play_sound(app_handle, sound_id) {
+        Ok(Some(playback_id)) if follow_ups > 1 => {
+            app_handle
+                .state::<AudioManager>()
+                .add_follow_ups(&playback_id, follow_ups - 1);
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to play queued trigger of {}: {}", sound_id, e),
+    }
+}
+
+/// Roles currently playing on the system default because their device is missing
+#[tauri::command]
+pub fn get_device_fallbacks() -> Result<Vec<DeviceFallback>, AppError> {
+    Ok(audio::active_fallbacks())
 }
 
-/// Stops all currently playing audio
+/// Stops all currently playing audio (and empties the playback queue)
 #[tauri::command]
-pub fn stop_all_audio(manager: State<'_, AudioManager>) -> Result<(), String> {
+pub fn stop_all_audio<R: Runtime>(
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle<R>,
+) -> Result<(), AppError> {
     manager.stop_all();
+    super::queue::emit_queue_updated(&app_handle, Default::default());
     Ok(())
 }
 
 /// Stops a specific playback by ID
 #[tauri::command]
-pub fn stop_playback(playback_id: String, manager: State<'_, AudioManager>) -> Result<(), String> {
+pub fn stop_playback(
+    playback_id: String,
+    manager: State<'_, AudioManager>,
+) -> Result<(), AppError> {
     if manager.signal_stop(&playback_id) {
         Ok(())
     } else {
-        Err(format!("Playback not found: {}", playback_id))
+        Err(AppError::new(
+            ErrorCode::PlaybackNotFound,
+            format!("Playback not found: {}", playback_id),
+        )
+        .with_context("playback_id", playback_id))
+    }
+}
+
+/// Pause a playback where it is (`resume_playback` continues it)
+///
+/// Emits `playback-paused`.
+#[tauri::command]
+pub fn pause_playback(
+    playback_id: String,
+    manager: State<'_, AudioManager>,
+) -> Result<(), AppError> {
+    set_playback_paused(&manager, playback_id, true)
+}
+
+/// Continue a paused playback from where it was paused
+///
+/// Emits `playback-paused`.
+#[tauri::command]
+pub fn resume_playback(
+    playback_id: String,
+    manager: State<'_, AudioManager>,
+) -> Result<(), AppError> {
+    set_playback_paused(&manager, playback_id, false)
+}
+
+fn set_playback_paused(
+    manager: &AudioManager,
+    playback_id: String,
+    paused: bool,
+) -> Result<(), AppError> {
+    if manager.set_paused(&playback_id, paused) {
+        Ok(())
+    } else {
+        Err(AppError::new(
+            ErrorCode::PlaybackNotFound,
+            format!("Playback not found: {}", playback_id),
+        )
+        .with_context("playback_id", playback_id))
+    }
+}
+
+/// Change the volume (0.0-1.0) of a playing sound without restarting it
+#[tauri::command]
+pub fn set_playback_volume(
+    playback_id: String,
+    volume: f32,
+    manager: State<'_, AudioManager>,
+) -> Result<(), AppError> {
+    if !(0.0..=1.0).contains(&volume) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Volume must be between 0.0 and 1.0",
+        )
+        .with_context("volume", volume));
+    }
+    if manager.set_playback_volume(&playback_id, volume) {
+        Ok(())
+    } else {
+        Err(AppError::new(
+            ErrorCode::PlaybackNotFound,
+            format!("Playback not found: {}", playback_id),
+        )
+        .with_context("playback_id", playback_id))
+    }
+}
+
+/// Audition a library sound on the monitor output only
+///
+/// Plays the sound's preview region, or its trimmed range when it has none,
+/// on `device_id` if given (e.g. a device being picked in Settings). The
+/// preview isn't tracked as the sound's playback, so it never blocks or
+/// restarts a real trigger; stop it with `stop_playback`.
+#[tauri::command]
+pub fn preview_sound(
+    sound_id: SoundId,
+    device_id: Option<DeviceId>,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<PlaybackResult, AppError> {
+    let sound = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .find(|s| s.id == sound_id)
+        .cloned()
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::SoundNotFound,
+                format!("Sound not found: {}", sound_id.as_str()),
+            )
+            .with_context("sound_id", sound_id.as_str())
+        })?;
+
+    let settings = state.read_settings().clone();
+    let Some(monitor) = device_id
+        .or(sound.monitor_device_id)
+        .or(settings.monitor_device_id)
+    else {
+        return Err(AppError::new(
+            ErrorCode::DeviceMissing,
+            "Please configure audio devices in Settings first",
+        ));
+    };
+    // The broadcast output stays silent, so it needn't be configured yet
+    let broadcast = sound
+        .broadcast_device_id
+        .or(settings.broadcast_device_id)
+        .unwrap_or_else(|| monitor.clone());
+
+    let (start_ms, end_ms) = match sound.preview {
+        Some(region) => (Some(region.start_ms), Some(region.end_ms)),
+        None => (sound.trim_start_ms, sound.trim_end_ms),
+    };
+
+    play_dual_output(
+        sound.file_path,
+        monitor,
+        broadcast,
+        sound.volume.unwrap_or(settings.default_volume),
+        start_ms,
+        end_ms,
+        None,
+        None,
+        Some(OutputRouting::MonitorOnly),
+        None,
+        manager,
+        app_handle,
+    )
+}
+
+/// Play a short test tone on one device, the monitor output by default
+///
+/// Nothing plays on the other output, so routing can be checked without
+/// sending anything to the broadcast device. Follows the master volume and
+/// output limiter; returns once the tone has played.
+#[tauri::command]
+pub async fn play_test_tone(
+    device_id: Option<DeviceId>,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let (device_id, master_volume, limit, small_buffers) = {
+        let settings = state.read_settings();
+        (
+            device_id.or_else(|| settings.monitor_device_id.clone()),
+            settings.master_volume,
+            settings.output_limiter_enabled,
+            settings.small_output_buffers,
+        )
+    };
+    let device_id = device_id.ok_or_else(|| {
+        AppError::new(
+            ErrorCode::DeviceMissing,
+            "Please configure audio devices in Settings first",
+        )
+    })?;
+
+    // Streams are not Send, so play on a blocking thread
+    tauri::async_runtime::spawn_blocking(move || -> Result<(), audio::AudioError> {
+        let device = audio::find_output_device(&device_id)?;
+        let (finished_tx, finished_rx) = mpsc::channel();
+        let stream = audio::create_playback_stream(
+            &device,
+            audio::PlaybackSource::Decoded {
+                audio_data: Arc::new(audio::test_tone()),
+                converted: None,
+            },
+            Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            Arc::new(AtomicU32::new(master_volume.to_bits())),
+            audio::PlaybackCursor::new(0, finished_tx),
+            None,
+            false,
+            limit,
+            small_buffers,
+        )?;
+        stream
+            .play()
+            .map_err(|e| audio::AudioError::StreamStart(e.to_string()))?;
+        info!("Playing test tone on {}", device_id);
+        // Don't hang on a device that stopped calling back
+        if finished_rx
+            .recv_timeout(audio::TEST_TONE_DURATION + Duration::from_secs(2))
+            .is_err()
+        {
+            warn!("Test tone on {} did not finish", device_id);
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Test tone task failed: {}", e)))?
+    .map_err(AppError::from)
+}
+
+/// Set the master volume (0.0-1.0); playing sounds follow without restarting
+///
+/// Emits `settings-changed`.
+#[tauri::command]
+pub fn set_master_volume(
+    volume: f32,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    if !(0.0..=1.0).contains(&volume) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Master volume must be between 0 and 1",
+        )
+        .with_context("volume", volume));
+    }
+
+    apply_master_volume(&app_handle, &state, &manager, volume)?;
+    Ok(())
+}
+
+/// Change the master volume by `delta`, clamped to 0.0-1.0 (volume hotkeys)
+///
+/// Returns the new volume. Emits `settings-changed`.
+pub fn step_master_volume(app_handle: &tauri::AppHandle, delta: f32) -> Result<f32, String> {
+    let state = app_handle.state::<AppState>();
+    let manager = app_handle.state::<AudioManager>();
+    let volume = (state.read_settings().master_volume + delta).clamp(0.0, 1.0);
+    apply_master_volume(app_handle, &state, &manager, volume)?;
+    Ok(volume)
+}
+
+/// Apply a master volume to playing sounds, persist it and notify the frontend
+fn apply_master_volume(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    manager: &AudioManager,
+    volume: f32,
+) -> Result<(), String> {
+    manager.set_master_volume(volume);
+
+    let mut settings = state.read_settings().clone();
+    settings.master_volume = volume;
+    state.update_and_save_settings(app_handle, settings.clone())?;
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        error!("Failed to emit settings changed event: {}", e);
     }
+    Ok(())
 }
 
 /// Clear the audio cache (forces re-decoding on next play)
 #[tauri::command]
-pub fn clear_audio_cache(manager: State<'_, AudioManager>) -> Result<(), String> {
+pub fn clear_audio_cache(manager: State<'_, AudioManager>) -> Result<(), AppError> {
     manager.clear_cache();
     Ok(())
 }
 
 /// Get audio cache statistics
 #[tauri::command]
-pub fn get_cache_stats(manager: State<'_, AudioManager>) -> Result<CacheStats, String> {
+pub fn get_cache_stats(manager: State<'_, AudioManager>) -> Result<CacheStats, AppError> {
     Ok(manager.cache_stats())
 }
 
 /// Get waveform data for an audio file
+///
+/// Cached per file and peak count; a file is decoded once and its peaks are
+/// kept on disk across restarts.
 #[tauri::command]
 pub fn get_waveform(
     file_path: String,
     num_peaks: usize,
     manager: State<'_, AudioManager>,
-) -> Result<WaveformData, String> {
-    // Use cache to get or decode the audio
-    let audio_data = manager
-        .get_cache()
-        .lock()
-        .unwrap()
-        .get_or_decode(&file_path)
-        .map_err(|e| e.to_string())?;
+) -> Result<WaveformData, AppError> {
+    Ok(manager.waveform(&file_path, num_peaks)?)
+}
+
+/// Get waveform data for part of an audio file (trim editor zoom)
+///
+/// Peaks span `start_ms`..`end_ms` and are scaled like the whole file's;
+/// `duration_ms` is the whole file's.
+#[tauri::command]
+pub fn get_waveform_range(
+    file_path: String,
+    start_ms: u64,
+    end_ms: u64,
+    num_peaks: usize,
+    manager: State<'_, AudioManager>,
+) -> Result<WaveformData, AppError> {
+    Ok(manager.waveform_range(&file_path, start_ms, end_ms, num_peaks)?)
+}
+
+/// Get grid card assets (tiny waveform, duration, icon) of many sounds at once
+///
+/// Served from the sidecar cache; sounds missing from it are decoded once.
+/// Unknown sound IDs are skipped.
+#[tauri::command]
+pub fn get_card_assets(
+    sound_ids: Vec<SoundId>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<CardAsset>, AppError> {
+    let sounds: Vec<_> = {
+        let library = state.read_sounds();
+        sound_ids
+            .iter()
+            .filter_map(|id| library.sounds.iter().find(|s| &s.id == id).cloned())
+            .collect()
+    };
+    Ok(card_assets::card_assets(&app_handle, &sounds))
+}
+
+/// Get spectrogram data (quantized STFT magnitudes) for an audio file
+#[tauri::command]
+pub fn get_spectrogram(
+    file_path: String,
+    width: usize,
+    height: usize,
+    manager: State<'_, AudioManager>,
+) -> Result<SpectrogramData, AppError> {
+    let audio_data = AudioCache::load(&manager.get_cache(), &file_path)?;
+
+    // Clamp to sane limits so a bad request can't allocate huge matrices
+    let width = width.min(audio::MAX_SPECTROGRAM_WIDTH);
+    let height = height.min(audio::MAX_SPECTROGRAM_HEIGHT);
+
+    Ok(audio::generate_spectrogram(&audio_data, width, height))
+}
+
+/// Get transient onset timestamps (ms) for an audio file, used as trim snap points
+#[tauri::command]
+pub fn detect_onsets(
+    file_path: String,
+    manager: State<'_, AudioManager>,
+) -> Result<Vec<u64>, AppError> {
+    let audio_data = AudioCache::load(&manager.get_cache(), &file_path)?;
 
-    // Generate waveform peaks
-    let waveform = audio::generate_peaks(&audio_data, num_peaks);
-    Ok(waveform)
+    Ok(audio::detect_onsets(&audio_data))
 }
 
 /// Preload audio files into cache (background, non-blocking)
@@ -465,15 +2068,13 @@ pub fn get_waveform(
 pub fn preload_sounds(file_paths: Vec<String>, manager: State<'_, AudioManager>) {
     let cache = manager.get_cache();
 
-    // Spawn background thread to preload without blocking UI
-    thread::spawn(move || {
+    // Preload on the decode pool without blocking UI. A single job keeps the
+    // other decode workers free for playback triggers.
+    manager.get_decode_pool().execute(move || {
         for path in file_paths {
-            let mut cache_guard = cache.lock().unwrap();
-            if cache_guard.get_or_decode(&path).is_ok() {
+            if AudioCache::load(&cache, &path).is_ok() {
                 debug!("Preloaded: {}", path);
             }
-            // Release lock between files to not block playback
-            drop(cache_guard);
         }
         debug!("Preload complete");
     });
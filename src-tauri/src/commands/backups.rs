@@ -0,0 +1,198 @@
+//! Backup commands (rolling copies of sounds.json, hotkeys.json, settings.json)
+
+use std::path::PathBuf;
+
+use tauri::{Emitter, Manager, State};
+
+use crate::discord::{self, DiscordCredentials};
+use crate::error::{AppError, ErrorCode};
+use crate::persistence::{self, BackupEntry};
+use crate::profiles;
+use crate::reload::{self, DataFile};
+use crate::session::ProfileDevices;
+use crate::settings::AppSettings;
+use crate::{api, api_security, audio, vbcable, AppState};
+
+/// Data files whose backups can be restored
+const RESTORABLE: [&str; 3] = ["sounds", "hotkeys", "settings"];
+
+/// Directory of a data file (and its backups): the library and hotkeys are
+/// the active profile's
+fn dir_of(app_handle: &tauri::AppHandle, source: &str) -> Result<PathBuf, String> {
+    if source == "settings" {
+        profiles::app_data_dir(app_handle)
+    } else {
+        profiles::data_dir(app_handle)
+    }
+}
+
+/// Backups of the data files, newest first
+#[tauri::command]
+pub fn list_backups(app_handle: tauri::AppHandle) -> Result<Vec<BackupEntry>, AppError> {
+    let mut backups = Vec::new();
+    for source in RESTORABLE {
+        let dir = dir_of(&app_handle, source)?;
+        backups.extend(
+            persistence::list_backups(&dir)?
+                .into_iter()
+                .filter(|b| b.source == source),
+        );
+    }
+    backups.sort_by(|a, b| b.created_ms.cmp(&a.created_ms));
+    Ok(backups)
+}
+
+/// Replace a data file with one of its backups
+///
+/// The current file is backed up first, so a restore can be undone. A
+/// restored library or hotkeys file emits `data-file-changed`, restored
+/// settings emit `settings-changed` and are applied like their setters
+/// apply them (see `apply_restored_settings`).
+#[tauri::command]
+pub fn restore_backup(
+    file_name: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let mut found = None;
+    for source in RESTORABLE {
+        let dir = dir_of(&app_handle, source)?;
+        if let Ok(backup) = persistence::find_backup(&dir, &file_name) {
+            if backup.source == source {
+                found = Some((dir, backup));
+                break;
+            }
+        }
+    }
+    let Some((dir, backup)) = found else {
+        return Err(AppError::new(ErrorCode::FileNotFound, "Backup not found")
+            .with_context("file_name", &file_name));
+    };
+    let invalid = |message: String| {
+        AppError::new(ErrorCode::InvalidRequest, message).with_context("file_name", &file_name)
+    };
+    let content = std::fs::read_to_string(dir.join(persistence::BACKUP_DIR).join(&file_name))
+        .map_err(|e| AppError::internal(format!("Failed to read backup: {}", e)))?;
+
+    // Keep the version being replaced
+    persistence::backup_now(&dir.join(format!("{}.json", backup.source)))?;
+
+    match backup.source.as_str() {
+        "sounds" => reload::restore(&app_handle, DataFile::Sounds, &content).map_err(invalid)?,
+        "hotkeys" => reload::restore(&app_handle, DataFile::Hotkeys, &content).map_err(invalid)?,
+        _ => {
+            let settings: AppSettings = serde_json::from_str(&content)
+                .map_err(|e| invalid(format!("Invalid settings backup: {}", e)))?;
+            let previous = state.read_settings().clone();
+            state.update_and_save_settings(&app_handle, settings.clone())?;
+            apply_restored_settings(&app_handle, &previous, &settings);
+            if let Err(e) = app_handle.emit("settings-changed", &settings) {
+                tracing::error!("Failed to emit settings-changed: {}", e);
+            }
+        }
+    }
+    tracing::info!("Restored backup {}", file_name);
+    Ok(())
+}
+
+/// Apply restored settings to the running app
+///
+/// Volume, devices, watched folders, the spectrum analyzer, the API server,
+/// Discord mute awareness and the microphone processing (gains, noise
+/// reduction, voice changer, mic ducking) apply immediately; active mic
+/// routing is restarted when mic monitoring or the monitor device changed.
+/// Failures are logged, the restored file is kept either way.
+///
+/// Only applied at the next start: mic routing auto-enable and the startup
+/// actions, start minimized, and the autostart flags (the autostart entry
+/// itself is only changed by `enable_autostart`/`disable_autostart`).
+/// Hotkeys live in hotkeys.json and are restored with that file.
+fn apply_restored_settings(
+    app_handle: &tauri::AppHandle,
+    previous: &AppSettings,
+    settings: &AppSettings,
+) {
+    let state = app_handle.state::<AppState>();
+    let manager = app_handle.state::<audio::AudioManager>();
+    manager.set_master_volume(settings.master_volume);
+    if settings.watched_folders != previous.watched_folders {
+        crate::folder_watch::start(app_handle);
+    }
+
+    // Remember the restored devices for this profile, like save_settings
+    let mut session = state.read_session().clone();
+    let devices = ProfileDevices {
+        monitor_device_id: settings.monitor_device_id.clone(),
+        broadcast_device_id: settings.broadcast_device_id.clone(),
+    };
+    if session.record_devices(&profiles::active(), devices) {
+        if let Err(e) = state.update_and_save_session(app_handle, session) {
+            tracing::warn!("Failed to save session: {}", e);
+        }
+    }
+
+    if settings.spectrum_analyzer_enabled != previous.spectrum_analyzer_enabled {
+        if settings.spectrum_analyzer_enabled {
+            super::start_spectrum_analyzer(app_handle.clone());
+        } else {
+            audio::stop_analyzer();
+        }
+    }
+
+    api_security::set_tokens(settings.api_tokens.clone());
+    api_security::set_open_local_access(settings.api_open_local_access);
+    if (settings.api_enabled, settings.api_port) != (previous.api_enabled, previous.api_port) {
+        if !settings.api_enabled {
+            api::stop();
+        } else if let Err(e) = api::start(settings.api_port) {
+            tracing::error!("Failed to start API server: {}", e);
+        }
+    }
+
+    if settings.discord_client_id != previous.discord_client_id {
+        discord::clear_authorization(app_handle);
+    }
+    let discord_of = |s: &AppSettings| {
+        (
+            s.discord_mute_awareness_enabled,
+            s.discord_client_id.clone(),
+            s.discord_client_secret.clone(),
+        )
+    };
+    if discord_of(settings) != discord_of(previous) {
+        match DiscordCredentials::from_settings(settings) {
+            Some(credentials) if settings.discord_mute_awareness_enabled => {
+                discord::start(app_handle.clone(), credentials)
+            }
+            _ => discord::stop(app_handle),
+        }
+    }
+
+    manager.set_mic_duck(settings.microphone_duck());
+    let routing_restart = settings.microphone_monitor_enabled
+        != previous.microphone_monitor_enabled
+        || (settings.microphone_monitor_enabled
+            && settings.monitor_device_id != previous.monitor_device_id);
+    match vbcable::get_routing_status() {
+        Some(microphone_id) if routing_restart => {
+            tracing::info!("Restarting microphone routing for the restored settings");
+            if let Err(e) = vbcable::disable_routing()
+                .and_then(|_| super::route_microphone(settings, &microphone_id, &manager))
+            {
+                tracing::error!("Failed to restart microphone routing: {}", e);
+            }
+        }
+        _ => {
+            vbcable::set_routing_gains(
+                settings.microphone_cable_gain,
+                settings.microphone_monitor_gain,
+            );
+            vbcable::apply_noise_reduction(settings.noise_reduction);
+            vbcable::apply_voice_changer(settings.voice_changer);
+            vbcable::set_duck_times(
+                settings.microphone_duck_attack_ms,
+                settings.microphone_duck_release_ms,
+            );
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! Audio engine status, startup report, self-test, gain staging and failure
+//! injection commands
+
+use std::sync::atomic::AtomicU32;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::audio::{
+    self, AudioData, AudioManager, CacheStats, DeviceId, Fault, GainChain, GainVerdict,
+    LatencyReport, OutputRouting, PoolStatus, StreamErrorRecord, MIN_SEGMENT_MS,
+};
+use crate::error::{AppError, ErrorCode};
+use crate::sounds::{PlayPolicy, SoundId};
+use crate::startup::{self, StartupReport};
+use crate::AppState;
+
+use super::PlaybackResult;
+
+/// Default length of A/B comparison segments
+const DEFAULT_AB_SEGMENT_MS: u64 = 2_000;
+
+/// Availability of a configured output device
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceStatus {
+    pub id: DeviceId,
+    /// Device name if it is currently present
+    pub name: Option<String>,
+    pub available: bool,
+}
+
+/// Microphone routing health
+#[derive(Debug, Clone, Serialize)]
+pub struct MicRoutingStatus {
+    /// Routing enabled in settings
+    pub enabled: bool,
+    /// Microphone currently routed (None if routing is not running)
+    pub active_device_id: Option<String>,
+    /// Routing is running as configured (or intentionally off)
+    pub healthy: bool,
+}
+
+/// Snapshot of the audio engine state
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineStatus {
+    /// Audio host API (e.g. "WASAPI")
+    pub host_api: String,
+    /// Playbacks currently decoding or playing
+    pub active_playbacks: usize,
+    /// Open output streams (two per playing sound)
+    pub active_streams: usize,
+    pub monitor_device: Option<DeviceStatus>,
+    pub broadcast_device: Option<DeviceStatus>,
+    pub cache: CacheStats,
+    /// Decode worker pool load
+    pub decode_pool: PoolStatus,
+    /// Playback worker pool load
+    pub playback_pool: PoolStatus,
+    pub mic_routing: MicRoutingStatus,
+    /// Recent stream errors, oldest first
+    pub recent_stream_errors: Vec<StreamErrorRecord>,
+    /// Size of the VB-Cable download/installer temp directory
+    pub vb_cable_temp_bytes: u64,
+    /// Last `measure_output_latency` result
+    pub output_latency: Option<LatencyReport>,
+}
+
+/// Outcome of a self-test stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Result of one self-test stage
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestStage {
+    pub name: String,
+    pub status: StageStatus,
+    /// Error or skip reason
+    pub detail: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Self-test report (passes if no stage failed)
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub stages: Vec<SelfTestStage>,
+}
+
+/// Expected output level of one sound
+#[derive(Debug, Clone, Serialize)]
+pub struct SoundGainReport {
+    pub sound_id: SoundId,
+    pub name: String,
+    pub chain: GainChain,
+    /// Integrated loudness (None if silent or not decodable)
+    pub measured_lufs: Option<f32>,
+    /// Peak of the file itself (dBFS)
+    pub source_peak_dbfs: Option<f32>,
+    /// Expected peak at the output after the whole chain (dBFS)
+    pub output_peak_dbfs: Option<f32>,
+    /// None if the file could not be decoded
+    pub verdict: Option<GainVerdict>,
+    pub error: Option<String>,
+}
+
+/// Gain staging report for the whole library
+#[derive(Debug, Clone, Serialize)]
+pub struct GainStagingReport {
+    pub sounds: Vec<SoundGainReport>,
+    pub clipping: usize,
+    pub inaudible: usize,
+}
+
+/// Report host API, active streams, device availability, cache, mic routing
+/// health and recent stream errors
+#[tauri::command]
+pub fn get_engine_status(
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+) -> Result<EngineStatus, AppError> {
+    let (monitor_id, broadcast_id, mic_enabled) = {
+        let settings = state.read_settings();
+        (
+            settings.monitor_device_id.clone(),
+            settings.broadcast_device_id.clone(),
+            settings.microphone_routing_enabled,
+        )
+    };
+
+    // A failed enumeration just marks every device unavailable
+    let devices = audio::enumerate_devices().unwrap_or_default();
+    let device_status = |id: DeviceId| {
+        let name = devices.iter().find(|d| d.id == id).map(|d| d.name.clone());
+        DeviceStatus {
+            available: name.is_some(),
+            id,
+            name,
+        }
+    };
+
+    let active_device_id = crate::vbcable::get_routing_status();
+    let routing_alive = crate::vbcable::is_routing_alive();
+    let mic_routing = MicRoutingStatus {
+        enabled: mic_enabled,
+        healthy: if mic_enabled {
+            routing_alive == Some(true)
+        } else {
+            routing_alive.is_none()
+        },
+        active_device_id,
+    };
+
+    let (decode_pool, playback_pool) = manager.pool_status();
+
+    Ok(EngineStatus {
+        host_api: audio::host_name(),
+        active_playbacks: manager.active_playback_count(),
+        active_streams: manager.playing_sound_count() * 2,
+        monitor_device: monitor_id.map(device_status),
+        broadcast_device: broadcast_id.map(device_status),
+        cache: manager.cache_stats(),
+        decode_pool,
+        playback_pool,
+        mic_routing,
+        recent_stream_errors: audio::recent_stream_errors(),
+        vb_cable_temp_bytes: crate::vbcable::temp_dir_size(),
+        output_latency: audio::last_latency_measurement(),
+    })
+}
+
+/// Play a test click on a device and report its output latency
+///
+/// Reports the host's latency for the device plus the app's buffering at
+/// the current smaller-buffers setting, to tune buffer sizes by. The result also
+/// shows up in the engine status.
+#[tauri::command]
+pub async fn measure_output_latency(
+    device_id: DeviceId,
+    state: State<'_, AppState>,
+) -> Result<LatencyReport, AppError> {
+    let small_buffers = state.read_settings().small_output_buffers;
+
+    // Streams are not Send, so measure on a blocking thread
+    tauri::async_runtime::spawn_blocking(move || {
+        let device = audio::find_output_device(&device_id)?;
+        audio::measure_output_latency(&device, device_id, small_buffers)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Latency measurement task failed: {}", e)))?
+    .map_err(AppError::from)
+}
+
+/// Durations of the startup phases (deferred ones may still be missing)
+#[tauri::command]
+pub fn get_startup_report() -> StartupReport {
+    startup::startup_report()
+}
+
+/// Simulate audio failures on the next playbacks (debug builds only)
+///
+/// Arms `count` (default 1) injections of `fault`; `None` disarms all faults.
+/// Device loss resolves both devices as unplugged (fallback and
+/// `audio-device-error` paths), decode failure fails the next decode and
+/// stream-build failure the next output stream.
+#[tauri::command]
+pub fn inject_audio_fault(fault: Option<Fault>, count: Option<u32>) -> Result<(), AppError> {
+    if !cfg!(debug_assertions) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Failure injection is only available in debug builds",
+        ));
+    }
+    match fault {
+        Some(fault) => {
+            let count = count.unwrap_or(1);
+            tracing::warn!("Injecting {:?} into the next {} operations", fault, count);
+            audio::arm_fault(fault, count);
+        }
+        None => audio::clear_faults(),
+    }
+    Ok(())
+}
+
+/// Exercise decode and stream creation on both configured devices
+///
+/// Decodes `file_path` (or the first sound in the library) and opens a
+/// silent stream on the monitor and broadcast devices.
+#[tauri::command]
+pub async fn run_self_test(
+    file_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SelfTestReport, AppError> {
+    let file_path = file_path.or_else(|| {
+        state
+            .read_sounds()
+            .sounds
+            .first()
+            .map(|s| s.file_path.clone())
+    });
+    let (monitor_id, broadcast_id) = {
+        let settings = state.read_settings();
+        (
+            settings.monitor_device_id.clone(),
+            settings.broadcast_device_id.clone(),
+        )
+    };
+
+    // Streams are not Send, so build and drop them on a blocking thread
+    let stages = tauri::async_runtime::spawn_blocking(move || {
+        vec![
+            run_stage("decode", || match &file_path {
+                Some(path) => audio::decode_audio_file(path)
+                    .map(|_| ())
+                    .map_err(|e| StageError::Failed(e.to_string())),
+                None => Err(StageError::Skipped("No sound available to decode")),
+            }),
+            run_stage("monitor_stream", || test_stream(monitor_id.as_ref())),
+            run_stage("broadcast_stream", || test_stream(broadcast_id.as_ref())),
+        ]
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Self-test task failed: {}", e)))?;
+
+    let passed = stages.iter().all(|s| s.status != StageStatus::Failed);
+    tracing::info!(passed = passed, "Audio engine self-test complete");
+
+    Ok(SelfTestReport { passed, stages })
+}
+
+/// Play a sound alternating between monitor-only and broadcast-only segments
+///
+/// Lets users verify that both paths sound identical (e.g. when VB-Cable
+/// resampling is suspected). `routing-segment` events report which output is
+/// audible; stop it like any other playback.
+#[tauri::command]
+pub fn start_ab_comparison(
+    sound_id: SoundId,
+    segment_ms: Option<u64>,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<PlaybackResult, AppError> {
+    let sound = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .find(|s| s.id == sound_id)
+        .cloned()
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::SoundNotFound,
+                format!("Sound not found: {}", sound_id.as_str()),
+            )
+            .with_context("sound_id", sound_id.as_str())
+        })?;
+
+    let settings = state.read_settings().clone();
+    let (Some(device1), Some(device2)) = (settings.monitor_device_id, settings.broadcast_device_id)
+    else {
+        return Err(AppError::new(
+            ErrorCode::DeviceMissing,
+            "Please configure audio devices in Settings first",
+        ));
+    };
+
+    let segment_ms = segment_ms
+        .unwrap_or(DEFAULT_AB_SEGMENT_MS)
+        .max(MIN_SEGMENT_MS);
+    tracing::info!(
+        "A/B comparison of '{}' with {}ms segments",
+        sound.name,
+        segment_ms
+    );
+
+    super::play_dual_output(
+        sound.file_path,
+        device1,
+        device2,
+        sound.volume.unwrap_or(settings.default_volume),
+        sound.trim_start_ms,
+        sound.trim_end_ms,
+        Some(sound.id.as_str().to_owned()),
+        Some(PlayPolicy::Restart),
+        Some(OutputRouting::Alternate { segment_ms }),
+        None,
+        manager,
+        app_handle,
+    )
+}
+
+/// Simulate the playback gain chain for every sound and flag sounds that
+/// will clip or be inaudible
+///
+/// Decodes each file once (outside the playback cache); loudness values are
+/// shared with playback normalization.
+#[tauri::command]
+pub async fn analyze_gain_staging(
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+) -> Result<GainStagingReport, AppError> {
+    let sounds = state.read_sounds().sounds.clone();
+    let (default_volume, normalization_target) = {
+        let settings = state.read_settings();
+        (
+            settings.default_volume,
+            settings
+                .normalization_enabled
+                .then_some(settings.normalization_target_lufs),
+        )
+    };
+    let loudness_cache = manager.get_loudness_cache();
+
+    let reports = tauri::async_runtime::spawn_blocking(move || {
+        sounds
+            .into_iter()
+            .map(|sound| {
+                let volume = sound.volume.unwrap_or(default_volume);
+                let decoded = audio::decode_audio_file(&sound.file_path);
+                let measured_lufs = decoded.as_ref().ok().and_then(|data| {
+                    super::audio::cached_loudness(&loudness_cache, &sound.file_path, data)
+                });
+                let normalization_gain = normalization_target.map_or(1.0, |target| {
+                    audio::normalization_gain(measured_lufs, target)
+                });
+                // No master volume stage yet, so it stays at unity
+                let chain = audio::simulate_chain(volume, normalization_gain, 1.0);
+
+                let (source_peak_dbfs, output_peak_dbfs, verdict, error) = match &decoded {
+                    Ok(data) => {
+                        let source = audio::to_db(audio::sample_peak(
+                            data,
+                            sound.trim_start_ms,
+                            sound.trim_end_ms,
+                        ));
+                        let output = source.zip(chain.total_gain_db).map(|(s, g)| s + g);
+                        (source, output, Some(audio::verdict(output)), None)
+                    }
+                    Err(e) => (None, None, None, Some(e.to_string())),
+                };
+
+                SoundGainReport {
+                    sound_id: sound.id,
+                    name: sound.name,
+                    chain,
+                    measured_lufs,
+                    source_peak_dbfs,
+                    output_peak_dbfs,
+                    verdict,
+                    error,
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Gain staging task failed: {}", e)))?;
+
+    let count = |v: GainVerdict| reports.iter().filter(|r| r.verdict == Some(v)).count();
+    let report = GainStagingReport {
+        clipping: count(GainVerdict::Clipping),
+        inaudible: count(GainVerdict::Inaudible),
+        sounds: reports,
+    };
+    tracing::info!(
+        sounds = report.sounds.len(),
+        clipping = report.clipping,
+        inaudible = report.inaudible,
+        "Gain staging analysis complete"
+    );
+    Ok(report)
+}
+
+/// Why a self-test stage did not pass
+enum StageError {
+    /// Precondition missing (nothing to test)
+    Skipped(&'static str),
+    Failed(String),
+}
+
+/// Time a stage and convert its result into a report entry
+fn run_stage(name: &str, stage: impl FnOnce() -> Result<(), StageError>) -> SelfTestStage {
+    let start = Instant::now();
+    let result = stage();
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    let (status, detail) = match result {
+        Ok(()) => (StageStatus::Passed, None),
+        Err(StageError::Skipped(reason)) => (StageStatus::Skipped, Some(reason.to_string())),
+        Err(StageError::Failed(error)) => {
+            tracing::warn!("Self-test stage '{}' failed: {}", name, error);
+            (StageStatus::Failed, Some(error))
+        }
+    };
+
+    SelfTestStage {
+        name: name.to_string(),
+        status,
+        detail,
+        duration_ms,
+    }
+}
+
+/// Open a short silent stream on a device and close it again
+fn test_stream(device_id: Option<&DeviceId>) -> Result<(), StageError> {
+    let device_id = device_id.ok_or(StageError::Skipped("Device not configured"))?;
+    let device =
+        audio::find_output_device(device_id).map_err(|e| StageError::Failed(e.to_string()))?;
+
+    let silence = Arc::new(AudioData {
+        samples: vec![0.0; 4800],
+        sample_rate: 48000,
+        channels: 1,
+    });
+    let (finished_tx, _finished_rx) = mpsc::channel();
+    let stream = audio::create_playback_stream(
+        &device,
+        audio::PlaybackSource::Decoded {
+            audio_data: silence,
+            converted: None,
+        },
+        Arc::new(AtomicU32::new(0.0f32.to_bits())),
+        Arc::new(AtomicU32::new(0.0f32.to_bits())),
+        audio::PlaybackCursor::new(0, finished_tx),
+        None,
+        false,
+        false,
+        false,
+    )
+    .map_err(|e| StageError::Failed(e.to_string()))?;
+    drop(stream);
+    Ok(())
+}
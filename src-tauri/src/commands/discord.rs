@@ -0,0 +1,60 @@
+//! Discord mute/deafen awareness commands
+
+use crate::discord::{self, DiscordCredentials, DiscordStatus};
+use crate::error::{AppError, ErrorCode};
+use crate::settings::AppSettings;
+use crate::AppState;
+use tauri::{Emitter, State};
+
+/// Enable/disable Discord mute awareness and update the application credentials
+///
+/// Changing the client ID drops the stored authorization, so Discord asks
+/// for approval again on the next connection.
+#[tauri::command]
+pub fn set_discord_mute_awareness(
+    enabled: bool,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    let mut settings = state.read_settings().clone();
+    let previous_client_id = settings.discord_client_id.clone();
+
+    if let Some(id) = client_id {
+        settings.discord_client_id = Some(id.trim().to_string());
+    }
+    if let Some(secret) = client_secret {
+        settings.discord_client_secret = Some(secret.trim().to_string());
+    }
+    settings.discord_mute_awareness_enabled = enabled;
+
+    let credentials = DiscordCredentials::from_settings(&settings);
+    if enabled && credentials.is_none() {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Discord client ID and secret are required",
+        ));
+    }
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    if settings.discord_client_id != previous_client_id {
+        discord::clear_authorization(&app_handle);
+    }
+
+    match credentials {
+        Some(credentials) if enabled => discord::start(app_handle.clone(), credentials),
+        _ => discord::stop(&app_handle),
+    }
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Get the Discord connection and voice state
+#[tauri::command]
+pub fn get_discord_status() -> Result<DiscordStatus, AppError> {
+    Ok(discord::status())
+}
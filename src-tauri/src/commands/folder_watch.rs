@@ -0,0 +1,85 @@
+//! Watched folder commands
+
+use std::path::Path;
+
+use tauri::{Emitter, State};
+
+use crate::error::{AppError, ErrorCode};
+use crate::folder_watch::{self, WatchedFolder};
+use crate::settings::AppSettings;
+use crate::sounds::CategoryId;
+use crate::AppState;
+
+/// Watch a folder, adding its audio files to `category_id`
+///
+/// Files already in the folder are added right away. Watching a folder again
+/// changes its category. Returns the watched folders and emits
+/// `settings-changed`.
+#[tauri::command]
+pub fn add_watched_folder(
+    path: String,
+    category_id: CategoryId,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<WatchedFolder>, AppError> {
+    if !Path::new(&path).is_dir() {
+        return Err(
+            AppError::new(ErrorCode::FileNotFound, "Folder not found").with_context("path", &path)
+        );
+    }
+    if !state
+        .read_sounds()
+        .categories
+        .iter()
+        .any(|c| c.id == category_id)
+    {
+        return Err(
+            AppError::new(ErrorCode::CategoryNotFound, "Category not found")
+                .with_context("category_id", category_id.as_str()),
+        );
+    }
+
+    let mut settings = state.read_settings().clone();
+    match settings.watched_folders.iter_mut().find(|f| f.path == path) {
+        Some(folder) => folder.category_id = category_id,
+        None => settings
+            .watched_folders
+            .push(WatchedFolder { path, category_id }),
+    }
+    apply_watched_folders(&state, &app_handle, settings)
+}
+
+/// Stop watching a folder (its sounds stay in the library)
+#[tauri::command]
+pub fn remove_watched_folder(
+    path: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<WatchedFolder>, AppError> {
+    let mut settings = state.read_settings().clone();
+    let before = settings.watched_folders.len();
+    settings.watched_folders.retain(|f| f.path != path);
+    if settings.watched_folders.len() == before {
+        return Err(
+            AppError::new(ErrorCode::InvalidRequest, "Folder is not watched")
+                .with_context("path", &path),
+        );
+    }
+    apply_watched_folders(&state, &app_handle, settings)
+}
+
+/// Save the settings, restart the watcher and notify the frontend
+fn apply_watched_folders(
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+    settings: AppSettings,
+) -> Result<Vec<WatchedFolder>, AppError> {
+    let folders = settings.watched_folders.clone();
+    state.update_and_save_settings(app_handle, settings.clone())?;
+    folder_watch::start(app_handle);
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(folders)
+}
@@ -1,13 +1,21 @@
 //! Global hotkey management commands
 
-use crate::hotkeys;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+
+use crate::bindings::{self, BindingFormat};
+use crate::error::{AppError, ErrorCode};
+use crate::hotkeys::{self, HotkeyCapture};
 use crate::sounds::SoundId;
 use crate::AppState;
-use tauri::State;
+use tauri::{Manager, State};
+
+/// How long start_hotkey_capture waits for a key press
+const HOTKEY_CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Load hotkey mappings from in-memory state
 #[tauri::command]
-pub fn load_hotkeys(state: State<'_, AppState>) -> Result<hotkeys::HotkeyMappings, String> {
+pub fn load_hotkeys(state: State<'_, AppState>) -> Result<hotkeys::HotkeyMappings, AppError> {
     let mappings = state.read_hotkeys();
     Ok(mappings.clone())
 }
@@ -15,11 +23,31 @@ pub fn load_hotkeys(state: State<'_, AppState>) -> Result<hotkeys::HotkeyMapping
 /// Save hotkey mappings to state and disk
 #[tauri::command]
 pub fn save_hotkeys(
-    mappings: hotkeys::HotkeyMappings,
+    mut mappings: hotkeys::HotkeyMappings,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    state.update_and_save_hotkeys(&app_handle, mappings)
+) -> Result<(), AppError> {
+    hotkeys::normalize_mappings(&mut mappings);
+    Ok(state.update_and_save_hotkeys(&app_handle, mappings)?)
+}
+
+/// Export all hotkey bindings as JSON or CSV
+///
+/// Writes the export to `path` if given; the content is returned either way
+/// (e.g. for copying to the clipboard).
+#[tauri::command]
+pub fn export_bindings(
+    format: BindingFormat,
+    path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let list = bindings::list_bindings(&state.read_hotkeys(), &state.read_sounds());
+    let content = bindings::render(&list, format)?;
+    if let Some(path) = path {
+        std::fs::write(&path, &content)
+            .map_err(|e| format!("Failed to write bindings to {}: {}", path, e))?;
+    }
+    Ok(content)
 }
 
 /// Register a global hotkey for a sound
@@ -29,9 +57,12 @@ pub fn register_hotkey(
     sound_id: SoundId,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
+    // Store and register the canonical form the shortcut handler matches against
+    let hotkey = hotkeys::normalize_hotkey_string(&hotkey);
+
     // Read current mappings from state
     let mut mappings = {
         let current = state.read_hotkeys();
@@ -39,19 +70,24 @@ pub fn register_hotkey(
     };
 
     // Add mapping (checks for duplicates)
-    hotkeys::add_mapping(&mut mappings, hotkey.clone(), sound_id.clone())?;
+    hotkeys::add_mapping(&mut mappings, hotkey.clone(), sound_id.clone())
+        .map_err(|e| AppError::new(ErrorCode::HotkeyConflict, e).with_context("hotkey", &hotkey))?;
 
     // Parse and register with the plugin
-    let shortcut = hotkey
-        .parse::<tauri_plugin_global_shortcut::Shortcut>()
-        .map_err(|e| format!("Failed to parse hotkey '{}': {}", hotkey, e))?;
+    let shortcut = parse_shortcut(&hotkey)?;
 
     tracing::info!("Parsed hotkey '{}' to shortcut: {:?}", hotkey, shortcut);
 
     app_handle
         .global_shortcut()
         .register(shortcut)
-        .map_err(|e| format!("Failed to register hotkey: {}", e))?;
+        .map_err(|e| {
+            AppError::new(
+                ErrorCode::HotkeyConflict,
+                format!("Failed to register hotkey: {}", e),
+            )
+            .with_context("hotkey", &hotkey)
+        })?;
 
     // Update state and persist to disk
     state.update_and_save_hotkeys(&app_handle, mappings)?;
@@ -64,15 +100,55 @@ pub fn register_hotkey(
     Ok(())
 }
 
+/// Register a global hotkey for an app action (e.g. toggle normalization)
+#[tauri::command]
+pub fn register_action_hotkey(
+    hotkey: String,
+    action: hotkeys::HotkeyAction,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let hotkey = hotkeys::normalize_hotkey_string(&hotkey);
+
+    let mut mappings = {
+        let current = state.read_hotkeys();
+        current.clone()
+    };
+
+    hotkeys::add_action_mapping(&mut mappings, hotkey.clone(), action.clone())
+        .map_err(|e| AppError::new(ErrorCode::HotkeyConflict, e).with_context("hotkey", &hotkey))?;
+
+    let shortcut = parse_shortcut(&hotkey)?;
+    app_handle
+        .global_shortcut()
+        .register(shortcut)
+        .map_err(|e| {
+            AppError::new(
+                ErrorCode::HotkeyConflict,
+                format!("Failed to register hotkey: {}", e),
+            )
+            .with_context("hotkey", &hotkey)
+        })?;
+
+    state.update_and_save_hotkeys(&app_handle, mappings)?;
+
+    tracing::info!("Registered action hotkey: {} -> {:?}", hotkey, action);
+    Ok(())
+}
+
 /// Unregister a global hotkey
 #[tauri::command]
 pub fn unregister_hotkey(
     hotkey: String,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
+    let hotkey = hotkeys::normalize_hotkey_string(&hotkey);
+
     // Read current mappings from state
     let mut mappings = {
         let current = state.read_hotkeys();
@@ -80,12 +156,11 @@ pub fn unregister_hotkey(
     };
 
     // Remove mapping
-    hotkeys::remove_mapping(&mut mappings, &hotkey)?;
+    hotkeys::remove_mapping(&mut mappings, &hotkey)
+        .map_err(|e| AppError::new(ErrorCode::HotkeyNotFound, e).with_context("hotkey", &hotkey))?;
 
     // Parse and unregister from the plugin
-    let shortcut = hotkey
-        .parse::<tauri_plugin_global_shortcut::Shortcut>()
-        .map_err(|e| format!("Failed to parse hotkey '{}': {}", hotkey, e))?;
+    let shortcut = parse_shortcut(&hotkey)?;
     app_handle
         .global_shortcut()
         .unregister(shortcut)
@@ -98,13 +173,190 @@ pub fn unregister_hotkey(
     Ok(())
 }
 
+/// Set whether a sound hotkey plays on press (`toggle`) or only while held
+#[tauri::command]
+pub fn set_hotkey_mode(
+    hotkey: String,
+    mode: hotkeys::HotkeyMode,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let mut mappings = {
+        let current = state.read_hotkeys();
+        current.clone()
+    };
+
+    hotkeys::set_mode(&mut mappings, &hotkey, mode)
+        .map_err(|e| AppError::new(ErrorCode::HotkeyNotFound, e).with_context("hotkey", &hotkey))?;
+
+    Ok(state.update_and_save_hotkeys(&app_handle, mappings)?)
+}
+
+/// Enable the hotkey bank on a modifier combination, or disable it with `None`
+///
+/// `<modifiers>+NumPad1` .. `NumPad9` then play the first nine sounds of the
+/// category selected in the dashboard.
+#[tauri::command]
+pub fn set_hotkey_bank(
+    modifiers: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<hotkeys::HotkeyBank>, AppError> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let mut mappings = {
+        let current = state.read_hotkeys();
+        current.clone()
+    };
+    let previous = mappings.bank.take();
+
+    if let Some(modifiers) = &modifiers {
+        hotkeys::set_bank(&mut mappings, modifiers)
+            .map_err(|e| AppError::new(ErrorCode::HotkeyConflict, e))?;
+    }
+    if mappings.bank == previous {
+        return Ok(previous);
+    }
+
+    let old_hotkeys = previous
+        .as_ref()
+        .map(hotkeys::bank_hotkeys)
+        .unwrap_or_default();
+    let new_hotkeys = mappings
+        .bank
+        .as_ref()
+        .map(hotkeys::bank_hotkeys)
+        .unwrap_or_default();
+    for hotkey in &old_hotkeys {
+        if let Ok(shortcut) = parse_shortcut(hotkey) {
+            let _ = app_handle.global_shortcut().unregister(shortcut);
+        }
+    }
+
+    // Register all nine slots or none of them
+    let mut registered = Vec::new();
+    for hotkey in &new_hotkeys {
+        let shortcut = parse_shortcut(hotkey)?;
+        if let Err(e) = app_handle.global_shortcut().register(shortcut) {
+            for shortcut in registered {
+                let _ = app_handle.global_shortcut().unregister(shortcut);
+            }
+            for hotkey in &old_hotkeys {
+                if let Ok(shortcut) = parse_shortcut(hotkey) {
+                    let _ = app_handle.global_shortcut().register(shortcut);
+                }
+            }
+            return Err(AppError::new(
+                ErrorCode::HotkeyConflict,
+                format!("Failed to register hotkey: {}", e),
+            )
+            .with_context("hotkey", hotkey));
+        }
+        registered.push(shortcut);
+    }
+
+    let bank = mappings.bank.clone();
+    state.update_and_save_hotkeys(&app_handle, mappings)?;
+
+    match &bank {
+        Some(bank) => tracing::info!("Hotkey bank enabled on {}", bank.modifiers),
+        None => tracing::info!("Hotkey bank disabled"),
+    }
+    Ok(bank)
+}
+
 /// Check if a hotkey is currently registered
 #[tauri::command]
-pub fn is_hotkey_registered(hotkey: String, app_handle: tauri::AppHandle) -> Result<bool, String> {
+pub fn is_hotkey_registered(
+    hotkey: String,
+    app_handle: tauri::AppHandle,
+) -> Result<bool, AppError> {
     use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
-    let shortcut = hotkey
-        .parse::<tauri_plugin_global_shortcut::Shortcut>()
-        .map_err(|e| format!("Failed to parse hotkey '{}': {}", hotkey, e))?;
+    let shortcut = parse_shortcut(&hotkey)?;
     Ok(app_handle.global_shortcut().is_registered(shortcut))
 }
+
+/// Parse a hotkey string into a plugin shortcut
+pub(super) fn parse_shortcut(
+    hotkey: &str,
+) -> Result<tauri_plugin_global_shortcut::Shortcut, AppError> {
+    hotkey
+        .parse::<tauri_plugin_global_shortcut::Shortcut>()
+        .map_err(|e| {
+            AppError::new(
+                ErrorCode::HotkeyInvalid,
+                format!("Failed to parse hotkey '{}': {}", hotkey, e),
+            )
+            .with_context("hotkey", hotkey)
+        })
+}
+
+/// Capture the next key combination pressed anywhere on the system
+///
+/// Temporarily registers a broad set of shortcuts and returns the first one
+/// pressed, normalized exactly as the global shortcut handler matches it.
+/// `cancel_hotkey_capture` cancels it (the app calls it on Escape).
+#[tauri::command]
+pub async fn start_hotkey_capture(app_handle: tauri::AppHandle) -> Result<String, AppError> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let capture = app_handle.state::<HotkeyCapture>();
+    let rx = capture
+        .begin()
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e))?;
+
+    // Register every candidate that isn't already taken. Mapped hotkeys are
+    // already registered and get routed to the capture by the handler.
+    let mut registered = Vec::new();
+    for candidate in hotkeys::capture_candidates() {
+        let Ok(shortcut) = candidate.parse::<tauri_plugin_global_shortcut::Shortcut>() else {
+            continue;
+        };
+        if app_handle.global_shortcut().is_registered(shortcut) {
+            continue;
+        }
+        if app_handle.global_shortcut().register(shortcut).is_ok() {
+            registered.push(shortcut);
+        }
+    }
+
+    tracing::debug!(
+        "Hotkey capture started ({} temporary shortcuts)",
+        registered.len()
+    );
+
+    let result =
+        tauri::async_runtime::spawn_blocking(move || rx.recv_timeout(HOTKEY_CAPTURE_TIMEOUT)).await;
+
+    // Always tear down the temporary listener, whatever the outcome
+    capture.end();
+    for shortcut in registered {
+        let _ = app_handle.global_shortcut().unregister(shortcut);
+    }
+
+    match result {
+        Ok(Ok(hotkey)) => {
+            tracing::info!("Captured hotkey: {}", hotkey);
+            Ok(hotkey)
+        }
+        Ok(Err(RecvTimeoutError::Disconnected)) => Err(AppError::new(
+            ErrorCode::Cancelled,
+            "Hotkey capture cancelled",
+        )),
+        Ok(Err(RecvTimeoutError::Timeout)) => Err(AppError::new(
+            ErrorCode::Timeout,
+            "Hotkey capture timed out",
+        )),
+        Err(e) => Err(AppError::internal(format!(
+            "Hotkey capture task failed: {}",
+            e
+        ))),
+    }
+}
+
+/// Cancel a running hotkey capture (no-op if none is active)
+#[tauri::command]
+pub fn cancel_hotkey_capture(capture: State<'_, HotkeyCapture>) {
+    capture.end();
+}
@@ -4,9 +4,11 @@
 
 use tracing::info;
 
+use crate::error::AppError;
+
 /// Get logs directory path
 #[tauri::command]
-pub fn get_logs_path() -> Result<String, String> {
+pub fn get_logs_path() -> Result<String, AppError> {
     let logs_dir = dirs::data_local_dir()
         .ok_or("Could not find app data directory")?
         .join("com.sonicdeck.app")
@@ -17,7 +19,7 @@ pub fn get_logs_path() -> Result<String, String> {
 
 /// Read the current log file
 #[tauri::command]
-pub fn read_logs() -> Result<String, String> {
+pub fn read_logs() -> Result<String, AppError> {
     let logs_dir = dirs::data_local_dir()
         .ok_or("Could not find app data directory")?
         .join("com.sonicdeck.app")
@@ -48,12 +50,12 @@ pub fn read_logs() -> Result<String, String> {
         .ok_or("Failed to find recent log file")?;
 
     std::fs::read_to_string(most_recent.path())
-        .map_err(|e| format!("Failed to read log file: {}", e))
+        .map_err(|e| AppError::internal(format!("Failed to read log file: {}", e)))
 }
 
 /// Clear all log files
 #[tauri::command]
-pub fn clear_logs() -> Result<(), String> {
+pub fn clear_logs() -> Result<(), AppError> {
     let logs_dir = dirs::data_local_dir()
         .ok_or("Could not find app data directory")?
         .join("com.sonicdeck.app")
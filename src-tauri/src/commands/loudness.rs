@@ -0,0 +1,207 @@
+//! Library loudness analysis
+//!
+//! Measures the integrated loudness of the whole library in the background
+//! and stores it on each sound (`Sound.lufs`), so loudness normalization has
+//! a value from the first trigger instead of measuring at decode time.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use tauri::{Emitter, Manager, State};
+use tracing::{error, info, warn};
+
+use crate::audio::{self, AudioManager};
+use crate::error::{AppError, ErrorCode};
+use crate::sounds::SoundId;
+use crate::AppState;
+
+/// Set while an analysis runs (one at a time)
+static ANALYSIS_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Measurements stored per library save
+const SAVE_BATCH: usize = 25;
+
+/// Clears `ANALYSIS_RUNNING` when dropped, also if the worker panics
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        ANALYSIS_RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// A finished measurement, waiting to be stored on its sound
+struct Measurement {
+    sound_id: SoundId,
+    file_path: String,
+    lufs: Option<f32>,
+}
+
+/// Payload of `loudness-analysis-progress`, emitted after each sound
+#[derive(Clone, Serialize)]
+struct LoudnessProgress {
+    sound_id: SoundId,
+    /// None if silent or not decodable
+    lufs: Option<f32>,
+    error: Option<String>,
+    done: usize,
+    total: usize,
+}
+
+/// Payload of `loudness-analysis-complete`
+#[derive(Clone, Serialize)]
+struct LoudnessComplete {
+    measured: usize,
+    failed: usize,
+}
+
+/// Measure the loudness of every sound in the background
+///
+/// Sounds that were already measured are skipped unless `remeasure` is set.
+/// Results are saved to the library in batches.
+/// Returns how many sounds will be measured; each one is reported with a
+/// `loudness-analysis-progress` event, the end with
+/// `loudness-analysis-complete`.
+#[tauri::command]
+pub fn analyze_library_loudness(
+    remeasure: Option<bool>,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, AppError> {
+    let remeasure = remeasure.unwrap_or(false);
+    let pending: Vec<(SoundId, String)> = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .filter(|s| remeasure || (s.lufs.is_none() && !s.loudness_measured))
+        .map(|s| (s.id.clone(), s.file_path.clone()))
+        .collect();
+
+    if ANALYSIS_RUNNING.swap(true, Ordering::AcqRel) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Loudness analysis is already running",
+        ));
+    }
+    let running = RunningGuard;
+
+    let total = pending.len();
+    let loudness_cache = manager.get_loudness_cache();
+    let spawned = std::thread::Builder::new()
+        .name("loudness-analysis".to_string())
+        .spawn(move || {
+            let mut failed = 0;
+            let mut unsaved = Vec::new();
+            for (done, (sound_id, file_path)) in pending.into_iter().enumerate() {
+                // Playback may have measured the file already
+                let cached = (!remeasure)
+                    .then(|| loudness_cache.lock().unwrap().get(&file_path).copied())
+                    .flatten();
+                let measured = match cached {
+                    Some(lufs) => Ok(lufs),
+                    None => audio::decode_audio_file(&file_path)
+                        .map(|data| audio::integrated_loudness(&data)),
+                };
+
+                let (lufs, error) = match measured {
+                    Ok(lufs) => {
+                        loudness_cache
+                            .lock()
+                            .unwrap()
+                            .insert(file_path.clone(), lufs);
+                        unsaved.push(Measurement {
+                            sound_id: sound_id.clone(),
+                            file_path,
+                            lufs,
+                        });
+                        (lufs, None)
+                    }
+                    Err(e) => {
+                        warn!("Loudness analysis of {} failed: {}", file_path, e);
+                        failed += 1;
+                        (None, Some(e.to_string()))
+                    }
+                };
+
+                let payload = LoudnessProgress {
+                    sound_id,
+                    lufs,
+                    error,
+                    done: done + 1,
+                    total,
+                };
+                if let Err(e) = app_handle.emit("loudness-analysis-progress", payload) {
+                    error!("Failed to emit loudness progress event: {}", e);
+                }
+
+                if unsaved.len() >= SAVE_BATCH {
+                    store_measurements(&app_handle, std::mem::take(&mut unsaved));
+                }
+            }
+            store_measurements(&app_handle, unsaved);
+
+            drop(running);
+            info!(
+                "Loudness analysis complete ({} measured, {} failed)",
+                total - failed,
+                failed
+            );
+            let payload = LoudnessComplete {
+                measured: total - failed,
+                failed,
+            };
+            if let Err(e) = app_handle.emit("loudness-analysis-complete", payload) {
+                error!("Failed to emit loudness complete event: {}", e);
+            }
+        });
+
+    // A failed spawn dropped the guard with the closure
+    if let Err(e) = spawned {
+        return Err(AppError::internal(format!(
+            "Failed to start loudness analysis: {}",
+            e
+        )));
+    }
+    info!("Analyzing loudness of {} sounds", total);
+    Ok(total)
+}
+
+/// Store measurements on their sounds with one save, skipping sounds that
+/// were deleted or pointed at another file meanwhile
+fn store_measurements(app_handle: &tauri::AppHandle, measurements: Vec<Measurement>) {
+    if measurements.is_empty() {
+        return;
+    }
+    let state = app_handle.state::<AppState>();
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+    let mut changed = false;
+    for measurement in &measurements {
+        let Some(sound) = library
+            .sounds
+            .iter_mut()
+            .find(|s| s.id == measurement.sound_id && s.file_path == measurement.file_path)
+        else {
+            continue;
+        };
+        if sound.loudness_measured && sound.lufs == measurement.lufs {
+            continue;
+        }
+        sound.lufs = measurement.lufs;
+        sound.loudness_measured = true;
+        changed = true;
+    }
+    if !changed {
+        return;
+    }
+    if let Err(e) = state.update_and_save_sounds(app_handle, library) {
+        error!(
+            "Failed to store loudness of {} sounds: {}",
+            measurements.len(),
+            e
+        );
+    }
+}
@@ -1,24 +1,66 @@
 //! Tauri command handlers organized by functionality
 //!
 //! This module contains all Tauri commands, grouped into logical submodules:
+//! - `api`: Local API server control
 //! - `audio`: Audio playback, device management, caching, waveforms
+//! - `backups`: Rolling backups of the data files and restoring them
+//! - `diagnostics`: Audio engine status, startup report and self-test
+//! - `discord`: Discord mute/deafen awareness
+//! - `folder_watch`: Folders whose audio files are added automatically
+//! - `session`: Session state restored at startup
 //! - `settings`: App settings and autostart configuration
+//! - `setup`: First-run setup wizard progress
+//! - `soundpack`: Soundpack export and import
+//! - `stats`: Play counts and recently played sounds
 //! - `hotkeys`: Global hotkey registration and management
 //! - `sounds`: Sound library and category management
 //! - `logs`: Log file access and management
+//! - `loudness`: Background loudness analysis of the library
+//! - `profiles`: Switchable boards with their own library, hotkeys and devices
+//! - `queue`: Playback queue (sounds played one after another)
+//! - `quick_search`: Quick sound search popup
+//! - `recording`: Recording clips into the library
 //! - `vbcable`: VB-Cable detection and default device management
 
+pub mod api;
 pub mod audio;
+pub mod backups;
+pub mod diagnostics;
+pub mod discord;
+pub mod folder_watch;
 pub mod hotkeys;
 pub mod logs;
+pub mod loudness;
+pub mod profiles;
+pub mod queue;
+pub mod quick_search;
+pub mod recording;
+pub mod session;
 pub mod settings;
+pub mod setup;
+pub mod soundpack;
 pub mod sounds;
+pub mod stats;
 pub mod vbcable;
 
 // Re-export all commands for easy access in lib.rs
+pub use api::*;
 pub use audio::*;
+pub use backups::*;
+pub use diagnostics::*;
+pub use discord::*;
+pub use folder_watch::*;
 pub use hotkeys::*;
 pub use logs::*;
+pub use loudness::*;
+pub use profiles::*;
+pub use queue::*;
+pub use quick_search::*;
+pub use recording::*;
+pub use session::*;
 pub use settings::*;
+pub use setup::*;
+pub use soundpack::*;
 pub use sounds::*;
+pub use stats::*;
 pub use vbcable::*;
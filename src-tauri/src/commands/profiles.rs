@@ -0,0 +1,42 @@
+//! Profile commands (switchable boards with their own library, hotkeys and
+//! devices)
+
+use crate::error::{AppError, ErrorCode};
+use crate::profiles::{self, ProfileList};
+
+/// Profiles and the active one
+#[tauri::command]
+pub fn list_profiles(app_handle: tauri::AppHandle) -> Result<ProfileList, AppError> {
+    Ok(profiles::list(&app_handle)?)
+}
+
+/// Create a profile, empty or with a copy of the active profile's library and
+/// hotkeys (`copy_current`)
+#[tauri::command]
+pub fn create_profile(
+    name: String,
+    copy_current: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<ProfileList, AppError> {
+    let profiles = profiles::create(&app_handle, &name, copy_current.unwrap_or(false))
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e).with_context("name", &name))?;
+    crate::tray::update_profiles(&app_handle);
+    Ok(profiles)
+}
+
+/// Delete a profile (not the default or the active one)
+#[tauri::command]
+pub fn delete_profile(name: String, app_handle: tauri::AppHandle) -> Result<ProfileList, AppError> {
+    let profiles = profiles::delete(&app_handle, &name)
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e).with_context("name", &name))?;
+    crate::tray::update_profiles(&app_handle);
+    Ok(profiles)
+}
+
+/// Switch to a profile, loading its library and hotkeys and using its
+/// devices; emits `profile-changed`
+#[tauri::command]
+pub fn switch_profile(name: String, app_handle: tauri::AppHandle) -> Result<ProfileList, AppError> {
+    profiles::switch(&app_handle, &name)
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e).with_context("name", &name))
+}
@@ -0,0 +1,167 @@
+//! Playback queue commands
+//!
+//! Queued sounds play one after another on the configured devices. The
+//! playback thread of a queued sound starts the next one when it ends (see
+//! `playback_finished`); every change is reported with `queue-updated`.
+
+use tauri::{Emitter, Manager, Runtime, State};
+use tracing::{debug, error, warn};
+
+use crate::audio::{AudioManager, PlaybackQueue, QueueSnapshot};
+use crate::error::{AppError, ErrorCode};
+use crate::sounds::SoundId;
+use crate::AppState;
+
+/// Add a sound to the playback queue (starts it if the queue is idle)
+#[tauri::command]
+pub fn queue_sound(sound_id: SoundId, app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    enqueue(&app_handle, &sound_id)
+}
+
+/// Stop the sound playing from the queue; the next one starts right away
+#[tauri::command]
+pub fn skip(manager: State<'_, AudioManager>) -> Result<(), AppError> {
+    let current = manager
+        .get_queue()
+        .lock()
+        .unwrap()
+        .current_playback()
+        .map(str::to_owned);
+    if let Some(playback_id) = current {
+        debug!("Skipping queued playback {}", playback_id);
+        manager.signal_stop(&playback_id);
+    }
+    Ok(())
+}
+
+/// Remove the waiting sounds (the one playing finishes)
+#[tauri::command]
+pub fn clear_queue(
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let queue = manager.get_queue();
+    let mut queue = queue.lock().unwrap();
+    queue.clear_pending();
+    emit_queue_updated(&app_handle, queue.snapshot());
+    Ok(())
+}
+
+/// Current queue contents
+#[tauri::command]
+pub fn get_queue(manager: State<'_, AudioManager>) -> Result<QueueSnapshot, AppError> {
+    Ok(manager.get_queue().lock().unwrap().snapshot())
+}
+
+/// Queue a sound (also used for hotkeys in queue mode)
+pub(crate) fn enqueue(app_handle: &tauri::AppHandle, sound_id: &SoundId) -> Result<(), AppError> {
+    let exists = app_handle
+        .state::<AppState>()
+        .read_sounds()
+        .sounds
+        .iter()
+        .any(|s| &s.id == sound_id);
+    if !exists {
+        return Err(AppError::new(
+            ErrorCode::SoundNotFound,
+            format!("Sound not found: {}", sound_id.as_str()),
+        )
+        .with_context("sound_id", sound_id.as_str()));
+    }
+
+    let queue = app_handle.state::<AudioManager>().get_queue();
+    let mut queue = queue.lock().unwrap();
+    if queue.push(sound_id.as_str().to_owned()) {
+        play_next(app_handle, &mut queue);
+    }
+    emit_queue_updated(app_handle, queue.snapshot());
+    Ok(())
+}
+
+/// Start the next queued sound if `playback_id` was the queue's current one
+///
+/// Called by every playback thread when it ends.
+pub(crate) fn playback_finished(app_handle: &tauri::AppHandle, playback_id: &str) {
+    let queue = app_handle.state::<AudioManager>().get_queue();
+    let mut queue = queue.lock().unwrap();
+    if !queue.finish(playback_id) {
+        return;
+    }
+    play_next(app_handle, &mut queue);
+    emit_queue_updated(app_handle, queue.snapshot());
+}
+
+/// Start queued sounds until one plays (skipping ones that can't)
+///
+/// Runs with the queue locked, so a fast-ending playback can't report its
+/// end before it is recorded as the current one.
+fn play_next(app_handle: &tauri::AppHandle, queue: &mut PlaybackQueue) {
+    while let Some(sound_id) = queue.start_next() {
+        match play_sound(app_handle, &sound_id) {
+            Ok(Some(playback_id)) => {
+                debug!("Queue playing {} ({})", sound_id, playback_id);
+                queue.set_playback(playback_id);
+                return;
+            }
+            Ok(None) => debug!("Queued sound {} was ignored", sound_id),
+            Err(e) => warn!("Failed to play queued sound {}: {}", sound_id, e),
+        }
+        queue.abandon_current();
+    }
+}
+
+/// Play a library sound on the configured devices
+pub(crate) fn play_sound(
+    app_handle: &tauri::AppHandle,
+    sound_id: &str,
+) -> Result<Option<String>, AppError> {
+    let state = app_handle.state::<AppState>();
+    let sound = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .find(|s| s.id.as_str() == sound_id)
+        .cloned()
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::SoundNotFound,
+                format!("Sound not found: {}", sound_id),
+            )
+            .with_context("sound_id", sound_id)
+        })?;
+
+    let settings = state.read_settings().clone();
+    let (Some(device1), Some(device2)) = (settings.monitor_device_id, settings.broadcast_device_id)
+    else {
+        return Err(AppError::new(
+            ErrorCode::DeviceMissing,
+            "Please configure audio devices in Settings first",
+        ));
+    };
+
+    let result = super::play_dual_output(
+        sound.file_path,
+        device1,
+        device2,
+        sound.volume.unwrap_or(settings.default_volume),
+        sound.trim_start_ms,
+        sound.trim_end_ms,
+        Some(sound.id.as_str().to_owned()),
+        Some(sound.play_policy),
+        None,
+        None,
+        app_handle.state::<AudioManager>(),
+        app_handle.clone(),
+    )?;
+    Ok(result.playback_id)
+}
+
+/// Emit `queue-updated` with the queue contents
+pub(crate) fn emit_queue_updated<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    snapshot: QueueSnapshot,
+) {
+    if let Err(e) = app_handle.emit("queue-updated", snapshot) {
+        error!("Failed to emit queue updated event: {}", e);
+    }
+}
@@ -0,0 +1,157 @@
+//! Quick sound search popup commands
+//!
+//! The popup is a second, borderless window that opens near the tray so any
+//! sound can be played by typing its name, without bringing up the main UI.
+
+use tauri::{Manager, Runtime, State};
+
+use crate::audio::AudioManager;
+use crate::error::{AppError, ErrorCode};
+use crate::search::{self, SearchResult, DEFAULT_SEARCH_LIMIT};
+use crate::sounds::SoundId;
+use crate::AppState;
+
+use super::PlaybackResult;
+
+/// Window label of the quick search popup
+pub const QUICK_SEARCH_LABEL: &str = "quick-search";
+
+const POPUP_WIDTH: f64 = 480.0;
+const POPUP_HEIGHT: f64 = 360.0;
+/// Distance from the screen edge (logical pixels)
+const POPUP_MARGIN: f64 = 16.0;
+
+/// Fuzzy search the sound library by name, tags and file name, best matches
+/// first
+///
+/// With `tags`, only sounds carrying all of them are searched.
+#[tauri::command]
+pub fn search_sounds(
+    query: String,
+    tags: Option<Vec<String>>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchResult>, AppError> {
+    let library = state.read_sounds();
+    Ok(search::search(
+        &library,
+        &query,
+        &tags.unwrap_or_default(),
+        limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+    ))
+}
+
+/// Show the quick search popup
+#[tauri::command]
+pub fn open_quick_search(app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    Ok(show_quick_search(&app_handle)?)
+}
+
+/// Hide the quick search popup
+#[tauri::command]
+pub fn hide_quick_search(app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    if let Some(window) = app_handle.get_webview_window(QUICK_SEARCH_LABEL) {
+        window
+            .hide()
+            .map_err(|e| format!("Failed to hide quick search: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Play a sound picked in the quick search popup on the configured devices
+#[tauri::command]
+pub fn play_quick_search_result(
+    sound_id: SoundId,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<PlaybackResult, AppError> {
+    let sound = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .find(|s| s.id == sound_id)
+        .cloned()
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::SoundNotFound,
+                format!("Sound not found: {}", sound_id.as_str()),
+            )
+            .with_context("sound_id", sound_id.as_str())
+        })?;
+
+    let settings = state.read_settings().clone();
+    let (Some(device1), Some(device2)) = (settings.monitor_device_id, settings.broadcast_device_id)
+    else {
+        return Err(AppError::new(
+            ErrorCode::DeviceMissing,
+            "Please configure audio devices in Settings first",
+        ));
+    };
+
+    super::play_dual_output(
+        sound.file_path,
+        device1,
+        device2,
+        sound.volume.unwrap_or(settings.default_volume),
+        sound.trim_start_ms,
+        sound.trim_end_ms,
+        Some(sound.id.as_str().to_owned()),
+        Some(sound.play_policy),
+        None,
+        None,
+        manager,
+        app_handle,
+    )
+}
+
+/// Show and focus the quick search popup, creating it on first use
+///
+/// Also used by the `OpenQuickSearch` hotkey action.
+pub fn show_quick_search<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+    let window = match app.get_webview_window(QUICK_SEARCH_LABEL) {
+        Some(window) => window,
+        None => tauri::WebviewWindowBuilder::new(
+            app,
+            QUICK_SEARCH_LABEL,
+            tauri::WebviewUrl::App("index.html".into()),
+        )
+        .title("SonicDeck Quick Search")
+        .inner_size(POPUP_WIDTH, POPUP_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to create quick search window: {}", e))?,
+    };
+
+    position_near_tray(&window);
+    window
+        .show()
+        .map_err(|e| format!("Failed to show quick search: {}", e))?;
+    let _ = window.set_focus();
+
+    tracing::debug!("Quick search opened");
+    Ok(())
+}
+
+/// Move the popup to the bottom-right corner of the primary monitor's
+/// work area, where the tray lives on Windows
+fn position_near_tray<R: Runtime>(window: &tauri::WebviewWindow<R>) {
+    let Ok(Some(monitor)) = window.primary_monitor() else {
+        return;
+    };
+
+    let scale = monitor.scale_factor();
+    let area = monitor.work_area();
+    let width = ((POPUP_WIDTH + POPUP_MARGIN) * scale) as i32;
+    let height = ((POPUP_HEIGHT + POPUP_MARGIN) * scale) as i32;
+
+    let x = area.position.x + area.size.width as i32 - width;
+    let y = area.position.y + area.size.height as i32 - height;
+    if let Err(e) = window.set_position(tauri::PhysicalPosition::new(x, y)) {
+        tracing::warn!("Failed to position quick search: {}", e);
+    }
+}
@@ -0,0 +1,81 @@
+//! Recording commands (clips captured in-app and added to the library)
+
+use tauri::State;
+use tracing::info;
+
+use crate::audio;
+use crate::error::{AppError, ErrorCode};
+use crate::recording::{self, RecordingFormat, RecordingSource, RecordingStatus};
+use crate::sounds::{self, CategoryId, Sound};
+use crate::AppState;
+
+/// Start recording a clip from a capture device or the VB-Cable mix
+///
+/// The clip is written to the app's `sounds` directory, as WAV unless
+/// `format` says otherwise.
+#[tauri::command]
+pub fn start_recording(
+    source: RecordingSource,
+    format: Option<RecordingFormat>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let dir = sounds::get_managed_sounds_dir(&app_handle)?;
+    recording::start(source, format.unwrap_or_default(), &dir)?;
+    Ok(())
+}
+
+/// Stop the recording and add the clip to `category_id`
+///
+/// The sound is named `name`, or after its file ("Recording (2)").
+#[tauri::command]
+pub async fn stop_recording(
+    category_id: CategoryId,
+    name: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    // Checked first, so a wrong category leaves the recording running
+    if !state
+        .read_sounds()
+        .categories
+        .iter()
+        .any(|c| c.id == category_id)
+    {
+        return Err(
+            AppError::new(ErrorCode::CategoryNotFound, "Category not found")
+                .with_context("category_id", category_id.as_str()),
+        );
+    }
+
+    let path = tauri::async_runtime::spawn_blocking(recording::stop)
+        .await
+        .map_err(|e| AppError::internal(format!("Recording task failed: {}", e)))??;
+    let file_path = path.to_string_lossy().to_string();
+    let duration_ms = audio::read_metadata(&file_path)
+        .ok()
+        .and_then(|metadata| metadata.duration_ms);
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| sounds::name_for_file(&path, None));
+
+    let mut library = state.read_sounds().clone();
+    let sound = sounds::add_sound(&mut library, name, file_path, category_id, None, None);
+    // add_sound appends
+    if let Some(added) = library.sounds.last_mut() {
+        added.duration_ms = duration_ms;
+    }
+    let sound = Sound {
+        duration_ms,
+        ..sound
+    };
+    state.update_and_save_sounds(&app_handle, library)?;
+    info!("Added recording {:?} as '{}'", path, sound.name);
+    Ok(sound)
+}
+
+/// The running recording, `None` if nothing is being recorded
+#[tauri::command]
+pub fn get_recording_status() -> Result<Option<RecordingStatus>, AppError> {
+    Ok(recording::status())
+}
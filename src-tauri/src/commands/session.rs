@@ -0,0 +1,46 @@
+//! Session state commands (last category, devices per profile, window
+//! geometry, last session's recap)
+
+use crate::error::AppError;
+use crate::recap::SessionRecap;
+use crate::session::SessionState;
+use crate::sounds::CategoryId;
+use crate::AppState;
+use tauri::State;
+
+/// Load session state restored at startup
+#[tauri::command]
+pub fn load_session(state: State<'_, AppState>) -> Result<SessionState, AppError> {
+    let session = state.read_session();
+    Ok(session.clone())
+}
+
+/// Remember the category selected in the dashboard
+#[tauri::command]
+pub fn set_last_category(
+    category_id: Option<CategoryId>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let mut session = state.read_session().clone();
+    if session.last_category_id == category_id {
+        return Ok(());
+    }
+    session.last_category_id = category_id;
+    Ok(state.update_and_save_session(&app_handle, session)?)
+}
+
+/// Recap of the last session, once: it's cleared so the next launch only
+/// shows a newer one
+#[tauri::command]
+pub fn take_session_recap(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<SessionRecap>, AppError> {
+    let mut session = state.read_session().clone();
+    let Some(recap) = session.last_recap.take() else {
+        return Ok(None);
+    };
+    state.update_and_save_session(&app_handle, session)?;
+    Ok(Some(recap))
+}
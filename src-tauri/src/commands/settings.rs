@@ -1,36 +1,181 @@
 //! Application settings and autostart management commands
 
-use crate::settings::{self, AppSettings};
+use crate::api;
+use crate::audio;
+use crate::error::{AppError, ErrorCode};
+use crate::profiles;
+use crate::session::ProfileDevices;
+use crate::settings::{
+    self, AppSettings, SettingsIssue, MAX_NORMALIZATION_TARGET_LUFS, MIN_NORMALIZATION_TARGET_LUFS,
+};
+use crate::startup::StartupAction;
 use crate::AppState;
-use tauri::State;
+use tauri::{Emitter, Manager, State};
 
 /// Load application settings from in-memory state
 #[tauri::command]
-pub fn load_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
+pub fn load_settings(state: State<'_, AppState>) -> Result<AppSettings, AppError> {
     let settings = state.read_settings();
     Ok(settings.clone())
 }
 
+/// Check the current settings for combinations that likely aren't intended
+/// (e.g. monitor and broadcast on the same device)
+#[tauri::command]
+pub fn validate_settings(state: State<'_, AppState>) -> Result<Vec<SettingsIssue>, AppError> {
+    Ok(state.read_settings().validate())
+}
+
 /// Save application settings to state and disk
 #[tauri::command]
 pub fn save_settings(
-    settings: AppSettings,
+    mut settings: AppSettings,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), AppError> {
+    // Newly selected devices were picked from the current device list
+    let known_devices = audio::known_devices();
+    let folders_changed;
+    {
+        let current = state.read_settings();
+        folders_changed = current.watched_folders != settings.watched_folders;
+        for (selected, previous) in [
+            (&settings.monitor_device_id, &current.monitor_device_id),
+            (&settings.broadcast_device_id, &current.broadcast_device_id),
+        ] {
+            if let Some(device_id) = selected.as_ref().filter(|_| selected != previous) {
+                audio::pin_device_name(&mut settings.device_names, device_id, &known_devices);
+            }
+        }
+    }
+
+    let devices = ProfileDevices {
+        monitor_device_id: settings.monitor_device_id.clone(),
+        broadcast_device_id: settings.broadcast_device_id.clone(),
+    };
+    app_handle
+        .state::<audio::AudioManager>()
+        .set_master_volume(settings.master_volume);
+    state.update_and_save_settings(&app_handle, settings)?;
+    if folders_changed {
+        crate::folder_watch::start(&app_handle);
+    }
+
+    // Remember the devices for this profile; a failed session write isn't fatal
+    let mut session = state.read_session().clone();
+    if session.record_devices(&profiles::active(), devices) {
+        if let Err(e) = state.update_and_save_session(&app_handle, session) {
+            tracing::warn!("Failed to save session: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Enable/disable loudness normalization and optionally change its target
+///
+/// Applies to playbacks started afterwards and emits `settings-changed`.
+#[tauri::command]
+pub fn set_normalization(
+    enabled: bool,
+    target_lufs: Option<f32>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    if let Some(target) = target_lufs {
+        if !(MIN_NORMALIZATION_TARGET_LUFS..=MAX_NORMALIZATION_TARGET_LUFS).contains(&target) {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "Normalization target must be between {} and {} LUFS",
+                    MIN_NORMALIZATION_TARGET_LUFS, MAX_NORMALIZATION_TARGET_LUFS
+                ),
+            )
+            .with_context("target_lufs", target));
+        }
+    }
+    Ok(apply_normalization(&app_handle, enabled, target_lufs)?)
+}
+
+/// Flip loudness normalization on/off (hotkey action)
+pub fn toggle_normalization(app_handle: &tauri::AppHandle) -> Result<AppSettings, String> {
+    let enabled = app_handle
+        .state::<AppState>()
+        .read_settings()
+        .normalization_enabled;
+    apply_normalization(app_handle, !enabled, None)
+}
+
+/// Update normalization settings, persist them and notify the frontend
+fn apply_normalization(
+    app_handle: &tauri::AppHandle,
+    enabled: bool,
+    target_lufs: Option<f32>,
+) -> Result<AppSettings, String> {
+    let state = app_handle.state::<AppState>();
+    let mut settings = state.read_settings().clone();
+    settings.normalization_enabled = enabled;
+    if let Some(target) = target_lufs {
+        settings.normalization_target_lufs = target;
+    }
+    state.update_and_save_settings(app_handle, settings.clone())?;
+
+    tracing::info!(
+        "Loudness normalization {} (target {} LUFS)",
+        if enabled { "enabled" } else { "disabled" },
+        settings.normalization_target_lufs
+    );
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Enable/disable the spectrum analyzer on the monitor output
+///
+/// While enabled, `spectrum-update` events carry band magnitudes at ~20 Hz.
+#[tauri::command]
+pub fn set_spectrum_analyzer(
+    enabled: bool,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    state.update_and_save_settings(&app_handle, settings)
+) -> Result<AppSettings, AppError> {
+    let mut settings = state.read_settings().clone();
+    settings.spectrum_analyzer_enabled = enabled;
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    if enabled {
+        start_spectrum_analyzer(app_handle.clone());
+    } else {
+        audio::stop_analyzer();
+    }
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Start the spectrum analyzer, emitting its frames as `spectrum-update`
+/// (and as levels to API spectators)
+pub fn start_spectrum_analyzer(app_handle: tauri::AppHandle) {
+    audio::start_analyzer(move |frame| {
+        api::publish_levels(&frame.bands);
+        if let Err(e) = app_handle.emit("spectrum-update", frame) {
+            tracing::error!("Failed to emit spectrum update: {}", e);
+        }
+    });
 }
 
 /// Get the settings file path (for debugging/info)
 #[tauri::command]
-pub fn get_settings_file_path(app_handle: tauri::AppHandle) -> Result<String, String> {
+pub fn get_settings_file_path(app_handle: tauri::AppHandle) -> Result<String, AppError> {
     let path = settings::get_settings_path(&app_handle)?;
     Ok(path.to_string_lossy().to_string())
 }
 
 /// Enable autostart on system boot
 #[tauri::command]
-pub fn enable_autostart(app_handle: tauri::AppHandle) -> Result<(), String> {
+pub fn enable_autostart(app_handle: tauri::AppHandle) -> Result<(), AppError> {
     #[cfg(desktop)]
     {
         use tauri_plugin_autostart::ManagerExt;
@@ -44,7 +189,7 @@ pub fn enable_autostart(app_handle: tauri::AppHandle) -> Result<(), String> {
 
 /// Disable autostart on system boot
 #[tauri::command]
-pub fn disable_autostart(app_handle: tauri::AppHandle) -> Result<(), String> {
+pub fn disable_autostart(app_handle: tauri::AppHandle) -> Result<(), AppError> {
     #[cfg(desktop)]
     {
         use tauri_plugin_autostart::ManagerExt;
@@ -56,16 +201,44 @@ pub fn disable_autostart(app_handle: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Set the autostart flags and the actions executed at startup
+///
+/// The autostart entry is rewritten with the new flags at the next start.
+/// Emits `settings-changed`.
+#[tauri::command]
+pub fn set_startup_options(
+    autostart_profile: Option<String>,
+    autostart_minimized: bool,
+    startup_actions: Vec<StartupAction>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    let autostart_profile = autostart_profile
+        .map(|profile| profile.trim().to_string())
+        .filter(|profile| !profile.is_empty());
+
+    let mut settings = state.read_settings().clone();
+    settings.autostart_profile = autostart_profile;
+    settings.autostart_minimized = autostart_minimized;
+    settings.startup_actions = startup_actions;
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
 /// Check if autostart is enabled
 #[tauri::command]
-pub fn is_autostart_enabled(app_handle: tauri::AppHandle) -> Result<bool, String> {
+pub fn is_autostart_enabled(app_handle: tauri::AppHandle) -> Result<bool, AppError> {
     #[cfg(desktop)]
     {
         use tauri_plugin_autostart::ManagerExt;
         app_handle
             .autolaunch()
             .is_enabled()
-            .map_err(|e| format!("Failed to check autostart status: {}", e))
+            .map_err(|e| AppError::internal(format!("Failed to check autostart status: {}", e)))
     }
     #[cfg(not(desktop))]
     Ok(false)
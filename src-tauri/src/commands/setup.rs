@@ -0,0 +1,113 @@
+//! First-run setup wizard commands
+
+use tauri::{Emitter, State};
+
+use crate::error::{AppError, ErrorCode};
+use crate::settings::AppSettings;
+use crate::setup::{SetupFacts, SetupProgress, SetupState, SetupStep};
+use crate::vbcable::detect_vb_cable;
+use crate::AppState;
+
+/// Get the wizard state, completing steps that are already done
+///
+/// Called when the wizard opens, so it resumes at the right step after a
+/// restart (e.g. once the VB-Cable installer finished).
+#[tauri::command]
+pub fn get_setup_state(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SetupState, AppError> {
+    let mut settings = state.read_settings().clone();
+    let facts = SetupFacts {
+        devices_chosen: devices_chosen(&settings),
+        vb_cable_installed: detect_vb_cable().is_some(),
+        mic_routed: settings.microphone_routing_enabled,
+    };
+
+    if settings.setup_progress.sync(facts) {
+        save_progress(&state, &app_handle, &settings)?;
+    }
+    Ok(settings.setup_progress.state())
+}
+
+/// Mark a wizard step as done
+///
+/// Steps with an observable outcome are checked first: devices must be
+/// chosen, VB-Cable installed and the microphone routed.
+#[tauri::command]
+pub fn complete_setup_step(
+    step: SetupStep,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SetupState, AppError> {
+    let mut settings = state.read_settings().clone();
+
+    match step {
+        SetupStep::Devices if !devices_chosen(&settings) => {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                "Choose a monitor and a broadcast device first",
+            ));
+        }
+        SetupStep::VbCable if detect_vb_cable().is_none() => {
+            return Err(AppError::new(
+                ErrorCode::VbCable,
+                "VB-Cable is not installed yet",
+            ));
+        }
+        SetupStep::MicRouting if !settings.microphone_routing_enabled => {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                "Enable microphone routing first",
+            ));
+        }
+        _ => {}
+    }
+
+    settings.setup_progress.complete(step);
+    save_progress(&state, &app_handle, &settings)?;
+    Ok(settings.setup_progress.state())
+}
+
+/// Skip a wizard step
+#[tauri::command]
+pub fn skip_setup_step(
+    step: SetupStep,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SetupState, AppError> {
+    let mut settings = state.read_settings().clone();
+    settings.setup_progress.skip(step);
+    save_progress(&state, &app_handle, &settings)?;
+    Ok(settings.setup_progress.state())
+}
+
+/// Start the wizard over
+#[tauri::command]
+pub fn reset_setup(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SetupState, AppError> {
+    let mut settings = state.read_settings().clone();
+    settings.setup_progress = SetupProgress::default();
+    save_progress(&state, &app_handle, &settings)?;
+    Ok(settings.setup_progress.state())
+}
+
+fn devices_chosen(settings: &AppSettings) -> bool {
+    settings.monitor_device_id.is_some() && settings.broadcast_device_id.is_some()
+}
+
+/// Persist settings with updated progress and notify the frontend
+fn save_progress(
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+) -> Result<(), AppError> {
+    state.update_and_save_settings(app_handle, settings.clone())?;
+
+    if let Err(e) = app_handle.emit("settings-changed", settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Soundpack export/import commands
+
+use std::path::Path;
+
+use crate::error::{AppError, ErrorCode};
+use crate::soundpack::{self, ConflictResolution, ExportSummary, ImportSummary};
+use crate::sounds::CategoryId;
+use crate::AppState;
+use tauri::State;
+
+/// Export a category (sounds, icons and hotkeys) as a soundpack zip
+#[tauri::command]
+pub fn export_category(
+    category_id: CategoryId,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<ExportSummary, AppError> {
+    let library = state.read_sounds().clone();
+    let mappings = state.read_hotkeys().clone();
+
+    let category = library
+        .categories
+        .iter()
+        .find(|c| c.id == category_id)
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::CategoryNotFound,
+                format!("Category not found: {}", category_id.as_str()),
+            )
+            .with_context("category_id", category_id.as_str())
+        })?;
+
+    let summary = soundpack::export(
+        &library,
+        &mappings,
+        std::slice::from_ref(&category_id),
+        &category.name,
+        Path::new(&path),
+    )?;
+    Ok(summary)
+}
+
+/// Export the whole library, or the given categories, as a soundpack zip
+///
+/// The pack is named after the file unless `name` is given.
+#[tauri::command]
+pub fn export_soundpack(
+    path: String,
+    category_ids: Option<Vec<CategoryId>>,
+    name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ExportSummary, AppError> {
+    let library = state.read_sounds().clone();
+    let mappings = state.read_hotkeys().clone();
+
+    let category_ids = match category_ids {
+        Some(category_ids) => {
+            if let Some(unknown) = category_ids
+                .iter()
+                .find(|id| !library.categories.iter().any(|c| &c.id == *id))
+            {
+                return Err(AppError::new(
+                    ErrorCode::CategoryNotFound,
+                    format!("Category not found: {}", unknown.as_str()),
+                )
+                .with_context("category_id", unknown.as_str()));
+            }
+            category_ids
+        }
+        None => library.categories.iter().map(|c| c.id.clone()).collect(),
+    };
+
+    let dest = Path::new(&path);
+    let name = name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| soundpack::name_from_path(dest));
+    let summary = soundpack::export(&library, &mappings, &category_ids, &name, dest)?;
+    Ok(summary)
+}
+
+/// Import a soundpack zip into the library
+///
+/// Name conflicts are resolved with `on_conflict` (default: rename).
+/// Suggested hotkeys that are free get assigned and registered.
+#[tauri::command]
+pub fn import_soundpack(
+    path: String,
+    on_conflict: Option<ConflictResolution>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<ImportSummary, AppError> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let pack_path = Path::new(&path);
+    if !pack_path.is_file() {
+        return Err(AppError::new(
+            ErrorCode::FileNotFound,
+            format!("Soundpack not found: {}", path),
+        ));
+    }
+
+    let mut library = state.read_sounds().clone();
+    let mut mappings = state.read_hotkeys().clone();
+    let audio_root = soundpack::get_soundpacks_dir(&app_handle)?;
+
+    let summary = soundpack::import(
+        &mut library,
+        &mut mappings,
+        pack_path,
+        &audio_root,
+        on_conflict.unwrap_or_default(),
+    )?;
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    state.update_and_save_hotkeys(&app_handle, mappings)?;
+
+    for hotkey in &summary.hotkeys_assigned {
+        let registered = super::hotkeys::parse_shortcut(hotkey)
+            .ok()
+            .and_then(|shortcut| app_handle.global_shortcut().register(shortcut).ok());
+        if registered.is_none() {
+            tracing::warn!("Failed to register imported hotkey: {}", hotkey);
+        }
+    }
+
+    Ok(summary)
+}
@@ -1,29 +1,52 @@
 //! Sound library and category management commands
 
+use crate::audio::{self, AudioError};
+use crate::error::{AppError, ErrorCode};
 use crate::hotkeys;
-use crate::sounds::{self, Category, CategoryId, Sound, SoundId, SoundLibrary};
-use crate::AppState;
+use crate::soundpack;
+use crate::sounds::{
+    self, Category, CategoryId, ImportWarning, PlayPolicy, PreviewRegion, Sound, SoundId,
+    SoundLibrary, TagCount, MAX_DEVICE_TRIM_DB, MIN_DEVICE_TRIM_DB,
+};
+use crate::{AppState, DeviceId};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tauri::State;
 use tracing::{info, warn};
 
 /// Load the sound library from in-memory state
 #[tauri::command]
-pub fn load_sounds(state: State<'_, AppState>) -> Result<SoundLibrary, String> {
+pub fn load_sounds(state: State<'_, AppState>) -> Result<SoundLibrary, AppError> {
     let library = state.read_sounds();
     Ok(library.clone())
 }
 
 /// Add a new sound to the library
+///
+/// With `copy_to_library` (default: the `copy_sounds_to_library` setting)
+/// the file is copied into the app's `sounds` directory first, so the sound
+/// keeps working when the original is moved or deleted.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn add_sound(
     name: String,
     file_path: String,
     category_id: CategoryId,
     icon: Option<String>,
     volume: Option<f32>,
+    copy_to_library: Option<bool>,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<Sound, String> {
+) -> Result<Sound, AppError> {
+    let copy = copy_to_library.unwrap_or_else(|| state.read_settings().copy_sounds_to_library);
+    let file_path = if copy {
+        let managed_dir = sounds::get_managed_sounds_dir(&app_handle)?;
+        copy_into_library(Path::new(&file_path), &managed_dir)?
+    } else {
+        file_path
+    };
+
     let mut library = {
         let current = state.read_sounds();
         current.clone()
@@ -34,6 +57,224 @@ pub fn add_sound(
     Ok(sound)
 }
 
+/// Outcome of one file passed to `import_files`
+#[derive(Debug, Clone, Serialize)]
+pub struct FileImportResult {
+    pub path: String,
+    /// The added sound, None if the file was skipped
+    pub sound: Option<Sound>,
+    pub error: Option<AppError>,
+}
+
+/// Add several files at once (drag and drop of many files)
+///
+/// Each file's headers are read for its length and title tag, which names
+/// the sound (otherwise the file name does). Files that aren't playable are
+/// skipped with an error in their result; all others are added with one
+/// library save. Results are in the order of `paths`.
+#[tauri::command]
+pub async fn import_files(
+    paths: Vec<String>,
+    category_id: CategoryId,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<FileImportResult>, AppError> {
+    if !state
+        .read_sounds()
+        .categories
+        .iter()
+        .any(|c| c.id == category_id)
+    {
+        return Err(
+            AppError::new(ErrorCode::CategoryNotFound, "Category not found")
+                .with_context("category_id", category_id.as_str()),
+        );
+    }
+    let managed_dir = if state.read_settings().copy_sounds_to_library {
+        Some(sounds::get_managed_sounds_dir(&app_handle)?)
+    } else {
+        None
+    };
+
+    let probed = tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .map(|path| {
+                let imported = audio::read_metadata(&path)
+                    .map_err(AppError::from)
+                    .and_then(|metadata| {
+                        let file_path = match &managed_dir {
+                            Some(dir) => copy_into_library(Path::new(&path), dir)?,
+                            None => path.clone(),
+                        };
+                        Ok((file_path, metadata))
+                    });
+                (path, imported)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Import task failed: {}", e)))?;
+
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+    let mut added = 0;
+    let results: Vec<FileImportResult> = probed
+        .into_iter()
+        .map(|(path, imported)| match imported {
+            Ok((file_path, metadata)) => {
+                let name = sounds::name_for_file(Path::new(&path), metadata.title.as_deref());
+                sounds::add_sound(
+                    &mut library,
+                    name,
+                    file_path,
+                    category_id.clone(),
+                    None,
+                    None,
+                );
+                // add_sound appends
+                if let Some(sound) = library.sounds.last_mut() {
+                    sound.duration_ms = metadata.duration_ms;
+                }
+                added += 1;
+                FileImportResult {
+                    path,
+                    sound: library.sounds.last().cloned(),
+                    error: None,
+                }
+            }
+            Err(error) => {
+                warn!("Skipping {} on import: {}", path, error);
+                FileImportResult {
+                    path,
+                    sound: None,
+                    error: Some(error),
+                }
+            }
+        })
+        .collect();
+
+    if added > 0 {
+        state.update_and_save_sounds(&app_handle, library)?;
+    }
+    info!("Imported {} of {} files", added, results.len());
+    Ok(results)
+}
+
+/// Copy a file into the managed sounds directory, returning the new path
+///
+/// Files already in the directory are left where they are.
+fn copy_into_library(source: &Path, managed_dir: &Path) -> Result<String, AppError> {
+    if source.starts_with(managed_dir) {
+        return Ok(source.to_string_lossy().to_string());
+    }
+    let Some(file_name) = source.file_name() else {
+        return Err(AppError::new(ErrorCode::InvalidRequest, "Not a file path")
+            .with_context("path", source.to_string_lossy()));
+    };
+    if !source.is_file() {
+        return Err(
+            AppError::new(ErrorCode::FileNotFound, "Sound file not found")
+                .with_context("path", source.to_string_lossy()),
+        );
+    }
+
+    let dest = soundpack::unique_path(managed_dir, &file_name.to_string_lossy());
+    std::fs::copy(source, &dest).map_err(|e| {
+        AppError::internal(format!("Failed to copy sound into library: {}", e))
+            .with_context("path", source.to_string_lossy())
+    })?;
+    info!("Copied {:?} into library as {:?}", source, dest);
+    Ok(dest.to_string_lossy().to_string())
+}
+
+/// Result of `migrate_sounds_to_library`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LibraryMigration {
+    /// Sounds now pointing at a copy in the library
+    pub copied: usize,
+    /// Sounds whose file already was in the app data directory
+    pub already_managed: usize,
+    /// Sounds whose file doesn't exist (left unchanged)
+    pub missing: Vec<SoundId>,
+    /// Sounds whose file couldn't be copied (left unchanged)
+    pub failed: Vec<SoundId>,
+}
+
+/// Copy the files of all existing sounds into the managed sounds directory
+///
+/// Sounds referencing the same file share one copy. The original files are
+/// left in place.
+#[tauri::command]
+pub async fn migrate_sounds_to_library(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<LibraryMigration, AppError> {
+    let managed_dir = sounds::get_managed_sounds_dir(&app_handle)?;
+    let data_dir = managed_dir
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| managed_dir.clone());
+    let pending: Vec<(SoundId, String)> = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .map(|s| (s.id.clone(), s.file_path.clone()))
+        .collect();
+
+    let (mut migration, copies) = tauri::async_runtime::spawn_blocking(move || {
+        let mut migration = LibraryMigration::default();
+        let mut copies: HashMap<String, String> = HashMap::new();
+        for (sound_id, file_path) in &pending {
+            let source = Path::new(file_path);
+            if source.starts_with(&data_dir) {
+                migration.already_managed += 1;
+            } else if !source.is_file() {
+                migration.missing.push(sound_id.clone());
+            } else if !copies.contains_key(file_path) {
+                match copy_into_library(source, &managed_dir) {
+                    Ok(dest) => {
+                        copies.insert(file_path.clone(), dest);
+                    }
+                    Err(e) => {
+                        warn!("Failed to copy {} into library: {}", file_path, e);
+                        migration.failed.push(sound_id.clone());
+                    }
+                }
+            }
+        }
+        (migration, copies)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Library migration task failed: {}", e)))?;
+
+    // Sounds edited meanwhile keep their new file
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+    for sound in &mut library.sounds {
+        if let Some(dest) = copies.get(&sound.file_path) {
+            sound.file_path = dest.clone();
+            migration.copied += 1;
+        }
+    }
+    if migration.copied > 0 {
+        state.update_and_save_sounds(&app_handle, library)?;
+    }
+
+    info!(
+        "Library migration: {} copied, {} already managed, {} missing, {} failed",
+        migration.copied,
+        migration.already_managed,
+        migration.missing.len(),
+        migration.failed.len()
+    );
+    Ok(migration)
+}
+
 /// Update an existing sound
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
@@ -48,7 +289,7 @@ pub fn update_sound(
     trim_end_ms: Option<u64>,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<Sound, String> {
+) -> Result<Sound, AppError> {
     let mut library = {
         let current = state.read_sounds();
         current.clone()
@@ -66,19 +307,150 @@ pub fn update_sound(
         None,                // Don't change is_favorite here
         Some(trim_start_ms), // Update trim_start_ms
         Some(trim_end_ms),   // Update trim_end_ms
-    )?;
+    )
+    .map_err(|e| sound_not_found(e, &sound_id))?;
 
     state.update_and_save_sounds(&app_handle, library)?;
     Ok(sound)
 }
 
+/// Check a sound's file for an empty file, no audio or silence
+///
+/// Called after importing a sound or pointing it at another file. The
+/// warning (None if the file is fine) is stored on the sound so the grid can
+/// show a badge. Files that can't be decoded at all return the decode error.
+#[tauri::command]
+pub async fn validate_sound_file(
+    sound_id: SoundId,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<ImportWarning>, AppError> {
+    let file_path = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .find(|s| s.id == sound_id)
+        .map(|s| s.file_path.clone())
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    let warning = tauri::async_runtime::spawn_blocking(move || inspect_file(&file_path))
+        .await
+        .map_err(|e| AppError::internal(format!("File check task failed: {}", e)))??;
+    if let Some(warning) = warning {
+        warn!(
+            "Sound {} imported with warning {:?}",
+            sound_id.as_str(),
+            warning
+        );
+    }
+
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+    if let Some(sound) = library.sounds.iter_mut().find(|s| s.id == sound_id) {
+        if sound.import_warning != warning {
+            sound.import_warning = warning;
+            state.update_and_save_sounds(&app_handle, library)?;
+        }
+    }
+    Ok(warning)
+}
+
+/// Write a sound's trim region, faded in and out, to a new WAV file
+///
+/// The sound and its file stay as they are. Without `output_path` the copy
+/// goes to the app's `sounds` directory, named after the sound's file
+/// ("horn (trimmed).wav"). Returns the path written.
+#[tauri::command]
+pub async fn render_trimmed_copy(
+    sound_id: SoundId,
+    output_path: Option<String>,
+    fade_in_ms: Option<u64>,
+    fade_out_ms: Option<u64>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, AppError> {
+    let sound = state
+        .read_sounds()
+        .sounds
+        .iter()
+        .find(|s| s.id == sound_id)
+        .cloned()
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    let output = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let stem = Path::new(&sound.file_path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "sound".to_string());
+            soundpack::unique_path(
+                &sounds::get_managed_sounds_dir(&app_handle)?,
+                &format!("{} (trimmed).wav", stem),
+            )
+        }
+    };
+    if output == Path::new(&sound.file_path) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "The trimmed copy can't replace the sound's own file",
+        )
+        .with_context("path", output.to_string_lossy()));
+    }
+
+    let written = output.clone();
+    tauri::async_runtime::spawn_blocking(move || -> Result<(), AppError> {
+        let decoded = audio::decode_audio_file(&sound.file_path)?;
+        let region = audio::render_region(
+            &decoded,
+            sound.trim_start_ms,
+            sound.trim_end_ms,
+            fade_in_ms.unwrap_or(0),
+            fade_out_ms.unwrap_or(0),
+        )
+        .map_err(|e| {
+            AppError::new(ErrorCode::InvalidRequest, e).with_context("sound_id", sound.id.as_str())
+        })?;
+        audio::write_wav(&written, &region)?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Render task failed: {}", e)))??;
+
+    info!(
+        "Rendered trimmed copy of {} to {:?}",
+        sound_id.as_str(),
+        output
+    );
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// Find content problems that make a file useless as a sound
+fn inspect_file(file_path: &str) -> Result<Option<ImportWarning>, AudioError> {
+    if std::fs::metadata(file_path)?.len() == 0 {
+        return Ok(Some(ImportWarning::EmptyFile));
+    }
+    match audio::decode_audio_file(file_path) {
+        Ok(data) if audio::is_silent(&data) => Ok(Some(ImportWarning::Silent)),
+        Ok(_) => Ok(None),
+        Err(AudioError::NoData) => Ok(Some(ImportWarning::NoAudio)),
+        Err(e) => Err(e),
+    }
+}
+
 /// Toggle favorite status of a sound
 #[tauri::command]
 pub fn toggle_favorite(
     sound_id: SoundId,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<Sound, String> {
+) -> Result<Sound, AppError> {
     let mut library = {
         let current = state.read_sounds();
         current.clone()
@@ -89,7 +461,9 @@ pub fn toggle_favorite(
         .sounds
         .iter_mut()
         .find(|s| s.id == sound_id)
-        .ok_or_else(|| format!("Sound not found: {}", sound_id.as_str()))?;
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
 
     sound.is_favorite = !sound.is_favorite;
     let updated_sound = sound.clone();
@@ -98,13 +472,397 @@ pub fn toggle_favorite(
     Ok(updated_sound)
 }
 
+/// Duplicate a sound (new ID, "(copy)" suffix) to create a variant of it,
+/// e.g. a different trim of the same file
+///
+/// Hotkeys are not copied.
+#[tauri::command]
+pub fn duplicate_sound(
+    sound_id: SoundId,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let copy = sounds::duplicate_sound(&mut library, &sound_id)
+        .map_err(|e| sound_not_found(e, &sound_id))?;
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(copy)
+}
+
+/// Set what re-triggering a sound while it plays does
+#[tauri::command]
+pub fn set_play_policy(
+    sound_id: SoundId,
+    play_policy: PlayPolicy,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.play_policy = play_policy;
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Enable or disable resuming a sound from where it was last stopped
+#[tauri::command]
+pub fn set_resume_playback(
+    sound_id: SoundId,
+    enabled: bool,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.resume_playback = enabled;
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    if !enabled {
+        state
+            .write_session()
+            .record_position(sound_id.as_str(), None);
+    }
+    Ok(updated_sound)
+}
+
+/// Mark a sound as temporary (or keep it forever with `None`)
+///
+/// Expired sounds are removed at the next startup.
+#[tauri::command]
+pub fn set_sound_expiry(
+    sound_id: SoundId,
+    expires_at: Option<u64>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.expires_at = expires_at;
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Set the level trims (dB) a sound plays with on the monitor and broadcast
+/// outputs (`None` = no trim)
+///
+/// Applies to playbacks started afterwards.
+#[tauri::command]
+pub fn set_sound_device_trim(
+    sound_id: SoundId,
+    monitor_trim_db: Option<f32>,
+    broadcast_trim_db: Option<f32>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    for (name, trim) in [
+        ("monitor_trim_db", monitor_trim_db),
+        ("broadcast_trim_db", broadcast_trim_db),
+    ] {
+        if let Some(trim) = trim {
+            if !(MIN_DEVICE_TRIM_DB..=MAX_DEVICE_TRIM_DB).contains(&trim) {
+                return Err(AppError::new(
+                    ErrorCode::InvalidRequest,
+                    format!(
+                        "Device trim must be between {} and {} dB",
+                        MIN_DEVICE_TRIM_DB, MAX_DEVICE_TRIM_DB
+                    ),
+                )
+                .with_context(name, trim));
+            }
+        }
+    }
+
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    // A 0 dB trim is no trim
+    sound.monitor_trim_db = monitor_trim_db.filter(|db| *db != 0.0);
+    sound.broadcast_trim_db = broadcast_trim_db.filter(|db| *db != 0.0);
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Put a sound into an exclusive group (or take it out with `None`)
+///
+/// Starting a sound stops the other playing sounds of its group.
+#[tauri::command]
+pub fn set_sound_group(
+    sound_id: SoundId,
+    group: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    // Blank names mean no group
+    sound.group = group
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty());
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Replace a sound's tags
+///
+/// Tags are trimmed; blank ones and duplicates (ignoring case) are dropped.
+#[tauri::command]
+pub fn set_sound_tags(
+    sound_id: SoundId,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = sounds::set_tags(&mut library, &sound_id, tags)
+        .map_err(|e| sound_not_found(e, &sound_id))?;
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(sound)
+}
+
+/// List all tags in the library with how many sounds carry each
+#[tauri::command]
+pub fn list_tags(state: State<'_, AppState>) -> Result<Vec<TagCount>, AppError> {
+    Ok(sounds::list_tags(&state.read_sounds()))
+}
+
+/// Set the output devices a sound plays on instead of the global ones
+/// (`None` = use the global device)
+#[tauri::command]
+pub fn set_sound_devices(
+    sound_id: SoundId,
+    monitor_device_id: Option<DeviceId>,
+    broadcast_device_id: Option<DeviceId>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.monitor_device_id = monitor_device_id;
+    sound.broadcast_device_id = broadcast_device_id;
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Set the region `preview_sound` auditions (`None` = preview the whole sound)
+#[tauri::command]
+pub fn set_sound_preview(
+    sound_id: SoundId,
+    preview: Option<PreviewRegion>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    if let Some(region) = preview {
+        region.validate().map_err(|e| {
+            AppError::new(ErrorCode::InvalidRequest, e)
+                .with_context("start_ms", region.start_ms)
+                .with_context("end_ms", region.end_ms)
+        })?;
+    }
+
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.preview = preview;
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Set the duration a sound is time-stretched to (`None` = play at normal speed)
+#[tauri::command]
+pub fn set_sound_fit_duration(
+    sound_id: SoundId,
+    duration_ms: Option<u64>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    if duration_ms == Some(0) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            "Fit duration must be greater than 0",
+        ));
+    }
+
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.fit_duration_ms = duration_ms;
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
+/// Set a sound's pitch shift (semitones) and playback speed
+///
+/// `None`, 0 semitones and speed 1 play the sound as it is.
+#[tauri::command]
+pub fn set_sound_pitch_speed(
+    sound_id: SoundId,
+    pitch_semitones: Option<f32>,
+    speed: Option<f32>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Sound, AppError> {
+    if let Some(pitch) = pitch_semitones {
+        if !(-audio::MAX_PITCH_SEMITONES..=audio::MAX_PITCH_SEMITONES).contains(&pitch) {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "Pitch must be between -{0} and {0} semitones",
+                    audio::MAX_PITCH_SEMITONES
+                ),
+            )
+            .with_context("pitch_semitones", pitch.to_string()));
+        }
+    }
+    if let Some(speed) = speed {
+        if !(audio::MIN_SPEED..=audio::MAX_SPEED).contains(&speed) {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "Speed must be between {} and {}",
+                    audio::MIN_SPEED,
+                    audio::MAX_SPEED
+                ),
+            )
+            .with_context("speed", speed.to_string()));
+        }
+    }
+
+    let mut library = {
+        let current = state.read_sounds();
+        current.clone()
+    };
+
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| s.id == sound_id)
+        .ok_or_else(|| {
+            sound_not_found(format!("Sound not found: {}", sound_id.as_str()), &sound_id)
+        })?;
+
+    sound.pitch_semitones = pitch_semitones.filter(|p| *p != 0.0);
+    sound.speed = speed.filter(|s| *s != 1.0);
+    let updated_sound = sound.clone();
+
+    state.update_and_save_sounds(&app_handle, library)?;
+    Ok(updated_sound)
+}
+
 /// Delete a sound from the library and remove associated hotkeys
 #[tauri::command]
 pub fn delete_sound(
     sound_id: SoundId,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // First, find and remove any hotkeys associated with this sound
     let mut mappings = {
         let current = state.read_hotkeys();
@@ -149,9 +907,30 @@ pub fn delete_sound(
         current.clone()
     };
 
-    sounds::delete_sound(&mut library, &sound_id)?;
+    let removed: Vec<Sound> = library
+        .sounds
+        .iter()
+        .filter(|s| s.id == sound_id)
+        .cloned()
+        .collect();
+    sounds::delete_sound(&mut library, &sound_id).map_err(|e| sound_not_found(e, &sound_id))?;
+
+    // Copies in the managed directory belong to the library
+    let managed_files = match sounds::get_managed_sounds_dir(&app_handle) {
+        Ok(dir) => sounds::orphaned_managed_files(&removed, &library, &dir),
+        Err(e) => {
+            warn!("Keeping file of deleted sound: {}", e);
+            Vec::new()
+        }
+    };
     state.update_and_save_sounds(&app_handle, library)?;
 
+    for file in managed_files {
+        if let Err(e) = std::fs::remove_file(&file) {
+            warn!("Failed to delete sound file {:?}: {}", file, e);
+        }
+    }
+
     Ok(())
 }
 
@@ -162,7 +941,7 @@ pub fn add_category(
     icon: Option<String>,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<Category, String> {
+) -> Result<Category, AppError> {
     let mut library = {
         let current = state.read_sounds();
         current.clone()
@@ -182,13 +961,14 @@ pub fn update_category(
     sort_order: Option<i32>,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<Category, String> {
+) -> Result<Category, AppError> {
     let mut library = {
         let current = state.read_sounds();
         current.clone()
     };
 
-    let category = sounds::update_category(&mut library, &category_id, name, icon, sort_order)?;
+    let category = sounds::update_category(&mut library, &category_id, name, icon, sort_order)
+        .map_err(|e| category_not_found(e, &category_id))?;
     state.update_and_save_sounds(&app_handle, library)?;
     Ok(category)
 }
@@ -200,13 +980,32 @@ pub fn delete_category(
     move_sounds_to: Option<CategoryId>,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut library = {
         let current = state.read_sounds();
         current.clone()
     };
 
-    sounds::delete_category(&mut library, &category_id, move_sounds_to)?;
+    if !library.categories.iter().any(|c| c.id == category_id) {
+        return Err(category_not_found(
+            format!("Category not found: {}", category_id.as_str()),
+            &category_id,
+        ));
+    }
+
+    sounds::delete_category(&mut library, &category_id, move_sounds_to)
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e))?;
     state.update_and_save_sounds(&app_handle, library)?;
     Ok(())
 }
+
+/// Build a `SoundNotFound` error carrying the sound ID
+fn sound_not_found(message: String, sound_id: &SoundId) -> AppError {
+    AppError::new(ErrorCode::SoundNotFound, message).with_context("sound_id", sound_id.as_str())
+}
+
+/// Build a `CategoryNotFound` error carrying the category ID
+fn category_not_found(message: String, category_id: &CategoryId) -> AppError {
+    AppError::new(ErrorCode::CategoryNotFound, message)
+        .with_context("category_id", category_id.as_str())
+}
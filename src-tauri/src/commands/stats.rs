@@ -0,0 +1,66 @@
+//! Play statistics commands ("Frequently used" and "Recent" views)
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::error::AppError;
+use crate::sounds::{Sound, SoundId};
+use crate::stats::PlayStats;
+use crate::AppState;
+
+/// Sounds listed when no limit is given
+const DEFAULT_LIMIT: usize = 20;
+
+/// A sound with its play statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct SoundPlayStats {
+    pub sound: Sound,
+    pub stats: PlayStats,
+}
+
+/// Play statistics of all played sounds, keyed by sound ID
+#[tauri::command]
+pub fn get_sound_stats(
+    state: State<'_, AppState>,
+) -> Result<HashMap<SoundId, PlayStats>, AppError> {
+    Ok(state.read_stats().sounds.clone())
+}
+
+/// Most played sounds, most plays first
+#[tauri::command]
+pub fn get_most_played(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SoundPlayStats>, AppError> {
+    let ranked = state.read_stats().most_played(usize::MAX);
+    Ok(with_sounds(&state, ranked, limit))
+}
+
+/// Last played sounds, most recent first
+#[tauri::command]
+pub fn get_recently_played(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SoundPlayStats>, AppError> {
+    let ranked = state.read_stats().recently_played(usize::MAX);
+    Ok(with_sounds(&state, ranked, limit))
+}
+
+/// Attach the sounds to ranked statistics, skipping sounds deleted since
+fn with_sounds(
+    state: &AppState,
+    ranked: Vec<(SoundId, PlayStats)>,
+    limit: Option<usize>,
+) -> Vec<SoundPlayStats> {
+    let library = state.read_sounds();
+    ranked
+        .into_iter()
+        .filter_map(|(id, stats)| {
+            let sound = library.sounds.iter().find(|s| s.id == id)?.clone();
+            Some(SoundPlayStats { sound, stats })
+        })
+        .take(limit.unwrap_or(DEFAULT_LIMIT))
+        .collect()
+}
@@ -1,13 +1,23 @@
 //! VB-Cable related Tauri commands
 
 use crate::vbcable::{
-    activate_comm_mode, cleanup_temp_files, deactivate_comm_mode, detect_vb_cable, disable_routing,
-    enable_routing, get_routing_status, install_vbcable, is_comm_mode_active, list_capture_devices,
-    uninstall_vbcable, wait_for_vb_cable, DefaultDeviceManager, RestoreResult, SavedDefaults,
-    VbCableStatus,
+    activate_comm_mode, apply_noise_reduction, apply_voice_changer, check_for_update,
+    cleanup_temp_files, deactivate_comm_mode, detect_vb_cable, disable_routing, enable_routing,
+    get_routing_stats, get_routing_status, install_vbcable, is_comm_mode_active,
+    list_capture_devices, set_duck_times, set_routing_gains, uninstall_vbcable, wait_for_vb_cable,
+    DefaultDeviceManager, MicRoutingStats, NoiseReduction, RestoreResult, SavedDefaults,
+    VbCableStatus, VbCableUpdateInfo, VoiceChanger,
 };
+use tauri::{Emitter, Manager, State};
 use tracing::info;
 
+use crate::audio::AudioManager;
+use crate::error::{AppError, ErrorCode};
+use crate::settings::{
+    AppSettings, MAX_MICROPHONE_DUCK_DB, MAX_MICROPHONE_DUCK_TIME_MS, MAX_MICROPHONE_GAIN,
+};
+use crate::AppState;
+
 /// Check if VB-Cable is installed and get its status
 #[tauri::command]
 pub fn check_vb_cable_status() -> VbCableStatus {
@@ -26,16 +36,24 @@ pub fn get_vb_cable_device_name() -> Option<String> {
     detect_vb_cable().map(|info| info.output_device)
 }
 
+/// Compare the installed VB-Cable driver version with the bundled installer's
+///
+/// The frontend offers a reinstall (`start_vb_cable_install`) when outdated.
+#[tauri::command]
+pub fn check_vb_cable_update() -> VbCableUpdateInfo {
+    check_for_update()
+}
+
 /// Save the current default audio device
 ///
 /// Call this before VB-Cable installation to preserve the user's original default device.
 /// Returns the saved device ID on success for use with restore_default_audio_device.
 #[tauri::command]
-pub fn save_default_audio_device() -> Result<String, String> {
-    let manager = DefaultDeviceManager::save_current_default()?;
+pub fn save_default_audio_device() -> Result<String, AppError> {
+    let manager = DefaultDeviceManager::save_current_default().map_err(vb_cable_error)?;
     manager
         .get_saved_device_id()
-        .ok_or_else(|| "No device saved".to_string())
+        .ok_or_else(|| AppError::new(ErrorCode::DeviceMissing, "No device saved"))
 }
 
 /// Restore a previously saved default audio device
@@ -43,8 +61,8 @@ pub fn save_default_audio_device() -> Result<String, String> {
 /// Call this after VB-Cable installation to restore the user's original default device.
 /// Pass the device_id returned from save_default_audio_device.
 #[tauri::command]
-pub fn restore_default_audio_device(device_id: String) -> Result<(), String> {
-    DefaultDeviceManager::restore_device(&device_id)
+pub fn restore_default_audio_device(device_id: String) -> Result<(), AppError> {
+    DefaultDeviceManager::restore_device(&device_id).map_err(vb_cable_error)
 }
 
 /// Start VB-Cable installation (download + silent install)
@@ -53,9 +71,9 @@ pub fn restore_default_audio_device(device_id: String) -> Result<(), String> {
 /// The installation is run synchronously (blocking) - Windows will show a driver
 /// approval dialog that the user must accept.
 #[tauri::command]
-pub fn start_vb_cable_install() -> Result<(), String> {
+pub fn start_vb_cable_install() -> Result<(), AppError> {
     info!("Starting VB-Cable installation from frontend request");
-    install_vbcable()
+    install_vbcable().map_err(vb_cable_error)
 }
 
 /// Cleanup temporary installation files
@@ -69,9 +87,10 @@ pub fn cleanup_vb_cable_install() {
 
 /// Open VB-Audio website (fallback if automated install fails)
 #[tauri::command]
-pub fn open_vb_audio_website() -> Result<(), String> {
+pub fn open_vb_audio_website() -> Result<(), AppError> {
     info!("Opening VB-Audio website in browser");
-    open::that("https://vb-audio.com/Cable/").map_err(|e| format!("Failed to open browser: {}", e))
+    open::that("https://vb-audio.com/Cable/")
+        .map_err(|e| AppError::internal(format!("Failed to open browser: {}", e)))
 }
 
 /// Save ALL default audio devices (render/capture, console/communications)
@@ -79,9 +98,9 @@ pub fn open_vb_audio_website() -> Result<(), String> {
 /// Call this before VB-Cable installation to preserve all user's default devices.
 /// Returns a struct with all 4 device IDs.
 #[tauri::command]
-pub fn save_all_default_devices() -> Result<SavedDefaults, String> {
+pub fn save_all_default_devices() -> Result<SavedDefaults, AppError> {
     info!("Saving all default audio devices");
-    DefaultDeviceManager::save_all_defaults()
+    DefaultDeviceManager::save_all_defaults().map_err(vb_cable_error)
 }
 
 /// Restore ALL default audio devices
@@ -122,19 +141,239 @@ pub fn list_microphones() -> Vec<(String, String)> {
 ///
 /// Routes audio from the specified microphone to VB-Cable's CABLE Input device.
 /// This allows the user's voice to be heard on Discord while using VB-Cable.
+/// With mic monitoring enabled in settings, it is also played on the monitor device.
 #[tauri::command]
-pub fn enable_microphone_routing(microphone_id: String) -> Result<(), String> {
+pub fn enable_microphone_routing(
+    microphone_id: String,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+) -> Result<(), AppError> {
     info!("Enabling microphone routing for device: {}", microphone_id);
-    enable_routing(&microphone_id)
+    let settings = state.read_settings().clone();
+    route_microphone(&settings, &microphone_id, &manager)
+        .map_err(|e| vb_cable_error(e).with_context("microphone_id", &microphone_id))
+}
+
+/// Route a microphone with the destinations, gains, noise reduction, voice
+/// changer and mic ducking from settings
+pub fn route_microphone(
+    settings: &AppSettings,
+    microphone_id: &str,
+    manager: &AudioManager,
+) -> Result<(), String> {
+    set_routing_gains(
+        settings.microphone_cable_gain,
+        settings.microphone_monitor_gain,
+    );
+    apply_noise_reduction(settings.noise_reduction);
+    apply_voice_changer(settings.voice_changer);
+    set_duck_times(
+        settings.microphone_duck_attack_ms,
+        settings.microphone_duck_release_ms,
+    );
+    let monitor_device = settings
+        .microphone_monitor_enabled
+        .then_some(settings.monitor_device_id.as_ref())
+        .flatten();
+    enable_routing(microphone_id, monitor_device, manager.mic_duck_gain())
+}
+
+/// Configure mic monitoring and the gains of both routing destinations
+///
+/// Gains apply immediately; toggling monitoring restarts active routing.
+/// Emits `settings-changed`.
+#[tauri::command]
+pub fn set_microphone_monitoring(
+    enabled: bool,
+    cable_gain: f32,
+    monitor_gain: f32,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    for (name, gain) in [("cable_gain", cable_gain), ("monitor_gain", monitor_gain)] {
+        if !(0.0..=MAX_MICROPHONE_GAIN).contains(&gain) {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "Microphone gain must be between 0 and {}",
+                    MAX_MICROPHONE_GAIN
+                ),
+            )
+            .with_context(name, gain));
+        }
+    }
+
+    let mut settings = state.read_settings().clone();
+    let monitoring_changed = settings.microphone_monitor_enabled != enabled;
+    settings.microphone_monitor_enabled = enabled;
+    settings.microphone_cable_gain = cable_gain;
+    settings.microphone_monitor_gain = monitor_gain;
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    set_routing_gains(cable_gain, monitor_gain);
+    if monitoring_changed {
+        if let Some(microphone_id) = get_routing_status() {
+            info!(
+                "Restarting microphone routing (monitoring {})",
+                if enabled { "on" } else { "off" }
+            );
+            disable_routing().map_err(vb_cable_error)?;
+            route_microphone(&settings, &microphone_id, &manager)
+                .map_err(|e| vb_cable_error(e).with_context("microphone_id", &microphone_id))?;
+        }
+    }
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Configure mic ducking: the routed microphone on CABLE Input is lowered by
+/// `depth_db` while sounds play
+///
+/// Applies immediately. Emits `settings-changed`.
+#[tauri::command]
+pub fn set_microphone_ducking(
+    enabled: bool,
+    depth_db: f32,
+    attack_ms: u32,
+    release_ms: u32,
+    state: State<'_, AppState>,
+    manager: State<'_, AudioManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    if !(0.0..=MAX_MICROPHONE_DUCK_DB).contains(&depth_db) {
+        return Err(AppError::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "Mic ducking depth must be between 0 and {} dB",
+                MAX_MICROPHONE_DUCK_DB
+            ),
+        )
+        .with_context("depth_db", depth_db));
+    }
+    for (name, ms) in [("attack_ms", attack_ms), ("release_ms", release_ms)] {
+        if ms > MAX_MICROPHONE_DUCK_TIME_MS {
+            return Err(AppError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "Mic ducking times must be at most {} ms",
+                    MAX_MICROPHONE_DUCK_TIME_MS
+                ),
+            )
+            .with_context(name, ms));
+        }
+    }
+
+    let mut settings = state.read_settings().clone();
+    settings.microphone_duck_enabled = enabled;
+    settings.microphone_duck_db = depth_db;
+    settings.microphone_duck_attack_ms = attack_ms;
+    settings.microphone_duck_release_ms = release_ms;
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+
+    manager.set_mic_duck(settings.microphone_duck());
+    set_duck_times(attack_ms, release_ms);
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Get the noise reduction settings of the routed microphone
+#[tauri::command]
+pub fn get_noise_reduction(state: State<'_, AppState>) -> NoiseReduction {
+    state.read_settings().noise_reduction
+}
+
+/// Configure noise suppression and the noise gate of the routed microphone
+///
+/// Applies immediately to active routing. Emits `settings-changed`.
+#[tauri::command]
+pub fn set_noise_reduction(
+    noise_reduction: NoiseReduction,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    noise_reduction
+        .validate()
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e))?;
+
+    let mut settings = state.read_settings().clone();
+    settings.noise_reduction = noise_reduction;
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+    apply_noise_reduction(noise_reduction);
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
+}
+
+/// Get the voice changer settings of the routed microphone
+#[tauri::command]
+pub fn get_voice_changer(state: State<'_, AppState>) -> VoiceChanger {
+    state.read_settings().voice_changer
+}
+
+/// Configure the voice changer of the routed microphone
+///
+/// Applies immediately to active routing. Emits `settings-changed`.
+#[tauri::command]
+pub fn set_voice_changer(
+    voice_changer: VoiceChanger,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, AppError> {
+    voice_changer
+        .validate()
+        .map_err(|e| AppError::new(ErrorCode::InvalidRequest, e))?;
+
+    let mut settings = state.read_settings().clone();
+    settings.voice_changer = voice_changer;
+    state.update_and_save_settings(&app_handle, settings.clone())?;
+    apply_voice_changer(voice_changer);
+
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        tracing::error!("Failed to emit settings changed event: {}", e);
+    }
+    Ok(settings)
 }
 
 /// Disable microphone routing
 ///
 /// Stops routing microphone audio to CABLE Input.
 #[tauri::command]
-pub fn disable_microphone_routing() -> Result<(), String> {
+pub fn disable_microphone_routing() -> Result<(), AppError> {
     info!("Disabling microphone routing");
-    disable_routing()
+    disable_routing().map_err(vb_cable_error)
+}
+
+/// Turn microphone routing off if active, else on for the configured microphone
+///
+/// Returns whether routing is now enabled.
+pub fn toggle_microphone_routing(app_handle: &tauri::AppHandle) -> Result<bool, String> {
+    if get_routing_status().is_some() {
+        info!("Disabling microphone routing");
+        disable_routing()?;
+        return Ok(false);
+    }
+
+    let settings = app_handle.state::<AppState>().read_settings().clone();
+    let microphone_id = settings
+        .microphone_routing_device_id
+        .clone()
+        .ok_or("No microphone configured for routing")?;
+    info!("Enabling microphone routing for device: {}", microphone_id);
+    route_microphone(
+        &settings,
+        &microphone_id,
+        &app_handle.state::<AudioManager>(),
+    )?;
+    Ok(true)
 }
 
 /// Get microphone routing status
@@ -145,6 +384,14 @@ pub fn get_microphone_routing_status() -> Option<String> {
     get_routing_status()
 }
 
+/// Get ring buffer overflow/underrun counts and the current buffer length
+///
+/// Returns None if microphone routing is not active.
+#[tauri::command]
+pub fn get_mic_routing_stats() -> Option<MicRoutingStats> {
+    get_routing_stats()
+}
+
 // ============================================================================
 // VB-Cable Uninstall Command
 // ============================================================================
@@ -154,9 +401,9 @@ pub fn get_microphone_routing_status() -> Option<String> {
 /// Downloads the installer if not cached and runs it with -u flag for uninstall.
 /// Will trigger UAC prompt for admin rights.
 #[tauri::command]
-pub fn start_vb_cable_uninstall() -> Result<(), String> {
+pub fn start_vb_cable_uninstall() -> Result<(), AppError> {
     info!("Starting VB-Cable uninstallation from frontend request");
-    uninstall_vbcable()
+    uninstall_vbcable().map_err(vb_cable_error)
 }
 
 // ============================================================================
@@ -167,9 +414,10 @@ pub fn start_vb_cable_uninstall() -> Result<(), String> {
 ///
 /// Opens the classic Windows Sound settings where users can manage audio devices.
 #[tauri::command]
-pub fn open_sound_settings() -> Result<(), String> {
+pub fn open_sound_settings() -> Result<(), AppError> {
     info!("Opening Windows Sound settings (mmsys.cpl)");
-    open::that("mmsys.cpl").map_err(|e| format!("Failed to open sound settings: {}", e))
+    open::that("mmsys.cpl")
+        .map_err(|e| AppError::internal(format!("Failed to open sound settings: {}", e)))
 }
 
 // ============================================================================
@@ -182,9 +430,9 @@ pub fn open_sound_settings() -> Result<(), String> {
 /// This makes Discord/Teams/Zoom automatically use VB-Cable while the app is running.
 /// The original device is saved and restored when deactivate_vbcable_comm_mode is called.
 #[tauri::command]
-pub fn activate_vbcable_comm_mode() -> Result<(), String> {
+pub fn activate_vbcable_comm_mode() -> Result<(), AppError> {
     info!("Activating VB-Cable communications mode");
-    activate_comm_mode()
+    activate_comm_mode().map_err(vb_cable_error)
 }
 
 /// Deactivate VB-Cable communications mode
@@ -192,9 +440,9 @@ pub fn activate_vbcable_comm_mode() -> Result<(), String> {
 /// Restores the original Windows default communications capture device.
 /// Call this when the app is closing or when the user disables VB-Cable integration.
 #[tauri::command]
-pub fn deactivate_vbcable_comm_mode() -> Result<(), String> {
+pub fn deactivate_vbcable_comm_mode() -> Result<(), AppError> {
     info!("Deactivating VB-Cable communications mode");
-    deactivate_comm_mode()
+    deactivate_comm_mode().map_err(vb_cable_error)
 }
 
 /// Check if VB-Cable communications mode is active
@@ -204,3 +452,8 @@ pub fn deactivate_vbcable_comm_mode() -> Result<(), String> {
 pub fn is_vbcable_comm_mode_active() -> bool {
     is_comm_mode_active()
 }
+
+/// Wrap a VB-Cable module error with the `VbCable` code
+fn vb_cable_error(message: String) -> AppError {
+    AppError::new(ErrorCode::VbCable, message)
+}
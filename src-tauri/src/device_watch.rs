@@ -0,0 +1,243 @@
+//! Output device hot-plug watching
+//!
+//! Windows reports endpoint changes (a device plugged in, removed, enabled,
+//! disabled or made the default) to a registered notification client. Each
+//! burst of changes rescans the output devices like `refresh_audio_devices`
+//! and emits `audio-devices-changed` with the new list. Saved devices are
+//! resolved whenever a sound plays, so a monitor or broadcast device that
+//! comes back is used again right away, and a fallback covering for it is
+//! reported as restored.
+
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use tauri::Emitter;
+use tracing::{debug, error, info};
+
+use crate::audio::AudioDevice;
+use crate::commands;
+
+/// Quiet time before rescanning; plugging in a device sends several
+/// notifications (added, state changed, default changed)
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Spawn the thread rescanning devices on endpoint changes
+///
+/// Does nothing on platforms without endpoint notifications.
+pub fn spawn_watcher(app: tauri::AppHandle) {
+    if !cfg!(windows) {
+        return;
+    }
+    let spawned = std::thread::Builder::new()
+        .name("device-watch".to_string())
+        .spawn(move || {
+            let (tx, rx) = mpsc::channel();
+            // Kept registered for as long as the thread runs
+            let Some(_registration) = notifications::register(tx) else {
+                return;
+            };
+            info!("Watching output devices for changes");
+            while let Some(changes) = next_burst(&rx, SETTLE_DELAY) {
+                debug!(changes, "Output devices changed");
+                rescan(&app);
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start device watcher thread: {}", e);
+    }
+}
+
+/// Wait for a notification and the ones following it within `settle`
+///
+/// Returns how many arrived, or None once the sender is gone.
+pub(crate) fn next_burst(rx: &mpsc::Receiver<()>, settle: Duration) -> Option<usize> {
+    rx.recv().ok()?;
+    let mut count = 1;
+    loop {
+        match rx.recv_timeout(settle) {
+            Ok(()) => count += 1,
+            Err(RecvTimeoutError::Timeout) => return Some(count),
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+fn rescan(app: &tauri::AppHandle) {
+    match commands::rescan_devices(app) {
+        Ok(devices) => emit_devices_changed(app, &devices),
+        Err(e) => error!("Failed to rescan output devices: {}", e),
+    }
+}
+
+fn emit_devices_changed(app: &tauri::AppHandle, devices: &[AudioDevice]) {
+    info!("Output devices changed, {} available", devices.len());
+    if let Err(e) = app.emit("audio-devices-changed", devices) {
+        error!("Failed to emit devices changed event: {}", e);
+    }
+}
+
+#[cfg(windows)]
+mod notifications {
+    use std::sync::mpsc::Sender;
+    use std::sync::Mutex;
+
+    use tracing::{error, warn};
+    use windows::core::{implement, Result, PCWSTR};
+    use windows::Win32::Foundation::PROPERTYKEY;
+    use windows::Win32::Media::Audio::{
+        eRender, EDataFlow, ERole, IMMDeviceEnumerator, IMMNotificationClient,
+        IMMNotificationClient_Impl, MMDeviceEnumerator, DEVICE_STATE,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_MULTITHREADED,
+    };
+
+    /// Forwards endpoint changes to the watcher thread
+    ///
+    /// Called on threads owned by the audio service, which must not be
+    /// blocked, so it only sends a wake-up.
+    #[implement(IMMNotificationClient)]
+    struct DeviceNotifications {
+        tx: Mutex<Sender<()>>,
+    }
+
+    impl DeviceNotifications {
+        fn notify(&self) {
+            if let Ok(tx) = self.tx.lock() {
+                let _ = tx.send(());
+            }
+        }
+    }
+
+    impl IMMNotificationClient_Impl for DeviceNotifications_Impl {
+        fn OnDeviceStateChanged(&self, _: &PCWSTR, _: DEVICE_STATE) -> Result<()> {
+            self.notify();
+            Ok(())
+        }
+
+        fn OnDeviceAdded(&self, _: &PCWSTR) -> Result<()> {
+            self.notify();
+            Ok(())
+        }
+
+        fn OnDeviceRemoved(&self, _: &PCWSTR) -> Result<()> {
+            self.notify();
+            Ok(())
+        }
+
+        fn OnDefaultDeviceChanged(&self, flow: EDataFlow, _: ERole, _: &PCWSTR) -> Result<()> {
+            if flow == eRender {
+                self.notify();
+            }
+            Ok(())
+        }
+
+        fn OnPropertyValueChanged(&self, _: &PCWSTR, _: &PROPERTYKEY) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Registered notification client; unregisters when dropped
+    pub struct Registration {
+        enumerator: IMMDeviceEnumerator,
+        client: IMMNotificationClient,
+        we_initialized_com: bool,
+    }
+
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            unsafe {
+                if let Err(e) = self
+                    .enumerator
+                    .UnregisterEndpointNotificationCallback(&self.client)
+                {
+                    warn!("Failed to unregister device notifications: {}", e);
+                }
+                if self.we_initialized_com {
+                    CoUninitialize();
+                }
+            }
+        }
+    }
+
+    /// Register for endpoint changes, each sending `()` on `tx`
+    pub fn register(tx: Sender<()>) -> Option<Registration> {
+        /// COM error: already initialized with different threading mode
+        const RPC_E_CHANGED_MODE: i32 = 0x80010106u32 as i32;
+
+        unsafe {
+            let hr = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let we_initialized_com = hr.is_ok();
+            if hr.is_err() && hr != windows::core::HRESULT(RPC_E_CHANGED_MODE) {
+                error!(
+                    "Failed to initialize COM for device notifications: {:?}",
+                    hr
+                );
+                return None;
+            }
+
+            let registered = (|| -> Result<(IMMDeviceEnumerator, IMMNotificationClient)> {
+                let enumerator: IMMDeviceEnumerator =
+                    CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+                let client: IMMNotificationClient =
+                    DeviceNotifications { tx: Mutex::new(tx) }.into();
+                enumerator.RegisterEndpointNotificationCallback(&client)?;
+                Ok((enumerator, client))
+            })();
+
+            match registered {
+                Ok((enumerator, client)) => Some(Registration {
+                    enumerator,
+                    client,
+                    we_initialized_com,
+                }),
+                Err(e) => {
+                    error!("Failed to register device notifications: {}", e);
+                    if we_initialized_com {
+                        CoUninitialize();
+                    }
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod notifications {
+    use std::sync::mpsc::Sender;
+
+    /// Endpoint notifications are Windows-only
+    pub fn register(_tx: Sender<()>) -> Option<()> {
+        None
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_burst_collects_notifications() {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..3 {
+            tx.send(()).unwrap();
+        }
+        assert_eq!(next_burst(&rx, Duration::from_millis(20)), Some(3));
+
+        // Nothing more arrives until the next change
+        tx.send(()).unwrap();
+        assert_eq!(next_burst(&rx, Duration::from_millis(20)), Some(1));
+    }
+
+    #[test]
+    fn test_next_burst_ends_without_sender() {
+        let (tx, rx) = mpsc::channel();
+        drop(tx);
+        assert_eq!(next_burst(&rx, Duration::from_millis(20)), None);
+    }
+}
@@ -0,0 +1,210 @@
+//! Discord RPC authorization
+//!
+//! Reading the voice state requires the `rpc.voice.read` scope. The user
+//! approves it once in the Discord client (AUTHORIZE), the returned code is
+//! exchanged for an OAuth2 token and the token is kept in
+//! `discord_token.json` so later sessions only need AUTHENTICATE.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+use tracing::{info, warn};
+
+use super::ipc::IpcClient;
+use super::DiscordCredentials;
+
+const TOKEN_URL: &str = "https://discord.com/api/oauth2/token";
+
+/// Redirect URI that must be registered on the Discord application
+const REDIRECT_URI: &str = "http://localhost";
+
+/// Scopes needed to read mute/deafen state
+const SCOPES: [&str; 2] = ["rpc", "rpc.voice.read"];
+
+/// Refresh tokens that expire within this window (seconds)
+const EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// OAuth2 token persisted between sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Expiry time (Unix seconds)
+    pub expires_at: u64,
+}
+
+impl StoredToken {
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at <= now + EXPIRY_MARGIN_SECS
+    }
+}
+
+/// Token endpoint response
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: u64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Get the path to the stored token file
+fn get_token_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    // Ensure directory exists
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data_dir.join("discord_token.json"))
+}
+
+fn load_token(app_handle: &tauri::AppHandle) -> Option<StoredToken> {
+    let content = std::fs::read_to_string(get_token_path(app_handle).ok()?).ok()?;
+    serde_json::from_str(&content)
+        .inspect_err(|e| warn!("Ignoring unreadable Discord token: {}", e))
+        .ok()
+}
+
+fn save_token(app_handle: &tauri::AppHandle, token: &StoredToken) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(token)
+        .map_err(|e| format!("Failed to serialize Discord token: {}", e))?;
+    crate::persistence::atomic_write(&get_token_path(app_handle)?, &json)
+}
+
+/// Forget the stored token (forces a new authorization)
+pub fn clear_token(app_handle: &tauri::AppHandle) {
+    if let Ok(path) = get_token_path(app_handle) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Authenticate the RPC connection, authorizing or refreshing as needed
+///
+/// `on_authorize` is called before Discord shows its approval prompt.
+pub fn authenticate(
+    client: &mut IpcClient,
+    app_handle: &tauri::AppHandle,
+    credentials: &DiscordCredentials,
+    on_authorize: impl FnOnce(),
+) -> Result<(), String> {
+    let stored = load_token(app_handle);
+    let token = match stored {
+        Some(token) if !token.is_expired(now_secs()) => token,
+        Some(StoredToken {
+            refresh_token: Some(refresh_token),
+            ..
+        }) => match refresh(credentials, &refresh_token) {
+            Ok(token) => token,
+            Err(e) => {
+                warn!("Discord token refresh failed, re-authorizing: {}", e);
+                on_authorize();
+                authorize(client, credentials)?
+            }
+        },
+        _ => {
+            on_authorize();
+            authorize(client, credentials)?
+        }
+    };
+    save_token(app_handle, &token)?;
+
+    if let Err(e) = client.command(
+        "AUTHENTICATE",
+        json!({ "access_token": token.access_token }),
+    ) {
+        // Revoked or issued for another application - start over next time
+        clear_token(app_handle);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Ask the user to approve access in Discord and exchange the code for a token
+fn authorize(
+    client: &mut IpcClient,
+    credentials: &DiscordCredentials,
+) -> Result<StoredToken, String> {
+    info!("Requesting Discord voice state authorization");
+    let data = client.command(
+        "AUTHORIZE",
+        json!({ "client_id": credentials.client_id, "scopes": SCOPES }),
+    )?;
+    let code = data["code"]
+        .as_str()
+        .ok_or_else(|| "Discord authorization returned no code".to_string())?;
+
+    request_token(&[
+        ("client_id", credentials.client_id.as_str()),
+        ("client_secret", credentials.client_secret.as_str()),
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", REDIRECT_URI),
+    ])
+}
+
+fn refresh(credentials: &DiscordCredentials, refresh_token: &str) -> Result<StoredToken, String> {
+    request_token(&[
+        ("client_id", credentials.client_id.as_str()),
+        ("client_secret", credentials.client_secret.as_str()),
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+    ])
+}
+
+/// POST to the OAuth2 token endpoint
+fn request_token(params: &[(&str, &str)]) -> Result<StoredToken, String> {
+    let response = reqwest::blocking::Client::new()
+        .post(TOKEN_URL)
+        .form(params)
+        .send()
+        .map_err(|e| format!("Discord token request failed: {}", e))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|e| format!("Failed to read Discord token response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!(
+            "Discord token request failed ({}): {}",
+            status, body
+        ));
+    }
+
+    let token: TokenResponse = serde_json::from_str(&body)
+        .map_err(|e| format!("Invalid Discord token response: {}", e))?;
+    Ok(StoredToken {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: now_secs() + token.expires_in,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_expiry_margin() {
+        let token = StoredToken {
+            access_token: "abc".to_string(),
+            refresh_token: None,
+            expires_at: 1_000,
+        };
+
+        assert!(!token.is_expired(1_000 - EXPIRY_MARGIN_SECS - 1));
+        assert!(token.is_expired(1_000 - EXPIRY_MARGIN_SECS));
+        assert!(token.is_expired(2_000));
+    }
+}
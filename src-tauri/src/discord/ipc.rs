@@ -0,0 +1,208 @@
+//! Discord local RPC transport
+//!
+//! Frames are `[opcode: u32 LE][length: u32 LE][JSON payload]`, exchanged over
+//! `\\.\pipe\discord-ipc-N` on Windows or `$XDG_RUNTIME_DIR/discord-ipc-N`
+//! elsewhere (N = 0..9, first one that accepts wins).
+
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use tracing::debug;
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+const OP_PING: u32 = 3;
+const OP_PONG: u32 = 4;
+
+/// RPC protocol version
+const RPC_VERSION: u32 = 1;
+
+/// Number of pipe slots Discord may listen on
+const MAX_PIPES: u32 = 10;
+
+/// Upper bound for a single frame payload (protects against garbage lengths)
+const MAX_FRAME_LEN: usize = 1024 * 1024;
+
+/// Bidirectional byte stream to the Discord client
+trait Transport: Read + Write + Send {}
+impl<T: Read + Write + Send> Transport for T {}
+
+/// Connection to the local Discord client
+pub struct IpcClient {
+    stream: Box<dyn Transport>,
+    nonce: u64,
+}
+
+impl IpcClient {
+    /// Connect to a running Discord client and complete the handshake
+    pub fn connect(client_id: &str) -> Result<Self, String> {
+        let stream = open_pipe().ok_or_else(|| "Discord is not running".to_string())?;
+        let mut client = Self { stream, nonce: 0 };
+
+        client.send(
+            OP_HANDSHAKE,
+            &json!({ "v": RPC_VERSION, "client_id": client_id }),
+        )?;
+
+        let (op, payload) = client.receive()?;
+        if op == OP_CLOSE {
+            return Err(format!(
+                "Discord rejected the handshake: {}",
+                payload["message"].as_str().unwrap_or("unknown reason")
+            ));
+        }
+        if payload["evt"] != "READY" {
+            return Err(format!("Unexpected handshake response: {}", payload));
+        }
+
+        debug!("Connected to Discord RPC");
+        Ok(client)
+    }
+
+    /// Send an RPC command and wait for its response data
+    ///
+    /// Unrelated dispatch events received in between are skipped.
+    pub fn command(&mut self, cmd: &str, args: Value) -> Result<Value, String> {
+        self.nonce += 1;
+        let nonce = self.nonce.to_string();
+        self.send(
+            OP_FRAME,
+            &json!({ "cmd": cmd, "args": args, "nonce": nonce }),
+        )?;
+
+        loop {
+            let (op, mut payload) = self.receive()?;
+            match op {
+                OP_FRAME if payload["nonce"] == nonce.as_str() => {
+                    if payload["evt"] == "ERROR" {
+                        return Err(format!(
+                            "Discord {} failed: {}",
+                            cmd,
+                            payload["data"]["message"]
+                                .as_str()
+                                .unwrap_or("unknown error")
+                        ));
+                    }
+                    return Ok(payload
+                        .get_mut("data")
+                        .map(Value::take)
+                        .unwrap_or(Value::Null));
+                }
+                OP_FRAME => continue,
+                OP_PING => self.send(OP_PONG, &payload)?,
+                OP_CLOSE => return Err("Discord closed the connection".to_string()),
+                _ => return Err(format!("Unexpected opcode {}", op)),
+            }
+        }
+    }
+
+    fn send(&mut self, op: u32, payload: &Value) -> Result<(), String> {
+        self.stream
+            .write_all(&encode_frame(op, payload))
+            .and_then(|_| self.stream.flush())
+            .map_err(|e| format!("Failed to write to Discord: {}", e))
+    }
+
+    fn receive(&mut self) -> Result<(u32, Value), String> {
+        read_frame(&mut self.stream)
+    }
+}
+
+/// Open the first Discord IPC pipe that accepts a connection
+#[cfg(windows)]
+fn open_pipe() -> Option<Box<dyn Transport>> {
+    (0..MAX_PIPES).find_map(|i| {
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!(r"\\.\pipe\discord-ipc-{}", i))
+            .ok()
+            .map(|file| Box::new(file) as Box<dyn Transport>)
+    })
+}
+
+/// Open the first Discord IPC socket that accepts a connection
+#[cfg(unix)]
+fn open_pipe() -> Option<Box<dyn Transport>> {
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .unwrap_or_else(|| "/tmp".to_string());
+
+    (0..MAX_PIPES).find_map(|i| {
+        std::os::unix::net::UnixStream::connect(format!("{}/discord-ipc-{}", dir, i))
+            .ok()
+            .map(|stream| Box::new(stream) as Box<dyn Transport>)
+    })
+}
+
+/// Serialize a frame (header + JSON payload)
+fn encode_frame(op: u32, payload: &Value) -> Vec<u8> {
+    let body = payload.to_string().into_bytes();
+    let mut frame = Vec::with_capacity(8 + body.len());
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&body);
+    frame
+}
+
+/// Read one frame from the stream
+fn read_frame(reader: &mut impl Read) -> Result<(u32, Value), String> {
+    let mut header = [0u8; 8];
+    reader
+        .read_exact(&mut header)
+        .map_err(|e| format!("Failed to read from Discord: {}", e))?;
+
+    let op = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(format!("Discord frame too large ({} bytes)", len));
+    }
+
+    let mut body = vec![0u8; len];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("Failed to read from Discord: {}", e))?;
+
+    let payload =
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid Discord payload: {}", e))?;
+    Ok((op, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let payload = json!({ "cmd": "GET_VOICE_SETTINGS", "nonce": "1" });
+        let frame = encode_frame(OP_FRAME, &payload);
+
+        let (op, decoded) = read_frame(&mut frame.as_slice()).unwrap();
+        assert_eq!(op, OP_FRAME);
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_frame_header_layout() {
+        let frame = encode_frame(OP_HANDSHAKE, &json!({}));
+        assert_eq!(&frame[0..4], &0u32.to_le_bytes());
+        assert_eq!(&frame[4..8], &2u32.to_le_bytes());
+        assert_eq!(&frame[8..], b"{}");
+    }
+
+    #[test]
+    fn test_read_frame_rejects_oversized_length() {
+        let mut frame = Vec::new();
+        frame.extend_from_slice(&OP_FRAME.to_le_bytes());
+        frame.extend_from_slice(&(MAX_FRAME_LEN as u32 + 1).to_le_bytes());
+
+        assert!(read_frame(&mut frame.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_read_frame_truncated() {
+        let frame = encode_frame(OP_FRAME, &json!({ "evt": "READY" }));
+        assert!(read_frame(&mut &frame[..frame.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Discord mute/deafen awareness
+//!
+//! Polls the user's voice settings through Discord's local RPC and silences
+//! the broadcast output while Discord reports the user as muted or deafened,
+//! so sounds don't reach a channel the user believes they are muted in.
+//!
+//! Discord only hands out the voice state to an application the user has
+//! authorized, so the integration needs the client ID and secret of a Discord
+//! application (with `http://localhost` as redirect URI). The monitor output
+//! is never affected.
+
+mod auth;
+mod ipc;
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tracing::{debug, info, warn};
+
+use crate::settings::AppSettings;
+use ipc::IpcClient;
+
+/// Interval between voice settings polls
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Delay before reconnecting after Discord closed or was not running
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Granularity of stop checks while sleeping
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+// ============================================================================
+// Global State
+// ============================================================================
+
+/// Broadcast output is silenced (user muted/deafened in Discord)
+static BROADCAST_SUPPRESSED: AtomicBool = AtomicBool::new(false);
+
+/// Last reported integration status
+static STATUS: Mutex<DiscordStatus> = Mutex::new(DiscordStatus::disabled());
+
+/// Stop signal of the running watcher thread
+static WATCHER_STOP: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+/// Discord application credentials used for RPC authorization
+#[derive(Debug, Clone)]
+pub struct DiscordCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+impl DiscordCredentials {
+    /// Credentials configured in settings (None if incomplete)
+    pub fn from_settings(settings: &AppSettings) -> Option<Self> {
+        match (&settings.discord_client_id, &settings.discord_client_secret) {
+            (Some(id), Some(secret)) if !id.is_empty() && !secret.is_empty() => Some(Self {
+                client_id: id.clone(),
+                client_secret: secret.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Connection state of the Discord integration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscordConnection {
+    /// Integration turned off
+    Disabled,
+    /// Looking for a running Discord client
+    Connecting,
+    /// Waiting for the user to approve access in Discord
+    Authorizing,
+    /// Voice state is being tracked
+    Connected,
+    /// Discord not running or the connection failed (retrying)
+    Unavailable,
+}
+
+/// Discord integration status (payload of `discord-voice-state`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscordStatus {
+    pub connection: DiscordConnection,
+    pub muted: bool,
+    pub deafened: bool,
+    /// Broadcast output currently silenced
+    pub broadcast_suppressed: bool,
+    /// Last connection error
+    pub error: Option<String>,
+}
+
+impl DiscordStatus {
+    const fn disabled() -> Self {
+        Self {
+            connection: DiscordConnection::Disabled,
+            muted: false,
+            deafened: false,
+            broadcast_suppressed: false,
+            error: None,
+        }
+    }
+
+    fn new(connection: DiscordConnection, muted: bool, deafened: bool) -> Self {
+        Self {
+            connection,
+            muted,
+            deafened,
+            broadcast_suppressed: connection == DiscordConnection::Connected && (muted || deafened),
+            error: None,
+        }
+    }
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Whether the broadcast output should currently be silent
+pub fn is_broadcast_suppressed() -> bool {
+    BROADCAST_SUPPRESSED.load(Ordering::Relaxed)
+}
+
+/// Current integration status
+pub fn status() -> DiscordStatus {
+    STATUS
+        .lock()
+        .map(|s| s.clone())
+        .unwrap_or_else(|_| DiscordStatus::disabled())
+}
+
+/// Start (or restart) watching Discord's voice state
+pub fn start(app_handle: tauri::AppHandle, credentials: DiscordCredentials) {
+    stop(&app_handle);
+
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    if let Ok(mut watcher) = WATCHER_STOP.lock() {
+        *watcher = Some(stop_signal.clone());
+    }
+    thread::spawn(move || {
+        watch(&app_handle, &credentials, &stop_signal);
+    });
+    info!("Discord mute awareness started");
+}
+
+/// Stop watching and release the broadcast output
+///
+/// Doesn't wait for the watcher thread; it exits after its current request.
+pub fn stop(app_handle: &tauri::AppHandle) {
+    let stop_signal = WATCHER_STOP.lock().ok().and_then(|mut w| w.take());
+    if let Some(stop_signal) = stop_signal {
+        stop_signal.store(true, Ordering::Relaxed);
+        info!("Discord mute awareness stopped");
+    }
+    set_status(app_handle, DiscordStatus::disabled());
+}
+
+/// Forget the stored Discord authorization
+pub fn clear_authorization(app_handle: &tauri::AppHandle) {
+    auth::clear_token(app_handle);
+}
+
+// ============================================================================
+// Watcher
+// ============================================================================
+
+/// Watcher thread body: connect, poll, reconnect until stopped
+fn watch(app_handle: &tauri::AppHandle, credentials: &DiscordCredentials, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        let Err(e) = run_session(app_handle, credentials, stop) else {
+            break;
+        };
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+
+        debug!("Discord RPC unavailable: {}", e);
+        let mut status = DiscordStatus::new(DiscordConnection::Unavailable, false, false);
+        status.error = Some(e);
+        set_status(app_handle, status);
+
+        sleep_unless_stopped(RECONNECT_DELAY, stop);
+    }
+}
+
+/// One connection to Discord; returns Ok when stopped
+fn run_session(
+    app_handle: &tauri::AppHandle,
+    credentials: &DiscordCredentials,
+    stop: &AtomicBool,
+) -> Result<(), String> {
+    set_status(
+        app_handle,
+        DiscordStatus::new(DiscordConnection::Connecting, false, false),
+    );
+    let mut client = IpcClient::connect(&credentials.client_id)?;
+
+    auth::authenticate(&mut client, app_handle, credentials, || {
+        set_status(
+            app_handle,
+            DiscordStatus::new(DiscordConnection::Authorizing, false, false),
+        );
+    })?;
+    info!("Discord RPC authenticated, tracking voice state");
+
+    while !stop.load(Ordering::Relaxed) {
+        let settings = client.command("GET_VOICE_SETTINGS", serde_json::json!({}))?;
+        let muted = settings["mute"].as_bool().unwrap_or(false);
+        let deafened = settings["deaf"].as_bool().unwrap_or(false);
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        set_status(
+            app_handle,
+            DiscordStatus::new(DiscordConnection::Connected, muted, deafened),
+        );
+
+        sleep_unless_stopped(POLL_INTERVAL, stop);
+    }
+    Ok(())
+}
+
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let mut remaining = duration;
+    while !remaining.is_zero() && !stop.load(Ordering::Relaxed) {
+        let step = remaining.min(STOP_CHECK_INTERVAL);
+        thread::sleep(step);
+        remaining -= step;
+    }
+}
+
+/// Publish a status change and emit `discord-voice-state` if it differs
+fn set_status(app_handle: &tauri::AppHandle, status: DiscordStatus) {
+    let Ok(mut current) = STATUS.lock() else {
+        return;
+    };
+    if *current == status {
+        return;
+    }
+
+    if current.broadcast_suppressed != status.broadcast_suppressed {
+        if status.broadcast_suppressed {
+            info!("Discord reports muted/deafened - broadcast output paused");
+        } else {
+            info!("Broadcast output resumed");
+        }
+    }
+    let suppression_changed = current.broadcast_suppressed != status.broadcast_suppressed;
+    BROADCAST_SUPPRESSED.store(status.broadcast_suppressed, Ordering::Relaxed);
+    *current = status.clone();
+    drop(current);
+
+    // Playing sounds pick up the broadcast mute right away
+    if suppression_changed {
+        app_handle
+            .state::<crate::audio::AudioManager>()
+            .refresh_playbacks();
+    }
+
+    if let Err(e) = app_handle.emit("discord-voice-state", &status) {
+        warn!("Failed to emit Discord voice state: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_suppresses_when_muted_or_deafened() {
+        let connected = DiscordConnection::Connected;
+        assert!(!DiscordStatus::new(connected, false, false).broadcast_suppressed);
+        assert!(DiscordStatus::new(connected, true, false).broadcast_suppressed);
+        assert!(DiscordStatus::new(connected, false, true).broadcast_suppressed);
+    }
+
+    #[test]
+    fn test_status_never_suppresses_without_connection() {
+        // Unknown voice state must not silence the broadcast output
+        for connection in [
+            DiscordConnection::Disabled,
+            DiscordConnection::Connecting,
+            DiscordConnection::Authorizing,
+            DiscordConnection::Unavailable,
+        ] {
+            assert!(!DiscordStatus::new(connection, true, true).broadcast_suppressed);
+        }
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        let json = serde_json::to_string(&DiscordStatus::new(
+            DiscordConnection::Connected,
+            true,
+            false,
+        ))
+        .unwrap();
+        assert!(json.contains("\"connection\":\"connected\""));
+        assert!(json.contains("\"broadcast_suppressed\":true"));
+    }
+}
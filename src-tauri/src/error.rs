@@ -0,0 +1,311 @@
+//! Typed errors returned by Tauri commands
+//!
+//! Commands return `AppError` instead of plain strings so the frontend can
+//! branch on a stable `code` and localize the message.
+
+use std::collections::BTreeMap;
+
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+
+use crate::audio::AudioError;
+use crate::i18n::{self, Language};
+
+/// Stable, machine-readable error codes (serialized as snake_case)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Audio device not configured, unplugged or not found
+    DeviceMissing,
+    /// Audio device present but failed to open or start
+    DeviceError,
+    /// Referenced file does not exist
+    FileNotFound,
+    /// File format or codec not supported
+    UnsupportedFormat,
+    /// File is DRM-protected
+    DrmProtected,
+    /// Audio data could not be decoded
+    DecodeFailed,
+    /// Hotkey already assigned or held by another application
+    HotkeyConflict,
+    /// Hotkey string could not be parsed
+    HotkeyInvalid,
+    /// Hotkey is not mapped
+    HotkeyNotFound,
+    /// Sound ID does not exist in the library
+    SoundNotFound,
+    /// Category ID does not exist in the library
+    CategoryNotFound,
+    /// Playback ID is not active
+    PlaybackNotFound,
+    /// Request was well-formed but not allowed in the current state
+    InvalidRequest,
+    /// Operation was cancelled by the user
+    Cancelled,
+    /// Operation did not complete in time
+    Timeout,
+    /// VB-Cable install, routing or device switching failed
+    VbCable,
+    /// Anything not covered above
+    Internal,
+}
+
+impl ErrorCode {
+    /// Snake-case name, as serialized
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::DeviceMissing => "device_missing",
+            ErrorCode::DeviceError => "device_error",
+            ErrorCode::FileNotFound => "file_not_found",
+            ErrorCode::UnsupportedFormat => "unsupported_format",
+            ErrorCode::DrmProtected => "drm_protected",
+            ErrorCode::DecodeFailed => "decode_failed",
+            ErrorCode::HotkeyConflict => "hotkey_conflict",
+            ErrorCode::HotkeyInvalid => "hotkey_invalid",
+            ErrorCode::HotkeyNotFound => "hotkey_not_found",
+            ErrorCode::SoundNotFound => "sound_not_found",
+            ErrorCode::CategoryNotFound => "category_not_found",
+            ErrorCode::PlaybackNotFound => "playback_not_found",
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::VbCable => "vb_cable",
+            ErrorCode::Internal => "internal",
+        }
+    }
+}
+
+/// Error returned by Tauri commands
+///
+/// Serialized as `{ code, message, context? }`, with the message in the
+/// active language (see [`AppError::localized_message`]).
+#[derive(Debug, Clone)]
+pub struct AppError {
+    /// Stable error code for branching and localization
+    pub code: ErrorCode,
+    /// Human-readable (English) message
+    pub message: String,
+    /// Extra values for message interpolation (e.g. `hotkey`, `path`)
+    pub context: BTreeMap<String, String>,
+}
+
+impl AppError {
+    /// Create an error with the given code and message
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            context: BTreeMap::new(),
+        }
+    }
+
+    /// Attach a context value
+    pub fn with_context(mut self, key: &str, value: impl ToString) -> Self {
+        self.context.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Shorthand for an `Internal` error
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// Message in the given language
+    ///
+    /// English keeps the detailed message. Other languages use the generic
+    /// text for the error code; the frontend can add details from `context`.
+    pub fn localized_message(&self, language: Language) -> String {
+        if language == Language::English {
+            return self.message.clone();
+        }
+
+        let mut args: Vec<(&str, &str)> = self
+            .context
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        args.push(("message", self.message.as_str()));
+        let key = format!("error.{}", self.code.as_str());
+        i18n::format_message(i18n::lookup(language, &key), &args)
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = if self.context.is_empty() { 2 } else { 3 };
+        let mut state = serializer.serialize_struct("AppError", fields)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.localized_message(i18n::current_language()))?;
+        if !self.context.is_empty() {
+            state.serialize_field("context", &self.context)?;
+        }
+        state.end()
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Untyped errors from lower layers become `Internal`
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<AudioError> for AppError {
+    fn from(error: AudioError) -> Self {
+        let code = match &error {
+            AudioError::FileOpen(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                ErrorCode::FileNotFound
+            }
+            AudioError::FileOpen(_) => ErrorCode::Internal,
+            AudioError::ProbeFormat(_)
+            | AudioError::NoTracks
+            | AudioError::UnsupportedCodec { .. } => ErrorCode::UnsupportedFormat,
+            AudioError::DrmProtected => ErrorCode::DrmProtected,
+            AudioError::DecoderCreation(_)
+            | AudioError::PacketRead(_)
+            | AudioError::Decode(_)
+            | AudioError::NoData => ErrorCode::DecodeFailed,
+            AudioError::NoDevices
+            | AudioError::InvalidDeviceId(_)
+            | AudioError::DeviceNotFound(_) => ErrorCode::DeviceMissing,
+            AudioError::DeviceEnumeration(_)
+            | AudioError::DeviceConfig(_)
+            | AudioError::UnsupportedFormat
+            | AudioError::StreamBuild(_)
+            | AudioError::StreamStart(_) => ErrorCode::DeviceError,
+            AudioError::Cancelled => ErrorCode::Cancelled,
+        };
+
+        let app_error = Self::new(code, error.to_string());
+        match error {
+            AudioError::UnsupportedCodec { codec } => app_error.with_context("codec", codec),
+            AudioError::InvalidDeviceId(id) | AudioError::DeviceNotFound(id) => {
+                app_error.with_context("device_id", id)
+            }
+            _ => app_error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_without_context() {
+        let err = AppError::new(ErrorCode::SoundNotFound, "Sound not found: abc");
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "sound_not_found");
+        assert_eq!(json["message"], "Sound not found: abc");
+        assert!(json.get("context").is_none());
+    }
+
+    #[test]
+    fn test_serialize_with_context() {
+        let err = AppError::new(
+            ErrorCode::HotkeyConflict,
+            "Hotkey 'Ctrl+A' is already assigned",
+        )
+        .with_context("hotkey", "Ctrl+A");
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "hotkey_conflict");
+        assert_eq!(json["context"]["hotkey"], "Ctrl+A");
+    }
+
+    #[test]
+    fn test_code_as_str_matches_serde() {
+        for code in [
+            ErrorCode::DeviceMissing,
+            ErrorCode::HotkeyConflict,
+            ErrorCode::VbCable,
+            ErrorCode::Internal,
+        ] {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+    }
+
+    #[test]
+    fn test_localized_message() {
+        let err = AppError::new(ErrorCode::SoundNotFound, "Sound not found: abc");
+        assert_eq!(
+            err.localized_message(Language::English),
+            "Sound not found: abc"
+        );
+        assert_eq!(
+            err.localized_message(Language::German),
+            "Sound nicht gefunden"
+        );
+
+        let err = AppError::internal("disk full");
+        assert_eq!(
+            err.localized_message(Language::German),
+            "Unerwarteter Fehler: disk full"
+        );
+    }
+
+    #[test]
+    fn test_from_string_is_internal() {
+        let err: AppError = "disk full".to_string().into();
+        assert_eq!(err.code, ErrorCode::Internal);
+        assert_eq!(err.to_string(), "disk full");
+    }
+
+    #[test]
+    fn test_from_audio_error_file_not_found() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let err: AppError = AudioError::FileOpen(io_err).into();
+        assert_eq!(err.code, ErrorCode::FileNotFound);
+    }
+
+    #[test]
+    fn test_from_audio_error_codes() {
+        let cases = [
+            (AudioError::DrmProtected, ErrorCode::DrmProtected),
+            (AudioError::NoData, ErrorCode::DecodeFailed),
+            (AudioError::NoDevices, ErrorCode::DeviceMissing),
+            (AudioError::Cancelled, ErrorCode::Cancelled),
+            (
+                AudioError::StreamBuild("busy".to_string()),
+                ErrorCode::DeviceError,
+            ),
+        ];
+        for (audio_err, expected) in cases {
+            let err: AppError = audio_err.into();
+            assert_eq!(err.code, expected);
+        }
+    }
+
+    #[test]
+    fn test_from_audio_error_keeps_context() {
+        let err: AppError = AudioError::UnsupportedCodec {
+            codec: "ALAC".to_string(),
+        }
+        .into();
+        assert_eq!(err.code, ErrorCode::UnsupportedFormat);
+        assert_eq!(err.context.get("codec").map(String::as_str), Some("ALAC"));
+
+        let err: AppError = AudioError::DeviceNotFound("device_3".to_string()).into();
+        assert_eq!(err.code, ErrorCode::DeviceMissing);
+        assert_eq!(
+            err.context.get("device_id").map(String::as_str),
+            Some("device_3")
+        );
+    }
+}
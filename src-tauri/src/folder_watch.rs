@@ -0,0 +1,347 @@
+//! Watched folders
+//!
+//! Audio files that appear in a watched folder are added to the folder's
+//! category, and sounds whose file disappears from it are removed. Folders
+//! are compared with the library at startup and after every change the
+//! watcher reports; each sync that changed something emits
+//! `watched-folder-changed`.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tracing::{error, info, warn};
+
+use crate::audio;
+use crate::hotkeys;
+use crate::sounds::{self, CategoryId, Sound, SoundId, SoundLibrary};
+use crate::AppState;
+
+/// File extensions picked up from watched folders
+const AUDIO_EXTENSIONS: [&str; 6] = ["mp3", "wav", "ogg", "m4a", "flac", "opus"];
+
+/// Quiet time before a changed folder is synced, so files still being
+/// copied aren't read half-written
+const SETTLE_DELAY: Duration = Duration::from_secs(1);
+
+/// Running watcher; dropping it ends the sync thread
+static WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Folder whose audio files are kept in the library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedFolder {
+    pub path: String,
+    /// Category new files are added to
+    pub category_id: CategoryId,
+}
+
+/// Payload of `watched-folder-changed`
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchedFolderChanged {
+    pub folder: String,
+    pub added: Vec<Sound>,
+    pub removed: Vec<SoundId>,
+    /// Files that couldn't be read as audio (retried on the next change)
+    pub failed: Vec<String>,
+}
+
+/// Difference between a folder and the library
+#[derive(Debug, Default, PartialEq)]
+struct FolderChanges {
+    /// Files no sound refers to yet
+    added: Vec<PathBuf>,
+    /// Sounds whose file is gone from the folder
+    removed: Vec<SoundId>,
+}
+
+/// Whether a file looks like audio, by extension
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Audio files directly in `dir`, sorted
+fn audio_files_in(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_audio_file(path))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn plan_changes(library: &SoundLibrary, folder: &Path, present: &[PathBuf]) -> FolderChanges {
+    let removed = library
+        .sounds
+        .iter()
+        .filter(|s| {
+            let path = Path::new(&s.file_path);
+            path.parent() == Some(folder) && !present.iter().any(|p| p == path)
+        })
+        .map(|s| s.id.clone())
+        .collect();
+    let added = present
+        .iter()
+        .filter(|p| !library.sounds.iter().any(|s| Path::new(&s.file_path) == *p))
+        .cloned()
+        .collect();
+    FolderChanges { added, removed }
+}
+
+/// Watch the folders in the settings, replacing the previous watcher
+///
+/// Syncs every folder once right away, to pick up what changed while the
+/// app wasn't running.
+pub fn start(app: &tauri::AppHandle) {
+    let folders = app
+        .state::<AppState>()
+        .read_settings()
+        .watched_folders
+        .clone();
+    let Ok(mut current) = WATCHER.lock() else {
+        return;
+    };
+    *current = None;
+    if folders.is_empty() {
+        return;
+    }
+
+    let (tx, rx) = mpsc::channel::<PathBuf>();
+    let watcher =
+        notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+            Ok(event) => {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+            Err(e) => warn!("Folder watch error: {}", e),
+        });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            error!("Failed to create folder watcher: {}", e);
+            return;
+        }
+    };
+    for folder in &folders {
+        if let Err(e) = watcher.watch(Path::new(&folder.path), RecursiveMode::NonRecursive) {
+            warn!("Cannot watch {}: {}", folder.path, e);
+        }
+    }
+    *current = Some(watcher);
+    drop(current);
+
+    info!("Watching {} folder(s) for new sounds", folders.len());
+    let app = app.clone();
+    let spawned = std::thread::Builder::new()
+        .name("folder-watch".to_string())
+        .spawn(move || run(&app, &folders, &rx));
+    if let Err(e) = spawned {
+        error!("Failed to start folder watch thread: {}", e);
+    }
+}
+
+/// Sync loop of one watcher, ends when the watcher is replaced
+fn run(app: &tauri::AppHandle, folders: &[WatchedFolder], rx: &mpsc::Receiver<PathBuf>) {
+    for folder in folders {
+        sync_and_report(app, folder);
+    }
+
+    while let Ok(path) = rx.recv() {
+        let mut changed = vec![path];
+        // Wait until the folder is quiet
+        loop {
+            match rx.recv_timeout(SETTLE_DELAY) {
+                Ok(path) => changed.push(path),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        for folder in folders {
+            let dir = Path::new(&folder.path);
+            if changed.iter().any(|p| p.parent() == Some(dir) || p == dir) {
+                sync_and_report(app, folder);
+            }
+        }
+    }
+}
+
+fn sync_and_report(app: &tauri::AppHandle, folder: &WatchedFolder) {
+    match sync_folder(app, folder) {
+        Ok(Some(change)) => {
+            info!(
+                "Watched folder {}: {} added, {} removed, {} unreadable",
+                folder.path,
+                change.added.len(),
+                change.removed.len(),
+                change.failed.len()
+            );
+            if let Err(e) = app.emit("watched-folder-changed", &change) {
+                error!("Failed to emit watched folder event: {}", e);
+            }
+        }
+        Ok(None) => {}
+        // An unreachable folder (unplugged drive) keeps its sounds
+        Err(e) => warn!("Skipping sync of watched folder: {}", e),
+    }
+}
+
+/// Bring the library in line with the folder; None if nothing changed
+fn sync_folder(
+    app: &tauri::AppHandle,
+    folder: &WatchedFolder,
+) -> Result<Option<WatchedFolderChanged>, String> {
+    let dir = Path::new(&folder.path);
+    let present = audio_files_in(dir)?;
+    let state = app.state::<AppState>();
+    let candidates = plan_changes(&state.read_sounds(), dir, &present);
+    if candidates == FolderChanges::default() {
+        return Ok(None);
+    }
+
+    // Read headers before taking the library, decoding can take a while
+    let mut readable = Vec::new();
+    let mut failed = Vec::new();
+    for path in candidates.added {
+        let file_path = path.to_string_lossy().to_string();
+        match audio::read_metadata(&file_path) {
+            Ok(metadata) => readable.push((path, file_path, metadata)),
+            Err(e) => {
+                warn!("Cannot import {}: {}", file_path, e);
+                failed.push(file_path);
+            }
+        }
+    }
+
+    let mut library = state.read_sounds().clone();
+    // The library may have changed while headers were read
+    let changes = plan_changes(&library, dir, &present);
+    let category_exists = library
+        .categories
+        .iter()
+        .any(|c| c.id == folder.category_id);
+    if !category_exists && !readable.is_empty() {
+        warn!(
+            "Category of watched folder {} no longer exists, not adding files",
+            folder.path
+        );
+    }
+
+    let mut added = Vec::new();
+    for (path, file_path, metadata) in readable {
+        if !category_exists || !changes.added.contains(&path) {
+            continue;
+        }
+        let name = sounds::name_for_file(&path, metadata.title.as_deref());
+        sounds::add_sound(
+            &mut library,
+            name,
+            file_path,
+            folder.category_id.clone(),
+            None,
+            None,
+        );
+        // add_sound appends
+        if let Some(sound) = library.sounds.last_mut() {
+            sound.duration_ms = metadata.duration_ms;
+            added.push(sound.clone());
+        }
+    }
+
+    let removed = changes.removed;
+    if !removed.is_empty() {
+        library.sounds.retain(|s| !removed.contains(&s.id));
+        let mut mappings = state.read_hotkeys().clone();
+        for sound_id in &removed {
+            for hotkey in hotkeys::get_hotkeys_for_sound(&mappings, sound_id) {
+                hotkeys::remove_mapping(&mut mappings, &hotkey)?;
+            }
+        }
+        state.update_and_save_hotkeys(app, mappings)?;
+    }
+    if added.is_empty() && removed.is_empty() && failed.is_empty() {
+        return Ok(None);
+    }
+    if !added.is_empty() || !removed.is_empty() {
+        state.update_and_save_sounds(app, library)?;
+    }
+
+    Ok(Some(WatchedFolderChanged {
+        folder: folder.path.clone(),
+        added,
+        removed,
+        failed,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library_with(paths: &[&str]) -> SoundLibrary {
+        let mut library = SoundLibrary::default();
+        let category = library.categories[0].id.clone();
+        for path in paths {
+            sounds::add_sound(
+                &mut library,
+                path.to_string(),
+                path.to_string(),
+                category.clone(),
+                None,
+                None,
+            );
+        }
+        library
+    }
+
+    #[test]
+    fn test_is_audio_file() {
+        assert!(is_audio_file(Path::new("/in/horn.mp3")));
+        assert!(is_audio_file(Path::new("/in/HORN.FLAC")));
+        assert!(!is_audio_file(Path::new("/in/notes.txt")));
+        assert!(!is_audio_file(Path::new("/in/horn")));
+    }
+
+    #[test]
+    fn test_plan_changes_adds_new_and_removes_missing() {
+        let library = library_with(&["/in/kept.mp3", "/in/gone.mp3", "/elsewhere/b.mp3"]);
+        let present = vec![PathBuf::from("/in/kept.mp3"), PathBuf::from("/in/new.wav")];
+
+        let changes = plan_changes(&library, Path::new("/in"), &present);
+        assert_eq!(changes.added, vec![PathBuf::from("/in/new.wav")]);
+        assert_eq!(changes.removed, vec![library.sounds[1].id.clone()]);
+    }
+
+    #[test]
+    fn test_plan_changes_ignores_subfolders_and_known_files() {
+        // Sounds in a subfolder aren't watched (non-recursive)
+        let library = library_with(&["/in/sub/a.mp3", "/in/a.mp3"]);
+        let present = vec![PathBuf::from("/in/a.mp3")];
+
+        let changes = plan_changes(&library, Path::new("/in"), &present);
+        assert_eq!(changes, FolderChanges::default());
+    }
+
+    #[test]
+    fn test_audio_files_in_lists_only_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.mp3"), b"x").unwrap();
+        std::fs::write(dir.path().join("a.wav"), b"x").unwrap();
+        std::fs::write(dir.path().join("readme.txt"), b"x").unwrap();
+        std::fs::create_dir(dir.path().join("sub.mp3")).unwrap();
+
+        let files = audio_files_in(dir.path()).unwrap();
+        assert_eq!(
+            files,
+            vec![dir.path().join("a.wav"), dir.path().join("b.mp3")]
+        );
+    }
+}
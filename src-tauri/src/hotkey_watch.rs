@@ -0,0 +1,223 @@
+//! Hotkey registration recovery
+//!
+//! Windows can drop global hotkeys without telling the application holding
+//! them, e.g. when Explorer restarts, on fast user switching, when a remote
+//! session connects or when the machine resumes from sleep. The shortcut
+//! plugin keeps listing them as registered, so every mapped hotkey is
+//! unregistered and registered again whenever Windows announces one of those
+//! events (`TaskbarCreated`, a session change or resume).
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use tauri::Manager;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+use tracing::{error, info, warn};
+
+use crate::device_watch::next_burst;
+use crate::hotkeys::{self, ShortcutRegistry};
+use crate::AppState;
+
+/// Quiet time before re-registering; unlocking after a session switch also
+/// recreates the taskbar
+const SETTLE_DELAY: Duration = Duration::from_secs(1);
+
+/// The app's global shortcut plugin
+struct PluginRegistry<'a>(&'a tauri::AppHandle);
+
+impl ShortcutRegistry for PluginRegistry<'_> {
+    fn register(&self, hotkey: &str) -> Result<(), String> {
+        let shortcut: Shortcut = hotkey.parse().map_err(|e| format!("{}", e))?;
+        self.0
+            .global_shortcut()
+            .register(shortcut)
+            .map_err(|e| e.to_string())
+    }
+
+    fn unregister(&self, hotkey: &str) -> Result<(), String> {
+        let shortcut: Shortcut = hotkey.parse().map_err(|e| format!("{}", e))?;
+        self.0
+            .global_shortcut()
+            .unregister(shortcut)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Spawn the threads re-registering hotkeys after session changes
+///
+/// Does nothing on platforms without those notifications.
+pub fn spawn_watcher(app: tauri::AppHandle) {
+    if !cfg!(windows) {
+        return;
+    }
+    let (tx, rx) = mpsc::channel();
+    let listener = std::thread::Builder::new()
+        .name("hotkey-notifications".to_string())
+        .spawn(move || notifications::run(tx));
+    if let Err(e) = listener {
+        error!("Failed to start hotkey notification thread: {}", e);
+        return;
+    }
+    let spawned = std::thread::Builder::new()
+        .name("hotkey-watch".to_string())
+        .spawn(move || {
+            info!("Watching for session changes that drop hotkeys");
+            while let Some(events) = next_burst(&rx, SETTLE_DELAY) {
+                info!(events, "Session changed, re-registering hotkeys");
+                reregister(&app);
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start hotkey watcher thread: {}", e);
+    }
+}
+
+/// Register every mapped hotkey again
+pub fn reregister(app: &tauri::AppHandle) {
+    let hotkeys: Vec<String> = {
+        let state = app.state::<AppState>();
+        let mappings = state.read_hotkeys();
+        hotkeys::all_hotkeys(&mappings).collect()
+    };
+    let failed = hotkeys::reregister_all(&PluginRegistry(app), &hotkeys);
+    if !failed.is_empty() {
+        warn!(
+            "{} of {} hotkeys not re-registered",
+            failed.len(),
+            hotkeys.len()
+        );
+    }
+}
+
+#[cfg(windows)]
+mod notifications {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::mpsc::Sender;
+    use std::sync::{Mutex, OnceLock};
+
+    use tracing::{error, warn};
+    use windows::core::w;
+    use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::System::RemoteDesktop::{
+        WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW,
+        RegisterClassW, RegisterWindowMessageW, TranslateMessage, MSG, PBT_APMRESUMEAUTOMATIC,
+        WINDOW_EX_STYLE, WINDOW_STYLE, WM_POWERBROADCAST, WM_WTSSESSION_CHANGE, WNDCLASSW,
+        WTS_CONSOLE_CONNECT, WTS_REMOTE_CONNECT, WTS_SESSION_UNLOCK,
+    };
+
+    /// Watcher wake-ups; the window procedure has no other way to reach it
+    static SENDER: OnceLock<Mutex<Sender<()>>> = OnceLock::new();
+
+    /// Message ID Explorer broadcasts once it (re)created the taskbar
+    static TASKBAR_CREATED: AtomicU32 = AtomicU32::new(0);
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        let dropped_hotkeys = match message {
+            WM_WTSSESSION_CHANGE => matches!(
+                wparam.0 as u32,
+                WTS_CONSOLE_CONNECT | WTS_REMOTE_CONNECT | WTS_SESSION_UNLOCK
+            ),
+            WM_POWERBROADCAST => wparam.0 as u32 == PBT_APMRESUMEAUTOMATIC,
+            _ => message != 0 && message == TASKBAR_CREATED.load(Ordering::Relaxed),
+        };
+        if dropped_hotkeys {
+            if let Some(Ok(tx)) = SENDER.get().map(|tx| tx.lock()) {
+                let _ = tx.send(());
+            }
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    /// Receive the notifications on a hidden window, each sending `()` on
+    /// `tx`; runs the window's message loop until it is destroyed
+    pub fn run(tx: Sender<()>) {
+        if SENDER.set(Mutex::new(tx)).is_err() {
+            warn!("Hotkey notifications are already being received");
+            return;
+        }
+
+        // SAFETY: the window class and window are created, used and torn down
+        // on this thread, `msg` outlives every call reading or writing it, and
+        // the window procedure only touches the statics above
+        unsafe {
+            TASKBAR_CREATED.store(
+                RegisterWindowMessageW(w!("TaskbarCreated")),
+                Ordering::Relaxed,
+            );
+
+            let instance: HINSTANCE = match GetModuleHandleW(None) {
+                Ok(module) => module.into(),
+                Err(e) => {
+                    error!(
+                        "Failed to get module handle for hotkey notifications: {}",
+                        e
+                    );
+                    return;
+                }
+            };
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(window_proc),
+                hInstance: instance,
+                lpszClassName: w!("SonicDeckHotkeyWatch"),
+                ..Default::default()
+            };
+            if RegisterClassW(&class) == 0 {
+                error!("Failed to register hotkey notification window class");
+                return;
+            }
+
+            // A hidden top-level window: message-only windows don't receive
+            // the TaskbarCreated broadcast
+            let window = match CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                w!("SonicDeckHotkeyWatch"),
+                w!(""),
+                WINDOW_STYLE::default(),
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                Some(instance),
+                None,
+            ) {
+                Ok(window) => window,
+                Err(e) => {
+                    error!("Failed to create hotkey notification window: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = WTSRegisterSessionNotification(window, NOTIFY_FOR_THIS_SESSION) {
+                warn!("Failed to register for session notifications: {}", e);
+            }
+
+            let mut msg = MSG::default();
+            // GetMessageW returns -1 on failure and 0 once the window is gone
+            while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+
+            let _ = WTSUnRegisterSessionNotification(window);
+            let _ = DestroyWindow(window);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod notifications {
+    use std::sync::mpsc::Sender;
+
+    /// Session notifications are Windows-only
+    pub fn run(_tx: Sender<()>) {}
+}
@@ -3,28 +3,86 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::Manager;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
 
-use crate::SoundId;
+use crate::reload::DataFile;
+use crate::sounds::{Sound, SoundLibrary};
+use crate::{CategoryId, SoundId};
 
-/// Hotkey mappings: keyboard shortcut string -> sound ID
+/// Hotkey mappings: keyboard shortcut string -> sound ID or action
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HotkeyMappings {
     pub mappings: HashMap<String, SoundId>,
+    /// Hotkeys bound to app actions instead of sounds
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub actions: HashMap<String, HotkeyAction>,
+    /// Modifier layer mapping NumPad1-9 to the selected category's sounds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank: Option<HotkeyBank>,
+    /// Trigger mode of sound hotkeys (unlisted hotkeys use `Toggle`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub modes: HashMap<String, HotkeyMode>,
 }
 
-/// Get the path to the hotkeys file
-pub fn get_hotkeys_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app_handle
-        .path()
-        .app_local_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+/// How a sound hotkey reacts to being pressed and released
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyMode {
+    /// Pressing plays the sound (following its play policy), release is ignored
+    #[default]
+    Toggle,
+    /// Push-to-talk style: the sound plays while the key is held and stops on release
+    HoldToPlay,
+}
 
-    // Ensure directory exists
-    std::fs::create_dir_all(&app_data_dir)
-        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+/// Hotkey bank: `<modifiers>+NumPad1` .. `NumPad9` play the first nine sounds
+/// of the currently selected category, resolved when the key is pressed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyBank {
+    /// Canonical modifier combination, e.g. "Ctrl+Alt"
+    pub modifiers: String,
+}
 
-    Ok(app_data_dir.join("hotkeys.json"))
+/// Number of bank slots (NumPad1-NumPad9)
+pub const BANK_SLOTS: usize = 9;
+
+/// App action a hotkey can trigger instead of playing a sound
+///
+/// Playing a sound is a sound mapping (`HotkeyMappings::mappings`); the
+/// shortcut handler resolves those to `PlaySound` before dispatching.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    /// Toggle LUFS loudness normalization on/off
+    ToggleNormalization,
+    /// Open the quick sound search popup
+    OpenQuickSearch,
+    /// Play a sound (resolved from sound mappings, not stored as an action)
+    PlaySound(SoundId),
+    /// Stop every playing sound and clear the queue
+    StopAll,
+    /// Stop a sound if it is playing
+    StopSound(SoundId),
+    /// Turn microphone routing to VB-Cable on/off
+    ToggleMicRouting,
+    /// Raise the master volume by `VOLUME_STEP`
+    VolumeUp,
+    /// Lower the master volume by `VOLUME_STEP`
+    VolumeDown,
+    /// Lower every sound by this many dB while the hotkey is held
+    Duck(u32),
+}
+
+/// Master volume change of one `VolumeUp`/`VolumeDown` press
+pub const VOLUME_STEP: f32 = 0.05;
+
+/// Largest attenuation of a `Duck` hotkey
+pub const MAX_DUCK_DB: u32 = 60;
+
+/// Get the path to the active profile's hotkeys file
+pub fn get_hotkeys_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::profiles::data_dir(app_handle)?.join("hotkeys.json"))
 }
 
 /// Load hotkey mappings from disk
@@ -38,10 +96,17 @@ pub fn load(app_handle: &tauri::AppHandle) -> Result<HotkeyMappings, String> {
 
     let content = std::fs::read_to_string(&hotkeys_path)
         .map_err(|e| format!("Failed to read hotkeys file: {}", e))?;
+    crate::reload::remember(DataFile::Hotkeys, &content);
 
-    let mappings: HotkeyMappings =
+    let mut mappings: HotkeyMappings =
         serde_json::from_str(&content).map_err(|e| format!("Failed to parse hotkeys: {}", e))?;
 
+    // One-time migration: rewrite files saved before canonical normalization
+    if normalize_mappings(&mut mappings) {
+        tracing::info!("Migrated hotkey mappings to canonical format");
+        save(&mappings, app_handle)?;
+    }
+
     Ok(mappings)
 }
 
@@ -52,19 +117,25 @@ pub fn save(mappings: &HotkeyMappings, app_handle: &tauri::AppHandle) -> Result<
     let json = serde_json::to_string_pretty(mappings)
         .map_err(|e| format!("Failed to serialize hotkeys: {}", e))?;
 
-    crate::persistence::atomic_write(&hotkeys_path, &json)?;
+    // Don't overwrite edits made outside the app that weren't reloaded yet
+    crate::reload::check_unmodified(DataFile::Hotkeys, &hotkeys_path)?;
+    crate::persistence::atomic_write_with_backup(&hotkeys_path, &json)?;
+    crate::reload::remember(DataFile::Hotkeys, &json);
 
     tracing::debug!("Hotkey mappings saved to {:?}", hotkeys_path);
     Ok(())
 }
 
 /// Add a hotkey mapping (checks for duplicates)
+///
+/// The hotkey is stored in canonical form (see `normalize_hotkey_string`).
 pub fn add_mapping(
     mappings: &mut HotkeyMappings,
     hotkey: String,
     sound_id: SoundId,
 ) -> Result<(), String> {
-    if mappings.mappings.contains_key(&hotkey) {
+    let hotkey = normalize_hotkey_string(&hotkey);
+    if is_assigned(mappings, &hotkey) {
         return Err(format!("Hotkey '{}' is already assigned", hotkey));
     }
 
@@ -73,9 +144,36 @@ pub fn add_mapping(
     Ok(())
 }
 
-/// Remove a hotkey mapping
+/// Add a hotkey -> action mapping (checks for duplicates)
+pub fn add_action_mapping(
+    mappings: &mut HotkeyMappings,
+    hotkey: String,
+    action: HotkeyAction,
+) -> Result<(), String> {
+    let hotkey = normalize_hotkey_string(&hotkey);
+    match action {
+        HotkeyAction::PlaySound(_) => {
+            return Err("Sound hotkeys are added with add_mapping".to_string());
+        }
+        HotkeyAction::Duck(db) if db == 0 || db > MAX_DUCK_DB => {
+            return Err(format!("Duck amount must be 1-{} dB", MAX_DUCK_DB));
+        }
+        _ => {}
+    }
+    if is_assigned(mappings, &hotkey) {
+        return Err(format!("Hotkey '{}' is already assigned", hotkey));
+    }
+
+    tracing::info!("Added hotkey action: {} -> {:?}", hotkey, action);
+    mappings.actions.insert(hotkey, action);
+    Ok(())
+}
+
+/// Remove a hotkey mapping (sound or action)
 pub fn remove_mapping(mappings: &mut HotkeyMappings, hotkey: &str) -> Result<(), String> {
-    if mappings.mappings.remove(hotkey).is_some() {
+    let hotkey = normalize_hotkey_string(hotkey);
+    if mappings.mappings.remove(&hotkey).is_some() || mappings.actions.remove(&hotkey).is_some() {
+        mappings.modes.remove(&hotkey);
         tracing::info!("Removed hotkey mapping: {}", hotkey);
         Ok(())
     } else {
@@ -88,6 +186,136 @@ pub fn get_sound_id<'a>(mappings: &'a HotkeyMappings, hotkey: &str) -> Option<&'
     mappings.mappings.get(hotkey)
 }
 
+/// Get the action bound to a hotkey
+pub fn get_action(mappings: &HotkeyMappings, hotkey: &str) -> Option<HotkeyAction> {
+    mappings.actions.get(hotkey).cloned()
+}
+
+/// Get the trigger mode of a hotkey
+pub fn get_mode(mappings: &HotkeyMappings, hotkey: &str) -> HotkeyMode {
+    mappings.modes.get(hotkey).copied().unwrap_or_default()
+}
+
+/// Set the trigger mode of a hotkey bound to a sound or a bank slot
+pub fn set_mode(
+    mappings: &mut HotkeyMappings,
+    hotkey: &str,
+    mode: HotkeyMode,
+) -> Result<(), String> {
+    let hotkey = normalize_hotkey_string(hotkey);
+    if !mappings.mappings.contains_key(&hotkey) && bank_slot(mappings, &hotkey).is_none() {
+        return Err(format!("Hotkey '{}' is not bound to a sound", hotkey));
+    }
+
+    match mode {
+        HotkeyMode::Toggle => mappings.modes.remove(&hotkey),
+        _ => mappings.modes.insert(hotkey.clone(), mode),
+    };
+    tracing::info!("Hotkey mode: {} -> {:?}", hotkey, mode);
+    Ok(())
+}
+
+/// Check whether a (canonical) hotkey is bound to a sound, an action or a bank slot
+pub fn is_assigned(mappings: &HotkeyMappings, hotkey: &str) -> bool {
+    mappings.mappings.contains_key(hotkey)
+        || mappings.actions.contains_key(hotkey)
+        || bank_slot(mappings, hotkey).is_some()
+}
+
+/// All bound hotkeys (sounds, actions and bank slots), for registration with the plugin
+pub fn all_hotkeys(mappings: &HotkeyMappings) -> impl Iterator<Item = String> + '_ {
+    mappings
+        .mappings
+        .keys()
+        .chain(mappings.actions.keys())
+        .cloned()
+        .chain(mappings.bank.iter().flat_map(bank_hotkeys))
+}
+
+/// Where global shortcuts are registered (the shortcut plugin in the app)
+pub trait ShortcutRegistry {
+    fn register(&self, hotkey: &str) -> Result<(), String>;
+    fn unregister(&self, hotkey: &str) -> Result<(), String>;
+}
+
+/// Register every hotkey again from scratch
+///
+/// The shortcut plugin's `is_registered` only checks its own list, which
+/// still holds hotkeys Windows dropped, so each hotkey is unregistered first
+/// (failing there just means it was already gone) and registered anew.
+/// Returns the hotkeys that couldn't be registered again.
+pub fn reregister_all(registry: &impl ShortcutRegistry, hotkeys: &[String]) -> Vec<String> {
+    let mut failed = Vec::new();
+    for hotkey in hotkeys {
+        let _ = registry.unregister(hotkey);
+        if let Err(e) = registry.register(hotkey) {
+            tracing::error!("Failed to re-register hotkey '{}': {}", hotkey, e);
+            failed.push(hotkey.clone());
+        }
+    }
+    failed
+}
+
+// ============================================================================
+// Hotkey Banks
+// ============================================================================
+
+/// The nine hotkeys of a bank, slot order
+pub fn bank_hotkeys(bank: &HotkeyBank) -> Vec<String> {
+    (1..=BANK_SLOTS)
+        .map(|n| format!("{}+NumPad{}", bank.modifiers, n))
+        .collect()
+}
+
+/// Bank slot (0-based) a canonical hotkey triggers, if any
+pub fn bank_slot(mappings: &HotkeyMappings, hotkey: &str) -> Option<usize> {
+    let bank = mappings.bank.as_ref()?;
+    bank_hotkeys(bank).iter().position(|h| h == hotkey)
+}
+
+/// Enable the hotkey bank on a modifier combination
+///
+/// Fails if the combination contains a non-modifier key or any of its nine
+/// hotkeys is already bound to a sound or an action.
+pub fn set_bank(mappings: &mut HotkeyMappings, modifiers: &str) -> Result<HotkeyBank, String> {
+    let modifiers = normalize_hotkey_string(modifiers);
+    if modifiers.is_empty()
+        || !modifiers
+            .split('+')
+            .all(|part| MODIFIER_ORDER.contains(&part))
+    {
+        return Err(format!(
+            "Bank modifiers must be modifier keys only (e.g. Ctrl+Alt), got '{}'",
+            modifiers
+        ));
+    }
+
+    let bank = HotkeyBank { modifiers };
+    if let Some(taken) = bank_hotkeys(&bank)
+        .into_iter()
+        .find(|h| mappings.mappings.contains_key(h) || mappings.actions.contains_key(h))
+    {
+        return Err(format!("Hotkey '{}' is already assigned", taken));
+    }
+
+    mappings.bank = Some(bank.clone());
+    tracing::info!("Hotkey bank set to {}+NumPad1-9", bank.modifiers);
+    Ok(bank)
+}
+
+/// Sound in a bank slot: the n-th sound of the category in library order
+pub fn bank_sound<'a>(
+    library: &'a SoundLibrary,
+    category_id: &CategoryId,
+    slot: usize,
+) -> Option<&'a Sound> {
+    library
+        .sounds
+        .iter()
+        .filter(|sound| &sound.category_id == category_id)
+        .nth(slot)
+}
+
 /// Get all hotkeys assigned to a specific sound
 pub fn get_hotkeys_for_sound(mappings: &HotkeyMappings, sound_id: &SoundId) -> Vec<String> {
     mappings
@@ -98,6 +326,308 @@ pub fn get_hotkeys_for_sound(mappings: &HotkeyMappings, sound_id: &SoundId) -> V
         .collect()
 }
 
+// ============================================================================
+// Normalization
+// ============================================================================
+
+/// Canonical modifier order in stored hotkey strings
+const MODIFIER_ORDER: [&str; 4] = ["Ctrl", "Shift", "Alt", "Super"];
+
+/// Normalize a hotkey string to the canonical storage format
+///
+/// Accepts both plugin output ("shift+control+KeyA") and UI strings
+/// ("Ctrl+Shift+A"). Modifiers are deduplicated and sorted into a fixed
+/// order so equivalent combinations always produce the same string.
+pub fn normalize_hotkey_string(hotkey: &str) -> String {
+    let mut modifiers: Vec<String> = Vec::new();
+    let mut keys: Vec<String> = Vec::new();
+
+    for part in hotkey.split('+') {
+        let token = normalize_hotkey_part(part);
+        if token.is_empty() {
+            continue;
+        }
+        if MODIFIER_ORDER.contains(&token.as_str()) {
+            if !modifiers.contains(&token) {
+                modifiers.push(token);
+            }
+        } else {
+            keys.push(token);
+        }
+    }
+
+    modifiers.sort_by_key(|m| MODIFIER_ORDER.iter().position(|o| o == m));
+    modifiers.extend(keys);
+    modifiers.join("+")
+}
+
+/// Normalize a single `+`-separated hotkey component
+fn normalize_hotkey_part(part: &str) -> String {
+    let trimmed = part.trim().to_lowercase();
+
+    match trimmed.as_str() {
+        // Handle modifier keys and their aliases
+        "control" | "ctrl" | "commandorcontrol" | "commandorctrl" | "cmdorctrl"
+        | "cmdorcontrol" => "Ctrl".to_string(),
+        "alt" | "option" => "Alt".to_string(),
+        "shift" => "Shift".to_string(),
+        "meta" | "super" | "cmd" | "command" | "win" => "Super".to_string(),
+        // Handle NumPad keys: numpad0 -> NumPad0, numpadadd -> NumPadAdd
+        key if key.starts_with("numpad") => {
+            format!("NumPad{}", capitalize_first(&key[6..]))
+        }
+        // Handle Digit keys: digit0 -> 0, digit1 -> 1, etc.
+        key if key.starts_with("digit") => key[5..].to_string(),
+        // Handle Key prefix: keya -> A, keyb -> B, etc.
+        key if key.starts_with("key") => key[3..].to_uppercase(),
+        // Capitalize first letter for all other keys
+        other => capitalize_first(other),
+    }
+}
+
+/// Capitalize the first letter of a string
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+/// Rewrite all mapping keys into canonical form
+///
+/// Returns true if anything changed. When two keys collapse onto the same
+/// canonical hotkey, the one already in canonical form wins.
+pub fn normalize_mappings(mappings: &mut HotkeyMappings) -> bool {
+    let sounds_changed = normalize_keys(&mut mappings.mappings);
+    let actions_changed = normalize_keys(&mut mappings.actions);
+    let modes_changed = normalize_keys(&mut mappings.modes);
+    let bank_changed = match mappings.bank.as_mut() {
+        Some(bank) => {
+            let canonical = normalize_hotkey_string(&bank.modifiers);
+            let changed = canonical != bank.modifiers;
+            bank.modifiers = canonical;
+            changed
+        }
+        None => false,
+    };
+
+    // A hotkey bound to both a sound and an action keeps the sound
+    let before = mappings.actions.len();
+    let sounds = &mut mappings.mappings;
+    // Sounds stored as `play_sound` actions become sound mappings
+    mappings.actions.retain(|hotkey, action| match action {
+        HotkeyAction::PlaySound(sound_id) => {
+            sounds
+                .entry(hotkey.clone())
+                .or_insert_with(|| sound_id.clone());
+            false
+        }
+        _ => true,
+    });
+    mappings
+        .actions
+        .retain(|hotkey, _| !sounds.contains_key(hotkey));
+
+    // Modes only apply to hotkeys that play sounds
+    let modes_before = mappings.modes.len();
+    let modes = std::mem::take(&mut mappings.modes);
+    mappings.modes = modes
+        .into_iter()
+        .filter(|(hotkey, _)| {
+            mappings.mappings.contains_key(hotkey) || bank_slot(mappings, hotkey).is_some()
+        })
+        .collect();
+
+    sounds_changed
+        || actions_changed
+        || modes_changed
+        || bank_changed
+        || mappings.actions.len() != before
+        || mappings.modes.len() != modes_before
+}
+
+/// Rewrite the keys of one hotkey map into canonical form
+fn normalize_keys<V: Clone>(map: &mut HashMap<String, V>) -> bool {
+    let mut keys: Vec<String> = map.keys().cloned().collect();
+    keys.sort_by_key(|k| (normalize_hotkey_string(k) != *k, k.clone()));
+
+    let mut changed = false;
+    let mut normalized = HashMap::with_capacity(keys.len());
+
+    for key in keys {
+        let value = map[&key].clone();
+        let canonical = normalize_hotkey_string(&key);
+
+        if canonical != key {
+            changed = true;
+        }
+        if normalized.contains_key(&canonical) {
+            tracing::warn!(
+                "Dropping duplicate hotkey mapping '{}' (normalizes to '{}')",
+                key,
+                canonical
+            );
+            changed = true;
+            continue;
+        }
+        normalized.insert(canonical, value);
+    }
+
+    *map = normalized;
+    changed
+}
+
+// ============================================================================
+// Hotkey Capture
+// ============================================================================
+
+/// Modifier combinations registered while capturing a hotkey
+///
+/// Shift alone isn't one of them: Shift with a letter or digit is how capitals
+/// and symbols are typed, which the registration would swallow in every other
+/// application while the capture runs (see `capture_candidates`).
+const CAPTURE_MODIFIERS: [&str; 6] = [
+    "Ctrl+",
+    "Alt+",
+    "Ctrl+Shift+",
+    "Ctrl+Alt+",
+    "Alt+Shift+",
+    "Ctrl+Alt+Shift+",
+];
+
+/// Pending "press a key to bind" request
+///
+/// While a capture is active, the global shortcut handler delivers the next
+/// pressed (normalized) hotkey here instead of triggering its mapping.
+#[derive(Default)]
+pub struct HotkeyCapture {
+    sender: Mutex<Option<Sender<String>>>,
+}
+
+impl HotkeyCapture {
+    /// Start a capture, returning the receiver for the next hotkey
+    pub fn begin(&self) -> Result<Receiver<String>, String> {
+        let mut sender = self.sender.lock().unwrap();
+        if sender.is_some() {
+            return Err("Hotkey capture already in progress".to_string());
+        }
+        let (tx, rx) = mpsc::channel();
+        *sender = Some(tx);
+        Ok(rx)
+    }
+
+    /// End the current capture (no-op if none is active); a waiting receiver
+    /// sees the capture cancelled
+    pub fn end(&self) {
+        self.sender.lock().unwrap().take();
+    }
+
+    /// Whether a capture is currently waiting for input
+    pub fn is_active(&self) -> bool {
+        self.sender.lock().unwrap().is_some()
+    }
+
+    /// Deliver a pressed hotkey to the active capture
+    ///
+    /// Returns true if a capture consumed the hotkey.
+    pub fn deliver(&self, hotkey: &str) -> bool {
+        match self.sender.lock().unwrap().take() {
+            Some(tx) => tx.send(hotkey.to_string()).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// Shortcut strings registered as a broad listener during capture
+///
+/// Covers function keys, letters, digits and numpad keys combined with every
+/// modifier set. Only function keys are also registered on their own and with
+/// Shift: bare numpad keys, letters and digits (or shifted ones) would swallow
+/// typing in every other application while the capture runs. The capture is
+/// cancelled from the app (`cancel_hotkey_capture`), not with a global key.
+pub fn capture_candidates() -> Vec<String> {
+    let function_keys: Vec<String> = (1..=24).map(|n| format!("F{}", n)).collect();
+    let mut keys = function_keys.clone();
+    keys.extend((0..=9).map(|n| format!("Numpad{}", n)));
+    keys.extend(
+        [
+            "NumpadAdd",
+            "NumpadSubtract",
+            "NumpadMultiply",
+            "NumpadDivide",
+            "NumpadDecimal",
+            "NumpadEnter",
+        ]
+        .iter()
+        .map(|k| k.to_string()),
+    );
+    keys.extend(('A'..='Z').map(|c| format!("Key{}", c)));
+    keys.extend((0..=9).map(|n| format!("Digit{}", n)));
+
+    let mut candidates = function_keys.clone();
+    candidates.extend(function_keys.iter().map(|key| format!("Shift+{}", key)));
+    candidates.extend(
+        CAPTURE_MODIFIERS
+            .iter()
+            .flat_map(|modifiers| keys.iter().map(move |key| format!("{}{}", modifiers, key))),
+    );
+    candidates
+}
+
+// ============================================================================
+// Hold To Play
+// ============================================================================
+
+/// Hotkeys that act while held down: hold-to-play playbacks and ducks
+///
+/// The key's release stops the playback recorded here or lifts the duck.
+/// Holding a key can repeat its press events, so a held hotkey doesn't start
+/// another playback.
+#[derive(Default)]
+pub struct HeldHotkeys {
+    playbacks: Mutex<HashMap<String, String>>,
+    /// Held duck hotkey -> its attenuation in dB
+    ducks: Mutex<HashMap<String, u32>>,
+}
+
+impl HeldHotkeys {
+    /// Record the playback a hold-to-play hotkey started
+    pub fn hold(&self, hotkey: &str, playback_id: String) {
+        self.playbacks
+            .lock()
+            .unwrap()
+            .insert(hotkey.to_string(), playback_id);
+    }
+
+    /// Whether the hotkey is currently held
+    pub fn is_held(&self, hotkey: &str) -> bool {
+        self.playbacks.lock().unwrap().contains_key(hotkey)
+            || self.ducks.lock().unwrap().contains_key(hotkey)
+    }
+
+    /// Forget a released hotkey, returning the playback to stop
+    pub fn release(&self, hotkey: &str) -> Option<String> {
+        self.playbacks.lock().unwrap().remove(hotkey)
+    }
+
+    /// Record a pressed duck hotkey, returning the attenuation to apply
+    /// (the strongest of all held ducks)
+    pub fn hold_duck(&self, hotkey: &str, db: u32) -> u32 {
+        let mut ducks = self.ducks.lock().unwrap();
+        ducks.insert(hotkey.to_string(), db);
+        ducks.values().copied().max().unwrap_or(0)
+    }
+
+    /// Forget a released duck hotkey, returning the attenuation still held
+    /// (0 once no duck is held); `None` if the hotkey wasn't ducking
+    pub fn release_duck(&self, hotkey: &str) -> Option<u32> {
+        let mut ducks = self.ducks.lock().unwrap();
+        ducks.remove(hotkey)?;
+        Some(ducks.values().copied().max().unwrap_or(0))
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -105,6 +635,8 @@ pub fn get_hotkeys_for_sound(mappings: &HotkeyMappings, sound_id: &SoundId) -> V
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
 
     // Helper to create a test SoundId
     fn test_sound_id(id: &str) -> SoundId {
@@ -122,6 +654,120 @@ mod tests {
         assert!(mappings.mappings.is_empty());
     }
 
+    // -------------------------------------------------------------------------
+    // normalize_hotkey_string Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_normalize_plugin_format() {
+        assert_eq!(normalize_hotkey_string("control+KeyA"), "Ctrl+A");
+        assert_eq!(normalize_hotkey_string("alt+Digit5"), "Alt+5");
+        assert_eq!(normalize_hotkey_string("control+Numpad8"), "Ctrl+NumPad8");
+        assert_eq!(
+            normalize_hotkey_string("shift+NumpadAdd"),
+            "Shift+NumPadAdd"
+        );
+        assert_eq!(normalize_hotkey_string("meta+F5"), "Super+F5");
+    }
+
+    #[test]
+    fn test_normalize_modifier_order() {
+        let expected = "Ctrl+Shift+Alt+F1";
+        assert_eq!(normalize_hotkey_string("shift+control+alt+F1"), expected);
+        assert_eq!(normalize_hotkey_string("Alt+Shift+Ctrl+F1"), expected);
+        assert_eq!(normalize_hotkey_string("Ctrl+Shift+Alt+F1"), expected);
+    }
+
+    #[test]
+    fn test_normalize_case_and_whitespace() {
+        assert_eq!(normalize_hotkey_string("CTRL + a"), "Ctrl+A");
+        assert_eq!(normalize_hotkey_string("ctrl+numpad1"), "Ctrl+NumPad1");
+    }
+
+    #[test]
+    fn test_normalize_aliases_and_duplicates() {
+        assert_eq!(normalize_hotkey_string("CmdOrCtrl+Option+X"), "Ctrl+Alt+X");
+        assert_eq!(normalize_hotkey_string("Ctrl+Control+X"), "Ctrl+X");
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() {
+        for hotkey in [
+            "shift+control+KeyQ",
+            "Alt+NumPadEnter",
+            "F13",
+            "Super+Space",
+        ] {
+            let once = normalize_hotkey_string(hotkey);
+            assert_eq!(normalize_hotkey_string(&once), once);
+        }
+    }
+
+    #[test]
+    fn test_normalize_mappings_migrates_keys() {
+        let mut mappings = HotkeyMappings::default();
+        mappings
+            .mappings
+            .insert("Shift+Ctrl+a".to_string(), test_sound_id("sound-1"));
+        mappings
+            .mappings
+            .insert("Alt+F2".to_string(), test_sound_id("sound-2"));
+
+        assert!(normalize_mappings(&mut mappings));
+        assert_eq!(
+            mappings.mappings.get("Ctrl+Shift+A"),
+            Some(&test_sound_id("sound-1"))
+        );
+        assert_eq!(
+            mappings.mappings.get("Alt+F2"),
+            Some(&test_sound_id("sound-2"))
+        );
+
+        // Second pass is a no-op
+        assert!(!normalize_mappings(&mut mappings));
+    }
+
+    #[test]
+    fn test_normalize_mappings_prefers_canonical_on_collision() {
+        let mut mappings = HotkeyMappings::default();
+        mappings
+            .mappings
+            .insert("Ctrl+Shift+A".to_string(), test_sound_id("canonical"));
+        mappings
+            .mappings
+            .insert("shift+control+KeyA".to_string(), test_sound_id("legacy"));
+
+        assert!(normalize_mappings(&mut mappings));
+        assert_eq!(mappings.mappings.len(), 1);
+        assert_eq!(
+            mappings.mappings.get("Ctrl+Shift+A"),
+            Some(&test_sound_id("canonical"))
+        );
+    }
+
+    #[test]
+    fn test_normalize_mappings_includes_actions() {
+        let mut mappings = HotkeyMappings::default();
+        mappings
+            .actions
+            .insert("alt+KeyN".to_string(), HotkeyAction::ToggleNormalization);
+        mappings
+            .mappings
+            .insert("Ctrl+F1".to_string(), test_sound_id("sound-1"));
+        mappings
+            .actions
+            .insert("control+F1".to_string(), HotkeyAction::ToggleNormalization);
+
+        assert!(normalize_mappings(&mut mappings));
+        assert_eq!(
+            get_action(&mappings, "Alt+N"),
+            Some(HotkeyAction::ToggleNormalization)
+        );
+        // Sound mapping wins over an action on the same hotkey
+        assert_eq!(get_action(&mappings, "Ctrl+F1"), None);
+        assert_eq!(mappings.mappings.len(), 1);
+    }
+
     // -------------------------------------------------------------------------
     // add_mapping Tests
     // -------------------------------------------------------------------------
@@ -163,6 +809,24 @@ mod tests {
         assert!(result.unwrap_err().contains("already assigned"));
     }
 
+    #[test]
+    fn test_add_mapping_normalizes_hotkey() {
+        let mut mappings = HotkeyMappings::default();
+        let sound = test_sound_id("sound-1");
+
+        add_mapping(
+            &mut mappings,
+            "shift+control+KeyA".to_string(),
+            sound.clone(),
+        )
+        .unwrap();
+        assert_eq!(mappings.mappings.get("Ctrl+Shift+A"), Some(&sound));
+
+        // Same combination in a different order is a duplicate
+        let result = add_mapping(&mut mappings, "Shift+Ctrl+A".to_string(), sound);
+        assert!(result.unwrap_err().contains("already assigned"));
+    }
+
     #[test]
     fn test_add_mapping_same_sound_different_hotkeys() {
         let mut mappings = HotkeyMappings::default();
@@ -203,6 +867,15 @@ mod tests {
         assert!(result.unwrap_err().contains("not found"));
     }
 
+    #[test]
+    fn test_remove_mapping_normalizes_hotkey() {
+        let mut mappings = HotkeyMappings::default();
+        add_mapping(&mut mappings, "Ctrl+Alt+F1".to_string(), test_sound_id("s")).unwrap();
+
+        assert!(remove_mapping(&mut mappings, "alt+control+F1").is_ok());
+        assert!(mappings.mappings.is_empty());
+    }
+
     #[test]
     fn test_remove_mapping_preserves_others() {
         let mut mappings = HotkeyMappings::default();
@@ -303,6 +976,385 @@ mod tests {
         assert!(!hotkeys.contains(&"Ctrl+B".to_string()));
     }
 
+    // -------------------------------------------------------------------------
+    // HotkeyCapture Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_capture_delivers_hotkey() {
+        let capture = HotkeyCapture::default();
+        let rx = capture.begin().unwrap();
+
+        assert!(capture.is_active());
+        assert!(capture.deliver("Ctrl+A"));
+        assert_eq!(rx.recv().unwrap(), "Ctrl+A");
+        assert!(!capture.is_active());
+    }
+
+    #[test]
+    fn test_capture_deliver_without_capture() {
+        let capture = HotkeyCapture::default();
+        assert!(!capture.deliver("Ctrl+A"));
+    }
+
+    /// Shortcut plugin stand-in: `listed` is its own bookkeeping, `active`
+    /// what Windows actually has registered
+    #[derive(Default)]
+    struct FakeRegistry {
+        listed: RefCell<HashSet<String>>,
+        active: RefCell<HashSet<String>>,
+        /// Hotkeys another application holds
+        taken: HashSet<String>,
+    }
+
+    impl ShortcutRegistry for FakeRegistry {
+        fn register(&self, hotkey: &str) -> Result<(), String> {
+            if self.taken.contains(hotkey) || !self.active.borrow_mut().insert(hotkey.into()) {
+                return Err("already registered".to_string());
+            }
+            self.listed.borrow_mut().insert(hotkey.to_string());
+            Ok(())
+        }
+
+        fn unregister(&self, hotkey: &str) -> Result<(), String> {
+            self.listed.borrow_mut().remove(hotkey);
+            if self.active.borrow_mut().remove(hotkey) {
+                Ok(())
+            } else {
+                Err("not registered".to_string())
+            }
+        }
+    }
+
+    #[test]
+    fn test_reregister_all_recovers_dropped_hotkeys() {
+        let registry = FakeRegistry::default();
+        let hotkeys = vec!["Ctrl+F1".to_string(), "Ctrl+F2".to_string()];
+        for hotkey in &hotkeys {
+            registry.register(hotkey).unwrap();
+        }
+
+        // Windows drops one while the plugin still lists it
+        registry.active.borrow_mut().remove("Ctrl+F1");
+        assert!(registry.listed.borrow().contains("Ctrl+F1"));
+
+        assert!(reregister_all(&registry, &hotkeys).is_empty());
+        assert!(registry.active.borrow().contains("Ctrl+F1"));
+        assert!(registry.active.borrow().contains("Ctrl+F2"));
+    }
+
+    #[test]
+    fn test_reregister_all_reports_failures() {
+        let registry = FakeRegistry {
+            taken: HashSet::from(["Ctrl+F3".to_string()]),
+            ..Default::default()
+        };
+        let hotkeys = vec!["Ctrl+F1".to_string(), "Ctrl+F3".to_string()];
+
+        assert_eq!(reregister_all(&registry, &hotkeys), vec!["Ctrl+F3"]);
+        assert!(registry.active.borrow().contains("Ctrl+F1"));
+    }
+
+    #[test]
+    fn test_capture_end_cancels_waiting_receiver() {
+        let capture = HotkeyCapture::default();
+        let rx = capture.begin().unwrap();
+
+        capture.end();
+        assert_eq!(
+            rx.recv_timeout(std::time::Duration::from_millis(10)),
+            Err(mpsc::RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn test_capture_rejects_concurrent_begin() {
+        let capture = HotkeyCapture::default();
+        let _rx = capture.begin().unwrap();
+        assert!(capture.begin().is_err());
+
+        capture.end();
+        assert!(capture.begin().is_ok());
+    }
+
+    #[test]
+    fn test_capture_candidates() {
+        let candidates = capture_candidates();
+
+        assert!(candidates.contains(&"Ctrl+KeyA".to_string()));
+        assert!(candidates.contains(&"Ctrl+Alt+Shift+F24".to_string()));
+        assert!(candidates.contains(&"Ctrl+Numpad5".to_string()));
+        assert!(candidates.contains(&"F13".to_string()));
+        assert!(candidates.contains(&"Shift+F13".to_string()));
+        // Bare or shifted typing keys would block typing elsewhere during
+        // capture, and so would a global cancel key
+        for typing in [
+            "KeyA",
+            "Digit1",
+            "Numpad5",
+            "Shift+KeyA",
+            "Shift+Digit1",
+            "Escape",
+        ] {
+            assert!(!candidates.contains(&typing.to_string()), "{}", typing);
+        }
+
+        let unique: std::collections::HashSet<_> = candidates.iter().collect();
+        assert_eq!(unique.len(), candidates.len());
+    }
+
+    // -------------------------------------------------------------------------
+    // Action Mapping Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_add_action_mapping() {
+        let mut mappings = HotkeyMappings::default();
+
+        add_action_mapping(
+            &mut mappings,
+            "shift+control+KeyN".to_string(),
+            HotkeyAction::ToggleNormalization,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_action(&mappings, "Ctrl+Shift+N"),
+            Some(HotkeyAction::ToggleNormalization)
+        );
+        assert!(is_assigned(&mappings, "Ctrl+Shift+N"));
+    }
+
+    #[test]
+    fn test_action_and_sound_share_hotkey_namespace() {
+        let mut mappings = HotkeyMappings::default();
+        add_mapping(&mut mappings, "Ctrl+A".to_string(), test_sound_id("s")).unwrap();
+
+        let result = add_action_mapping(
+            &mut mappings,
+            "Ctrl+A".to_string(),
+            HotkeyAction::ToggleNormalization,
+        );
+        assert!(result.is_err());
+
+        add_action_mapping(
+            &mut mappings,
+            "Ctrl+B".to_string(),
+            HotkeyAction::ToggleNormalization,
+        )
+        .unwrap();
+        let result = add_mapping(&mut mappings, "Ctrl+B".to_string(), test_sound_id("s"));
+        assert!(result.is_err());
+        assert_eq!(all_hotkeys(&mappings).count(), 2);
+    }
+
+    #[test]
+    fn test_remove_mapping_removes_action() {
+        let mut mappings = HotkeyMappings::default();
+        add_action_mapping(
+            &mut mappings,
+            "Ctrl+N".to_string(),
+            HotkeyAction::ToggleNormalization,
+        )
+        .unwrap();
+
+        assert!(remove_mapping(&mut mappings, "Ctrl+N").is_ok());
+        assert!(mappings.actions.is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // Hotkey Bank Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_set_bank_normalizes_modifiers() {
+        let mut mappings = HotkeyMappings::default();
+        let bank = set_bank(&mut mappings, "alt+control").unwrap();
+
+        assert_eq!(bank.modifiers, "Ctrl+Alt");
+        assert_eq!(bank_slot(&mappings, "Ctrl+Alt+NumPad1"), Some(0));
+        assert_eq!(bank_slot(&mappings, "Ctrl+Alt+NumPad9"), Some(8));
+        assert_eq!(bank_slot(&mappings, "Ctrl+Alt+NumPad0"), None);
+        assert!(is_assigned(&mappings, "Ctrl+Alt+NumPad5"));
+        assert_eq!(all_hotkeys(&mappings).count(), BANK_SLOTS);
+    }
+
+    #[test]
+    fn test_set_bank_rejects_keys_and_conflicts() {
+        let mut mappings = HotkeyMappings::default();
+        assert!(set_bank(&mut mappings, "Ctrl+A").is_err());
+        assert!(set_bank(&mut mappings, "").is_err());
+
+        add_mapping(
+            &mut mappings,
+            "Ctrl+Alt+NumPad3".to_string(),
+            test_sound_id("s"),
+        )
+        .unwrap();
+        assert!(set_bank(&mut mappings, "Ctrl+Alt").is_err());
+        assert!(mappings.bank.is_none());
+
+        // Bank slots block explicit bindings too
+        set_bank(&mut mappings, "Shift+Alt").unwrap();
+        let result = add_mapping(
+            &mut mappings,
+            "Shift+Alt+NumPad1".to_string(),
+            test_sound_id("s"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_bank_sound_uses_library_order() {
+        let mut library = SoundLibrary::default();
+        let category = CategoryId::from_string("cat".to_string());
+        let other = CategoryId::from_string("other".to_string());
+        for (name, category_id) in [("a", &category), ("x", &other), ("b", &category)] {
+            crate::sounds::add_sound(
+                &mut library,
+                name.to_string(),
+                format!("{}.mp3", name),
+                category_id.clone(),
+                None,
+                None,
+            );
+        }
+
+        let name = |slot| bank_sound(&library, &category, slot).map(|s| s.name.as_str());
+        assert_eq!(name(0), Some("a"));
+        assert_eq!(name(1), Some("b"));
+        assert_eq!(name(2), None);
+    }
+
+    #[test]
+    fn test_normalize_mappings_moves_play_sound_actions() {
+        let mut mappings = HotkeyMappings::default();
+        mappings.actions.insert(
+            "Ctrl+1".to_string(),
+            HotkeyAction::PlaySound(test_sound_id("s")),
+        );
+        mappings
+            .actions
+            .insert("Ctrl+2".to_string(), HotkeyAction::StopAll);
+
+        assert!(normalize_mappings(&mut mappings));
+        assert_eq!(get_sound_id(&mappings, "Ctrl+1"), Some(&test_sound_id("s")));
+        assert_eq!(get_action(&mappings, "Ctrl+1"), None);
+        assert_eq!(get_action(&mappings, "Ctrl+2"), Some(HotkeyAction::StopAll));
+    }
+
+    #[test]
+    fn test_action_serde_format() {
+        // Unit actions stay plain strings, actions with a sound carry its ID
+        assert_eq!(
+            serde_json::to_string(&HotkeyAction::VolumeUp).unwrap(),
+            "\"volume_up\""
+        );
+        let stop: HotkeyAction = serde_json::from_str(r#"{"stop_sound":"s"}"#).unwrap();
+        assert_eq!(stop, HotkeyAction::StopSound(test_sound_id("s")));
+
+        let mut mappings = HotkeyMappings::default();
+        let play = HotkeyAction::PlaySound(test_sound_id("s"));
+        assert!(add_action_mapping(&mut mappings, "Ctrl+P".to_string(), play).is_err());
+    }
+
+    #[test]
+    fn test_normalize_mappings_normalizes_bank() {
+        let mut mappings = HotkeyMappings {
+            bank: Some(HotkeyBank {
+                modifiers: "alt+control".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(normalize_mappings(&mut mappings));
+        assert_eq!(mappings.bank.unwrap().modifiers, "Ctrl+Alt");
+    }
+
+    // -------------------------------------------------------------------------
+    // Hotkey Mode Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_set_mode_only_for_sound_hotkeys() {
+        let mut mappings = HotkeyMappings::default();
+        add_mapping(&mut mappings, "Ctrl+A".to_string(), test_sound_id("s")).unwrap();
+        add_action_mapping(
+            &mut mappings,
+            "Ctrl+B".to_string(),
+            HotkeyAction::OpenQuickSearch,
+        )
+        .unwrap();
+
+        assert_eq!(get_mode(&mappings, "Ctrl+A"), HotkeyMode::Toggle);
+        set_mode(&mut mappings, "control+KeyA", HotkeyMode::HoldToPlay).unwrap();
+        assert_eq!(get_mode(&mappings, "Ctrl+A"), HotkeyMode::HoldToPlay);
+
+        assert!(set_mode(&mut mappings, "Ctrl+B", HotkeyMode::HoldToPlay).is_err());
+        assert!(set_mode(&mut mappings, "Ctrl+C", HotkeyMode::HoldToPlay).is_err());
+
+        // Toggle is the default and isn't stored
+        set_mode(&mut mappings, "Ctrl+A", HotkeyMode::Toggle).unwrap();
+        assert!(mappings.modes.is_empty());
+    }
+
+    #[test]
+    fn test_mode_follows_its_mapping() {
+        let mut mappings = HotkeyMappings::default();
+        add_mapping(&mut mappings, "Ctrl+A".to_string(), test_sound_id("s")).unwrap();
+        set_mode(&mut mappings, "Ctrl+A", HotkeyMode::HoldToPlay).unwrap();
+
+        remove_mapping(&mut mappings, "Ctrl+A").unwrap();
+        assert!(mappings.modes.is_empty());
+
+        // Stale modes of unbound hotkeys are dropped on load
+        mappings
+            .modes
+            .insert("Ctrl+Z".to_string(), HotkeyMode::HoldToPlay);
+        assert!(normalize_mappings(&mut mappings));
+        assert!(mappings.modes.is_empty());
+    }
+
+    #[test]
+    fn test_held_hotkeys_release_once() {
+        let held = HeldHotkeys::default();
+        held.hold("Ctrl+A", "playback-1".to_string());
+
+        assert!(held.is_held("Ctrl+A"));
+        assert_eq!(held.release("Ctrl+A"), Some("playback-1".to_string()));
+        assert!(!held.is_held("Ctrl+A"));
+        assert_eq!(held.release("Ctrl+A"), None);
+    }
+
+    #[test]
+    fn test_held_ducks_apply_the_strongest() {
+        let held = HeldHotkeys::default();
+        assert_eq!(held.hold_duck("Ctrl+1", 6), 6);
+        assert_eq!(held.hold_duck("Ctrl+2", 12), 12);
+        assert!(held.is_held("Ctrl+1"));
+
+        assert_eq!(held.release_duck("Ctrl+2"), Some(6));
+        assert_eq!(held.release_duck("Ctrl+1"), Some(0));
+        assert_eq!(held.release_duck("Ctrl+1"), None);
+        assert!(!held.is_held("Ctrl+1"));
+    }
+
+    #[test]
+    fn test_add_action_mapping_validates_duck_amount() {
+        let mut mappings = HotkeyMappings::default();
+        assert!(
+            add_action_mapping(&mut mappings, "Ctrl+1".to_string(), HotkeyAction::Duck(0)).is_err()
+        );
+        assert!(add_action_mapping(
+            &mut mappings,
+            "Ctrl+1".to_string(),
+            HotkeyAction::Duck(MAX_DUCK_DB + 1)
+        )
+        .is_err());
+        assert!(
+            add_action_mapping(&mut mappings, "Ctrl+1".to_string(), HotkeyAction::Duck(12)).is_ok()
+        );
+    }
+
     // -------------------------------------------------------------------------
     // Serialization Tests
     // -------------------------------------------------------------------------
@@ -330,4 +1382,18 @@ mod tests {
 
         assert!(deserialized.mappings.is_empty());
     }
+
+    #[test]
+    fn test_hotkey_mappings_without_actions_field() {
+        // Files written before hotkey actions existed
+        let json = r#"{"mappings":{"Ctrl+A":"sound-1"}}"#;
+        let deserialized: HotkeyMappings = serde_json::from_str(json).unwrap();
+
+        assert_eq!(deserialized.mappings.len(), 1);
+        assert!(deserialized.actions.is_empty());
+
+        // Empty action maps are not written back
+        let json = serde_json::to_string(&deserialized).unwrap();
+        assert!(!json.contains("actions"));
+    }
 }
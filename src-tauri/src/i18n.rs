@@ -0,0 +1,239 @@
+//! Localized backend strings (tray menu, command errors, notifications)
+//!
+//! Messages live in embedded key -> text tables, one per language. A missing
+//! translation falls back to English, a missing English entry to the key.
+//! Placeholders use `{name}` and are filled by [`tf`].
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// UI language for backend strings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Language {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Language::English => 0,
+            Language::German => 1,
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => EN,
+            Language::German => DE,
+        }
+    }
+}
+
+/// Active language (mirrors `AppSettings::language`)
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Set the active language (called when settings load or change)
+pub fn set_language(language: Language) {
+    CURRENT.store(language.as_u8(), Ordering::Relaxed);
+}
+
+/// Get the active language
+pub fn current_language() -> Language {
+    Language::from_u8(CURRENT.load(Ordering::Relaxed))
+}
+
+/// Look up a message in the active language
+pub fn t(key: &'static str) -> &'static str {
+    lookup(current_language(), key)
+}
+
+/// Look up a message in the active language and fill in `{name}` placeholders
+pub fn tf(key: &'static str, args: &[(&str, &str)]) -> String {
+    format_message(lookup(current_language(), key), args)
+}
+
+/// Look up a message, falling back to English and then to the key itself
+pub fn lookup(language: Language, key: &str) -> &str {
+    find(language, key)
+        .or_else(|| find(Language::English, key))
+        .unwrap_or(key)
+}
+
+fn find(language: Language, key: &str) -> Option<&'static str> {
+    language
+        .catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| *text)
+}
+
+/// Replace `{name}` placeholders with the given values
+///
+/// Unknown placeholders are left as they are.
+pub fn format_message(template: &str, args: &[(&str, &str)]) -> String {
+    let mut message = template.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    message
+}
+
+// ============================================================================
+// MESSAGE CATALOGS
+// ============================================================================
+
+const EN: &[(&str, &str)] = &[
+    // Tray menu
+    ("tray.show_hide", "Show/Hide"),
+    ("tray.quick_search", "Quick Search"),
+    ("tray.stop_all", "Stop All Sounds"),
+    ("tray.profiles", "Profiles"),
+    ("tray.default_profile", "Default"),
+    ("tray.quit", "Quit"),
+    // Command errors (by error code)
+    ("error.device_missing", "Audio device not found"),
+    ("error.device_error", "Audio device could not be opened"),
+    ("error.file_not_found", "File not found"),
+    ("error.unsupported_format", "Unsupported audio format"),
+    ("error.drm_protected", "File is DRM-protected"),
+    ("error.decode_failed", "Audio could not be decoded"),
+    ("error.hotkey_conflict", "Hotkey is already in use"),
+    ("error.hotkey_invalid", "Invalid hotkey"),
+    ("error.hotkey_not_found", "Hotkey is not assigned"),
+    ("error.sound_not_found", "Sound not found"),
+    ("error.category_not_found", "Category not found"),
+    ("error.playback_not_found", "Playback is not active"),
+    ("error.invalid_request", "Request not allowed right now"),
+    ("error.cancelled", "Cancelled"),
+    ("error.timeout", "Operation timed out"),
+    ("error.vb_cable", "VB-Cable operation failed"),
+    ("error.internal", "Unexpected error: {message}"),
+    // Notifications
+    ("notify.decode_failed", "Failed to decode: {error}"),
+    (
+        "notify.devices_not_found",
+        "Devices not found: {monitor} / {broadcast}",
+    ),
+    (
+        "notify.instance_running.title",
+        "SonicDeck is already running",
+    ),
+    (
+        "notify.instance_running.message",
+        "Another SonicDeck instance is already running (possibly in another user session). \
+         Changes made in one instance may overwrite the other's.",
+    ),
+];
+
+const DE: &[(&str, &str)] = &[
+    // Tray menu
+    ("tray.show_hide", "Anzeigen/Ausblenden"),
+    ("tray.quick_search", "Schnellsuche"),
+    ("tray.stop_all", "Alle Sounds stoppen"),
+    ("tray.profiles", "Profile"),
+    ("tray.default_profile", "Standard"),
+    ("tray.quit", "Beenden"),
+    // Command errors (by error code)
+    ("error.device_missing", "Audiogerät nicht gefunden"),
+    (
+        "error.device_error",
+        "Audiogerät konnte nicht geöffnet werden",
+    ),
+    ("error.file_not_found", "Datei nicht gefunden"),
+    (
+        "error.unsupported_format",
+        "Nicht unterstütztes Audioformat",
+    ),
+    ("error.drm_protected", "Datei ist DRM-geschützt"),
+    ("error.decode_failed", "Audio konnte nicht dekodiert werden"),
+    ("error.hotkey_conflict", "Hotkey ist bereits belegt"),
+    ("error.hotkey_invalid", "Ungültiger Hotkey"),
+    ("error.hotkey_not_found", "Hotkey ist nicht zugewiesen"),
+    ("error.sound_not_found", "Sound nicht gefunden"),
+    ("error.category_not_found", "Kategorie nicht gefunden"),
+    ("error.playback_not_found", "Wiedergabe ist nicht aktiv"),
+    ("error.invalid_request", "Aktion ist gerade nicht möglich"),
+    ("error.cancelled", "Abgebrochen"),
+    ("error.timeout", "Zeitüberschreitung"),
+    ("error.vb_cable", "VB-Cable-Vorgang fehlgeschlagen"),
+    ("error.internal", "Unerwarteter Fehler: {message}"),
+    // Notifications
+    ("notify.decode_failed", "Dekodieren fehlgeschlagen: {error}"),
+    (
+        "notify.devices_not_found",
+        "Geräte nicht gefunden: {monitor} / {broadcast}",
+    ),
+    ("notify.instance_running.title", "SonicDeck läuft bereits"),
+    (
+        "notify.instance_running.message",
+        "Eine andere SonicDeck-Instanz läuft bereits (eventuell in einer anderen \
+         Benutzersitzung). Änderungen in einer Instanz können die der anderen überschreiben.",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_per_language() {
+        assert_eq!(lookup(Language::English, "tray.quit"), "Quit");
+        assert_eq!(lookup(Language::German, "tray.quit"), "Beenden");
+    }
+
+    #[test]
+    fn test_lookup_falls_back() {
+        assert_eq!(lookup(Language::German, "missing.key"), "missing.key");
+    }
+
+    #[test]
+    fn test_catalogs_have_same_keys() {
+        for (key, _) in EN {
+            assert!(
+                find(Language::German, key).is_some(),
+                "German lacks {}",
+                key
+            );
+        }
+        for (key, _) in DE {
+            assert!(
+                find(Language::English, key).is_some(),
+                "English lacks {}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_message() {
+        let message = format_message(
+            "Devices not found: {monitor} / {broadcast}",
+            &[("monitor", "device_0"), ("broadcast", "device_2")],
+        );
+        assert_eq!(message, "Devices not found: device_0 / device_2");
+        assert_eq!(format_message("Hi {name}", &[]), "Hi {name}");
+    }
+
+    #[test]
+    fn test_language_serde() {
+        assert_eq!(serde_json::to_string(&Language::German).unwrap(), "\"de\"");
+        let parsed: Language = serde_json::from_str("\"en\"").unwrap();
+        assert_eq!(parsed, Language::English);
+        assert_eq!(
+            Language::from_u8(Language::German.as_u8()),
+            Language::German
+        );
+    }
+}
@@ -2,20 +2,45 @@
 //!
 //! Rust backend with dual-output audio routing (cpal-based implementation).
 
+mod api;
+mod api_security;
+mod app_rules;
 mod audio;
+mod bindings;
+mod card_assets;
 mod commands;
+mod device_watch;
+mod discord;
+mod error;
+mod folder_watch;
+mod hotkey_watch;
 mod hotkeys;
+mod i18n;
 mod persistence;
+mod profiles;
+mod recap;
+mod recording;
+mod reload;
+mod search;
+mod session;
 mod settings;
+mod setup;
+mod soundpack;
 mod sounds;
+mod startup;
 mod state;
+mod stats;
 mod tray;
 mod vbcable;
+mod writer;
 
 use tauri::Manager;
 use tracing::{error, info};
 
-pub use audio::{AudioDevice, AudioManager, CacheStats, DeviceId, WaveformData};
+pub use audio::{
+    generate_peaks, AudioData, AudioDevice, AudioManager, CacheStats, DeviceId, WaveformData,
+};
+pub use error::{AppError, ErrorCode};
 pub use settings::AppSettings;
 pub use sounds::{Category, CategoryId, Sound, SoundId, SoundLibrary};
 pub use state::AppState;
@@ -23,44 +48,6 @@ pub use state::AppState;
 // GLOBAL SHORTCUT HANDLING
 // ============================================================================
 
-/// Normalize hotkey string to match our storage format
-fn normalize_hotkey_string(hotkey: &str) -> String {
-    hotkey
-        .split('+')
-        .map(|part| {
-            let trimmed = part.trim().to_lowercase();
-
-            // Handle modifier keys
-            match trimmed.as_str() {
-                "control" => "Ctrl".to_string(),
-                "alt" => "Alt".to_string(),
-                "shift" => "Shift".to_string(),
-                "meta" => "Super".to_string(),
-                // Handle NumPad keys: numpad0 -> NumPad0, numpadadd -> NumPadAdd
-                key if key.starts_with("numpad") => {
-                    format!("NumPad{}", capitalize_first(&key[6..]))
-                }
-                // Handle Digit keys: digit0 -> 0, digit1 -> 1, etc.
-                key if key.starts_with("digit") => key[5..].to_string(),
-                // Handle Key prefix: keya -> A, keyb -> B, etc.
-                key if key.starts_with("key") => key[3..].to_uppercase(),
-                // Capitalize first letter for all other keys
-                other => capitalize_first(other),
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("+")
-}
-
-/// Capitalize the first letter of a string
-fn capitalize_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().chain(chars).collect(),
-    }
-}
-
 /// Handle global shortcut events
 #[cfg(desktop)]
 fn handle_global_shortcut(
@@ -73,7 +60,7 @@ fn handle_global_shortcut(
     let hotkey_str = shortcut.to_string();
 
     // Normalize the hotkey string to match our stored format
-    let normalized_hotkey = normalize_hotkey_string(&hotkey_str);
+    let normalized_hotkey = hotkeys::normalize_hotkey_string(&hotkey_str);
 
     tracing::info!(
         "Global shortcut event received: {} -> normalized: {} (state: {:?})",
@@ -82,9 +69,30 @@ fn handle_global_shortcut(
         event.state
     );
 
-    // Only handle pressed state
-    if event.state != ShortcutState::Pressed {
-        tracing::debug!("Ignoring non-pressed state: {:?}", event.state);
+    // Releases only matter to hold-to-play and duck hotkeys
+    if event.state == ShortcutState::Released {
+        handle_hotkey_release(app, &normalized_hotkey);
+        return;
+    }
+
+    // A pending hotkey capture takes the press instead of its mapping
+    if app
+        .state::<hotkeys::HotkeyCapture>()
+        .deliver(&normalized_hotkey)
+    {
+        tracing::debug!("Hotkey press delivered to capture: {}", normalized_hotkey);
+        return;
+    }
+
+    // Repeated presses while a hold-to-play hotkey is held down
+    if app
+        .state::<hotkeys::HeldHotkeys>()
+        .is_held(&normalized_hotkey)
+    {
+        tracing::debug!(
+            "Ignoring repeated press of held hotkey: {}",
+            normalized_hotkey
+        );
         return;
     }
 
@@ -101,13 +109,30 @@ fn handle_global_shortcut(
         mappings.mappings.len()
     );
 
-    // Get sound ID for this hotkey using the normalized string
-    let sound_id = match hotkeys::get_sound_id(&mappings, &normalized_hotkey) {
-        Some(id) => {
+    // Resolve the hotkey to an action; sound mappings and bank slots play
+    // sounds, bank slots resolve against the selected category at press time
+    let bank_slot = hotkeys::bank_slot(&mappings, &normalized_hotkey);
+    let action = match (
+        hotkeys::get_action(&mappings, &normalized_hotkey),
+        hotkeys::get_sound_id(&mappings, &normalized_hotkey),
+        bank_slot,
+    ) {
+        (Some(action), _, _) => action,
+        (None, Some(id), _) => {
             tracing::info!("Found sound mapping: '{}' -> {:?}", normalized_hotkey, id);
-            id.clone()
+            hotkeys::HotkeyAction::PlaySound(id.clone())
         }
-        None => {
+        (None, None, Some(slot)) => match resolve_bank_sound(&app_state, slot) {
+            Some(id) => {
+                tracing::info!("Hotkey bank slot {} -> {:?}", slot + 1, id);
+                hotkeys::HotkeyAction::PlaySound(id)
+            }
+            None => {
+                tracing::debug!("Hotkey bank slot {} is empty", slot + 1);
+                return;
+            }
+        },
+        (None, None, None) => {
             tracing::warn!(
                 "No sound mapped to hotkey: '{}'. Available mappings:",
                 normalized_hotkey
@@ -118,20 +143,43 @@ fn handle_global_shortcut(
             return;
         }
     };
+    let mode = hotkeys::get_mode(&mappings, &normalized_hotkey);
     drop(mappings); // Release read lock early
 
+    let (sound_id, feedback_action) = match &action {
+        hotkeys::HotkeyAction::PlaySound(id) => (Some(id.as_str().to_owned()), None),
+        other => (None, Some(other.clone())),
+    };
+    api::publish(
+        app,
+        api::TriggerFeedback::HotkeyFired {
+            hotkey: normalized_hotkey.clone(),
+            sound_id,
+            action: feedback_action,
+        },
+    );
+
+    handle_hotkey_action(app, &normalized_hotkey, action, mode);
+}
+
+/// Play the sound of a hotkey
+#[cfg(desktop)]
+fn play_hotkey_sound(
+    app: &tauri::AppHandle,
+    hotkey: &str,
+    sound_id: &SoundId,
+    mode: hotkeys::HotkeyMode,
+) {
+    let app_state = app.state::<AppState>();
+
     // Read sound library from in-memory state
     let library = app_state.read_sounds();
 
     // Find the sound
-    let sound = match library.sounds.iter().find(|s| s.id == sound_id) {
+    let sound = match library.sounds.iter().find(|s| &s.id == sound_id) {
         Some(s) => s.clone(),
         None => {
-            tracing::warn!(
-                "Sound not found for hotkey: {} -> {:?}",
-                hotkey_str,
-                sound_id
-            );
+            tracing::warn!("Sound not found for hotkey: {} -> {:?}", hotkey, sound_id);
             return;
         }
     };
@@ -142,8 +190,17 @@ fn handle_global_shortcut(
     let monitor_device = settings.monitor_device_id.clone();
     let broadcast_device = settings.broadcast_device_id.clone();
     let default_volume = settings.default_volume;
+    let queue_mode_enabled = settings.queue_mode_enabled;
     drop(settings); // Release read lock early
 
+    // In queue mode the sound waits for the previous one to end
+    if queue_mode_enabled {
+        if let Err(e) = commands::enqueue(app, &sound.id) {
+            tracing::error!("Failed to queue sound from hotkey: {}", e);
+        }
+        return;
+    }
+
     // Get device IDs
     let device1 = match monitor_device {
         Some(id) => id,
@@ -176,41 +233,165 @@ fn handle_global_shortcut(
         sound.trim_start_ms,
         sound.trim_end_ms,
         Some(sound.id.as_str().to_owned()),
+        Some(sound.play_policy),
+        None,
+        None,
         manager,
         app.clone(),
     ) {
-        Ok(result) => match result.action.as_str() {
-            "ignored" => {
-                tracing::debug!(
-                    "Hotkey '{}' ignored - sound '{}' already playing",
-                    normalized_hotkey,
-                    sound.name
-                );
-            }
-            "restarted" => {
-                tracing::info!(
-                    "Hotkey '{}' restarted sound '{}' (playback: {:?}, stopped: {:?})",
-                    normalized_hotkey,
-                    sound.name,
-                    result.playback_id,
-                    result.stopped_playback_id
-                );
+        Ok(result) => {
+            if mode == hotkeys::HotkeyMode::HoldToPlay {
+                if let Some(playback_id) = &result.playback_id {
+                    app.state::<hotkeys::HeldHotkeys>()
+                        .hold(hotkey, playback_id.clone());
+                }
             }
-            _ => {
-                tracing::info!(
-                    "Hotkey '{}' triggered sound '{}' (playback: {:?})",
-                    normalized_hotkey,
-                    sound.name,
-                    result.playback_id
-                );
+            match result.action.as_str() {
+                "ignored" => {
+                    tracing::debug!(
+                        "Hotkey '{}' ignored - sound '{}' already playing",
+                        hotkey,
+                        sound.name
+                    );
+                }
+                "extended" => {
+                    tracing::debug!(
+                        "Hotkey '{}' extended sound '{}' (playback: {:?})",
+                        hotkey,
+                        sound.name,
+                        result.playback_id
+                    );
+                }
+                "queued" => {
+                    tracing::debug!(
+                        "Hotkey '{}' queued sound '{}' after playback {:?}",
+                        hotkey,
+                        sound.name,
+                        result.playback_id
+                    );
+                }
+                "restarted" => {
+                    tracing::info!(
+                        "Hotkey '{}' restarted sound '{}' (playback: {:?}, stopped: {:?})",
+                        hotkey,
+                        sound.name,
+                        result.playback_id,
+                        result.stopped_playback_id
+                    );
+                }
+                _ => {
+                    tracing::info!(
+                        "Hotkey '{}' triggered sound '{}' (playback: {:?})",
+                        hotkey,
+                        sound.name,
+                        result.playback_id
+                    );
+                }
             }
-        },
+        }
         Err(e) => {
             tracing::error!("Failed to play sound from hotkey: {}", e);
         }
     }
 }
 
+/// Stop the sound of a hold-to-play hotkey or lift a duck when its key is
+/// released
+#[cfg(desktop)]
+fn handle_hotkey_release(app: &tauri::AppHandle, hotkey: &str) {
+    let held = app.state::<hotkeys::HeldHotkeys>();
+    if let Some(db) = held.release_duck(hotkey) {
+        tracing::info!("Duck hotkey '{}' released, duck now {} dB", hotkey, db);
+        app.state::<AudioManager>().set_duck(db);
+    }
+    if let Some(playback_id) = held.release(hotkey) {
+        tracing::info!(
+            "Hotkey '{}' released, stopping playback {}",
+            hotkey,
+            playback_id
+        );
+        app.state::<AudioManager>().signal_stop(&playback_id);
+    }
+}
+
+/// Sound in a hotkey bank slot of the selected category (or the first one)
+#[cfg(desktop)]
+fn resolve_bank_sound(app_state: &AppState, slot: usize) -> Option<SoundId> {
+    let library = app_state.read_sounds();
+    let category_id = match app_state.read_session().last_category_id.clone() {
+        Some(id) => id,
+        None => library
+            .categories
+            .iter()
+            .min_by_key(|c| c.sort_order)?
+            .id
+            .clone(),
+    };
+    hotkeys::bank_sound(&library, &category_id, slot).map(|sound| sound.id.clone())
+}
+
+/// Run the action bound to a hotkey
+#[cfg(desktop)]
+fn handle_hotkey_action(
+    app: &tauri::AppHandle,
+    hotkey: &str,
+    action: hotkeys::HotkeyAction,
+    mode: hotkeys::HotkeyMode,
+) {
+    tracing::info!("Hotkey action triggered: {:?}", action);
+
+    match action {
+        hotkeys::HotkeyAction::ToggleNormalization => {
+            if let Err(e) = commands::toggle_normalization(app) {
+                tracing::error!("Failed to toggle loudness normalization: {}", e);
+            }
+        }
+        hotkeys::HotkeyAction::OpenQuickSearch => {
+            if let Err(e) = commands::show_quick_search(app) {
+                tracing::error!("Failed to open quick search: {}", e);
+            }
+        }
+        hotkeys::HotkeyAction::PlaySound(sound_id) => {
+            play_hotkey_sound(app, hotkey, &sound_id, mode);
+        }
+        hotkeys::HotkeyAction::StopAll => {
+            if let Err(e) = commands::stop_all_audio(app.state::<AudioManager>(), app.clone()) {
+                tracing::error!("Failed to stop all sounds: {}", e);
+            }
+        }
+        hotkeys::HotkeyAction::StopSound(sound_id) => {
+            let manager = app.state::<AudioManager>();
+            match manager.get_sound_state(sound_id.as_str()) {
+                Some(state) => {
+                    manager.signal_stop(state.playback_id());
+                }
+                None => tracing::debug!("Sound {:?} is not playing", sound_id),
+            }
+        }
+        hotkeys::HotkeyAction::ToggleMicRouting => match commands::toggle_microphone_routing(app) {
+            Ok(enabled) => tracing::info!(
+                "Microphone routing {}",
+                if enabled { "enabled" } else { "disabled" }
+            ),
+            Err(e) => tracing::error!("Failed to toggle microphone routing: {}", e),
+        },
+        hotkeys::HotkeyAction::VolumeUp => {
+            if let Err(e) = commands::step_master_volume(app, hotkeys::VOLUME_STEP) {
+                tracing::error!("Failed to raise master volume: {}", e);
+            }
+        }
+        hotkeys::HotkeyAction::VolumeDown => {
+            if let Err(e) = commands::step_master_volume(app, -hotkeys::VOLUME_STEP) {
+                tracing::error!("Failed to lower master volume: {}", e);
+            }
+        }
+        hotkeys::HotkeyAction::Duck(db) => {
+            let db = app.state::<hotkeys::HeldHotkeys>().hold_duck(hotkey, db);
+            app.state::<AudioManager>().set_duck(db);
+        }
+    }
+}
+
 /// Register all saved hotkeys on app startup
 #[cfg(desktop)]
 fn register_saved_hotkeys(app: &tauri::AppHandle) -> Result<(), String> {
@@ -218,11 +399,11 @@ fn register_saved_hotkeys(app: &tauri::AppHandle) -> Result<(), String> {
 
     let mappings = hotkeys::load(app)?;
 
-    for (hotkey, sound_id) in &mappings.mappings {
+    for hotkey in hotkeys::all_hotkeys(&mappings) {
         if let Ok(shortcut) = hotkey.parse::<tauri_plugin_global_shortcut::Shortcut>() {
             match app.global_shortcut().register(shortcut) {
                 Ok(_) => {
-                    tracing::info!("Registered saved hotkey: {} -> {:?}", hotkey, sound_id);
+                    tracing::info!("Registered saved hotkey: {}", hotkey);
                 }
                 Err(e) => {
                     tracing::error!("Failed to register saved hotkey '{}': {}", hotkey, e);
@@ -275,21 +456,431 @@ fn cleanup_orphaned_hotkeys(app: &tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Remove temporary sounds whose expiry has passed, together with their
+/// hotkeys and any soundpack files nothing else uses
+///
+/// Runs before saved hotkeys are registered, so no shortcuts need unregistering.
+fn remove_expired_sounds(app: &tauri::AppHandle) -> Result<(), String> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+
+    let state = app.state::<AppState>();
+    let mut library = state.read_sounds().clone();
+    let expired = sounds::remove_expired(&mut library, now_ms);
+    if expired.is_empty() {
+        return Ok(());
+    }
+
+    let mut mappings = state.read_hotkeys().clone();
+    for sound in &expired {
+        tracing::info!("Removing expired sound '{}'", sound.name);
+        for hotkey in hotkeys::get_hotkeys_for_sound(&mappings, &sound.id) {
+            hotkeys::remove_mapping(&mut mappings, &hotkey)?;
+        }
+    }
+
+    let managed_files = match soundpack::get_soundpacks_dir(app) {
+        Ok(dir) => sounds::orphaned_managed_files(&expired, &library, &dir),
+        Err(e) => {
+            tracing::warn!("Keeping files of expired sounds: {}", e);
+            Vec::new()
+        }
+    };
+
+    state.update_and_save_hotkeys(app, mappings)?;
+    state.update_and_save_sounds(app, library)?;
+
+    for file in managed_files {
+        if let Err(e) = std::fs::remove_file(&file) {
+            tracing::warn!("Failed to delete expired sound file {:?}: {}", file, e);
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// STARTUP ACTIONS
+// ============================================================================
+
+/// Route a microphone to VB-Cable and make VB-Cable the communications device
+#[cfg(desktop)]
+fn start_microphone_routing(app: &tauri::AppHandle, device_id: &str) {
+    info!("Auto-enabling microphone routing for device: {}", device_id);
+    let settings = app.state::<AppState>().read_settings().clone();
+    if let Err(e) = commands::route_microphone(&settings, device_id, &app.state::<AudioManager>()) {
+        error!("Failed to auto-enable microphone routing: {}", e);
+    }
+
+    // Also activate communications mode so Discord uses VB-Cable
+    info!("Auto-activating VB-Cable communications mode");
+    if let Err(e) = vbcable::activate_comm_mode() {
+        error!("Failed to auto-activate communications mode: {}", e);
+    }
+}
+
+/// Execute the startup actions from settings, in order
+///
+/// A failing action is logged and doesn't stop the ones after it.
+#[cfg(desktop)]
+fn run_startup_actions(app: &tauri::AppHandle) {
+    let (actions, configured_microphone) = {
+        let state = app.state::<AppState>();
+        let settings = state.read_settings();
+        (
+            settings.startup_actions.clone(),
+            settings.microphone_routing_device_id.clone(),
+        )
+    };
+
+    for action in actions {
+        info!("Running startup action: {:?}", action);
+        match action {
+            startup::StartupAction::EnableMicRouting { microphone_id } => {
+                if let Some(active) = vbcable::get_routing_status() {
+                    info!("Microphone routing already active for device: {}", active);
+                    continue;
+                }
+                match microphone_id.or_else(|| configured_microphone.clone()) {
+                    Some(device_id) => start_microphone_routing(app, &device_id),
+                    None => error!("Startup action needs a microphone, none is configured"),
+                }
+            }
+            startup::StartupAction::ApplyProfileDevices { profile } => {
+                match profiles::apply_devices(app, &profile) {
+                    Ok(true) => info!("Applied devices of profile '{}'", profile),
+                    Ok(false) => error!("No devices recorded for profile '{}'", profile),
+                    Err(e) => error!("Failed to apply devices of profile '{}': {}", profile, e),
+                }
+            }
+        }
+    }
+}
+
+/// Run the startup work that can wait until the window is shown
+fn spawn_deferred_startup(app: tauri::AppHandle) {
+    let spawned = std::thread::Builder::new()
+        .name("deferred-startup".to_string())
+        .spawn(move || {
+            #[cfg(desktop)]
+            startup::time_deferred_phase(startup::StartupPhase::DeviceEnumeration, || {
+                migrate_saved_devices(&app);
+                check_saved_devices(&app)
+            });
+            startup::time_deferred_phase(startup::StartupPhase::CacheWarmup, || {
+                warm_up_cache(&app)
+            });
+            startup::time_deferred_phase(startup::StartupPhase::CardAssets, || {
+                card_assets::warm_up(&app)
+            });
+            startup::mark_deferred_complete();
+        });
+    if let Err(e) = spawned {
+        error!("Failed to spawn deferred startup thread: {}", e);
+    }
+}
+
+/// Point saved device IDs (settings, profiles and per-sound overrides) at
+/// their devices' current IDs
+///
+/// Emits `device-migration-needed` with the IDs that match no device, so the
+/// user is asked to select them again.
+#[cfg(desktop)]
+fn migrate_saved_devices(app: &tauri::AppHandle) {
+    use tauri::Emitter;
+
+    let devices = match audio::enumerate_devices() {
+        Ok(devices) => devices,
+        Err(e) => {
+            error!("Failed to enumerate devices for migration: {}", e);
+            return;
+        }
+    };
+
+    let state = app.state::<AppState>();
+    let mut settings = state.read_settings().clone();
+    let mut session = state.read_session().clone();
+    let mut library = state.read_sounds().clone();
+    let saved_names = settings.device_names.clone();
+    let mut migrator = audio::DeviceIdMigrator::new(&saved_names, &devices);
+
+    let settings_moved = migrator.migrate(&mut settings.monitor_device_id)
+        | migrator.migrate(&mut settings.broadcast_device_id);
+    let mut session_moved = false;
+    for profile in session.devices_by_profile.values_mut() {
+        session_moved |= migrator.migrate(&mut profile.monitor_device_id);
+        session_moved |= migrator.migrate(&mut profile.broadcast_device_id);
+    }
+    let mut sounds_moved = false;
+    for sound in &mut library.sounds {
+        sounds_moved |= migrator.migrate(&mut sound.monitor_device_id);
+        sounds_moved |= migrator.migrate(&mut sound.broadcast_device_id);
+    }
+    let (names, unresolved) = migrator.finish();
+
+    if settings_moved || names != settings.device_names {
+        settings.device_names = names;
+        if let Err(e) = state.update_and_save_settings(app, settings.clone()) {
+            error!("Failed to save migrated devices: {}", e);
+        } else if settings_moved {
+            if let Err(e) = app.emit("settings-changed", &settings) {
+                error!("Failed to emit settings changed event: {}", e);
+            }
+        }
+    }
+    if session_moved {
+        if let Err(e) = state.update_and_save_session(app, session) {
+            error!("Failed to save migrated profile devices: {}", e);
+        }
+    }
+    if sounds_moved {
+        if let Err(e) = state.update_and_save_sounds(app, library) {
+            error!("Failed to save migrated sound devices: {}", e);
+        }
+    }
+
+    if !unresolved.is_empty() {
+        tracing::warn!(
+            "Saved devices {:?} match no output device, asking to select them again",
+            unresolved
+        );
+        if let Err(e) = app.emit("device-migration-needed", &unresolved) {
+            error!("Failed to emit device migration event: {}", e);
+        }
+    }
+}
+
+/// Fall back to the system default for saved devices that are gone
+#[cfg(desktop)]
+fn check_saved_devices(app: &tauri::AppHandle) {
+    let (monitor_device, broadcast_device, device_fallback_enabled) = {
+        let state = app.state::<AppState>();
+        let settings = state.read_settings();
+        (
+            settings.monitor_device_id.clone(),
+            settings.broadcast_device_id.clone(),
+            settings.device_fallback_enabled,
+        )
+    };
+    if !device_fallback_enabled {
+        return;
+    }
+
+    match audio::check_saved_devices(monitor_device.as_ref(), broadcast_device.as_ref()) {
+        Ok(changes) => {
+            for change in &changes {
+                commands::emit_device_fallback(app, change);
+            }
+        }
+        Err(e) => error!("Failed to check saved devices: {}", e),
+    }
+}
+
+/// Decode the sounds of the last used category, so their first play is instant
+fn warm_up_cache(app: &tauri::AppHandle) {
+    let file_paths: Vec<String> = {
+        let state = app.state::<AppState>();
+        let library = state.read_sounds();
+        let category_id = state.read_session().last_category_id.clone().or_else(|| {
+            library
+                .categories
+                .iter()
+                .min_by_key(|c| c.sort_order)
+                .map(|c| c.id.clone())
+        });
+        library
+            .sounds
+            .iter()
+            .filter(|s| Some(&s.category_id) == category_id.as_ref())
+            .map(|s| s.file_path.clone())
+            .collect()
+    };
+
+    let cache = app.state::<AudioManager>().get_cache();
+    let mut warmed = 0;
+    for path in &file_paths {
+        if audio::AudioCache::load(&cache, path).is_ok() {
+            warmed += 1;
+        }
+    }
+    info!(
+        "Cache warmup decoded {} of {} sounds",
+        warmed,
+        file_paths.len()
+    );
+}
+
+// ============================================================================
+// INSTANCE LOCK
+// ============================================================================
+
+/// Hold the data directory lock, warning if another instance already holds it
+///
+/// A second instance (e.g. in another user session) would write the same JSON
+/// files; writes are serialized, but each instance overwrites the other's changes.
+fn acquire_instance_lock(app: &tauri::AppHandle) {
+    let dir = match app.path().app_local_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            error!("Failed to get app data directory: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        error!("Failed to create app data directory: {}", e);
+        return;
+    }
+
+    match persistence::acquire_instance_lock(&dir) {
+        Ok(Some(lock)) => {
+            app.manage(lock);
+        }
+        Ok(None) => {
+            use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+            tracing::warn!(
+                "Another instance is using {}, changes may overwrite each other",
+                dir.display()
+            );
+            app.dialog()
+                .message(i18n::t("notify.instance_running.message"))
+                .title(i18n::t("notify.instance_running.title"))
+                .kind(MessageDialogKind::Warning)
+                .show(|_| {});
+        }
+        Err(e) => error!("Failed to check for other instances: {}", e),
+    }
+}
+
+// ============================================================================
+// WINDOW GEOMETRY
+// ============================================================================
+
+/// Store the recap of this session for the next launch (an idle session
+/// keeps the previous recap)
+fn record_session_recap(state: &AppState) {
+    let recap = recap::finish(|sound_id| {
+        state
+            .read_sounds()
+            .sounds
+            .iter()
+            .find(|s| s.id.as_str() == sound_id)
+            .map(|s| s.name.clone())
+    });
+    if let Some(recap) = recap {
+        info!(
+            "Session recap: {} sounds played, {} errors",
+            recap.sounds_played, recap.errors
+        );
+        state.write_session().last_recap = Some(recap);
+    }
+}
+
+/// Restore the main window position and size from the saved session
+#[cfg(desktop)]
+fn restore_window_geometry(app: &tauri::AppHandle) {
+    let Some(geometry) = app.state::<AppState>().read_session().window else {
+        return;
+    };
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    if geometry.width > 0 && geometry.height > 0 {
+        let _ = window.set_size(tauri::PhysicalSize::new(geometry.width, geometry.height));
+    }
+
+    // Only restore the position if it is still on a connected monitor
+    let on_screen = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .any(|monitor| {
+            let pos = monitor.position();
+            let size = monitor.size();
+            geometry.x >= pos.x
+                && geometry.y >= pos.y
+                && geometry.x < pos.x + size.width as i32
+                && geometry.y < pos.y + size.height as i32
+        });
+    if on_screen {
+        let _ = window.set_position(tauri::PhysicalPosition::new(geometry.x, geometry.y));
+    }
+
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Record the current main window geometry in the in-memory session
+///
+/// Minimized windows report bogus positions and are skipped; while maximized
+/// only the flag is updated so the restored size stays the normal one.
+#[cfg(desktop)]
+fn track_window_geometry(window: &tauri::Window) {
+    if window.label() != "main" || window.is_minimized().unwrap_or(false) {
+        return;
+    }
+    let maximized = window.is_maximized().unwrap_or(false);
+
+    let app = window.app_handle();
+    let state = app.state::<AppState>();
+    let mut session = state.write_session();
+
+    if maximized {
+        if let Some(geometry) = session.window.as_mut() {
+            geometry.maximized = true;
+            return;
+        }
+    }
+
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    session.window = Some(session::WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized,
+    });
+}
+
 // ============================================================================
 // TAURI APP INITIALIZATION
 // ============================================================================
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    startup::mark_launched();
+    let launch_args = startup::LaunchArgs::from_env();
+    startup::set_launch_profile(launch_args.profile.clone());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             commands::list_audio_devices,
+            commands::refresh_audio_devices,
+            commands::get_device_fallbacks,
             commands::play_dual_output,
             commands::stop_all_audio,
             commands::stop_playback,
+            commands::pause_playback,
+            commands::resume_playback,
+            commands::set_playback_volume,
+            commands::preview_sound,
+            commands::play_test_tone,
+            commands::set_master_volume,
+            commands::queue_sound,
+            commands::skip,
+            commands::clear_queue,
+            commands::get_queue,
             commands::clear_audio_cache,
             commands::get_cache_stats,
             commands::preload_sounds,
@@ -297,28 +888,103 @@ pub fn run() {
             commands::read_logs,
             commands::clear_logs,
             commands::get_waveform,
+            commands::get_waveform_range,
+            commands::get_card_assets,
+            commands::get_spectrogram,
+            commands::detect_onsets,
+            commands::get_engine_status,
+            commands::measure_output_latency,
+            commands::get_startup_report,
+            commands::inject_audio_fault,
+            commands::run_self_test,
+            commands::start_ab_comparison,
+            commands::analyze_gain_staging,
+            commands::analyze_library_loudness,
+            commands::set_discord_mute_awareness,
+            commands::get_discord_status,
+            commands::set_api_server,
+            commands::set_api_open_local_access,
+            commands::get_api_status,
+            commands::create_api_token,
+            commands::revoke_api_token,
+            commands::get_api_audit_log,
+            commands::get_setup_state,
+            commands::complete_setup_step,
+            commands::skip_setup_step,
+            commands::reset_setup,
+            commands::search_sounds,
+            commands::open_quick_search,
+            commands::hide_quick_search,
+            commands::play_quick_search_result,
+            commands::export_category,
+            commands::export_soundpack,
+            commands::import_soundpack,
+            commands::load_session,
+            commands::take_session_recap,
+            commands::set_last_category,
             commands::load_settings,
+            commands::validate_settings,
             commands::save_settings,
+            commands::set_normalization,
+            commands::set_spectrum_analyzer,
             commands::get_settings_file_path,
             commands::enable_autostart,
             commands::disable_autostart,
             commands::is_autostart_enabled,
+            commands::set_startup_options,
             commands::load_hotkeys,
             commands::save_hotkeys,
+            commands::export_bindings,
             commands::register_hotkey,
+            commands::register_action_hotkey,
+            commands::set_hotkey_bank,
+            commands::set_hotkey_mode,
             commands::unregister_hotkey,
             commands::is_hotkey_registered,
+            commands::start_hotkey_capture,
+            commands::cancel_hotkey_capture,
             commands::load_sounds,
             commands::add_sound,
+            commands::import_files,
+            commands::migrate_sounds_to_library,
+            commands::add_watched_folder,
+            commands::remove_watched_folder,
+            commands::validate_sound_file,
+            commands::render_trimmed_copy,
             commands::update_sound,
             commands::toggle_favorite,
+            commands::set_play_policy,
+            commands::set_resume_playback,
+            commands::set_sound_expiry,
+            commands::set_sound_device_trim,
+            commands::set_sound_group,
+            commands::set_sound_tags,
+            commands::list_tags,
+            commands::get_sound_stats,
+            commands::get_most_played,
+            commands::get_recently_played,
+            commands::list_backups,
+            commands::restore_backup,
+            commands::list_profiles,
+            commands::create_profile,
+            commands::delete_profile,
+            commands::switch_profile,
+            commands::set_sound_devices,
+            commands::start_recording,
+            commands::stop_recording,
+            commands::get_recording_status,
+            commands::set_sound_preview,
+            commands::set_sound_fit_duration,
+            commands::set_sound_pitch_speed,
             commands::delete_sound,
+            commands::duplicate_sound,
             commands::add_category,
             commands::update_category,
             commands::delete_category,
             // VB-Cable integration commands
             commands::check_vb_cable_status,
             commands::get_vb_cable_device_name,
+            commands::check_vb_cable_update,
             commands::save_default_audio_device,
             commands::restore_default_audio_device,
             commands::start_vb_cable_install,
@@ -332,6 +998,13 @@ pub fn run() {
             commands::enable_microphone_routing,
             commands::disable_microphone_routing,
             commands::get_microphone_routing_status,
+            commands::get_mic_routing_stats,
+            commands::set_microphone_monitoring,
+            commands::get_noise_reduction,
+            commands::set_noise_reduction,
+            commands::set_microphone_ducking,
+            commands::get_voice_changer,
+            commands::set_voice_changer,
             // VB-Cable uninstall command
             commands::start_vb_cable_uninstall,
             // Sound settings command
@@ -341,36 +1014,78 @@ pub fn run() {
             commands::deactivate_vbcable_comm_mode,
             commands::is_vbcable_comm_mode_active,
         ])
-        .setup(|app| {
+        .setup(move |app| {
+            recap::begin();
+
+            // Warn if another instance writes the same data files
+            acquire_instance_lock(app.handle());
+
+            // Disk writes of state changes happen on a background thread
+            app.manage(writer::SaveWriter::spawn(app.handle().clone()));
+
             // Initialize app state (load all data from disk once at startup)
-            let app_state = AppState::load(app.handle())?;
+            let app_state = startup::time_phase(startup::StartupPhase::StateLoad, || {
+                AppState::load(app.handle())
+            })?;
 
             // Initialize audio manager
             let audio_manager = AudioManager::new();
+            audio_manager.set_master_volume(app_state.read_settings().master_volume);
+            audio_manager.set_mic_duck(app_state.read_settings().microphone_duck());
+            match app.path().app_local_data_dir() {
+                Ok(dir) => audio_manager.set_waveform_dir(dir.join("waveforms")),
+                Err(e) => error!("Failed to get app data directory: {}", e),
+            }
 
             // Register state managers
             app.manage(app_state);
             app.manage(audio_manager);
+            app.manage(hotkeys::HotkeyCapture::default());
+            app.manage(hotkeys::HeldHotkeys::default());
+
+            // Resume the spectrum analyzer if it was enabled in settings
+            if app
+                .state::<AppState>()
+                .read_settings()
+                .spectrum_analyzer_enabled
+            {
+                commands::start_spectrum_analyzer(app.handle().clone());
+            }
+
+            // Drop temporary sounds that expired while the app was closed
+            if let Err(e) = remove_expired_sounds(app.handle()) {
+                error!("Failed to remove expired sounds: {}", e);
+            }
+
+            // Use the devices of the profile selected with --profile
+            if let Some(profile) = &launch_args.profile {
+                match profiles::apply_devices(app.handle(), profile) {
+                    Ok(true) => info!("Launched with profile '{}'", profile),
+                    Ok(false) => info!("Launched with new profile '{}'", profile),
+                    Err(e) => error!("Failed to apply devices of profile '{}': {}", profile, e),
+                }
+            }
 
             #[cfg(desktop)]
             {
                 use tauri::Manager;
                 use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
 
-                // Initialize autostart plugin
-                app.handle()
-                    .plugin(tauri_plugin_autostart::init(
-                        MacosLauncher::LaunchAgent,
-                        None::<Vec<&str>>,
-                    ))
-                    .map_err(|e| format!("Failed to initialize autostart plugin: {}", e))?;
-
                 // Apply saved autostart setting from in-memory state
                 let state = app.state::<AppState>();
                 let settings = state.read_settings();
                 let autostart_enabled = settings.autostart_enabled;
+                let autostart_args = startup::autostart_args(&settings);
                 drop(settings);
 
+                // Initialize autostart plugin (the entry carries the autostart flags)
+                app.handle()
+                    .plugin(tauri_plugin_autostart::init(
+                        MacosLauncher::LaunchAgent,
+                        Some(autostart_args),
+                    ))
+                    .map_err(|e| format!("Failed to initialize autostart plugin: {}", e))?;
+
                 let autostart_manager = app.autolaunch();
                 if autostart_enabled {
                     let _ = autostart_manager.enable();
@@ -394,26 +1109,57 @@ pub fn run() {
                     error!("Failed to cleanup orphaned hotkeys: {}", e);
                 }
 
-                // Cleanup orphaned VB-Cable temp files from previous sessions
-                vbcable::cleanup_temp_files();
+                // Cleanup stale VB-Cable temp files from previous sessions
+                vbcable::cleanup_stale_temp_files();
 
                 // Recover from potential crash - restore original communications device
                 vbcable::recover_comm_mode();
 
+                // Tell the frontend when microphone routing fails and recovers
+                let handle = app.handle().clone();
+                vbcable::set_routing_listener(move |event| {
+                    use tauri::Emitter;
+                    if let Err(e) = handle.emit(event.name(), &event) {
+                        error!("Failed to emit {} event: {}", event.name(), e);
+                    }
+                });
+
                 // Register saved hotkeys
-                if let Err(e) = register_saved_hotkeys(app.handle()) {
-                    error!("Failed to register saved hotkeys: {}", e);
-                }
+                startup::time_phase(startup::StartupPhase::HotkeyRegistration, || {
+                    if let Err(e) = register_saved_hotkeys(app.handle()) {
+                        error!("Failed to register saved hotkeys: {}", e);
+                    }
+                });
+
+                // Recover hotkeys lost to Explorer restarts / session switches
+                hotkey_watch::spawn_watcher(app.handle().clone());
+
+                // Pick up sounds.json / hotkeys.json edits made outside the app
+                reload::spawn_watcher(app.handle().clone());
+
+                // Add and remove sounds as files change in watched folders
+                folder_watch::start(app.handle());
+
+                // Switch profile/category with the foreground application
+                app_rules::spawn_watcher(app.handle().clone());
+
+                // Rescan output devices when one is plugged in or removed
+                device_watch::spawn_watcher(app.handle().clone());
 
                 // Initialize system tray
-                if let Err(e) = tray::init(app.handle()) {
-                    error!("Failed to initialize system tray: {}", e);
-                }
+                startup::time_phase(startup::StartupPhase::TrayInit, || {
+                    if let Err(e) = tray::init(app.handle()) {
+                        error!("Failed to initialize system tray: {}", e);
+                    }
+                });
+
+                // Restore window geometry from the last session
+                restore_window_geometry(app.handle());
 
                 // Optionally start minimized (read from in-memory state)
                 let state = app.state::<AppState>();
                 let settings = state.read_settings();
-                let start_minimized = settings.start_minimized;
+                let start_minimized = settings.start_minimized || launch_args.start_minimized;
                 drop(settings);
 
                 if start_minimized {
@@ -423,6 +1169,33 @@ pub fn run() {
                     }
                 }
 
+                // Start the local API server if it was enabled in settings
+                let api_port = {
+                    let state = app.state::<AppState>();
+                    let settings = state.read_settings();
+                    api_security::set_tokens(settings.api_tokens.clone());
+                    api_security::set_open_local_access(settings.api_open_local_access);
+                    settings.api_enabled.then_some(settings.api_port)
+                };
+                if let Some(port) = api_port {
+                    if let Err(e) = api::start(port) {
+                        error!("Failed to start API server: {}", e);
+                    }
+                }
+
+                // Resume Discord mute awareness if it was enabled in settings
+                let discord_credentials = {
+                    let state = app.state::<AppState>();
+                    let settings = state.read_settings();
+                    settings
+                        .discord_mute_awareness_enabled
+                        .then(|| discord::DiscordCredentials::from_settings(&settings))
+                        .flatten()
+                };
+                if let Some(credentials) = discord_credentials {
+                    discord::start(app.handle().clone(), credentials);
+                }
+
                 // Auto-enable microphone routing if it was enabled in settings
                 let state = app.state::<AppState>();
                 let settings = state.read_settings();
@@ -432,27 +1205,42 @@ pub fn run() {
 
                 if mic_routing_enabled {
                     if let Some(device_id) = mic_device_id {
-                        info!("Auto-enabling microphone routing for device: {}", device_id);
-                        if let Err(e) = vbcable::enable_routing(&device_id) {
-                            error!("Failed to auto-enable microphone routing: {}", e);
-                        }
-
-                        // Also activate communications mode so Discord uses VB-Cable
-                        info!("Auto-activating VB-Cable communications mode");
-                        if let Err(e) = vbcable::activate_comm_mode() {
-                            error!("Failed to auto-activate communications mode: {}", e);
-                        }
+                        start_microphone_routing(app.handle(), &device_id);
                     }
                 }
+
+                // User-configured startup actions run last
+                run_startup_actions(app.handle());
             }
 
+            // Device checks and cache warmup don't need to hold up the window
+            spawn_deferred_startup(app.handle().clone());
+
+            startup::mark_setup_complete();
             Ok(())
         })
         .on_window_event(|window, event| {
+            #[cfg(desktop)]
+            if matches!(
+                event,
+                tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
+            ) {
+                track_window_geometry(window);
+            }
+
             // Handle window close button based on minimize_to_tray setting
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if window.label() != "main" {
+                    return;
+                }
+
                 let app = window.app_handle();
                 let state = app.state::<AppState>();
+
+                if let Err(e) = state.save_session(app) {
+                    error!("Failed to save session: {}", e);
+                }
+
                 let settings = state.read_settings();
                 let minimize_to_tray = settings.minimize_to_tray;
                 drop(settings);
@@ -470,8 +1258,17 @@ pub fn run() {
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|_app, event| {
+        .run(|app, event| {
             if let tauri::RunEvent::Exit = event {
+                let state = app.state::<AppState>();
+                record_session_recap(&state);
+
+                // Finish queued writes and persist session state (window
+                // geometry is only tracked in memory)
+                if let Err(e) = state.save_session(app) {
+                    error!("Failed to save session on exit: {}", e);
+                }
+
                 // Deactivate VB-Cable communications mode on exit
                 info!("App exiting - deactivating VB-Cable communications mode");
                 if let Err(e) = vbcable::deactivate_comm_mode() {
@@ -1,12 +1,85 @@
 //! Atomic file persistence utilities
 //!
 //! Provides crash-safe file writing using the write-to-temp-and-rename pattern.
+//! Writes are serialized across processes with an advisory lock file, so two
+//! instances (e.g. in different user sessions) can't interleave their writes.
+//!
+//! The library, hotkeys and settings are also copied to `backups/` before
+//! they're overwritten, keeping the last `MAX_BACKUPS` versions of each.
 
-use std::fs::{self, File};
+use std::fs::{self, File, OpenOptions, TryLockError};
 use std::io::{BufWriter, Write};
 use std::path::Path;
-use std::time::Instant;
-use tracing::debug;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::stats::now_ms;
+
+/// Lock file held while a data file is written
+const WRITE_LOCK_FILE: &str = "write.lock";
+
+/// Lock file held for the whole lifetime of the app
+const INSTANCE_LOCK_FILE: &str = "instance.lock";
+
+/// Directory (next to the data files) holding backups
+pub const BACKUP_DIR: &str = "backups";
+
+/// Backups kept per data file
+pub const MAX_BACKUPS: usize = 10;
+
+/// Minimum age of the newest backup before another one is taken, so a burst
+/// of saves doesn't push older versions out
+const BACKUP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// A backup copy of a data file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupEntry {
+    /// File name in the backup directory (`sounds-<unix ms>.json`)
+    pub file_name: String,
+    /// Data file it is a copy of, without extension (`sounds`)
+    pub source: String,
+    /// Unix time the backup was taken (ms)
+    pub created_ms: u64,
+    pub size_bytes: u64,
+}
+
+/// Exclusive lock on the data directory, released when dropped
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+/// Try to become the only instance using the data directory `dir`
+///
+/// Returns `Ok(None)` when another process already holds the lock.
+pub fn acquire_instance_lock(dir: &Path) -> Result<Option<InstanceLock>, String> {
+    let file = open_lock_file(&dir.join(INSTANCE_LOCK_FILE))?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(InstanceLock { _file: file })),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(e)) => Err(format!("Failed to lock data directory: {}", e)),
+    }
+}
+
+/// Block until no other process is writing to the directory of `path`
+fn lock_for_write(path: &Path) -> Result<File, String> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let file = open_lock_file(&dir.join(WRITE_LOCK_FILE))?;
+    file.lock()
+        .map_err(|e| format!("Failed to acquire write lock: {}", e))?;
+    Ok(file)
+}
+
+fn open_lock_file(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| format!("Failed to open lock file: {}", e))
+}
 
 /// Writes data atomically to a file.
 ///
@@ -20,6 +93,9 @@ pub fn atomic_write(path: &Path, data: &str) -> Result<(), String> {
 
     debug!(path = %path_str, bytes = bytes_written, "Starting atomic write");
 
+    // Held until the rename is done (released on drop)
+    let _write_lock = lock_for_write(path)?;
+
     let temp_path = path.with_extension("json.tmp");
 
     // Create temp file
@@ -58,6 +134,125 @@ pub fn atomic_write(path: &Path, data: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// `atomic_write`, copying the current file to the backups first
+///
+/// A failed backup is logged, it never blocks the save.
+pub fn atomic_write_with_backup(path: &Path, data: &str) -> Result<(), String> {
+    if let Err(e) = backup_file(path, now_ms(), BACKUP_INTERVAL) {
+        warn!("Failed to back up {}: {}", path.display(), e);
+    }
+    atomic_write(path, data)
+}
+
+/// Copy `path` to the backup directory and drop backups beyond `MAX_BACKUPS`
+///
+/// Skipped if the file doesn't exist or its newest backup is younger than
+/// `min_interval`. Returns the backup that was written.
+pub fn backup_file(
+    path: &Path,
+    now_ms: u64,
+    min_interval: Duration,
+) -> Result<Option<BackupEntry>, String> {
+    let (Some(dir), Some(source)) = (path.parent(), path.file_stem().and_then(|s| s.to_str()))
+    else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let mut backups: Vec<BackupEntry> = list_backups(dir)?
+        .into_iter()
+        .filter(|b| b.source == source)
+        .collect();
+    if let Some(newest) = backups.first() {
+        if now_ms.saturating_sub(newest.created_ms) < min_interval.as_millis() as u64 {
+            return Ok(None);
+        }
+    }
+
+    let backup_dir = dir.join(BACKUP_DIR);
+    fs::create_dir_all(&backup_dir)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    // Never overwrite a backup taken in the same millisecond
+    let mut created_ms = now_ms;
+    while backups.iter().any(|b| b.created_ms == created_ms) {
+        created_ms += 1;
+    }
+    let file_name = format!("{}-{}.json", source, created_ms);
+    let size_bytes = fs::copy(path, backup_dir.join(&file_name))
+        .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+    debug!(backup = %file_name, "Data file backed up");
+
+    let entry = BackupEntry {
+        file_name,
+        source: source.to_string(),
+        created_ms,
+        size_bytes,
+    };
+    backups.insert(0, entry.clone());
+    for old in backups.iter().skip(MAX_BACKUPS) {
+        if let Err(e) = fs::remove_file(backup_dir.join(&old.file_name)) {
+            warn!("Failed to remove old backup {}: {}", old.file_name, e);
+        }
+    }
+    Ok(Some(entry))
+}
+
+/// Backups of the data files in `data_dir`, newest first
+pub fn list_backups(data_dir: &Path) -> Result<Vec<BackupEntry>, String> {
+    let backup_dir = data_dir.join(BACKUP_DIR);
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries =
+        fs::read_dir(&backup_dir).map_err(|e| format!("Failed to read backup directory: {}", e))?;
+
+    let mut backups: Vec<BackupEntry> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_str()?.to_string();
+            let (source, created_ms) = parse_backup_name(&file_name)?;
+            Some(BackupEntry {
+                source: source.to_string(),
+                created_ms,
+                size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                file_name,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| {
+        b.created_ms
+            .cmp(&a.created_ms)
+            .then_with(|| a.source.cmp(&b.source))
+    });
+    Ok(backups)
+}
+
+/// Backup in `data_dir` by file name
+pub fn find_backup(data_dir: &Path, file_name: &str) -> Result<BackupEntry, String> {
+    list_backups(data_dir)?
+        .into_iter()
+        .find(|b| b.file_name == file_name)
+        .ok_or_else(|| format!("Backup not found: {}", file_name))
+}
+
+/// Back up `path` now, regardless of when the last backup was taken
+pub fn backup_now(path: &Path) -> Result<Option<BackupEntry>, String> {
+    backup_file(path, now_ms(), Duration::ZERO)
+}
+
+/// Split `sounds-1700000000000.json` into source and timestamp
+fn parse_backup_name(file_name: &str) -> Option<(&str, u64)> {
+    let (source, created_ms) = file_name.strip_suffix(".json")?.rsplit_once('-')?;
+    let valid_source = !source.is_empty()
+        && source
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let created_ms = created_ms.parse().ok()?;
+    valid_source.then_some((source, created_ms))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,4 +350,93 @@ mod tests {
         let read_content = fs::read_to_string(&file_path).unwrap();
         assert_eq!(read_content, "");
     }
+
+    #[test]
+    fn test_atomic_write_waits_for_write_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("locked.json");
+
+        // Another process writing: hold the write lock
+        let held = lock_for_write(&file_path).unwrap();
+
+        let writer_path = file_path.clone();
+        let writer = std::thread::spawn(move || atomic_write(&writer_path, "content"));
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(!file_path.exists());
+
+        drop(held);
+        writer.join().unwrap().unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "content");
+    }
+
+    #[test]
+    fn test_backup_rotation_keeps_newest() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("sounds.json");
+        fs::write(&file_path, "v0").unwrap();
+
+        for i in 0..MAX_BACKUPS as u64 + 3 {
+            fs::write(&file_path, format!("v{}", i)).unwrap();
+            let entry = backup_file(&file_path, 1000 + i, Duration::ZERO).unwrap();
+            assert!(entry.is_some());
+        }
+
+        let backups = list_backups(temp_dir.path()).unwrap();
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        assert_eq!(backups[0].file_name, format!("sounds-{}.json", 1012));
+        assert_eq!(backups.last().unwrap().created_ms, 1003);
+        let newest = temp_dir.path().join(BACKUP_DIR).join(&backups[0].file_name);
+        assert_eq!(fs::read_to_string(newest).unwrap(), "v12");
+    }
+
+    #[test]
+    fn test_backup_skipped_within_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        let interval = Duration::from_secs(60);
+
+        // Nothing to back up yet
+        assert!(backup_file(&file_path, 0, interval).unwrap().is_none());
+
+        fs::write(&file_path, "{}").unwrap();
+        assert!(backup_file(&file_path, 1_000, interval).unwrap().is_some());
+        assert!(backup_file(&file_path, 30_000, interval).unwrap().is_none());
+        assert!(backup_file(&file_path, 61_000, interval).unwrap().is_some());
+
+        // Backups of other files don't count
+        let other = temp_dir.path().join("hotkeys.json");
+        fs::write(&other, "{}").unwrap();
+        assert!(backup_file(&other, 61_500, interval).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_find_backup_rejects_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("hotkeys.json");
+        fs::write(&file_path, "{}").unwrap();
+        let entry = backup_file(&file_path, 5, Duration::ZERO).unwrap().unwrap();
+
+        assert_eq!(find_backup(temp_dir.path(), &entry.file_name), Ok(entry));
+        assert!(find_backup(temp_dir.path(), "hotkeys-6.json").is_err());
+        assert!(find_backup(temp_dir.path(), "../hotkeys.json").is_err());
+        assert!(find_backup(temp_dir.path(), "../backups/hotkeys-5.json").is_err());
+        assert_eq!(parse_backup_name("sounds-12.json"), Some(("sounds", 12)));
+        assert_eq!(parse_backup_name("sounds.json"), None);
+    }
+
+    #[test]
+    fn test_instance_lock_exclusive() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let first = acquire_instance_lock(temp_dir.path()).unwrap();
+        assert!(first.is_some());
+
+        // Second instance sees the lock
+        assert!(acquire_instance_lock(temp_dir.path()).unwrap().is_none());
+
+        // Released when the first instance exits
+        drop(first);
+        assert!(acquire_instance_lock(temp_dir.path()).unwrap().is_some());
+    }
 }
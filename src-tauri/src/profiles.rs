@@ -0,0 +1,333 @@
+//! Profiles (switchable boards)
+//!
+//! A profile has its own sound library, hotkeys and play statistics, plus the
+//! output devices last used with it (kept in the session). The default
+//! profile uses the files in the app data directory, other profiles keep
+//! theirs in `profiles/<name>/`. A profile without a directory (one only
+//! named with `--profile`) shares the default library and only switches
+//! devices, as before profiles had libraries.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use tracing::{error, info, warn};
+
+use crate::session::{ProfileDevices, DEFAULT_PROFILE};
+use crate::AppState;
+
+/// Directory (under app data) holding the non-default profiles
+pub const PROFILES_DIR: &str = "profiles";
+
+/// Longest profile name
+const MAX_NAME_LEN: usize = 40;
+
+/// Profile whose data is loaded, `None` until startup picked one
+static ACTIVE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Profiles and the active one (payload of `profile-changed`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileList {
+    pub active: String,
+    /// Default profile first, then by name
+    pub profiles: Vec<String>,
+}
+
+/// Name of the profile whose data is loaded
+pub fn active() -> String {
+    ACTIVE
+        .read()
+        .ok()
+        .and_then(|active| active.clone())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Make `name` the profile data is loaded from and saved to
+pub fn set_active(name: &str) {
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = Some(name.to_string());
+    }
+}
+
+/// Check a name for a new profile, returning it trimmed
+///
+/// Names become directory names, so only letters, digits, spaces, `-` and
+/// `_` are allowed.
+pub fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!(
+            "Profile name is longer than {} characters",
+            MAX_NAME_LEN
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        return Err(format!(
+            "Profile name '{}' may only contain letters, digits, spaces, '-' and '_'",
+            name
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Directory holding a profile's data files
+///
+/// The default profile and profiles without a directory of their own use
+/// `data_dir` itself.
+pub fn profile_dir_in(data_dir: &Path, name: &str) -> PathBuf {
+    if name != DEFAULT_PROFILE && validate_name(name).is_ok() {
+        let dir = data_dir.join(PROFILES_DIR).join(name);
+        if dir.is_dir() {
+            return dir;
+        }
+    }
+    data_dir.to_path_buf()
+}
+
+/// Whether a profile has its own directory (or is the default profile)
+pub fn exists_in(data_dir: &Path, name: &str) -> bool {
+    name == DEFAULT_PROFILE || profile_dir_in(data_dir, name) != data_dir
+}
+
+/// Profiles with a library, default first
+pub fn list_in(data_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(data_dir.join(PROFILES_DIR))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|name| name != DEFAULT_PROFILE && validate_name(name).is_ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
+
+/// App data directory (settings, session, shared sound files)
+pub fn app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    // Ensure directory exists
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data_dir)
+}
+
+/// Directory of the active profile's library, hotkeys and statistics
+pub fn data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(profile_dir_in(&app_data_dir(app_handle)?, &active()))
+}
+
+/// Profiles and the active one
+pub fn list(app_handle: &tauri::AppHandle) -> Result<ProfileList, String> {
+    let active = active();
+    let mut profiles = list_in(&app_data_dir(app_handle)?);
+    // A profile named on the command line is listed while it's active
+    if !profiles.contains(&active) {
+        profiles.push(active.clone());
+    }
+    Ok(ProfileList { active, profiles })
+}
+
+/// Create a profile, empty or with a copy of the active profile's library
+/// and hotkeys
+pub fn create(
+    app_handle: &tauri::AppHandle,
+    name: &str,
+    copy_current: bool,
+) -> Result<ProfileList, String> {
+    let name = validate_name(name)?;
+    let app_data_dir = app_data_dir(app_handle)?;
+    if list_in(&app_data_dir)
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(&name))
+    {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+
+    if copy_current {
+        // Copy what's been saved, including changes still queued
+        app_handle.state::<crate::writer::SaveWriter>().flush();
+    }
+    let source = data_dir(app_handle)?;
+    let dir = app_data_dir.join(PROFILES_DIR).join(&name);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create profile directory: {}", e))?;
+    if copy_current {
+        for file in ["sounds.json", "hotkeys.json"] {
+            let from = source.join(file);
+            if from.exists() {
+                std::fs::copy(&from, dir.join(file))
+                    .map_err(|e| format!("Failed to copy {}: {}", file, e))?;
+            }
+        }
+    }
+
+    info!("Created profile '{}'", name);
+    list(app_handle)
+}
+
+/// Delete a profile's directory and remembered devices
+///
+/// The default and the active profile can't be deleted. Sound files copied
+/// into the library stay, other profiles may use them.
+pub fn delete(app_handle: &tauri::AppHandle, name: &str) -> Result<ProfileList, String> {
+    if name == DEFAULT_PROFILE {
+        return Err("The default profile can't be deleted".to_string());
+    }
+    if name == active() {
+        return Err("Switch to another profile before deleting this one".to_string());
+    }
+    let app_data_dir = app_data_dir(app_handle)?;
+    let dir = profile_dir_in(&app_data_dir, name);
+    if dir == app_data_dir {
+        return Err(format!("Profile '{}' not found", name));
+    }
+
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete profile: {}", e))?;
+
+    let state = app_handle.state::<AppState>();
+    let mut session = state.read_session().clone();
+    if session.devices_by_profile.remove(name).is_some() {
+        state.update_and_save_session(app_handle, session)?;
+    }
+
+    info!("Deleted profile '{}'", name);
+    list(app_handle)
+}
+
+/// Switch to another profile: load its library, hotkeys and statistics,
+/// register its hotkeys and use its devices
+///
+/// Emits `profile-changed`.
+pub fn switch(app_handle: &tauri::AppHandle, name: &str) -> Result<ProfileList, String> {
+    let app_data_dir = app_data_dir(app_handle)?;
+    if !exists_in(&app_data_dir, name) {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    let previous = active();
+    if name == previous {
+        return list(app_handle);
+    }
+
+    // Remember the devices of the profile being left, to return to them
+    let state = app_handle.state::<AppState>();
+    let devices = {
+        let settings = state.read_settings();
+        ProfileDevices {
+            monitor_device_id: settings.monitor_device_id.clone(),
+            broadcast_device_id: settings.broadcast_device_id.clone(),
+        }
+    };
+    let mut session = state.read_session().clone();
+    if session.record_devices(&previous, devices) {
+        state.update_and_save_session(app_handle, session)?;
+    }
+
+    state.switch_profile(app_handle, name)?;
+
+    match apply_devices(app_handle, name) {
+        Ok(true) => info!("Applied devices of profile '{}'", name),
+        Ok(false) => {}
+        Err(e) => warn!("Failed to apply devices of profile '{}': {}", name, e),
+    }
+
+    // Start with this profile next time (unless `--profile` says otherwise)
+    let mut session = state.read_session().clone();
+    session.active_profile = Some(name.to_string());
+    state.update_and_save_session(app_handle, session)?;
+
+    crate::tray::update_profiles(app_handle);
+    let profiles = list(app_handle)?;
+    if let Err(e) = app_handle.emit("profile-changed", &profiles) {
+        error!("Failed to emit profile-changed: {}", e);
+    }
+    info!("Switched to profile '{}'", name);
+    Ok(profiles)
+}
+
+/// Switch to the output devices last used with a profile
+///
+/// Returns false if no devices are recorded for it.
+pub fn apply_devices(app_handle: &tauri::AppHandle, profile: &str) -> Result<bool, String> {
+    let state = app_handle.state::<AppState>();
+    let Some(devices) = state
+        .read_session()
+        .devices_by_profile
+        .get(profile)
+        .cloned()
+    else {
+        return Ok(false);
+    };
+
+    let mut settings = state.read_settings().clone();
+    settings.monitor_device_id = devices.monitor_device_id;
+    settings.broadcast_device_id = devices.broadcast_device_id;
+    state.update_and_save_settings(app_handle, settings.clone())?;
+    if let Err(e) = app_handle.emit("settings-changed", &settings) {
+        error!("Failed to emit settings-changed: {}", e);
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert_eq!(validate_name(" Streaming ").unwrap(), "Streaming");
+        assert_eq!(validate_name("Late_Night-2").unwrap(), "Late_Night-2");
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name("D&D night").is_err());
+        assert!(validate_name("../sounds").is_err());
+        assert!(validate_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_profile_dir_falls_back_to_data_dir() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let streaming = data_dir.path().join(PROFILES_DIR).join("Streaming");
+        std::fs::create_dir_all(&streaming).unwrap();
+
+        assert_eq!(profile_dir_in(data_dir.path(), "Streaming"), streaming);
+        assert_eq!(
+            profile_dir_in(data_dir.path(), DEFAULT_PROFILE),
+            data_dir.path()
+        );
+        // Named on the command line only: shares the default library
+        assert_eq!(profile_dir_in(data_dir.path(), "Other"), data_dir.path());
+        assert!(exists_in(data_dir.path(), "Streaming"));
+        assert!(!exists_in(data_dir.path(), "Other"));
+    }
+
+    #[test]
+    fn test_list_puts_default_first() {
+        let data_dir = tempfile::tempdir().unwrap();
+        assert_eq!(list_in(data_dir.path()), vec![DEFAULT_PROFILE]);
+
+        for name in ["streaming", "D and D", "not a profile.txt"] {
+            std::fs::create_dir_all(data_dir.path().join(PROFILES_DIR).join(name)).unwrap();
+        }
+        std::fs::write(data_dir.path().join(PROFILES_DIR).join("file"), "").unwrap();
+
+        assert_eq!(
+            list_in(data_dir.path()),
+            vec![DEFAULT_PROFILE, "D and D", "streaming"]
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Session recap
+//!
+//! Tallies what happens while the app runs (plays, playback time, errors).
+//! On exit the tally becomes a `SessionRecap` stored in the session file, and
+//! the next launch shows it once.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::stats::now_ms;
+
+/// Tally of the running session
+static TALLY: Mutex<SessionTally> = Mutex::new(SessionTally::new());
+
+/// Summary of a finished session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecap {
+    /// Unix time in milliseconds
+    pub started_ms: u64,
+    /// Unix time in milliseconds
+    pub ended_ms: u64,
+    /// Playbacks started
+    pub sounds_played: u32,
+    pub most_played: Option<MostPlayed>,
+    /// Time sounds were audible, overlapping playbacks counted separately
+    pub playback_ms: u64,
+    /// Playbacks that failed to start and device stream errors
+    pub errors: u32,
+}
+
+/// Sound played most often in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MostPlayed {
+    pub sound_id: String,
+    /// None if the sound was deleted during the session
+    pub sound_name: Option<String>,
+    pub plays: u32,
+}
+
+#[derive(Debug)]
+struct SessionTally {
+    started_ms: u64,
+    /// Plays per sound, in order of the first play
+    plays: Vec<(String, u32)>,
+    sounds_played: u32,
+    playback: Duration,
+    errors: u32,
+}
+
+impl SessionTally {
+    const fn new() -> Self {
+        Self {
+            started_ms: 0,
+            plays: Vec::new(),
+            sounds_played: 0,
+            playback: Duration::ZERO,
+            errors: 0,
+        }
+    }
+
+    fn record_play(&mut self, sound_id: &str) {
+        self.sounds_played += 1;
+        if sound_id.is_empty() {
+            return;
+        }
+        match self.plays.iter_mut().find(|(id, _)| id == sound_id) {
+            Some((_, count)) => *count += 1,
+            None => self.plays.push((sound_id.to_string(), 1)),
+        }
+    }
+
+    /// Recap of the tally; None if nothing happened
+    fn recap(
+        &self,
+        ended_ms: u64,
+        sound_name: impl Fn(&str) -> Option<String>,
+    ) -> Option<SessionRecap> {
+        if self.sounds_played == 0 && self.errors == 0 {
+            return None;
+        }
+        // Ties go to the sound played first
+        let most_played = self
+            .plays
+            .iter()
+            .fold(None::<&(String, u32)>, |best, entry| match best {
+                Some(best) if best.1 >= entry.1 => Some(best),
+                _ => Some(entry),
+            })
+            .map(|(sound_id, plays)| MostPlayed {
+                sound_id: sound_id.clone(),
+                sound_name: sound_name(sound_id),
+                plays: *plays,
+            });
+        Some(SessionRecap {
+            started_ms: self.started_ms,
+            ended_ms,
+            sounds_played: self.sounds_played,
+            most_played,
+            playback_ms: self.playback.as_millis() as u64,
+            errors: self.errors,
+        })
+    }
+}
+
+/// Mark the start of the session (at startup)
+pub fn begin() {
+    if let Ok(mut tally) = TALLY.lock() {
+        tally.started_ms = now_ms();
+    }
+}
+
+/// Count a started playback (`sound_id` is empty for plain file playback)
+pub fn record_play(sound_id: &str) {
+    if let Ok(mut tally) = TALLY.lock() {
+        tally.record_play(sound_id);
+    }
+}
+
+/// Add the audible time of a finished playback
+pub fn record_playback_time(played: Duration) {
+    if let Ok(mut tally) = TALLY.lock() {
+        tally.playback += played;
+    }
+}
+
+/// Count an error (a playback that failed to start, a stream error)
+pub fn record_error() {
+    if let Ok(mut tally) = TALLY.lock() {
+        tally.errors += 1;
+    }
+}
+
+/// Recap of the session so far; None if nothing was played and nothing failed
+pub fn finish(sound_name: impl Fn(&str) -> Option<String>) -> Option<SessionRecap> {
+    TALLY
+        .lock()
+        .ok()
+        .and_then(|tally| tally.recap(now_ms(), sound_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_session_has_no_recap() {
+        let tally = SessionTally::new();
+        assert_eq!(tally.recap(1_000, |_| None), None);
+    }
+
+    #[test]
+    fn test_recap_counts_plays_time_and_errors() {
+        let mut tally = SessionTally::new();
+        tally.started_ms = 500;
+        tally.record_play("s1");
+        tally.record_play("s2");
+        tally.record_play("s2");
+        tally.record_play("");
+        tally.playback += Duration::from_millis(2_500);
+        tally.errors += 1;
+
+        let recap = tally
+            .recap(9_000, |id| (id == "s2").then(|| "Horn".to_string()))
+            .unwrap();
+        assert_eq!(recap.started_ms, 500);
+        assert_eq!(recap.ended_ms, 9_000);
+        assert_eq!(recap.sounds_played, 4);
+        assert_eq!(recap.playback_ms, 2_500);
+        assert_eq!(recap.errors, 1);
+        assert_eq!(
+            recap.most_played,
+            Some(MostPlayed {
+                sound_id: "s2".to_string(),
+                sound_name: Some("Horn".to_string()),
+                plays: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_most_played_tie_goes_to_first_played() {
+        let mut tally = SessionTally::new();
+        tally.record_play("s1");
+        tally.record_play("s2");
+        let recap = tally.recap(0, |_| None).unwrap();
+        assert_eq!(recap.most_played.unwrap().sound_id, "s1");
+    }
+}
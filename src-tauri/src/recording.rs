@@ -0,0 +1,322 @@
+//! Recording clips
+//!
+//! Captures a microphone, or everything sent through VB-Cable ("CABLE
+//! Output": the soundboard plus the routed microphone), into a WAV file. The
+//! input callback only pushes samples to a lock-free ring; the recording
+//! thread writes them out every 100 ms, so long recordings don't pile up in
+//! memory. Stopping finishes the file and, for Ogg, converts it to Ogg Opus.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use crate::audio::{self, AudioData, StreamRing, WavWriter};
+use crate::soundpack::unique_path;
+use crate::vbcable;
+
+/// Longest recording; capture pauses there until the recording is stopped
+pub const MAX_RECORDING: Duration = Duration::from_secs(10 * 60);
+
+/// How often captured samples are written to the file
+const WRITE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Audio the capture ring holds, so a slow write doesn't drop samples
+const CAPTURE_RING_SECONDS: usize = 2;
+
+/// Sample rate clips are encoded at as Ogg Opus
+const OPUS_RATE: u32 = 48_000;
+
+/// Running recording
+static RECORDING: Mutex<Option<RecordingHandle>> = Mutex::new(None);
+
+/// Where a recording is captured from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordingSource {
+    /// A capture device (IDs as from `list_microphones`)
+    Device { device_id: String },
+    /// CABLE Output: what is sent over VB-Cable
+    CableMix,
+}
+
+/// File format of a finished recording
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFormat {
+    #[default]
+    Wav,
+    /// Ogg Opus, much smaller than WAV
+    Ogg,
+}
+
+/// State of the running recording (`get_recording_status`)
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingStatus {
+    pub source: RecordingSource,
+    pub format: RecordingFormat,
+    pub elapsed_ms: u64,
+    /// Capture reached `MAX_RECORDING` and stopped
+    pub limit_reached: bool,
+}
+
+/// Handle for controlling the running recording
+struct RecordingHandle {
+    source: RecordingSource,
+    format: RecordingFormat,
+    /// WAV file being written
+    path: PathBuf,
+    started: Instant,
+    stop_signal: Arc<AtomicBool>,
+    limit_reached: Arc<AtomicBool>,
+    thread_handle: JoinHandle<Result<(), String>>,
+}
+
+/// Start recording from `source` into a new file in `dir`
+///
+/// Fails if a recording is already running or the device can't be opened.
+pub fn start(source: RecordingSource, format: RecordingFormat, dir: &Path) -> Result<(), String> {
+    let mut current = RECORDING.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if current.is_some() {
+        return Err("A recording is already running".to_string());
+    }
+
+    let device = match &source {
+        RecordingSource::Device { device_id } => vbcable::find_capture_device(device_id)
+            .ok_or_else(|| format!("Capture device not found: {}", device_id))?,
+        RecordingSource::CableMix => vbcable::find_cable_output_device()
+            .ok_or("CABLE Output device not found. Is VB-Cable installed?")?,
+    };
+    let config = device
+        .default_input_config()
+        .map_err(|e| format!("No input config for recording device: {}", e))?;
+    let channels = config.channels();
+    let sample_rate = config.sample_rate().0;
+
+    let path = unique_path(dir, "Recording.wav");
+    let mut writer = WavWriter::create(&path, channels, sample_rate)?;
+    let max_samples = MAX_RECORDING.as_secs() as usize * sample_rate as usize * channels as usize;
+
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let limit_reached = Arc::new(AtomicBool::new(false));
+    let (started_tx, started_rx) = mpsc::channel::<Result<(), String>>();
+    let thread_stop = stop_signal.clone();
+    let thread_limit = limit_reached.clone();
+
+    // The stream is created on the thread that keeps it alive
+    let spawned = thread::Builder::new()
+        .name("recording".to_string())
+        .spawn(move || {
+            let capacity = sample_rate as usize * channels as usize * CAPTURE_RING_SECONDS;
+            let captured = Arc::new(StreamRing::new(capacity, channels));
+            let captured_input = captured.clone();
+            let overrun = Arc::new(AtomicBool::new(false));
+            let overrun_input = overrun.clone();
+            let stream = device
+                .build_input_stream(
+                    &config.into(),
+                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                        // Whole frames only, so a full ring never shifts channels
+                        let room = capacity - captured_input.buffered();
+                        let fits = data.len().min(room - room % channels as usize);
+                        captured_input.push(&data[..fits]);
+                        if fits < data.len() {
+                            overrun_input.store(true, Ordering::Relaxed);
+                        }
+                    },
+                    move |err| {
+                        error!("Recording stream error: {}", err);
+                    },
+                    None,
+                )
+                .map_err(|e| format!("Failed to open recording device: {}", e))
+                .and_then(|stream| {
+                    stream
+                        .play()
+                        .map(|_| stream)
+                        .map_err(|e| format!("Failed to start recording: {}", e))
+                });
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = started_tx.send(Err(e.clone()));
+                    return Err(e);
+                }
+            };
+            let _ = started_tx.send(Ok(()));
+
+            let mut written = 0;
+            let mut chunk = Vec::new();
+            let mut drain = |writer: &mut WavWriter| -> Result<(), String> {
+                chunk.resize(captured.buffered(), 0.0);
+                captured.pop(&mut chunk);
+                if overrun.swap(false, Ordering::Relaxed) {
+                    warn!("Recording fell behind, captured samples were dropped");
+                }
+                let room = max_samples - written;
+                if chunk.len() > room && !thread_limit.swap(true, Ordering::Relaxed) {
+                    warn!("Recording reached {:?}, capture stopped", MAX_RECORDING);
+                }
+                let chunk = &chunk[..chunk.len().min(room)];
+                written += chunk.len();
+                writer.write(chunk)
+            };
+            while !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(WRITE_INTERVAL);
+                drain(&mut writer)?;
+            }
+            drop(stream);
+            // Samples captured since the last write
+            drain(&mut writer)?;
+            writer.finish()
+        });
+    let thread_handle = match spawned {
+        Ok(handle) => handle,
+        Err(e) => {
+            let _ = std::fs::remove_file(&path);
+            return Err(format!("Failed to start recording thread: {}", e));
+        }
+    };
+
+    match started_rx.recv() {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+        Err(_) => {
+            let _ = std::fs::remove_file(&path);
+            return Err("Recording thread ended unexpectedly".to_string());
+        }
+    }
+
+    info!(
+        "Recording {:?} to {:?} ({} Hz, {} ch)",
+        source, path, sample_rate, channels
+    );
+    *current = Some(RecordingHandle {
+        source,
+        format,
+        path,
+        started: Instant::now(),
+        stop_signal,
+        limit_reached,
+        thread_handle,
+    });
+    Ok(())
+}
+
+/// Stop the recording and finish its file, returning the file's path
+///
+/// Ogg recordings are converted here, which takes a moment for long ones.
+pub fn stop() -> Result<PathBuf, String> {
+    let handle = RECORDING
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .take()
+        .ok_or("No recording is running")?;
+    handle.stop_signal.store(true, Ordering::Relaxed);
+    handle
+        .thread_handle
+        .join()
+        .map_err(|_| "Recording thread panicked".to_string())??;
+    info!(
+        "Recording stopped after {:?}: {:?}",
+        handle.started.elapsed(),
+        handle.path
+    );
+
+    match handle.format {
+        RecordingFormat::Wav => Ok(handle.path),
+        RecordingFormat::Ogg => {
+            let dir = handle.path.parent().unwrap_or(Path::new("."));
+            let stem = handle
+                .path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Recording".to_string());
+            let ogg_path = unique_path(dir, &format!("{}.ogg", stem));
+            match convert_to_ogg(&handle.path, &ogg_path) {
+                Ok(()) => {
+                    if let Err(e) = std::fs::remove_file(&handle.path) {
+                        warn!("Failed to remove {:?}: {}", handle.path, e);
+                    }
+                    Ok(ogg_path)
+                }
+                Err(e) => {
+                    // The WAV is still a usable clip
+                    warn!("Keeping recording as WAV: {}", e);
+                    Ok(handle.path)
+                }
+            }
+        }
+    }
+}
+
+/// State of the running recording, `None` if none is running
+pub fn status() -> Option<RecordingStatus> {
+    let current = RECORDING.lock().ok()?;
+    current.as_ref().map(|handle| RecordingStatus {
+        source: handle.source.clone(),
+        format: handle.format,
+        elapsed_ms: handle.started.elapsed().min(MAX_RECORDING).as_millis() as u64,
+        limit_reached: handle.limit_reached.load(Ordering::Relaxed),
+    })
+}
+
+/// Encode a finished WAV recording as Ogg Opus
+fn convert_to_ogg(wav: &Path, ogg: &Path) -> Result<(), String> {
+    let recorded = audio::decode_audio_file(&wav.to_string_lossy()).map_err(|e| e.to_string())?;
+    let audio = audio::resample(&to_stereo_at_most(recorded), OPUS_RATE);
+    let bytes = audio::encode_ogg_opus(&audio)?;
+    std::fs::write(ogg, bytes).map_err(|e| format!("Failed to write {}: {}", ogg.display(), e))
+}
+
+/// Audio with more than two channels mixed down to mono (Opus files here
+/// are mono or stereo)
+fn to_stereo_at_most(audio: AudioData) -> AudioData {
+    let channels = audio.channels as usize;
+    if channels <= 2 {
+        return audio;
+    }
+    let samples = audio
+        .samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+    AudioData {
+        samples,
+        sample_rate: audio.sample_rate,
+        channels: 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surround_is_mixed_to_mono() {
+        let audio = AudioData {
+            samples: vec![0.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.3, 0.3, 0.0, 0.0, 0.0, 0.0],
+            sample_rate: 48_000,
+            channels: 6,
+        };
+        let mixed = to_stereo_at_most(audio);
+        assert_eq!(mixed.channels, 1);
+        assert_eq!(mixed.samples.len(), 2);
+        assert!(mixed.samples.iter().all(|s| (s - 0.1).abs() < 1e-6));
+
+        let stereo = AudioData {
+            samples: vec![0.1, 0.2],
+            sample_rate: 48_000,
+            channels: 2,
+        };
+        assert_eq!(to_stereo_at_most(stereo).samples, vec![0.1, 0.2]);
+    }
+}
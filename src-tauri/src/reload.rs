@@ -0,0 +1,464 @@
+//! Hot-reload of data files edited outside the app
+//!
+//! `sounds.json` and `hotkeys.json` are polled for changes. Content the app
+//! loaded or wrote itself is recognized by its fingerprint, so only outside
+//! edits (by hand or by a sync tool) trigger a reload. Saves check the
+//! fingerprint as well and refuse to overwrite an edit that hasn't been
+//! picked up yet.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use tracing::{debug, error, info, warn};
+
+use crate::hotkeys::{self, HotkeyMappings};
+use crate::sounds::{self, SoundLibrary};
+use crate::AppState;
+
+/// How often the data files are checked for outside changes
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fingerprints of the content the app last loaded or wrote
+static KNOWN: Mutex<KnownContent> = Mutex::new(KnownContent::new());
+
+/// Data file that can be reloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataFile {
+    Sounds,
+    Hotkeys,
+}
+
+impl DataFile {
+    fn file_name(self) -> &'static str {
+        match self {
+            DataFile::Sounds => "sounds.json",
+            DataFile::Hotkeys => "hotkeys.json",
+        }
+    }
+}
+
+/// Payload of the `data-file-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct DataFileChanged {
+    pub file: DataFile,
+    /// False when the edit was rejected (in-memory state kept)
+    pub reloaded: bool,
+    /// Why the edit was rejected
+    pub error: Option<String>,
+}
+
+/// Remember content the app loaded or wrote itself
+pub fn remember(file: DataFile, content: &str) {
+    if let Ok(mut known) = KNOWN.lock() {
+        known.set(file, fingerprint(content));
+    }
+}
+
+/// Fail if `path` was edited outside the app since it was last loaded or saved
+///
+/// Called before every save, so an outside edit that the watcher hasn't
+/// reloaded yet is never overwritten.
+pub fn check_unmodified(file: DataFile, path: &Path) -> Result<(), String> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        // Missing or unreadable: nothing to protect
+        return Ok(());
+    };
+    let modified = KNOWN
+        .lock()
+        .map(|known| known.is_external(file, fingerprint(&content)))
+        .unwrap_or(false);
+    if modified {
+        Err(format!(
+            "{} was changed outside SonicDeck; it is being reloaded, please try again",
+            file.file_name()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Spawn the background thread that reloads edited data files
+pub fn spawn_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut watched: Vec<WatchedFile> = [DataFile::Sounds, DataFile::Hotkeys]
+            .into_iter()
+            .filter_map(|file| match data_file_path(&app, file) {
+                Ok(path) => Some(WatchedFile::new(file, path)),
+                Err(e) => {
+                    error!("Cannot watch {}: {}", file.file_name(), e);
+                    None
+                }
+            })
+            .collect();
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            for watched in &mut watched {
+                // Follow profile switches
+                if let Ok(path) = data_file_path(&app, watched.file) {
+                    if path != watched.path {
+                        *watched = WatchedFile::new(watched.file, path);
+                    }
+                }
+                if let Some(content) = watched.poll() {
+                    handle_change(&app, watched.file, &content);
+                }
+            }
+        }
+    });
+}
+
+/// Replace the library or hotkeys with a restored backup and queue it for saving
+///
+/// The content is validated like an outside edit; `data-file-changed` tells
+/// the frontend to refresh.
+pub fn restore(app: &tauri::AppHandle, file: DataFile, content: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    match file {
+        DataFile::Sounds => {
+            reload_sounds(app, content)?;
+            let library = state.read_sounds().clone();
+            state.update_and_save_sounds(app, library)?;
+        }
+        DataFile::Hotkeys => {
+            reload_hotkeys(app, content)?;
+            let mappings = state.read_hotkeys().clone();
+            state.update_and_save_hotkeys(app, mappings)?;
+        }
+    }
+    info!("{} restored from a backup", file.file_name());
+
+    let change = DataFileChanged {
+        file,
+        reloaded: true,
+        error: None,
+    };
+    if let Err(e) = app.emit("data-file-changed", &change) {
+        error!("Failed to emit data file change event: {}", e);
+    }
+    Ok(())
+}
+
+fn data_file_path(app: &tauri::AppHandle, file: DataFile) -> Result<PathBuf, String> {
+    match file {
+        DataFile::Sounds => sounds::get_sounds_path(app),
+        DataFile::Hotkeys => hotkeys::get_hotkeys_path(app),
+    }
+}
+
+/// Reload a changed file if the edit came from outside the app
+fn handle_change(app: &tauri::AppHandle, file: DataFile, content: &str) {
+    let hash = fingerprint(content);
+    let is_new = KNOWN
+        .lock()
+        .map(|mut known| known.is_external(file, hash) && known.first_report(file, hash))
+        .unwrap_or(false);
+    if !is_new {
+        return;
+    }
+
+    info!("{} changed outside the app, reloading", file.file_name());
+    let result = match file {
+        DataFile::Sounds => reload_sounds(app, content),
+        DataFile::Hotkeys => reload_hotkeys(app, content),
+    };
+
+    let change = match result {
+        Ok(()) => {
+            remember(file, content);
+            DataFileChanged {
+                file,
+                reloaded: true,
+                error: None,
+            }
+        }
+        Err(e) => {
+            // Saves stay blocked until the file is fixed, so the edit isn't lost
+            warn!("Rejected outside edit of {}: {}", file.file_name(), e);
+            DataFileChanged {
+                file,
+                reloaded: false,
+                error: Some(e),
+            }
+        }
+    };
+
+    if let Err(e) = app.emit("data-file-changed", &change) {
+        error!("Failed to emit data file change event: {}", e);
+    }
+}
+
+fn reload_sounds(app: &tauri::AppHandle, content: &str) -> Result<(), String> {
+    let mut library: SoundLibrary =
+        serde_json::from_str(content).map_err(|e| format!("Invalid sounds file: {}", e))?;
+    validate_library(&library)?;
+    sounds::resolve_stored_paths(&mut library, &crate::profiles::app_data_dir(app)?);
+
+    let state = app.state::<AppState>();
+    *state.write_sounds() = library;
+    debug!("Sound library reloaded from disk");
+    Ok(())
+}
+
+fn reload_hotkeys(app: &tauri::AppHandle, content: &str) -> Result<(), String> {
+    let mut mappings: HotkeyMappings =
+        serde_json::from_str(content).map_err(|e| format!("Invalid hotkeys file: {}", e))?;
+    hotkeys::normalize_mappings(&mut mappings);
+    validate_hotkeys(&mappings)?;
+
+    let state = app.state::<AppState>();
+    let previous: HashSet<String> = hotkeys::all_hotkeys(&state.read_hotkeys()).collect();
+    let current: HashSet<String> = hotkeys::all_hotkeys(&mappings).collect();
+    *state.write_hotkeys() = mappings;
+
+    #[cfg(desktop)]
+    update_registrations(app, &previous, &current);
+    debug!(
+        "Hotkeys reloaded from disk ({} -> {} bindings)",
+        previous.len(),
+        current.len()
+    );
+    Ok(())
+}
+
+/// Register added and unregister removed global shortcuts
+#[cfg(desktop)]
+pub fn update_registrations(
+    app: &tauri::AppHandle,
+    previous: &HashSet<String>,
+    current: &HashSet<String>,
+) {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+    for hotkey in previous.difference(current) {
+        if let Ok(shortcut) = hotkey.parse::<Shortcut>() {
+            let _ = app.global_shortcut().unregister(shortcut);
+        }
+    }
+    for hotkey in current.difference(previous) {
+        match hotkey.parse::<Shortcut>() {
+            Ok(shortcut) => {
+                if let Err(e) = app.global_shortcut().register(shortcut) {
+                    error!("Failed to register reloaded hotkey '{}': {}", hotkey, e);
+                }
+            }
+            Err(_) => error!("Failed to parse reloaded hotkey: {}", hotkey),
+        }
+    }
+}
+
+/// Reject libraries the app can't work with (duplicate or dangling IDs)
+fn validate_library(library: &SoundLibrary) -> Result<(), String> {
+    let mut category_ids = HashSet::new();
+    for category in &library.categories {
+        if !category_ids.insert(&category.id) {
+            return Err(format!("Duplicate category ID: {}", category.id.as_str()));
+        }
+    }
+
+    let mut sound_ids = HashSet::new();
+    for sound in &library.sounds {
+        if !sound_ids.insert(&sound.id) {
+            return Err(format!("Duplicate sound ID: {}", sound.id.as_str()));
+        }
+        if !category_ids.contains(&sound.category_id) {
+            return Err(format!(
+                "Sound '{}' refers to unknown category {}",
+                sound.name,
+                sound.category_id.as_str()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject mappings that bind one hotkey to both a sound and an action
+fn validate_hotkeys(mappings: &HotkeyMappings) -> Result<(), String> {
+    match mappings
+        .actions
+        .keys()
+        .find(|hotkey| mappings.mappings.contains_key(*hotkey))
+    {
+        Some(hotkey) => Err(format!(
+            "Hotkey {} is bound to both a sound and an action",
+            hotkey
+        )),
+        None => Ok(()),
+    }
+}
+
+fn fingerprint(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Last modification seen for a watched file
+struct WatchedFile {
+    file: DataFile,
+    path: PathBuf,
+    last_seen: Option<(SystemTime, u64)>,
+}
+
+impl WatchedFile {
+    fn new(file: DataFile, path: PathBuf) -> Self {
+        let last_seen = Self::stamp(&path);
+        Self {
+            file,
+            path,
+            last_seen,
+        }
+    }
+
+    fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    /// Content of the file if it changed since the last poll
+    fn poll(&mut self) -> Option<String> {
+        let stamp = Self::stamp(&self.path);
+        if stamp == self.last_seen {
+            return None;
+        }
+        self.last_seen = stamp;
+        std::fs::read_to_string(&self.path).ok()
+    }
+}
+
+/// Fingerprints per data file
+#[derive(Debug)]
+struct KnownContent {
+    /// Content the app loaded or wrote
+    known: [Option<u64>; 2],
+    /// Last outside edit that was reported (avoids repeating the event)
+    reported: [Option<u64>; 2],
+}
+
+impl KnownContent {
+    const fn new() -> Self {
+        Self {
+            known: [None; 2],
+            reported: [None; 2],
+        }
+    }
+
+    fn set(&mut self, file: DataFile, hash: u64) {
+        self.known[file as usize] = Some(hash);
+        self.reported[file as usize] = None;
+    }
+
+    fn is_external(&self, file: DataFile, hash: u64) -> bool {
+        self.known[file as usize] != Some(hash)
+    }
+
+    /// True the first time an outside edit is seen
+    fn first_report(&mut self, file: DataFile, hash: u64) -> bool {
+        let reported = &mut self.reported[file as usize];
+        if *reported == Some(hash) {
+            return false;
+        }
+        *reported = Some(hash);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sounds::{Category, CategoryId, SoundId};
+
+    fn add(library: &mut SoundLibrary, category_id: &str) {
+        sounds::add_sound(
+            library,
+            "Sound".to_string(),
+            "C:/sounds/sound.mp3".to_string(),
+            CategoryId::from_string(category_id.to_string()),
+            None,
+            None,
+        );
+    }
+
+    // ========== Fingerprint tracking ==========
+
+    #[test]
+    fn test_own_content_is_not_external() {
+        let mut known = KnownContent::new();
+        known.set(DataFile::Sounds, fingerprint("{}"));
+
+        assert!(!known.is_external(DataFile::Sounds, fingerprint("{}")));
+        assert!(known.is_external(DataFile::Sounds, fingerprint("{ }")));
+        // Files are tracked separately
+        assert!(known.is_external(DataFile::Hotkeys, fingerprint("{}")));
+    }
+
+    #[test]
+    fn test_outside_edit_reported_once() {
+        let mut known = KnownContent::new();
+        let edit = fingerprint("edited");
+
+        assert!(known.first_report(DataFile::Hotkeys, edit));
+        assert!(!known.first_report(DataFile::Hotkeys, edit));
+
+        // Once the app takes over the file again, a new edit is reported
+        known.set(DataFile::Hotkeys, fingerprint("saved"));
+        assert!(known.first_report(DataFile::Hotkeys, edit));
+    }
+
+    // ========== Validation ==========
+
+    #[test]
+    fn test_validate_library_duplicate_sound() {
+        let mut library = SoundLibrary::default();
+        add(&mut library, "default");
+        assert!(validate_library(&library).is_ok());
+
+        let duplicate = library.sounds[0].clone();
+        library.sounds.push(duplicate);
+        assert!(validate_library(&library)
+            .unwrap_err()
+            .contains("Duplicate sound ID"));
+    }
+
+    #[test]
+    fn test_validate_library_categories() {
+        let mut library = SoundLibrary::default();
+        add(&mut library, "missing");
+        assert!(validate_library(&library)
+            .unwrap_err()
+            .contains("unknown category"));
+
+        library.sounds.clear();
+        library.categories.push(Category {
+            id: CategoryId::from_string("default".to_string()),
+            name: "Copy".to_string(),
+            icon: None,
+            sort_order: 1,
+        });
+        assert!(validate_library(&library)
+            .unwrap_err()
+            .contains("Duplicate category ID"));
+    }
+
+    #[test]
+    fn test_validate_hotkeys_conflict() {
+        let mut mappings = HotkeyMappings::default();
+        mappings
+            .mappings
+            .insert("Ctrl+1".to_string(), SoundId::new());
+        assert!(validate_hotkeys(&mappings).is_ok());
+
+        mappings.actions.insert(
+            "Ctrl+1".to_string(),
+            hotkeys::HotkeyAction::ToggleNormalization,
+        );
+        assert!(validate_hotkeys(&mappings).is_err());
+    }
+}
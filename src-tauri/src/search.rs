@@ -0,0 +1,274 @@
+//! Fuzzy sound search
+//!
+//! Matches the query as a case-insensitive subsequence of the sound name, its
+//! tags and its file name, and ranks hits by how "tight" the match is:
+//! consecutive characters, matches at word starts and a match at the very
+//! beginning score higher. A name match beats an equally good tag match,
+//! which beats a file name match.
+
+use serde::Serialize;
+
+use crate::sounds::{self, Sound, SoundLibrary};
+
+/// Default number of results returned to the quick search popup
+pub const DEFAULT_SEARCH_LIMIT: usize = 8;
+
+const SCORE_MATCH: i32 = 1;
+const SCORE_CONSECUTIVE: i32 = 8;
+const SCORE_WORD_START: i32 = 6;
+const SCORE_PREFIX: i32 = 12;
+const SCORE_FAVORITE: i32 = 3;
+const PENALTY_TAG: i32 = 10;
+const PENALTY_FILE_NAME: i32 = 20;
+
+/// Which part of a sound the query matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    Name,
+    Tag,
+    FileName,
+}
+
+/// A ranked search hit
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub sound: Sound,
+    pub score: i32,
+    /// None for listings without a query
+    pub matched: Option<MatchField>,
+}
+
+/// Score `text` against `query`, or None if it doesn't match
+///
+/// Every query character must appear in order; whitespace in the query is
+/// ignored so "air h" finds "Air Horn".
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    score_prepared(&prepare_query(query), text)
+}
+
+/// Lowercased query characters without whitespace
+fn prepare_query(query: &str) -> Vec<char> {
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn score_prepared(query: &[char], text: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (i, c) in text.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if *c != query[next] {
+            continue;
+        }
+
+        score += SCORE_MATCH;
+        if i == 0 {
+            score += SCORE_PREFIX;
+        } else if !text[i - 1].is_alphanumeric() {
+            score += SCORE_WORD_START;
+        }
+        if previous_match.is_some_and(|p| p + 1 == i) {
+            score += SCORE_CONSECUTIVE;
+        }
+
+        previous_match = Some(i);
+        next += 1;
+    }
+
+    // Prefer shorter names when the match is otherwise equal
+    (next == query.len()).then(|| score * 100 - text.len() as i32)
+}
+
+/// Search the library, best matches first
+///
+/// Only sounds carrying all of `tags` match. An empty query lists all of
+/// those, or the favorites if no tags are given, so the popup isn't blank
+/// when it opens.
+pub fn search(
+    library: &SoundLibrary,
+    query: &str,
+    tags: &[String],
+    limit: usize,
+) -> Vec<SearchResult> {
+    let candidates = library
+        .sounds
+        .iter()
+        .filter(|sound| sounds::has_tags(sound, tags));
+    // Rank borrowed hits, only the ones returned are cloned
+    let mut hits: Vec<(&Sound, i32, Option<MatchField>)> = if query.trim().is_empty() {
+        candidates
+            .filter(|sound| sound.is_favorite || !tags.is_empty())
+            .map(|sound| (sound, 0, None))
+            .collect()
+    } else {
+        let query = prepare_query(query);
+        candidates
+            .filter_map(|sound| {
+                let (score, field) = score_sound(&query, sound)?;
+                let bonus = if sound.is_favorite { SCORE_FAVORITE } else { 0 };
+                Some((sound, score + bonus * 100, Some(field)))
+            })
+            .collect()
+    };
+
+    hits.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| a.0.name.to_lowercase().cmp(&b.0.name.to_lowercase()))
+    });
+    hits.into_iter()
+        .take(limit)
+        .map(|(sound, score, matched)| SearchResult {
+            sound: sound.clone(),
+            score,
+            matched,
+        })
+        .collect()
+}
+
+/// Best match of the query over a sound's name, tags and file name
+fn score_sound(query: &[char], sound: &Sound) -> Option<(i32, MatchField)> {
+    let name = score_prepared(query, &sound.name).map(|s| (s, MatchField::Name));
+    let tag = sound
+        .tags
+        .iter()
+        .filter_map(|tag| score_prepared(query, tag))
+        .max()
+        .map(|s| (s - PENALTY_TAG * 100, MatchField::Tag));
+    let file_name = std::path::Path::new(&sound.file_path)
+        .file_stem()
+        .and_then(|stem| score_prepared(query, &stem.to_string_lossy()))
+        .map(|s| (s - PENALTY_FILE_NAME * 100, MatchField::FileName));
+
+    // Ties go to the earlier field
+    [name, tag, file_name].into_iter().flatten().fold(
+        None,
+        |best: Option<(i32, MatchField)>, hit| match best {
+            Some(best) if best.0 >= hit.0 => Some(best),
+            _ => Some(hit),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sounds::{self, CategoryId};
+
+    fn library(names: &[&str]) -> SoundLibrary {
+        let mut library = SoundLibrary::default();
+        let category = CategoryId::new();
+        for name in names {
+            sounds::add_sound(
+                &mut library,
+                name.to_string(),
+                format!("C:/sounds/{}.mp3", name),
+                category.clone(),
+                None,
+                None,
+            );
+        }
+        library
+    }
+
+    #[test]
+    fn test_fuzzy_score_requires_subsequence() {
+        assert!(fuzzy_score("ahn", "Air Horn").is_some());
+        assert!(fuzzy_score("air h", "Air Horn").is_some());
+        assert!(fuzzy_score("nha", "Air Horn").is_none());
+        assert_eq!(fuzzy_score("", "Air Horn"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_tight_matches() {
+        let prefix = fuzzy_score("air", "Air Horn").unwrap();
+        let scattered = fuzzy_score("air", "Applause In Room").unwrap();
+        assert!(prefix > scattered);
+
+        let word_start = fuzzy_score("horn", "Air Horn").unwrap();
+        let inner = fuzzy_score("horn", "Thorny").unwrap();
+        assert!(word_start > inner);
+    }
+
+    #[test]
+    fn test_search_ranks_and_limits() {
+        let library = library(&["Drumroll", "Hair Dryer", "Sad Trombone", "Air Horn"]);
+
+        let results = search(&library, "air", &[], 10);
+        let names: Vec<_> = results.iter().map(|r| r.sound.name.as_str()).collect();
+        assert_eq!(names, vec!["Air Horn", "Hair Dryer"]);
+
+        assert_eq!(search(&library, "r", &[], 1).len(), 1);
+    }
+
+    #[test]
+    fn test_empty_query_lists_favorites() {
+        let mut library = library(&["Drumroll", "Air Horn"]);
+        library.sounds[1].is_favorite = true;
+
+        let results = search(&library, "  ", &[], 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].sound.name, "Air Horn");
+    }
+
+    #[test]
+    fn test_search_matches_tags_and_file_names() {
+        let mut library = library(&["Air Horn", "Drumroll"]);
+        library.sounds[1].tags = vec!["victory".to_string()];
+        library.sounds[1].file_path = "C:/sounds/tada_fanfare.mp3".to_string();
+
+        let results = search(&library, "victory", &[], 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].sound.name, "Drumroll");
+        assert_eq!(results[0].matched, Some(MatchField::Tag));
+
+        let results = search(&library, "fanfare", &[], 10);
+        assert_eq!(results[0].matched, Some(MatchField::FileName));
+
+        // The file name "Air Horn.mp3" matches too, the name ranks higher
+        let results = search(&library, "horn", &[], 10);
+        assert_eq!(results[0].matched, Some(MatchField::Name));
+    }
+
+    #[test]
+    fn test_name_match_outranks_tag_match() {
+        let mut library = library(&["Victory Fanfare", "Drumroll"]);
+        library.sounds[1].tags = vec!["victory".to_string()];
+
+        let results = search(&library, "victory", &[], 10);
+        let names: Vec<_> = results.iter().map(|r| r.sound.name.as_str()).collect();
+        assert_eq!(names, vec!["Victory Fanfare", "Drumroll"]);
+    }
+
+    #[test]
+    fn test_search_filters_by_tags() {
+        let mut library = library(&["Air Horn", "Airplane", "Applause"]);
+        library.sounds[0].tags = vec!["meme".to_string(), "loud".to_string()];
+        library.sounds[2].tags = vec!["Meme".to_string()];
+
+        let meme = vec!["meme".to_string()];
+        let results = search(&library, "air", &meme, 10);
+        let names: Vec<_> = results.iter().map(|r| r.sound.name.as_str()).collect();
+        assert_eq!(names, vec!["Air Horn"]);
+
+        // Without a query every tagged sound is listed
+        let results = search(&library, "", &meme, 10);
+        let names: Vec<_> = results.iter().map(|r| r.sound.name.as_str()).collect();
+        assert_eq!(names, vec!["Air Horn", "Applause"]);
+
+        let both = vec!["meme".to_string(), "loud".to_string()];
+        assert_eq!(search(&library, "", &both, 10).len(), 1);
+    }
+}
@@ -0,0 +1,246 @@
+//! Session state persistence
+//!
+//! Stores transient, UI-relevant state (last selected category, last used
+//! devices per profile, window geometry, remembered playback positions, the
+//! recap of the last session) in `session.json`, separate from user-edited
+//! settings so it can be rewritten freely.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::Manager;
+
+use crate::recap::SessionRecap;
+use crate::{CategoryId, DeviceId};
+
+/// Profile name used until profiles are configured
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Main window position and size (physical pixels)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// Output devices last used with a profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileDevices {
+    pub monitor_device_id: Option<DeviceId>,
+    pub broadcast_device_id: Option<DeviceId>,
+}
+
+/// Transient session state restored at startup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Category selected in the dashboard when the app was last used
+    #[serde(default)]
+    pub last_category_id: Option<CategoryId>,
+    /// Last used output devices, keyed by profile name
+    #[serde(default)]
+    pub devices_by_profile: HashMap<String, ProfileDevices>,
+    /// Main window geometry
+    #[serde(default)]
+    pub window: Option<WindowGeometry>,
+    /// Where sounds with `resume_playback` were stopped (ms), keyed by sound ID
+    #[serde(default)]
+    pub resume_positions: HashMap<String, u64>,
+    /// Recap of the last session, until it was shown
+    #[serde(default)]
+    pub last_recap: Option<SessionRecap>,
+    /// Profile switched to last, loaded at startup unless `--profile` names one
+    #[serde(default)]
+    pub active_profile: Option<String>,
+}
+
+impl SessionState {
+    /// Record the devices used with a profile
+    ///
+    /// Returns true if the stored value changed.
+    pub fn record_devices(&mut self, profile: &str, devices: ProfileDevices) -> bool {
+        if self.devices_by_profile.get(profile) == Some(&devices) {
+            return false;
+        }
+        self.devices_by_profile.insert(profile.to_string(), devices);
+        true
+    }
+
+    /// Position to resume a sound from, if one is remembered inside its trim range
+    pub fn resume_position(
+        &self,
+        sound_id: &str,
+        trim_start_ms: Option<u64>,
+        trim_end_ms: Option<u64>,
+    ) -> Option<u64> {
+        let position = *self.resume_positions.get(sound_id)?;
+        let after_start = position > trim_start_ms.unwrap_or(0);
+        let before_end = !matches!(trim_end_ms, Some(end) if position >= end);
+        (after_start && before_end).then_some(position)
+    }
+
+    /// Remember where a sound was stopped, or forget it (`None`) once it
+    /// played to the end
+    pub fn record_position(&mut self, sound_id: &str, position_ms: Option<u64>) {
+        match position_ms {
+            Some(position) => {
+                self.resume_positions.insert(sound_id.to_string(), position);
+            }
+            None => {
+                self.resume_positions.remove(sound_id);
+            }
+        }
+    }
+}
+
+/// Get the path to the session file
+pub fn get_session_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    // Ensure directory exists
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data_dir.join("session.json"))
+}
+
+/// Load session state from disk
+///
+/// A corrupt session file is not fatal: it is logged and replaced with defaults.
+pub fn load(app_handle: &tauri::AppHandle) -> Result<SessionState, String> {
+    let session_path = get_session_path(app_handle)?;
+
+    if !session_path.exists() {
+        return Ok(SessionState::default());
+    }
+
+    let content = std::fs::read_to_string(&session_path)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    match serde_json::from_str(&content) {
+        Ok(session) => Ok(session),
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable session file: {}", e);
+            Ok(SessionState::default())
+        }
+    }
+}
+
+/// Save session state to disk (atomic write)
+pub fn save(session: &SessionState, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let session_path = get_session_path(app_handle)?;
+
+    let json = serde_json::to_string_pretty(session)
+        .map_err(|e| format!("Failed to serialize session: {}", e))?;
+
+    crate::persistence::atomic_write(&session_path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(index: usize) -> Option<DeviceId> {
+        Some(DeviceId::from_index(index))
+    }
+
+    #[test]
+    fn test_session_default() {
+        let session = SessionState::default();
+        assert!(session.last_category_id.is_none());
+        assert!(session.devices_by_profile.is_empty());
+        assert!(session.window.is_none());
+        assert!(session.last_recap.is_none());
+        assert!(session.active_profile.is_none());
+    }
+
+    #[test]
+    fn test_session_deserialize_empty_object() {
+        let session: SessionState = serde_json::from_str("{}").unwrap();
+        assert!(session.window.is_none());
+    }
+
+    #[test]
+    fn test_record_devices_reports_changes() {
+        let mut session = SessionState::default();
+        let devices = ProfileDevices {
+            monitor_device_id: device(0),
+            broadcast_device_id: device(2),
+        };
+
+        assert!(session.record_devices(DEFAULT_PROFILE, devices.clone()));
+        assert!(!session.record_devices(DEFAULT_PROFILE, devices.clone()));
+        assert_eq!(
+            session.devices_by_profile.get(DEFAULT_PROFILE),
+            Some(&devices)
+        );
+    }
+
+    #[test]
+    fn test_resume_position_within_trim() {
+        let mut session = SessionState::default();
+        assert_eq!(session.resume_position("s1", None, None), None);
+
+        session.record_position("s1", Some(5_000));
+        assert_eq!(session.resume_position("s1", None, None), Some(5_000));
+        assert_eq!(
+            session.resume_position("s1", Some(1_000), Some(9_000)),
+            Some(5_000)
+        );
+        // Trim changed since the position was remembered
+        assert_eq!(session.resume_position("s1", Some(6_000), None), None);
+        assert_eq!(session.resume_position("s1", None, Some(5_000)), None);
+
+        session.record_position("s1", None);
+        assert_eq!(session.resume_position("s1", None, None), None);
+    }
+
+    #[test]
+    fn test_session_serde_roundtrip() {
+        let mut session = SessionState {
+            window: Some(WindowGeometry {
+                x: 100,
+                y: -20,
+                width: 1280,
+                height: 720,
+                maximized: false,
+            }),
+            last_recap: Some(SessionRecap {
+                started_ms: 1_000,
+                ended_ms: 61_000,
+                sounds_played: 3,
+                most_played: None,
+                playback_ms: 4_200,
+                errors: 0,
+            }),
+            ..Default::default()
+        };
+        session.record_devices(
+            "Streaming",
+            ProfileDevices {
+                monitor_device_id: device(1),
+                broadcast_device_id: None,
+            },
+        );
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: SessionState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.window, session.window);
+        assert_eq!(restored.last_recap, session.last_recap);
+        assert_eq!(
+            restored.devices_by_profile.get("Streaming"),
+            session.devices_by_profile.get("Streaming")
+        );
+    }
+}
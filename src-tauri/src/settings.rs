@@ -3,9 +3,17 @@
 //! Stores settings as JSON in the platform-specific app data directory.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tauri::Manager;
 
+use crate::api_security::ApiToken;
+use crate::app_rules::AppRule;
+use crate::folder_watch::WatchedFolder;
+use crate::i18n::Language;
+use crate::setup::SetupProgress;
+use crate::startup::StartupAction;
+use crate::vbcable::{NoiseReduction, VoiceChanger};
 use crate::DeviceId;
 
 /// Application settings for device routing and preferences
@@ -37,8 +45,181 @@ pub struct AppSettings {
     /// Whether microphone routing is enabled
     #[serde(default)]
     pub microphone_routing_enabled: bool,
+    /// Normalize playback loudness to `normalization_target_lufs`
+    #[serde(default)]
+    pub normalization_enabled: bool,
+    /// Loudness normalization target (LUFS)
+    #[serde(default = "default_normalization_target_lufs")]
+    pub normalization_target_lufs: f32,
+    /// Silence the broadcast output while Discord reports the user muted/deafened
+    #[serde(default)]
+    pub discord_mute_awareness_enabled: bool,
+    /// Client ID of the Discord application used for RPC
+    #[serde(default)]
+    pub discord_client_id: Option<String>,
+    /// Client secret of the Discord application used for RPC
+    #[serde(default)]
+    pub discord_client_secret: Option<String>,
+    /// Serve the local API (trigger feedback events) on 127.0.0.1
+    #[serde(default)]
+    pub api_enabled: bool,
+    /// Local API port
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+    /// Play on the system default device when a saved device is missing
+    #[serde(default = "default_device_fallback_enabled")]
+    pub device_fallback_enabled: bool,
+    /// First-run setup wizard progress
+    #[serde(default)]
+    pub setup_progress: SetupProgress,
+    /// Language for tray menu, error messages and notifications
+    #[serde(default)]
+    pub language: Language,
+    /// Stream band magnitudes of the monitor output to the UI visualizer
+    #[serde(default)]
+    pub spectrum_analyzer_enabled: bool,
+    /// Profile passed to autostart launches (`--profile <name>`)
+    #[serde(default)]
+    pub autostart_profile: Option<String>,
+    /// Start hidden in the tray when launched by autostart (`--start-minimized`)
+    #[serde(default)]
+    pub autostart_minimized: bool,
+    /// Actions executed once at startup, in order
+    #[serde(default)]
+    pub startup_actions: Vec<StartupAction>,
+    /// Also play the routed microphone on the monitor device (mic monitoring)
+    #[serde(default)]
+    pub microphone_monitor_enabled: bool,
+    /// Gain of the routed microphone on CABLE Input (0.0 - 2.0)
+    #[serde(default = "default_microphone_gain")]
+    pub microphone_cable_gain: f32,
+    /// Gain of the microphone on the monitor device (0.0 - 2.0)
+    #[serde(default = "default_microphone_gain")]
+    pub microphone_monitor_gain: f32,
+    /// Noise suppression and gate of the routed microphone
+    #[serde(default)]
+    pub noise_reduction: NoiseReduction,
+    /// Effects applied to the routed microphone
+    #[serde(default)]
+    pub voice_changer: VoiceChanger,
+    /// Lower the routed microphone on CABLE Input while sounds play
+    #[serde(default)]
+    pub microphone_duck_enabled: bool,
+    /// How far the microphone is lowered (dB, 0 - 60)
+    #[serde(default = "default_microphone_duck_db")]
+    pub microphone_duck_db: f32,
+    /// Time to lower the microphone when a sound starts (ms)
+    #[serde(default = "default_microphone_duck_attack_ms")]
+    pub microphone_duck_attack_ms: u32,
+    /// Time to bring the microphone back after sounds end (ms)
+    #[serde(default = "default_microphone_duck_release_ms")]
+    pub microphone_duck_release_ms: u32,
+    /// Mix sounds into the routed microphone's CABLE Input stream instead of
+    /// playing them on the broadcast device
+    #[serde(default)]
+    pub soundboard_mic_mix_enabled: bool,
+    /// Tokens for local API clients (the API is closed while empty, unless
+    /// `api_open_local_access` is on)
+    #[serde(default)]
+    pub api_tokens: Vec<ApiToken>,
+    /// Allow local API clients without a token while no token exists
+    #[serde(default)]
+    pub api_open_local_access: bool,
+    /// Triggered sounds wait for the previous one instead of overlapping
+    #[serde(default)]
+    pub queue_mode_enabled: bool,
+    /// Convert sample rates with a band-limited resampler before playback
+    /// (sharper, but sounds take longer to start)
+    #[serde(default)]
+    pub high_quality_resampling: bool,
+    /// Cache sounds converted to the devices' sample rates (uses more memory)
+    #[serde(default)]
+    pub resample_cache_enabled: bool,
+    /// Overall output level of every playback (0.0-1.0), adjustable while playing
+    #[serde(default = "default_master_volume")]
+    pub master_volume: f32,
+    /// Level of the monitor output (0.0-1.0), relative to the sound's volume
+    #[serde(default = "default_output_volume")]
+    pub monitor_volume: f32,
+    /// Level of the broadcast output (0.0-1.0), relative to the sound's volume
+    #[serde(default = "default_output_volume")]
+    pub broadcast_volume: f32,
+    /// Crossfade from a sound's old to its new playback on restart (ms, 0 cuts)
+    #[serde(default = "default_restart_crossfade_ms")]
+    pub restart_crossfade_ms: u32,
+    /// Most sounds playing at once; the oldest stops for a new one
+    #[serde(default = "default_max_concurrent_sounds")]
+    pub max_concurrent_sounds: u32,
+    /// Name of the device each saved device ID refers to, so saved IDs
+    /// follow their device when the enumeration order changes
+    #[serde(default)]
+    pub device_names: HashMap<DeviceId, String>,
+    /// Pass every output through a limiter, so boosted or normalized sounds
+    /// don't clip
+    #[serde(default = "default_output_limiter_enabled")]
+    pub output_limiter_enabled: bool,
+    /// Open output streams with the smallest buffers the device accepts
+    /// (lower trigger-to-sound latency, more risk of crackling). Streams stay
+    /// in WASAPI shared mode, so the engine period still applies.
+    #[serde(default, alias = "low_latency_output")]
+    pub small_output_buffers: bool,
+    /// What playback does when monitor and broadcast are the same device
+    #[serde(default)]
+    pub identical_device_behavior: IdenticalDeviceBehavior,
+    /// Copy added sounds into the app's `sounds` directory instead of
+    /// referencing them where they are
+    #[serde(default)]
+    pub copy_sounds_to_library: bool,
+    /// Folders whose audio files are added to the library automatically
+    #[serde(default)]
+    pub watched_folders: Vec<WatchedFolder>,
+    /// Switch profile/category while certain applications are focused
+    #[serde(default)]
+    pub app_rules_enabled: bool,
+    /// Rules checked against the foreground application, first match wins
+    #[serde(default)]
+    pub app_rules: Vec<AppRule>,
+}
+
+/// Playback on monitor and broadcast set to the same device
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdenticalDeviceBehavior {
+    /// Play one stream (at the monitor level), no phasing or doubled volume
+    #[default]
+    SingleStream,
+    /// Play both streams anyway
+    BothStreams,
+}
+
+/// A settings combination that likely isn't intended
+/// (returned by `validate_settings`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SettingsIssue {
+    /// Monitor and broadcast are the same device
+    IdenticalOutputDevices {
+        device_id: DeviceId,
+        behavior: IdenticalDeviceBehavior,
+    },
 }
 
+/// Accepted range for the loudness normalization target (LUFS)
+pub const MIN_NORMALIZATION_TARGET_LUFS: f32 = -40.0;
+pub const MAX_NORMALIZATION_TARGET_LUFS: f32 = -5.0;
+
+/// Longest restart crossfade (ms)
+pub const MAX_RESTART_CROSSFADE_MS: u32 = 500;
+
+/// Highest gain for the routed microphone destinations
+pub const MAX_MICROPHONE_GAIN: f32 = 2.0;
+
+/// Deepest mic ducking
+pub const MAX_MICROPHONE_DUCK_DB: f32 = 60.0;
+
+/// Longest mic ducking attack or release
+pub const MAX_MICROPHONE_DUCK_TIME_MS: u32 = 5000;
+
 fn default_volume_multiplier() -> f32 {
     1.0 // Default: disabled (no boost), sounds play at normal Windows volume
 }
@@ -47,6 +228,54 @@ fn default_minimize_to_tray() -> bool {
     true // Default: close minimizes to tray
 }
 
+fn default_normalization_target_lufs() -> f32 {
+    -16.0 // Default: typical voice chat / streaming level
+}
+
+fn default_api_port() -> u16 {
+    7393 // Default: unassigned port, unlikely to clash with other local tools
+}
+
+fn default_device_fallback_enabled() -> bool {
+    true // Default: keep sounds audible when a device is unplugged
+}
+
+fn default_microphone_gain() -> f32 {
+    1.0 // Default: microphone level unchanged
+}
+
+fn default_microphone_duck_db() -> f32 {
+    12.0 // Default: voice stays understandable under the sound
+}
+
+fn default_microphone_duck_attack_ms() -> u32 {
+    20 // Default: quick enough not to cover the sound's start
+}
+
+fn default_microphone_duck_release_ms() -> u32 {
+    400 // Default: no pumping between sounds played in a row
+}
+
+fn default_restart_crossfade_ms() -> u32 {
+    30 // Default: just long enough to avoid the click of a hard cut
+}
+
+fn default_max_concurrent_sounds() -> u32 {
+    16 // Default: plenty for overlapping effects, before the mix turns to mush
+}
+
+fn default_output_limiter_enabled() -> bool {
+    true // Default: a clipping broadcast is worse than a slightly softer peak
+}
+
+fn default_master_volume() -> f32 {
+    1.0
+}
+
+fn default_output_volume() -> f32 {
+    1.0 // Default: both outputs at the sound's volume
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -60,10 +289,76 @@ impl Default for AppSettings {
             autostart_enabled: false,
             microphone_routing_device_id: None,
             microphone_routing_enabled: false,
+            normalization_enabled: false,
+            normalization_target_lufs: default_normalization_target_lufs(),
+            discord_mute_awareness_enabled: false,
+            discord_client_id: None,
+            discord_client_secret: None,
+            api_enabled: false,
+            api_port: default_api_port(),
+            device_fallback_enabled: default_device_fallback_enabled(),
+            setup_progress: SetupProgress::default(),
+            language: Language::default(),
+            spectrum_analyzer_enabled: false,
+            autostart_profile: None,
+            autostart_minimized: false,
+            startup_actions: Vec::new(),
+            microphone_monitor_enabled: false,
+            microphone_cable_gain: default_microphone_gain(),
+            microphone_monitor_gain: default_microphone_gain(),
+            noise_reduction: NoiseReduction::default(),
+            voice_changer: VoiceChanger::default(),
+            microphone_duck_enabled: false,
+            microphone_duck_db: default_microphone_duck_db(),
+            microphone_duck_attack_ms: default_microphone_duck_attack_ms(),
+            microphone_duck_release_ms: default_microphone_duck_release_ms(),
+            soundboard_mic_mix_enabled: false,
+            api_tokens: Vec::new(),
+            api_open_local_access: false,
+            queue_mode_enabled: false,
+            high_quality_resampling: false,
+            resample_cache_enabled: false,
+            master_volume: default_master_volume(),
+            monitor_volume: default_output_volume(),
+            broadcast_volume: default_output_volume(),
+            restart_crossfade_ms: default_restart_crossfade_ms(),
+            max_concurrent_sounds: default_max_concurrent_sounds(),
+            device_names: HashMap::new(),
+            output_limiter_enabled: default_output_limiter_enabled(),
+            small_output_buffers: false,
+            identical_device_behavior: IdenticalDeviceBehavior::default(),
+            copy_sounds_to_library: false,
+            watched_folders: Vec::new(),
+            app_rules_enabled: false,
+            app_rules: Vec::new(),
         }
     }
 }
 
+impl AppSettings {
+    /// Depth of mic ducking in dB, `None` while it's off
+    pub fn microphone_duck(&self) -> Option<f32> {
+        self.microphone_duck_enabled
+            .then_some(self.microphone_duck_db)
+    }
+
+    /// Settings combinations that likely aren't intended
+    pub fn validate(&self) -> Vec<SettingsIssue> {
+        let mut issues = Vec::new();
+        if let (Some(monitor), Some(broadcast)) =
+            (&self.monitor_device_id, &self.broadcast_device_id)
+        {
+            if monitor == broadcast {
+                issues.push(SettingsIssue::IdenticalOutputDevices {
+                    device_id: monitor.clone(),
+                    behavior: self.identical_device_behavior,
+                });
+            }
+        }
+        issues
+    }
+}
+
 /// Get the path to the settings file
 pub fn get_settings_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app_handle
@@ -103,7 +398,7 @@ pub fn save(settings: &AppSettings, app_handle: &tauri::AppHandle) -> Result<(),
     let json = serde_json::to_string_pretty(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-    crate::persistence::atomic_write(&settings_path, &json)
+    crate::persistence::atomic_write_with_backup(&settings_path, &json)
 }
 
 // ============================================================================
@@ -128,6 +423,12 @@ mod tests {
         assert!(default_minimize_to_tray());
     }
 
+    #[test]
+    fn test_default_normalization_target_in_range() {
+        let target = default_normalization_target_lufs();
+        assert!((MIN_NORMALIZATION_TARGET_LUFS..=MAX_NORMALIZATION_TARGET_LUFS).contains(&target));
+    }
+
     // -------------------------------------------------------------------------
     // AppSettings::default() Tests
     // -------------------------------------------------------------------------
@@ -146,6 +447,51 @@ mod tests {
         assert!(!settings.autostart_enabled);
         assert_eq!(settings.microphone_routing_device_id, None);
         assert!(!settings.microphone_routing_enabled);
+        assert!(!settings.normalization_enabled);
+        assert_eq!(settings.normalization_target_lufs, -16.0);
+        assert!(!settings.discord_mute_awareness_enabled);
+        assert_eq!(settings.discord_client_id, None);
+        assert_eq!(settings.discord_client_secret, None);
+        assert!(!settings.api_enabled);
+        assert_eq!(settings.api_port, 7393);
+        assert!(settings.device_fallback_enabled);
+        assert!(settings.setup_progress.completed.is_empty());
+        assert_eq!(settings.language, Language::English);
+        assert!(!settings.spectrum_analyzer_enabled);
+        assert_eq!(settings.autostart_profile, None);
+        assert!(!settings.autostart_minimized);
+        assert!(settings.startup_actions.is_empty());
+        assert!(!settings.microphone_monitor_enabled);
+        assert_eq!(settings.microphone_cable_gain, 1.0);
+        assert_eq!(settings.microphone_monitor_gain, 1.0);
+        assert_eq!(settings.noise_reduction, NoiseReduction::default());
+        assert_eq!(settings.voice_changer, VoiceChanger::default());
+        assert!(!settings.microphone_duck_enabled);
+        assert_eq!(settings.microphone_duck_db, 12.0);
+        assert_eq!(settings.microphone_duck_attack_ms, 20);
+        assert_eq!(settings.microphone_duck_release_ms, 400);
+        assert_eq!(settings.microphone_duck(), None);
+        assert!(!settings.soundboard_mic_mix_enabled);
+        assert!(settings.api_tokens.is_empty());
+        assert!(!settings.api_open_local_access);
+        assert!(!settings.queue_mode_enabled);
+        assert!(!settings.high_quality_resampling);
+        assert!(!settings.resample_cache_enabled);
+        assert_eq!(settings.master_volume, 1.0);
+        assert_eq!(settings.monitor_volume, 1.0);
+        assert_eq!(settings.broadcast_volume, 1.0);
+        assert_eq!(settings.restart_crossfade_ms, 30);
+        assert_eq!(settings.max_concurrent_sounds, 16);
+        assert!(settings.output_limiter_enabled);
+        assert!(!settings.small_output_buffers);
+        assert_eq!(
+            settings.identical_device_behavior,
+            IdenticalDeviceBehavior::SingleStream
+        );
+        assert!(!settings.copy_sounds_to_library);
+        assert!(settings.watched_folders.is_empty());
+        assert!(!settings.app_rules_enabled);
+        assert!(settings.app_rules.is_empty());
     }
 
     #[test]
@@ -183,6 +529,71 @@ mod tests {
             autostart_enabled: true,
             microphone_routing_device_id: Some("device_2".to_string()),
             microphone_routing_enabled: true,
+            normalization_enabled: true,
+            normalization_target_lufs: -23.0,
+            discord_mute_awareness_enabled: true,
+            discord_client_id: Some("123456789".to_string()),
+            discord_client_secret: Some("secret".to_string()),
+            api_enabled: true,
+            api_port: 9000,
+            device_fallback_enabled: false,
+            setup_progress: SetupProgress::default(),
+            language: Language::German,
+            spectrum_analyzer_enabled: true,
+            autostart_profile: Some("Streaming".to_string()),
+            autostart_minimized: true,
+            startup_actions: vec![StartupAction::EnableMicRouting {
+                microphone_id: None,
+            }],
+            microphone_monitor_enabled: true,
+            microphone_cable_gain: 0.8,
+            microphone_monitor_gain: 0.3,
+            noise_reduction: NoiseReduction {
+                gate_enabled: true,
+                gate_threshold_db: -40.0,
+                suppression_enabled: true,
+                suppression_db: 30.0,
+            },
+            voice_changer: VoiceChanger {
+                enabled: true,
+                pitch_semitones: -4.0,
+                robot: false,
+                echo_delay_ms: 250,
+                echo_feedback: 0.3,
+            },
+            microphone_duck_enabled: true,
+            microphone_duck_db: 18.0,
+            microphone_duck_attack_ms: 5,
+            microphone_duck_release_ms: 1000,
+            soundboard_mic_mix_enabled: true,
+            api_tokens: vec![ApiToken::generate(
+                "Stream Deck".to_string(),
+                vec![crate::api_security::Capability::Play],
+            )],
+            api_open_local_access: true,
+            queue_mode_enabled: true,
+            high_quality_resampling: true,
+            resample_cache_enabled: true,
+            master_volume: 0.8,
+            monitor_volume: 0.5,
+            broadcast_volume: 1.0,
+            restart_crossfade_ms: 0,
+            max_concurrent_sounds: 4,
+            device_names: HashMap::from([(DeviceId::from_index(0), "Speakers".to_string())]),
+            output_limiter_enabled: false,
+            small_output_buffers: true,
+            identical_device_behavior: IdenticalDeviceBehavior::BothStreams,
+            copy_sounds_to_library: true,
+            watched_folders: vec![WatchedFolder {
+                path: "/music/drops".to_string(),
+                category_id: crate::sounds::CategoryId::from_string("cat-1".to_string()),
+            }],
+            app_rules_enabled: true,
+            app_rules: vec![AppRule {
+                process_name: "obs64.exe".to_string(),
+                profile: Some("Streaming".to_string()),
+                category_id: None,
+            }],
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -196,6 +607,7 @@ mod tests {
             deserialized.broadcast_device_id,
             Some(DeviceId::from_index(1))
         );
+        assert_eq!(deserialized.device_names, settings.device_names);
         assert_eq!(deserialized.default_volume, 0.75);
         assert_eq!(deserialized.volume_multiplier, 0.5);
         assert_eq!(
@@ -210,6 +622,56 @@ mod tests {
             Some("device_2".to_string())
         );
         assert!(deserialized.microphone_routing_enabled);
+        assert!(deserialized.normalization_enabled);
+        assert_eq!(deserialized.normalization_target_lufs, -23.0);
+        assert!(deserialized.discord_mute_awareness_enabled);
+        assert_eq!(
+            deserialized.discord_client_id,
+            Some("123456789".to_string())
+        );
+        assert_eq!(
+            deserialized.discord_client_secret,
+            Some("secret".to_string())
+        );
+        assert!(deserialized.api_enabled);
+        assert_eq!(deserialized.api_port, 9000);
+        assert!(!deserialized.device_fallback_enabled);
+        assert_eq!(deserialized.setup_progress, SetupProgress::default());
+        assert_eq!(deserialized.language, Language::German);
+        assert!(deserialized.spectrum_analyzer_enabled);
+        assert_eq!(deserialized.autostart_profile.as_deref(), Some("Streaming"));
+        assert!(deserialized.autostart_minimized);
+        assert_eq!(deserialized.startup_actions.len(), 1);
+        assert!(deserialized.microphone_monitor_enabled);
+        assert_eq!(deserialized.microphone_cable_gain, 0.8);
+        assert_eq!(deserialized.microphone_monitor_gain, 0.3);
+        assert_eq!(deserialized.noise_reduction, settings.noise_reduction);
+        assert_eq!(deserialized.voice_changer, settings.voice_changer);
+        assert_eq!(deserialized.microphone_duck(), Some(18.0));
+        assert_eq!(deserialized.microphone_duck_attack_ms, 5);
+        assert_eq!(deserialized.microphone_duck_release_ms, 1000);
+        assert!(deserialized.soundboard_mic_mix_enabled);
+        assert_eq!(deserialized.api_tokens, settings.api_tokens);
+        assert!(deserialized.api_open_local_access);
+        assert!(deserialized.queue_mode_enabled);
+        assert!(deserialized.high_quality_resampling);
+        assert!(deserialized.resample_cache_enabled);
+        assert_eq!(deserialized.master_volume, 0.8);
+        assert_eq!(deserialized.monitor_volume, 0.5);
+        assert_eq!(deserialized.broadcast_volume, 1.0);
+        assert_eq!(deserialized.restart_crossfade_ms, 0);
+        assert_eq!(deserialized.max_concurrent_sounds, 4);
+        assert!(!deserialized.output_limiter_enabled);
+        assert!(deserialized.small_output_buffers);
+        assert_eq!(
+            deserialized.identical_device_behavior,
+            IdenticalDeviceBehavior::BothStreams
+        );
+        assert!(deserialized.copy_sounds_to_library);
+        assert_eq!(deserialized.watched_folders.len(), 1);
+        assert_eq!(deserialized.watched_folders[0].path, "/music/drops");
+        assert!(deserialized.app_rules_enabled);
+        assert_eq!(deserialized.app_rules, settings.app_rules);
     }
 
     #[test]
@@ -231,6 +693,51 @@ mod tests {
         assert!(!settings.autostart_enabled);
         assert_eq!(settings.microphone_routing_device_id, None);
         assert!(!settings.microphone_routing_enabled);
+        assert!(!settings.normalization_enabled);
+        assert_eq!(settings.normalization_target_lufs, -16.0);
+        assert!(!settings.discord_mute_awareness_enabled);
+        assert_eq!(settings.discord_client_id, None);
+        assert_eq!(settings.discord_client_secret, None);
+        assert!(!settings.api_enabled);
+        assert_eq!(settings.api_port, 7393);
+        assert!(settings.device_fallback_enabled);
+        assert!(settings.setup_progress.completed.is_empty());
+        assert_eq!(settings.language, Language::English);
+        assert!(!settings.spectrum_analyzer_enabled);
+        assert_eq!(settings.autostart_profile, None);
+        assert!(!settings.autostart_minimized);
+        assert!(settings.startup_actions.is_empty());
+        assert!(!settings.microphone_monitor_enabled);
+        assert_eq!(settings.microphone_cable_gain, 1.0);
+        assert_eq!(settings.microphone_monitor_gain, 1.0);
+        assert_eq!(settings.noise_reduction, NoiseReduction::default());
+        assert_eq!(settings.voice_changer, VoiceChanger::default());
+        assert!(!settings.microphone_duck_enabled);
+        assert_eq!(settings.microphone_duck_db, 12.0);
+        assert_eq!(settings.microphone_duck_attack_ms, 20);
+        assert_eq!(settings.microphone_duck_release_ms, 400);
+        assert_eq!(settings.microphone_duck(), None);
+        assert!(!settings.soundboard_mic_mix_enabled);
+        assert!(settings.api_tokens.is_empty());
+        assert!(!settings.api_open_local_access);
+        assert!(!settings.queue_mode_enabled);
+        assert!(!settings.high_quality_resampling);
+        assert!(!settings.resample_cache_enabled);
+        assert_eq!(settings.master_volume, 1.0);
+        assert_eq!(settings.monitor_volume, 1.0);
+        assert_eq!(settings.broadcast_volume, 1.0);
+        assert_eq!(settings.restart_crossfade_ms, 30);
+        assert_eq!(settings.max_concurrent_sounds, 16);
+        assert!(settings.output_limiter_enabled);
+        assert!(!settings.small_output_buffers);
+        assert_eq!(
+            settings.identical_device_behavior,
+            IdenticalDeviceBehavior::SingleStream
+        );
+        assert!(!settings.copy_sounds_to_library);
+        assert!(settings.watched_folders.is_empty());
+        assert!(!settings.app_rules_enabled);
+        assert!(settings.app_rules.is_empty());
     }
 
     #[test]
@@ -263,4 +770,39 @@ mod tests {
             "device_5"
         );
     }
+    #[test]
+    fn test_validate_identical_output_devices() {
+        let mut settings = AppSettings {
+            monitor_device_id: Some(DeviceId::from_index(1)),
+            broadcast_device_id: Some(DeviceId::from_index(2)),
+            ..AppSettings::default()
+        };
+        assert!(settings.validate().is_empty());
+
+        settings.broadcast_device_id = Some(DeviceId::from_index(1));
+        assert_eq!(
+            settings.validate(),
+            [SettingsIssue::IdenticalOutputDevices {
+                device_id: DeviceId::from_index(1),
+                behavior: IdenticalDeviceBehavior::SingleStream,
+            }]
+        );
+
+        let json = serde_json::to_value(&settings.validate()[0]).unwrap();
+        assert_eq!(json["kind"], "identical_output_devices");
+        assert_eq!(json["behavior"], "single_stream");
+    }
+
+    #[test]
+    fn test_small_output_buffers_reads_old_name() {
+        let json = r#"{
+            "monitor_device_id": null,
+            "broadcast_device_id": null,
+            "default_volume": 0.5,
+            "last_file_path": null,
+            "low_latency_output": true
+        }"#;
+        let settings: AppSettings = serde_json::from_str(json).unwrap();
+        assert!(settings.small_output_buffers);
+    }
 }
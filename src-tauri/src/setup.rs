@@ -0,0 +1,204 @@
+//! First-run setup wizard progress
+//!
+//! Onboarding steps run in a fixed order. Progress is stored in settings, so
+//! the wizard resumes where it left off after a restart - including the one
+//! the VB-Cable install (UAC prompt, driver approval) may require.
+
+use serde::{Deserialize, Serialize};
+
+/// A step of the setup wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    /// Monitor and broadcast devices chosen
+    Devices,
+    /// VB-Cable installed
+    VbCable,
+    /// Microphone routed through VB-Cable
+    MicRouting,
+    /// A test sound was played
+    TestSound,
+}
+
+/// Wizard steps in the order they are shown
+pub const SETUP_STEPS: [SetupStep; 4] = [
+    SetupStep::Devices,
+    SetupStep::VbCable,
+    SetupStep::MicRouting,
+    SetupStep::TestSound,
+];
+
+/// Persisted wizard progress (part of `AppSettings`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupProgress {
+    /// Steps the user finished
+    #[serde(default)]
+    pub completed: Vec<SetupStep>,
+    /// Steps the user chose to skip
+    #[serde(default)]
+    pub skipped: Vec<SetupStep>,
+}
+
+/// What is already in place on this system (used to resume the wizard)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SetupFacts {
+    pub devices_chosen: bool,
+    pub vb_cable_installed: bool,
+    pub mic_routed: bool,
+}
+
+/// Wizard state returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupState {
+    /// Step to show next (None once every step is done or skipped)
+    pub current_step: Option<SetupStep>,
+    pub completed: Vec<SetupStep>,
+    pub skipped: Vec<SetupStep>,
+    pub is_complete: bool,
+}
+
+impl SetupProgress {
+    /// First step that is neither completed nor skipped
+    pub fn current_step(&self) -> Option<SetupStep> {
+        SETUP_STEPS
+            .into_iter()
+            .find(|step| !self.completed.contains(step) && !self.skipped.contains(step))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.current_step().is_none()
+    }
+
+    /// Mark a step as done (a previously skipped step counts as done now)
+    pub fn complete(&mut self, step: SetupStep) {
+        self.skipped.retain(|s| *s != step);
+        if !self.completed.contains(&step) {
+            self.completed.push(step);
+        }
+    }
+
+    /// Skip a step; completed steps stay completed
+    pub fn skip(&mut self, step: SetupStep) {
+        if !self.completed.contains(&step) && !self.skipped.contains(&step) {
+            self.skipped.push(step);
+        }
+    }
+
+    /// Complete steps whose outcome is already in place
+    ///
+    /// Returns true if anything changed. The test sound step is never
+    /// inferred, the user has to confirm they heard it.
+    pub fn sync(&mut self, facts: SetupFacts) -> bool {
+        let before = self.clone();
+        let inferred = [
+            (SetupStep::Devices, facts.devices_chosen),
+            (SetupStep::VbCable, facts.vb_cable_installed),
+            (SetupStep::MicRouting, facts.mic_routed),
+        ];
+        for (step, done) in inferred {
+            if done {
+                self.complete(step);
+            }
+        }
+        *self != before
+    }
+
+    pub fn state(&self) -> SetupState {
+        SetupState {
+            current_step: self.current_step(),
+            completed: self.completed.clone(),
+            skipped: self.skipped.clone(),
+            is_complete: self.is_complete(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_progress_starts_at_devices() {
+        let progress = SetupProgress::default();
+        assert_eq!(progress.current_step(), Some(SetupStep::Devices));
+        assert!(!progress.is_complete());
+    }
+
+    #[test]
+    fn test_steps_advance_in_order() {
+        let mut progress = SetupProgress::default();
+        progress.complete(SetupStep::Devices);
+        assert_eq!(progress.current_step(), Some(SetupStep::VbCable));
+
+        progress.skip(SetupStep::VbCable);
+        assert_eq!(progress.current_step(), Some(SetupStep::MicRouting));
+
+        progress.complete(SetupStep::MicRouting);
+        progress.complete(SetupStep::TestSound);
+        assert!(progress.is_complete());
+        assert_eq!(progress.state().current_step, None);
+    }
+
+    #[test]
+    fn test_complete_after_skip() {
+        let mut progress = SetupProgress::default();
+        progress.skip(SetupStep::VbCable);
+        progress.complete(SetupStep::VbCable);
+        progress.complete(SetupStep::VbCable);
+
+        assert_eq!(progress.completed, vec![SetupStep::VbCable]);
+        assert!(progress.skipped.is_empty());
+
+        // Skipping a completed step doesn't undo it
+        progress.skip(SetupStep::VbCable);
+        assert!(progress.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_sync_resumes_after_install() {
+        let mut progress = SetupProgress::default();
+        progress.complete(SetupStep::Devices);
+
+        // Restart after the VB-Cable installer: the step completes itself
+        let changed = progress.sync(SetupFacts {
+            devices_chosen: true,
+            vb_cable_installed: true,
+            mic_routed: false,
+        });
+        assert!(changed);
+        assert_eq!(progress.current_step(), Some(SetupStep::MicRouting));
+
+        // Nothing new to infer
+        assert!(!progress.sync(SetupFacts {
+            devices_chosen: true,
+            vb_cable_installed: true,
+            mic_routed: false,
+        }));
+    }
+
+    #[test]
+    fn test_sync_never_infers_test_sound() {
+        let mut progress = SetupProgress::default();
+        progress.sync(SetupFacts {
+            devices_chosen: true,
+            vb_cable_installed: true,
+            mic_routed: true,
+        });
+        assert_eq!(progress.current_step(), Some(SetupStep::TestSound));
+    }
+
+    #[test]
+    fn test_progress_serde_roundtrip() {
+        let mut progress = SetupProgress::default();
+        progress.complete(SetupStep::MicRouting);
+        progress.skip(SetupStep::VbCable);
+
+        let json = serde_json::to_string(&progress).unwrap();
+        assert!(json.contains("\"mic_routing\""));
+        let parsed: SetupProgress = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, progress);
+
+        let empty: SetupProgress = serde_json::from_str("{}").unwrap();
+        assert_eq!(empty, SetupProgress::default());
+    }
+}
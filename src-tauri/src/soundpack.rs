@@ -0,0 +1,903 @@
+//! Shareable soundpacks (zip)
+//!
+//! A soundpack contains `soundpack.json` (categories, sounds and hotkey
+//! suggestions) and the audio files under `audio/`. Paths inside the pack are
+//! relative, so packs can be shared between machines and merged into any
+//! library.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use tracing::{info, warn};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::hotkeys::{self, HotkeyMappings};
+use crate::sounds::{self, CategoryId, PlayPolicy, SoundId, SoundLibrary};
+
+/// Current soundpack format version
+pub const SOUNDPACK_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "soundpack.json";
+const AUDIO_DIR: &str = "audio";
+
+/// Zip bomb protection
+const MAX_PACK_ENTRIES: usize = 5000;
+const MAX_MANIFEST_SIZE: u64 = 10 * 1024 * 1024; // 10 MB
+const MAX_AUDIO_FILE_SIZE: u64 = 200 * 1024 * 1024; // 200 MB per file
+const MAX_EXTRACTED_SIZE: u64 = 4 * 1024 * 1024 * 1024; // 4 GB total
+
+/// `soundpack.json` contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoundpackManifest {
+    pub format_version: u32,
+    /// Pack name (used for the extraction folder)
+    pub name: String,
+    pub categories: Vec<PackCategory>,
+    pub sounds: Vec<PackSound>,
+    /// Hotkeys the author used; applied on import when free
+    #[serde(default)]
+    pub hotkeys: Vec<HotkeySuggestion>,
+}
+
+/// Category as stored in a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackCategory {
+    /// Pack-local ID referenced by sounds
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+}
+
+/// Sound as stored in a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackSound {
+    /// Pack-local ID referenced by hotkey suggestions
+    pub id: String,
+    pub name: String,
+    /// Path of the audio file inside the pack
+    pub file: String,
+    pub category_id: String,
+    pub icon: Option<String>,
+    pub volume: Option<f32>,
+    #[serde(default)]
+    pub trim_start_ms: Option<u64>,
+    #[serde(default)]
+    pub trim_end_ms: Option<u64>,
+    #[serde(default)]
+    pub play_policy: PlayPolicy,
+}
+
+/// Hotkey suggested for a pack sound
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeySuggestion {
+    pub hotkey: String,
+    pub sound_id: String,
+}
+
+/// How to handle a pack sound whose name already exists in the target category
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Keep the existing sound, don't import
+    Skip,
+    /// Import under a new name ("Airhorn (2)")
+    #[default]
+    Rename,
+    /// Point the existing sound at the imported file and metadata
+    Replace,
+}
+
+/// Result of exporting a soundpack
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub sound_count: usize,
+    /// Sounds left out because their audio file is missing
+    pub missing_files: Vec<String>,
+}
+
+/// Result of importing a soundpack
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    /// Categories the pack was merged into (existing or new)
+    pub category_ids: Vec<CategoryId>,
+    pub imported: Vec<SoundId>,
+    pub replaced: Vec<SoundId>,
+    /// Names of sounds skipped because of a conflict
+    pub skipped: Vec<String>,
+    /// Suggested hotkeys that were assigned
+    pub hotkeys_assigned: Vec<String>,
+    /// Suggested hotkeys already in use (not assigned)
+    pub hotkey_conflicts: Vec<String>,
+}
+
+/// Folder imported soundpack audio is extracted to
+pub fn get_soundpacks_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    let dir = app_data_dir.join("soundpacks");
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create soundpacks directory: {}", e))?;
+
+    Ok(dir)
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// Export categories with their sounds and hotkeys into a zip at `dest`
+pub fn export(
+    library: &SoundLibrary,
+    mappings: &HotkeyMappings,
+    category_ids: &[CategoryId],
+    name: &str,
+    dest: &Path,
+) -> Result<ExportSummary, String> {
+    let (manifest, files, missing_files) = build_manifest(library, mappings, category_ids, name);
+
+    let file = File::create(dest).map_err(|e| format!("Failed to create soundpack: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    // Audio is already compressed; only the manifest benefits from deflate
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize soundpack manifest: {}", e))?;
+    zip.start_file(MANIFEST_NAME, deflated)
+        .map_err(|e| format!("Failed to write soundpack manifest: {}", e))?;
+    zip.write_all(json.as_bytes())
+        .map_err(|e| format!("Failed to write soundpack manifest: {}", e))?;
+
+    for (pack_path, source) in &files {
+        let mut source_file = File::open(source)
+            .map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+        zip.start_file(pack_path.as_str(), stored)
+            .map_err(|e| format!("Failed to add {}: {}", pack_path, e))?;
+        io::copy(&mut source_file, &mut zip)
+            .map_err(|e| format!("Failed to add {}: {}", pack_path, e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish soundpack: {}", e))?;
+
+    info!(
+        "Exported soundpack '{}' with {} sounds to {}",
+        name,
+        manifest.sounds.len(),
+        dest.display()
+    );
+
+    Ok(ExportSummary {
+        path: dest.to_string_lossy().to_string(),
+        sound_count: manifest.sounds.len(),
+        missing_files,
+    })
+}
+
+/// Build the manifest and the list of (pack path, source file) to bundle
+///
+/// Sounds whose audio file doesn't exist are left out and returned by name.
+fn build_manifest(
+    library: &SoundLibrary,
+    mappings: &HotkeyMappings,
+    category_ids: &[CategoryId],
+    name: &str,
+) -> (SoundpackManifest, Vec<(String, PathBuf)>, Vec<String>) {
+    let categories: Vec<PackCategory> = library
+        .categories
+        .iter()
+        .filter(|c| category_ids.contains(&c.id))
+        .map(|c| PackCategory {
+            id: c.id.as_str().to_string(),
+            name: c.name.clone(),
+            icon: c.icon.clone(),
+        })
+        .collect();
+
+    let mut sounds = Vec::new();
+    let mut files = Vec::new();
+    let mut missing_files = Vec::new();
+
+    for sound in library
+        .sounds
+        .iter()
+        .filter(|s| category_ids.contains(&s.category_id))
+    {
+        let source = PathBuf::from(&sound.file_path);
+        if !source.is_file() {
+            warn!("Soundpack export: missing file for '{}'", sound.name);
+            missing_files.push(sound.name.clone());
+            continue;
+        }
+
+        let file_name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "sound".to_string());
+        // Index prefix keeps files with the same name apart
+        let pack_path = format!("{}/{:04}_{}", AUDIO_DIR, files.len(), file_name);
+
+        sounds.push(PackSound {
+            id: sound.id.as_str().to_string(),
+            name: sound.name.clone(),
+            file: pack_path.clone(),
+            category_id: sound.category_id.as_str().to_string(),
+            icon: sound.icon.clone(),
+            volume: sound.volume,
+            trim_start_ms: sound.trim_start_ms,
+            trim_end_ms: sound.trim_end_ms,
+            play_policy: sound.play_policy,
+        });
+        files.push((pack_path, source));
+    }
+
+    let mut hotkeys: Vec<HotkeySuggestion> = mappings
+        .mappings
+        .iter()
+        .filter(|(_, id)| sounds.iter().any(|s| s.id == id.as_str()))
+        .map(|(hotkey, id)| HotkeySuggestion {
+            hotkey: hotkey.clone(),
+            sound_id: id.as_str().to_string(),
+        })
+        .collect();
+    hotkeys.sort_by(|a, b| a.hotkey.cmp(&b.hotkey));
+
+    let manifest = SoundpackManifest {
+        format_version: SOUNDPACK_FORMAT_VERSION,
+        name: name.to_string(),
+        categories,
+        sounds,
+        hotkeys,
+    };
+    (manifest, files, missing_files)
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Import a soundpack, extracting its audio below `audio_root`
+///
+/// Categories are matched by name (case-insensitive) and created when
+/// missing. Suggested hotkeys are only assigned when free. If the import
+/// fails partway, the files extracted so far are deleted again.
+pub fn import(
+    library: &mut SoundLibrary,
+    mappings: &mut HotkeyMappings,
+    pack_path: &Path,
+    audio_root: &Path,
+    on_conflict: ConflictResolution,
+) -> Result<ImportSummary, String> {
+    let file = File::open(pack_path).map_err(|e| format!("Failed to open soundpack: {}", e))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| format!("Failed to read soundpack: {}", e))?;
+    if archive.len() > MAX_PACK_ENTRIES {
+        return Err(format!(
+            "Soundpack contains too many files ({}, max: {})",
+            archive.len(),
+            MAX_PACK_ENTRIES
+        ));
+    }
+
+    let manifest = read_manifest(&mut archive)?;
+    let dest_dir = audio_root.join(sanitize_file_name(&manifest.name));
+    let created_dir = !dest_dir.exists();
+    fs::create_dir_all(&dest_dir)
+        .map_err(|e| format!("Failed to create soundpack folder: {}", e))?;
+
+    let mut extracted_size = 0u64;
+    let mut extracted = Vec::new();
+    let merged = merge_manifest(library, mappings, &manifest, on_conflict, |pack_file| {
+        let path = extract_audio(&mut archive, pack_file, &dest_dir, &mut extracted_size)?;
+        extracted.push(PathBuf::from(&path));
+        Ok(path)
+    });
+    let summary = match merged {
+        Ok(summary) => summary,
+        Err(e) => {
+            remove_extracted(&extracted, created_dir.then_some(dest_dir.as_path()));
+            return Err(e);
+        }
+    };
+
+    info!(
+        "Imported soundpack '{}': {} new, {} replaced, {} skipped, {} hotkeys",
+        manifest.name,
+        summary.imported.len(),
+        summary.replaced.len(),
+        summary.skipped.len(),
+        summary.hotkeys_assigned.len()
+    );
+    Ok(summary)
+}
+
+fn read_manifest(archive: &mut ZipArchive<File>) -> Result<SoundpackManifest, String> {
+    let entry = archive
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| format!("Not a soundpack: {} missing", MANIFEST_NAME))?;
+    if entry.size() > MAX_MANIFEST_SIZE {
+        return Err("Soundpack manifest is too large".to_string());
+    }
+
+    let mut json = String::new();
+    entry
+        .take(MAX_MANIFEST_SIZE)
+        .read_to_string(&mut json)
+        .map_err(|e| format!("Failed to read soundpack manifest: {}", e))?;
+
+    let manifest: SoundpackManifest =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid soundpack manifest: {}", e))?;
+    if manifest.format_version > SOUNDPACK_FORMAT_VERSION {
+        return Err(format!(
+            "Soundpack format {} is newer than supported ({})",
+            manifest.format_version, SOUNDPACK_FORMAT_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Extract one audio file and return its absolute path
+fn extract_audio(
+    archive: &mut ZipArchive<File>,
+    pack_file: &str,
+    dest_dir: &Path,
+    extracted_size: &mut u64,
+) -> Result<String, String> {
+    let mut entry = archive
+        .by_name(pack_file)
+        .map_err(|e| format!("Missing {} in soundpack: {}", pack_file, e))?;
+
+    let size = entry.size();
+    if size > MAX_AUDIO_FILE_SIZE {
+        return Err(format!("{} exceeds the size limit", pack_file));
+    }
+    *extracted_size += size;
+    if *extracted_size > MAX_EXTRACTED_SIZE {
+        return Err("Soundpack exceeds the total size limit".to_string());
+    }
+
+    // Only the file name is used, so entries can't escape dest_dir
+    let file_name = entry
+        .enclosed_name()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .ok_or_else(|| format!("Invalid file name in soundpack: {}", pack_file))?;
+    let out_path = unique_path(dest_dir, &file_name);
+
+    let mut out = File::create(&out_path)
+        .map_err(|e| format!("Failed to create {}: {}", out_path.display(), e))?;
+    if let Err(e) = io::copy(&mut (&mut entry).take(MAX_AUDIO_FILE_SIZE), &mut out) {
+        drop(out);
+        let _ = fs::remove_file(&out_path);
+        return Err(format!("Failed to extract {}: {}", pack_file, e));
+    }
+
+    Ok(out_path.to_string_lossy().to_string())
+}
+
+/// Delete the files of a failed import, and its folder if the import created it
+fn remove_extracted(files: &[PathBuf], created_dir: Option<&Path>) {
+    for file in files {
+        if let Err(e) = fs::remove_file(file) {
+            warn!("Failed to remove {}: {}", file.display(), e);
+        }
+    }
+    if let Some(dir) = created_dir {
+        // Only removes the folder if nothing else ended up in it
+        let _ = fs::remove_dir(dir);
+    }
+}
+
+/// Merge a manifest into the library
+///
+/// `extract` turns a pack file path into a local file path; it's only called
+/// for sounds that are actually imported.
+fn merge_manifest(
+    library: &mut SoundLibrary,
+    mappings: &mut HotkeyMappings,
+    manifest: &SoundpackManifest,
+    on_conflict: ConflictResolution,
+    mut extract: impl FnMut(&str) -> Result<String, String>,
+) -> Result<ImportSummary, String> {
+    let mut summary = ImportSummary::default();
+
+    // Pack category ID -> library category ID
+    let mut category_map: HashMap<&str, CategoryId> = HashMap::new();
+    for pack_category in &manifest.categories {
+        let existing = library
+            .categories
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(&pack_category.name))
+            .map(|c| c.id.clone());
+        let id = existing.unwrap_or_else(|| {
+            sounds::add_category(
+                library,
+                pack_category.name.clone(),
+                pack_category.icon.clone(),
+            )
+            .id
+        });
+        category_map.insert(pack_category.id.as_str(), id.clone());
+        summary.category_ids.push(id);
+    }
+
+    // Pack sound ID -> library sound ID
+    let mut sound_map: HashMap<&str, SoundId> = HashMap::new();
+    for pack_sound in &manifest.sounds {
+        let Some(category_id) = category_map.get(pack_sound.category_id.as_str()) else {
+            warn!("Soundpack sound '{}' has no category", pack_sound.name);
+            summary.skipped.push(pack_sound.name.clone());
+            continue;
+        };
+
+        let existing = library
+            .sounds
+            .iter()
+            .position(|s| &s.category_id == category_id && s.name == pack_sound.name);
+
+        match (existing, on_conflict) {
+            (Some(_), ConflictResolution::Skip) => {
+                summary.skipped.push(pack_sound.name.clone());
+            }
+            (Some(index), ConflictResolution::Replace) => {
+                let file_path = extract(&pack_sound.file)?;
+                let sound = &mut library.sounds[index];
+                sound.file_path = file_path;
+                sound.icon = pack_sound.icon.clone();
+                sound.volume = pack_sound.volume.map(|v| v.clamp(0.0, 1.0));
+                sound.trim_start_ms = pack_sound.trim_start_ms;
+                sound.trim_end_ms = pack_sound.trim_end_ms;
+                sound.play_policy = pack_sound.play_policy;
+                sound_map.insert(pack_sound.id.as_str(), sound.id.clone());
+                summary.replaced.push(sound.id.clone());
+            }
+            (existing, _) => {
+                let name = if existing.is_some() {
+                    unique_sound_name(library, category_id, &pack_sound.name)
+                } else {
+                    pack_sound.name.clone()
+                };
+                let file_path = extract(&pack_sound.file)?;
+                let sound_id = sounds::add_sound(
+                    library,
+                    name,
+                    file_path,
+                    category_id.clone(),
+                    pack_sound.icon.clone(),
+                    pack_sound.volume,
+                )
+                .id;
+                if let Some(added) = library.sounds.last_mut() {
+                    added.trim_start_ms = pack_sound.trim_start_ms;
+                    added.trim_end_ms = pack_sound.trim_end_ms;
+                    added.play_policy = pack_sound.play_policy;
+                }
+                sound_map.insert(pack_sound.id.as_str(), sound_id.clone());
+                summary.imported.push(sound_id);
+            }
+        }
+    }
+
+    for suggestion in &manifest.hotkeys {
+        let Some(sound_id) = sound_map.get(suggestion.sound_id.as_str()) else {
+            continue;
+        };
+        let hotkey = hotkeys::normalize_hotkey_string(&suggestion.hotkey);
+        if hotkeys::add_mapping(mappings, hotkey.clone(), sound_id.clone()).is_ok() {
+            summary.hotkeys_assigned.push(hotkey);
+        } else {
+            summary.hotkey_conflicts.push(hotkey);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// "Name (2)", "Name (3)", ... - first name not used in the category
+fn unique_sound_name(library: &SoundLibrary, category_id: &CategoryId, name: &str) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| {
+            !library
+                .sounds
+                .iter()
+                .any(|s| &s.category_id == category_id && &s.name == candidate)
+        })
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// First path in `dir` for `file_name` that doesn't exist yet
+pub(crate) fn unique_path(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let path = Path::new(file_name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|p| !p.exists())
+        .unwrap_or(candidate)
+}
+
+/// Pack name for a pack exported to `path` ("Stream Board.sonicpack.zip"
+/// becomes "Stream Board")
+pub fn name_from_path(path: &Path) -> String {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = file_name
+        .strip_suffix(".zip")
+        .unwrap_or(&file_name)
+        .trim_end_matches(".sonicpack");
+    if name.trim().is_empty() {
+        "Soundpack".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Make a pack name safe to use as a folder name
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let trimmed = sanitized.trim().trim_matches('.');
+    if trimmed.is_empty() {
+        "soundpack".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn library_with_sound(dir: &TempDir, name: &str) -> (SoundLibrary, SoundId) {
+        let mut library = SoundLibrary::default();
+        let file = dir.path().join(format!("{}.mp3", name));
+        fs::write(&file, b"audio").unwrap();
+        let sound = sounds::add_sound(
+            &mut library,
+            name.to_string(),
+            file.to_string_lossy().to_string(),
+            CategoryId::from_string("default".to_string()),
+            Some("📯".to_string()),
+            Some(0.8),
+        );
+        (library, sound.id)
+    }
+
+    fn default_category() -> CategoryId {
+        CategoryId::from_string("default".to_string())
+    }
+
+    // -------------------------------------------------------------------------
+    // Export
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_build_manifest_includes_sounds_and_hotkeys() {
+        let dir = TempDir::new().unwrap();
+        let (library, sound_id) = library_with_sound(&dir, "Airhorn");
+        let mut mappings = HotkeyMappings::default();
+        hotkeys::add_mapping(&mut mappings, "Ctrl+F1".to_string(), sound_id.clone()).unwrap();
+
+        let (manifest, files, missing) =
+            build_manifest(&library, &mappings, &[default_category()], "Pack");
+
+        assert_eq!(manifest.categories.len(), 1);
+        assert_eq!(manifest.sounds.len(), 1);
+        assert_eq!(manifest.sounds[0].file, "audio/0000_Airhorn.mp3");
+        assert_eq!(manifest.hotkeys.len(), 1);
+        assert_eq!(manifest.hotkeys[0].sound_id, sound_id.as_str());
+        assert_eq!(files.len(), 1);
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn test_build_manifest_skips_missing_files() {
+        let mut library = SoundLibrary::default();
+        sounds::add_sound(
+            &mut library,
+            "Gone".to_string(),
+            "/nonexistent/gone.mp3".to_string(),
+            default_category(),
+            None,
+            None,
+        );
+
+        let (manifest, files, missing) = build_manifest(
+            &library,
+            &HotkeyMappings::default(),
+            &[default_category()],
+            "Pack",
+        );
+
+        assert!(manifest.sounds.is_empty());
+        assert!(files.is_empty());
+        assert_eq!(missing, vec!["Gone".to_string()]);
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let (library, sound_id) = library_with_sound(&dir, "Airhorn");
+        let mut mappings = HotkeyMappings::default();
+        hotkeys::add_mapping(&mut mappings, "Ctrl+F1".to_string(), sound_id).unwrap();
+
+        let pack = dir.path().join("pack.zip");
+        let summary = export(&library, &mappings, &[default_category()], "My Pack", &pack).unwrap();
+        assert_eq!(summary.sound_count, 1);
+
+        let mut target = SoundLibrary::default();
+        let mut target_mappings = HotkeyMappings::default();
+        let audio_root = dir.path().join("imported");
+        let result = import(
+            &mut target,
+            &mut target_mappings,
+            &pack,
+            &audio_root,
+            ConflictResolution::Rename,
+        )
+        .unwrap();
+
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.hotkeys_assigned, vec!["Ctrl+F1".to_string()]);
+        let sound = &target.sounds[0];
+        assert_eq!(sound.name, "Airhorn");
+        assert_eq!(sound.volume, Some(0.8));
+        assert!(sound
+            .file_path
+            .starts_with(&*audio_root.join("My Pack").to_string_lossy()));
+        assert_eq!(fs::read(&sound.file_path).unwrap(), b"audio");
+    }
+
+    #[test]
+    fn test_failed_import_removes_extracted_files() {
+        let dir = TempDir::new().unwrap();
+        let mut manifest = pack_manifest();
+        let mut missing = manifest.sounds[0].clone();
+        missing.id = "pack-missing".to_string();
+        missing.name = "Missing".to_string();
+        missing.file = "audio/0001_missing.mp3".to_string();
+        manifest.sounds.push(missing);
+
+        // The second sound's file isn't in the pack
+        let pack = dir.path().join("broken.zip");
+        let mut zip = ZipWriter::new(File::create(&pack).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file(MANIFEST_NAME, options).unwrap();
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+        zip.start_file("audio/0000_airhorn.mp3", options).unwrap();
+        zip.write_all(b"audio").unwrap();
+        zip.finish().unwrap();
+
+        let audio_root = dir.path().join("imported");
+        let result = import(
+            &mut SoundLibrary::default(),
+            &mut HotkeyMappings::default(),
+            &pack,
+            &audio_root,
+            ConflictResolution::Rename,
+        );
+
+        assert!(result.unwrap_err().contains("0001_missing.mp3"));
+        assert!(!audio_root.join("Pack").exists());
+    }
+
+    // -------------------------------------------------------------------------
+    // Merge / Conflict Resolution
+    // -------------------------------------------------------------------------
+
+    fn pack_manifest() -> SoundpackManifest {
+        SoundpackManifest {
+            format_version: SOUNDPACK_FORMAT_VERSION,
+            name: "Pack".to_string(),
+            categories: vec![PackCategory {
+                id: "pack-cat".to_string(),
+                name: "general".to_string(),
+                icon: None,
+            }],
+            sounds: vec![PackSound {
+                id: "pack-sound".to_string(),
+                name: "Airhorn".to_string(),
+                file: "audio/0000_airhorn.mp3".to_string(),
+                category_id: "pack-cat".to_string(),
+                icon: None,
+                volume: Some(0.3),
+                trim_start_ms: Some(100),
+                trim_end_ms: None,
+                play_policy: PlayPolicy::Extend,
+            }],
+            hotkeys: vec![HotkeySuggestion {
+                hotkey: "ctrl+f1".to_string(),
+                sound_id: "pack-sound".to_string(),
+            }],
+        }
+    }
+
+    fn merge(
+        library: &mut SoundLibrary,
+        mappings: &mut HotkeyMappings,
+        on_conflict: ConflictResolution,
+    ) -> ImportSummary {
+        merge_manifest(library, mappings, &pack_manifest(), on_conflict, |file| {
+            Ok(format!("/imported/{}", file))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_matches_category_by_name() {
+        let mut library = SoundLibrary::default();
+        let summary = merge(
+            &mut library,
+            &mut HotkeyMappings::default(),
+            ConflictResolution::Rename,
+        );
+
+        // "general" merges into the default "General" category
+        assert_eq!(library.categories.len(), 1);
+        assert_eq!(summary.category_ids, vec![default_category()]);
+        assert_eq!(library.sounds[0].trim_start_ms, Some(100));
+        assert_eq!(library.sounds[0].play_policy, PlayPolicy::Extend);
+    }
+
+    #[test]
+    fn test_merge_conflict_rename() {
+        let dir = TempDir::new().unwrap();
+        let (mut library, _) = library_with_sound(&dir, "Airhorn");
+
+        let summary = merge(
+            &mut library,
+            &mut HotkeyMappings::default(),
+            ConflictResolution::Rename,
+        );
+
+        assert_eq!(summary.imported.len(), 1);
+        assert!(library.sounds.iter().any(|s| s.name == "Airhorn (2)"));
+    }
+
+    #[test]
+    fn test_merge_conflict_skip() {
+        let dir = TempDir::new().unwrap();
+        let (mut library, _) = library_with_sound(&dir, "Airhorn");
+
+        let summary = merge(
+            &mut library,
+            &mut HotkeyMappings::default(),
+            ConflictResolution::Skip,
+        );
+
+        assert!(summary.imported.is_empty());
+        assert_eq!(summary.skipped, vec!["Airhorn".to_string()]);
+        assert_eq!(library.sounds.len(), 1);
+        // Hotkey of a skipped sound is not assigned
+        assert!(summary.hotkeys_assigned.is_empty());
+    }
+
+    #[test]
+    fn test_merge_conflict_replace() {
+        let dir = TempDir::new().unwrap();
+        let (mut library, sound_id) = library_with_sound(&dir, "Airhorn");
+
+        let summary = merge(
+            &mut library,
+            &mut HotkeyMappings::default(),
+            ConflictResolution::Replace,
+        );
+
+        assert_eq!(summary.replaced, vec![sound_id]);
+        assert_eq!(library.sounds.len(), 1);
+        assert_eq!(
+            library.sounds[0].file_path,
+            "/imported/audio/0000_airhorn.mp3"
+        );
+        assert_eq!(library.sounds[0].volume, Some(0.3));
+    }
+
+    #[test]
+    fn test_merge_hotkey_conflict_keeps_existing() {
+        let mut library = SoundLibrary::default();
+        let mut mappings = HotkeyMappings::default();
+        let other = SoundId::new();
+        hotkeys::add_mapping(&mut mappings, "Ctrl+F1".to_string(), other.clone()).unwrap();
+
+        let summary = merge(&mut library, &mut mappings, ConflictResolution::Rename);
+
+        assert_eq!(summary.hotkey_conflicts, vec!["Ctrl+F1".to_string()]);
+        assert_eq!(hotkeys::get_sound_id(&mappings, "Ctrl+F1"), Some(&other));
+    }
+
+    // -------------------------------------------------------------------------
+    // Helpers
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_unique_path_appends_counter() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.mp3"), b"").unwrap();
+
+        assert_eq!(unique_path(dir.path(), "b.mp3"), dir.path().join("b.mp3"));
+        assert_eq!(
+            unique_path(dir.path(), "a.mp3"),
+            dir.path().join("a (2).mp3")
+        );
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("Memes: Vol/2"), "Memes_ Vol_2");
+        assert_eq!(sanitize_file_name(".."), "soundpack");
+        assert_eq!(sanitize_file_name("  "), "soundpack");
+    }
+
+    #[test]
+    fn test_name_from_path() {
+        assert_eq!(
+            name_from_path(Path::new("/packs/Stream Board.sonicpack.zip")),
+            "Stream Board"
+        );
+        assert_eq!(name_from_path(Path::new("memes.zip")), "memes");
+        assert_eq!(name_from_path(Path::new(".zip")), "Soundpack");
+    }
+
+    #[test]
+    fn test_build_manifest_whole_library() {
+        let dir = TempDir::new().unwrap();
+        let (mut library, _) = library_with_sound(&dir, "Airhorn");
+        let other = sounds::add_category(&mut library, "Music".to_string(), None);
+        let file = dir.path().join("song.mp3");
+        fs::write(&file, b"audio").unwrap();
+        sounds::add_sound(
+            &mut library,
+            "Song".to_string(),
+            file.to_string_lossy().to_string(),
+            other.id.clone(),
+            None,
+            None,
+        );
+
+        let category_ids: Vec<CategoryId> =
+            library.categories.iter().map(|c| c.id.clone()).collect();
+        let (manifest, files, _) = build_manifest(
+            &library,
+            &HotkeyMappings::default(),
+            &category_ids,
+            "Library",
+        );
+        assert_eq!(manifest.categories.len(), library.categories.len());
+        assert_eq!(manifest.sounds.len(), 2);
+        assert_eq!(files.len(), 2);
+    }
+}
@@ -3,9 +3,12 @@
 //! Stores sounds and categories as JSON in the platform-specific app data directory.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::Manager;
 
+use crate::reload::DataFile;
+use crate::DeviceId;
+
 /// Unique identifier for a sound
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -80,6 +83,63 @@ fn uuid_v4() -> String {
     )
 }
 
+/// What happens when a sound is triggered while it's still playing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayPolicy {
+    /// Stop the current playback and start from the beginning
+    #[default]
+    Restart,
+    /// Keep playing and play once more when it ends (for looped ambiences)
+    Extend,
+    /// Start another playback on top of the current one
+    Overlap,
+    /// Keep playing and drop the trigger
+    IgnoreIfPlaying,
+    /// Play again as a new playback once the current one has ended
+    Queue,
+}
+
+/// Problem with a sound's file found when it was imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportWarning {
+    /// The file is zero bytes long
+    EmptyFile,
+    /// The file decodes to no samples at all
+    NoAudio,
+    /// The file only contains silence
+    Silent,
+}
+
+/// Short region auditioned by `preview_sound` (independent of the trim)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewRegion {
+    /// Start of the region in milliseconds
+    pub start_ms: u64,
+    /// End of the region in milliseconds
+    pub end_ms: u64,
+}
+
+/// Longest allowed preview region (ms)
+pub const MAX_PREVIEW_MS: u64 = 10_000;
+
+impl PreviewRegion {
+    /// Check that the region is non-empty and at most `MAX_PREVIEW_MS` long
+    pub fn validate(&self) -> Result<(), String> {
+        if self.end_ms <= self.start_ms {
+            return Err("Preview end must be after its start".to_string());
+        }
+        if self.end_ms - self.start_ms > MAX_PREVIEW_MS {
+            return Err(format!(
+                "Preview must be at most {} seconds long",
+                MAX_PREVIEW_MS / 1000
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// A sound in the library
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sound {
@@ -104,8 +164,66 @@ pub struct Sound {
     /// Optional trim end time in milliseconds
     #[serde(default)]
     pub trim_end_ms: Option<u64>,
+    /// Behavior when re-triggered while playing
+    #[serde(default)]
+    pub play_policy: PlayPolicy,
+    /// Resume from where playback was last stopped (for long files)
+    #[serde(default)]
+    pub resume_playback: bool,
+    /// Optional expiry (Unix time in milliseconds) for temporary sounds
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// Optional level trim on the monitor output (dB)
+    #[serde(default)]
+    pub monitor_trim_db: Option<f32>,
+    /// Optional level trim on the broadcast output (dB), e.g. to tame bass-heavy clips
+    #[serde(default)]
+    pub broadcast_trim_db: Option<f32>,
+    /// Optional exclusive group (e.g. "Music"): starting a sound stops the
+    /// other sounds of its group
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Optional monitor device used instead of the global one
+    #[serde(default)]
+    pub monitor_device_id: Option<DeviceId>,
+    /// Optional broadcast device used instead of the global one
+    #[serde(default)]
+    pub broadcast_device_id: Option<DeviceId>,
+    /// Optional region auditioned when previewing (e.g. on hover)
+    #[serde(default)]
+    pub preview: Option<PreviewRegion>,
+    /// Play the (trimmed) sound time-stretched to last exactly this long
+    #[serde(default)]
+    pub fit_duration_ms: Option<u64>,
+    /// Pitch shift in semitones, keeping the length (None = original pitch)
+    #[serde(default)]
+    pub pitch_semitones: Option<f32>,
+    /// Playback speed; the pitch follows, like a tape (None = normal speed)
+    #[serde(default)]
+    pub speed: Option<f32>,
+    /// Problem found by `validate_sound_file` (shown as a badge)
+    #[serde(default)]
+    pub import_warning: Option<ImportWarning>,
+    /// Integrated loudness of the file (LUFS), measured by
+    /// `analyze_library_loudness` (None until measured, or if silent)
+    #[serde(default)]
+    pub lufs: Option<f32>,
+    /// Whether `lufs` was measured (it stays None for silent files, which
+    /// aren't measured again)
+    #[serde(default)]
+    pub loudness_measured: bool,
+    /// Length of the file, read from its headers on bulk import
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Free-form labels for filtering beyond the category
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
+/// Accepted range for per-device level trims (dB)
+pub const MIN_DEVICE_TRIM_DB: f32 = -30.0;
+pub const MAX_DEVICE_TRIM_DB: f32 = 6.0;
+
 /// A category to organize sounds
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Category {
@@ -143,18 +261,57 @@ impl Default for SoundLibrary {
     }
 }
 
-/// Get the path to the sounds file
+/// Directory under app data that imported sounds are copied into
+pub const MANAGED_SOUNDS_DIR: &str = "sounds";
+
+/// Get the path to the active profile's sounds file
 pub fn get_sounds_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::profiles::data_dir(app_handle)?.join("sounds.json"))
+}
+
+/// Get the directory sounds are copied into on import (created if missing)
+pub fn get_managed_sounds_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app_handle
         .path()
         .app_local_data_dir()
         .map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    // Ensure directory exists
-    std::fs::create_dir_all(&app_data_dir)
-        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    let dir = app_data_dir.join(MANAGED_SOUNDS_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create sounds directory: {}", e))?;
+
+    Ok(dir)
+}
 
-    Ok(app_data_dir.join("sounds.json"))
+/// Copy of the library as written to disk: files inside the app data
+/// directory are stored relative to it ("sounds/horn.mp3"), so the library
+/// survives moving the data directory
+pub fn to_stored_paths(library: &SoundLibrary, data_dir: &Path) -> SoundLibrary {
+    let mut stored = library.clone();
+    for sound in &mut stored.sounds {
+        if let Ok(relative) = Path::new(&sound.file_path).strip_prefix(data_dir) {
+            let parts: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            sound.file_path = parts.join("/");
+        }
+    }
+    stored
+}
+
+/// Turn relative paths read from disk back into absolute ones
+pub fn resolve_stored_paths(library: &mut SoundLibrary, data_dir: &Path) {
+    for sound in &mut library.sounds {
+        if sound.file_path.is_empty() || Path::new(&sound.file_path).is_absolute() {
+            continue;
+        }
+        let path = sound
+            .file_path
+            .split('/')
+            .fold(data_dir.to_path_buf(), |path, part| path.join(part));
+        sound.file_path = path.to_string_lossy().to_string();
+    }
 }
 
 /// Load sound library from disk
@@ -168,9 +325,12 @@ pub fn load(app_handle: &tauri::AppHandle) -> Result<SoundLibrary, String> {
 
     let content = std::fs::read_to_string(&sounds_path)
         .map_err(|e| format!("Failed to read sounds file: {}", e))?;
+    crate::reload::remember(DataFile::Sounds, &content);
 
-    let library: SoundLibrary =
+    let mut library: SoundLibrary =
         serde_json::from_str(&content).map_err(|e| format!("Failed to parse sounds: {}", e))?;
+    // Relative to the app data directory, which every profile shares
+    resolve_stored_paths(&mut library, &crate::profiles::app_data_dir(app_handle)?);
 
     Ok(library)
 }
@@ -179,10 +339,15 @@ pub fn load(app_handle: &tauri::AppHandle) -> Result<SoundLibrary, String> {
 pub fn save(library: &SoundLibrary, app_handle: &tauri::AppHandle) -> Result<(), String> {
     let sounds_path = get_sounds_path(app_handle)?;
 
-    let json = serde_json::to_string_pretty(library)
+    let stored = to_stored_paths(library, &crate::profiles::app_data_dir(app_handle)?);
+    let json = serde_json::to_string_pretty(&stored)
         .map_err(|e| format!("Failed to serialize sounds: {}", e))?;
 
-    crate::persistence::atomic_write(&sounds_path, &json)
+    // Don't overwrite edits made outside the app that weren't reloaded yet
+    crate::reload::check_unmodified(DataFile::Sounds, &sounds_path)?;
+    crate::persistence::atomic_write_with_backup(&sounds_path, &json)?;
+    crate::reload::remember(DataFile::Sounds, &json);
+    Ok(())
 }
 
 // ============================================================================
@@ -208,11 +373,111 @@ pub fn add_sound(
         is_favorite: false,
         trim_start_ms: None,
         trim_end_ms: None,
+        play_policy: PlayPolicy::default(),
+        resume_playback: false,
+        expires_at: None,
+        monitor_trim_db: None,
+        broadcast_trim_db: None,
+        group: None,
+        monitor_device_id: None,
+        broadcast_device_id: None,
+        preview: None,
+        fit_duration_ms: None,
+        pitch_semitones: None,
+        speed: None,
+        import_warning: None,
+        lufs: None,
+        loudness_measured: false,
+        duration_ms: None,
+        tags: Vec::new(),
     };
     library.sounds.push(sound.clone());
     sound
 }
 
+/// A tag and how many sounds carry it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Trim tags, drop blank ones and duplicates (ignoring case, the first
+/// spelling wins)
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// Whether a sound carries all of `tags` (ignoring case)
+pub fn has_tags(sound: &Sound, tags: &[String]) -> bool {
+    tags.iter()
+        .all(|tag| sound.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+}
+
+/// Replace a sound's tags
+pub fn set_tags(
+    library: &mut SoundLibrary,
+    sound_id: &SoundId,
+    tags: Vec<String>,
+) -> Result<Sound, String> {
+    let sound = library
+        .sounds
+        .iter_mut()
+        .find(|s| &s.id == sound_id)
+        .ok_or_else(|| format!("Sound not found: {}", sound_id.as_str()))?;
+    sound.tags = normalize_tags(tags);
+    Ok(sound.clone())
+}
+
+/// All tags in the library with their use counts, alphabetically
+///
+/// Spellings that differ only in case count as one tag.
+pub fn list_tags(library: &SoundLibrary) -> Vec<TagCount> {
+    let mut counts: Vec<TagCount> = Vec::new();
+    for tag in library.sounds.iter().flat_map(|s| &s.tags) {
+        match counts.iter_mut().find(|c| c.tag.eq_ignore_ascii_case(tag)) {
+            Some(count) => count.count += 1,
+            None => counts.push(TagCount {
+                tag: tag.clone(),
+                count: 1,
+            }),
+        }
+    }
+    counts.sort_by_key(|c| c.tag.to_lowercase());
+    counts
+}
+
+/// Name for a sound imported without one: the file's title tag, otherwise
+/// the file name with dashes and underscores as spaces, words capitalized
+/// ("air_horn-loud.mp3" becomes "Air Horn Loud")
+pub fn name_for_file(path: &Path, title: Option<&str>) -> String {
+    if let Some(title) = title.map(str::trim).filter(|t| !t.is_empty()) {
+        return title.to_string();
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    stem.split(['-', '_', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
 /// Update an existing sound
 #[allow(clippy::too_many_arguments)]
 pub fn update_sound(
@@ -237,6 +502,12 @@ pub fn update_sound(
         sound.name = name;
     }
     if let Some(file_path) = file_path {
+        // The measured loudness and length were of the old file
+        if file_path != sound.file_path {
+            sound.lufs = None;
+            sound.loudness_measured = false;
+            sound.duration_ms = None;
+        }
         sound.file_path = file_path;
     }
     if let Some(category_id) = category_id {
@@ -273,6 +544,61 @@ pub fn delete_sound(library: &mut SoundLibrary, sound_id: &SoundId) -> Result<()
     Ok(())
 }
 
+/// Copy a sound under a new ID with a "(copy)" name suffix
+///
+/// The copy keeps every other field (file, trims, volume, policy, ...) and is
+/// placed right after the original.
+pub fn duplicate_sound(library: &mut SoundLibrary, sound_id: &SoundId) -> Result<Sound, String> {
+    let index = library
+        .sounds
+        .iter()
+        .position(|s| &s.id == sound_id)
+        .ok_or_else(|| format!("Sound not found: {}", sound_id.as_str()))?;
+
+    let original = &library.sounds[index];
+    let copy = Sound {
+        id: SoundId::new(),
+        name: format!("{} (copy)", original.name),
+        ..original.clone()
+    };
+    library.sounds.insert(index + 1, copy.clone());
+    Ok(copy)
+}
+
+/// Remove all sounds whose expiry is at or before `now_ms`
+///
+/// Returns the removed sounds so callers can clean up their hotkeys and files.
+pub fn remove_expired(library: &mut SoundLibrary, now_ms: u64) -> Vec<Sound> {
+    let (expired, kept) = std::mem::take(&mut library.sounds)
+        .into_iter()
+        .partition(|s| s.expires_at.is_some_and(|at| at <= now_ms));
+    library.sounds = kept;
+    expired
+}
+
+/// Files of removed sounds that live in an app-managed directory and are no
+/// longer referenced by any remaining sound
+pub fn orphaned_managed_files(
+    removed: &[Sound],
+    library: &SoundLibrary,
+    managed_dir: &Path,
+) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = removed
+        .iter()
+        .map(|s| PathBuf::from(&s.file_path))
+        .filter(|path| path.starts_with(managed_dir))
+        .filter(|path| {
+            !library
+                .sounds
+                .iter()
+                .any(|s| Path::new(&s.file_path) == path.as_path())
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
 /// Add a new category
 pub fn add_category(library: &mut SoundLibrary, name: String, icon: Option<String>) -> Category {
     let max_order = library
@@ -509,6 +835,98 @@ mod tests {
         assert_eq!(deserialized.sounds[0].name, "Test Sound");
     }
 
+    #[test]
+    fn test_sound_play_policy_defaults_to_restart() {
+        let json = r#"{
+            "id": "s1",
+            "name": "Old Sound",
+            "file_path": "/path/to/sound.mp3",
+            "category_id": "default",
+            "icon": null,
+            "volume": null
+        }"#;
+
+        let sound: Sound = serde_json::from_str(json).unwrap();
+        assert_eq!(sound.play_policy, PlayPolicy::Restart);
+
+        let json = serde_json::to_string(&PlayPolicy::Extend).unwrap();
+        assert_eq!(json, "\"extend\"");
+        let json = serde_json::to_string(&PlayPolicy::IgnoreIfPlaying).unwrap();
+        assert_eq!(json, "\"ignore_if_playing\"");
+    }
+
+    #[test]
+    fn test_sound_import_warning_serde() {
+        let json = r#"{
+            "id": "s1",
+            "name": "Old Sound",
+            "file_path": "/path/to/sound.mp3",
+            "category_id": "default",
+            "icon": null,
+            "volume": null
+        }"#;
+        let mut sound: Sound = serde_json::from_str(json).unwrap();
+        assert_eq!(sound.import_warning, None);
+
+        sound.import_warning = Some(ImportWarning::NoAudio);
+        let json = serde_json::to_string(&sound).unwrap();
+        assert!(json.contains("\"import_warning\":\"no_audio\""));
+    }
+
+    #[test]
+    fn test_sound_lufs_defaults_and_resets_with_file() {
+        let json = r#"{
+            "id": "s1",
+            "name": "Old Sound",
+            "file_path": "/path/to/sound.mp3",
+            "category_id": "default",
+            "icon": null,
+            "volume": null
+        }"#;
+        let sound: Sound = serde_json::from_str(json).unwrap();
+        assert_eq!(sound.lufs, None);
+        assert!(!sound.loudness_measured);
+
+        let mut library = SoundLibrary::default();
+        library.sounds.push(Sound {
+            lufs: Some(-18.5),
+            loudness_measured: true,
+            ..sound
+        });
+        let id = library.sounds[0].id.clone();
+        let path = Some("/path/to/sound.mp3".to_string());
+        let same = update_sound(
+            &mut library,
+            &id,
+            None,
+            path,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(same.unwrap().lufs, Some(-18.5));
+
+        let path = Some("/path/to/other.mp3".to_string());
+        let moved = update_sound(
+            &mut library,
+            &id,
+            None,
+            path,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let moved = moved.unwrap();
+        assert_eq!(moved.lufs, None);
+        assert!(!moved.loudness_measured);
+    }
+
     // -------------------------------------------------------------------------
     // add_sound Tests
     // -------------------------------------------------------------------------
@@ -797,6 +1215,249 @@ mod tests {
         assert!(!library.sounds.iter().any(|s| s.id == sound2.id));
     }
 
+    // -------------------------------------------------------------------------
+    // duplicate_sound Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_duplicate_sound_copies_fields() {
+        let mut library = SoundLibrary::default();
+        let original = add_sound(
+            &mut library,
+            "Intro".to_string(),
+            "/intro.mp3".to_string(),
+            CategoryId::from_string("default".to_string()),
+            Some("🎬".to_string()),
+            Some(0.6),
+        );
+        add_sound(
+            &mut library,
+            "Outro".to_string(),
+            "/outro.mp3".to_string(),
+            CategoryId::from_string("default".to_string()),
+            None,
+            None,
+        );
+        library.sounds[0].trim_start_ms = Some(1_000);
+        library.sounds[0].play_policy = PlayPolicy::Extend;
+
+        let copy = duplicate_sound(&mut library, &original.id).unwrap();
+
+        assert_ne!(copy.id, original.id);
+        assert_eq!(copy.name, "Intro (copy)");
+        assert_eq!(copy.file_path, original.file_path);
+        assert_eq!(copy.icon, original.icon);
+        assert_eq!(copy.volume, original.volume);
+        assert_eq!(copy.trim_start_ms, Some(1_000));
+        assert_eq!(copy.play_policy, PlayPolicy::Extend);
+        // Placed right after the original
+        assert_eq!(library.sounds.len(), 3);
+        assert_eq!(library.sounds[1].id, copy.id);
+        assert_eq!(library.sounds[2].name, "Outro");
+    }
+
+    #[test]
+    fn test_duplicate_sound_not_found() {
+        let mut library = SoundLibrary::default();
+        assert!(duplicate_sound(&mut library, &SoundId::new()).is_err());
+        assert!(library.sounds.is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // Expiry Tests
+    // -------------------------------------------------------------------------
+
+    fn add_temporary(library: &mut SoundLibrary, name: &str, path: &str, expires_at: Option<u64>) {
+        add_sound(
+            library,
+            name.to_string(),
+            path.to_string(),
+            CategoryId::from_string("default".to_string()),
+            None,
+            None,
+        );
+        library.sounds.last_mut().unwrap().expires_at = expires_at;
+    }
+
+    #[test]
+    fn test_remove_expired() {
+        let mut library = SoundLibrary::default();
+        add_temporary(&mut library, "Past", "/past.mp3", Some(500));
+        add_temporary(&mut library, "Now", "/now.mp3", Some(1000));
+        add_temporary(&mut library, "Future", "/future.mp3", Some(2000));
+        add_temporary(&mut library, "Forever", "/forever.mp3", None);
+
+        let removed = remove_expired(&mut library, 1000);
+
+        let removed: Vec<_> = removed.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(removed, vec!["Past", "Now"]);
+        let kept: Vec<_> = library.sounds.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(kept, vec!["Future", "Forever"]);
+    }
+
+    #[test]
+    fn test_orphaned_managed_files() {
+        let mut library = SoundLibrary::default();
+        add_temporary(&mut library, "Managed", "/data/soundpacks/p/a.mp3", Some(1));
+        add_temporary(&mut library, "Shared", "/data/soundpacks/p/b.mp3", Some(1));
+        add_temporary(&mut library, "External", "/music/c.mp3", Some(1));
+        // Still uses b.mp3 after the others expire
+        add_temporary(
+            &mut library,
+            "Shared Copy",
+            "/data/soundpacks/p/b.mp3",
+            None,
+        );
+
+        let removed = remove_expired(&mut library, 10);
+        let files = orphaned_managed_files(&removed, &library, Path::new("/data/soundpacks"));
+
+        assert_eq!(files, vec![PathBuf::from("/data/soundpacks/p/a.mp3")]);
+    }
+
+    #[test]
+    fn test_stored_paths_relative_inside_data_dir() {
+        let data_dir = Path::new("/data");
+        let mut library = SoundLibrary::default();
+        let category = library.categories[0].id.clone();
+        let managed = add_sound(
+            &mut library,
+            "Managed".to_string(),
+            "/data/sounds/horn.mp3".to_string(),
+            category.clone(),
+            None,
+            None,
+        );
+        add_sound(
+            &mut library,
+            "External".to_string(),
+            "/music/c.mp3".to_string(),
+            category,
+            None,
+            None,
+        );
+
+        let stored = to_stored_paths(&library, data_dir);
+        assert_eq!(stored.sounds[0].file_path, "sounds/horn.mp3");
+        assert_eq!(stored.sounds[1].file_path, "/music/c.mp3");
+        // The library in memory keeps absolute paths
+        assert_eq!(library.sounds[0].file_path, managed.file_path);
+
+        let mut resolved = stored;
+        resolve_stored_paths(&mut resolved, data_dir);
+        assert_eq!(
+            Path::new(&resolved.sounds[0].file_path),
+            Path::new("/data/sounds/horn.mp3")
+        );
+        assert_eq!(resolved.sounds[1].file_path, "/music/c.mp3");
+    }
+
+    #[test]
+    fn test_resolve_stored_paths_follows_moved_data_dir() {
+        let mut library = SoundLibrary::default();
+        let category = library.categories[0].id.clone();
+        add_sound(
+            &mut library,
+            "Managed".to_string(),
+            "sounds/horn.mp3".to_string(),
+            category,
+            None,
+            None,
+        );
+
+        resolve_stored_paths(&mut library, Path::new("/new/data"));
+        assert_eq!(
+            Path::new(&library.sounds[0].file_path),
+            Path::new("/new/data/sounds/horn.mp3")
+        );
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec![
+            " Meme ".to_string(),
+            "".to_string(),
+            "loud".to_string(),
+            "meme".to_string(),
+        ];
+        assert_eq!(normalize_tags(tags), vec!["Meme", "loud"]);
+    }
+
+    #[test]
+    fn test_set_tags_and_filter() {
+        let mut library = SoundLibrary::default();
+        let category = library.categories[0].id.clone();
+        let sound = add_sound(
+            &mut library,
+            "Horn".to_string(),
+            "/horn.mp3".to_string(),
+            category,
+            None,
+            None,
+        );
+
+        let tagged = set_tags(
+            &mut library,
+            &sound.id,
+            vec!["Meme".to_string(), "loud".to_string()],
+        )
+        .unwrap();
+        assert!(has_tags(&tagged, &["meme".to_string()]));
+        assert!(has_tags(&tagged, &["MEME".to_string(), "Loud".to_string()]));
+        assert!(!has_tags(
+            &tagged,
+            &["meme".to_string(), "quiet".to_string()]
+        ));
+        assert!(has_tags(&tagged, &[]));
+
+        assert!(set_tags(&mut library, &SoundId::new(), vec![]).is_err());
+    }
+
+    #[test]
+    fn test_list_tags_counts_case_insensitively() {
+        let mut library = SoundLibrary::default();
+        let category = library.categories[0].id.clone();
+        for (name, tags) in [
+            ("a", vec!["meme", "loud"]),
+            ("b", vec!["Meme"]),
+            ("c", vec![]),
+        ] {
+            let sound = add_sound(
+                &mut library,
+                name.to_string(),
+                format!("/{}.mp3", name),
+                category.clone(),
+                None,
+                None,
+            );
+            let tags = tags.into_iter().map(String::from).collect();
+            set_tags(&mut library, &sound.id, tags).unwrap();
+        }
+
+        assert_eq!(
+            list_tags(&library),
+            vec![
+                TagCount {
+                    tag: "loud".to_string(),
+                    count: 1
+                },
+                TagCount {
+                    tag: "meme".to_string(),
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_name_for_file() {
+        let path = Path::new("/music/air_horn-loud.mp3");
+        assert_eq!(name_for_file(path, None), "Air Horn Loud");
+        assert_eq!(name_for_file(path, Some("  ")), "Air Horn Loud");
+        assert_eq!(name_for_file(path, Some(" Horn ")), "Horn");
+        assert_eq!(name_for_file(Path::new("bruh.wav"), None), "Bruh");
+    }
+
     // -------------------------------------------------------------------------
     // add_category Tests
     // -------------------------------------------------------------------------
@@ -1021,4 +1682,14 @@ mod tests {
         assert_eq!(library.sounds.len(), 1);
         assert_eq!(library.sounds[0].name, "DefaultSound");
     }
+
+    #[test]
+    fn test_preview_region_validation() {
+        let region = |start_ms, end_ms| PreviewRegion { start_ms, end_ms };
+        assert!(region(12_000, 14_000).validate().is_ok());
+        assert!(region(0, MAX_PREVIEW_MS).validate().is_ok());
+        assert!(region(2_000, 2_000).validate().is_err());
+        assert!(region(3_000, 1_000).validate().is_err());
+        assert!(region(0, MAX_PREVIEW_MS + 1).validate().is_err());
+    }
 }
@@ -0,0 +1,298 @@
+//! Launch arguments and startup actions
+//!
+//! The autostart entry can carry flags (`--profile <name>`,
+//! `--start-minimized`) so a boot launch differs from a manual one. Settings
+//! additionally hold a list of actions executed once during setup.
+//!
+//! Startup phases are timed for `get_startup_report`; work that isn't needed
+//! before the window shows runs deferred on a background thread.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::settings::AppSettings;
+
+/// Flag selecting the profile to start with
+pub const PROFILE_FLAG: &str = "--profile";
+
+/// Flag hiding the window at startup (like the `start_minimized` setting)
+pub const START_MINIMIZED_FLAG: &str = "--start-minimized";
+
+/// Profile selected on the command line, set once at startup
+static LAUNCH_PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// When `run` was entered (start of the startup report)
+static LAUNCHED_AT: OnceLock<Instant> = OnceLock::new();
+
+/// Phase timings collected during startup
+static REPORT: Mutex<StartupReport> = Mutex::new(StartupReport::new());
+
+/// Options parsed from the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchArgs {
+    /// `--profile <name>` / `--profile=<name>`
+    pub profile: Option<String>,
+    /// `--start-minimized`
+    pub start_minimized: bool,
+}
+
+impl LaunchArgs {
+    /// Parse launch flags, ignoring anything unknown (e.g. `--debug`)
+    pub fn parse<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            if arg == START_MINIMIZED_FLAG {
+                parsed.start_minimized = true;
+            } else if arg == PROFILE_FLAG {
+                parsed.profile = args.next().and_then(|name| profile_name(name.as_ref()));
+            } else if let Some(name) = arg.strip_prefix("--profile=") {
+                parsed.profile = profile_name(name);
+            }
+        }
+        parsed
+    }
+
+    /// Parse the arguments of the current process
+    pub fn from_env() -> Self {
+        Self::parse(std::env::args().skip(1))
+    }
+}
+
+fn profile_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Remember the profile selected on the command line (first call wins)
+pub fn set_launch_profile(profile: Option<String>) {
+    let _ = LAUNCH_PROFILE.set(profile);
+}
+
+/// Profile named with `--profile`, if any
+pub fn launch_profile() -> Option<String> {
+    LAUNCH_PROFILE.get().cloned().flatten()
+}
+
+/// Arguments written into the autostart entry
+pub fn autostart_args(settings: &AppSettings) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(profile) = settings.autostart_profile.as_deref().and_then(profile_name) {
+        args.push(PROFILE_FLAG.to_string());
+        args.push(profile);
+    }
+    if settings.autostart_minimized {
+        args.push(START_MINIMIZED_FLAG.to_string());
+    }
+    args
+}
+
+/// Action executed once during startup, in list order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StartupAction {
+    /// Route a microphone to VB-Cable (the configured one if `microphone_id` is unset)
+    EnableMicRouting {
+        #[serde(default)]
+        microphone_id: Option<String>,
+    },
+    /// Switch to the output devices last used with a profile
+    ApplyProfileDevices { profile: String },
+}
+
+/// Startup phase measured for the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    /// Loading settings, sounds, hotkeys and session from disk
+    StateLoad,
+    /// Checking the saved output devices against the present ones
+    DeviceEnumeration,
+    /// Registering the saved global shortcuts
+    HotkeyRegistration,
+    TrayInit,
+    /// Decoding the sounds of the last category into the cache
+    CacheWarmup,
+    /// Precomputing grid card waveforms missing from the sidecar cache
+    CardAssets,
+}
+
+/// Duration of one startup phase
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseTiming {
+    pub phase: StartupPhase,
+    pub duration_ms: u64,
+    /// Ran after setup, in the background
+    pub deferred: bool,
+}
+
+/// Startup phases in the order they finished
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupReport {
+    pub phases: Vec<PhaseTiming>,
+    /// Launch until setup returned (the window shows right after)
+    pub setup_ms: Option<u64>,
+    /// Launch until the deferred work finished
+    pub deferred_ms: Option<u64>,
+}
+
+impl StartupReport {
+    const fn new() -> Self {
+        Self {
+            phases: Vec::new(),
+            setup_ms: None,
+            deferred_ms: None,
+        }
+    }
+
+    fn record(&mut self, phase: StartupPhase, duration: Duration, deferred: bool) {
+        self.phases.push(PhaseTiming {
+            phase,
+            duration_ms: duration.as_millis() as u64,
+            deferred,
+        });
+    }
+}
+
+/// Start the startup clock (first call wins)
+pub fn mark_launched() {
+    let _ = LAUNCHED_AT.set(Instant::now());
+}
+
+/// Run a setup phase and record how long it took
+pub fn time_phase<T>(phase: StartupPhase, f: impl FnOnce() -> T) -> T {
+    timed(phase, false, f)
+}
+
+/// Run a deferred (background) phase and record how long it took
+pub fn time_deferred_phase<T>(phase: StartupPhase, f: impl FnOnce() -> T) -> T {
+    timed(phase, true, f)
+}
+
+fn timed<T>(phase: StartupPhase, deferred: bool, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    let duration = start.elapsed();
+    tracing::debug!(
+        phase = ?phase,
+        deferred = deferred,
+        duration_ms = duration.as_millis() as u64,
+        "Startup phase finished"
+    );
+    if let Ok(mut report) = REPORT.lock() {
+        report.record(phase, duration, deferred);
+    }
+    result
+}
+
+/// Record that setup returned
+pub fn mark_setup_complete() {
+    let elapsed = since_launch();
+    tracing::info!(setup_ms = ?elapsed, "Setup complete");
+    if let Ok(mut report) = REPORT.lock() {
+        report.setup_ms = elapsed;
+    }
+}
+
+/// Record that the deferred startup work finished
+pub fn mark_deferred_complete() {
+    let elapsed = since_launch();
+    tracing::info!(deferred_ms = ?elapsed, "Deferred startup work complete");
+    if let Ok(mut report) = REPORT.lock() {
+        report.deferred_ms = elapsed;
+    }
+}
+
+/// Timings collected so far
+pub fn startup_report() -> StartupReport {
+    REPORT
+        .lock()
+        .map(|report| report.clone())
+        .unwrap_or_else(|_| StartupReport::new())
+}
+
+fn since_launch() -> Option<u64> {
+    LAUNCHED_AT
+        .get()
+        .map(|launched| launched.elapsed().as_millis() as u64)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launch_args() {
+        let args = LaunchArgs::parse(["--debug", "--profile", "Streaming", "--start-minimized"]);
+        assert_eq!(args.profile.as_deref(), Some("Streaming"));
+        assert!(args.start_minimized);
+
+        let args = LaunchArgs::parse(["--profile=Late Night"]);
+        assert_eq!(args.profile.as_deref(), Some("Late Night"));
+        assert!(!args.start_minimized);
+
+        // Missing or blank profile names are ignored
+        assert_eq!(LaunchArgs::parse(["--profile"]), LaunchArgs::default());
+        assert_eq!(LaunchArgs::parse(["--profile=  "]), LaunchArgs::default());
+    }
+
+    #[test]
+    fn test_autostart_args_roundtrip() {
+        let mut settings = AppSettings::default();
+        assert!(autostart_args(&settings).is_empty());
+
+        settings.autostart_profile = Some("Streaming".to_string());
+        settings.autostart_minimized = true;
+        let args = autostart_args(&settings);
+        assert_eq!(args, ["--profile", "Streaming", "--start-minimized"]);
+
+        let parsed = LaunchArgs::parse(&args);
+        assert_eq!(parsed.profile.as_deref(), Some("Streaming"));
+        assert!(parsed.start_minimized);
+    }
+
+    #[test]
+    fn test_report_records_phases_in_order() {
+        let mut report = StartupReport::new();
+        report.record(StartupPhase::StateLoad, Duration::from_millis(12), false);
+        report.record(StartupPhase::CacheWarmup, Duration::from_millis(340), true);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["phases"][0]["phase"], "state_load");
+        assert_eq!(json["phases"][0]["duration_ms"], 12);
+        assert_eq!(json["phases"][1]["phase"], "cache_warmup");
+        assert_eq!(json["phases"][1]["deferred"], true);
+        assert!(json["setup_ms"].is_null());
+    }
+
+    #[test]
+    fn test_startup_action_serde() {
+        let json = r#"[
+            {"type": "enable_mic_routing"},
+            {"type": "apply_profile_devices", "profile": "Streaming"}
+        ]"#;
+        let actions: Vec<StartupAction> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            actions,
+            vec![
+                StartupAction::EnableMicRouting {
+                    microphone_id: None
+                },
+                StartupAction::ApplyProfileDevices {
+                    profile: "Streaming".to_string()
+                },
+            ]
+        );
+    }
+}
@@ -1,12 +1,18 @@
 //! In-memory application state for zero-latency hotkey handling
 //!
-//! All state changes are written to both in-memory state and disk for persistence.
+//! State changes are applied in memory immediately and persisted by the
+//! background writer (see `writer`).
 
 use std::sync::{Arc, RwLock};
 
+use tauri::Manager;
+
 use crate::hotkeys::HotkeyMappings;
+use crate::session::SessionState;
 use crate::settings::AppSettings;
-use crate::sounds::SoundLibrary;
+use crate::sounds::{SoundId, SoundLibrary};
+use crate::stats::SoundStats;
+use crate::writer::{SaveWriter, Snapshot};
 
 /// Thread-safe in-memory application state
 pub struct AppState {
@@ -16,6 +22,10 @@ pub struct AppState {
     pub sounds: Arc<RwLock<SoundLibrary>>,
     /// Application settings (devices, volumes, preferences)
     pub settings: Arc<RwLock<AppSettings>>,
+    /// Transient session state (last category, devices per profile, window geometry)
+    pub session: Arc<RwLock<SessionState>>,
+    /// Play counts and last-played times per sound
+    pub stats: Arc<RwLock<SoundStats>>,
 }
 
 impl AppState {
@@ -23,12 +33,24 @@ impl AppState {
     pub fn load(app_handle: &tauri::AppHandle) -> Result<Self, String> {
         tracing::info!("Loading application state from disk");
 
+        let settings = crate::settings::load(app_handle)?;
+        let session = crate::session::load(app_handle)?;
+        let profile = crate::startup::launch_profile()
+            .or(session.active_profile.clone())
+            .unwrap_or_else(|| crate::session::DEFAULT_PROFILE.to_string());
+        crate::profiles::set_active(&profile);
+
         let hotkeys = crate::hotkeys::load(app_handle)?;
         let sounds = crate::sounds::load(app_handle)?;
-        let settings = crate::settings::load(app_handle)?;
+        let mut stats = crate::stats::load(app_handle)?;
+        if stats.retain_library(&sounds) {
+            crate::stats::save(&stats, app_handle)?;
+        }
+        crate::i18n::set_language(settings.language);
 
         tracing::info!(
-            "State loaded: {} hotkeys, {} sounds, {} categories",
+            "State loaded: profile '{}', {} hotkeys, {} sounds, {} categories",
+            profile,
             hotkeys.mappings.len(),
             sounds.sounds.len(),
             sounds.categories.len()
@@ -38,6 +60,8 @@ impl AppState {
             hotkeys: Arc::new(RwLock::new(hotkeys)),
             sounds: Arc::new(RwLock::new(sounds)),
             settings: Arc::new(RwLock::new(settings)),
+            session: Arc::new(RwLock::new(session)),
+            stats: Arc::new(RwLock::new(stats)),
         })
     }
 
@@ -83,51 +107,183 @@ impl AppState {
             .expect("RwLock poisoned: settings write failed")
     }
 
-    /// Update hotkeys in memory and persist to disk
+    /// Get a read-locked reference to session state
+    pub fn read_session(&self) -> std::sync::RwLockReadGuard<'_, SessionState> {
+        self.session
+            .read()
+            .expect("RwLock poisoned: session read failed")
+    }
+
+    /// Get a write-locked reference to session state
+    pub fn write_session(&self) -> std::sync::RwLockWriteGuard<'_, SessionState> {
+        self.session
+            .write()
+            .expect("RwLock poisoned: session write failed")
+    }
+
+    /// Get a read-locked reference to play statistics
+    pub fn read_stats(&self) -> std::sync::RwLockReadGuard<'_, SoundStats> {
+        self.stats
+            .read()
+            .expect("RwLock poisoned: stats read failed")
+    }
+
+    /// Get a write-locked reference to play statistics
+    pub fn write_stats(&self) -> std::sync::RwLockWriteGuard<'_, SoundStats> {
+        self.stats
+            .write()
+            .expect("RwLock poisoned: stats write failed")
+    }
+
+    /// Update hotkeys in memory and queue them for saving
+    ///
+    /// Disk errors are reported by the writer with `save-failed`.
     pub fn update_and_save_hotkeys(
         &self,
         app_handle: &tauri::AppHandle,
         mappings: HotkeyMappings,
     ) -> Result<(), String> {
-        // Write to disk first (fail fast if disk error)
-        crate::hotkeys::save(&mappings, app_handle)?;
-
-        // Update in-memory state
-        *self.write_hotkeys() = mappings;
+        // Queue under the write lock so snapshots are written in update order
+        let mut current = self.write_hotkeys();
+        app_handle
+            .state::<SaveWriter>()
+            .enqueue(Snapshot::Hotkeys(mappings.clone()));
+        *current = mappings;
 
-        tracing::debug!("Hotkeys updated in memory and persisted to disk");
+        tracing::debug!("Hotkeys updated in memory and queued for saving");
         Ok(())
     }
 
-    /// Update sound library in memory and persist to disk
+    /// Update sound library in memory and queue it for saving
     pub fn update_and_save_sounds(
         &self,
         app_handle: &tauri::AppHandle,
         library: SoundLibrary,
     ) -> Result<(), String> {
-        // Write to disk first (fail fast if disk error)
-        crate::sounds::save(&library, app_handle)?;
-
-        // Update in-memory state
-        *self.write_sounds() = library;
+        // Queue under the write lock so snapshots are written in update order
+        let mut current = self.write_sounds();
+        app_handle
+            .state::<SaveWriter>()
+            .enqueue(Snapshot::Sounds(library.clone()));
+        *current = library;
 
-        tracing::debug!("Sound library updated in memory and persisted to disk");
+        tracing::debug!("Sound library updated in memory and queued for saving");
         Ok(())
     }
 
-    /// Update settings in memory and persist to disk
+    /// Update settings in memory and queue them for saving
     pub fn update_and_save_settings(
         &self,
         app_handle: &tauri::AppHandle,
         settings: AppSettings,
     ) -> Result<(), String> {
-        // Write to disk first (fail fast if disk error)
-        crate::settings::save(&settings, app_handle)?;
+        let language = settings.language;
+        let (language_changed, rules_changed) = {
+            let current = self.read_settings();
+            (
+                current.language != language,
+                current.app_rules_enabled != settings.app_rules_enabled
+                    || current.app_rules != settings.app_rules,
+            )
+        };
 
-        // Update in-memory state
-        *self.write_settings() = settings;
+        // Queue under the write lock so snapshots are written in update order
+        let mut current = self.write_settings();
+        app_handle
+            .state::<SaveWriter>()
+            .enqueue(Snapshot::Settings(settings.clone()));
+        *current = settings;
+        drop(current);
 
-        tracing::debug!("Settings updated in memory and persisted to disk");
+        if language_changed {
+            crate::i18n::set_language(language);
+            crate::tray::update_labels(app_handle);
+        }
+        if rules_changed {
+            crate::app_rules::rules_changed();
+        }
+
+        tracing::debug!("Settings updated in memory and queued for saving");
         Ok(())
     }
+
+    /// Update session state in memory and queue it for saving
+    pub fn update_and_save_session(
+        &self,
+        app_handle: &tauri::AppHandle,
+        session: SessionState,
+    ) -> Result<(), String> {
+        // Queue under the write lock so snapshots are written in update order
+        let mut current = self.write_session();
+        app_handle
+            .state::<SaveWriter>()
+            .enqueue(Snapshot::Session(session.clone()));
+        *current = session;
+
+        tracing::debug!("Session updated in memory and queued for saving");
+        Ok(())
+    }
+
+    /// Count a play of a sound and queue the statistics for saving
+    pub fn record_play(&self, app_handle: &tauri::AppHandle, sound_id: &SoundId) {
+        let mut stats = self.write_stats();
+        stats.record_play(sound_id, crate::stats::now_ms());
+        app_handle
+            .state::<SaveWriter>()
+            .enqueue(Snapshot::Stats(stats.clone()));
+    }
+
+    /// Load another profile's hotkeys, library and statistics
+    ///
+    /// Queued writes of the current profile are flushed first. Global
+    /// shortcuts follow the new hotkeys.
+    pub fn switch_profile(&self, app_handle: &tauri::AppHandle, name: &str) -> Result<(), String> {
+        app_handle.state::<SaveWriter>().flush();
+        let previous_profile = crate::profiles::active();
+        crate::profiles::set_active(name);
+
+        let loaded = (|| {
+            let hotkeys = crate::hotkeys::load(app_handle)?;
+            let sounds = crate::sounds::load(app_handle)?;
+            let mut stats = crate::stats::load(app_handle)?;
+            stats.retain_library(&sounds);
+            Ok::<_, String>((hotkeys, sounds, stats))
+        })();
+        let (hotkeys, sounds, stats) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                crate::profiles::set_active(&previous_profile);
+                return Err(e);
+            }
+        };
+
+        #[cfg(desktop)]
+        {
+            let previous: std::collections::HashSet<String> =
+                crate::hotkeys::all_hotkeys(&self.read_hotkeys()).collect();
+            let current = crate::hotkeys::all_hotkeys(&hotkeys).collect();
+            crate::reload::update_registrations(app_handle, &previous, &current);
+        }
+
+        tracing::info!(
+            "Loaded profile '{}': {} hotkeys, {} sounds",
+            name,
+            hotkeys.mappings.len(),
+            sounds.sounds.len()
+        );
+        *self.write_hotkeys() = hotkeys;
+        *self.write_sounds() = sounds;
+        *self.write_stats() = stats;
+        Ok(())
+    }
+
+    /// Persist the current in-memory session state, after all queued writes
+    ///
+    /// Window geometry is only tracked in memory while the app runs and
+    /// written out here on close/exit.
+    pub fn save_session(&self, app_handle: &tauri::AppHandle) -> Result<(), String> {
+        app_handle.state::<SaveWriter>().flush();
+        let session = self.read_session().clone();
+        crate::session::save(&session, app_handle)
+    }
 }
@@ -0,0 +1,189 @@
+//! Play statistics
+//!
+//! Counts how often each sound was played and when it was last played, for
+//! the "Frequently used" and "Recent" views. Stored in `stats.json`, apart
+//! from the library so a play never rewrites `sounds.json`. Each profile has
+//! its own.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::sounds::{SoundId, SoundLibrary};
+
+/// Plays of one sound
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayStats {
+    pub play_count: u32,
+    /// Unix time of the last play (ms)
+    pub last_played_ms: u64,
+}
+
+/// Play statistics of the library
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SoundStats {
+    #[serde(default)]
+    pub sounds: HashMap<SoundId, PlayStats>,
+}
+
+impl SoundStats {
+    /// Count a play of a sound
+    pub fn record_play(&mut self, sound_id: &SoundId, now_ms: u64) {
+        let stats = self.sounds.entry(sound_id.clone()).or_default();
+        stats.play_count = stats.play_count.saturating_add(1);
+        stats.last_played_ms = now_ms;
+    }
+
+    /// Most played sounds, ties broken by the more recent play
+    pub fn most_played(&self, limit: usize) -> Vec<(SoundId, PlayStats)> {
+        let mut entries = self.entries();
+        entries.sort_by(|a, b| {
+            b.1.play_count
+                .cmp(&a.1.play_count)
+                .then(b.1.last_played_ms.cmp(&a.1.last_played_ms))
+        });
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Last played sounds, most recent first
+    pub fn recently_played(&self, limit: usize) -> Vec<(SoundId, PlayStats)> {
+        let mut entries = self.entries();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.1.last_played_ms));
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Forget sounds that are no longer in the library
+    ///
+    /// Returns true if anything was removed.
+    pub fn retain_library(&mut self, library: &SoundLibrary) -> bool {
+        let before = self.sounds.len();
+        self.sounds
+            .retain(|id, _| library.sounds.iter().any(|s| &s.id == id));
+        self.sounds.len() != before
+    }
+
+    fn entries(&self) -> Vec<(SoundId, PlayStats)> {
+        self.sounds
+            .iter()
+            .map(|(id, stats)| (id.clone(), *stats))
+            .collect()
+    }
+}
+
+/// Current Unix time in ms
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Get the path to the active profile's stats file
+pub fn get_stats_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::profiles::data_dir(app_handle)?.join("stats.json"))
+}
+
+/// Load play statistics from disk
+///
+/// A corrupt stats file is not fatal: it is logged and replaced with defaults.
+pub fn load(app_handle: &tauri::AppHandle) -> Result<SoundStats, String> {
+    let stats_path = get_stats_path(app_handle)?;
+
+    if !stats_path.exists() {
+        return Ok(SoundStats::default());
+    }
+
+    let content = std::fs::read_to_string(&stats_path)
+        .map_err(|e| format!("Failed to read stats file: {}", e))?;
+
+    match serde_json::from_str(&content) {
+        Ok(stats) => Ok(stats),
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable stats file: {}", e);
+            Ok(SoundStats::default())
+        }
+    }
+}
+
+/// Save play statistics to disk (atomic write)
+pub fn save(stats: &SoundStats, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let stats_path = get_stats_path(app_handle)?;
+
+    let json = serde_json::to_string_pretty(stats)
+        .map_err(|e| format!("Failed to serialize stats: {}", e))?;
+
+    crate::persistence::atomic_write(&stats_path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(plays: &[(&SoundId, u64)]) -> SoundStats {
+        let mut stats = SoundStats::default();
+        for (id, at) in plays {
+            stats.record_play(id, *at);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_record_play_counts_and_timestamps() {
+        let id = SoundId::new();
+        let stats = played(&[(&id, 100), (&id, 250)]);
+        assert_eq!(
+            stats.sounds[&id],
+            PlayStats {
+                play_count: 2,
+                last_played_ms: 250
+            }
+        );
+    }
+
+    #[test]
+    fn test_most_played_and_recent_order() {
+        let (a, b, c) = (SoundId::new(), SoundId::new(), SoundId::new());
+        let stats = played(&[(&a, 1), (&a, 2), (&b, 3), (&c, 4), (&b, 5)]);
+
+        let most: Vec<SoundId> = stats.most_played(10).into_iter().map(|e| e.0).collect();
+        // a and b tie on plays, b was played later
+        assert_eq!(most, vec![b.clone(), a.clone(), c.clone()]);
+
+        let recent: Vec<SoundId> = stats.recently_played(2).into_iter().map(|e| e.0).collect();
+        assert_eq!(recent, vec![b, c]);
+    }
+
+    #[test]
+    fn test_retain_library_drops_deleted_sounds() {
+        let mut library = SoundLibrary::default();
+        let category = library.categories[0].id.clone();
+        let kept = crate::sounds::add_sound(
+            &mut library,
+            "Kept".to_string(),
+            "/kept.mp3".to_string(),
+            category,
+            None,
+            None,
+        )
+        .id;
+        let deleted = SoundId::new();
+        let mut stats = played(&[(&kept, 1), (&deleted, 2)]);
+
+        assert!(stats.retain_library(&library));
+        assert_eq!(stats.sounds.keys().collect::<Vec<_>>(), vec![&kept]);
+        assert!(!stats.retain_library(&library));
+    }
+
+    #[test]
+    fn test_stats_deserialize_empty_object() {
+        let stats: SoundStats = serde_json::from_str("{}").unwrap();
+        assert!(stats.sounds.is_empty());
+    }
+}
@@ -1,44 +1,140 @@
 //! System tray icon and menu management
 
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{CheckMenuItemBuilder, MenuBuilder, MenuItem, MenuItemBuilder, Submenu, SubmenuBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     Manager, Runtime,
 };
 
+use crate::i18n;
+use crate::profiles::{self, ProfileList};
+use crate::session::DEFAULT_PROFILE;
+
+/// Menu ID prefix of the profile entries (`profile:<name>`)
+const PROFILE_ITEM_PREFIX: &str = "profile:";
+
+/// Tray menu items, kept so labels can follow language changes
+struct TrayMenuItems<R: Runtime> {
+    show_hide: MenuItem<R>,
+    quick_search: MenuItem<R>,
+    stop_all: MenuItem<R>,
+    profiles: Submenu<R>,
+    quit: MenuItem<R>,
+}
+
 /// Initialize the system tray icon and menu
-pub fn init<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), Box<dyn std::error::Error>> {
+pub fn init(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     // Create menu items
-    let show_hide = MenuItemBuilder::with_id("show_hide", "Show/Hide").build(app)?;
-    let stop_all = MenuItemBuilder::with_id("stop_all", "Stop All Sounds").build(app)?;
-    let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
+    let show_hide = MenuItemBuilder::with_id("show_hide", i18n::t("tray.show_hide")).build(app)?;
+    let quick_search =
+        MenuItemBuilder::with_id("quick_search", i18n::t("tray.quick_search")).build(app)?;
+    let stop_all = MenuItemBuilder::with_id("stop_all", i18n::t("tray.stop_all")).build(app)?;
+    let profiles = SubmenuBuilder::new(app, i18n::t("tray.profiles")).build()?;
+    fill_profiles(app, &profiles, &profiles::list(app)?)?;
+    let quit = MenuItemBuilder::with_id("quit", i18n::t("tray.quit")).build(app)?;
 
     // Build menu with items and separator
     let menu = MenuBuilder::new(app)
-        .items(&[&show_hide, &stop_all])
+        .items(&[&show_hide, &quick_search, &stop_all])
+        .separator()
+        .item(&profiles)
         .separator()
         .items(&[&quit])
         .build()?;
 
     // Build tray icon with menu and event handlers
     // Note: We keep the menu but handle clicks manually to distinguish left/right
-    TrayIconBuilder::<R>::new()
+    TrayIconBuilder::new()
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
         .show_menu_on_left_click(false) // Disable automatic menu on left click
         .on_menu_event(move |app, event| {
-            handle_tray_menu_event(app, event.id().as_ref());
+            let id = event.id().as_ref();
+            match id.strip_prefix(PROFILE_ITEM_PREFIX) {
+                Some(profile) => {
+                    if let Err(e) = profiles::switch(app, profile) {
+                        tracing::error!("Failed to switch profile from tray: {}", e);
+                    }
+                }
+                None => handle_tray_menu_event(app, id),
+            }
         })
         .on_tray_icon_event(|tray, event| {
             handle_tray_icon_event(tray, event);
         })
         .build(app)?;
 
+    app.manage(TrayMenuItems {
+        show_hide,
+        quick_search,
+        stop_all,
+        profiles,
+        quit,
+    });
+
     tracing::info!("System tray initialized");
 
     Ok(())
 }
 
+/// Relabel the tray menu in the active language
+pub fn update_labels<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(items) = app.try_state::<TrayMenuItems<R>>() else {
+        return;
+    };
+
+    let labels = [
+        (&items.show_hide, "tray.show_hide"),
+        (&items.quick_search, "tray.quick_search"),
+        (&items.stop_all, "tray.stop_all"),
+        (&items.quit, "tray.quit"),
+    ];
+    for (item, key) in labels {
+        if let Err(e) = item.set_text(i18n::t(key)) {
+            tracing::warn!("Failed to update tray label {}: {}", key, e);
+        }
+    }
+    if let Err(e) = items.profiles.set_text(i18n::t("tray.profiles")) {
+        tracing::warn!("Failed to update tray label tray.profiles: {}", e);
+    }
+}
+
+/// Rebuild the profile entries after profiles were created, deleted or switched
+pub fn update_profiles(app: &tauri::AppHandle) {
+    let Some(items) = app.try_state::<TrayMenuItems<tauri::Wry>>() else {
+        return;
+    };
+    let result = profiles::list(app)
+        .map_err(Into::into)
+        .and_then(|list| fill_profiles(app, &items.profiles, &list));
+    if let Err(e) = result {
+        tracing::warn!("Failed to update tray profiles: {}", e);
+    }
+}
+
+/// Replace the entries of the profile submenu, checking the active profile
+fn fill_profiles<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    submenu: &Submenu<R>,
+    list: &ProfileList,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for item in submenu.items()? {
+        submenu.remove(&item)?;
+    }
+    for name in &list.profiles {
+        let label = if name == DEFAULT_PROFILE {
+            i18n::t("tray.default_profile").to_string()
+        } else {
+            name.clone()
+        };
+        let item = CheckMenuItemBuilder::with_id(format!("{}{}", PROFILE_ITEM_PREFIX, name), label)
+            .checked(*name == list.active)
+            .build(app)?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
 /// Handle tray menu item clicks
 fn handle_tray_menu_event<R: Runtime>(app: &tauri::AppHandle<R>, event_id: &str) {
     match event_id {
@@ -55,10 +151,15 @@ fn handle_tray_menu_event<R: Runtime>(app: &tauri::AppHandle<R>, event_id: &str)
                 }
             }
         }
+        "quick_search" => {
+            if let Err(e) = crate::commands::show_quick_search(app) {
+                tracing::error!("Failed to open quick search from tray: {}", e);
+            }
+        }
         "stop_all" => {
             // Call the stop_all_audio command
             let manager = app.state::<crate::AudioManager>();
-            if let Err(e) = crate::commands::stop_all_audio(manager) {
+            if let Err(e) = crate::commands::stop_all_audio(manager, app.clone()) {
                 tracing::error!("Failed to stop all audio from tray: {}", e);
             } else {
                 tracing::debug!("Stopped all audio from tray menu");
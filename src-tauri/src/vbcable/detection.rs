@@ -1,10 +1,23 @@
-//! VB-Cable detection via cpal device enumeration
+//! VB-Cable detection via cpal device enumeration, driver version via the registry
 
 use cpal::traits::{DeviceTrait, HostTrait};
 use serde::Serialize;
 use std::thread;
 use std::time::Duration;
 use tracing::{debug, info, warn};
+use windows::core::PCWSTR;
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+
+use super::installer::BUNDLED_DRIVER_VERSION;
+
+/// Device class key of sound, video and game controllers; every installed
+/// driver has a numbered subkey (`0000`, `0001`, ...) with its description
+/// and version
+const MEDIA_CLASS_KEY: &str =
+    r"SYSTEM\CurrentControlSet\Control\Class\{4d36e96c-e325-11ce-bfc1-08002be10318}";
+
+/// Highest driver subkey index searched
+const MAX_DRIVER_SUBKEYS: u32 = 256;
 
 /// Information about detected VB-Cable devices
 #[derive(Debug, Clone, Serialize)]
@@ -97,10 +110,133 @@ pub fn wait_for_vb_cable() -> Option<VbCableInfo> {
     None
 }
 
+// ============================================================================
+// Driver Version
+// ============================================================================
+
+/// Installed driver version compared with the one SonicDeck installs
+#[derive(Debug, Clone, Serialize)]
+pub struct VbCableUpdateInfo {
+    /// Installed driver version, None if it couldn't be read
+    pub installed_version: Option<String>,
+    /// Version installed by the downloaded driver pack
+    pub bundled_version: String,
+    /// The installed driver is older than the bundled one
+    pub update_available: bool,
+}
+
+/// Compare the installed VB-Cable driver with the bundled installer's version
+///
+/// Older drivers are known to cause crackling and dropouts; an unreadable
+/// version never reports an update.
+pub fn check_for_update() -> VbCableUpdateInfo {
+    let installed_version = installed_driver_version();
+    let update_available = installed_version
+        .as_deref()
+        .is_some_and(|installed| is_outdated(installed, BUNDLED_DRIVER_VERSION));
+
+    info!(
+        "VB-Cable driver version: {:?} (bundled {}, update available: {})",
+        installed_version, BUNDLED_DRIVER_VERSION, update_available
+    );
+
+    VbCableUpdateInfo {
+        installed_version,
+        bundled_version: BUNDLED_DRIVER_VERSION.to_string(),
+        update_available,
+    }
+}
+
+/// Read the installed VB-Cable driver version from the device class key
+fn installed_driver_version() -> Option<String> {
+    for index in 0..MAX_DRIVER_SUBKEYS {
+        let key = format!("{}\\{:04}", MEDIA_CLASS_KEY, index);
+        let Some(description) = read_registry_string(&key, "DriverDesc") else {
+            continue;
+        };
+        if description
+            .to_lowercase()
+            .contains("vb-audio virtual cable")
+        {
+            debug!("VB-Cable driver found at {}", key);
+            return read_registry_string(&key, "DriverVersion");
+        }
+    }
+    warn!("VB-Cable driver not found in the registry");
+    None
+}
+
+/// Read a string value below HKEY_LOCAL_MACHINE
+fn read_registry_string(key: &str, value: &str) -> Option<String> {
+    let key_wide: Vec<u16> = key.encode_utf16().chain(std::iter::once(0)).collect();
+    let value_wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buffer = [0u16; 256];
+    let mut size = std::mem::size_of_val(&buffer) as u32;
+
+    // SAFETY: both names are NUL-terminated, and `size` holds the buffer size in bytes
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            PCWSTR(key_wide.as_ptr()),
+            PCWSTR(value_wide.as_ptr()),
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+    };
+    result.ok().ok()?;
+
+    // `size` includes the terminating NUL
+    let len = (size as usize / 2).saturating_sub(1);
+    Some(String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// Parse a dotted driver version ("3.3.1.7"); missing parts count as 0
+fn parse_version(version: &str) -> Option<[u32; 4]> {
+    let mut parts = [0u32; 4];
+    let mut count = 0;
+    for part in version.trim().split('.') {
+        if count == parts.len() {
+            return None;
+        }
+        parts[count] = part.trim().parse().ok()?;
+        count += 1;
+    }
+    Some(parts)
+}
+
+/// Whether `installed` is older than `bundled` (unparseable versions never are)
+fn is_outdated(installed: &str, bundled: &str) -> bool {
+    match (parse_version(installed), parse_version(bundled)) {
+        (Some(installed), Some(bundled)) => installed < bundled,
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("3.3.1.7"), Some([3, 3, 1, 7]));
+        assert_eq!(parse_version("3.3"), Some([3, 3, 0, 0]));
+        assert_eq!(parse_version(" 3.3.1.7 "), Some([3, 3, 1, 7]));
+        assert_eq!(parse_version("3.3.1.7.1"), None);
+        assert_eq!(parse_version("v3.3"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn test_is_outdated() {
+        assert!(is_outdated("3.3.1.4", "3.3.1.7"));
+        assert!(is_outdated("3.2.9.9", "3.3.1.7"));
+        assert!(!is_outdated("3.3.1.7", "3.3.1.7"));
+        assert!(!is_outdated("3.3.1.10", "3.3.1.7"));
+        assert!(!is_outdated("unknown", "3.3.1.7"));
+    }
+
     #[test]
     fn test_vb_cable_info_serialization() {
         let info = VbCableInfo {
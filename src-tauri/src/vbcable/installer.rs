@@ -1,10 +1,17 @@
 //! VB-Cable installer download and launch
 //!
 //! Provides functionality to download, extract, and launch the VB-Cable installer.
+//!
+//! Downloads live in a temp directory. The extracted installer is kept for
+//! retries and uninstalls; its SHA-256 is recorded outside the temp directory
+//! so a leftover copy is only reused if it wasn't modified. Files older than
+//! `TEMP_FILE_MAX_AGE` are removed at startup.
 
+use sha2::{Digest, Sha256};
 use std::fs::{self, File};
 use std::io::{copy, Cursor};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tracing::{debug, error, info, warn};
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::{HWND, WAIT_OBJECT_0};
@@ -17,15 +24,77 @@ const VBCABLE_DOWNLOAD_URL: &str =
 const VBCABLE_ZIP_NAME: &str = "VBCABLE_Driver_Pack45.zip";
 const VBCABLE_INSTALLER_NAME: &str = "VBCABLE_Setup_x64.exe";
 
+/// Driver version installed from the pack above (keep in sync with the URL)
+pub const BUNDLED_DRIVER_VERSION: &str = "3.3.1.7";
+
+/// Recorded SHA-256 of the extracted installer (in the app data directory)
+const INSTALLER_HASH_FILE: &str = "vbcable_installer.sha256";
+
+/// Temp files older than this are removed at startup
+const TEMP_FILE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 // ZIP bomb protection limits
 const MAX_ZIP_SIZE: u64 = 10 * 1024 * 1024; // 10 MB download limit
 const MAX_EXTRACTED_SIZE: u64 = 50 * 1024 * 1024; // 50 MB total extracted limit
 const MAX_FILE_COUNT: usize = 50; // Max files in archive
 const MAX_SINGLE_FILE_SIZE: u64 = 20 * 1024 * 1024; // 20 MB per file limit
 
+/// Directory for downloads and the extracted installer
+fn temp_dir() -> PathBuf {
+    std::env::temp_dir().join("sonicdeck_vbcable")
+}
+
+/// Get the recorded installer hash path
+fn get_hash_file_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("com.sonicdeck.app").join(INSTALLER_HASH_FILE))
+}
+
+/// Hex SHA-256 of a file
+fn file_sha256(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    copy(&mut file, &mut hasher).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Record the hash of a freshly extracted installer
+fn record_installer_hash(installer_path: &Path) -> Result<(), String> {
+    let path = get_hash_file_path().ok_or("Could not determine hash file path")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let hash = file_sha256(installer_path)?;
+    fs::write(&path, &hash).map_err(|e| format!("Failed to write hash file: {}", e))?;
+    debug!("Recorded installer hash {}", hash);
+    Ok(())
+}
+
+/// Whether a leftover installer still matches the hash recorded at extraction
+fn verify_installer(installer_path: &Path) -> bool {
+    let Some(recorded) = get_hash_file_path().and_then(|p| fs::read_to_string(p).ok()) else {
+        warn!("No recorded hash for the leftover installer");
+        return false;
+    };
+    match file_sha256(installer_path) {
+        Ok(hash) if hash == recorded.trim() => true,
+        Ok(hash) => {
+            warn!(
+                "Leftover installer hash mismatch (expected {}, found {})",
+                recorded.trim(),
+                hash
+            );
+            false
+        }
+        Err(e) => {
+            warn!("Failed to hash leftover installer: {}", e);
+            false
+        }
+    }
+}
+
 /// Download VB-Cable installer ZIP to temp directory
 pub fn download_vbcable() -> Result<PathBuf, String> {
-    let temp_dir = std::env::temp_dir().join("sonicdeck_vbcable");
+    let temp_dir = temp_dir();
     fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
 
     let zip_path = temp_dir.join(VBCABLE_ZIP_NAME);
@@ -157,6 +226,9 @@ pub fn extract_installer(zip_path: &PathBuf) -> Result<PathBuf, String> {
     match installer_path {
         Some(path) => {
             info!("Extracted {} files, installer at {:?}", archive.len(), path);
+            if let Err(e) = record_installer_hash(&path) {
+                warn!("Failed to record installer hash: {}", e);
+            }
             Ok(path)
         }
         None => {
@@ -265,7 +337,7 @@ pub fn install_vbcable() -> Result<(), String> {
 
 /// Cleanup temp files
 pub fn cleanup_temp_files() {
-    let temp_dir = std::env::temp_dir().join("sonicdeck_vbcable");
+    let temp_dir = temp_dir();
     if temp_dir.exists() {
         match fs::remove_dir_all(&temp_dir) {
             Ok(_) => info!("Cleaned up VB-Cable temp files"),
@@ -274,6 +346,62 @@ pub fn cleanup_temp_files() {
     }
 }
 
+/// Remove temp files left from sessions older than `TEMP_FILE_MAX_AGE`
+///
+/// Newer files are kept, so an uninstall can reuse the installer.
+pub fn cleanup_stale_temp_files() {
+    let temp_dir = temp_dir();
+    if !temp_dir.exists() {
+        return;
+    }
+    let removed = remove_stale_files(&temp_dir, TEMP_FILE_MAX_AGE, SystemTime::now());
+    if removed > 0 {
+        info!("Removed {} stale VB-Cable temp files", removed);
+    }
+    // Drop the directory once it's empty
+    let _ = fs::remove_dir(&temp_dir);
+}
+
+/// Remove files in `dir` last modified more than `max_age` before `now`
+///
+/// Returns the number of removed files.
+fn remove_stale_files(dir: &Path, max_age: Duration, now: SystemTime) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        if !metadata.is_file() || age.is_none_or(|age| age <= max_age) {
+            continue;
+        }
+        match fs::remove_file(entry.path()) {
+            Ok(_) => removed += 1,
+            Err(e) => warn!("Failed to remove stale temp file {:?}: {}", entry.path(), e),
+        }
+    }
+    removed
+}
+
+/// Total size of the VB-Cable temp directory in bytes
+pub fn temp_dir_size() -> u64 {
+    let Ok(entries) = fs::read_dir(temp_dir()) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
 /// Launch VB-Cable uninstaller with admin elevation (UAC prompt) and wait for completion
 ///
 /// Uses the same installer executable with -u flag for uninstall.
@@ -353,16 +481,15 @@ fn launch_uninstaller(installer_path: &PathBuf) -> Result<(), String> {
 pub fn uninstall_vbcable() -> Result<(), String> {
     info!("Starting VB-Cable uninstallation flow");
 
-    // Check if installer already exists in temp directory
-    let temp_dir = std::env::temp_dir().join("sonicdeck_vbcable");
-    let installer_path = temp_dir.join(VBCABLE_INSTALLER_NAME);
+    // Reuse the installer left in the temp directory if it's unmodified
+    let installer_path = temp_dir().join(VBCABLE_INSTALLER_NAME);
 
-    let final_installer_path = if installer_path.exists() {
+    let final_installer_path = if installer_path.exists() && verify_installer(&installer_path) {
         info!("Using existing installer at {:?}", installer_path);
         installer_path
     } else {
         // Need to download the installer first
-        info!("Installer not found, downloading...");
+        info!("No verified installer found, downloading...");
         let zip_path = download_vbcable()?;
         let path = extract_installer(&zip_path)?;
 
@@ -379,3 +506,39 @@ pub fn uninstall_vbcable() -> Result<(), String> {
     info!("VB-Cable uninstallation flow completed");
     Ok(())
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("VBCABLE_Setup_x64.exe"), b"installer").unwrap();
+        fs::write(dir.path().join("vbMmeCable64_win7.inf"), b"driver").unwrap();
+        let now = SystemTime::now();
+
+        // Fresh files are kept
+        assert_eq!(remove_stale_files(dir.path(), TEMP_FILE_MAX_AGE, now), 0);
+
+        // A week and a day later they're stale
+        let later = now + TEMP_FILE_MAX_AGE + Duration::from_secs(24 * 60 * 60);
+        assert_eq!(remove_stale_files(dir.path(), TEMP_FILE_MAX_AGE, later), 2);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_file_sha256() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.bin");
+        fs::write(&path, b"abc").unwrap();
+        assert_eq!(
+            file_sha256(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
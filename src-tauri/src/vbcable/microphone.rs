@@ -4,18 +4,36 @@
 //! while using VB-Cable for soundboard routing.
 //!
 //! Audio flow: Microphone -> [This Module] -> CABLE Input -> CABLE Output -> Discord
+//!                                         \-> Monitor device (optional mic monitoring)
+//!
+//! The capture passes through noise reduction (`noise.rs`) and the voice
+//! changer (`voice.rs`) before it is buffered, so CABLE Input and the
+//! monitor both get the cleaned up, changed voice.
+//! CABLE Input alone is ducked while sounds play (gain shared with
+//! `AudioManager`). With soundboard-to-mic mixing, playbacks are rendered
+//! straight into the CABLE Input stream and summed with the microphone,
+//! through a limiter, instead of playing on a broadcast device.
 //!
 //! ## Latency
 //! Uses a 100ms ring buffer for balance between latency and stability.
-//! Earlier versions used 1s which caused noticeable delay (see #83).
+//! Earlier versions used 1s which caused noticeable delay (see #83). When
+//! underruns keep happening (bursty drivers, clock drift), the buffer grows
+//! in steps up to 400ms. The buffer is lock-free, so neither the
+//! microphone nor the output callbacks ever wait on each other.
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
 use tracing::{debug, error, info, warn};
 
-use crate::audio::DeviceId;
+use super::noise::NoiseProcessor;
+use super::voice::VoiceProcessor;
+use crate::audio::{DeviceId, Limiter, MixRenderer};
 
 // ============================================================================
 // Global Routing State
@@ -24,14 +42,99 @@ use crate::audio::DeviceId;
 /// Global state for active microphone routing - only stores thread-safe data
 static ROUTING_STATE: Mutex<Option<RoutingHandle>> = Mutex::new(None);
 
+/// Receives routing losses and recoveries (set once at startup)
+static ROUTING_LISTENER: Mutex<Option<RoutingListener>> = Mutex::new(None);
+
+type RoutingListener = Box<dyn Fn(RoutingEvent) + Send>;
+
+/// Delay before the first attempt to restore failed routing (doubles with
+/// every failed attempt)
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest delay between attempts to restore routing
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Time without microphone callbacks after which the session counts as failed
+const INPUT_STALL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often the routing thread checks the stop signal and session health
+const SUPERVISOR_POLL: Duration = Duration::from_millis(100);
+
+/// Initial ring buffer length: a balance between latency and stability.
+/// Earlier versions used 1s which caused noticeable delay (see #83), 50ms
+/// was too aggressive and caused audio glitches
+const BASE_BUFFER_MS: u32 = 100;
+
+/// Longest the ring buffer grows to
+const MAX_BUFFER_MS: u32 = 400;
+
+/// Underruns within `UNDERRUN_WINDOW` that make the ring buffer grow
+const SUSTAINED_UNDERRUNS: u64 = 10;
+
+/// Span over which underruns are counted
+const UNDERRUN_WINDOW: Duration = Duration::from_secs(5);
+
 /// Thread-safe handle for controlling an active routing session
 struct RoutingHandle {
     /// Device ID of the microphone being routed
     microphone_id: String,
+    /// Output device the microphone is also monitored on
+    monitor_device: Option<DeviceId>,
+    /// Sample rate and channels of the CABLE Input stream
+    cable_format: Arc<Mutex<(u32, u16)>>,
     /// Signal to stop the routing thread
     stop_signal: Arc<AtomicBool>,
-    /// Handle to the routing thread (for cleanup)
-    _thread_handle: JoinHandle<()>,
+    /// Whether the streams run (false while recovering from a failure)
+    healthy: Arc<AtomicBool>,
+    /// Ring buffer counters and size
+    stats: Arc<RingStats>,
+    /// Handle to the routing thread (for cleanup and health checks)
+    thread_handle: JoinHandle<()>,
+}
+
+/// Routing failed or runs again; emitted as `mic-routing-lost` and
+/// `mic-routing-restored`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum RoutingEvent {
+    /// The microphone or CABLE Input failed, routing retries until restored
+    Lost {
+        microphone_id: String,
+        error: String,
+        /// Delay before the first attempt to restore it
+        retry_in_ms: u64,
+    },
+    /// Routing runs again after it was lost
+    Restored {
+        microphone_id: String,
+        /// Failed attempts before it was restored
+        attempts: u32,
+    },
+}
+
+impl RoutingEvent {
+    /// Name of the frontend event
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lost { .. } => "mic-routing-lost",
+            Self::Restored { .. } => "mic-routing-restored",
+        }
+    }
+}
+
+/// Call `listener` with every routing loss and recovery
+pub fn set_routing_listener(listener: impl Fn(RoutingEvent) + Send + 'static) {
+    if let Ok(mut current) = ROUTING_LISTENER.lock() {
+        *current = Some(Box::new(listener));
+    }
+}
+
+fn notify(event: RoutingEvent) {
+    if let Ok(listener) = ROUTING_LISTENER.lock() {
+        if let Some(listener) = listener.as_ref() {
+            listener(event);
+        }
+    }
 }
 
 // ============================================================================
@@ -66,7 +169,7 @@ pub fn list_capture_devices() -> Vec<(String, String)> {
 }
 
 /// Find a capture device by DeviceId
-fn find_capture_device(device_id: &str) -> Option<cpal::Device> {
+pub fn find_capture_device(device_id: &str) -> Option<cpal::Device> {
     let host = cpal::default_host();
 
     // Parse the index from the device_id (e.g., "device_0" -> 0)
@@ -77,6 +180,17 @@ fn find_capture_device(device_id: &str) -> Option<cpal::Device> {
     host.input_devices().ok()?.nth(index)
 }
 
+/// Find CABLE Output (the capture side of VB-Cable, carrying everything
+/// played to CABLE Input)
+pub fn find_cable_output_device() -> Option<cpal::Device> {
+    let host = cpal::default_host();
+    host.input_devices().ok()?.find(|device| {
+        device
+            .name()
+            .is_ok_and(|name| name.to_lowercase().contains("cable output"))
+    })
+}
+
 /// Find CABLE Input device (output device for routing audio to VB-Cable)
 fn find_cable_input_device() -> Option<cpal::Device> {
     let host = cpal::default_host();
@@ -99,64 +213,556 @@ fn find_cable_input_device() -> Option<cpal::Device> {
 // Microphone Routing
 // ============================================================================
 
-/// Ring buffer for transferring audio between input and output streams
+/// Gain applied to the CABLE Input destination (f32 bits, 1.0 = unchanged)
+static CABLE_GAIN: AtomicU32 = AtomicU32::new(0x3F80_0000);
+
+/// Gain applied to the mic monitoring destination (f32 bits, 1.0 = unchanged)
+static MONITOR_GAIN: AtomicU32 = AtomicU32::new(0x3F80_0000);
+
+/// Time mic ducking takes to lower the microphone when a sound starts
+static DUCK_ATTACK_MS: AtomicU32 = AtomicU32::new(20);
+
+/// Time mic ducking takes to bring the microphone back after sounds end
+static DUCK_RELEASE_MS: AtomicU32 = AtomicU32::new(400);
+
+/// Overflows, underruns and size of the routing ring buffer
+///
+/// Shared by all sessions of one routing, so counts and the grown size
+/// survive a recovery.
+#[derive(Debug)]
+struct RingStats {
+    /// Writes that overwrote samples a reader hadn't read yet
+    overflows: AtomicU64,
+    /// Reads that found fewer samples than requested
+    underruns: AtomicU64,
+    /// Buffer length (ms)
+    buffer_ms: AtomicU32,
+    /// Times the buffer grew after sustained underruns
+    resizes: AtomicU32,
+}
+
+impl Default for RingStats {
+    fn default() -> Self {
+        Self {
+            overflows: AtomicU64::new(0),
+            underruns: AtomicU64::new(0),
+            buffer_ms: AtomicU32::new(BASE_BUFFER_MS),
+            resizes: AtomicU32::new(0),
+        }
+    }
+}
+
+/// Ring buffer counters of the active routing (`get_mic_routing_stats`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MicRoutingStats {
+    pub overflows: u64,
+    pub underruns: u64,
+    /// Current buffer length (ms), grows from 100 after sustained underruns
+    pub buffer_ms: u32,
+    pub resizes: u32,
+}
+
+impl From<&RingStats> for MicRoutingStats {
+    fn from(stats: &RingStats) -> Self {
+        Self {
+            overflows: stats.overflows.load(Ordering::Relaxed),
+            underruns: stats.underruns.load(Ordering::Relaxed),
+            buffer_ms: stats.buffer_ms.load(Ordering::Relaxed),
+            resizes: stats.resizes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Lock-free ring buffer for transferring audio between the input and output
+/// streams
+///
+/// Single producer (the microphone callback), one reader per output stream,
+/// each with its own read position, so one microphone capture feeds several
+/// destinations. Samples are stored as f32 bits in atomics and positions only
+/// ever grow, so no callback waits on a lock. A reader that falls more than
+/// the current capacity behind skips ahead to the oldest sample still within it.
 struct RingBuffer {
-    buffer: Vec<f32>,
-    write_pos: usize,
-    read_pos: usize,
-    capacity: usize,
-    overflow_logged: bool,
+    samples: Box<[AtomicU32]>,
+    /// Samples written so far (including the prefill)
+    write_pos: AtomicUsize,
+    /// Samples read so far, per reader
+    read_pos: Box<[AtomicUsize]>,
+    /// Silence each reader plays before its next samples (buffer growth)
+    pending_silence: Box<[AtomicUsize]>,
+    /// Samples a reader may lag behind, up to the allocated length
+    capacity: AtomicUsize,
+    overflow_logged: AtomicBool,
+    stats: Arc<RingStats>,
 }
 
 impl RingBuffer {
     /// Create a new ring buffer with prefilled silence
     ///
-    /// Prefills half the buffer with silence so the output stream
-    /// never "starves" waiting for input data. This prevents
+    /// Prefills half the buffer with silence so the output streams
+    /// never "starve" waiting for input data. This prevents
     /// audio glitches at stream startup (industry standard practice).
-    fn new(capacity: usize) -> Self {
+    /// Room for `max_capacity` samples is allocated up front, so the buffer
+    /// can grow without replacing it.
+    fn new(capacity: usize, max_capacity: usize, readers: usize, stats: Arc<RingStats>) -> Self {
+        let max_capacity = max_capacity.max(capacity);
         Self {
-            buffer: vec![0.0; capacity],
-            write_pos: capacity / 2, // Start ahead to prevent underruns
-            read_pos: 0,
-            capacity,
-            overflow_logged: false,
+            samples: (0..max_capacity).map(|_| AtomicU32::new(0)).collect(),
+            write_pos: AtomicUsize::new(capacity / 2), // Start ahead to prevent underruns
+            read_pos: (0..readers).map(|_| AtomicUsize::new(0)).collect(),
+            pending_silence: (0..readers).map(|_| AtomicUsize::new(0)).collect(),
+            capacity: AtomicUsize::new(capacity),
+            overflow_logged: AtomicBool::new(false),
+            stats,
         }
     }
 
-    fn write(&mut self, samples: &[f32]) {
-        for &sample in samples {
-            self.buffer[self.write_pos] = sample;
-            self.write_pos = (self.write_pos + 1) % self.capacity;
-
-            // Detect buffer overflow (write catching up to read)
-            if self.write_pos == self.read_pos && !self.overflow_logged {
+    /// Append samples (microphone callback only)
+    fn write(&self, samples: &[f32]) {
+        let write_pos = self.write_pos.load(Ordering::Relaxed);
+        for (i, sample) in samples.iter().enumerate() {
+            self.samples[(write_pos + i) % self.samples.len()]
+                .store(sample.to_bits(), Ordering::Relaxed);
+        }
+        let write_pos = write_pos + samples.len();
+        self.write_pos.store(write_pos, Ordering::Release);
+
+        // Detect buffer overflow (write got more than the capacity ahead of
+        // a reader); that reader skips the overwritten samples
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let overflowed = self
+            .read_pos
+            .iter()
+            .any(|read_pos| write_pos - read_pos.load(Ordering::Acquire) > capacity);
+        if overflowed {
+            self.stats.overflows.fetch_add(1, Ordering::Relaxed);
+            if !self.overflow_logged.swap(true, Ordering::Relaxed) {
                 warn!("Ring buffer overflow detected - some audio data may be lost");
-                self.overflow_logged = true;
             }
         }
     }
 
-    fn read(&mut self, output: &mut [f32]) {
-        for sample in output.iter_mut() {
-            *sample = self.buffer[self.read_pos];
-            self.read_pos = (self.read_pos + 1) % self.capacity;
+    /// Read the next samples of `reader` (its output callback only), silence
+    /// where the microphone hasn't delivered them yet
+    fn read(&self, reader: usize, output: &mut [f32]) {
+        let silence = self.pending_silence[reader]
+            .load(Ordering::Relaxed)
+            .min(output.len());
+        let (silent, output) = output.split_at_mut(silence);
+        silent.fill(0.0);
+        self.pending_silence[reader].fetch_sub(silence, Ordering::Relaxed);
+
+        let write_pos = self.write_pos.load(Ordering::Acquire);
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let read_pos = self.read_pos[reader]
+            .load(Ordering::Relaxed)
+            .max(write_pos.saturating_sub(capacity));
+        let available = (write_pos - read_pos).min(output.len());
+        for (i, sample) in output[..available].iter_mut().enumerate() {
+            let bits = self.samples[(read_pos + i) % self.samples.len()].load(Ordering::Relaxed);
+            *sample = f32::from_bits(bits);
+        }
+        self.read_pos[reader].store(read_pos + available, Ordering::Release);
+
+        if available < output.len() {
+            output[available..].fill(0.0);
+            self.stats.underruns.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Let readers lag up to `capacity` samples (at most the allocated
+    /// length), inserting silence so they sit half a buffer behind again
+    ///
+    /// `capacity` must be a multiple of twice the channel count, so the
+    /// silence keeps channels in place.
+    fn grow(&self, capacity: usize) {
+        let capacity = capacity.min(self.samples.len());
+        let previous = self.capacity.swap(capacity, Ordering::Relaxed);
+        let silence = capacity.saturating_sub(previous) / 2;
+        for pending in self.pending_silence.iter() {
+            pending.fetch_add(silence, Ordering::Relaxed);
         }
     }
 }
 
-/// Enable microphone routing to CABLE Input
+/// Buffer length after sustained underruns at `buffer_ms`, `None` at the
+/// longest
+fn grown_buffer_ms(buffer_ms: u32) -> Option<u32> {
+    (buffer_ms < MAX_BUFFER_MS).then(|| (buffer_ms * 3 / 2).min(MAX_BUFFER_MS))
+}
+
+/// Set the gains of both routing destinations (applies immediately)
+pub fn set_routing_gains(cable_gain: f32, monitor_gain: f32) {
+    CABLE_GAIN.store(cable_gain.to_bits(), Ordering::Relaxed);
+    MONITOR_GAIN.store(monitor_gain.to_bits(), Ordering::Relaxed);
+    debug!(
+        "Microphone routing gains: cable {:.2}, monitor {:.2}",
+        cable_gain, monitor_gain
+    );
+}
+
+/// ID of the next mix source
+static NEXT_MIX_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Most playbacks mixed at once; the callback never holds more, so it never
+/// allocates
+const MIX_SOURCES_CAPACITY: usize = 32;
+
+/// A playback rendered into the CABLE Input stream
+struct MixSource {
+    id: u64,
+    paused: Arc<AtomicBool>,
+    renderer: MixRenderer,
+}
+
+/// Change to the playbacks mixed into CABLE Input
+enum MixCommand {
+    Add(MixSource),
+    Remove(u64),
+}
+
+/// Single-producer single-consumer queue with a fixed number of slots
+///
+/// Like `StreamRing` for samples: the producer only fills free slots and
+/// publishes them with `write_pos`, the consumer only empties published ones
+/// and frees them with `read_pos`. Neither side locks or allocates.
+struct SlotQueue<T> {
+    slots: Box<[UnsafeCell<Option<T>>]>,
+    read_pos: AtomicUsize,
+    write_pos: AtomicUsize,
+}
+
+// SAFETY: a slot is only touched by the one producer while it is free and by
+// the one consumer while it is published; `write_pos`/`read_pos` hand it over
+// with Release/Acquire. `slot_queue` returns exactly one of each end.
+unsafe impl<T: Send> Sync for SlotQueue<T> {}
+
+/// Sending end of a `SlotQueue`
+struct SlotProducer<T>(Arc<SlotQueue<T>>);
+
+/// Receiving end of a `SlotQueue`
+struct SlotConsumer<T>(Arc<SlotQueue<T>>);
+
+fn slot_queue<T>(capacity: usize) -> (SlotProducer<T>, SlotConsumer<T>) {
+    let queue = Arc::new(SlotQueue {
+        slots: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
+        read_pos: AtomicUsize::new(0),
+        write_pos: AtomicUsize::new(0),
+    });
+    (SlotProducer(queue.clone()), SlotConsumer(queue))
+}
+
+impl<T> SlotProducer<T> {
+    /// Append `item`, handing it back if every slot is taken
+    fn push(&mut self, item: T) -> Result<(), T> {
+        let queue = &self.0;
+        let write = queue.write_pos.load(Ordering::Relaxed);
+        let read = queue.read_pos.load(Ordering::Acquire);
+        if write - read == queue.slots.len() {
+            return Err(item);
+        }
+        // SAFETY: the slot at `write` is free (the consumer is done with it,
+        // see `read_pos`) and only this producer writes free slots
+        unsafe { *queue.slots[write % queue.slots.len()].get() = Some(item) };
+        queue.write_pos.store(write + 1, Ordering::Release);
+        Ok(())
+    }
+}
+
+impl<T> SlotConsumer<T> {
+    /// Take the oldest item, if any
+    fn pop(&mut self) -> Option<T> {
+        let queue = &self.0;
+        let read = queue.read_pos.load(Ordering::Relaxed);
+        let write = queue.write_pos.load(Ordering::Acquire);
+        if read == write {
+            return None;
+        }
+        // SAFETY: the slot at `read` was published by the producer (see
+        // `write_pos`) and only this consumer empties published slots
+        let item = unsafe { (*queue.slots[read % queue.slots.len()].get()).take() };
+        queue.read_pos.store(read + 1, Ordering::Release);
+        item
+    }
+}
+
+/// Playbacks mixed into the CABLE Input stream (soundboard-to-mic mixing)
+///
+/// Owned by the CABLE Input callback, which applies queued changes without
+/// locking or allocating. Removed playbacks are sent back, so their buffers
+/// aren't freed on the audio thread.
+struct MixSources {
+    sources: Vec<MixSource>,
+    commands: SlotConsumer<MixCommand>,
+    retired: SlotProducer<MixSource>,
+}
+
+impl MixSources {
+    /// Apply queued changes, then add every unpaused playback to `data`,
+    /// rendering each into `scratch` (its length a multiple of the channel
+    /// count)
+    fn mix(&mut self, data: &mut [f32], scratch: &mut [f32]) {
+        while let Some(command) = self.commands.pop() {
+            match command {
+                // The control side admits at most MIX_SOURCES_CAPACITY
+                // playbacks, so neither this nor `retired` runs out of room
+                MixCommand::Add(source) => self.sources.push(source),
+                MixCommand::Remove(id) => {
+                    if let Some(index) = self.sources.iter().position(|s| s.id == id) {
+                        let _ = self.retired.push(self.sources.swap_remove(index));
+                    }
+                }
+            }
+        }
+
+        for source in self.sources.iter_mut() {
+            if source.paused.load(Ordering::Acquire) {
+                continue;
+            }
+            for chunk in data.chunks_mut(scratch.len()) {
+                let rendered = &mut scratch[..chunk.len()];
+                source.renderer.render(rendered);
+                for (sample, mixed) in chunk.iter_mut().zip(rendered.iter()) {
+                    *sample += mixed;
+                }
+            }
+        }
+    }
+}
+
+/// Control side of the mix queue
+struct MixControl {
+    /// Sends changes to the callback
+    commands: SlotProducer<MixCommand>,
+    /// Playbacks the callback removed, dropped on the next change
+    retired: SlotConsumer<MixSource>,
+    /// Playbacks added and not yet handed back
+    live: usize,
+}
+
+/// Both ends of the mix queue
+struct MixQueue {
+    control: Mutex<MixControl>,
+    /// The callback side while no CABLE Input stream runs; playbacks stay
+    /// mixed across routing restarts
+    parked: Mutex<Option<MixSources>>,
+}
+
+impl MixQueue {
+    fn new() -> Self {
+        // Each live playback has at most an add and a remove queued
+        let (command_tx, command_rx) = slot_queue(MIX_SOURCES_CAPACITY * 2);
+        let (retired_tx, retired_rx) = slot_queue(MIX_SOURCES_CAPACITY);
+        Self {
+            control: Mutex::new(MixControl {
+                commands: command_tx,
+                retired: retired_rx,
+                live: 0,
+            }),
+            parked: Mutex::new(Some(MixSources {
+                sources: Vec::with_capacity(MIX_SOURCES_CAPACITY),
+                commands: command_rx,
+                retired: retired_tx,
+            })),
+        }
+    }
+
+    /// Queue a change for the callback, dropping playbacks it removed
+    ///
+    /// Adding fails while `MIX_SOURCES_CAPACITY` playbacks are mixed.
+    fn send(&self, command: MixCommand) -> Result<(), String> {
+        let mut control = self
+            .control
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        while control.retired.pop().is_some() {
+            control.live -= 1;
+        }
+        let adding = matches!(command, MixCommand::Add(_));
+        if adding && control.live == MIX_SOURCES_CAPACITY {
+            return Err(format!(
+                "At most {} sounds can be mixed into the microphone at once",
+                MIX_SOURCES_CAPACITY
+            ));
+        }
+        control
+            .commands
+            .push(command)
+            .map_err(|_| "Mix queue is full".to_string())?;
+        if adding {
+            control.live += 1;
+        }
+        Ok(())
+    }
+}
+
+fn mix_queue() -> &'static MixQueue {
+    static QUEUE: OnceLock<MixQueue> = OnceLock::new();
+    QUEUE.get_or_init(MixQueue::new)
+}
+
+/// The mixed playbacks, held by a CABLE Input callback; parked again when
+/// the stream is dropped
+struct CableMix(Option<MixSources>);
+
+impl CableMix {
+    /// Take the mixed playbacks for a new CABLE Input stream
+    fn take() -> Self {
+        let sources = mix_queue()
+            .parked
+            .lock()
+            .ok()
+            .and_then(|mut parked| parked.take());
+        if sources.is_none() {
+            warn!("Mixed playbacks are held by another CABLE Input stream");
+        }
+        Self(sources)
+    }
+
+    fn mix(&mut self, data: &mut [f32], scratch: &mut [f32]) {
+        if let Some(sources) = &mut self.0 {
+            sources.mix(data, scratch);
+        }
+    }
+}
+
+impl Drop for CableMix {
+    fn drop(&mut self) {
+        if let Some(sources) = self.0.take() {
+            if let Ok(mut parked) = mix_queue().parked.lock() {
+                *parked = Some(sources);
+            }
+        }
+    }
+}
+
+/// A playback mixed into CABLE Input; dropping it takes the playback out
+pub struct MixHandle {
+    id: u64,
+    paused: Arc<AtomicBool>,
+}
+
+impl MixHandle {
+    /// Stop or resume pulling samples, like pausing a device stream
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Release);
+    }
+}
+
+impl Drop for MixHandle {
+    fn drop(&mut self) {
+        let _ = mix_queue().send(MixCommand::Remove(self.id));
+    }
+}
+
+/// Sample rate and channels playbacks are mixed in at, `None` while no
+/// microphone is routed
+pub fn mix_format() -> Option<(u32, u16)> {
+    ROUTING_STATE
+        .lock()
+        .ok()?
+        .as_ref()
+        .and_then(|routing| routing.cable_format.lock().ok().map(|format| *format))
+}
+
+/// Mix a playback (rendering at `mix_format`) into the CABLE Input stream
+///
+/// Fails while `MIX_SOURCES_CAPACITY` playbacks are already mixed.
+pub fn add_mix_source(renderer: MixRenderer) -> Result<MixHandle, String> {
+    if get_routing_status().is_none() {
+        return Err("Microphone routing is not active".to_string());
+    }
+    let id = NEXT_MIX_ID.fetch_add(1, Ordering::Relaxed);
+    let paused = Arc::new(AtomicBool::new(false));
+    mix_queue().send(MixCommand::Add(MixSource {
+        id,
+        paused: paused.clone(),
+        renderer,
+    }))?;
+    Ok(MixHandle { id, paused })
+}
+
+/// Set how fast mic ducking follows sounds starting and ending (applies
+/// immediately)
+pub fn set_duck_times(attack_ms: u32, release_ms: u32) {
+    DUCK_ATTACK_MS.store(attack_ms, Ordering::Relaxed);
+    DUCK_RELEASE_MS.store(release_ms, Ordering::Relaxed);
+}
+
+/// Moves the CABLE Input gain toward the mic duck target, with the attack
+/// time going down and the release time going up
+struct DuckEnvelope {
+    /// Target gain (f32 bits), set by `AudioManager`
+    target: Arc<AtomicU32>,
+    gain: f32,
+    sample_rate: f32,
+}
+
+impl DuckEnvelope {
+    fn new(target: Arc<AtomicU32>, sample_rate: u32) -> Self {
+        Self {
+            target,
+            gain: 1.0,
+            sample_rate: sample_rate as f32,
+        }
+    }
+
+    /// Per-frame smoothing coefficient for a time constant
+    fn coefficient(&self, ms: u32) -> f32 {
+        if ms == 0 {
+            return 0.0;
+        }
+        (-1000.0 / (ms as f32 * self.sample_rate)).exp()
+    }
+
+    /// Apply the gain to interleaved samples
+    fn apply(&mut self, data: &mut [f32], channels: usize) {
+        let target = f32::from_bits(self.target.load(Ordering::Relaxed));
+        if self.gain == target && target == 1.0 {
+            return;
+        }
+        let coefficient = if target < self.gain {
+            self.coefficient(DUCK_ATTACK_MS.load(Ordering::Relaxed))
+        } else {
+            self.coefficient(DUCK_RELEASE_MS.load(Ordering::Relaxed))
+        };
+        for frame in data.chunks_mut(channels.max(1)) {
+            self.gain = target + (self.gain - target) * coefficient;
+            for sample in frame {
+                *sample *= self.gain;
+            }
+        }
+        // Settle instead of approaching forever
+        if (self.gain - target).abs() < 1e-4 {
+            self.gain = target;
+        }
+    }
+}
+
+/// Enable microphone routing to CABLE Input, optionally also to a monitor device
 ///
 /// Captures audio from the specified microphone and routes it to CABLE Input.
 /// This allows the user's voice to be mixed with soundboard audio in VB-Cable.
-pub fn enable_routing(microphone_id: &str) -> Result<(), String> {
+/// With `monitor_device`, the same capture is also played there ("mic
+/// monitoring"); if that stream fails, routing to CABLE Input continues.
+/// CABLE Input follows `duck_gain` (see `set_duck_times`).
+///
+/// If the microphone or CABLE Input fails later (unplugged, driver reset),
+/// routing reopens them with growing delays until it runs again or is
+/// disabled, reporting both to the routing listener.
+pub fn enable_routing(
+    microphone_id: &str,
+    monitor_device: Option<&DeviceId>,
+    duck_gain: Arc<AtomicU32>,
+) -> Result<(), String> {
     // Check if routing is already active
     {
         let state = ROUTING_STATE
             .lock()
             .map_err(|e| format!("Lock error: {}", e))?;
         if let Some(existing) = state.as_ref() {
-            if existing.microphone_id == microphone_id {
+            if existing.microphone_id == microphone_id
+                && existing.monitor_device.as_ref() == monitor_device
+            {
                 info!(
                     "Microphone routing already active for device: {}",
                     microphone_id
@@ -164,213 +770,549 @@ pub fn enable_routing(microphone_id: &str) -> Result<(), String> {
                 return Ok(());
             }
             return Err(
-                "Routing already active with different microphone. Disable first.".to_string(),
+                "Routing already active with different settings. Disable first.".to_string(),
             );
         }
     }
 
-    // Find microphone device
-    let mic_device = find_capture_device(microphone_id)
-        .ok_or_else(|| format!("Microphone device not found: {}", microphone_id))?;
-    let mic_name = mic_device.name().unwrap_or_else(|_| "Unknown".to_string());
-    info!("Found microphone: {}", mic_name);
+    let devices = RoutingDevices::open(microphone_id, monitor_device, true)?;
+    info!(
+        "Microphone routing enabled: {} -> {}",
+        devices.mic_name, devices.cable_name
+    );
 
-    // Find CABLE Input device
-    let cable_device =
-        find_cable_input_device().ok_or("CABLE Input device not found. Is VB-Cable installed?")?;
-    let cable_name = cable_device
-        .name()
-        .unwrap_or_else(|_| "Unknown".to_string());
-    info!("Found CABLE Input: {}", cable_name);
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let healthy = Arc::new(AtomicBool::new(false));
+    let cable_format = Arc::new(Mutex::new(devices.cable_format()));
+    let stats = Arc::new(RingStats::default());
+    let supervisor = RoutingSupervisor {
+        microphone_id: microphone_id.to_string(),
+        monitor_device: monitor_device.cloned(),
+        duck_gain,
+        stop_signal: stop_signal.clone(),
+        healthy: healthy.clone(),
+        cable_format: cable_format.clone(),
+        stats: stats.clone(),
+    };
+    let thread_handle = thread::spawn(move || supervisor.run(devices));
 
-    // Get supported configs
-    let input_config = mic_device
-        .default_input_config()
-        .map_err(|e| format!("No input config for microphone: {}", e))?;
-    let output_config = cable_device
-        .default_output_config()
-        .map_err(|e| format!("No output config for CABLE Input: {}", e))?;
+    // Store handle
+    let mut state = ROUTING_STATE
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?;
+    *state = Some(RoutingHandle {
+        microphone_id: microphone_id.to_string(),
+        monitor_device: monitor_device.cloned(),
+        cable_format,
+        stop_signal,
+        healthy,
+        stats,
+        thread_handle,
+    });
 
+    Ok(())
+}
+
+/// Devices and formats of one routing session
+struct RoutingDevices {
+    mic: cpal::Device,
+    mic_name: String,
+    cable: cpal::Device,
+    cable_name: String,
+    /// Monitor device and its channel count
+    monitor: Option<(cpal::Device, u16)>,
+    input_config: cpal::SupportedStreamConfig,
+    output_channels: u16,
+}
+
+impl RoutingDevices {
+    /// Find the devices and their formats
+    ///
+    /// A missing monitor device is an error with `require_monitor`, else
+    /// routing goes on without monitoring.
+    fn open(
+        microphone_id: &str,
+        monitor_device: Option<&DeviceId>,
+        require_monitor: bool,
+    ) -> Result<Self, String> {
+        // Find microphone device
+        let mic = find_capture_device(microphone_id)
+            .ok_or_else(|| format!("Microphone device not found: {}", microphone_id))?;
+        let mic_name = mic.name().unwrap_or_else(|_| "Unknown".to_string());
+        info!("Found microphone: {}", mic_name);
+
+        // Find CABLE Input device
+        let cable = find_cable_input_device()
+            .ok_or("CABLE Input device not found. Is VB-Cable installed?")?;
+        let cable_name = cable.name().unwrap_or_else(|_| "Unknown".to_string());
+        info!("Found CABLE Input: {}", cable_name);
+
+        // Find the monitor device (optional destination)
+        let monitor = match monitor_device.map(open_monitor_device) {
+            Some(Ok(monitor)) => Some(monitor),
+            Some(Err(e)) if require_monitor => return Err(e),
+            Some(Err(e)) => {
+                warn!("Routing without mic monitoring: {}", e);
+                None
+            }
+            None => None,
+        };
+
+        // Get supported configs
+        let input_config = mic
+            .default_input_config()
+            .map_err(|e| format!("No input config for microphone: {}", e))?;
+        let output_config = cable
+            .default_output_config()
+            .map_err(|e| format!("No output config for CABLE Input: {}", e))?;
+
+        info!(
+            "Input config: {} Hz, {} channels, {:?}",
+            input_config.sample_rate().0,
+            input_config.channels(),
+            input_config.sample_format()
+        );
+        info!(
+            "Output config: {} Hz, {} channels, {:?}",
+            output_config.sample_rate().0,
+            output_config.channels(),
+            output_config.sample_format()
+        );
+
+        Ok(Self {
+            mic,
+            mic_name,
+            cable,
+            cable_name,
+            monitor,
+            input_config,
+            output_channels: output_config.channels(),
+        })
+    }
+
+    /// Sample rate and channels of the CABLE Input stream (it runs at the
+    /// microphone's rate)
+    fn cable_format(&self) -> (u32, u16) {
+        (self.input_config.sample_rate().0, self.output_channels)
+    }
+}
+
+fn open_monitor_device(device_id: &DeviceId) -> Result<(cpal::Device, u16), String> {
+    let device = crate::audio::find_output_device(device_id)
+        .map_err(|e| format!("Monitor device for mic monitoring not found: {}", e))?;
+    let config = device
+        .default_output_config()
+        .map_err(|e| format!("No output config for monitor device: {}", e))?;
     info!(
-        "Input config: {} Hz, {} channels, {:?}",
-        input_config.sample_rate().0,
-        input_config.channels(),
-        input_config.sample_format()
-    );
-    info!(
-        "Output config: {} Hz, {} channels, {:?}",
-        output_config.sample_rate().0,
-        output_config.channels(),
-        output_config.sample_format()
+        "Mic monitoring on: {}",
+        device.name().unwrap_or_else(|_| "Unknown".to_string())
     );
+    Ok((device, config.channels()))
+}
 
-    // Create stop signal
-    let stop_signal = Arc::new(AtomicBool::new(false));
-    let stop_signal_clone = stop_signal.clone();
-
-    // Store configuration for the thread
-    let mic_id = microphone_id.to_string();
-    let input_channels = input_config.channels();
-    let output_channels = output_config.channels();
-    let sample_rate = input_config.sample_rate();
-
-    // Calculate buffer size for ~100ms latency (balance between latency and stability)
-    // Formula: sample_rate * channels / 10 (100ms = 1/10 second)
-    // Note: 50ms was too aggressive and caused audio glitches
-    let buffer_size = (sample_rate.0 as usize * input_channels as usize / 10).max(4096);
-    debug!(
-        "Ring buffer size: {} samples (~100ms at {} Hz, {} ch)",
-        buffer_size, sample_rate.0, input_channels
-    );
+/// First error of a routing session's microphone or CABLE Input stream
+#[derive(Clone, Default)]
+struct SessionFailure(Arc<Mutex<Option<String>>>);
 
-    // Spawn routing thread
-    let thread_handle = thread::spawn(move || {
-        let ring_buffer = Arc::new(Mutex::new(RingBuffer::new(buffer_size)));
-        let ring_buffer_input = ring_buffer.clone();
-        let ring_buffer_output = ring_buffer;
+impl SessionFailure {
+    fn report(&self, message: String) {
+        if let Ok(mut failure) = self.0.lock() {
+            failure.get_or_insert(message);
+        }
+    }
 
-        let stop_signal_input = stop_signal_clone.clone();
-        let stop_signal_output = stop_signal_clone.clone();
+    fn take(&self) -> Option<String> {
+        self.0.lock().ok()?.take()
+    }
+}
 
-        // Build input stream (capture from microphone)
-        let input_stream = match mic_device.build_input_stream(
-            &input_config.into(),
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if stop_signal_input.load(Ordering::Relaxed) {
-                    return;
-                }
+/// Runs routing sessions on the routing thread until routing is disabled,
+/// starting a new one whenever a session fails
+struct RoutingSupervisor {
+    microphone_id: String,
+    monitor_device: Option<DeviceId>,
+    duck_gain: Arc<AtomicU32>,
+    stop_signal: Arc<AtomicBool>,
+    /// Set while a session's streams run
+    healthy: Arc<AtomicBool>,
+    cable_format: Arc<Mutex<(u32, u16)>>,
+    stats: Arc<RingStats>,
+}
 
-                if let Ok(mut buffer) = ring_buffer_input.lock() {
-                    buffer.write(data);
+impl RoutingSupervisor {
+    fn run(self, devices: RoutingDevices) {
+        let mut devices = Some(devices);
+        // Failed sessions since routing last ran
+        let mut failures = 0u32;
+
+        while !self.stop_signal.load(Ordering::Relaxed) {
+            let opened = match devices.take() {
+                Some(devices) => Ok(devices),
+                None => {
+                    RoutingDevices::open(&self.microphone_id, self.monitor_device.as_ref(), false)
                 }
-            },
-            move |err| {
-                error!("Input stream error: {}", err);
-            },
-            None,
-        ) {
-            Ok(stream) => stream,
-            Err(e) => {
-                error!("Failed to build input stream: {}", e);
-                return;
+            };
+            let result = opened.and_then(|devices| {
+                if let Ok(mut format) = self.cable_format.lock() {
+                    *format = devices.cable_format();
+                }
+                self.run_session(devices, || {
+                    self.healthy.store(true, Ordering::Relaxed);
+                    if failures > 0 {
+                        info!("Microphone routing restored after {} attempt(s)", failures);
+                        notify(RoutingEvent::Restored {
+                            microphone_id: self.microphone_id.clone(),
+                            attempts: failures,
+                        });
+                    }
+                    failures = 0;
+                })
+            });
+            self.healthy.store(false, Ordering::Relaxed);
+
+            let Err(e) = result else {
+                break;
+            };
+            if self.stop_signal.load(Ordering::Relaxed) {
+                break;
+            }
+            let delay = retry_delay(failures);
+            if failures == 0 {
+                warn!("Microphone routing lost: {}", e);
+                notify(RoutingEvent::Lost {
+                    microphone_id: self.microphone_id.clone(),
+                    error: e,
+                    retry_in_ms: delay.as_millis() as u64,
+                });
+            } else {
+                debug!(
+                    "Microphone routing retry {} failed ({}), next in {:?}",
+                    failures, e, delay
+                );
+            }
+            failures = failures.saturating_add(1);
+
+            let retry_at = Instant::now() + delay;
+            while Instant::now() < retry_at && !self.stop_signal.load(Ordering::Relaxed) {
+                thread::sleep(SUPERVISOR_POLL);
             }
+        }
+
+        info!("Microphone routing thread stopping");
+    }
+
+    /// Build and run the streams until routing is disabled (`Ok`) or the
+    /// microphone or CABLE Input fails (`Err`)
+    fn run_session(
+        &self,
+        devices: RoutingDevices,
+        on_started: impl FnOnce(),
+    ) -> Result<(), String> {
+        let RoutingDevices {
+            mic,
+            cable,
+            monitor,
+            input_config,
+            output_channels,
+            ..
+        } = devices;
+        let input_channels = input_config.channels();
+        let sample_rate = input_config.sample_rate();
+
+        // Samples in the ring buffer for its current length (starts at
+        // 100ms), whole frames in both halves
+        let frame_pair = input_channels as usize * 2;
+        let buffer_len = |buffer_ms: u32| {
+            let samples =
+                sample_rate.0 as usize * input_channels as usize * buffer_ms as usize / 1000;
+            samples.max(4096).div_ceil(frame_pair) * frame_pair
         };
+        let buffer_ms = self.stats.buffer_ms.load(Ordering::Relaxed);
+        let buffer_size = buffer_len(buffer_ms);
+        debug!(
+            "Ring buffer size: {} samples (~{}ms at {} Hz, {} ch)",
+            buffer_size, buffer_ms, sample_rate.0, input_channels
+        );
 
-        // Build output stream config matching input sample rate
-        let output_stream_config = cpal::StreamConfig {
-            channels: output_channels,
+        let readers = if monitor.is_some() { 2 } else { 1 };
+        let ring_buffer = Arc::new(RingBuffer::new(
+            buffer_size,
+            buffer_len(MAX_BUFFER_MS),
+            readers,
+            self.stats.clone(),
+        ));
+        let ring_buffer_input = ring_buffer.clone();
+        let failure = SessionFailure::default();
+        // Input callbacks so far, to notice a microphone that went quiet
+        // without reporting an error
+        let callbacks = Arc::new(AtomicU64::new(0));
+
+        let stop_signal_input = self.stop_signal.clone();
+        let callbacks_input = callbacks.clone();
+        let mut noise = NoiseProcessor::new(sample_rate.0, input_channels);
+        let mut noise_buffer = Vec::with_capacity(buffer_size);
+        let mut voice = VoiceProcessor::new(sample_rate.0, input_channels);
+        let mut voice_buffer = Vec::with_capacity(buffer_size);
+        let input_failure = failure.clone();
+
+        // Build input stream (capture from microphone)
+        let input_stream = mic
+            .build_input_stream(
+                &input_config.into(),
+                move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    callbacks_input.fetch_add(1, Ordering::Relaxed);
+                    if stop_signal_input.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    let data = noise.process(data, &mut noise_buffer);
+                    let data = voice.process(data, &mut voice_buffer);
+                    ring_buffer_input.write(data);
+                },
+                move |err| {
+                    error!("Input stream error: {}", err);
+                    input_failure.report(format!("Microphone stream error: {}", err));
+                },
+                None,
+            )
+            .map_err(|e| format!("Failed to build input stream: {}", e))?;
+
+        // Build output stream (play to CABLE Input, reader 0)
+        let cable_destination = RoutingDestination {
+            reader: 0,
+            gain: &CABLE_GAIN,
+            duck: Some(self.duck_gain.clone()),
+            mix: true,
+            input_channels,
+            output_channels,
             sample_rate,
+        };
+        let output_stream = cable_destination
+            .build_stream(
+                &cable,
+                ring_buffer.clone(),
+                self.stop_signal.clone(),
+                Some(failure.clone()),
+            )
+            .map_err(|e| format!("Failed to build output stream: {}", e))?;
+
+        // Build the mic monitoring stream (reader 1); failures only lose monitoring
+        let monitor_stream = monitor.and_then(|(device, channels)| {
+            let destination = RoutingDestination {
+                reader: 1,
+                gain: &MONITOR_GAIN,
+                duck: None,
+                mix: false,
+                input_channels,
+                output_channels: channels,
+                sample_rate,
+            };
+            match destination.build_stream(
+                &device,
+                ring_buffer.clone(),
+                self.stop_signal.clone(),
+                None,
+            ) {
+                Ok(stream) => Some(stream),
+                Err(e) => {
+                    error!("Failed to build mic monitoring stream: {}", e);
+                    None
+                }
+            }
+        });
+
+        // Start all streams
+        input_stream
+            .play()
+            .map_err(|e| format!("Failed to start input stream: {}", e))?;
+        output_stream
+            .play()
+            .map_err(|e| format!("Failed to start output stream: {}", e))?;
+        if let Some(stream) = &monitor_stream {
+            if let Err(e) = stream.play() {
+                error!("Failed to start mic monitoring stream: {}", e);
+            }
+        }
+
+        info!(
+            "Microphone routing started: {} -> CABLE Input{}",
+            self.microphone_id,
+            if monitor_stream.is_some() {
+                " + monitor"
+            } else {
+                ""
+            }
+        );
+        on_started();
+
+        // Keep the streams alive while routing is active and they work
+        let mut last_callbacks = 0;
+        let mut last_callback_at = Instant::now();
+        let mut window_start = Instant::now();
+        let mut window_underruns = self.stats.underruns.load(Ordering::Relaxed);
+        while !self.stop_signal.load(Ordering::Relaxed) {
+            thread::sleep(SUPERVISOR_POLL);
+            if let Some(e) = failure.take() {
+                return Err(e);
+            }
+
+            // Grow the buffer when underruns keep happening; the outputs
+            // play the added silence, one short gap instead of repeated
+            // dropouts
+            let underruns = self.stats.underruns.load(Ordering::Relaxed);
+            let recent = underruns - window_underruns;
+            if recent >= SUSTAINED_UNDERRUNS {
+                let current = self.stats.buffer_ms.load(Ordering::Relaxed);
+                if let Some(grown) = grown_buffer_ms(current) {
+                    warn!(
+                        "{} ring buffer underruns in {:?}, growing it from {}ms to {}ms",
+                        recent,
+                        window_start.elapsed(),
+                        current,
+                        grown
+                    );
+                    ring_buffer.grow(buffer_len(grown));
+                    self.stats.buffer_ms.store(grown, Ordering::Relaxed);
+                    self.stats.resizes.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            if recent >= SUSTAINED_UNDERRUNS || window_start.elapsed() >= UNDERRUN_WINDOW {
+                window_start = Instant::now();
+                window_underruns = self.stats.underruns.load(Ordering::Relaxed);
+            }
+
+            let count = callbacks.load(Ordering::Relaxed);
+            if count != last_callbacks {
+                last_callbacks = count;
+                last_callback_at = Instant::now();
+            } else if last_callback_at.elapsed() > INPUT_STALL_TIMEOUT {
+                return Err("Microphone stopped delivering audio".to_string());
+            }
+        }
+        // Streams are dropped here, which stops them
+        Ok(())
+    }
+}
+
+/// Delay before the next attempt after `failures` failed ones in a row
+fn retry_delay(failures: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(1 << failures.min(16))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// One output stream fed from the routing ring buffer
+struct RoutingDestination {
+    /// Ring buffer reader index
+    reader: usize,
+    /// Live gain of this destination
+    gain: &'static AtomicU32,
+    /// Mic duck target gain this destination follows
+    duck: Option<Arc<AtomicU32>>,
+    /// Sum mixed playbacks into this destination (through a limiter)
+    mix: bool,
+    input_channels: u16,
+    output_channels: u16,
+    sample_rate: cpal::SampleRate,
+}
+
+impl RoutingDestination {
+    /// Build the output stream (runs at the microphone's sample rate)
+    fn build_stream(
+        self,
+        device: &cpal::Device,
+        ring_buffer: Arc<RingBuffer>,
+        stop_signal: Arc<AtomicBool>,
+        failure: Option<SessionFailure>,
+    ) -> Result<cpal::Stream, String> {
+        let config = cpal::StreamConfig {
+            channels: self.output_channels,
+            sample_rate: self.sample_rate,
             buffer_size: cpal::BufferSize::Default,
         };
 
-        // Build output stream (play to CABLE Input)
-        let input_ch = input_channels;
-        let output_ch = output_channels;
+        let reader = self.reader;
+        let gain = self.gain;
+        let mut duck = self
+            .duck
+            .map(|target| DuckEnvelope::new(target, self.sample_rate.0));
+        let input_ch = self.input_channels;
+        let output_ch = self.output_channels;
 
         // Pre-allocate conversion buffer to avoid heap allocation in audio callback
         // Max buffer size for typical audio callbacks (256-4096 samples per channel)
         const MAX_CALLBACK_SAMPLES: usize = 8192;
-        let conversion_buffer = Arc::new(Mutex::new(vec![0.0f32; MAX_CALLBACK_SAMPLES]));
-        let conversion_buffer_clone = conversion_buffer.clone();
-
-        let output_stream = match cable_device.build_output_stream(
-            &output_stream_config,
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                if stop_signal_output.load(Ordering::Relaxed) {
-                    // Fill with silence when stopped
-                    for sample in data.iter_mut() {
-                        *sample = 0.0;
+        let mut conv_buf = vec![0.0f32; MAX_CALLBACK_SAMPLES];
+        let mut mix_buf =
+            vec![0.0f32; MAX_CALLBACK_SAMPLES / output_ch as usize * output_ch as usize];
+        let mut mix = self.mix.then(|| {
+            (
+                CableMix::take(),
+                Limiter::new(self.sample_rate.0, output_ch as usize),
+            )
+        });
+
+        device
+            .build_output_stream(
+                &config,
+                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                    if stop_signal.load(Ordering::Relaxed) {
+                        // Fill with silence when stopped
+                        data.fill(0.0);
+                        return;
                     }
-                    return;
-                }
 
-                if let Ok(mut buffer) = ring_buffer_output.lock() {
                     // Handle channel conversion if needed
                     if input_ch == output_ch {
-                        buffer.read(data);
+                        ring_buffer.read(reader, data);
                     } else if input_ch == 1 && output_ch == 2 {
                         // Mono to stereo: duplicate each sample
-                        let mono_samples = data.len() / 2;
-                        if let Ok(mut conv_buf) = conversion_buffer_clone.try_lock() {
-                            let conv_slice =
-                                &mut conv_buf[..mono_samples.min(MAX_CALLBACK_SAMPLES)];
-                            buffer.read(conv_slice);
-                            for (i, &sample) in conv_slice.iter().enumerate() {
-                                if i * 2 + 1 < data.len() {
-                                    data[i * 2] = sample;
-                                    data[i * 2 + 1] = sample;
-                                }
-                            }
+                        let mono_samples = (data.len() / 2).min(MAX_CALLBACK_SAMPLES);
+                        let conv_slice = &mut conv_buf[..mono_samples];
+                        ring_buffer.read(reader, conv_slice);
+                        for (i, &sample) in conv_slice.iter().enumerate() {
+                            data[i * 2] = sample;
+                            data[i * 2 + 1] = sample;
                         }
                     } else if input_ch == 2 && output_ch == 1 {
                         // Stereo to mono: average channels
                         let stereo_samples = (data.len() * 2).min(MAX_CALLBACK_SAMPLES);
-                        if let Ok(mut conv_buf) = conversion_buffer_clone.try_lock() {
-                            let conv_slice = &mut conv_buf[..stereo_samples];
-                            buffer.read(conv_slice);
-                            for i in 0..data.len() {
-                                if i * 2 + 1 < stereo_samples {
-                                    data[i] = (conv_slice[i * 2] + conv_slice[i * 2 + 1]) * 0.5;
-                                }
+                        let conv_slice = &mut conv_buf[..stereo_samples];
+                        ring_buffer.read(reader, conv_slice);
+                        for i in 0..data.len() {
+                            if i * 2 + 1 < stereo_samples {
+                                data[i] = (conv_slice[i * 2] + conv_slice[i * 2 + 1]) * 0.5;
                             }
                         }
                     } else {
                         // Fallback: just read what we can
-                        buffer.read(data);
-                    }
-                } else {
-                    // Fill with silence if lock fails
-                    for sample in data.iter_mut() {
-                        *sample = 0.0;
+                        ring_buffer.read(reader, data);
                     }
-                }
-            },
-            move |err| {
-                error!("Output stream error: {}", err);
-            },
-            None,
-        ) {
-            Ok(stream) => stream,
-            Err(e) => {
-                error!("Failed to build output stream: {}", e);
-                return;
-            }
-        };
 
-        // Start both streams
-        if let Err(e) = input_stream.play() {
-            error!("Failed to start input stream: {}", e);
-            return;
-        }
-        if let Err(e) = output_stream.play() {
-            error!("Failed to start output stream: {}", e);
-            return;
-        }
-
-        info!("Microphone routing started: {} -> CABLE Input", mic_id);
-
-        // Keep thread alive while routing is active
-        while !stop_signal_clone.load(Ordering::Relaxed) {
-            thread::sleep(std::time::Duration::from_millis(100));
-        }
-
-        info!("Microphone routing thread stopping");
-        // Streams are dropped here, which stops them
-    });
-
-    info!("Microphone routing enabled: {} -> {}", mic_name, cable_name);
-
-    // Store handle
-    let mut state = ROUTING_STATE
-        .lock()
-        .map_err(|e| format!("Lock error: {}", e))?;
-    *state = Some(RoutingHandle {
-        microphone_id: microphone_id.to_string(),
-        stop_signal,
-        _thread_handle: thread_handle,
-    });
-
-    Ok(())
+                    let gain = f32::from_bits(gain.load(Ordering::Relaxed));
+                    if gain != 1.0 {
+                        for sample in data.iter_mut() {
+                            *sample *= gain;
+                        }
+                    }
+                    if let Some(duck) = &mut duck {
+                        duck.apply(data, output_ch as usize);
+                    }
+                    if let Some((sources, limiter)) = &mut mix {
+                        sources.mix(data, &mut mix_buf);
+                        limiter.process(data);
+                    }
+                },
+                move |err| {
+                    error!("Output stream error: {}", err);
+                    if let Some(failure) = &failure {
+                        failure.report(format!("CABLE Input stream error: {}", err));
+                    }
+                },
+                None,
+            )
+            .map_err(|e| e.to_string())
+    }
 }
 
 /// Disable microphone routing
@@ -406,6 +1348,29 @@ pub fn get_routing_status() -> Option<String> {
         .and_then(|state| state.as_ref().map(|s| s.microphone_id.clone()))
 }
 
+/// Ring buffer counters of the active routing
+///
+/// Returns None if routing is not enabled.
+pub fn get_routing_stats() -> Option<MicRoutingStats> {
+    ROUTING_STATE.lock().ok().and_then(|state| {
+        state
+            .as_ref()
+            .map(|routing| MicRoutingStats::from(routing.stats.as_ref()))
+    })
+}
+
+/// Check whether the active routing thread is still running and its
+/// streams work (false while it recovers from a failure)
+///
+/// Returns None if routing is not enabled.
+pub fn is_routing_alive() -> Option<bool> {
+    ROUTING_STATE.lock().ok().and_then(|state| {
+        state.as_ref().map(|routing| {
+            !routing.thread_handle.is_finished() && routing.healthy.load(Ordering::Relaxed)
+        })
+    })
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -416,68 +1381,325 @@ mod tests {
 
     #[test]
     fn test_ring_buffer_prefill() {
-        let buffer = RingBuffer::new(10);
+        let buffer = RingBuffer::new(10, 10, 1, Default::default());
 
         // Buffer should be prefilled with write_pos ahead of read_pos
-        assert_eq!(buffer.write_pos, 5); // capacity / 2
-        assert_eq!(buffer.read_pos, 0);
-        assert!(!buffer.overflow_logged);
+        assert_eq!(buffer.write_pos.load(Ordering::Relaxed), 5); // capacity / 2
+        assert_eq!(buffer.read_pos[0].load(Ordering::Relaxed), 0);
+        assert!(!buffer.overflow_logged.load(Ordering::Relaxed));
 
         // Buffer contains silence (0.0)
-        assert!(buffer.buffer.iter().all(|&x| x == 0.0));
+        assert!(buffer
+            .samples
+            .iter()
+            .all(|x| f32::from_bits(x.load(Ordering::Relaxed)) == 0.0));
     }
 
     #[test]
     fn test_ring_buffer_write_read() {
-        let mut buffer = RingBuffer::new(10);
+        let buffer = RingBuffer::new(10, 10, 1, Default::default());
 
         // Skip prefilled silence first (5 samples)
         let mut prefill = [0.0; 5];
-        buffer.read(&mut prefill);
+        buffer.read(0, &mut prefill);
         assert!(prefill.iter().all(|&x| x == 0.0));
 
         // Now write and read should be in sync
         buffer.write(&[1.0, 2.0, 3.0]);
 
         let mut output = [0.0; 3];
-        buffer.read(&mut output);
+        buffer.read(0, &mut output);
 
         assert_eq!(output, [1.0, 2.0, 3.0]);
     }
 
     #[test]
     fn test_ring_buffer_wrap() {
-        let mut buffer = RingBuffer::new(8);
+        let buffer = RingBuffer::new(8, 8, 1, Default::default());
 
         // Skip prefilled silence (4 samples)
         let mut prefill = [0.0; 4];
-        buffer.read(&mut prefill);
+        buffer.read(0, &mut prefill);
 
         // Write and read multiple times to test wrap-around
         buffer.write(&[1.0, 2.0]);
         let mut out1 = [0.0; 2];
-        buffer.read(&mut out1);
+        buffer.read(0, &mut out1);
         assert_eq!(out1, [1.0, 2.0]);
 
         buffer.write(&[3.0, 4.0, 5.0]);
         let mut out2 = [0.0; 3];
-        buffer.read(&mut out2);
+        buffer.read(0, &mut out2);
         assert_eq!(out2, [3.0, 4.0, 5.0]);
     }
 
     #[test]
     fn test_ring_buffer_overflow_detection() {
-        let mut buffer = RingBuffer::new(8);
-        assert!(!buffer.overflow_logged);
+        let buffer = RingBuffer::new(8, 8, 1, Default::default());
+        assert!(!buffer.overflow_logged.load(Ordering::Relaxed));
 
         // Write enough samples to cause overflow (more than capacity)
-        // Buffer starts with write_pos = 4, read_pos = 0
-        // Writing 5 samples: write_pos goes 4->5->6->7->0->1
-        // When write_pos becomes 0 and read_pos is still 0, overflow is detected
+        // Buffer starts with write_pos = 4, read_pos = 0 (4 unread)
+        // Writing 5 samples: write_pos goes 4->9, 9 samples unread
+        // The 5th sample overwrites the unread one in slot 0
         buffer.write(&[1.0, 2.0, 3.0, 4.0, 5.0]);
 
         // Overflow should be detected (write caught up to read)
-        assert!(buffer.overflow_logged);
+        assert!(buffer.overflow_logged.load(Ordering::Relaxed));
+        assert_eq!(buffer.stats.overflows.load(Ordering::Relaxed), 1);
+
+        // The reader continues at the oldest sample left
+        let mut output = [0.0; 8];
+        buffer.read(0, &mut output);
+        assert_eq!(output, [0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(buffer.stats.underruns.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_ring_buffer_underrun_reads_silence() {
+        let buffer = RingBuffer::new(8, 8, 1, Default::default());
+        buffer.write(&[1.0, 2.0]);
+
+        // 4 samples of prefill and 2 written, then nothing
+        let mut output = [9.0; 8];
+        buffer.read(0, &mut output);
+        assert_eq!(output, [0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(buffer.stats.underruns.load(Ordering::Relaxed), 1);
+
+        // Stale samples are never replayed
+        buffer.write(&[3.0]);
+        let mut output = [9.0; 2];
+        buffer.read(0, &mut output);
+        assert_eq!(output, [3.0, 0.0]);
+        assert_eq!(buffer.stats.underruns.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            MicRoutingStats::from(buffer.stats.as_ref()),
+            MicRoutingStats {
+                overflows: 0,
+                underruns: 2,
+                buffer_ms: BASE_BUFFER_MS,
+                resizes: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ring_buffer_grows_with_silence() {
+        let buffer = RingBuffer::new(4, 8, 1, Default::default());
+        buffer.write(&[1.0, 2.0]);
+
+        // Growing to 8 puts 2 more samples of silence ahead of the reader
+        buffer.grow(8);
+        let mut output = [9.0; 6];
+        buffer.read(0, &mut output);
+        assert_eq!(output, [0.0, 0.0, 0.0, 0.0, 1.0, 2.0]);
+        assert_eq!(buffer.stats.underruns.load(Ordering::Relaxed), 0);
+
+        // The reader may now lag 8 samples before it overflows
+        buffer.write(&[3.0; 8]);
+        assert_eq!(buffer.stats.overflows.load(Ordering::Relaxed), 0);
+        buffer.write(&[4.0]);
+        assert_eq!(buffer.stats.overflows.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_buffer_grows_in_steps_up_to_max() {
+        assert_eq!(grown_buffer_ms(BASE_BUFFER_MS), Some(150));
+        assert_eq!(grown_buffer_ms(150), Some(225));
+        assert_eq!(grown_buffer_ms(300), Some(MAX_BUFFER_MS));
+        assert_eq!(grown_buffer_ms(MAX_BUFFER_MS), None);
+    }
+
+    #[test]
+    fn test_ring_buffer_independent_readers() {
+        let buffer = RingBuffer::new(8, 8, 2, Default::default());
+
+        // Reader 0 skips the prefilled silence, reader 1 lags behind
+        let mut prefill = [0.0; 4];
+        buffer.read(0, &mut prefill);
+        buffer.write(&[1.0, 2.0]);
+
+        let mut out0 = [0.0; 2];
+        buffer.read(0, &mut out0);
+        assert_eq!(out0, [1.0, 2.0]);
+
+        let mut out1 = [0.0; 6];
+        buffer.read(1, &mut out1);
+        assert_eq!(out1, [0.0, 0.0, 0.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_duck_envelope_attack_and_release() {
+        set_duck_times(10, 100);
+        let target = Arc::new(AtomicU32::new(0.1f32.to_bits()));
+        let mut duck = DuckEnvelope::new(target.clone(), 48_000);
+
+        // One attack time (480 frames, stereo) gets 63% of the way down
+        let mut data = vec![1.0; 960];
+        duck.apply(&mut data, 2);
+        assert_eq!(data[958], data[959]);
+        assert!((data[959] - (0.1 + 0.9 * (-1f32).exp())).abs() < 1e-3);
+
+        // Settled after a while, then released ten times slower
+        duck.apply(&mut vec![1.0; 96_000], 2);
+        assert_eq!(duck.gain, 0.1);
+        target.store(1.0f32.to_bits(), Ordering::Relaxed);
+        let mut data = vec![1.0; 960];
+        duck.apply(&mut data, 2);
+        assert!((data[959] - (1.0 - 0.9 * (-0.1f32).exp())).abs() < 1e-3);
+    }
+
+    /// Mono renderer of 1000 full-scale samples
+    fn test_renderer() -> MixRenderer {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let source = crate::audio::PlaybackSource::Decoded {
+            audio_data: Arc::new(crate::audio::AudioData {
+                samples: vec![1.0; 1000],
+                sample_rate: 48_000,
+                channels: 1,
+            }),
+            converted: None,
+        };
+        MixRenderer::new(
+            source,
+            Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            crate::audio::PlaybackCursor::new(0, tx),
+            None,
+            48_000,
+            1,
+        )
+    }
+
+    fn test_mix_source(id: u64) -> MixCommand {
+        MixCommand::Add(MixSource {
+            id,
+            paused: Arc::new(AtomicBool::new(false)),
+            renderer: test_renderer(),
+        })
+    }
+
+    #[test]
+    fn test_slot_queue_is_bounded_and_wraps() {
+        let (mut tx, mut rx) = slot_queue(2);
+        assert_eq!(tx.push(1), Ok(()));
+        assert_eq!(tx.push(2), Ok(()));
+        assert_eq!(tx.push(3), Err(3));
+        assert_eq!(rx.pop(), Some(1));
+        assert_eq!(tx.push(3), Ok(()));
+        assert_eq!(rx.pop(), Some(2));
+        assert_eq!(rx.pop(), Some(3));
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_mix_queue_rejects_playbacks_over_capacity() {
+        let queue = MixQueue::new();
+        for id in 0..MIX_SOURCES_CAPACITY as u64 {
+            queue.send(test_mix_source(id)).unwrap();
+        }
+        assert!(queue.send(test_mix_source(99)).is_err());
+
+        // Removing one makes room once the callback handed it back
+        let mut sources = queue.parked.lock().unwrap().take().unwrap();
+        sources.mix(&mut [0.0; 4], &mut [0.0; 4]);
+        assert_eq!(sources.sources.len(), MIX_SOURCES_CAPACITY);
+        queue.send(MixCommand::Remove(0)).unwrap();
+        assert!(queue.send(test_mix_source(99)).is_err());
+        sources.mix(&mut [0.0; 4], &mut [0.0; 4]);
+        queue.send(test_mix_source(99)).unwrap();
+        sources.mix(&mut [0.0; 4], &mut [0.0; 4]);
+        assert_eq!(sources.sources.len(), MIX_SOURCES_CAPACITY);
+        assert_eq!(sources.sources.capacity(), MIX_SOURCES_CAPACITY);
+    }
+
+    #[test]
+    fn test_mixed_playbacks_are_summed_until_dropped() {
+        let renderer = test_renderer();
+        let paused = Arc::new(AtomicBool::new(false));
+        mix_queue()
+            .send(MixCommand::Add(MixSource {
+                id: u64::MAX,
+                paused: paused.clone(),
+                renderer,
+            }))
+            .unwrap();
+        let handle = MixHandle {
+            id: u64::MAX,
+            paused,
+        };
+        let mut mix = CableMix::take();
+
+        // Rendered in scratch-sized chunks on top of the microphone
+        let mut data = vec![0.1; 100];
+        mix.mix(&mut data, &mut [0.0; 32]);
+        assert!(data.iter().all(|&s| (s - 0.3).abs() < 1e-6));
+
+        handle.set_paused(true);
+        let mut data = vec![0.1; 100];
+        mix.mix(&mut data, &mut [0.0; 32]);
+        assert!(data.iter().all(|&s| s == 0.1));
+
+        // Removed by the callback, handed back to be dropped elsewhere
+        drop(handle);
+        let mut data = vec![0.1; 100];
+        mix.mix(&mut data, &mut [0.0; 32]);
+        assert!(mix.0.as_ref().unwrap().sources.is_empty());
+        let mut control = mix_queue().control.lock().unwrap();
+        assert!(control.retired.pop().is_some());
+        control.live -= 1;
+        drop(control);
+
+        // Parked for the next stream
+        drop(mix);
+        assert!(mix_queue().parked.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_max() {
+        assert_eq!(retry_delay(0), Duration::from_millis(500));
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(3), Duration::from_secs(4));
+        assert_eq!(retry_delay(6), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_session_failure_keeps_first_error() {
+        let failure = SessionFailure::default();
+        assert_eq!(failure.take(), None);
+        failure.clone().report("device removed".to_string());
+        failure.report("stream closed".to_string());
+        assert_eq!(failure.take().as_deref(), Some("device removed"));
+        assert_eq!(failure.take(), None);
+    }
+
+    #[test]
+    fn test_routing_event_payloads() {
+        let lost = RoutingEvent::Lost {
+            microphone_id: "wasapi:0:Mic".to_string(),
+            error: "Microphone stopped delivering audio".to_string(),
+            retry_in_ms: 500,
+        };
+        assert_eq!(lost.name(), "mic-routing-lost");
+        assert_eq!(
+            serde_json::to_value(&lost).unwrap(),
+            serde_json::json!({
+                "microphone_id": "wasapi:0:Mic",
+                "error": "Microphone stopped delivering audio",
+                "retry_in_ms": 500,
+            })
+        );
+
+        let restored = RoutingEvent::Restored {
+            microphone_id: "wasapi:0:Mic".to_string(),
+            attempts: 3,
+        };
+        assert_eq!(restored.name(), "mic-routing-restored");
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::json!({ "microphone_id": "wasapi:0:Mic", "attempts": 3 })
+        );
     }
 
     #[test]
@@ -1,19 +1,33 @@
 //! VB-Cable integration module
 //!
 //! Provides VB-Cable detection, installation, Windows default audio device management,
-//! microphone routing, and automatic communications device switching for Discord integration.
+//! microphone routing with noise reduction and a voice changer, and automatic communications
+//! device switching for Discord integration.
 
 mod communications;
 mod default_device;
 mod detection;
 mod installer;
 mod microphone;
+mod noise;
+mod voice;
 
 pub use communications::{
     activate as activate_comm_mode, deactivate as deactivate_comm_mode,
     is_active as is_comm_mode_active, recover_from_crash as recover_comm_mode,
 };
 pub use default_device::{DefaultDeviceManager, RestoreResult, SavedDefaults};
-pub use detection::{detect_vb_cable, wait_for_vb_cable, VbCableStatus};
-pub use installer::{cleanup_temp_files, install_vbcable, uninstall_vbcable};
-pub use microphone::{disable_routing, enable_routing, get_routing_status, list_capture_devices};
+pub use detection::{
+    check_for_update, detect_vb_cable, wait_for_vb_cable, VbCableStatus, VbCableUpdateInfo,
+};
+pub use installer::{
+    cleanup_stale_temp_files, cleanup_temp_files, install_vbcable, temp_dir_size, uninstall_vbcable,
+};
+pub use microphone::{
+    add_mix_source, disable_routing, enable_routing, find_cable_output_device, find_capture_device,
+    get_routing_stats, get_routing_status, is_routing_alive, list_capture_devices, mix_format,
+    set_duck_times, set_routing_gains, set_routing_listener, MicRoutingStats, MixHandle,
+    RoutingEvent,
+};
+pub use noise::{apply_noise_reduction, NoiseReduction};
+pub use voice::{apply_voice_changer, VoiceChanger};
@@ -0,0 +1,502 @@
+//! Noise suppression and noise gate for the routed microphone
+//!
+//! Both run in the microphone's input callback ahead of the voice changer,
+//! so background hiss is gone before CABLE Input and the monitor get the
+//! capture. Parameters change like the voice changer's: `apply_noise_reduction`
+//! bumps a version the callback compares against.
+//!
+//! - Suppression: short-time spectra of half-overlapping frames. Each bin's
+//!   noise floor is its quietest level of the last second or so, and bins
+//!   close to the floor are lowered by up to the suppression depth
+//! - Gate: silences the microphone while its level stays below the
+//!   threshold, held open briefly so the ends of words aren't cut
+
+use std::f32::consts::PI;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::audio::fft_in_place;
+
+/// Quietest gate threshold (dBFS)
+pub const MIN_GATE_THRESHOLD_DB: f32 = -90.0;
+
+/// Deepest noise suppression (dB)
+pub const MAX_SUPPRESSION_DB: f32 = 40.0;
+
+/// Samples per suppression frame (~11 ms at 48 kHz, also the added latency)
+const FRAME: usize = 512;
+
+/// Samples between suppression frames
+const HOP: usize = FRAME / 2;
+
+/// Frequency bins of a frame
+const BINS: usize = FRAME / 2 + 1;
+
+/// Span over which a bin's quietest level is taken as its noise floor (the
+/// floor follows louder noise after one to two spans)
+const NOISE_WINDOW_MS: f32 = 750.0;
+
+/// Bins must be this many times the noise floor (power) to pass unchanged;
+/// the tracked minimum sits below the average noise level
+const OVER_SUBTRACTION: f32 = 4.0;
+
+/// Share of a bin's previous gain kept each frame (less warbling)
+const GAIN_SMOOTHING: f32 = 0.5;
+
+/// Time for the level the gate follows to fall after a peak
+const GATE_LEVEL_MS: f32 = 10.0;
+
+/// Time for the gate to open
+const GATE_ATTACK_MS: f32 = 1.0;
+
+/// Time the gate stays open after the level falls below the threshold
+const GATE_HOLD_MS: f32 = 150.0;
+
+/// Time for the gate to close
+const GATE_RELEASE_MS: f32 = 80.0;
+
+/// Parameters last set with `apply_noise_reduction`
+static PARAMS: Mutex<NoiseReduction> = Mutex::new(NoiseReduction::DEFAULT);
+
+/// Incremented on every change of `PARAMS`
+static VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// Noise reduction settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoiseReduction {
+    /// Silence the microphone below the gate threshold
+    pub gate_enabled: bool,
+    /// Level the microphone must reach to pass the gate (dBFS, -90 to 0)
+    pub gate_threshold_db: f32,
+    /// Lower steady background noise
+    pub suppression_enabled: bool,
+    /// How far noise is lowered (dB, 0 - 40)
+    pub suppression_db: f32,
+}
+
+impl Default for NoiseReduction {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl NoiseReduction {
+    const DEFAULT: Self = Self {
+        gate_enabled: false,
+        gate_threshold_db: -50.0,
+        suppression_enabled: false,
+        suppression_db: 20.0,
+    };
+
+    /// Check that every parameter is in its range
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_GATE_THRESHOLD_DB..=0.0).contains(&self.gate_threshold_db) {
+            return Err(format!(
+                "Gate threshold must be between {} and 0 dB",
+                MIN_GATE_THRESHOLD_DB
+            ));
+        }
+        if !(0.0..=MAX_SUPPRESSION_DB).contains(&self.suppression_db) {
+            return Err(format!(
+                "Noise suppression must be between 0 and {} dB",
+                MAX_SUPPRESSION_DB
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.gate_enabled || self.suppression_enabled
+    }
+}
+
+/// Use these parameters from now on (applies to running routing at once)
+pub fn apply_noise_reduction(params: NoiseReduction) {
+    if let Ok(mut current) = PARAMS.lock() {
+        *current = params;
+        VERSION.fetch_add(1, Ordering::Release);
+        debug!("Noise reduction: {:?}", params);
+    }
+}
+
+/// Suppression state of one channel
+struct ChannelState {
+    /// Newest `FRAME` input samples
+    input: Vec<f32>,
+    /// Overlap-added output, the first `HOP` samples are complete
+    accum: Vec<f32>,
+    /// Output handed out until the next frame
+    output: Vec<f32>,
+    /// Smoothed power per bin
+    power: Vec<f32>,
+    /// Power per bin averaged over longer, for the noise floor
+    average: Vec<f32>,
+    /// Quietest average per bin in the previous noise window
+    noise: Vec<f32>,
+    /// Quietest average per bin in the current noise window
+    window_min: Vec<f32>,
+    /// Gain applied per bin in the last frame
+    gain: Vec<f32>,
+}
+
+impl ChannelState {
+    fn new() -> Self {
+        Self {
+            input: vec![0.0; FRAME],
+            accum: vec![0.0; FRAME],
+            output: vec![0.0; HOP],
+            power: vec![0.0; BINS],
+            average: vec![0.0; BINS],
+            noise: vec![f32::INFINITY; BINS],
+            window_min: vec![f32::INFINITY; BINS],
+            gain: vec![1.0; BINS],
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Runs noise suppression and the gate on interleaved capture (one per
+/// routing session)
+///
+/// All buffers are allocated up front, processing never allocates.
+pub struct NoiseProcessor {
+    params: NoiseReduction,
+    version: u64,
+    channels: usize,
+    states: Vec<ChannelState>,
+    /// Position within the current hop
+    hop_pos: usize,
+    /// Square root of a periodic Hann window, for analysis and synthesis
+    window: Vec<f32>,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    /// Frames per noise window, and frames into the current one
+    noise_window: usize,
+    noise_window_pos: usize,
+    gate_gain: f32,
+    gate_level: f32,
+    gate_hold: usize,
+    gate_hold_frames: usize,
+    gate_level_decay: f32,
+    gate_attack: f32,
+    gate_release: f32,
+}
+
+impl NoiseProcessor {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let rate = sample_rate.max(1) as f32;
+        let channels = channels.max(1) as usize;
+        let coefficient = |ms: f32| (-1000.0 / (ms * rate)).exp();
+        Self {
+            params: NoiseReduction::DEFAULT,
+            // Picks up the current parameters on the first callback
+            version: u64::MAX,
+            channels,
+            states: (0..channels).map(|_| ChannelState::new()).collect(),
+            hop_pos: 0,
+            window: (0..FRAME)
+                .map(|i| (PI * i as f32 / FRAME as f32).sin())
+                .collect(),
+            re: vec![0.0; FRAME],
+            im: vec![0.0; FRAME],
+            noise_window: ((NOISE_WINDOW_MS / 1000.0 * rate) as usize / HOP).max(1),
+            noise_window_pos: 0,
+            gate_gain: 0.0,
+            gate_level: 0.0,
+            gate_hold: 0,
+            gate_hold_frames: (GATE_HOLD_MS / 1000.0 * rate) as usize,
+            gate_level_decay: coefficient(GATE_LEVEL_MS),
+            gate_attack: coefficient(GATE_ATTACK_MS),
+            gate_release: coefficient(GATE_RELEASE_MS),
+        }
+    }
+
+    /// Copy changed parameters, if the lock is free right now
+    fn refresh(&mut self) {
+        let version = VERSION.load(Ordering::Acquire);
+        if version == self.version {
+            return;
+        }
+        let Ok(params) = PARAMS.try_lock() else {
+            return;
+        };
+        let was_suppressing = self.params.suppression_enabled;
+        self.params = *params;
+        self.version = version;
+        // Learn the noise again rather than output stale frames
+        if self.params.suppression_enabled && !was_suppressing {
+            for state in &mut self.states {
+                state.reset();
+            }
+            self.hop_pos = 0;
+            self.noise_window_pos = 0;
+        }
+    }
+
+    /// Suppress noise and gate `input`, using `scratch` for the result
+    ///
+    /// Returns `input` itself while both are off.
+    pub fn process<'a>(&mut self, input: &'a [f32], scratch: &'a mut Vec<f32>) -> &'a [f32] {
+        self.refresh();
+        if !self.params.is_active() {
+            return input;
+        }
+        scratch.clear();
+        scratch.extend_from_slice(input);
+        if self.params.suppression_enabled {
+            self.suppress(scratch);
+        }
+        if self.params.gate_enabled {
+            self.gate(scratch);
+        }
+        scratch
+    }
+
+    /// Replace each sample with the suppressed output one frame behind it
+    fn suppress(&mut self, samples: &mut [f32]) {
+        for frame in samples.chunks_mut(self.channels) {
+            for (sample, state) in frame.iter_mut().zip(&mut self.states) {
+                state.input[FRAME - HOP + self.hop_pos] = *sample;
+                *sample = state.output[self.hop_pos];
+            }
+            self.hop_pos += 1;
+            if self.hop_pos == HOP {
+                self.hop_pos = 0;
+                for channel in 0..self.channels {
+                    self.suppress_frame(channel);
+                }
+                self.noise_window_pos += 1;
+                if self.noise_window_pos == self.noise_window {
+                    self.noise_window_pos = 0;
+                    for state in &mut self.states {
+                        state.noise.copy_from_slice(&state.window_min);
+                        state.window_min.fill(f32::INFINITY);
+                    }
+                }
+            }
+        }
+    }
+
+    fn suppress_frame(&mut self, channel: usize) {
+        let floor = 10f32.powf(-self.params.suppression_db / 20.0);
+        let state = &mut self.states[channel];
+
+        for (i, (re, im)) in self.re.iter_mut().zip(&mut self.im).enumerate() {
+            *re = state.input[i] * self.window[i];
+            *im = 0.0;
+        }
+        fft_in_place(&mut self.re, &mut self.im);
+
+        for bin in 0..BINS {
+            let power = self.re[bin].powi(2) + self.im[bin].powi(2);
+            // The averages start from the first frame, not from silence
+            if state.noise[bin].is_infinite() && state.window_min[bin].is_infinite() {
+                state.power[bin] = power;
+                state.average[bin] = power;
+            }
+            state.power[bin] = 0.8 * state.power[bin] + 0.2 * power;
+            state.average[bin] = 0.9 * state.average[bin] + 0.1 * power;
+            state.window_min[bin] = state.window_min[bin].min(state.average[bin]);
+            let noise = state.noise[bin].min(state.window_min[bin]);
+
+            let clean = 1.0 - OVER_SUBTRACTION * noise / state.power[bin].max(f32::MIN_POSITIVE);
+            let target = clean.max(0.0).sqrt().max(floor);
+            let gain = GAIN_SMOOTHING * state.gain[bin] + (1.0 - GAIN_SMOOTHING) * target;
+            state.gain[bin] = gain;
+
+            self.re[bin] *= gain;
+            self.im[bin] *= gain;
+            // Mirrored bins of the real signal's spectrum
+            if bin > 0 && bin < FRAME / 2 {
+                self.re[FRAME - bin] *= gain;
+                self.im[FRAME - bin] *= gain;
+            }
+        }
+
+        // Inverse transform: conjugate, forward FFT, scale (the result is real)
+        for im in &mut self.im {
+            *im = -*im;
+        }
+        fft_in_place(&mut self.re, &mut self.im);
+
+        let scale = 1.0 / FRAME as f32;
+        for (i, accum) in state.accum.iter_mut().enumerate() {
+            *accum += self.re[i] * scale * self.window[i];
+        }
+        state.output.copy_from_slice(&state.accum[..HOP]);
+        state.accum.copy_within(HOP.., 0);
+        state.accum[FRAME - HOP..].fill(0.0);
+        state.input.copy_within(HOP.., 0);
+    }
+
+    fn gate(&mut self, samples: &mut [f32]) {
+        let threshold = 10f32.powf(self.params.gate_threshold_db / 20.0);
+        for frame in samples.chunks_mut(self.channels) {
+            let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            // Peak follower, so a waveform's zero crossings don't close it
+            self.gate_level = peak.max(self.gate_level * self.gate_level_decay);
+            if self.gate_level >= threshold {
+                self.gate_hold = self.gate_hold_frames;
+            } else {
+                self.gate_hold = self.gate_hold.saturating_sub(1);
+            }
+
+            let (target, coefficient) = if self.gate_hold > 0 {
+                (1.0, self.gate_attack)
+            } else {
+                (0.0, self.gate_release)
+            };
+            self.gate_gain = target + (self.gate_gain - target) * coefficient;
+            for sample in frame {
+                *sample *= self.gate_gain;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    const RATE: u32 = 48_000;
+
+    fn processor(params: NoiseReduction) -> NoiseProcessor {
+        let mut processor = NoiseProcessor::new(RATE, 1);
+        processor.params = params;
+        processor.version = VERSION.load(Ordering::Acquire);
+        processor
+    }
+
+    /// Deterministic white noise at the given peak level
+    fn noise(frames: usize, level: f32) -> Vec<f32> {
+        let mut seed = 0x2545_f491u32;
+        (0..frames)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                (seed as f32 / u32::MAX as f32 * 2.0 - 1.0) * level
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    fn suppression(db: f32) -> NoiseReduction {
+        NoiseReduction {
+            suppression_enabled: true,
+            suppression_db: db,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_suppression_off_depth_reconstructs_input() {
+        let mut processor = processor(suppression(0.0));
+        let input: Vec<f32> = (0..4096)
+            .map(|i| (TAU * 440.0 * i as f32 / RATE as f32).sin() * 0.5)
+            .collect();
+        let mut output = input.clone();
+        processor.suppress(&mut output);
+
+        // Delayed by one frame, otherwise unchanged
+        for (out, original) in output[FRAME..].iter().zip(&input) {
+            assert!((out - original).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_suppression_lowers_steady_noise() {
+        let mut processor = processor(suppression(20.0));
+        let mut samples = noise(RATE as usize * 3, 0.05);
+        processor.suppress(&mut samples);
+
+        // Once the floor is learned, noise drops by close to the full depth
+        let learned = RATE as usize * 2;
+        let before = rms(&noise(RATE as usize * 3, 0.05)[learned..]);
+        let after = rms(&samples[learned..]);
+        assert!(after < before * 0.2, "{} -> {}", before, after);
+    }
+
+    #[test]
+    fn test_suppression_keeps_tone_over_noise() {
+        let mut processor = processor(suppression(20.0));
+        // Noise to learn from, then a tone over it
+        let tone_start = RATE as usize * 2;
+        let mut samples: Vec<f32> = noise(RATE as usize * 3, 0.01)
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let tone = (TAU * 1000.0 * i as f32 / RATE as f32).sin() * 0.5;
+                n + if i >= tone_start { tone } else { 0.0 }
+            })
+            .collect();
+        processor.suppress(&mut samples);
+
+        let tone = rms(&samples[tone_start + RATE as usize / 10..]);
+        assert!(tone > 0.5 / 2f32.sqrt() * 0.9, "tone {}", tone);
+    }
+
+    #[test]
+    fn test_gate_closes_below_threshold_and_holds() {
+        let mut processor = processor(NoiseReduction {
+            gate_enabled: true,
+            gate_threshold_db: -30.0,
+            ..Default::default()
+        });
+        // Quiet hiss, a loud word, then hiss again
+        let mut samples = noise(RATE as usize / 2, 0.005);
+        samples.extend(noise(RATE as usize / 10, 0.5));
+        samples.extend(noise(RATE as usize / 2, 0.005));
+        processor.gate(&mut samples);
+
+        let word_start = RATE as usize / 2;
+        let word_end = word_start + RATE as usize / 10;
+        let hiss = rms(&noise(RATE as usize / 2, 0.005));
+        assert_eq!(rms(&samples[..word_start]), 0.0);
+        assert!(rms(&samples[word_start + 480..word_end]) > 0.2);
+        // Still open right after the word, closed once the hold ran out
+        assert!(rms(&samples[word_end..word_end + 480]) > hiss * 0.9);
+        assert!(rms(&samples[samples.len() - 4800..]) < hiss * 0.1);
+    }
+
+    #[test]
+    fn test_inactive_reduction_passes_input_through() {
+        let input = noise(256, 0.1);
+        let mut scratch = Vec::new();
+        let mut processor = processor(NoiseReduction::default());
+        let output = processor.process(&input, &mut scratch);
+        assert!(std::ptr::eq(output, input.as_slice()));
+    }
+
+    #[test]
+    fn test_validate_ranges() {
+        assert!(NoiseReduction::default().validate().is_ok());
+        for invalid in [
+            NoiseReduction {
+                gate_threshold_db: 3.0,
+                ..Default::default()
+            },
+            NoiseReduction {
+                gate_threshold_db: -100.0,
+                ..Default::default()
+            },
+            NoiseReduction {
+                suppression_db: 50.0,
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err());
+        }
+    }
+}
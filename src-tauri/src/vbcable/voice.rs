@@ -0,0 +1,383 @@
+//! Voice changer for the routed microphone
+//!
+//! Effects run in the microphone's input callback, before the capture is
+//! handed to CABLE Input and the monitor device, so both hear the changed
+//! voice. Parameters can change while routing runs: `apply_voice_changer`
+//! bumps a version the callback compares against, and only then does the
+//! callback try (without waiting) to copy the new parameters.
+//!
+//! - Pitch: two read taps moving through a short delay line at the pitch
+//!   ratio, crossfaded so each jumps back while it is silent
+//! - Robot: ring modulation with a low sine
+//! - Echo: a feedback delay line
+
+use std::f32::consts::{PI, TAU};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Largest pitch shift either way (one octave)
+pub const MAX_VOICE_PITCH_SEMITONES: f32 = 12.0;
+
+/// Longest echo delay
+pub const MAX_ECHO_DELAY_MS: u32 = 1000;
+
+/// Highest echo feedback (more never dies down)
+pub const MAX_ECHO_FEEDBACK: f32 = 0.9;
+
+/// Length of the pitch shifter's crossfaded window
+const PITCH_WINDOW_MS: f32 = 40.0;
+
+/// Carrier frequency of the robot voice
+const ROBOT_HZ: f32 = 50.0;
+
+/// Level of the echo relative to the voice
+const ECHO_MIX: f32 = 0.5;
+
+/// Parameters last set with `apply_voice_changer`
+static PARAMS: Mutex<VoiceChanger> = Mutex::new(VoiceChanger {
+    enabled: false,
+    pitch_semitones: 0.0,
+    robot: false,
+    echo_delay_ms: 0,
+    echo_feedback: 0.0,
+});
+
+/// Incremented on every change of `PARAMS`
+static VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// Voice changer settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceChanger {
+    /// Apply the effects below
+    pub enabled: bool,
+    /// Pitch shift in semitones (-12 to 12)
+    pub pitch_semitones: f32,
+    /// Ring-modulated robot voice
+    pub robot: bool,
+    /// Echo delay in milliseconds, 0 = no echo
+    pub echo_delay_ms: u32,
+    /// Share of the echo fed back into it (0.0 - 0.9)
+    pub echo_feedback: f32,
+}
+
+impl VoiceChanger {
+    /// Check that every parameter is in its range
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-MAX_VOICE_PITCH_SEMITONES..=MAX_VOICE_PITCH_SEMITONES).contains(&self.pitch_semitones)
+        {
+            return Err(format!(
+                "Voice pitch must be between -{0} and {0} semitones",
+                MAX_VOICE_PITCH_SEMITONES
+            ));
+        }
+        if self.echo_delay_ms > MAX_ECHO_DELAY_MS {
+            return Err(format!(
+                "Echo delay must be at most {} ms",
+                MAX_ECHO_DELAY_MS
+            ));
+        }
+        if !(0.0..=MAX_ECHO_FEEDBACK).contains(&self.echo_feedback) {
+            return Err(format!(
+                "Echo feedback must be between 0 and {}",
+                MAX_ECHO_FEEDBACK
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether any effect changes the voice
+    fn is_active(&self) -> bool {
+        self.enabled && (self.pitch_semitones != 0.0 || self.robot || self.echo_delay_ms > 0)
+    }
+}
+
+/// Use these parameters from now on (applies to running routing at once)
+pub fn apply_voice_changer(params: VoiceChanger) {
+    if let Ok(mut current) = PARAMS.lock() {
+        *current = params;
+        VERSION.fetch_add(1, Ordering::Release);
+        debug!("Voice changer: {:?}", params);
+    }
+}
+
+/// Pitch shifter of one channel
+struct PitchShifter {
+    buffer: Vec<f32>,
+    write: usize,
+    /// Position of the first tap in the window (0.0 - 1.0)
+    phase: f32,
+    /// Window length in frames
+    window: f32,
+}
+
+impl PitchShifter {
+    fn new(window: usize) -> Self {
+        Self {
+            buffer: vec![0.0; (window + 2).next_power_of_two()],
+            write: 0,
+            phase: 0.0,
+            window: window as f32,
+        }
+    }
+
+    fn process(&mut self, sample: f32, ratio: f32) -> f32 {
+        let mask = self.buffer.len() - 1;
+        self.buffer[self.write] = sample;
+        // The delay shrinks while reading faster than writing (pitch up)
+        self.phase = (self.phase + (1.0 - ratio) / self.window).rem_euclid(1.0);
+
+        let mut out = 0.0;
+        for offset in [0.0, 0.5] {
+            let position = (self.phase + offset) % 1.0;
+            // sin² crossfade: both taps' gains add up to 1
+            let gain = (PI * position).sin().powi(2);
+            let read = self.write as f32 - 1.0 - position * self.window;
+            let read = read.rem_euclid(self.buffer.len() as f32);
+            let index = read as usize & mask;
+            let next = self.buffer[(index + 1) & mask];
+            let frac = read.fract();
+            out += gain * (self.buffer[index] + (next - self.buffer[index]) * frac);
+        }
+        self.write = (self.write + 1) & mask;
+        out
+    }
+
+    fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.phase = 0.0;
+    }
+}
+
+/// Effect state of one channel
+struct ChannelState {
+    pitch: PitchShifter,
+    echo: Vec<f32>,
+}
+
+/// Runs the voice changer on interleaved capture (one per routing session)
+///
+/// All buffers are allocated up front, processing never allocates.
+pub struct VoiceProcessor {
+    params: VoiceChanger,
+    version: u64,
+    sample_rate: u32,
+    channels: usize,
+    states: Vec<ChannelState>,
+    echo_pos: usize,
+    robot_phase: f32,
+}
+
+impl VoiceProcessor {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let window = (PITCH_WINDOW_MS / 1000.0 * sample_rate as f32) as usize;
+        let echo_len = (MAX_ECHO_DELAY_MS as usize * sample_rate as usize / 1000).max(1);
+        let channels = channels.max(1) as usize;
+        Self {
+            params: VoiceChanger::default(),
+            // Picks up the current parameters on the first callback
+            version: u64::MAX,
+            sample_rate,
+            channels,
+            states: (0..channels)
+                .map(|_| ChannelState {
+                    pitch: PitchShifter::new(window.max(4)),
+                    echo: vec![0.0; echo_len],
+                })
+                .collect(),
+            echo_pos: 0,
+            robot_phase: 0.0,
+        }
+    }
+
+    /// Copy changed parameters, if the lock is free right now
+    fn refresh(&mut self) {
+        let version = VERSION.load(Ordering::Acquire);
+        if version == self.version {
+            return;
+        }
+        let Ok(params) = PARAMS.try_lock() else {
+            return;
+        };
+        let was_active = self.params.is_active();
+        self.params = *params;
+        self.version = version;
+        // Don't replay what was captured before the effects were off
+        if self.params.is_active() && !was_active {
+            for state in &mut self.states {
+                state.pitch.reset();
+                state.echo.fill(0.0);
+            }
+        }
+    }
+
+    /// Apply the effects to `input`, using `scratch` for the result
+    ///
+    /// Returns `input` itself while no effect is on.
+    pub fn process<'a>(&mut self, input: &'a [f32], scratch: &'a mut Vec<f32>) -> &'a [f32] {
+        self.refresh();
+        if !self.params.is_active() {
+            return input;
+        }
+        scratch.clear();
+        scratch.extend_from_slice(input);
+        self.process_in_place(scratch);
+        scratch
+    }
+
+    fn process_in_place(&mut self, samples: &mut [f32]) {
+        let params = self.params;
+        let ratio = 2f32.powf(params.pitch_semitones / 12.0);
+        let echo_len = self.states[0].echo.len();
+        let echo_delay =
+            (params.echo_delay_ms as usize * self.sample_rate as usize / 1000).min(echo_len - 1);
+        let robot_step = TAU * ROBOT_HZ / self.sample_rate as f32;
+
+        for frame in samples.chunks_mut(self.channels) {
+            let carrier = self.robot_phase.sin();
+            let echo_read = (self.echo_pos + echo_len - echo_delay) % echo_len;
+            for (sample, state) in frame.iter_mut().zip(&mut self.states) {
+                let mut value = *sample;
+                if params.pitch_semitones != 0.0 {
+                    value = state.pitch.process(value, ratio);
+                }
+                if params.robot {
+                    value *= carrier;
+                }
+                if echo_delay > 0 {
+                    let delayed = state.echo[echo_read];
+                    state.echo[self.echo_pos] = value + delayed * params.echo_feedback;
+                    value += delayed * ECHO_MIX;
+                }
+                *sample = value;
+            }
+            self.echo_pos = (self.echo_pos + 1) % echo_len;
+            self.robot_phase = (self.robot_phase + robot_step) % TAU;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    fn processor(params: VoiceChanger) -> VoiceProcessor {
+        let mut processor = VoiceProcessor::new(RATE, 1);
+        processor.params = params;
+        processor.version = VERSION.load(Ordering::Acquire);
+        processor
+    }
+
+    fn sine(freq: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|i| (TAU * freq * i as f32 / RATE as f32).sin() * 0.5)
+            .collect()
+    }
+
+    /// Zero crossings per second
+    fn crossing_rate(samples: &[f32]) -> f32 {
+        let crossings = samples
+            .windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count();
+        crossings as f32 / (samples.len() as f32 / RATE as f32)
+    }
+
+    #[test]
+    fn test_pitch_shift_raises_frequency() {
+        let mut processor = processor(VoiceChanger {
+            enabled: true,
+            pitch_semitones: 12.0,
+            ..Default::default()
+        });
+        let mut samples = sine(220.0, RATE as usize);
+        processor.process_in_place(&mut samples);
+
+        // Past the first window the tone is an octave up
+        let ratio = crossing_rate(&samples[RATE as usize / 10..]) / 220.0 / 2.0;
+        assert!((ratio - 2.0).abs() < 0.15, "ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_echo_repeats_after_delay() {
+        let mut processor = processor(VoiceChanger {
+            enabled: true,
+            echo_delay_ms: 10,
+            echo_feedback: 0.5,
+            ..Default::default()
+        });
+        let mut samples = vec![0.0; 2000];
+        samples[0] = 1.0;
+        processor.process_in_place(&mut samples);
+
+        // 10 ms at 48 kHz, then fed back at half the level
+        assert_eq!(samples[0], 1.0);
+        assert!((samples[480] - ECHO_MIX).abs() < 1e-6);
+        assert!((samples[960] - ECHO_MIX * 0.5).abs() < 1e-6);
+        assert_eq!(samples[100], 0.0);
+    }
+
+    #[test]
+    fn test_robot_modulates_level() {
+        let mut processor = processor(VoiceChanger {
+            enabled: true,
+            robot: true,
+            ..Default::default()
+        });
+        let mut samples = vec![0.5; RATE as usize / 50];
+        processor.process_in_place(&mut samples);
+
+        // One carrier period: up, through zero, down
+        let max = samples.iter().cloned().fold(f32::MIN, f32::max);
+        let min = samples.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(max > 0.49 && min < -0.49);
+    }
+
+    #[test]
+    fn test_inactive_changer_passes_input_through() {
+        let input = sine(440.0, 256);
+        let mut scratch = Vec::new();
+        for params in [
+            VoiceChanger::default(),
+            VoiceChanger {
+                enabled: true,
+                ..Default::default()
+            },
+            VoiceChanger {
+                enabled: false,
+                robot: true,
+                ..Default::default()
+            },
+        ] {
+            let mut processor = processor(params);
+            let output = processor.process(&input, &mut scratch);
+            assert!(std::ptr::eq(output, input.as_slice()));
+        }
+    }
+
+    #[test]
+    fn test_validate_ranges() {
+        assert!(VoiceChanger::default().validate().is_ok());
+        for invalid in [
+            VoiceChanger {
+                pitch_semitones: 13.0,
+                ..Default::default()
+            },
+            VoiceChanger {
+                echo_delay_ms: 1500,
+                ..Default::default()
+            },
+            VoiceChanger {
+                echo_feedback: 1.0,
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err());
+        }
+    }
+}
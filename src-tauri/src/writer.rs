@@ -0,0 +1,254 @@
+//! Background writer for data files
+//!
+//! State changes are applied in memory synchronously and handed to a single
+//! writer thread, so command handlers never wait for the disk. The outcome of
+//! each write is reported with `save-complete` / `save-failed`. Changes queued
+//! while the writer is busy are coalesced: only the latest snapshot of a file
+//! is written.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::Emitter;
+use tracing::{debug, error};
+
+use crate::hotkeys::HotkeyMappings;
+use crate::session::SessionState;
+use crate::settings::AppSettings;
+use crate::sounds::SoundLibrary;
+use crate::stats::SoundStats;
+
+/// How long `flush` waits for pending writes
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Data file written in the background
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveTarget {
+    Hotkeys,
+    Sounds,
+    Settings,
+    Session,
+    Stats,
+}
+
+/// State to write, taken when the change was made
+#[derive(Debug, Clone)]
+pub enum Snapshot {
+    Hotkeys(HotkeyMappings),
+    Sounds(SoundLibrary),
+    Settings(AppSettings),
+    Session(SessionState),
+    Stats(SoundStats),
+}
+
+impl Snapshot {
+    pub fn target(&self) -> SaveTarget {
+        match self {
+            Snapshot::Hotkeys(_) => SaveTarget::Hotkeys,
+            Snapshot::Sounds(_) => SaveTarget::Sounds,
+            Snapshot::Settings(_) => SaveTarget::Settings,
+            Snapshot::Session(_) => SaveTarget::Session,
+            Snapshot::Stats(_) => SaveTarget::Stats,
+        }
+    }
+
+    fn save(&self, app_handle: &tauri::AppHandle) -> Result<(), String> {
+        match self {
+            Snapshot::Hotkeys(mappings) => crate::hotkeys::save(mappings, app_handle),
+            Snapshot::Sounds(library) => crate::sounds::save(library, app_handle),
+            Snapshot::Settings(settings) => crate::settings::save(settings, app_handle),
+            Snapshot::Session(session) => crate::session::save(session, app_handle),
+            Snapshot::Stats(stats) => crate::stats::save(stats, app_handle),
+        }
+    }
+}
+
+/// Payload of `save-complete`
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveComplete {
+    pub file: SaveTarget,
+}
+
+/// Payload of `save-failed`
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveFailed {
+    pub file: SaveTarget,
+    pub error: String,
+}
+
+/// Snapshots waiting to be written, at most one per file
+#[derive(Debug, Default)]
+struct Queue {
+    pending: VecDeque<Snapshot>,
+    /// A snapshot was taken by the writer and is being written
+    writing: bool,
+}
+
+impl Queue {
+    /// Queue a snapshot, replacing an older one of the same file in place
+    fn push(&mut self, snapshot: Snapshot) {
+        let target = snapshot.target();
+        match self.pending.iter_mut().find(|s| s.target() == target) {
+            Some(queued) => *queued = snapshot,
+            None => self.pending.push_back(snapshot),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.pending.is_empty() && !self.writing
+    }
+}
+
+/// Queue shared with the writer thread
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    changed: Condvar,
+}
+
+/// Handle to the writer thread (managed Tauri state)
+pub struct SaveWriter {
+    shared: Arc<Shared>,
+}
+
+impl SaveWriter {
+    /// Spawn the writer thread
+    pub fn spawn(app_handle: tauri::AppHandle) -> Self {
+        let shared = Arc::new(Shared::default());
+
+        let worker = Arc::clone(&shared);
+        std::thread::Builder::new()
+            .name("save-writer".to_string())
+            .spawn(move || worker.run(&app_handle))
+            .expect("Failed to spawn save writer thread");
+
+        Self { shared }
+    }
+
+    /// Queue a snapshot for writing
+    pub fn enqueue(&self, snapshot: Snapshot) {
+        let mut queue = self.shared.queue.lock().unwrap();
+        queue.push(snapshot);
+        self.shared.changed.notify_all();
+    }
+
+    /// Block until every queued snapshot is written (used before exit)
+    pub fn flush(&self) {
+        let queue = self.shared.queue.lock().unwrap();
+        let (queue, timeout) = self
+            .shared
+            .changed
+            .wait_timeout_while(queue, FLUSH_TIMEOUT, |queue| !queue.is_idle())
+            .unwrap();
+        if timeout.timed_out() {
+            error!(
+                "Gave up waiting for {} pending save(s)",
+                queue.pending.len()
+            );
+        }
+    }
+}
+
+impl Shared {
+    fn run(&self, app_handle: &tauri::AppHandle) {
+        loop {
+            let snapshot = {
+                let mut queue = self
+                    .changed
+                    .wait_while(self.queue.lock().unwrap(), |queue| queue.pending.is_empty())
+                    .unwrap();
+                queue.writing = true;
+                queue.pending.pop_front()
+            };
+
+            if let Some(snapshot) = snapshot {
+                write(app_handle, &snapshot);
+            }
+
+            self.queue.lock().unwrap().writing = false;
+            self.changed.notify_all();
+        }
+    }
+}
+
+/// Write one snapshot and report the result
+fn write(app_handle: &tauri::AppHandle, snapshot: &Snapshot) {
+    let file = snapshot.target();
+    let start = Instant::now();
+
+    let result = match snapshot.save(app_handle) {
+        Ok(()) => {
+            debug!("{:?} saved in {:?}", file, start.elapsed());
+            app_handle.emit("save-complete", SaveComplete { file })
+        }
+        Err(e) => {
+            error!("Failed to save {:?}: {}", file, e);
+            app_handle.emit("save-failed", SaveFailed { file, error: e })
+        }
+    };
+    if let Err(e) = result {
+        error!("Failed to emit save result event: {}", e);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::sounds::CategoryId;
+
+    fn session(category: &CategoryId) -> Snapshot {
+        Snapshot::Session(SessionState {
+            last_category_id: Some(category.clone()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_queue_coalesces_per_file() {
+        let mut queue = Queue::default();
+        assert!(queue.is_idle());
+
+        let latest = CategoryId::new();
+        queue.push(session(&CategoryId::new()));
+        queue.push(Snapshot::Hotkeys(HotkeyMappings::default()));
+        queue.push(session(&latest));
+
+        // Latest session replaces the first in place, ahead of the hotkeys
+        assert_eq!(queue.pending.len(), 2);
+        match &queue.pending[0] {
+            Snapshot::Session(session) => assert_eq!(session.last_category_id, Some(latest)),
+            other => panic!("unexpected snapshot {:?}", other.target()),
+        }
+        assert_eq!(queue.pending[1].target(), SaveTarget::Hotkeys);
+    }
+
+    #[test]
+    fn test_queue_busy_while_writing() {
+        let mut queue = Queue {
+            writing: true,
+            ..Default::default()
+        };
+        assert!(!queue.is_idle());
+        queue.writing = false;
+        queue.push(Snapshot::Hotkeys(HotkeyMappings::default()));
+        assert!(!queue.is_idle());
+    }
+
+    #[test]
+    fn test_save_event_serialization() {
+        let json = serde_json::to_string(&SaveFailed {
+            file: SaveTarget::Sounds,
+            error: "disk full".to_string(),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"file":"sounds","error":"disk full"}"#);
+    }
+}
@@ -49,6 +49,26 @@ fn test_m4a_fixture_exists() {
     );
 }
 
+#[test]
+fn test_flac_fixture_exists() {
+    let path = get_test_file_path("test_stereo.flac");
+    assert!(
+        path.exists(),
+        "Test fixture test_stereo.flac not found at {:?}",
+        path
+    );
+}
+
+#[test]
+fn test_opus_fixture_exists() {
+    let path = get_test_file_path("test_stereo.opus");
+    assert!(
+        path.exists(),
+        "Test fixture test_stereo.opus not found at {:?}",
+        path
+    );
+}
+
 // ============================================================================
 // File Size Sanity Tests
 // ============================================================================
@@ -83,6 +103,16 @@ fn test_m4a_file_size() {
     assert!(metadata.len() > 0, "M4A file is empty");
 }
 
+#[test]
+fn test_flac_file_size() {
+    let path = get_test_file_path("test_stereo.flac");
+    let metadata = std::fs::metadata(&path).unwrap();
+
+    // Should be a small file (< 30KB for 1 second audio)
+    assert!(metadata.len() < 30 * 1024, "FLAC file unexpectedly large");
+    assert!(metadata.len() > 0, "FLAC file is empty");
+}
+
 // ============================================================================
 // File Format Header Tests
 // ============================================================================
@@ -124,3 +154,27 @@ fn test_m4a_has_valid_header() {
         "M4A file has invalid header"
     );
 }
+
+#[test]
+fn test_flac_has_valid_header() {
+    let path = get_test_file_path("test_stereo.flac");
+    let bytes = std::fs::read(&path).unwrap();
+
+    // FLAC files start with "fLaC" followed by the STREAMINFO block
+    assert!(
+        bytes.len() >= 5 && &bytes[0..4] == b"fLaC" && bytes[4] & 0x7F == 0,
+        "FLAC file has invalid header"
+    );
+}
+
+#[test]
+fn test_opus_has_valid_header() {
+    let path = get_test_file_path("test_stereo.opus");
+    let bytes = std::fs::read(&path).unwrap();
+
+    // Ogg Opus: "OggS" page whose first packet is the "OpusHead" header
+    assert!(
+        bytes.len() >= 36 && &bytes[0..4] == b"OggS" && &bytes[28..36] == b"OpusHead",
+        "Opus file has invalid header"
+    );
+}
@@ -1,13 +1,15 @@
 import { useEffect, useRef, useState, memo } from "react";
-import { waveformQueue } from "../../utils/waveformQueue";
+import { cardAssetLoader } from "../../utils/cardAssets";
 
 interface MiniWaveformProps {
+  soundId: string;
   filePath: string;
   isPlaying?: boolean;
   height?: number;
 }
 
 function MiniWaveformComponent({
+  soundId,
   filePath,
   isPlaying = false,
   height = 24,
@@ -41,7 +43,7 @@ function MiniWaveformComponent({
     };
   }, []);
 
-  // Load waveform data from backend (only when visible, with delay)
+  // Load waveform data from backend (only when visible)
   useEffect(() => {
     if (!isVisible) return;
     if (loadedPathRef.current === filePath && peaks) {
@@ -50,16 +52,16 @@ function MiniWaveformComponent({
 
     loadedPathRef.current = filePath;
 
-    // Use global queue to throttle requests
-    waveformQueue
-      .add(filePath, 40)
-      .then((data) => {
-        setPeaks(data.peaks);
+    // Batched with the other visible cards into one request
+    cardAssetLoader
+      .load(soundId, filePath)
+      .then((asset) => {
+        setPeaks(asset.peaks);
       })
       .catch((err) => {
         console.error("Failed to load waveform:", err);
       });
-  }, [filePath, isVisible]);
+  }, [soundId, filePath, isVisible]);
 
   // Draw waveform on canvas
   useEffect(() => {
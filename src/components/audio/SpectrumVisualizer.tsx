@@ -0,0 +1,36 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { SpectrumFrame } from "../../types";
+
+/**
+ * Bar visualizer of the monitor output, fed by `spectrum-update` events
+ * (only sent while the spectrum analyzer is enabled in settings).
+ * Renders nothing until the first frame arrives.
+ */
+export default function SpectrumVisualizer() {
+  const [bands, setBands] = useState<number[] | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<SpectrumFrame>("spectrum-update", (event) => {
+      setBands(event.payload.bands);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!bands) return null;
+
+  return (
+    <div className="flex items-end gap-px h-8 w-24" aria-hidden="true">
+      {bands.map((level, index) => (
+        <div
+          key={index}
+          className="flex-1 bg-discord-primary rounded-sm transition-[height] duration-75"
+          style={{ height: `${Math.max(level * 100, 4)}%` }}
+        />
+      ))}
+    </div>
+  );
+}
@@ -1,6 +1,12 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Category } from "../../types";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import {
+  Category,
+  SoundpackExportSummary,
+  SoundpackImportSummary,
+} from "../../types";
+import { formatError } from "../../utils/errors";
 
 interface CategoryTabsProps {
   categories: Category[];
@@ -83,7 +89,7 @@ export default function CategoryTabs({
       setEditingCategoryName("");
     } catch (error) {
       console.error("Failed to update category:", error);
-      alert(`Failed to update category: ${error}`);
+      alert(`Failed to update category: ${formatError(error)}`);
     }
   };
 
@@ -110,7 +116,84 @@ export default function CategoryTabs({
       onContextMenuChange(null);
     } catch (error) {
       console.error("Failed to delete category:", error);
-      alert(`Failed to delete category: ${error}`);
+      alert(`Failed to delete category: ${formatError(error)}`);
+    }
+  };
+
+  const handleExportCategory = async (categoryId: string) => {
+    const category = categories.find((c) => c.id === categoryId);
+    if (!category) return;
+    onContextMenuChange(null);
+
+    const path = await save({
+      defaultPath: `${category.name}.sonicpack.zip`,
+      filters: [{ name: "Soundpack", extensions: ["zip"] }],
+    });
+    if (!path) return;
+
+    try {
+      const summary = await invoke<SoundpackExportSummary>("export_category", {
+        categoryId,
+        path,
+      });
+      if (summary.missing_files.length > 0) {
+        alert(
+          `Exported ${summary.sound_count} sounds.\n\nSkipped (file missing): ${summary.missing_files.join(", ")}`
+        );
+      }
+    } catch (error) {
+      console.error("Failed to export category:", error);
+      alert(`Failed to export category: ${formatError(error)}`);
+    }
+  };
+
+  const handleExportLibrary = async () => {
+    const path = await save({
+      defaultPath: "Sonic Deck Library.sonicpack.zip",
+      filters: [{ name: "Soundpack", extensions: ["zip"] }],
+    });
+    if (!path) return;
+
+    try {
+      const summary = await invoke<SoundpackExportSummary>(
+        "export_soundpack",
+        { path }
+      );
+      if (summary.missing_files.length > 0) {
+        alert(
+          `Exported ${summary.sound_count} sounds.\n\nSkipped (file missing): ${summary.missing_files.join(", ")}`
+        );
+      }
+    } catch (error) {
+      console.error("Failed to export library:", error);
+      alert(`Failed to export library: ${formatError(error)}`);
+    }
+  };
+
+  const handleImportSoundpack = async () => {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: "Soundpack", extensions: ["zip"] }],
+    });
+    if (!path || Array.isArray(path)) return;
+
+    try {
+      const summary = await invoke<SoundpackImportSummary>(
+        "import_soundpack",
+        { path, onConflict: "rename" }
+      );
+      await onCategoriesChange();
+      if (summary.category_ids.length > 0) {
+        onSelectCategory(summary.category_ids[0]);
+      }
+      if (summary.hotkey_conflicts.length > 0) {
+        alert(
+          `Imported ${summary.imported.length} sounds.\n\nHotkeys already in use (not assigned): ${summary.hotkey_conflicts.join(", ")}`
+        );
+      }
+    } catch (error) {
+      console.error("Failed to import soundpack:", error);
+      alert(`Failed to import soundpack: ${formatError(error)}`);
     }
   };
 
@@ -198,6 +281,13 @@ export default function CategoryTabs({
                   >
                     Edit Category
                   </button>
+                  <button
+                    onClick={() => handleExportCategory(category.id)}
+                    className="w-full px-4 py-2 text-left text-sm text-discord-text
+                             hover:bg-discord-primary hover:text-white transition-colors"
+                  >
+                    Export as Soundpack
+                  </button>
                   <button
                     onClick={() => handleDeleteCategory(category.id)}
                     className="w-full px-4 py-2 text-left text-sm text-discord-danger
@@ -258,6 +348,25 @@ export default function CategoryTabs({
           + Add Category
         </button>
       )}
+
+      <button
+        onClick={handleImportSoundpack}
+        title="Import a soundpack (.zip)"
+        className="px-4 py-2 rounded-lg font-medium whitespace-nowrap transition-colors
+                 bg-discord-dark text-discord-text-muted hover:bg-discord-darker
+                 hover:text-discord-text"
+      >
+        Import Pack
+      </button>
+      <button
+        onClick={handleExportLibrary}
+        title="Export all categories as a soundpack (.zip)"
+        className="px-4 py-2 rounded-lg font-medium whitespace-nowrap transition-colors
+                 bg-discord-dark text-discord-text-muted hover:bg-discord-darker
+                 hover:text-discord-text"
+      >
+        Export All
+      </button>
     </div>
   );
 }
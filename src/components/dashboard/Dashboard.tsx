@@ -1,17 +1,35 @@
 import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { open } from "@tauri-apps/plugin-dialog";
-import { Sound } from "../../types";
-import { ANIMATION_DURATIONS } from "../../constants";
+import {
+  AppRuleChanged,
+  AppSettings,
+  DataFileChangedEvent,
+  FileImportResult,
+  MicRoutingLost,
+  MicRoutingRestored,
+  ProfileList,
+  SaveFailedEvent,
+  SessionRecap,
+  SessionState,
+  Sound,
+  SoundPlayStats,
+  WatchedFolderChanged,
+} from "../../types";
+import { SYSTEM_DEFAULT_DEVICE_ID } from "../../constants";
+import { formatError } from "../../utils/errors";
 import CategoryTabs from "../categories/CategoryTabs";
 import DashboardHeader from "./DashboardHeader";
 import DashboardSoundGrid from "./DashboardSoundGrid";
+import SessionRecapModal from "../modals/SessionRecapModal";
 import SoundModal from "../modals/SoundModal";
 import Toast from "../common/Toast";
 import TrimEditor from "../modals/TrimEditor";
 import { useAudioPlayback } from "../../hooks/useAudioPlayback";
 import { useFileDrop } from "../../hooks/useFileDrop";
 import { useHotkeyMappings } from "../../hooks/useHotkeyMappings";
+import { usePlaybackQueue } from "../../hooks/usePlaybackQueue";
 import { useAudio } from "../../contexts/AudioContext";
 import { useSettings } from "../../contexts/SettingsContext";
 import { useSoundLibrary } from "../../contexts/SoundLibraryContext";
@@ -41,17 +59,27 @@ export default function Dashboard({
 
   // Category selection
   const [selectedCategoryId, setSelectedCategoryId] = useState<string>("");
+  // Category an application rule asked for, until the library has it
+  const [ruleCategoryId, setRuleCategoryId] = useState<string | null>(null);
   const [showFavoritesOnly, setShowFavoritesOnly] = useState<boolean>(false);
 
+  // "Recent" / "Frequently used" view across all categories
+  const [statsView, setStatsView] = useState<"recent" | "frequent" | null>(
+    null
+  );
+  const [statsSounds, setStatsSounds] = useState<Sound[]>([]);
+
   // Modal state
   const [isModalOpen, setIsModalOpen] = useState(false);
   const [editingSound, setEditingSound] = useState<Sound | null>(null);
   const [droppedFilePath, setDroppedFilePath] = useState<string | null>(null);
-  const [fileQueue, setFileQueue] = useState<string[]>([]);
 
   // Trim editor state
   const [trimEditorSound, setTrimEditorSound] = useState<Sound | null>(null);
 
+  // Recap of the last session, shown once after launch
+  const [sessionRecap, setSessionRecap] = useState<SessionRecap | null>(null);
+
   // Global context menu state - only one menu open at a time
   const [openContextMenu, setOpenContextMenu] = useState<{
     type: "sound" | "category";
@@ -68,10 +96,14 @@ export default function Dashboard({
 
   const {
     playingSoundIds,
+    decodingSoundIds,
+    ignoredSoundIds,
     activeWaveform,
     isWaveformExiting,
     playSound,
     stopAllAudio,
+    setActiveVolume,
+    toggleActivePause,
     setupAudioListeners,
   } = useAudioPlayback({
     device1,
@@ -81,25 +113,52 @@ export default function Dashboard({
     soundLibrary,
   });
 
+  const { queue, queueSound, skip, clearQueue } = usePlaybackQueue(showToast);
+
+  // In queue mode a click waits for the sound playing from the queue
+  const handlePlay = useCallback(
+    (sound: Sound) => {
+      if (settings?.queue_mode_enabled) {
+        queueSound(sound.id);
+      } else {
+        playSound(sound);
+      }
+    },
+    [settings?.queue_mode_enabled, queueSound, playSound]
+  );
+
+  const soundName = (soundId: string) =>
+    soundLibrary.sounds.find((s) => s.id === soundId)?.name ?? soundId;
+
   const handleFilesDropped = useCallback(
-    (audioFiles: string[]) => {
+    async (audioFiles: string[]) => {
       if (audioFiles.length === 1) {
         // Single file - open modal directly
         setDroppedFilePath(audioFiles[0]);
         setEditingSound(null);
         setIsModalOpen(true);
-      } else {
-        // Multiple files - start queue
-        setFileQueue(audioFiles);
-        setDroppedFilePath(audioFiles[0]);
-        setEditingSound(null);
-        setIsModalOpen(true);
+        return;
+      }
+
+      // Multiple files - add all at once, named after their title tags
+      try {
+        const results = await invoke<FileImportResult[]>("import_files", {
+          paths: audioFiles,
+          categoryId: selectedCategoryId,
+        });
+        await refreshSounds();
+        const skipped = results.filter((r) => r.error !== null).length;
+        const added = results.length - skipped;
         showToast(
-          `Adding ${audioFiles.length} sounds (1/${audioFiles.length})`
+          skipped > 0
+            ? `Added ${added} sounds, skipped ${skipped} unsupported files`
+            : `Added ${added} sounds`
         );
+      } catch (error) {
+        showToast(`Import Error: ${formatError(error)}`);
       }
     },
-    [showToast]
+    [selectedCategoryId, refreshSounds, showToast]
   );
 
   const {
@@ -129,15 +188,66 @@ export default function Dashboard({
     }
   }, [settings, hasLoadedSettings, setDevice1, setDevice2]);
 
-  // Set initial category when categories load
+  // Set initial category when categories load (restore last session's choice)
   useEffect(() => {
-    if (soundLibrary.categories.length > 0 && !selectedCategoryId) {
+    if (
+      soundLibrary.categories.length > 0 &&
+      !selectedCategoryId &&
+      !ruleCategoryId
+    ) {
       const sorted = [...soundLibrary.categories].sort(
         (a, b) => a.sort_order - b.sort_order
       );
-      setSelectedCategoryId(sorted[0]?.id || "");
+      const fallback = sorted[0]?.id || "";
+      invoke<SessionState>("load_session")
+        .then((session) => {
+          const lastId = session.last_category_id;
+          const exists = sorted.some((c) => c.id === lastId);
+          setSelectedCategoryId(exists && lastId ? lastId : fallback);
+        })
+        .catch(() => setSelectedCategoryId(fallback));
     }
-  }, [soundLibrary.categories, selectedCategoryId]);
+  }, [soundLibrary.categories, selectedCategoryId, ruleCategoryId]);
+
+  // Show the category of an application rule once its profile's library
+  // is loaded
+  useEffect(() => {
+    if (
+      ruleCategoryId &&
+      soundLibrary.categories.some((c) => c.id === ruleCategoryId)
+    ) {
+      setSelectedCategoryId(ruleCategoryId);
+      setRuleCategoryId(null);
+    }
+  }, [soundLibrary.categories, ruleCategoryId]);
+
+  // Show what happened in the last session
+  useEffect(() => {
+    invoke<SessionRecap | null>("take_session_recap")
+      .then(setSessionRecap)
+      .catch((error) =>
+        console.error("Failed to load session recap:", formatError(error))
+      );
+  }, []);
+
+  // Remember the selected category for the next session
+  const handleSelectCategory = useCallback((categoryId: string) => {
+    setSelectedCategoryId(categoryId);
+    invoke("set_last_category", { categoryId }).catch((error) =>
+      console.error("Failed to save last category:", formatError(error))
+    );
+  }, []);
+
+  // A recorded clip was added to the selected category
+  const handleRecorded = useCallback(
+    (sound: Sound) => {
+      refreshSounds().catch((e) =>
+        console.error("Failed to refresh sounds:", formatError(e))
+      );
+      showToast(`Recorded "${sound.name}"`);
+    },
+    [refreshSounds, showToast]
+  );
 
   // Setup audio and file drop listeners
   useEffect(() => {
@@ -150,6 +260,129 @@ export default function Dashboard({
     };
   }, [setupAudioListeners, setupFileDropListeners]);
 
+  // Data files edited outside the app were reloaded by the backend
+  useEffect(() => {
+    const unlisten = listen<DataFileChangedEvent>(
+      "data-file-changed",
+      (event) => {
+        const { file, reloaded, error } = event.payload;
+        const label = file === "sounds" ? "Sound library" : "Hotkeys";
+        if (!reloaded) {
+          showToast(`${label} file was edited but can't be loaded: ${error}`);
+          return;
+        }
+        const refresh = file === "sounds" ? refreshSounds : refreshHotkeys;
+        refresh().catch((e) =>
+          console.error(`Failed to refresh ${file}:`, formatError(e))
+        );
+        showToast(`${label} reloaded from disk`);
+      }
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [refreshSounds, refreshHotkeys, showToast]);
+
+  // Files appeared in or vanished from a watched folder
+  useEffect(() => {
+    const unlisten = listen<WatchedFolderChanged>(
+      "watched-folder-changed",
+      (event) => {
+        const { added, removed, failed } = event.payload;
+        refreshSounds().catch((e) =>
+          console.error("Failed to refresh sounds:", formatError(e))
+        );
+        if (removed.length > 0) {
+          refreshHotkeys().catch((e) =>
+            console.error("Failed to refresh hotkeys:", formatError(e))
+          );
+        }
+        const parts = [
+          added.length > 0 && `${added.length} added`,
+          removed.length > 0 && `${removed.length} removed`,
+          failed.length > 0 && `${failed.length} unreadable`,
+        ].filter(Boolean);
+        showToast(`Watched folder: ${parts.join(", ")}`);
+      }
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [refreshSounds, refreshHotkeys, showToast]);
+
+  // A focused application's rule started or stopped applying
+  useEffect(() => {
+    const unlisten = listen<AppRuleChanged>("app-rule-changed", (event) => {
+      const categoryId = event.payload.rule?.category_id;
+      if (categoryId) {
+        setRuleCategoryId(categoryId);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Another profile's library and hotkeys were loaded
+  useEffect(() => {
+    const unlisten = listen<ProfileList>("profile-changed", (event) => {
+      refreshSounds().catch((e) =>
+        console.error("Failed to refresh sounds:", formatError(e))
+      );
+      refreshHotkeys().catch((e) =>
+        console.error("Failed to refresh hotkeys:", formatError(e))
+      );
+      // Categories belong to the profile, pick one of the new library's
+      setSelectedCategoryId("");
+      // Use the devices remembered for the profile
+      invoke<AppSettings>("load_settings")
+        .then((loaded) => {
+          if (loaded.monitor_device_id) {
+            setDevice1(loaded.monitor_device_id);
+          }
+          if (loaded.broadcast_device_id) {
+            setDevice2(loaded.broadcast_device_id);
+          }
+        })
+        .catch((e) => console.error("Failed to load settings:", formatError(e)));
+      const { active } = event.payload;
+      const name = active === "default" ? "Default" : active;
+      showToast(`Switched to profile ${name}`);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [refreshSounds, refreshHotkeys, showToast, setDevice1, setDevice2]);
+
+  // Changes are saved in the background; tell the user if a write failed
+  useEffect(() => {
+    const unlisten = listen<SaveFailedEvent>("save-failed", (event) => {
+      const { file, error } = event.payload;
+      showToast(`Failed to save ${file}: ${error}`);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [showToast]);
+
+  // Microphone routing failed or recovered (it retries on its own)
+  useEffect(() => {
+    const unlistenLost = listen<MicRoutingLost>("mic-routing-lost", (event) => {
+      const { error } = event.payload;
+      showToast(`Microphone routing lost, reconnecting: ${error}`);
+    });
+    const unlistenRestored = listen<MicRoutingRestored>(
+      "mic-routing-restored",
+      () => {
+        showToast("Microphone routing restored");
+      }
+    );
+    return () => {
+      unlistenLost.then((fn) => fn());
+      unlistenRestored.then((fn) => fn());
+    };
+  }, [showToast]);
+
   // Check if devices are configured
   const devicesConfigured = device1 && device2;
 
@@ -167,12 +400,28 @@ export default function Dashboard({
     .filter((s) => !s.is_favorite)
     .sort((a, b) => a.name.localeCompare(b.name));
 
+  // Reload the stats view when it's opened and after plays or library edits
+  useEffect(() => {
+    if (!statsView) return;
+    const command =
+      statsView === "recent" ? "get_recently_played" : "get_most_played";
+    invoke<SoundPlayStats[]>(command, { limit: 24 })
+      .then((entries) => setStatsSounds(entries.map((e) => e.sound)))
+      .catch((error) => console.error(`Failed to load ${command}:`, error));
+  }, [statsView, playingSoundIds, soundLibrary]);
+
+  const toggleStatsView = (view: "recent" | "frequent") => {
+    setStatsView((current) => (current === view ? null : view));
+  };
+
   // Check if selected devices are still available
   // Note: Using [devices] instead of [devices.length] to trigger on any device list changes
   // React compares array references, not just length
   useEffect(() => {
     if (devices.length > 0 && device1 && device2) {
-      const device1Available = devices.some((d) => d.id === device1);
+      const device1Available =
+        device1 === SYSTEM_DEFAULT_DEVICE_ID ||
+        devices.some((d) => d.id === device1);
       const device2Available = devices.some((d) => d.id === device2);
 
       if (!device1Available) {
@@ -194,7 +443,7 @@ export default function Dashboard({
         filters: [
           {
             name: "Audio Files",
-            extensions: ["mp3", "wav", "ogg", "m4a", "flac"],
+            extensions: ["mp3", "wav", "ogg", "m4a", "flac", "opus"],
           },
         ],
       });
@@ -204,7 +453,7 @@ export default function Dashboard({
       // Handle both single file (string) and multiple files (array)
       const files = Array.isArray(selected) ? selected : [selected];
       const audioFiles = files.filter((path: string) =>
-        /\.(mp3|wav|ogg|m4a|flac)$/i.test(path)
+        /\.(mp3|wav|ogg|m4a|flac|opus)$/i.test(path)
       );
 
       if (audioFiles.length === 0) {
@@ -215,7 +464,7 @@ export default function Dashboard({
       handleFilesDropped(audioFiles);
     } catch (error) {
       console.error("File dialog error:", error);
-      showToast(`Error opening file dialog: ${error}`);
+      showToast(`Error opening file dialog: ${formatError(error)}`);
     }
   };
 
@@ -233,7 +482,19 @@ export default function Dashboard({
       await refreshSounds();
       showToast(`Deleted: ${sound.name}`);
     } catch (error) {
-      showToast(`Delete Error: ${error}`);
+      showToast(`Delete Error: ${formatError(error)}`);
+    }
+  };
+
+  const handleDuplicateSound = async (sound: Sound) => {
+    try {
+      const copy = await invoke<Sound>("duplicate_sound", {
+        soundId: sound.id,
+      });
+      await refreshSounds();
+      showToast(`Duplicated: ${copy.name}`);
+    } catch (error) {
+      showToast(`Duplicate Error: ${formatError(error)}`);
     }
   };
 
@@ -249,7 +510,7 @@ export default function Dashboard({
           : `Added to favorites: ${sound.name}`
       );
     } catch (error) {
-      showToast(`Favorite Error: ${error}`);
+      showToast(`Favorite Error: ${formatError(error)}`);
     }
   };
 
@@ -265,6 +526,11 @@ export default function Dashboard({
     [refreshSounds, showToast]
   );
 
+  const handlePreviewSave = useCallback(async () => {
+    await refreshSounds();
+    showToast("Preview saved");
+  }, [refreshSounds, showToast]);
+
   return (
     <div
       className="w-full h-full bg-discord-darkest flex flex-col"
@@ -277,9 +543,14 @@ export default function Dashboard({
         volume={volume}
         onVolumeChange={setVolume}
         onStopAll={stopAllAudio}
+        onActiveVolumeChange={setActiveVolume}
+        onToggleActivePause={toggleActivePause}
         activeWaveform={activeWaveform}
         isWaveformExiting={isWaveformExiting}
         playingSoundIds={playingSoundIds}
+        categoryId={selectedCategoryId}
+        onRecorded={handleRecorded}
+        onRecordingError={showToast}
       />
 
       {/* Device Warning */}
@@ -295,6 +566,42 @@ export default function Dashboard({
         </div>
       )}
 
+      {/* Playback Queue */}
+      {(queue.current || queue.pending.length > 0) && (
+        <div className="mx-6 mt-4 bg-discord-dark rounded-lg px-4 py-2 flex items-center gap-3 text-sm">
+          <span className="text-discord-text-muted flex-shrink-0">Queue:</span>
+          <span className="text-discord-text flex-1 min-w-0 truncate">
+            {queue.current && (
+              <span className="font-semibold">
+                {soundName(queue.current)}
+              </span>
+            )}
+            {queue.pending.length > 0 && (
+              <span className="text-discord-text-muted">
+                {queue.current ? " → " : ""}
+                {queue.pending.map(soundName).join(" → ")}
+              </span>
+            )}
+          </span>
+          <button
+            onClick={skip}
+            disabled={!queue.current}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                     text-xs transition-colors disabled:opacity-50 flex-shrink-0"
+          >
+            Skip
+          </button>
+          <button
+            onClick={clearQueue}
+            disabled={queue.pending.length === 0}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-danger rounded
+                     text-xs transition-colors disabled:opacity-50 flex-shrink-0"
+          >
+            Clear
+          </button>
+        </div>
+      )}
+
       {/* Main Content */}
       <div className="flex-1 overflow-hidden flex flex-col py-6">
         {/* Category Tabs + Favorites Button */}
@@ -303,7 +610,7 @@ export default function Dashboard({
             <CategoryTabs
               categories={soundLibrary.categories}
               selectedCategoryId={selectedCategoryId}
-              onSelectCategory={setSelectedCategoryId}
+              onSelectCategory={handleSelectCategory}
               onCategoriesChange={refreshSounds}
               openContextMenuId={
                 openContextMenu?.type === "category" ? openContextMenu.id : null
@@ -331,18 +638,50 @@ export default function Dashboard({
           >
             <span className="text-xl">⭐</span>
           </button>
+
+          {/* Play Statistics Views */}
+          <button
+            onClick={() => toggleStatsView("recent")}
+            className={`px-3 py-2 rounded-lg font-medium transition-all flex-shrink-0
+                     ${
+                       statsView === "recent"
+                         ? "bg-discord-primary text-white hover:bg-discord-primary-hover"
+                         : "bg-discord-dark text-discord-text-muted hover:bg-discord-darker hover:text-discord-text"
+                     }`}
+            title={statsView === "recent" ? "Show category" : "Recently played"}
+          >
+            <span className="text-xl">🕘</span>
+          </button>
+          <button
+            onClick={() => toggleStatsView("frequent")}
+            className={`px-3 py-2 rounded-lg font-medium transition-all flex-shrink-0
+                     ${
+                       statsView === "frequent"
+                         ? "bg-discord-primary text-white hover:bg-discord-primary-hover"
+                         : "bg-discord-dark text-discord-text-muted hover:bg-discord-darker hover:text-discord-text"
+                     }`}
+            title={
+              statsView === "frequent" ? "Show category" : "Frequently used"
+            }
+          >
+            <span className="text-xl">🔥</span>
+          </button>
         </div>
 
         {/* Sound Grid */}
         <div className="flex-1 overflow-auto px-6">
           <DashboardSoundGrid
-            favoriteSounds={favoriteSounds}
-            regularSounds={regularSounds}
-            showFavoritesOnly={showFavoritesOnly}
+            favoriteSounds={statsView ? statsSounds : favoriteSounds}
+            regularSounds={statsView ? [] : regularSounds}
+            showFavoritesOnly={statsView !== null || showFavoritesOnly}
+            emptyMessage={statsView ? "No sounds played yet" : undefined}
             playingSoundIds={playingSoundIds}
-            onPlay={playSound}
+            decodingSoundIds={decodingSoundIds}
+            ignoredSoundIds={ignoredSoundIds}
+            onPlay={handlePlay}
             onEdit={handleEditSound}
             onDelete={handleDeleteSound}
+            onDuplicate={handleDuplicateSound}
             onToggleFavorite={handleToggleFavorite}
             onTrim={handleTrimSound}
             onAddSound={handleAddSound}
@@ -363,7 +702,7 @@ export default function Dashboard({
           <div className="bg-discord-dark rounded-lg p-8 text-center">
             <div className="text-6xl mb-4">🎵</div>
             <p className="text-xl text-discord-text font-medium">
-              Drop audio file to add sound
+              Drop audio files to add sounds
             </p>
             <p className="text-sm text-discord-text-muted mt-2">
               Supports MP3, WAV, OGG, M4A, FLAC
@@ -379,38 +718,10 @@ export default function Dashboard({
           setIsModalOpen(false);
           setEditingSound(null);
           setDroppedFilePath(null);
-          setFileQueue([]);
         }}
         onSave={async () => {
           await refreshSounds();
-
-          // Check if there are more files in queue
-          if (fileQueue.length > 1) {
-            // Remove first file and continue with next
-            const remainingFiles = fileQueue.slice(1);
-            const totalFiles = fileQueue.length;
-            const currentFileNum = totalFiles - remainingFiles.length + 1;
-
-            // Close modal briefly to trigger useEffect reset
-            setIsModalOpen(false);
-
-            // Then reopen with next file after a brief delay
-            setTimeout(() => {
-              setFileQueue(remainingFiles);
-              setDroppedFilePath(remainingFiles[0]);
-              setEditingSound(null);
-              setIsModalOpen(true);
-              showToast(`Adding sounds (${currentFileNum}/${totalFiles})`);
-            }, ANIMATION_DURATIONS.MODAL_TRANSITION);
-          } else {
-            // Queue finished
-            const totalAdded = fileQueue.length;
-            setFileQueue([]);
-            setDroppedFilePath(null);
-            if (totalAdded > 0) {
-              showToast(`Successfully added ${totalAdded} sounds!`);
-            }
-          }
+          setDroppedFilePath(null);
         }}
         categories={soundLibrary.categories}
         sound={editingSound}
@@ -423,12 +734,21 @@ export default function Dashboard({
         <Toast message={toastMessage} onClose={() => setToastMessage(null)} />
       )}
 
+      {/* Last session's recap */}
+      {sessionRecap && (
+        <SessionRecapModal
+          recap={sessionRecap}
+          onClose={() => setSessionRecap(null)}
+        />
+      )}
+
       {/* Trim Editor Modal */}
       {trimEditorSound && (
         <TrimEditor
           sound={trimEditorSound}
           onClose={() => setTrimEditorSound(null)}
           onSave={handleTrimSave}
+          onPreviewSave={handlePreviewSave}
         />
       )}
     </div>
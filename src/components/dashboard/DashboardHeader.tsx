@@ -1,22 +1,35 @@
 import FullWaveform from "../audio/FullWaveform";
+import SpectrumVisualizer from "../audio/SpectrumVisualizer";
+import RecordButton from "./RecordButton";
 import { ActiveWaveform } from "../../hooks/useAudioPlayback";
+import { Sound } from "../../types";
 
 interface DashboardHeaderProps {
   volume: number;
   onVolumeChange: (volume: number) => void;
   onStopAll: () => void;
+  onActiveVolumeChange: (volume: number) => void;
+  onToggleActivePause: () => void;
   activeWaveform: ActiveWaveform | null;
   isWaveformExiting: boolean;
   playingSoundIds: Set<string>;
+  categoryId: string; // Category recorded clips are added to
+  onRecorded: (sound: Sound) => void;
+  onRecordingError: (message: string) => void;
 }
 
 export default function DashboardHeader({
   volume,
   onVolumeChange,
   onStopAll,
+  onActiveVolumeChange,
+  onToggleActivePause,
   activeWaveform,
   isWaveformExiting,
   playingSoundIds,
+  categoryId,
+  onRecorded,
+  onRecordingError,
 }: DashboardHeaderProps) {
   return (
     <div className="bg-discord-darker px-6 py-4 border-b border-discord-dark">
@@ -52,10 +65,39 @@ export default function DashboardHeader({
               </div>
             )}
           </div>
+
+          {/* Pause and live volume of the playing sound */}
+          <div className="flex-shrink-0 h-full flex items-center gap-2">
+            {activeWaveform && playingSoundIds.has(activeWaveform.soundId) && (
+              <>
+                <button
+                  onClick={onToggleActivePause}
+                  className="text-discord-text-muted hover:text-discord-text transition-colors"
+                  title={activeWaveform.paused ? "Resume" : "Pause"}
+                >
+                  {activeWaveform.paused ? "▶" : "⏸"}
+                </button>
+                <input
+                  type="range"
+                  min="0"
+                  max="1"
+                  step="0.01"
+                  value={activeWaveform.volume}
+                  onChange={(e) =>
+                    onActiveVolumeChange(parseFloat(e.target.value))
+                  }
+                  className="w-16"
+                  title={`${activeWaveform.soundName}: ${Math.round(activeWaveform.volume * 100)}%`}
+                />
+              </>
+            )}
+          </div>
         </div>
 
         {/* Right: Controls */}
         <div className="flex items-center gap-3 flex-shrink-0 h-full">
+          <SpectrumVisualizer />
+
           {/* Volume Control */}
           <div className="flex items-center gap-2">
             <input
@@ -77,6 +119,13 @@ export default function DashboardHeader({
             </span>
           </div>
 
+          {/* Record a clip into the selected category */}
+          <RecordButton
+            categoryId={categoryId}
+            onRecorded={onRecorded}
+            onError={onRecordingError}
+          />
+
           {/* Stop Button */}
           <button
             onClick={onStopAll}
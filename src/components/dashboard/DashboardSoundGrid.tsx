@@ -5,10 +5,15 @@ interface DashboardSoundGridProps {
   favoriteSounds: Sound[];
   regularSounds: Sound[];
   showFavoritesOnly: boolean;
+  /** Shown instead of the empty category hint */
+  emptyMessage?: string;
   playingSoundIds: Set<string>;
+  decodingSoundIds: Set<string>;
+  ignoredSoundIds: Set<string>;
   onPlay: (sound: Sound) => void;
   onEdit: (sound: Sound) => void;
   onDelete: (sound: Sound) => void;
+  onDuplicate: (sound: Sound) => void;
   onToggleFavorite: (sound: Sound) => void;
   onTrim: (sound: Sound) => void;
   onAddSound: () => void;
@@ -22,10 +27,14 @@ export default function DashboardSoundGrid({
   favoriteSounds,
   regularSounds,
   showFavoritesOnly,
+  emptyMessage,
   playingSoundIds,
+  decodingSoundIds,
+  ignoredSoundIds,
   onPlay,
   onEdit,
   onDelete,
+  onDuplicate,
   onToggleFavorite,
   onTrim,
   onAddSound,
@@ -42,7 +51,9 @@ export default function DashboardSoundGrid({
     return (
       <div className="h-full flex flex-col items-center justify-center text-discord-text-muted">
         <div className="text-6xl mb-4">🔇</div>
-        <p className="text-lg mb-2">No sounds in this category</p>
+        <p className="text-lg mb-2">
+          {emptyMessage ?? "No sounds in this category"}
+        </p>
         <p className="text-sm mb-4">
           Click "Add Sound" or drag & drop an audio file to get started
         </p>
@@ -72,9 +83,12 @@ export default function DashboardSoundGrid({
                 key={sound.id}
                 sound={sound}
                 isPlaying={playingSoundIds.has(sound.id)}
+                isDecoding={decodingSoundIds.has(sound.id)}
+                isIgnored={ignoredSoundIds.has(sound.id)}
                 onPlay={onPlay}
                 onEdit={onEdit}
                 onDelete={onDelete}
+                onDuplicate={onDuplicate}
                 onToggleFavorite={onToggleFavorite}
                 onTrim={onTrim}
                 showMenu={
@@ -115,9 +129,12 @@ export default function DashboardSoundGrid({
                 key={sound.id}
                 sound={sound}
                 isPlaying={playingSoundIds.has(sound.id)}
+                isDecoding={decodingSoundIds.has(sound.id)}
+                isIgnored={ignoredSoundIds.has(sound.id)}
                 onPlay={onPlay}
                 onEdit={onEdit}
                 onDelete={onDelete}
+                onDuplicate={onDuplicate}
                 onToggleFavorite={onToggleFavorite}
                 onTrim={onTrim}
                 showMenu={
@@ -157,9 +174,12 @@ export default function DashboardSoundGrid({
               key={sound.id}
               sound={sound}
               isPlaying={playingSoundIds.has(sound.id)}
+              isDecoding={decodingSoundIds.has(sound.id)}
+              isIgnored={ignoredSoundIds.has(sound.id)}
               onPlay={onPlay}
               onEdit={onEdit}
               onDelete={onDelete}
+              onDuplicate={onDuplicate}
               onToggleFavorite={onToggleFavorite}
               onTrim={onTrim}
               showMenu={
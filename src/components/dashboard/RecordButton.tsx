@@ -0,0 +1,162 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  RecordingFormat,
+  RecordingSource,
+  RecordingStatus,
+  Sound,
+} from "../../types";
+import { formatError } from "../../utils/errors";
+
+interface RecordButtonProps {
+  categoryId: string; // Category recorded clips are added to
+  onRecorded: (sound: Sound) => void;
+  onError: (message: string) => void;
+}
+
+const CABLE_MIX = "cable_mix";
+
+const formatElapsed = (ms: number) => {
+  const seconds = Math.floor(ms / 1000);
+  return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, "0")}`;
+};
+
+export default function RecordButton({
+  categoryId,
+  onRecorded,
+  onError,
+}: RecordButtonProps) {
+  const [showOptions, setShowOptions] = useState<boolean>(false);
+  const [microphones, setMicrophones] = useState<[string, string][]>([]);
+  const [sourceId, setSourceId] = useState<string>("");
+  const [format, setFormat] = useState<RecordingFormat>("wav");
+  const [status, setStatus] = useState<RecordingStatus | null>(null);
+  const [isStopping, setIsStopping] = useState<boolean>(false);
+  const isRecording = status !== null;
+
+  // Pick up a recording that is still running (e.g. after a reload)
+  useEffect(() => {
+    invoke<RecordingStatus | null>("get_recording_status")
+      .then(setStatus)
+      .catch(() => {});
+  }, []);
+
+  // Elapsed time while recording
+  useEffect(() => {
+    if (!isRecording) return;
+    const interval = setInterval(() => {
+      invoke<RecordingStatus | null>("get_recording_status")
+        .then(setStatus)
+        .catch(() => {});
+    }, 500);
+    return () => clearInterval(interval);
+  }, [isRecording]);
+
+  const handleOpenOptions = async () => {
+    setShowOptions((open) => !open);
+    try {
+      const devices = await invoke<[string, string][]>("list_microphones");
+      setMicrophones(devices);
+      if (!sourceId && devices.length > 0) {
+        setSourceId(devices[0][0]);
+      }
+    } catch (error) {
+      onError(`Failed to list microphones: ${formatError(error)}`);
+    }
+  };
+
+  const handleStart = async () => {
+    const source: RecordingSource =
+      sourceId === CABLE_MIX
+        ? { type: "cable_mix" }
+        : { type: "device", device_id: sourceId };
+    try {
+      await invoke("start_recording", { source, format });
+      setShowOptions(false);
+      setStatus(await invoke<RecordingStatus | null>("get_recording_status"));
+    } catch (error) {
+      onError(`Recording failed: ${formatError(error)}`);
+    }
+  };
+
+  const handleStop = async () => {
+    setIsStopping(true);
+    try {
+      const sound = await invoke<Sound>("stop_recording", { categoryId });
+      setStatus(null);
+      onRecorded(sound);
+    } catch (error) {
+      onError(`Failed to save recording: ${formatError(error)}`);
+    } finally {
+      setIsStopping(false);
+    }
+  };
+
+  if (status) {
+    return (
+      <button
+        onClick={handleStop}
+        disabled={isStopping || !categoryId}
+        className="px-3 py-2 bg-discord-darkest hover:bg-discord-dark rounded-lg
+                   text-sm text-red-400 transition-colors disabled:opacity-50"
+        title={
+          status.limit_reached
+            ? "Length limit reached, stop to save the clip"
+            : "Stop and add the clip to this category"
+        }
+      >
+        {isStopping ? "Saving..." : `⏹ ${formatElapsed(status.elapsed_ms)}`}
+      </button>
+    );
+  }
+
+  return (
+    <div className="relative">
+      <button
+        onClick={handleOpenOptions}
+        className="px-3 py-2 bg-discord-darkest hover:bg-discord-dark rounded-lg
+                   text-sm text-discord-text transition-colors"
+        title="Record a clip"
+      >
+        ⏺
+      </button>
+      {showOptions && (
+        <div
+          className="absolute right-0 top-full mt-2 z-20 w-64 p-3 space-y-2
+                     bg-discord-dark border border-discord-darkest rounded-lg shadow-lg"
+        >
+          <select
+            value={sourceId}
+            onChange={(e) => setSourceId(e.target.value)}
+            className="w-full bg-discord-darker border border-discord-dark rounded px-2 py-1
+                     text-sm text-discord-text focus:outline-none"
+          >
+            {microphones.map(([id, name]) => (
+              <option key={id} value={id}>
+                {name}
+              </option>
+            ))}
+            <option value={CABLE_MIX}>VB-Cable mix (what others hear)</option>
+          </select>
+          <select
+            value={format}
+            onChange={(e) => setFormat(e.target.value as RecordingFormat)}
+            className="w-full bg-discord-darker border border-discord-dark rounded px-2 py-1
+                     text-sm text-discord-text focus:outline-none"
+          >
+            <option value="wav">WAV</option>
+            <option value="ogg">Ogg Opus (smaller)</option>
+          </select>
+          <button
+            onClick={handleStart}
+            disabled={!sourceId || !categoryId}
+            className="w-full px-3 py-1 bg-discord-danger hover:bg-red-600 rounded
+                     text-sm text-white transition-colors disabled:opacity-50"
+          >
+            Start recording
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
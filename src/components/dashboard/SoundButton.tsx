@@ -1,14 +1,23 @@
-import { useEffect, useState, memo } from "react";
-import { Sound, HotkeyMapping } from "../../types";
+import { useEffect, useRef, useState, memo } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Sound, HotkeyMapping, PlaybackResult } from "../../types";
+import { formatError, IMPORT_WARNING_LABELS } from "../../utils/errors";
 import HotkeyManager from "../modals/HotkeyManager";
+import MiniWaveform from "../audio/MiniWaveform";
 import { formatHotkeyForDisplay } from "../../utils/hotkeyDisplay";
 
+/** Hover this long before a sound's preview region is auditioned */
+const PREVIEW_HOVER_DELAY_MS = 600;
+
 interface SoundButtonProps {
   sound: Sound;
   isPlaying: boolean;
+  isDecoding?: boolean; // Triggered but still decoding - show a spinner
+  isIgnored?: boolean; // Last trigger was ignored (cooldown) - flash briefly
   onPlay: (sound: Sound) => void;
   onEdit: (sound: Sound) => void;
   onDelete: (sound: Sound) => void;
+  onDuplicate: (sound: Sound) => void;
   onToggleFavorite: (sound: Sound) => void;
   onTrim: (sound: Sound) => void;
   showMenu: boolean;
@@ -20,9 +29,12 @@ interface SoundButtonProps {
 function SoundButtonComponent({
   sound,
   isPlaying,
+  isDecoding = false,
+  isIgnored = false,
   onPlay,
   onEdit,
   onDelete,
+  onDuplicate,
   onToggleFavorite,
   onTrim,
   showMenu,
@@ -31,8 +43,65 @@ function SoundButtonComponent({
   onHotkeyChanged,
 }: SoundButtonProps) {
   const [showHotkeyManager, setShowHotkeyManager] = useState(false);
+  const previewTimer = useRef<number | null>(null);
+  const previewPlaybackId = useRef<string | null>(null);
+  const isHovered = useRef(false);
+
+  // Audition the preview region (monitor only) after hovering for a moment
+  const startPreview = () => {
+    isHovered.current = true;
+    if (!sound.preview) return;
+    previewTimer.current = window.setTimeout(() => {
+      previewTimer.current = null;
+      invoke<PlaybackResult>("preview_sound", { soundId: sound.id })
+        .then((result) => {
+          // Left (or clicked) before the preview started
+          if (!isHovered.current && result.playback_id) {
+            invoke("stop_playback", { playbackId: result.playback_id }).catch(
+              () => {}
+            );
+            return;
+          }
+          previewPlaybackId.current = result.playback_id;
+        })
+        .catch((error) =>
+          console.error("Failed to preview sound:", formatError(error))
+        );
+    }, PREVIEW_HOVER_DELAY_MS);
+  };
+
+  const stopPreview = () => {
+    isHovered.current = false;
+    if (previewTimer.current !== null) {
+      window.clearTimeout(previewTimer.current);
+      previewTimer.current = null;
+    }
+    const playbackId = previewPlaybackId.current;
+    previewPlaybackId.current = null;
+    if (playbackId) {
+      // The preview may already have ended on its own
+      invoke("stop_playback", { playbackId }).catch(() => {});
+    }
+  };
+
+  // Don't leave a preview playing when the button goes away
+  useEffect(() => {
+    const timer = previewTimer;
+    const playback = previewPlaybackId;
+    const hovered = isHovered;
+    return () => {
+      hovered.current = false;
+      if (timer.current !== null) window.clearTimeout(timer.current);
+      if (playback.current) {
+        invoke("stop_playback", { playbackId: playback.current }).catch(
+          () => {}
+        );
+      }
+    };
+  }, []);
 
   const handleClick = () => {
+    stopPreview();
     // Always call onPlay - let Dashboard handle restart logic
     onPlay(sound);
   };
@@ -64,6 +133,12 @@ function SoundButtonComponent({
     onDelete(sound);
   };
 
+  const handleDuplicate = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    onMenuChange(false);
+    onDuplicate(sound);
+  };
+
   const handleToggleFavorite = (e: React.MouseEvent) => {
     e.stopPropagation();
     onMenuChange(false);
@@ -82,6 +157,9 @@ function SoundButtonComponent({
     setShowHotkeyManager(true);
   };
 
+  // Only show the playing style once the sound is audible
+  const isAudible = isPlaying && !isDecoding;
+
   // Get the hotkey assigned to this sound
   const assignedHotkey = Object.entries(hotkeyMappings.mappings).find(
     ([_, soundId]) => soundId === sound.id
@@ -92,20 +170,23 @@ function SoundButtonComponent({
       <button
         onClick={handleClick}
         onContextMenu={handleContextMenu}
+        onMouseEnter={startPreview}
+        onMouseLeave={stopPreview}
         className={`w-full h-24 rounded-lg font-medium transition-all transform
                    flex flex-col items-center justify-center gap-1 p-2
                    ${
-                     isPlaying
+                     isAudible
                        ? "bg-discord-success scale-95 shadow-lg shadow-discord-success/30"
                        : "bg-discord-dark hover:bg-discord-darker hover:scale-[1.02]"
                    }
+                   ${isIgnored ? "ring-2 ring-discord-warning" : ""}
                    text-discord-text border border-discord-darker
                    focus:outline-none focus:ring-2 focus:ring-discord-primary`}
         title={`Play: ${sound.name}\nPath: ${sound.file_path}\nRight-click for options`}
       >
         {/* Icon or default */}
         <span className="text-xl">
-          {sound.icon || (isPlaying ? "🔊" : "🔈")}
+          {sound.icon || (isAudible ? "🔊" : "🔈")}
         </span>
 
         {/* Name - truncated */}
@@ -113,6 +194,16 @@ function SoundButtonComponent({
           {sound.name}
         </span>
 
+        {/* Waveform thumbnail */}
+        <div className="w-full px-2">
+          <MiniWaveform
+            soundId={sound.id}
+            filePath={sound.file_path}
+            isPlaying={isAudible}
+            height={10}
+          />
+        </div>
+
         {/* Hotkey display */}
         {assignedHotkey && (
           <span className="text-[10px] text-discord-text-muted font-mono truncate w-full px-1 text-center">
@@ -120,6 +211,16 @@ function SoundButtonComponent({
           </span>
         )}
 
+        {/* Import warning badge */}
+        {sound.import_warning && (
+          <div
+            className="absolute bottom-1 left-1 text-discord-warning text-xs"
+            title={IMPORT_WARNING_LABELS[sound.import_warning]}
+          >
+            ⚠
+          </div>
+        )}
+
         {/* Favorite star */}
         {sound.is_favorite && (
           <div className="absolute top-1 left-1 text-yellow-400 text-lg">
@@ -127,8 +228,16 @@ function SoundButtonComponent({
           </div>
         )}
 
+        {/* Decoding spinner */}
+        {isDecoding && (
+          <span
+            className="absolute top-1 right-1 w-3 h-3 rounded-full border-2
+                       border-white/60 border-t-transparent animate-spin"
+          ></span>
+        )}
+
         {/* Playing indicator */}
-        {isPlaying && (
+        {isAudible && (
           <div className="absolute top-1 right-1 flex gap-0.5">
             <span className="w-1 h-3 bg-white rounded-full animate-pulse"></span>
             <span
@@ -164,6 +273,13 @@ function SoundButtonComponent({
           >
             Edit Sound
           </button>
+          <button
+            onClick={handleDuplicate}
+            className="w-full px-4 py-2 text-left text-sm text-discord-text
+                     hover:bg-discord-primary hover:text-white transition-colors"
+          >
+            Duplicate Sound
+          </button>
           <button
             onClick={handleTrim}
             className="w-full px-4 py-2 text-left text-sm text-discord-text
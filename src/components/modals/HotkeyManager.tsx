@@ -2,6 +2,7 @@ import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { Sound, HotkeyMapping } from "../../types";
 import { formatHotkeyForDisplay } from "../../utils/hotkeyDisplay";
+import { formatError } from "../../utils/errors";
 
 interface HotkeyManagerProps {
   sound: Sound;
@@ -25,6 +26,9 @@ export default function HotkeyManager({
   const existingHotkey = Object.entries(hotkeyMappings.mappings).find(
     ([_, soundId]) => soundId === sound.id
   )?.[0];
+  const holdToPlay =
+    !!existingHotkey &&
+    hotkeyMappings.modes?.[existingHotkey] === "hold_to_play";
 
   // Normalize modifier key names
   const normalizeKey = (key: string): string => {
@@ -193,7 +197,7 @@ export default function HotkeyManager({
 
       // Don't auto-close modal - let user close it manually
     } catch (err) {
-      setError(`Failed to assign hotkey: ${err}`);
+      setError(`Failed to assign hotkey: ${formatError(err)}`);
       setIsCapturing(false);
     }
   };
@@ -215,7 +219,23 @@ export default function HotkeyManager({
 
       // Don't auto-close modal - let user close it manually
     } catch (err) {
-      setError(`Failed to remove hotkey: ${err}`);
+      setError(`Failed to remove hotkey: ${formatError(err)}`);
+    }
+  };
+
+  // Switch the existing hotkey between press and hold-to-play
+  const toggleHoldToPlay = async () => {
+    if (!existingHotkey) return;
+
+    try {
+      await invoke("set_hotkey_mode", {
+        hotkey: existingHotkey,
+        mode: holdToPlay ? "toggle" : "hold_to_play",
+      });
+      setError("");
+      onHotkeyAssigned();
+    } catch (err) {
+      setError(`Failed to change hotkey mode: ${formatError(err)}`);
     }
   };
 
@@ -285,6 +305,16 @@ export default function HotkeyManager({
                 Remove
               </button>
             </div>
+            <label className="flex items-center gap-2 mt-3 text-sm text-discord-text cursor-pointer">
+              <input
+                type="checkbox"
+                checked={holdToPlay}
+                onChange={toggleHoldToPlay}
+                className="rounded border-discord-dark bg-discord-darker
+                         text-discord-primary focus:ring-discord-primary"
+              />
+              Hold to play (stops when the key is released)
+            </label>
           </div>
         )}
 
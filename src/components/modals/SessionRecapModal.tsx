@@ -0,0 +1,87 @@
+import { SessionRecap } from "../../types";
+
+interface SessionRecapModalProps {
+  recap: SessionRecap;
+  onClose: () => void;
+}
+
+/** Format a duration as "1h 02m", "3m 05s" or "12s" */
+function formatDuration(ms: number): string {
+  const totalSeconds = Math.round(ms / 1000);
+  const hours = Math.floor(totalSeconds / 3600);
+  const minutes = Math.floor((totalSeconds % 3600) / 60);
+  const seconds = totalSeconds % 60;
+  if (hours > 0) {
+    return `${hours}h ${minutes.toString().padStart(2, "0")}m`;
+  }
+  if (minutes > 0) {
+    return `${minutes}m ${seconds.toString().padStart(2, "0")}s`;
+  }
+  return `${seconds}s`;
+}
+
+export default function SessionRecapModal({
+  recap,
+  onClose,
+}: SessionRecapModalProps) {
+  const mostPlayed = recap.most_played;
+  const stats = [
+    { label: "Sounds played", value: recap.sounds_played.toString() },
+    { label: "Playback time", value: formatDuration(recap.playback_ms) },
+    {
+      label: "Session length",
+      value: formatDuration(recap.ended_ms - recap.started_ms),
+    },
+    { label: "Errors", value: recap.errors.toString() },
+  ];
+
+  return (
+    <div
+      className="fixed inset-0 bg-black/50 flex items-center justify-center z-50"
+      onClick={onClose}
+    >
+      <div
+        className="bg-discord-dark rounded-lg p-6 w-[420px]"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="flex items-center justify-between mb-1">
+          <h2 className="text-xl font-semibold text-discord-text">
+            Last Session
+          </h2>
+          <button
+            onClick={onClose}
+            className="text-discord-text-muted hover:text-discord-text text-xl"
+          >
+            ✕
+          </button>
+        </div>
+        <p className="text-xs text-discord-text-muted mb-4">
+          {new Date(recap.started_ms).toLocaleString()}
+        </p>
+
+        <div className="grid grid-cols-2 gap-3 mb-4">
+          {stats.map((stat) => (
+            <div key={stat.label} className="bg-discord-darker rounded p-3">
+              <p className="text-xs text-discord-text-muted">{stat.label}</p>
+              <p className="text-lg font-semibold text-discord-text">
+                {stat.value}
+              </p>
+            </div>
+          ))}
+        </div>
+
+        {mostPlayed && (
+          <p className="text-sm text-discord-text">
+            Most played:{" "}
+            <span className="font-semibold">
+              {mostPlayed.sound_name ?? "Deleted sound"}
+            </span>{" "}
+            <span className="text-discord-text-muted">
+              ({mostPlayed.plays}×)
+            </span>
+          </p>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,7 +1,10 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
-import { Sound, Category } from "../../types";
+import { Sound, Category, ImportWarning, PlayPolicy } from "../../types";
+import { formatError, IMPORT_WARNING_LABELS } from "../../utils/errors";
+import { useSoundLibrary } from "../../contexts/SoundLibraryContext";
+import { useAudio } from "../../contexts/AudioContext";
 import EmojiPicker from "../common/EmojiPicker";
 
 interface SoundModalProps {
@@ -29,11 +32,37 @@ export default function SoundModal({
   const [icon, setIcon] = useState<string | null>(null);
   const [volume, setVolume] = useState<number | null>(null);
   const [useCustomVolume, setUseCustomVolume] = useState(false);
+  const [playPolicy, setPlayPolicy] = useState<PlayPolicy>("restart");
+  const [resumePlayback, setResumePlayback] = useState(false);
+  // "YYYY-MM-DD" (local) or "" for a permanent sound
+  const [expiryDate, setExpiryDate] = useState("");
+  const [monitorTrimDb, setMonitorTrimDb] = useState(0);
+  const [broadcastTrimDb, setBroadcastTrimDb] = useState(0);
+  const [group, setGroup] = useState("");
+  // Comma-separated, as typed
+  const [tags, setTags] = useState("");
+  // Seconds as typed, "" = play at normal speed
+  const [fitDuration, setFitDuration] = useState("");
+  const [pitchSemitones, setPitchSemitones] = useState(0);
+  const [speed, setSpeed] = useState(1);
+  // "" = use the global device
+  const [monitorDeviceId, setMonitorDeviceId] = useState("");
+  const [broadcastDeviceId, setBroadcastDeviceId] = useState("");
   const [isSubmitting, setIsSubmitting] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
   const isEditMode = !!sound;
 
+  const { soundLibrary } = useSoundLibrary();
+  const { devices } = useAudio();
+  const existingGroups = [
+    ...new Set(
+      soundLibrary.sounds
+        .map((s) => s.group)
+        .filter((g): g is string => !!g)
+    ),
+  ].sort();
+
   // Initialize form when modal opens
   useEffect(() => {
     if (isOpen) {
@@ -45,6 +74,20 @@ export default function SoundModal({
         setIcon(sound.icon);
         setVolume(sound.volume);
         setUseCustomVolume(sound.volume !== null);
+        setPlayPolicy(sound.play_policy ?? "restart");
+        setResumePlayback(sound.resume_playback ?? false);
+        setExpiryDate(toDateInput(sound.expires_at));
+        setMonitorTrimDb(sound.monitor_trim_db ?? 0);
+        setBroadcastTrimDb(sound.broadcast_trim_db ?? 0);
+        setGroup(sound.group ?? "");
+        setTags((sound.tags ?? []).join(", "));
+        setFitDuration(
+          sound.fit_duration_ms ? String(sound.fit_duration_ms / 1000) : ""
+        );
+        setPitchSemitones(sound.pitch_semitones ?? 0);
+        setSpeed(sound.speed ?? 1);
+        setMonitorDeviceId(sound.monitor_device_id ?? "");
+        setBroadcastDeviceId(sound.broadcast_device_id ?? "");
       } else {
         // Add mode - use defaults
         const path = defaultFilePath || "";
@@ -53,6 +96,18 @@ export default function SoundModal({
         setIcon(null);
         setVolume(null);
         setUseCustomVolume(false);
+        setPlayPolicy("restart");
+        setResumePlayback(false);
+        setExpiryDate("");
+        setMonitorTrimDb(0);
+        setBroadcastTrimDb(0);
+        setGroup("");
+        setTags("");
+        setFitDuration("");
+        setPitchSemitones(0);
+        setSpeed(1);
+        setMonitorDeviceId("");
+        setBroadcastDeviceId("");
 
         // Auto-generate name from defaultFilePath if provided
         if (path) {
@@ -117,7 +172,7 @@ export default function SoundModal({
     const files = Array.from(e.dataTransfer.files);
     const audioFile = files.find(
       (f) =>
-        f.type.startsWith("audio/") || /\.(mp3|wav|ogg|m4a|flac)$/i.test(f.name)
+        f.type.startsWith("audio/") || /\.(mp3|wav|ogg|m4a|flac|opus)$/i.test(f.name)
     );
 
     if (audioFile) {
@@ -137,7 +192,7 @@ export default function SoundModal({
         filters: [
           {
             name: "Audio Files",
-            extensions: ["mp3", "wav", "ogg", "m4a", "flac"],
+            extensions: ["mp3", "wav", "ogg", "m4a", "flac", "opus"],
           },
         ],
       });
@@ -169,11 +224,12 @@ export default function SoundModal({
       setIsSubmitting(true);
       setError(null);
 
+      let saved: Sound;
       if (isEditMode && sound) {
         // Update existing sound
         // Always send volume when editing (even if null) to support clearing
         const volumeValue = useCustomVolume ? volume : null;
-        await invoke("update_sound", {
+        saved = await invoke<Sound>("update_sound", {
           soundId: sound.id,
           name: name.trim(),
           filePath: filePath.trim(),
@@ -183,7 +239,7 @@ export default function SoundModal({
         });
       } else {
         // Add new sound
-        await invoke("add_sound", {
+        saved = await invoke<Sound>("add_sound", {
           name: name.trim(),
           filePath: filePath.trim(),
           categoryId: categoryId,
@@ -192,10 +248,107 @@ export default function SoundModal({
         });
       }
 
+      if (saved.play_policy !== playPolicy) {
+        await invoke("set_play_policy", {
+          soundId: saved.id,
+          playPolicy,
+        });
+      }
+
+      if ((saved.resume_playback ?? false) !== resumePlayback) {
+        await invoke("set_resume_playback", {
+          soundId: saved.id,
+          enabled: resumePlayback,
+        });
+      }
+
+      const expiresAt = fromDateInput(expiryDate);
+      if ((saved.expires_at ?? null) !== expiresAt) {
+        await invoke("set_sound_expiry", {
+          soundId: saved.id,
+          expiresAt,
+        });
+      }
+
+      if (
+        (saved.monitor_trim_db ?? 0) !== monitorTrimDb ||
+        (saved.broadcast_trim_db ?? 0) !== broadcastTrimDb
+      ) {
+        await invoke("set_sound_device_trim", {
+          soundId: saved.id,
+          monitorTrimDb: monitorTrimDb || null,
+          broadcastTrimDb: broadcastTrimDb || null,
+        });
+      }
+
+      const groupName = group.trim() || null;
+      if ((saved.group ?? null) !== groupName) {
+        await invoke("set_sound_group", {
+          soundId: saved.id,
+          group: groupName,
+        });
+      }
+
+      const tagList = tags
+        .split(",")
+        .map((t) => t.trim())
+        .filter((t) => t.length > 0);
+      if (tagList.join(",") !== (saved.tags ?? []).join(",")) {
+        await invoke("set_sound_tags", {
+          soundId: saved.id,
+          tags: tagList,
+        });
+      }
+
+      const fitSeconds = parseFloat(fitDuration);
+      const fitDurationMs =
+        fitSeconds > 0 ? Math.round(fitSeconds * 1000) : null;
+      if ((saved.fit_duration_ms ?? null) !== fitDurationMs) {
+        await invoke("set_sound_fit_duration", {
+          soundId: saved.id,
+          durationMs: fitDurationMs,
+        });
+      }
+
+      if (
+        (saved.pitch_semitones ?? 0) !== pitchSemitones ||
+        (saved.speed ?? 1) !== speed
+      ) {
+        await invoke("set_sound_pitch_speed", {
+          soundId: saved.id,
+          pitchSemitones: pitchSemitones || null,
+          speed: speed === 1 ? null : speed,
+        });
+      }
+
+      const monitorDevice = monitorDeviceId || null;
+      const broadcastDevice = broadcastDeviceId || null;
+      if (
+        (saved.monitor_device_id ?? null) !== monitorDevice ||
+        (saved.broadcast_device_id ?? null) !== broadcastDevice
+      ) {
+        await invoke("set_sound_devices", {
+          soundId: saved.id,
+          monitorDeviceId: monitorDevice,
+          broadcastDeviceId: broadcastDevice,
+        });
+      }
+
+      // Flag empty or silent files (shown as a badge on the sound)
+      if (!isEditMode || saved.file_path !== sound?.file_path) {
+        const warning = await invoke<ImportWarning | null>(
+          "validate_sound_file",
+          { soundId: saved.id }
+        );
+        if (warning) {
+          alert(`"${saved.name}" was saved, but: ${IMPORT_WARNING_LABELS[warning]}`);
+        }
+      }
+
       await onSave();
       onClose();
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     } finally {
       setIsSubmitting(false);
     }
@@ -361,6 +514,228 @@ export default function SoundModal({
               </div>
             )}
           </div>
+
+          {/* Per-sound output devices */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Output devices
+            </label>
+            {(
+              [
+                ["Monitor", monitorDeviceId, setMonitorDeviceId],
+                ["Broadcast", broadcastDeviceId, setBroadcastDeviceId],
+              ] as const
+            ).map(([label, deviceId, setDeviceId]) => (
+              <div key={label} className="flex items-center gap-3 mb-2">
+                <span className="text-sm text-discord-text w-20">{label}</span>
+                <select
+                  value={deviceId}
+                  onChange={(e) => setDeviceId(e.target.value)}
+                  className="flex-1 bg-discord-darker border border-discord-dark rounded
+                           px-3 py-2 text-discord-text focus:outline-none
+                           focus:ring-2 focus:ring-discord-primary"
+                >
+                  <option value="">Global setting</option>
+                  {devices.map((device) => (
+                    <option key={device.id} value={device.id}>
+                      {device.name}
+                    </option>
+                  ))}
+                </select>
+              </div>
+            ))}
+          </div>
+
+          {/* Per-device level trims */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Output level trim
+            </label>
+            {(
+              [
+                ["Monitor", monitorTrimDb, setMonitorTrimDb],
+                ["Broadcast", broadcastTrimDb, setBroadcastTrimDb],
+              ] as const
+            ).map(([label, trimDb, setTrimDb]) => (
+              <div key={label} className="flex items-center gap-3">
+                <span className="text-sm text-discord-text w-20">{label}</span>
+                <input
+                  type="range"
+                  min="-30"
+                  max="6"
+                  step="0.5"
+                  value={trimDb}
+                  onChange={(e) => setTrimDb(parseFloat(e.target.value))}
+                  className="flex-1"
+                />
+                <span className="text-sm w-16 text-right text-discord-text">
+                  {trimDb > 0 ? "+" : ""}
+                  {trimDb.toFixed(1)} dB
+                </span>
+              </div>
+            ))}
+            <p className="text-xs text-discord-text-muted mt-1">
+              Adjusts this sound on one output only, e.g. quieter in your
+              headphones than on stream.
+            </p>
+          </div>
+
+          {/* Re-trigger behavior */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              When triggered while playing
+            </label>
+            <select
+              value={playPolicy}
+              onChange={(e) => setPlayPolicy(e.target.value as PlayPolicy)}
+              className="w-full bg-discord-darker border border-discord-dark rounded
+                       px-3 py-2 text-discord-text focus:outline-none
+                       focus:ring-2 focus:ring-discord-primary"
+            >
+              <option value="restart">Restart from the beginning</option>
+              <option value="extend">Keep playing, play once more</option>
+              <option value="overlap">Play again on top</option>
+              <option value="ignore_if_playing">Ignore the trigger</option>
+              <option value="queue">Play again after it ends</option>
+            </select>
+            <label className="flex items-center gap-2 text-sm text-discord-text-muted mt-3 cursor-pointer">
+              <input
+                type="checkbox"
+                checked={resumePlayback}
+                onChange={(e) => setResumePlayback(e.target.checked)}
+                className="rounded border-discord-dark bg-discord-darker
+                         text-discord-primary focus:ring-discord-primary"
+              />
+              Resume where it was stopped (long clips, music beds)
+            </label>
+          </div>
+
+          {/* Exclusive group */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Exclusive group (optional)
+            </label>
+            <input
+              type="text"
+              value={group}
+              onChange={(e) => setGroup(e.target.value)}
+              list="sound-groups"
+              placeholder="e.g. Music"
+              className="w-full bg-discord-darker border border-discord-dark rounded
+                       px-3 py-2 text-discord-text focus:outline-none
+                       focus:ring-2 focus:ring-discord-primary"
+            />
+            <datalist id="sound-groups">
+              {existingGroups.map((g) => (
+                <option key={g} value={g} />
+              ))}
+            </datalist>
+            <p className="text-xs text-discord-text-muted mt-1">
+              Starting this sound stops other playing sounds of the same group.
+            </p>
+          </div>
+
+          {/* Tags */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Tags (optional)
+            </label>
+            <input
+              type="text"
+              value={tags}
+              onChange={(e) => setTags(e.target.value)}
+              placeholder="e.g. meme, loud"
+              className="w-full bg-discord-darker border border-discord-dark rounded
+                       px-3 py-2 text-discord-text focus:outline-none
+                       focus:ring-2 focus:ring-discord-primary"
+            />
+            <p className="text-xs text-discord-text-muted mt-1">
+              Separate tags with commas. Type #tag in quick search to filter.
+            </p>
+          </div>
+
+          {/* Fit to duration */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Fit to duration in seconds (optional)
+            </label>
+            <input
+              type="number"
+              min="0.1"
+              step="0.1"
+              value={fitDuration}
+              onChange={(e) => setFitDuration(e.target.value)}
+              placeholder="e.g. 10"
+              className="w-full bg-discord-darker border border-discord-dark rounded
+                       px-3 py-2 text-discord-text focus:outline-none
+                       focus:ring-2 focus:ring-discord-primary"
+            />
+            <p className="text-xs text-discord-text-muted mt-1">
+              Speeds up or slows down the sound to last exactly this long,
+              without changing its pitch (at most 4x either way).
+            </p>
+          </div>
+
+          {/* Pitch and speed */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Pitch and speed
+            </label>
+            <div className="flex items-center gap-3">
+              <span className="text-sm text-discord-text w-20">Pitch</span>
+              <input
+                type="range"
+                min="-12"
+                max="12"
+                step="1"
+                value={pitchSemitones}
+                onChange={(e) => setPitchSemitones(parseFloat(e.target.value))}
+                className="flex-1"
+              />
+              <span className="text-sm w-16 text-right text-discord-text">
+                {pitchSemitones > 0 ? "+" : ""}
+                {pitchSemitones} st
+              </span>
+            </div>
+            <div className="flex items-center gap-3">
+              <span className="text-sm text-discord-text w-20">Speed</span>
+              <input
+                type="range"
+                min="0.5"
+                max="2"
+                step="0.05"
+                value={speed}
+                onChange={(e) => setSpeed(parseFloat(e.target.value))}
+                className="flex-1"
+              />
+              <span className="text-sm w-16 text-right text-discord-text">
+                {speed.toFixed(2)}x
+              </span>
+            </div>
+            <p className="text-xs text-discord-text-muted mt-1">
+              Pitch keeps the length; speed changes the pitch along, like a
+              tape. With a fit duration set, only the pitch applies.
+            </p>
+          </div>
+
+          {/* Temporary sound */}
+          <div>
+            <label className="block text-sm font-medium text-discord-text-muted mb-2">
+              Remove after (optional)
+            </label>
+            <input
+              type="date"
+              value={expiryDate}
+              onChange={(e) => setExpiryDate(e.target.value)}
+              className="w-full bg-discord-darker border border-discord-dark rounded
+                       px-3 py-2 text-discord-text focus:outline-none
+                       focus:ring-2 focus:ring-discord-primary"
+            />
+            <p className="text-xs text-discord-text-muted mt-1">
+              Temporary sounds are removed, with their hotkeys, on the first
+              start after this day.
+            </p>
+          </div>
         </div>
 
         {/* Actions */}
@@ -392,3 +767,18 @@ export default function SoundModal({
     </div>
   );
 }
+
+/** Unix ms -> local "YYYY-MM-DD" for a date input */
+function toDateInput(ms: number | null | undefined): string {
+  if (ms == null) return "";
+  const date = new Date(ms);
+  const pad = (n: number) => String(n).padStart(2, "0");
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+}
+
+/** Local "YYYY-MM-DD" -> Unix ms at the end of that day (null if empty) */
+function fromDateInput(value: string): number | null {
+  if (!value) return null;
+  const [year, month, day] = value.split("-").map(Number);
+  return new Date(year, month - 1, day, 23, 59, 59, 999).getTime();
+}
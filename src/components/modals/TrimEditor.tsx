@@ -1,11 +1,14 @@
 import { useEffect, useRef, useState, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
 import { Sound } from "../../types";
+import { formatError } from "../../utils/errors";
 
 interface TrimEditorProps {
   sound: Sound;
   onClose: () => void;
   onSave: (trimStartMs: number | null, trimEndMs: number | null) => void;
+  onPreviewSave: () => void;
 }
 
 interface WaveformData {
@@ -13,10 +16,28 @@ interface WaveformData {
   duration_ms: number;
 }
 
+/** Handles snap to an onset closer than this (in pixels) */
+const SNAP_DISTANCE_PX = 8;
+
+/** Longest preview region the backend accepts (MAX_PREVIEW_MS) */
+const MAX_PREVIEW_MS = 10000;
+
+/** Peaks drawn across the editor, zoomed in or not */
+const EDITOR_PEAKS = 400;
+
+/** Deepest zoom (the visible range is at least 1/MAX_ZOOM of the sound) */
+const MAX_ZOOM = 64;
+
+interface ViewRange {
+  start: number;
+  end: number;
+}
+
 export default function TrimEditor({
   sound,
   onClose,
   onSave,
+  onPreviewSave,
 }: TrimEditorProps) {
   const canvasRef = useRef<HTMLCanvasElement>(null);
   const containerRef = useRef<HTMLDivElement>(null);
@@ -31,12 +52,30 @@ export default function TrimEditor({
   // Drag state
   const [isDragging, setIsDragging] = useState<"start" | "end" | null>(null);
 
+  // Transient onsets (ms) the trim handles snap to
+  const [onsets, setOnsets] = useState<number[]>([]);
+
+  // Fades applied when exporting a trimmed copy
+  const [fadeInMs, setFadeInMs] = useState<number>(0);
+  const [fadeOutMs, setFadeOutMs] = useState<number>(0);
+  const [isExporting, setIsExporting] = useState(false);
+
+  // Zoomed-in range (ms) and its peaks, null while the whole sound is shown
+  const [view, setView] = useState<ViewRange | null>(null);
+  const [viewPeaks, setViewPeaks] = useState<number[] | null>(null);
+
+  const viewStart = view?.start ?? 0;
+  const viewSpan = Math.max(
+    1,
+    (view?.end ?? waveformData?.duration_ms ?? 0) - viewStart
+  );
+
   // Load waveform data
   useEffect(() => {
     setIsLoading(true);
     invoke<WaveformData>("get_waveform", {
       filePath: sound.file_path,
-      numPeaks: 400, // Good resolution for editor
+      numPeaks: EDITOR_PEAKS,
     })
       .then((data) => {
         setWaveformData(data);
@@ -52,6 +91,38 @@ export default function TrimEditor({
       });
   }, [sound.file_path]);
 
+  // Load the peaks of the zoomed-in range (served from the backend's cache)
+  useEffect(() => {
+    if (!view) {
+      setViewPeaks(null);
+      return;
+    }
+    let cancelled = false;
+    invoke<WaveformData>("get_waveform_range", {
+      filePath: sound.file_path,
+      startMs: Math.round(view.start),
+      endMs: Math.round(view.end),
+      numPeaks: EDITOR_PEAKS,
+    })
+      .then((data) => {
+        if (!cancelled) setViewPeaks(data.peaks);
+      })
+      .catch((err) => console.error("Failed to load zoomed waveform:", err));
+    return () => {
+      cancelled = true;
+    };
+  }, [view, sound.file_path]);
+
+  // Load onset markers (optional, the editor works without them)
+  useEffect(() => {
+    invoke<number[]>("detect_onsets", { filePath: sound.file_path })
+      .then(setOnsets)
+      .catch((err) => {
+        console.error("Failed to detect onsets:", err);
+        setOnsets([]);
+      });
+  }, [sound.file_path]);
+
   // Draw waveform with trim region
   useEffect(() => {
     const canvas = canvasRef.current;
@@ -75,20 +146,22 @@ export default function TrimEditor({
 
     const width = rect.width;
     const height = 120;
-    const barWidth = width / waveformData.peaks.length;
+    const peaks = viewPeaks ?? waveformData.peaks;
+    const barWidth = width / peaks.length;
     const midY = height / 2;
+    const xOf = (ms: number) => ((ms - viewStart) / viewSpan) * width;
 
     // Clear canvas
     ctx.clearRect(0, 0, width, height);
 
     // Calculate trim positions
     const duration = waveformData.duration_ms;
-    const startX = (trimStart / duration) * width;
-    const endX = ((trimEnd || duration) / duration) * width;
+    const startX = xOf(trimStart);
+    const endX = xOf(trimEnd || duration);
 
     // Draw waveform bars
-    for (let i = 0; i < waveformData.peaks.length; i++) {
-      const barHeight = Math.max(2, waveformData.peaks[i] * midY * 0.85);
+    for (let i = 0; i < peaks.length; i++) {
+      const barHeight = Math.max(2, peaks[i] * midY * 0.85);
       const x = i * barWidth;
       const barMidX = x + barWidth / 2;
 
@@ -103,6 +176,19 @@ export default function TrimEditor({
       ctx.fillRect(x, midY - barHeight, actualBarWidth, barHeight * 2);
     }
 
+    // Draw onset markers
+    ctx.strokeStyle = "rgba(250, 166, 26, 0.6)";
+    ctx.lineWidth = 1;
+    for (const onset of onsets) {
+      const x = xOf(onset);
+      ctx.beginPath();
+      ctx.moveTo(x, 0);
+      ctx.lineTo(x, 10);
+      ctx.moveTo(x, height - 10);
+      ctx.lineTo(x, height);
+      ctx.stroke();
+    }
+
     // Draw trim region borders
     ctx.strokeStyle = "#ffffff";
     ctx.lineWidth = 3;
@@ -118,7 +204,15 @@ export default function TrimEditor({
     ctx.moveTo(endX, 0);
     ctx.lineTo(endX, height);
     ctx.stroke();
-  }, [waveformData, trimStart, trimEnd]);
+  }, [
+    waveformData,
+    viewPeaks,
+    viewStart,
+    viewSpan,
+    onsets,
+    trimStart,
+    trimEnd,
+  ]);
 
   // Handle mouse down on trim handles
   const handleMouseDown = useCallback(
@@ -137,7 +231,18 @@ export default function TrimEditor({
       const rect = containerRef.current.getBoundingClientRect();
       const x = e.clientX - rect.left;
       const percentage = Math.max(0, Math.min(1, x / rect.width));
-      const ms = Math.round(percentage * waveformData.duration_ms);
+      let ms = Math.round(viewStart + percentage * viewSpan);
+
+      // Snap to the nearest onset unless Alt is held
+      if (!e.altKey && onsets.length > 0) {
+        const snapMs = (SNAP_DISTANCE_PX / rect.width) * viewSpan;
+        const nearest = onsets.reduce((best, onset) =>
+          Math.abs(onset - ms) < Math.abs(best - ms) ? onset : best
+        );
+        if (Math.abs(nearest - ms) <= snapMs) {
+          ms = nearest;
+        }
+      }
 
       if (isDragging === "start") {
         const maxStart = (trimEnd || waveformData.duration_ms) - 100; // Min 100ms region
@@ -147,7 +252,15 @@ export default function TrimEditor({
         setTrimEnd(Math.max(ms, minEnd));
       }
     },
-    [isDragging, waveformData, trimStart, trimEnd]
+    [
+      isDragging,
+      waveformData,
+      viewStart,
+      viewSpan,
+      onsets,
+      trimStart,
+      trimEnd,
+    ]
   );
 
   // Handle mouse up
@@ -167,6 +280,34 @@ export default function TrimEditor({
     }
   }, [isDragging, handleMouseMove, handleMouseUp]);
 
+  // Zoom by `factor` (< 1 zooms in), keeping `centerMs` where it is
+  const zoomAround = (centerMs: number, factor: number) => {
+    if (!waveformData) return;
+    const duration = waveformData.duration_ms;
+    const span = Math.max(duration / MAX_ZOOM, viewSpan * factor);
+    if (span >= duration) {
+      setView(null);
+      return;
+    }
+    const fraction = (centerMs - viewStart) / viewSpan;
+    const start = Math.max(
+      0,
+      Math.min(duration - span, centerMs - fraction * span)
+    );
+    setView({ start, end: start + span });
+  };
+
+  // Zoom with the mouse wheel around the pointer
+  const handleWheel = (e: React.WheelEvent) => {
+    if (!containerRef.current || e.deltaY === 0) return;
+    const rect = containerRef.current.getBoundingClientRect();
+    const fraction = Math.max(
+      0,
+      Math.min(1, (e.clientX - rect.left) / rect.width)
+    );
+    zoomAround(viewStart + fraction * viewSpan, e.deltaY < 0 ? 0.5 : 2);
+  };
+
   // Format time helper
   const formatTime = (ms: number): string => {
     const totalSeconds = Math.floor(ms / 1000);
@@ -197,7 +338,56 @@ export default function TrimEditor({
       onClose();
     } catch (error) {
       console.error("Failed to save trim:", error);
-      alert(`Failed to save trim: ${error}`);
+      alert(`Failed to save trim: ${formatError(error)}`);
+    }
+  };
+
+  // Save the selected region as the hover preview (the trim is unchanged)
+  const handleSavePreview = async (clear: boolean) => {
+    try {
+      await invoke("set_sound_preview", {
+        soundId: sound.id,
+        preview: clear
+          ? null
+          : {
+              start_ms: Math.round(trimStart),
+              end_ms: Math.round(trimEnd ?? waveformData?.duration_ms ?? 0),
+            },
+      });
+      onPreviewSave();
+      onClose();
+    } catch (error) {
+      console.error("Failed to save preview:", error);
+      alert(`Failed to save preview: ${formatError(error)}`);
+    }
+  };
+
+  // Write the saved trim region, with fades, to a new WAV file
+  const handleExport = async () => {
+    const stem = sound.file_path
+      .split(/[\\/]/)
+      .pop()
+      ?.replace(/\.[^.]+$/, "");
+    const outputPath = await save({
+      defaultPath: `${stem || sound.name} (trimmed).wav`,
+      filters: [{ name: "WAV", extensions: ["wav"] }],
+    });
+    if (!outputPath) return;
+
+    setIsExporting(true);
+    try {
+      const written = await invoke<string>("render_trimmed_copy", {
+        soundId: sound.id,
+        outputPath,
+        fadeInMs,
+        fadeOutMs,
+      });
+      alert(`Trimmed copy saved to ${written}`);
+    } catch (error) {
+      console.error("Failed to export trimmed copy:", error);
+      alert(`Failed to export trimmed copy: ${formatError(error)}`);
+    } finally {
+      setIsExporting(false);
     }
   };
 
@@ -219,6 +409,13 @@ export default function TrimEditor({
 
   const duration = waveformData.duration_ms;
   const trimmedDuration = (trimEnd || duration) - trimStart;
+  // Position (0-1) of a time in the visible range
+  const fractionOf = (ms: number) => (ms - viewStart) / viewSpan;
+  const inView = (ms: number) => fractionOf(ms) >= 0 && fractionOf(ms) <= 1;
+  // The export renders the saved trim, not unsaved handle positions
+  const trimUnsaved =
+    trimStart !== (sound.trim_start_ms || 0) ||
+    (trimEnd ?? duration) !== (sound.trim_end_ms ?? duration);
 
   return (
     <div className="fixed inset-0 z-50 bg-black/80 flex items-center justify-center p-6">
@@ -230,7 +427,49 @@ export default function TrimEditor({
           </h2>
           <p className="text-sm text-discord-text-muted">
             Drag the white lines to trim the audio
+            {onsets.length > 0 &&
+              " (they snap to the orange onset markers, hold Alt to place freely)"}
+          </p>
+          <p className="text-sm text-discord-text-muted">
+            Scroll over the waveform to zoom in and out
           </p>
+          <p className="text-sm text-discord-text-muted">
+            Or select up to {MAX_PREVIEW_MS / 1000} seconds and save them as
+            the preview played when hovering the button
+          </p>
+        </div>
+
+        {/* Zoom */}
+        <div className="flex items-center justify-end gap-2 mb-2 text-sm">
+          <span className="text-discord-text-muted">
+            {view
+              ? `${formatTime(view.start)} - ${formatTime(view.end)} (${Math.round(duration / viewSpan)}x)`
+              : "Whole sound"}
+          </span>
+          <button
+            onClick={() => zoomAround(viewStart + viewSpan / 2, 2)}
+            disabled={!view}
+            className="px-2 py-1 bg-discord-dark hover:bg-discord-darkest rounded
+                     text-discord-text transition-colors disabled:opacity-50"
+          >
+            −
+          </button>
+          <button
+            onClick={() => zoomAround(viewStart + viewSpan / 2, 0.5)}
+            disabled={viewSpan <= duration / MAX_ZOOM}
+            className="px-2 py-1 bg-discord-dark hover:bg-discord-darkest rounded
+                     text-discord-text transition-colors disabled:opacity-50"
+          >
+            +
+          </button>
+          <button
+            onClick={() => setView(null)}
+            disabled={!view}
+            className="px-2 py-1 bg-discord-dark hover:bg-discord-darkest rounded
+                     text-discord-text transition-colors disabled:opacity-50"
+          >
+            Fit
+          </button>
         </div>
 
         {/* Waveform Editor */}
@@ -238,6 +477,7 @@ export default function TrimEditor({
           ref={containerRef}
           className="relative bg-discord-darkest rounded-lg p-4 mb-6"
           style={{ cursor: isDragging ? "col-resize" : "default" }}
+          onWheel={handleWheel}
         >
           <canvas
             ref={canvasRef}
@@ -245,17 +485,39 @@ export default function TrimEditor({
             style={{ height: "120px" }}
           />
 
-          {/* Trim handles */}
-          <div
-            className="absolute top-0 bottom-0 w-2 bg-white/20 hover:bg-white/40 cursor-col-resize"
-            style={{ left: `${(trimStart / duration) * 100}%` }}
-            onMouseDown={(e) => handleMouseDown(e, "start")}
-          />
-          <div
-            className="absolute top-0 bottom-0 w-2 bg-white/20 hover:bg-white/40 cursor-col-resize"
-            style={{ left: `${((trimEnd || duration) / duration) * 100}%` }}
-            onMouseDown={(e) => handleMouseDown(e, "end")}
-          />
+          {/* Saved preview region */}
+          {sound.preview &&
+            (() => {
+              const left = Math.max(0, fractionOf(sound.preview.start_ms));
+              const right = Math.min(1, fractionOf(sound.preview.end_ms));
+              return (
+                right > left && (
+                  <div
+                    className="absolute top-0 bottom-0 bg-discord-success/20 pointer-events-none"
+                    style={{
+                      left: `${left * 100}%`,
+                      width: `${(right - left) * 100}%`,
+                    }}
+                  />
+                )
+              );
+            })()}
+
+          {/* Trim handles (only those in the visible range) */}
+          {inView(trimStart) && (
+            <div
+              className="absolute top-0 bottom-0 w-2 bg-white/20 hover:bg-white/40 cursor-col-resize"
+              style={{ left: `${fractionOf(trimStart) * 100}%` }}
+              onMouseDown={(e) => handleMouseDown(e, "start")}
+            />
+          )}
+          {inView(trimEnd || duration) && (
+            <div
+              className="absolute top-0 bottom-0 w-2 bg-white/20 hover:bg-white/40 cursor-col-resize"
+              style={{ left: `${fractionOf(trimEnd || duration) * 100}%` }}
+              onMouseDown={(e) => handleMouseDown(e, "end")}
+            />
+          )}
         </div>
 
         {/* Time Info */}
@@ -280,6 +542,48 @@ export default function TrimEditor({
           </div>
         </div>
 
+        {/* Export */}
+        <div className="flex flex-wrap items-center gap-3 mb-6 text-sm">
+          <label className="flex items-center gap-2 text-discord-text-muted">
+            Fade in (ms)
+            <input
+              type="number"
+              min={0}
+              step={10}
+              value={fadeInMs}
+              onChange={(e) =>
+                setFadeInMs(Math.max(0, Number(e.target.value) || 0))
+              }
+              className="w-20 bg-discord-dark border border-discord-dark rounded px-2 py-1
+                       text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+            />
+          </label>
+          <label className="flex items-center gap-2 text-discord-text-muted">
+            Fade out (ms)
+            <input
+              type="number"
+              min={0}
+              step={10}
+              value={fadeOutMs}
+              onChange={(e) =>
+                setFadeOutMs(Math.max(0, Number(e.target.value) || 0))
+              }
+              className="w-20 bg-discord-dark border border-discord-dark rounded px-2 py-1
+                       text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+            />
+          </label>
+          <button
+            onClick={handleExport}
+            disabled={trimUnsaved || isExporting}
+            title={trimUnsaved ? "Save the trim first" : undefined}
+            className="px-4 py-2 bg-discord-dark hover:bg-discord-darkest rounded-lg
+                     text-discord-text transition-colors disabled:opacity-50
+                     disabled:cursor-not-allowed"
+          >
+            {isExporting ? "Exporting..." : "Export Trimmed Copy"}
+          </button>
+        </div>
+
         {/* Actions */}
         <div className="flex justify-between">
           <button
@@ -290,6 +594,29 @@ export default function TrimEditor({
             Reset to Full
           </button>
           <div className="flex gap-3">
+            {sound.preview && (
+              <button
+                onClick={() => handleSavePreview(true)}
+                className="px-4 py-2 bg-discord-dark hover:bg-discord-darkest rounded-lg
+                         text-discord-text transition-colors"
+              >
+                Clear Preview
+              </button>
+            )}
+            <button
+              onClick={() => handleSavePreview(false)}
+              disabled={trimmedDuration > MAX_PREVIEW_MS}
+              title={
+                trimmedDuration > MAX_PREVIEW_MS
+                  ? `Select at most ${MAX_PREVIEW_MS / 1000} seconds`
+                  : undefined
+              }
+              className="px-4 py-2 bg-discord-success hover:bg-green-600 rounded-lg
+                       text-white transition-colors disabled:opacity-50
+                       disabled:cursor-not-allowed"
+            >
+              Save as Preview
+            </button>
             <button
               onClick={onClose}
               className="px-4 py-2 bg-discord-danger hover:bg-red-600 rounded-lg
@@ -0,0 +1,144 @@
+import { useEffect, useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { SearchResult } from "../../types";
+import { formatError } from "../../utils/errors";
+
+/**
+ * Spotlight-style popup (separate "quick-search" window) for playing any
+ * sound by typing its name. Enter plays, Escape or losing focus hides it.
+ */
+export default function QuickSearch() {
+  const [query, setQuery] = useState("");
+  const [results, setResults] = useState<SearchResult[]>([]);
+  const [selected, setSelected] = useState(0);
+  const [error, setError] = useState<string>("");
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  const hide = () => {
+    invoke("hide_quick_search").catch(console.error);
+  };
+
+  // Search as the user types (backend fuzzy search); "#word" filters by tag
+  useEffect(() => {
+    let cancelled = false;
+    const words = query.split(/\s+/);
+    const tags = words
+      .filter((w) => w.startsWith("#") && w.length > 1)
+      .map((w) => w.slice(1));
+    const text = words.filter((w) => !w.startsWith("#")).join(" ");
+    invoke<SearchResult[]>("search_sounds", { query: text, tags })
+      .then((found) => {
+        if (cancelled) return;
+        setResults(found);
+        setSelected(0);
+      })
+      .catch((err) => setError(formatError(err)));
+    return () => {
+      cancelled = true;
+    };
+  }, [query]);
+
+  // Reset and refocus whenever the popup is shown; hide when it loses focus
+  useEffect(() => {
+    const unlisten = getCurrentWindow().onFocusChanged(
+      ({ payload: focused }) => {
+        if (focused) {
+          setQuery("");
+          setError("");
+          inputRef.current?.focus();
+        } else {
+          hide();
+        }
+      }
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const play = async (result: SearchResult | undefined) => {
+    if (!result) return;
+    try {
+      await invoke("play_quick_search_result", { soundId: result.sound.id });
+      hide();
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent<HTMLInputElement>) => {
+    switch (e.key) {
+      case "ArrowDown":
+        e.preventDefault();
+        setSelected((i) => Math.min(i + 1, results.length - 1));
+        break;
+      case "ArrowUp":
+        e.preventDefault();
+        setSelected((i) => Math.max(i - 1, 0));
+        break;
+      case "Enter":
+        e.preventDefault();
+        play(results[selected]);
+        break;
+      case "Escape":
+        e.preventDefault();
+        hide();
+        break;
+    }
+  };
+
+  return (
+    <div className="w-full h-full bg-discord-darkest border border-discord-dark rounded-lg flex flex-col overflow-hidden">
+      <input
+        ref={inputRef}
+        autoFocus
+        value={query}
+        onChange={(e) => setQuery(e.target.value)}
+        onKeyDown={handleKeyDown}
+        placeholder="Search sounds..."
+        className="w-full bg-discord-darker px-4 py-3 text-lg text-discord-text
+                 placeholder-discord-text-muted focus:outline-none"
+      />
+
+      <div className="flex-1 overflow-y-auto">
+        {results.length === 0 ? (
+          <p className="px-4 py-3 text-sm text-discord-text-muted">
+            {query ? "No matching sounds" : "Type to search your sounds"}
+          </p>
+        ) : (
+          results.map((result, index) => (
+            <button
+              key={result.sound.id}
+              onMouseEnter={() => setSelected(index)}
+              onClick={() => play(result)}
+              className={`w-full flex items-center gap-3 px-4 py-2 text-left transition-colors ${
+                index === selected
+                  ? "bg-discord-primary text-white"
+                  : "text-discord-text hover:bg-discord-dark"
+              }`}
+            >
+              <span className="text-xl w-6 text-center">
+                {result.sound.icon || "🔊"}
+              </span>
+              <span className="flex-1 truncate">{result.sound.name}</span>
+              {result.matched === "tag" && (
+                <span className="text-xs opacity-70">tag</span>
+              )}
+              {result.matched === "file_name" && (
+                <span className="text-xs opacity-70">file</span>
+              )}
+              {result.sound.is_favorite && <span>⭐</span>}
+            </button>
+          ))
+        )}
+      </div>
+
+      {error && (
+        <p className="px-4 py-2 text-xs text-discord-danger border-t border-discord-dark">
+          {error}
+        </p>
+      )}
+    </div>
+  );
+}
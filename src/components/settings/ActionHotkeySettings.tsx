@@ -0,0 +1,187 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { BindingFormat, HotkeyAction } from "../../types";
+import { useHotkeyMappings } from "../../hooks/useHotkeyMappings";
+import { formatError } from "../../utils/errors";
+import { captureHotkey } from "../../utils/hotkeyCapture";
+import { formatHotkeyForDisplay } from "../../utils/hotkeyDisplay";
+
+// Global actions offered here (normalization and quick search have their own sections)
+const ACTIONS: { action: HotkeyAction; label: string }[] = [
+  { action: "stop_all", label: "Stop all sounds" },
+  { action: "toggle_mic_routing", label: "Toggle microphone routing" },
+  { action: "volume_up", label: "Master volume up" },
+  { action: "volume_down", label: "Master volume down" },
+];
+
+// Duck hotkey attenuation (matches hotkeys::MAX_DUCK_DB)
+const DEFAULT_DUCK_DB = 12;
+const MAX_DUCK_DB = 60;
+
+export default function ActionHotkeySettings() {
+  const { hotkeyMappings, refreshHotkeys } = useHotkeyMappings();
+  const [capturing, setCapturing] = useState<HotkeyAction | null>(null);
+  const [error, setError] = useState<string>("");
+
+  const hotkeyFor = (action: HotkeyAction) =>
+    Object.entries(hotkeyMappings.actions ?? {}).find(
+      ([_, bound]) => bound === action
+    )?.[0];
+
+  const duckBinding = Object.entries(hotkeyMappings.actions ?? {}).find(
+    ([_, bound]) => typeof bound === "object" && "duck" in bound
+  ) as [string, { duck: number }] | undefined;
+  const duckHotkey = duckBinding?.[0];
+  const boundDuckDb = duckBinding?.[1].duck ?? DEFAULT_DUCK_DB;
+  const [duckDb, setDuckDb] = useState<number | null>(null);
+  const isCapturingDuck =
+    typeof capturing === "object" && capturing !== null && "duck" in capturing;
+
+  const handleSet = async (
+    action: HotkeyAction,
+    existing = hotkeyFor(action)
+  ) => {
+    setCapturing(action);
+    setError("");
+    try {
+      const hotkey = await captureHotkey();
+      if (existing) {
+        await invoke("unregister_hotkey", { hotkey: existing });
+      }
+      await invoke("register_action_hotkey", { hotkey, action });
+    } catch (err) {
+      setError(formatError(err));
+    } finally {
+      setCapturing(null);
+      await refreshHotkeys();
+    }
+  };
+
+  const handleClear = async (hotkey: string) => {
+    try {
+      await invoke("unregister_hotkey", { hotkey });
+    } catch (err) {
+      setError(formatError(err));
+    } finally {
+      await refreshHotkeys();
+    }
+  };
+
+  const handleExport = async (format: BindingFormat) => {
+    const path = await save({
+      defaultPath: `sonicdeck-bindings.${format}`,
+      filters: [{ name: format.toUpperCase(), extensions: [format] }],
+    });
+    if (!path) return;
+
+    try {
+      await invoke<string>("export_bindings", { format, path });
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">
+        Action Hotkeys
+      </h2>
+
+      {ACTIONS.map(({ action, label }) => {
+        const hotkey = hotkeyFor(action);
+        const isCapturing = capturing === action;
+        return (
+          <div
+            key={label}
+            className="flex items-center gap-2 text-sm text-discord-text"
+          >
+            <span className="flex-1">{label}</span>
+            <span className="font-mono text-discord-text-muted">
+              {isCapturing
+                ? "Press a key combination..."
+                : hotkey
+                  ? formatHotkeyForDisplay(hotkey)
+                  : "None"}
+            </span>
+            <button
+              onClick={() => handleSet(action)}
+              disabled={capturing !== null}
+              className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                       text-xs transition-colors disabled:opacity-50"
+            >
+              Set
+            </button>
+            {hotkey && !isCapturing && (
+              <button
+                onClick={() => handleClear(hotkey)}
+                className="px-2 py-1 bg-discord-darker hover:bg-discord-danger rounded
+                         text-xs transition-colors"
+              >
+                Clear
+              </button>
+            )}
+          </div>
+        );
+      })}
+
+      <div className="flex items-center gap-2 text-sm text-discord-text">
+        <span className="flex-1">Duck all sounds while held</span>
+        <input
+          type="number"
+          min={1}
+          max={MAX_DUCK_DB}
+          value={duckDb ?? boundDuckDb}
+          onChange={(e) => setDuckDb(parseInt(e.target.value, 10) || 1)}
+          className="w-14 px-2 py-1 bg-discord-darker rounded text-xs"
+          title="Attenuation in dB"
+        />
+        <span className="text-xs text-discord-text-muted">dB</span>
+        <span className="font-mono text-discord-text-muted">
+          {isCapturingDuck
+            ? "Press a key combination..."
+            : duckHotkey
+              ? formatHotkeyForDisplay(duckHotkey)
+              : "None"}
+        </span>
+        <button
+          onClick={() =>
+            handleSet({ duck: duckDb ?? boundDuckDb }, duckHotkey)
+          }
+          disabled={capturing !== null}
+          className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                   text-xs transition-colors disabled:opacity-50"
+        >
+          Set
+        </button>
+        {duckHotkey && !isCapturingDuck && (
+          <button
+            onClick={() => handleClear(duckHotkey)}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-danger rounded
+                     text-xs transition-colors"
+          >
+            Clear
+          </button>
+        )}
+      </div>
+      <div className="flex items-center gap-2 text-sm text-discord-text">
+        <span className="flex-1">Export all bindings (for OBS, overlays)</span>
+        {(["json", "csv"] as const).map((format) => (
+          <button
+            key={format}
+            onClick={() => handleExport(format)}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                     text-xs transition-colors"
+          >
+            {format.toUpperCase()}
+          </button>
+        ))}
+      </div>
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+      <p className="text-xs text-discord-text-muted">
+        Volume hotkeys change the master volume in 5% steps. The duck hotkey
+        lowers every sound while it is held down.
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,284 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  ApiAuditEntry,
+  ApiCapability,
+  ApiStatus,
+  AppSettings,
+} from "../../types";
+import { formatError } from "../../utils/errors";
+
+const CAPABILITIES: { value: ApiCapability; label: string }[] = [
+  { value: "events", label: "Receive events" },
+  { value: "spectate", label: "Watch now playing (read-only)" },
+  { value: "play", label: "Play sounds" },
+  { value: "stop", label: "Stop playback" },
+  { value: "edit_library", label: "Edit library" },
+  { value: "edit_settings", label: "Change settings" },
+];
+
+interface ApiSettingsProps {
+  settings: AppSettings;
+  onUpdateSetting: (
+    key: keyof AppSettings,
+    value: string | number | boolean | null
+  ) => void;
+}
+
+export default function ApiSettings({
+  settings,
+  onUpdateSetting,
+}: ApiSettingsProps) {
+  const [status, setStatus] = useState<ApiStatus | null>(null);
+  const [error, setError] = useState<string>("");
+  const [tokenName, setTokenName] = useState("");
+  const [tokenCapabilities, setTokenCapabilities] = useState<ApiCapability[]>([
+    "events",
+  ]);
+  const [auditLog, setAuditLog] = useState<ApiAuditEntry[]>([]);
+
+  const refreshStatus = async () => {
+    try {
+      setStatus(await invoke<ApiStatus>("get_api_status"));
+    } catch (err) {
+      console.error("Failed to get API status:", err);
+    }
+  };
+
+  const refreshAuditLog = async () => {
+    try {
+      setAuditLog(await invoke<ApiAuditEntry[]>("get_api_audit_log"));
+    } catch (err) {
+      console.error("Failed to get API audit log:", err);
+    }
+  };
+
+  useEffect(() => {
+    refreshStatus();
+    refreshAuditLog();
+  }, []);
+
+  const toggleCapability = (capability: ApiCapability, enabled: boolean) => {
+    setTokenCapabilities((current) =>
+      enabled
+        ? [...current, capability]
+        : current.filter((c) => c !== capability)
+    );
+  };
+
+  const createToken = async () => {
+    setError("");
+    try {
+      await invoke("create_api_token", {
+        name: tokenName,
+        capabilities: tokenCapabilities,
+      });
+      setTokenName("");
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const revokeToken = async (tokenId: string) => {
+    setError("");
+    try {
+      await invoke("revoke_api_token", { tokenId });
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const applyOpenLocalAccess = async (open: boolean) => {
+    setError("");
+    try {
+      await invoke<AppSettings>("set_api_open_local_access", { open });
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const applyApiServer = async (enabled: boolean) => {
+    setError("");
+    try {
+      await invoke<AppSettings>("set_api_server", {
+        enabled,
+        port: settings.api_port,
+      });
+    } catch (err) {
+      setError(formatError(err));
+    } finally {
+      await refreshStatus();
+    }
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">
+        Local API
+      </h2>
+
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={settings.api_enabled}
+          onChange={(e) => applyApiServer(e.target.checked)}
+          className="rounded border-discord-dark bg-discord-darker
+                   text-discord-primary focus:ring-discord-primary cursor-pointer"
+        />
+        <span className="text-sm text-discord-text">
+          Enable local API for Stream Deck / RGB feedback
+        </span>
+      </label>
+      <p className="text-xs text-discord-text-muted ml-6">
+        Streams sound started/stopped and hotkey events as server-sent events
+        from{" "}
+        <code className="bg-discord-darker px-1 rounded">
+          http://127.0.0.1:{settings.api_port}/events
+        </code>
+        . Only reachable from this computer.
+      </p>
+
+      <div className="flex items-center gap-3 ml-6">
+        <label className="text-sm font-medium text-discord-text">Port</label>
+        <input
+          type="number"
+          min={1}
+          max={65535}
+          value={settings.api_port}
+          onChange={(e) => onUpdateSetting("api_port", Number(e.target.value))}
+          className="w-28 bg-discord-darker text-discord-text rounded px-3 py-2 text-sm"
+        />
+        {status?.running_port != null && (
+          <span className="text-xs text-discord-success">
+            Running on port {status.running_port}
+          </span>
+        )}
+      </div>
+
+      <div className="space-y-2">
+        <h3 className="text-sm font-medium text-discord-text">Access tokens</h3>
+        <p className="text-xs text-discord-text-muted">
+          Clients must send a token as{" "}
+          <code className="bg-discord-darker px-1 rounded">
+            Authorization: Bearer &lt;token&gt;
+          </code>{" "}
+          and may only do what the token allows. The event streams (
+          <code className="bg-discord-darker px-1 rounded">/events</code>,{" "}
+          <code className="bg-discord-darker px-1 rounded">/spectate</code>)
+          also accept{" "}
+          <code className="bg-discord-darker px-1 rounded">?token=</code>,
+          since browser event sources can't send headers.
+        </p>
+
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.api_open_local_access}
+            onChange={(e) => applyOpenLocalAccess(e.target.checked)}
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span className="text-sm text-discord-text">
+            Allow local programs without a token while no token exists
+          </span>
+        </label>
+
+        {settings.api_tokens.map((token) => (
+          <div
+            key={token.id}
+            className="bg-discord-darker rounded p-3 flex items-start gap-3"
+          >
+            <div className="flex-1 min-w-0">
+              <div className="text-sm text-discord-text">{token.name}</div>
+              <div className="text-xs text-discord-text-muted">
+                {token.capabilities
+                  .map(
+                    (c) => CAPABILITIES.find((o) => o.value === c)?.label ?? c
+                  )
+                  .join(", ")}
+              </div>
+              <code className="text-xs text-discord-text-muted break-all select-all">
+                {token.secret}
+              </code>
+            </div>
+            <button
+              onClick={() => revokeToken(token.id)}
+              className="text-xs text-discord-danger hover:underline"
+            >
+              Revoke
+            </button>
+          </div>
+        ))}
+
+        <div className="flex flex-wrap items-center gap-3">
+          <input
+            type="text"
+            value={tokenName}
+            onChange={(e) => setTokenName(e.target.value)}
+            placeholder="Client name, e.g. Stream Deck"
+            className="flex-1 bg-discord-darker text-discord-text rounded px-3 py-2 text-sm"
+          />
+          <button
+            onClick={createToken}
+            disabled={!tokenName.trim() || tokenCapabilities.length === 0}
+            className="px-3 py-2 bg-discord-primary hover:bg-discord-primary/80
+                     disabled:opacity-50 rounded text-sm text-white"
+          >
+            Create token
+          </button>
+        </div>
+        <div className="flex flex-wrap gap-4">
+          {CAPABILITIES.map(({ value, label }) => (
+            <label
+              key={value}
+              className="flex items-center gap-2 text-xs text-discord-text cursor-pointer"
+            >
+              <input
+                type="checkbox"
+                checked={tokenCapabilities.includes(value)}
+                onChange={(e) => toggleCapability(value, e.target.checked)}
+                className="rounded border-discord-dark bg-discord-darker
+                         text-discord-primary focus:ring-discord-primary"
+              />
+              {label}
+            </label>
+          ))}
+        </div>
+      </div>
+
+      <div className="space-y-2">
+        <div className="flex items-center justify-between">
+          <h3 className="text-sm font-medium text-discord-text">
+            Recent requests
+          </h3>
+          <button
+            onClick={refreshAuditLog}
+            className="text-xs text-discord-primary hover:underline"
+          >
+            Refresh
+          </button>
+        </div>
+        {auditLog.length === 0 ? (
+          <p className="text-xs text-discord-text-muted">No requests yet.</p>
+        ) : (
+          <ul className="text-xs space-y-1 max-h-40 overflow-y-auto">
+            {auditLog.slice(0, 50).map((entry, index) => (
+              <li
+                key={`${entry.timestamp_ms}-${index}`}
+                className={
+                  entry.allowed ? "text-discord-text" : "text-discord-danger"
+                }
+              >
+                {new Date(entry.timestamp_ms).toLocaleTimeString()}{" "}
+                {entry.request} - {entry.token_name ?? "no token"}
+                {entry.denial && ` (${entry.denial.replace(/_/g, " ")})`}
+              </li>
+            ))}
+          </ul>
+        )}
+      </div>
+
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
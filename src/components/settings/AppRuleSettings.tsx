@@ -0,0 +1,163 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { AppRule, AppSettings, ProfileList } from "../../types";
+import { useSoundLibrary } from "../../contexts/SoundLibraryContext";
+import { formatError } from "../../utils/errors";
+
+interface AppRuleSettingsProps {
+  settings: AppSettings;
+  onUpdateSetting: (key: keyof AppSettings, value: boolean | AppRule[]) => void;
+}
+
+const profileLabel = (name: string) => (name === "default" ? "Default" : name);
+
+export default function AppRuleSettings({
+  settings,
+  onUpdateSetting,
+}: AppRuleSettingsProps) {
+  const { soundLibrary } = useSoundLibrary();
+  const [profiles, setProfiles] = useState<string[]>([]);
+  const [processName, setProcessName] = useState<string>("");
+  const [profile, setProfile] = useState<string>("");
+  const [categoryId, setCategoryId] = useState<string>("");
+  const [error, setError] = useState<string>("");
+
+  const rules = settings.app_rules ?? [];
+
+  useEffect(() => {
+    invoke<ProfileList>("list_profiles")
+      .then((list) => setProfiles(list.profiles))
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  const categoryName = (id: string) =>
+    soundLibrary.categories.find((c) => c.id === id)?.name ?? "Other category";
+
+  const handleAddRule = () => {
+    const name = processName.trim();
+    if (!name) return;
+    onUpdateSetting("app_rules", [
+      ...rules.filter(
+        (rule) => rule.process_name.toLowerCase() !== name.toLowerCase()
+      ),
+      {
+        process_name: name,
+        profile: profile || null,
+        category_id: categoryId || null,
+      },
+    ]);
+    setProcessName("");
+    setProfile("");
+    setCategoryId("");
+  };
+
+  const describe = (rule: AppRule) =>
+    [
+      rule.profile && `profile ${profileLabel(rule.profile)}`,
+      rule.category_id && `category ${categoryName(rule.category_id)}`,
+    ]
+      .filter(Boolean)
+      .join(", ") || "nothing";
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">
+        Application Rules
+      </h2>
+      <label className="flex items-center gap-2 text-sm text-discord-text">
+        <input
+          type="checkbox"
+          checked={settings.app_rules_enabled ?? false}
+          onChange={(e) =>
+            onUpdateSetting("app_rules_enabled", e.target.checked)
+          }
+          className="w-4 h-4 rounded"
+        />
+        Switch profile and category with the focused application
+      </label>
+      <p className="text-xs text-discord-text-muted">
+        While a listed application is in the foreground, its profile (with
+        that profile's hotkeys) and category are used. When you leave it, the
+        previous profile comes back. Windows only.
+      </p>
+
+      {rules.length > 0 && (
+        <div className="space-y-2">
+          {rules.map((rule) => (
+            <div
+              key={rule.process_name}
+              className="bg-discord-darker rounded px-4 py-2 flex items-center justify-between gap-3"
+            >
+              <div className="min-w-0">
+                <p className="text-sm text-discord-text truncate">
+                  {rule.process_name}
+                </p>
+                <p className="text-xs text-discord-text-muted">
+                  → {describe(rule)}
+                </p>
+              </div>
+              <button
+                onClick={() =>
+                  onUpdateSetting("app_rules", rules.filter((r) => r !== rule))
+                }
+                className="px-2 py-1 bg-discord-dark hover:bg-discord-danger rounded
+                         text-xs text-discord-text transition-colors"
+              >
+                Remove
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="flex flex-wrap items-center gap-2">
+        <input
+          type="text"
+          value={processName}
+          onChange={(e) => setProcessName(e.target.value)}
+          placeholder="obs64.exe"
+          className="flex-1 bg-discord-darker border border-discord-dark rounded px-3 py-2
+                   text-sm text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+        />
+        <select
+          value={profile}
+          onChange={(e) => setProfile(e.target.value)}
+          className="bg-discord-darker border border-discord-dark rounded px-3 py-2
+                   text-sm text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+        >
+          <option value="">Keep profile</option>
+          {profiles.map((name) => (
+            <option key={name} value={name}>
+              {profileLabel(name)}
+            </option>
+          ))}
+        </select>
+        <select
+          value={categoryId}
+          onChange={(e) => setCategoryId(e.target.value)}
+          className="bg-discord-darker border border-discord-dark rounded px-3 py-2
+                   text-sm text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+        >
+          <option value="">Keep category</option>
+          {soundLibrary.categories.map((category) => (
+            <option key={category.id} value={category.id}>
+              {category.name}
+            </option>
+          ))}
+        </select>
+        <button
+          onClick={handleAddRule}
+          disabled={!processName.trim() || (!profile && !categoryId)}
+          className="px-3 py-2 bg-discord-primary hover:bg-discord-primary/80 rounded
+                   text-sm text-white transition-colors disabled:opacity-50"
+        >
+          Add Rule
+        </button>
+      </div>
+      <p className="text-xs text-discord-text-muted">
+        Categories are those of the current profile's library.
+      </p>
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
@@ -1,11 +1,18 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { AudioDevice, AppSettings } from "../../types";
+import { SYSTEM_DEFAULT_DEVICE_ID } from "../../constants";
+import { formatError } from "../../utils/errors";
 
 interface AudioDeviceSettingsProps {
   settings: AppSettings;
   devices: AudioDevice[];
   isRefreshing: boolean;
   onRefresh: () => void;
-  onUpdateSetting: (key: keyof AppSettings, value: string | null) => void;
+  onUpdateSetting: (
+    key: keyof AppSettings,
+    value: string | boolean | null
+  ) => void;
   isDeviceAvailable: (deviceId: string) => boolean;
 }
 
@@ -17,6 +24,34 @@ export default function AudioDeviceSettings({
   onUpdateSetting,
   isDeviceAvailable,
 }: AudioDeviceSettingsProps) {
+  const [testingDeviceId, setTestingDeviceId] = useState<string | null>(null);
+  const [testError, setTestError] = useState<string>("");
+
+  // Beep on one device only, to check routing without a sound on both outputs
+  const playTestTone = async (deviceId: string) => {
+    setTestError("");
+    setTestingDeviceId(deviceId);
+    try {
+      await invoke("play_test_tone", { deviceId });
+    } catch (err) {
+      setTestError(formatError(err));
+    } finally {
+      setTestingDeviceId(null);
+    }
+  };
+
+  const testButton = (deviceId: string) => (
+    <button
+      onClick={() => playTestTone(deviceId)}
+      disabled={testingDeviceId !== null}
+      className="ml-2 px-2 py-0.5 text-xs bg-discord-darker hover:bg-discord-primary
+               disabled:opacity-50 disabled:cursor-not-allowed rounded text-discord-text
+               transition-colors"
+    >
+      {testingDeviceId === deviceId ? "Playing..." : "Test"}
+    </button>
+  );
+
   return (
     <div className="bg-discord-dark rounded-lg p-6 space-y-4">
       <div className="flex items-center justify-between mb-4">
@@ -52,6 +87,9 @@ export default function AudioDeviceSettings({
                      text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
           >
             <option value="">Not configured</option>
+            <option value={SYSTEM_DEFAULT_DEVICE_ID}>
+              Follow system default
+            </option>
             {devices.map((device) => (
               <option key={device.id} value={device.id}>
                 {device.name} {device.is_default ? "(Default)" : ""}
@@ -68,6 +106,7 @@ export default function AudioDeviceSettings({
             isDeviceAvailable(settings.monitor_device_id) && (
               <p className="text-xs text-discord-success mt-1">
                 ✓ Device online
+                {testButton(settings.monitor_device_id)}
               </p>
             )}
         </div>
@@ -105,22 +144,61 @@ export default function AudioDeviceSettings({
             isDeviceAvailable(settings.broadcast_device_id) && (
               <p className="text-xs text-discord-success mt-1">
                 ✓ Device online
+                {testButton(settings.broadcast_device_id)}
               </p>
             )}
         </div>
       </div>
 
+      {testError && (
+        <p className="text-xs text-discord-danger">
+          Test tone failed: {testError}
+        </p>
+      )}
+
       {/* Warning if both devices are the same */}
       {settings.monitor_device_id &&
         settings.broadcast_device_id &&
         settings.monitor_device_id === settings.broadcast_device_id && (
-          <div className="bg-discord-warning/20 border border-discord-warning rounded p-3">
+          <div className="bg-discord-warning/20 border border-discord-warning rounded p-3 space-y-2">
             <p className="text-sm text-discord-warning">
               ⚠️ Warning: Both outputs are set to the same device. For
               dual-output routing, select different devices.
             </p>
+            <label className="flex items-center gap-2 text-sm text-discord-text">
+              On the same device:
+              <select
+                value={settings.identical_device_behavior}
+                onChange={(e) =>
+                  onUpdateSetting("identical_device_behavior", e.target.value)
+                }
+                className="bg-discord-darker border border-discord-dark rounded px-2 py-1
+                         text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+              >
+                <option value="single_stream">Play once</option>
+                <option value="both_streams">
+                  Play on both outputs (doubled)
+                </option>
+              </select>
+            </label>
           </div>
         )}
+
+      {/* Fallback to the system default device */}
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={settings.device_fallback_enabled}
+          onChange={(e) =>
+            onUpdateSetting("device_fallback_enabled", e.target.checked)
+          }
+          className="rounded border-discord-dark bg-discord-darker
+                   text-discord-primary focus:ring-discord-primary cursor-pointer"
+        />
+        <span className="text-sm text-discord-text">
+          Use the system default device while a selected device is missing
+        </span>
+      </label>
     </div>
   );
 }
@@ -0,0 +1,99 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { BackupEntry } from "../../types";
+import { formatError } from "../../utils/errors";
+
+const FILE_LABELS: Record<string, string> = {
+  sounds: "Sound library",
+  hotkeys: "Hotkeys",
+  settings: "Settings",
+};
+
+export default function BackupSettings() {
+  const [backups, setBackups] = useState<BackupEntry[]>([]);
+  const [restoring, setRestoring] = useState<string | null>(null);
+  const [message, setMessage] = useState<string>("");
+
+  const loadBackups = useCallback(async () => {
+    try {
+      setBackups(await invoke<BackupEntry[]>("list_backups"));
+    } catch (error) {
+      setMessage(formatError(error));
+    }
+  }, []);
+
+  useEffect(() => {
+    loadBackups();
+  }, [loadBackups]);
+
+  const handleRestore = async (backup: BackupEntry) => {
+    const label = FILE_LABELS[backup.source] ?? backup.source;
+    const when = new Date(backup.created_ms).toLocaleString();
+    const question = `Replace the current ${label.toLowerCase()} with the backup from ${when}?`;
+    if (!confirm(question)) return;
+    setRestoring(backup.file_name);
+    setMessage("");
+    try {
+      await invoke("restore_backup", { fileName: backup.file_name });
+      setMessage(`${label} restored from ${when}`);
+    } catch (error) {
+      setMessage(formatError(error));
+    } finally {
+      setRestoring(null);
+      // The replaced version was backed up as well
+      loadBackups();
+    }
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <div className="flex items-center justify-between">
+        <h2 className="text-xl font-semibold text-discord-text">Backups</h2>
+        <button
+          onClick={loadBackups}
+          className="px-3 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                   text-xs text-discord-text transition-colors"
+        >
+          Refresh
+        </button>
+      </div>
+      <p className="text-xs text-discord-text-muted">
+        The sound library, hotkeys and settings are backed up before they're
+        saved (at most every 10 minutes, last 10 versions of each). Restoring
+        keeps a backup of the version it replaces.
+      </p>
+
+      {backups.length === 0 ? (
+        <p className="text-sm text-discord-text-muted">No backups yet</p>
+      ) : (
+        <div className="space-y-2 max-h-64 overflow-auto">
+          {backups.map((backup) => (
+            <div
+              key={backup.file_name}
+              className="bg-discord-darker rounded px-4 py-2 flex items-center justify-between gap-3"
+            >
+              <div className="min-w-0">
+                <p className="text-sm text-discord-text">
+                  {FILE_LABELS[backup.source] ?? backup.source}
+                </p>
+                <p className="text-xs text-discord-text-muted">
+                  {new Date(backup.created_ms).toLocaleString()} ·{" "}
+                  {(backup.size_bytes / 1024).toFixed(1)} KB
+                </p>
+              </div>
+              <button
+                onClick={() => handleRestore(backup)}
+                disabled={restoring !== null}
+                className="px-2 py-1 bg-discord-dark hover:bg-discord-primary rounded
+                         text-xs text-discord-text transition-colors disabled:opacity-50"
+              >
+                {restoring === backup.file_name ? "Restoring..." : "Restore"}
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+      {message && <p className="text-xs text-discord-text-muted">{message}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,135 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { AppSettings, DiscordConnection, DiscordStatus } from "../../types";
+import { formatError } from "../../utils/errors";
+
+interface DiscordSettingsProps {
+  settings: AppSettings;
+  onUpdateSetting: (
+    key: keyof AppSettings,
+    value: string | number | boolean | null
+  ) => void;
+}
+
+const CONNECTION_LABELS: Record<DiscordConnection, string> = {
+  disabled: "Disabled",
+  connecting: "Connecting to Discord...",
+  authorizing: "Approve the request in Discord",
+  connected: "Connected",
+  unavailable: "Discord not available (retrying)",
+};
+
+export default function DiscordSettings({
+  settings,
+  onUpdateSetting,
+}: DiscordSettingsProps) {
+  const [status, setStatus] = useState<DiscordStatus | null>(null);
+  const [error, setError] = useState<string>("");
+
+  useEffect(() => {
+    invoke<DiscordStatus>("get_discord_status")
+      .then(setStatus)
+      .catch((err) => console.error("Failed to get Discord status:", err));
+
+    const unlisten = listen<DiscordStatus>("discord-voice-state", (event) => {
+      setStatus(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const applyMuteAwareness = async (enabled: boolean) => {
+    setError("");
+    try {
+      await invoke<AppSettings>("set_discord_mute_awareness", {
+        enabled,
+        clientId: settings.discord_client_id,
+        clientSecret: settings.discord_client_secret,
+      });
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">
+        Discord Mute Awareness
+      </h2>
+
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={settings.discord_mute_awareness_enabled}
+          onChange={(e) => applyMuteAwareness(e.target.checked)}
+          className="rounded border-discord-dark bg-discord-darker
+                   text-discord-primary focus:ring-discord-primary cursor-pointer"
+        />
+        <span className="text-sm text-discord-text">
+          Pause broadcast output while muted or deafened in Discord
+        </span>
+      </label>
+      <p className="text-xs text-discord-text-muted ml-6">
+        Sounds keep playing on your monitor device only. Requires a Discord
+        application (discord.com/developers) with{" "}
+        <code className="bg-discord-darker px-1 rounded">http://localhost</code>{" "}
+        as redirect URI; Discord asks you to approve access once.
+      </p>
+
+      <div className="grid grid-cols-2 gap-3">
+        <div>
+          <label className="block text-sm font-medium text-discord-text mb-2">
+            Client ID
+          </label>
+          <input
+            type="text"
+            value={settings.discord_client_id ?? ""}
+            onChange={(e) =>
+              onUpdateSetting("discord_client_id", e.target.value || null)
+            }
+            className="w-full bg-discord-darker text-discord-text rounded px-3 py-2 text-sm"
+          />
+        </div>
+        <div>
+          <label className="block text-sm font-medium text-discord-text mb-2">
+            Client Secret
+          </label>
+          <input
+            type="password"
+            value={settings.discord_client_secret ?? ""}
+            onChange={(e) =>
+              onUpdateSetting("discord_client_secret", e.target.value || null)
+            }
+            className="w-full bg-discord-darker text-discord-text rounded px-3 py-2 text-sm"
+          />
+        </div>
+      </div>
+
+      {status && status.connection !== "disabled" && (
+        <div className="text-sm text-discord-text-muted">
+          Status: {CONNECTION_LABELS[status.connection]}
+          {status.connection === "connected" && (
+            <span
+              className={
+                status.broadcast_suppressed
+                  ? "ml-2 text-discord-warning"
+                  : "ml-2 text-discord-success"
+              }
+            >
+              {status.deafened
+                ? "Deafened - broadcast paused"
+                : status.muted
+                  ? "Muted - broadcast paused"
+                  : "Broadcasting"}
+            </span>
+          )}
+        </div>
+      )}
+
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
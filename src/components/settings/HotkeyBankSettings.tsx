@@ -0,0 +1,55 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { HotkeyBank } from "../../types";
+import { useHotkeyMappings } from "../../hooks/useHotkeyMappings";
+import { formatError } from "../../utils/errors";
+
+// Modifier layers offered for the bank (canonical backend format)
+const BANK_MODIFIERS = ["Ctrl+Alt", "Ctrl+Shift", "Shift+Alt", "Ctrl+Shift+Alt"];
+
+export default function HotkeyBankSettings() {
+  const { hotkeyMappings, refreshHotkeys } = useHotkeyMappings();
+  const [bankError, setBankError] = useState<string>("");
+
+  const bankModifiers = hotkeyMappings.bank?.modifiers ?? "";
+
+  const handleChangeBank = async (modifiers: string) => {
+    setBankError("");
+    try {
+      await invoke<HotkeyBank | null>("set_hotkey_bank", {
+        modifiers: modifiers || null,
+      });
+    } catch (error) {
+      setBankError(formatError(error));
+    } finally {
+      await refreshHotkeys();
+    }
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">
+        Hotkey Bank
+      </h2>
+
+      <select
+        value={bankModifiers}
+        onChange={(e) => handleChangeBank(e.target.value)}
+        className="w-full bg-discord-darker border border-discord-dark rounded px-3 py-2
+                 text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+      >
+        <option value="">Disabled</option>
+        {BANK_MODIFIERS.map((modifiers) => (
+          <option key={modifiers} value={modifiers}>
+            {modifiers} + NumPad 1-9
+          </option>
+        ))}
+      </select>
+      {bankError && <p className="text-xs text-discord-danger">{bankError}</p>}
+      <p className="text-xs text-discord-text-muted">
+        The modifier plus NumPad 1-9 plays the first nine sounds of the
+        category selected in the dashboard, no individual bindings needed.
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,114 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { NoiseReduction } from "../../types";
+import { formatError } from "../../utils/errors";
+import { useSettings } from "../../contexts/SettingsContext";
+
+const DEFAULT_NOISE_REDUCTION: NoiseReduction = {
+  gate_enabled: false,
+  gate_threshold_db: -50,
+  suppression_enabled: false,
+  suppression_db: 20,
+};
+
+export default function NoiseReductionSettings() {
+  const { settings } = useSettings();
+  const saved = settings?.noise_reduction ?? DEFAULT_NOISE_REDUCTION;
+  const [draft, setDraft] = useState<NoiseReduction>(saved);
+  const [error, setError] = useState<string | null>(null);
+
+  // Keep the sliders in sync with saved settings
+  useEffect(() => {
+    setDraft(saved);
+  }, [
+    saved.gate_enabled,
+    saved.gate_threshold_db,
+    saved.suppression_enabled,
+    saved.suppression_db,
+  ]);
+
+  const apply = async (noiseReduction: NoiseReduction) => {
+    setDraft(noiseReduction);
+    setError(null);
+    try {
+      await invoke("set_noise_reduction", { noiseReduction });
+    } catch (e) {
+      setError(`Failed to update noise reduction: ${formatError(e)}`);
+    }
+  };
+
+  const commit = () => apply(draft);
+
+  return (
+    <div className="mt-3 space-y-2">
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={draft.suppression_enabled}
+          onChange={(e) =>
+            apply({ ...draft, suppression_enabled: e.target.checked })
+          }
+          className="rounded border-discord-dark bg-discord-darker
+                   text-discord-primary focus:ring-discord-primary cursor-pointer"
+        />
+        <span className="text-sm text-discord-text">
+          Suppress background noise
+        </span>
+      </label>
+
+      {draft.suppression_enabled && (
+        <label className="block text-xs text-discord-text-muted">
+          Lower noise by: {draft.suppression_db} dB
+          <input
+            type="range"
+            min="0"
+            max="40"
+            step="1"
+            value={draft.suppression_db}
+            onChange={(e) =>
+              setDraft({ ...draft, suppression_db: parseFloat(e.target.value) })
+            }
+            onPointerUp={commit}
+            onKeyUp={commit}
+            className="w-full"
+          />
+        </label>
+      )}
+
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={draft.gate_enabled}
+          onChange={(e) => apply({ ...draft, gate_enabled: e.target.checked })}
+          className="rounded border-discord-dark bg-discord-darker
+                   text-discord-primary focus:ring-discord-primary cursor-pointer"
+        />
+        <span className="text-sm text-discord-text">Noise gate</span>
+      </label>
+
+      {draft.gate_enabled && (
+        <label className="block text-xs text-discord-text-muted">
+          Threshold: {draft.gate_threshold_db} dB
+          <input
+            type="range"
+            min="-90"
+            max="0"
+            step="1"
+            value={draft.gate_threshold_db}
+            onChange={(e) =>
+              setDraft({
+                ...draft,
+                gate_threshold_db: parseFloat(e.target.value),
+              })
+            }
+            onPointerUp={commit}
+            onKeyUp={commit}
+            className="w-full"
+          />
+        </label>
+      )}
+
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
@@ -1,4 +1,17 @@
-import { AppSettings } from "../../types";
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import {
+  AppSettings,
+  LibraryMigration,
+  LoudnessComplete,
+  LoudnessProgress,
+} from "../../types";
+import { useSoundLibrary } from "../../contexts/SoundLibraryContext";
+import { useHotkeyMappings } from "../../hooks/useHotkeyMappings";
+import { formatError } from "../../utils/errors";
+import { captureHotkey } from "../../utils/hotkeyCapture";
+import { formatHotkeyForDisplay } from "../../utils/hotkeyDisplay";
 
 interface PlaybackSettingsProps {
   settings: AppSettings;
@@ -12,12 +25,208 @@ export default function PlaybackSettings({
   settings,
   onUpdateSetting,
 }: PlaybackSettingsProps) {
+  const { hotkeyMappings, refreshHotkeys } = useHotkeyMappings();
+  const [isCapturing, setIsCapturing] = useState(false);
+  const [hotkeyError, setHotkeyError] = useState<string>("");
+  const [analyzerError, setAnalyzerError] = useState<string>("");
+  const [masterVolumeError, setMasterVolumeError] = useState<string>("");
+  const [loudnessStatus, setLoudnessStatus] = useState<string>("");
+  const [isAnalyzingLoudness, setIsAnalyzingLoudness] = useState(false);
+  const { refreshSounds } = useSoundLibrary();
+  const [migrationStatus, setMigrationStatus] = useState<string>("");
+  const [isMigrating, setIsMigrating] = useState(false);
+
+  useEffect(() => {
+    const unlistenProgress = listen<LoudnessProgress>(
+      "loudness-analysis-progress",
+      (event) => {
+        setLoudnessStatus(
+          `Measuring ${event.payload.done} of ${event.payload.total}...`
+        );
+      }
+    );
+    const unlistenComplete = listen<LoudnessComplete>(
+      "loudness-analysis-complete",
+      (event) => {
+        setIsAnalyzingLoudness(false);
+        const { measured, failed } = event.payload;
+        setLoudnessStatus(
+          failed > 0
+            ? `Measured ${measured} sounds, ${failed} could not be decoded`
+            : `Measured ${measured} sounds`
+        );
+      }
+    );
+    return () => {
+      unlistenProgress.then((fn) => fn());
+      unlistenComplete.then((fn) => fn());
+    };
+  }, []);
+
+  const handleAnalyzeLoudness = async () => {
+    setIsAnalyzingLoudness(true);
+    setLoudnessStatus("");
+    try {
+      const total = await invoke<number>("analyze_library_loudness");
+      if (total === 0) {
+        setLoudnessStatus("All sounds are measured already");
+      }
+    } catch (error) {
+      setIsAnalyzingLoudness(false);
+      setLoudnessStatus(formatError(error));
+    }
+  };
+
+  const handleMigrateLibrary = async () => {
+    setIsMigrating(true);
+    setMigrationStatus("");
+    try {
+      const result = await invoke<LibraryMigration>(
+        "migrate_sounds_to_library"
+      );
+      await refreshSounds();
+      const problems = result.missing.length + result.failed.length;
+      setMigrationStatus(
+        problems > 0
+          ? `Copied ${result.copied} sounds, ${problems} could not be copied`
+          : `Copied ${result.copied} sounds`
+      );
+    } catch (error) {
+      setMigrationStatus(formatError(error));
+    } finally {
+      setIsMigrating(false);
+    }
+  };
+
+  const normalizationHotkey = Object.entries(hotkeyMappings.actions ?? {}).find(
+    ([_, action]) => action === "toggle_normalization"
+  )?.[0];
+
+  const handleSetNormalizationHotkey = async () => {
+    setIsCapturing(true);
+    setHotkeyError("");
+    try {
+      const hotkey = await captureHotkey();
+      if (normalizationHotkey) {
+        await invoke("unregister_hotkey", { hotkey: normalizationHotkey });
+      }
+      await invoke("register_action_hotkey", {
+        hotkey,
+        action: "toggle_normalization",
+      });
+    } catch (error) {
+      setHotkeyError(formatError(error));
+    } finally {
+      setIsCapturing(false);
+      await refreshHotkeys();
+    }
+  };
+
+  const handleToggleSpectrumAnalyzer = async (enabled: boolean) => {
+    setAnalyzerError("");
+    try {
+      await invoke<AppSettings>("set_spectrum_analyzer", { enabled });
+      onUpdateSetting("spectrum_analyzer_enabled", enabled);
+    } catch (error) {
+      setAnalyzerError(formatError(error));
+    }
+  };
+
+  // Applied right away, also to sounds that are playing
+  const handleMasterVolumeChange = async (volume: number) => {
+    setMasterVolumeError("");
+    onUpdateSetting("master_volume", volume);
+    try {
+      await invoke("set_master_volume", { volume });
+    } catch (error) {
+      setMasterVolumeError(formatError(error));
+    }
+  };
+
+  const handleClearNormalizationHotkey = async () => {
+    if (!normalizationHotkey) return;
+    try {
+      await invoke("unregister_hotkey", { hotkey: normalizationHotkey });
+    } catch (error) {
+      setHotkeyError(formatError(error));
+    } finally {
+      await refreshHotkeys();
+    }
+  };
+
   return (
     <div className="bg-discord-dark rounded-lg p-6 space-y-4">
       <h2 className="text-xl font-semibold text-discord-text mb-4">
         Playback Preferences
       </h2>
 
+      {/* Master Volume */}
+      <div>
+        <label className="block text-sm font-medium text-discord-text mb-2">
+          Master Volume: {Math.round(settings.master_volume * 100)}%
+        </label>
+        <input
+          type="range"
+          min="0"
+          max="1"
+          step="0.01"
+          value={settings.master_volume}
+          onChange={(e) =>
+            handleMasterVolumeChange(parseFloat(e.target.value))
+          }
+          className="w-full"
+        />
+        <p className="text-xs text-discord-text-muted mt-1">
+          Overall level of all sounds. Changes apply immediately, even to
+          sounds that are playing.
+        </p>
+        {masterVolumeError && (
+          <p className="text-xs text-discord-danger mt-1">
+            {masterVolumeError}
+          </p>
+        )}
+      </div>
+
+      {/* Output Volumes */}
+      <div className="grid grid-cols-2 gap-4">
+        <div>
+          <label className="block text-sm font-medium text-discord-text mb-2">
+            Monitor Volume: {Math.round(settings.monitor_volume * 100)}%
+          </label>
+          <input
+            type="range"
+            min="0"
+            max="1"
+            step="0.01"
+            value={settings.monitor_volume}
+            onChange={(e) =>
+              onUpdateSetting("monitor_volume", parseFloat(e.target.value))
+            }
+            className="w-full"
+          />
+        </div>
+        <div>
+          <label className="block text-sm font-medium text-discord-text mb-2">
+            Broadcast Volume: {Math.round(settings.broadcast_volume * 100)}%
+          </label>
+          <input
+            type="range"
+            min="0"
+            max="1"
+            step="0.01"
+            value={settings.broadcast_volume}
+            onChange={(e) =>
+              onUpdateSetting("broadcast_volume", parseFloat(e.target.value))
+            }
+            className="w-full"
+          />
+        </div>
+        <p className="col-span-2 text-xs text-discord-text-muted">
+          Level of each output relative to the sound's volume, e.g. to
+          keep your own monitoring quieter than what the stream hears.
+        </p>
+      </div>
+
       {/* Default Volume */}
       <div>
         <label className="block text-sm font-medium text-discord-text mb-2">
@@ -122,6 +331,302 @@ export default function PlaybackSettings({
           </p>
         )}
       </div>
+
+      {/* Loudness Normalization */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.normalization_enabled}
+            onChange={(e) =>
+              onUpdateSetting("normalization_enabled", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Loudness Normalization</span>
+          {settings.normalization_enabled && (
+            <span className="ml-1 font-bold">
+              ({settings.normalization_target_lufs} LUFS)
+            </span>
+          )}
+        </label>
+
+        {settings.normalization_enabled && (
+          <div className="relative mt-2">
+            <input
+              type="range"
+              min="-30"
+              max="-10"
+              step="1"
+              value={settings.normalization_target_lufs}
+              onChange={(e) =>
+                onUpdateSetting(
+                  "normalization_target_lufs",
+                  parseFloat(e.target.value)
+                )
+              }
+              className="w-full"
+              style={{
+                accentColor: "#5865f2",
+              }}
+            />
+          </div>
+        )}
+        <p className="text-xs text-discord-text-muted mt-1">
+          Plays every sound at the same perceived loudness. Lower targets are
+          quieter; -16 LUFS suits most voice chats.
+        </p>
+
+        {/* Library analysis */}
+        <div className="flex items-center gap-2 mt-3 text-sm text-discord-text">
+          <button
+            onClick={handleAnalyzeLoudness}
+            disabled={isAnalyzingLoudness}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                     text-xs transition-colors disabled:opacity-50"
+          >
+            Analyze Library
+          </button>
+          <span className="text-xs text-discord-text-muted">
+            {loudnessStatus ||
+              "Measures new sounds in the background, so they play normalized right away"}
+          </span>
+        </div>
+
+        {/* Toggle hotkey */}
+        <div className="flex items-center gap-2 mt-3 text-sm text-discord-text">
+          <span>Toggle hotkey:</span>
+          <span className="font-mono text-discord-text-muted">
+            {isCapturing
+              ? "Press a key combination..."
+              : normalizationHotkey
+                ? formatHotkeyForDisplay(normalizationHotkey)
+                : "None"}
+          </span>
+          <button
+            onClick={handleSetNormalizationHotkey}
+            disabled={isCapturing}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                     text-xs transition-colors disabled:opacity-50"
+          >
+            Set
+          </button>
+          {normalizationHotkey && !isCapturing && (
+            <button
+              onClick={handleClearNormalizationHotkey}
+              className="px-2 py-1 bg-discord-darker hover:bg-discord-danger rounded
+                       text-xs transition-colors"
+            >
+              Clear
+            </button>
+          )}
+        </div>
+        {hotkeyError && (
+          <p className="text-xs text-discord-danger mt-1">{hotkeyError}</p>
+        )}
+      </div>
+
+      {/* Queue Mode */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.queue_mode_enabled}
+            onChange={(e) =>
+              onUpdateSetting("queue_mode_enabled", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Queue Mode</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Sounds triggered while another one plays wait their turn instead of
+          playing over it.
+        </p>
+      </div>
+
+      {/* Restart Crossfade */}
+      <div>
+        <label className="block text-sm font-medium text-discord-text mb-2">
+          Restart Crossfade: {settings.restart_crossfade_ms} ms
+        </label>
+        <input
+          type="range"
+          min="0"
+          max="500"
+          step="10"
+          value={settings.restart_crossfade_ms}
+          onChange={(e) =>
+            onUpdateSetting("restart_crossfade_ms", parseInt(e.target.value))
+          }
+          className="w-full"
+        />
+        <p className="text-xs text-discord-text-muted mt-1">
+          A restarted sound fades in while its previous playback fades out,
+          instead of cutting off with a click. 0 cuts right away.
+        </p>
+      </div>
+
+      {/* Playback Limit */}
+      <div>
+        <label className="block text-sm font-medium text-discord-text mb-2">
+          Max Simultaneous Sounds
+        </label>
+        <input
+          type="number"
+          min={1}
+          max={32}
+          value={settings.max_concurrent_sounds}
+          onChange={(e) =>
+            onUpdateSetting("max_concurrent_sounds", Number(e.target.value))
+          }
+          className="w-20 bg-discord-darker text-discord-text rounded px-3 py-2 text-sm"
+        />
+        <p className="text-xs text-discord-text-muted mt-1">
+          When more sounds would play at once, the oldest one stops to keep the
+          mix clean.
+        </p>
+      </div>
+
+      {/* High-Quality Resampling */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.high_quality_resampling}
+            onChange={(e) =>
+              onUpdateSetting("high_quality_resampling", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>High-Quality Resampling</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Converts sounds to the device's sample rate with a sharper filter
+          (e.g. 44.1 kHz files on 48 kHz devices). Sounds take a little longer
+          to start.
+        </p>
+      </div>
+
+      {/* Output Limiter */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.output_limiter_enabled}
+            onChange={(e) =>
+              onUpdateSetting("output_limiter_enabled", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Output Limiter</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Keeps peaks of boosted or normalized sounds below -1 dBFS instead of
+          letting them clip, on both outputs.
+        </p>
+      </div>
+
+      {/* Smaller Output Buffers */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.small_output_buffers}
+            onChange={(e) =>
+              onUpdateSetting("small_output_buffers", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Smaller Output Buffers</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Opens outputs with the smallest buffers the device accepts, so sounds
+          start a little sooner. Turn it off if playback crackles. Outputs stay
+          in Windows shared mode, which still adds its own ~10 ms period.
+        </p>
+      </div>
+
+      {/* Resample Cache */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.resample_cache_enabled}
+            onChange={(e) =>
+              onUpdateSetting("resample_cache_enabled", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Cache Resampled Sounds</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Keeps sounds converted to your devices' sample rates, so repeated
+          plays need no conversion. Uses more memory.
+        </p>
+      </div>
+
+      {/* Spectrum Analyzer */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.spectrum_analyzer_enabled}
+            onChange={(e) => handleToggleSpectrumAnalyzer(e.target.checked)}
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Spectrum Visualizer</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Shows the frequency spectrum of what you hear in the dashboard
+          header. Uses a little extra CPU while sounds play.
+        </p>
+        {analyzerError && (
+          <p className="text-xs text-discord-danger mt-1">{analyzerError}</p>
+        )}
+      </div>
+
+      {/* Library Storage */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium text-discord-text mb-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.copy_sounds_to_library}
+            onChange={(e) =>
+              onUpdateSetting("copy_sounds_to_library", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span>Copy Sounds into Library</span>
+        </label>
+        <p className="text-xs text-discord-text-muted mt-1">
+          Added sounds are copied into the app's data folder, so they keep
+          working when the original file is moved or deleted.
+        </p>
+
+        <div className="flex items-center gap-2 mt-3 text-sm text-discord-text">
+          <button
+            onClick={handleMigrateLibrary}
+            disabled={isMigrating}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                     text-xs transition-colors disabled:opacity-50"
+          >
+            Copy Existing Sounds
+          </button>
+          <span className="text-xs text-discord-text-muted">
+            {migrationStatus ||
+              "Copies the files of sounds already in the library; originals stay"}
+          </span>
+        </div>
+      </div>
     </div>
   );
 }
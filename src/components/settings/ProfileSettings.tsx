@@ -0,0 +1,143 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { ProfileList } from "../../types";
+import { formatError } from "../../utils/errors";
+
+const DEFAULT_PROFILE = "default";
+
+const profileLabel = (name: string) =>
+  name === DEFAULT_PROFILE ? "Default" : name;
+
+export default function ProfileSettings() {
+  const [profileList, setProfileList] = useState<ProfileList | null>(null);
+  const [newName, setNewName] = useState<string>("");
+  const [copyCurrent, setCopyCurrent] = useState<boolean>(false);
+  const [error, setError] = useState<string>("");
+
+  const loadProfiles = useCallback(async () => {
+    try {
+      setProfileList(await invoke<ProfileList>("list_profiles"));
+    } catch (err) {
+      setError(formatError(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    loadProfiles();
+    // Profiles can also be switched from the tray
+    const unlisten = listen<ProfileList>("profile-changed", (event) => {
+      setProfileList(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadProfiles]);
+
+  const run = async (command: string, args: Record<string, unknown>) => {
+    setError("");
+    try {
+      setProfileList(await invoke<ProfileList>(command, args));
+      return true;
+    } catch (err) {
+      setError(formatError(err));
+      return false;
+    }
+  };
+
+  const handleCreate = async () => {
+    if (await run("create_profile", { name: newName, copyCurrent })) {
+      setNewName("");
+    }
+  };
+
+  const handleDelete = async (name: string) => {
+    if (!confirm(`Delete profile "${name}" with its sounds and hotkeys?`)) {
+      return;
+    }
+    await run("delete_profile", { name });
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">Profiles</h2>
+      <p className="text-xs text-discord-text-muted">
+        Each profile has its own sound library, hotkeys and output devices.
+        Profiles can also be switched from the tray menu.
+      </p>
+
+      {profileList && (
+        <div className="space-y-2">
+          {profileList.profiles.map((name) => {
+            const isActive = name === profileList.active;
+            return (
+              <div
+                key={name}
+                className="bg-discord-darker rounded px-4 py-2 flex items-center justify-between gap-3"
+              >
+                <span className="text-sm text-discord-text truncate">
+                  {profileLabel(name)}
+                  {isActive && (
+                    <span className="ml-2 text-xs text-discord-success">
+                      active
+                    </span>
+                  )}
+                </span>
+                <div className="flex gap-2">
+                  {!isActive && (
+                    <button
+                      onClick={() => run("switch_profile", { name })}
+                      className="px-2 py-1 bg-discord-dark hover:bg-discord-primary rounded
+                               text-xs text-discord-text transition-colors"
+                    >
+                      Switch
+                    </button>
+                  )}
+                  {!isActive && name !== DEFAULT_PROFILE && (
+                    <button
+                      onClick={() => handleDelete(name)}
+                      className="px-2 py-1 bg-discord-dark hover:bg-discord-danger rounded
+                               text-xs text-discord-text transition-colors"
+                    >
+                      Delete
+                    </button>
+                  )}
+                </div>
+              </div>
+            );
+          })}
+        </div>
+      )}
+
+      <div className="flex items-center gap-2">
+        <input
+          type="text"
+          value={newName}
+          onChange={(e) => setNewName(e.target.value)}
+          placeholder="New profile name"
+          maxLength={40}
+          className="flex-1 bg-discord-darker border border-discord-dark rounded px-3 py-2
+                   text-sm text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+        />
+        <button
+          onClick={handleCreate}
+          disabled={!newName.trim()}
+          className="px-3 py-2 bg-discord-primary hover:bg-discord-primary/80 rounded
+                   text-sm text-white transition-colors disabled:opacity-50"
+        >
+          Create
+        </button>
+      </div>
+      <label className="flex items-center gap-2 text-sm text-discord-text">
+        <input
+          type="checkbox"
+          checked={copyCurrent}
+          onChange={(e) => setCopyCurrent(e.target.checked)}
+          className="w-4 h-4 rounded"
+        />
+        Start with a copy of the current sounds and hotkeys
+      </label>
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
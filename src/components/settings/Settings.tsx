@@ -1,17 +1,29 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { AppSettings } from "../../types";
+import { AppRule, AppSettings, StartupAction } from "../../types";
+import { SYSTEM_DEFAULT_DEVICE_ID } from "../../constants";
+import { formatError } from "../../utils/errors";
 import { useAudio } from "../../contexts/AudioContext";
 import { useSettings as useSettingsContext } from "../../contexts/SettingsContext";
+import { useSoundLibrary } from "../../contexts/SoundLibraryContext";
 import AudioDeviceSettings from "./AudioDeviceSettings";
 import PlaybackSettings from "./PlaybackSettings";
+import ProfileSettings from "./ProfileSettings";
+import HotkeyBankSettings from "./HotkeyBankSettings";
+import ActionHotkeySettings from "./ActionHotkeySettings";
+import DiscordSettings from "./DiscordSettings";
+import ApiSettings from "./ApiSettings";
 import SystemTraySettings from "./SystemTraySettings";
 import VbCableSettings from "./VbCableSettings";
+import WatchedFolderSettings from "./WatchedFolderSettings";
+import AppRuleSettings from "./AppRuleSettings";
+import BackupSettings from "./BackupSettings";
 import SettingsAbout from "./SettingsAbout";
 
 export default function Settings() {
   // Contexts
   const { devices, refreshDevices } = useAudio();
+  const { refreshSounds } = useSoundLibrary();
   const {
     settings: contextSettings,
     saveSettings: saveSettingsToContext,
@@ -29,6 +41,31 @@ export default function Settings() {
     autostart_enabled: false,
     microphone_routing_device_id: null,
     microphone_routing_enabled: false,
+    microphone_monitor_enabled: false,
+    microphone_cable_gain: 1.0,
+    microphone_monitor_gain: 1.0,
+    normalization_enabled: false,
+    spectrum_analyzer_enabled: false,
+    normalization_target_lufs: -16,
+    discord_mute_awareness_enabled: false,
+    discord_client_id: null,
+    discord_client_secret: null,
+    api_enabled: false,
+    api_port: 7393,
+    device_fallback_enabled: true,
+    setup_progress: { completed: [], skipped: [] },
+    language: "en",
+    autostart_profile: null,
+    autostart_minimized: false,
+    startup_actions: [],
+    api_tokens: [],
+    api_open_local_access: false,
+    queue_mode_enabled: false,
+    high_quality_resampling: false,
+    resample_cache_enabled: false,
+    master_volume: 1.0,
+    monitor_volume: 1.0,
+    broadcast_volume: 1.0,
   });
   const [isRefreshing, setIsRefreshing] = useState<boolean>(false);
   const [isSaving, setIsSaving] = useState<boolean>(false);
@@ -59,10 +96,12 @@ export default function Settings() {
     setIsRefreshing(true);
     try {
       await refreshDevices();
+      // Per-sound device overrides may have followed a shifted device
+      await refreshSounds();
       setStatus("Devices refreshed successfully!");
     } catch (error) {
       console.error("Failed to refresh devices:", error);
-      setStatus(`Error: ${error}`);
+      setStatus(`Error: ${formatError(error)}`);
     } finally {
       setIsRefreshing(false);
     }
@@ -75,7 +114,7 @@ export default function Settings() {
       setStatus("Settings saved successfully!");
     } catch (error) {
       console.error("Failed to save settings:", error);
-      setStatus(`Error: ${error}`);
+      setStatus(`Error: ${formatError(error)}`);
     } finally {
       setIsSaving(false);
     }
@@ -88,19 +127,22 @@ export default function Settings() {
       setStatus("Settings reset to defaults!");
     } catch (error) {
       console.error("Failed to reset settings:", error);
-      setStatus(`Error: ${error}`);
+      setStatus(`Error: ${formatError(error)}`);
     }
   };
 
   const updateSetting = (
     key: keyof AppSettings,
-    value: string | number | boolean | null
+    value: string | number | boolean | null | StartupAction[] | AppRule[]
   ) => {
     setSettings((prev) => ({ ...prev, [key]: value }));
   };
 
   const isDeviceAvailable = (deviceId: string): boolean => {
-    return devices.some((d) => d.id === deviceId);
+    return (
+      deviceId === SYSTEM_DEFAULT_DEVICE_ID ||
+      devices.some((d) => d.id === deviceId)
+    );
   };
 
   const handleToggleAutostart = async () => {
@@ -113,7 +155,7 @@ export default function Settings() {
         await invoke("disable_autostart");
       }
     } catch (err) {
-      setStatus(`Error: ${err}`);
+      setStatus(`Error: ${formatError(err)}`);
     }
   };
 
@@ -164,6 +206,33 @@ export default function Settings() {
             onUpdateSetting={updateSetting}
           />
 
+          {/* Profiles */}
+          <ProfileSettings />
+
+          {/* Per-application rules */}
+          <AppRuleSettings
+            settings={settings}
+            onUpdateSetting={updateSetting}
+          />
+
+          {/* Watched Folders */}
+          <WatchedFolderSettings settings={settings} />
+
+          {/* Hotkey Bank */}
+          <HotkeyBankSettings />
+
+          {/* Action Hotkeys */}
+          <ActionHotkeySettings />
+
+          {/* Discord Mute Awareness */}
+          <DiscordSettings
+            settings={settings}
+            onUpdateSetting={updateSetting}
+          />
+
+          {/* Local API */}
+          <ApiSettings settings={settings} onUpdateSetting={updateSetting} />
+
           {/* System Tray & Startup */}
           <SystemTraySettings
             settings={settings}
@@ -171,6 +240,9 @@ export default function Settings() {
             onToggleAutostart={handleToggleAutostart}
           />
 
+          {/* Backups */}
+          <BackupSettings />
+
           {/* Available Devices List */}
           <div className="bg-discord-dark rounded-lg p-6">
             <h3 className="text-lg font-semibold text-discord-text mb-3">
@@ -196,7 +268,10 @@ export default function Settings() {
                       )}
                     </div>
                     <div className="flex gap-2">
-                      {settings.monitor_device_id === device.id && (
+                      {(settings.monitor_device_id === device.id ||
+                        (settings.monitor_device_id ===
+                          SYSTEM_DEFAULT_DEVICE_ID &&
+                          device.is_default)) && (
                         <span className="px-2 py-0.5 bg-blue-600 rounded text-xs text-white">
                           MONITOR
                         </span>
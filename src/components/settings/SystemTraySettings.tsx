@@ -1,10 +1,16 @@
-import { AppSettings } from "../../types";
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { AppSettings, StartupAction } from "../../types";
+import { useHotkeyMappings } from "../../hooks/useHotkeyMappings";
+import { formatError } from "../../utils/errors";
+import { captureHotkey } from "../../utils/hotkeyCapture";
+import { formatHotkeyForDisplay } from "../../utils/hotkeyDisplay";
 
 interface SystemTraySettingsProps {
   settings: AppSettings;
   onUpdateSetting: (
     key: keyof AppSettings,
-    value: string | number | boolean | null
+    value: string | number | boolean | null | StartupAction[]
   ) => void;
   onToggleAutostart: () => Promise<void>;
 }
@@ -14,6 +20,74 @@ export default function SystemTraySettings({
   onUpdateSetting,
   onToggleAutostart,
 }: SystemTraySettingsProps) {
+  const { hotkeyMappings, refreshHotkeys } = useHotkeyMappings();
+  const [isCapturing, setIsCapturing] = useState(false);
+  const [hotkeyError, setHotkeyError] = useState<string>("");
+  const [newActionProfile, setNewActionProfile] = useState<string>("");
+
+  const hasMicRoutingAction = settings.startup_actions.some(
+    (action) => action.type === "enable_mic_routing"
+  );
+
+  const setStartupActions = (actions: StartupAction[]) => {
+    onUpdateSetting("startup_actions", actions);
+  };
+
+  const handleToggleMicRoutingAction = (enabled: boolean) => {
+    setStartupActions(
+      enabled
+        ? [...settings.startup_actions, { type: "enable_mic_routing" }]
+        : settings.startup_actions.filter(
+            (action) => action.type !== "enable_mic_routing"
+          )
+    );
+  };
+
+  const handleAddProfileAction = () => {
+    const profile = newActionProfile.trim();
+    if (!profile) return;
+    setStartupActions([
+      ...settings.startup_actions,
+      { type: "apply_profile_devices", profile },
+    ]);
+    setNewActionProfile("");
+  };
+
+  const quickSearchHotkey = Object.entries(hotkeyMappings.actions ?? {}).find(
+    ([_, action]) => action === "open_quick_search"
+  )?.[0];
+
+  const handleSetQuickSearchHotkey = async () => {
+    setIsCapturing(true);
+    setHotkeyError("");
+    try {
+      const hotkey = await captureHotkey();
+      if (quickSearchHotkey) {
+        await invoke("unregister_hotkey", { hotkey: quickSearchHotkey });
+      }
+      await invoke("register_action_hotkey", {
+        hotkey,
+        action: "open_quick_search",
+      });
+    } catch (error) {
+      setHotkeyError(formatError(error));
+    } finally {
+      setIsCapturing(false);
+      await refreshHotkeys();
+    }
+  };
+
+  const handleClearQuickSearchHotkey = async () => {
+    if (!quickSearchHotkey) return;
+    try {
+      await invoke("unregister_hotkey", { hotkey: quickSearchHotkey });
+    } catch (error) {
+      setHotkeyError(formatError(error));
+    } finally {
+      await refreshHotkeys();
+    }
+  };
+
   return (
     <>
       {/* System Tray Preferences */}
@@ -60,6 +134,48 @@ export default function SystemTraySettings({
         </p>
       </div>
 
+      {/* Quick Search */}
+      <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+        <h2 className="text-xl font-semibold text-discord-text mb-4">
+          Quick Search
+        </h2>
+
+        <div className="flex items-center gap-2 text-sm text-discord-text">
+          <span>Open hotkey:</span>
+          <span className="font-mono text-discord-text-muted">
+            {isCapturing
+              ? "Press a key combination..."
+              : quickSearchHotkey
+                ? formatHotkeyForDisplay(quickSearchHotkey)
+                : "None"}
+          </span>
+          <button
+            onClick={handleSetQuickSearchHotkey}
+            disabled={isCapturing}
+            className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                     text-xs transition-colors disabled:opacity-50"
+          >
+            Set
+          </button>
+          {quickSearchHotkey && !isCapturing && (
+            <button
+              onClick={handleClearQuickSearchHotkey}
+              className="px-2 py-1 bg-discord-darker hover:bg-discord-danger rounded
+                       text-xs transition-colors"
+            >
+              Clear
+            </button>
+          )}
+        </div>
+        {hotkeyError && (
+          <p className="text-xs text-discord-danger">{hotkeyError}</p>
+        )}
+        <p className="text-xs text-discord-text-muted">
+          Opens a small search popup near the tray (also in the tray menu).
+          Type a sound name and press Enter to play it.
+        </p>
+      </div>
+
       {/* Startup Behavior */}
       <div className="bg-discord-dark rounded-lg p-6 space-y-4">
         <h2 className="text-xl font-semibold text-discord-text mb-4">
@@ -81,6 +197,123 @@ export default function SystemTraySettings({
         <p className="text-xs text-discord-text-muted ml-6">
           Automatically start SonicDeck when you log into Windows.
         </p>
+
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={settings.autostart_minimized}
+            onChange={(e) =>
+              onUpdateSetting("autostart_minimized", e.target.checked)
+            }
+            className="rounded border-discord-dark bg-discord-darker
+                     text-discord-primary focus:ring-discord-primary cursor-pointer"
+          />
+          <span className="text-sm text-discord-text">
+            Start minimized when launched on system startup
+          </span>
+        </label>
+
+        <div className="space-y-1">
+          <label className="block text-sm text-discord-text">
+            Autostart profile
+          </label>
+          <input
+            type="text"
+            value={settings.autostart_profile ?? ""}
+            onChange={(e) =>
+              onUpdateSetting("autostart_profile", e.target.value || null)
+            }
+            placeholder="default"
+            className="w-full bg-discord-darker border border-discord-dark rounded px-3 py-2
+                     text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+          />
+          <p className="text-xs text-discord-text-muted">
+            Autostart launches use the output devices last used with this
+            profile. Takes effect from the next start.
+          </p>
+        </div>
+
+        <div className="space-y-2">
+          <h3 className="text-sm font-semibold text-discord-text">
+            Startup actions
+          </h3>
+          <label className="flex items-center gap-2 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={hasMicRoutingAction}
+              onChange={(e) => handleToggleMicRoutingAction(e.target.checked)}
+              className="rounded border-discord-dark bg-discord-darker
+                       text-discord-primary focus:ring-discord-primary cursor-pointer"
+            />
+            <span className="text-sm text-discord-text">
+              Enable microphone routing
+            </span>
+          </label>
+
+          {settings.startup_actions.map((action, index) =>
+            action.type === "apply_profile_devices" ? (
+              <div
+                key={index}
+                className="flex items-center justify-between text-sm text-discord-text"
+              >
+                <span>Apply devices of profile "{action.profile}"</span>
+                <button
+                  onClick={() =>
+                    setStartupActions(
+                      settings.startup_actions.filter((_, i) => i !== index)
+                    )
+                  }
+                  className="px-2 py-1 bg-discord-darker hover:bg-discord-danger rounded
+                           text-xs transition-colors"
+                >
+                  Remove
+                </button>
+              </div>
+            ) : null
+          )}
+
+          <div className="flex gap-2">
+            <input
+              type="text"
+              value={newActionProfile}
+              onChange={(e) => setNewActionProfile(e.target.value)}
+              placeholder="Profile name"
+              className="flex-1 bg-discord-darker border border-discord-dark rounded px-3 py-1
+                       text-sm text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+            />
+            <button
+              onClick={handleAddProfileAction}
+              disabled={!newActionProfile.trim()}
+              className="px-2 py-1 bg-discord-darker hover:bg-discord-primary rounded
+                       text-xs transition-colors disabled:opacity-50"
+            >
+              Add device preset
+            </button>
+          </div>
+          <p className="text-xs text-discord-text-muted">
+            Run once every time SonicDeck starts, in the order listed.
+          </p>
+        </div>
+      </div>
+
+      {/* Language */}
+      <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+        <h2 className="text-xl font-semibold text-discord-text mb-4">
+          Language
+        </h2>
+
+        <select
+          value={settings.language}
+          onChange={(e) => onUpdateSetting("language", e.target.value)}
+          className="w-full bg-discord-darker border border-discord-dark rounded px-3 py-2
+                   text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+        >
+          <option value="en">English</option>
+          <option value="de">Deutsch</option>
+        </select>
+        <p className="text-xs text-discord-text-muted">
+          Used for the tray menu, error messages and notifications.
+        </p>
       </div>
     </>
   );
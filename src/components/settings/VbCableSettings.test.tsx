@@ -17,6 +17,9 @@ vi.mock("../../contexts/SettingsContext", () => ({
     settings: {
       microphone_routing_device_id: null,
       microphone_routing_enabled: false,
+      microphone_monitor_enabled: false,
+      microphone_cable_gain: 1.0,
+      microphone_monitor_gain: 1.0,
       broadcast_device_id: null,
     },
     saveSettings: mockSaveSettings,
@@ -223,6 +226,26 @@ describe("VbCableSettings", () => {
         });
       });
     });
+
+    it("calls set_microphone_monitoring when mic monitoring toggled", async () => {
+      render(<VbCableSettings />);
+
+      await waitFor(() => {
+        expect(
+          screen.getByText("Also play my microphone on the monitor device")
+        ).toBeInTheDocument();
+      });
+
+      fireEvent.click(screen.getByRole("checkbox"));
+
+      await waitFor(() => {
+        expect(invoke).toHaveBeenCalledWith("set_microphone_monitoring", {
+          enabled: true,
+          cableGain: 1.0,
+          monitorGain: 1.0,
+        });
+      });
+    });
   });
 
   describe("open sound settings", () => {
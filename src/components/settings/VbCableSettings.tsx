@@ -2,12 +2,17 @@ import { useState, useEffect, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import {
   AudioDevice,
+  MicRoutingStats,
   RestoreResult,
   SavedDefaults,
   VbCableStatus,
+  VbCableUpdateInfo,
 } from "../../types";
+import { formatError } from "../../utils/errors";
 import { useSettings } from "../../contexts/SettingsContext";
 import { useAudio } from "../../contexts/AudioContext";
+import NoiseReductionSettings from "./NoiseReductionSettings";
+import VoiceChangerSettings from "./VoiceChangerSettings";
 
 interface VbCableSettingsProps {
   onDeviceChange?: () => void;
@@ -21,12 +26,23 @@ export default function VbCableSettings({
   const [isUninstalling, setIsUninstalling] = useState(false);
   const [installStep, setInstallStep] = useState<string>("");
   const [error, setError] = useState<string | null>(null);
+  const [updateInfo, setUpdateInfo] = useState<VbCableUpdateInfo | null>(
+    null
+  );
 
   // Microphone routing state
   const [microphones, setMicrophones] = useState<[string, string][]>([]);
   const [selectedMicrophone, setSelectedMicrophone] = useState<string>("");
   const [isRoutingActive, setIsRoutingActive] = useState(false);
   const [isRoutingLoading, setIsRoutingLoading] = useState(false);
+  const [cableGain, setCableGain] = useState(1.0);
+  const [monitorGain, setMonitorGain] = useState(1.0);
+  const [duckDb, setDuckDb] = useState(12);
+  const [duckAttackMs, setDuckAttackMs] = useState(20);
+  const [duckReleaseMs, setDuckReleaseMs] = useState(400);
+  const [routingStats, setRoutingStats] = useState<MicRoutingStats | null>(
+    null
+  );
 
   // Guard against race conditions from rapid clicks (state updates are async)
   const operationInProgress = useRef(false);
@@ -69,6 +85,87 @@ export default function VbCableSettings({
     checkStatus();
   }, []);
 
+  // Follow the routing buffer while routing is active
+  useEffect(() => {
+    if (!isRoutingActive) {
+      setRoutingStats(null);
+      return;
+    }
+    const poll = () =>
+      invoke<MicRoutingStats | null>("get_mic_routing_stats")
+        .then(setRoutingStats)
+        .catch((e) =>
+          console.error("Failed to get routing stats:", formatError(e))
+        );
+    poll();
+    const interval = setInterval(poll, 2000);
+    return () => clearInterval(interval);
+  }, [isRoutingActive]);
+
+  // Keep the gain sliders in sync with saved settings
+  useEffect(() => {
+    if (settings) {
+      setCableGain(settings.microphone_cable_gain);
+      setMonitorGain(settings.microphone_monitor_gain);
+    }
+  }, [settings?.microphone_cable_gain, settings?.microphone_monitor_gain]);
+
+  // Keep the ducking sliders in sync with saved settings
+  useEffect(() => {
+    if (settings) {
+      setDuckDb(settings.microphone_duck_db);
+      setDuckAttackMs(settings.microphone_duck_attack_ms);
+      setDuckReleaseMs(settings.microphone_duck_release_ms);
+    }
+  }, [
+    settings?.microphone_duck_db,
+    settings?.microphone_duck_attack_ms,
+    settings?.microphone_duck_release_ms,
+  ]);
+
+  const applyMicDucking = async (enabled: boolean) => {
+    setError(null);
+    try {
+      await invoke("set_microphone_ducking", {
+        enabled,
+        depthDb: duckDb,
+        attackMs: duckAttackMs,
+        releaseMs: duckReleaseMs,
+      });
+    } catch (e) {
+      setError(`Failed to update mic ducking: ${formatError(e)}`);
+    }
+  };
+
+  const commitDucking = () => {
+    applyMicDucking(settings?.microphone_duck_enabled ?? false);
+  };
+
+  const applyMicMonitoring = async (
+    enabled: boolean,
+    nextCableGain: number,
+    nextMonitorGain: number
+  ) => {
+    setError(null);
+    try {
+      await invoke("set_microphone_monitoring", {
+        enabled,
+        cableGain: nextCableGain,
+        monitorGain: nextMonitorGain,
+      });
+    } catch (e) {
+      setError(`Failed to update mic monitoring: ${formatError(e)}`);
+    }
+  };
+
+  const commitGains = () => {
+    applyMicMonitoring(
+      settings?.microphone_monitor_enabled ?? false,
+      cableGain,
+      monitorGain
+    );
+  };
+
   // Load microphone data when VB-Cable is installed
   useEffect(() => {
     if (status?.status === "installed") {
@@ -76,13 +173,24 @@ export default function VbCableSettings({
     }
   }, [status, loadMicrophoneData]);
 
+  // Check for an outdated driver when VB-Cable is installed
+  useEffect(() => {
+    if (status?.status !== "installed") {
+      setUpdateInfo(null);
+      return;
+    }
+    invoke<VbCableUpdateInfo>("check_vb_cable_update")
+      .then(setUpdateInfo)
+      .catch((e) => console.error("Failed to check VB-Cable version:", e));
+  }, [status]);
+
   const checkStatus = async () => {
     try {
       const result = await invoke<VbCableStatus>("check_vb_cable_status");
       setStatus(result);
       setError(null);
     } catch (e) {
-      setError(`Status check failed: ${e}`);
+      setError(`Status check failed: ${formatError(e)}`);
     }
   };
 
@@ -112,7 +220,7 @@ export default function VbCableSettings({
         });
       }
     } catch (e) {
-      setError(`Microphone routing failed: ${e}`);
+      setError(`Microphone routing failed: ${formatError(e)}`);
     } finally {
       setIsRoutingLoading(false);
     }
@@ -138,7 +246,7 @@ export default function VbCableSettings({
         });
       }
     } catch (e) {
-      setError(`Failed to disable microphone routing: ${e}`);
+      setError(`Failed to disable microphone routing: ${formatError(e)}`);
     } finally {
       setIsRoutingLoading(false);
     }
@@ -236,7 +344,7 @@ export default function VbCableSettings({
 
       setInstallStep("");
     } catch (e) {
-      setError(`Installation failed: ${e}`);
+      setError(`Installation failed: ${formatError(e)}`);
       setInstallStep("");
     } finally {
       operationInProgress.current = false;
@@ -248,7 +356,7 @@ export default function VbCableSettings({
     try {
       await invoke("open_vb_audio_website");
     } catch (e) {
-      setError(`Could not open website: ${e}`);
+      setError(`Could not open website: ${formatError(e)}`);
     }
   };
 
@@ -294,7 +402,7 @@ export default function VbCableSettings({
 
       setInstallStep("");
     } catch (e) {
-      setError(`Uninstallation failed: ${e}`);
+      setError(`Uninstallation failed: ${formatError(e)}`);
       setInstallStep("");
     } finally {
       operationInProgress.current = false;
@@ -327,9 +435,31 @@ export default function VbCableSettings({
             </div>
             <p className="text-sm text-discord-text-muted">
               Device: {status.info.output_device}
+              {updateInfo?.installed_version &&
+                ` (driver ${updateInfo.installed_version})`}
             </p>
           </div>
 
+          {updateInfo?.update_available && (
+            <div className="p-3 bg-discord-darker rounded space-y-2">
+              <p className="text-sm text-discord-warning">
+                Your VB-Cable driver ({updateInfo.installed_version}) is
+                outdated. Older drivers can cause crackling or dropouts.
+              </p>
+              <button
+                onClick={handleInstall}
+                disabled={isInstalling}
+                className="px-4 py-2 bg-discord-primary hover:bg-discord-primary-hover rounded
+                         text-white text-sm font-medium transition-colors
+                         disabled:opacity-50 disabled:cursor-not-allowed"
+              >
+                {isInstalling
+                  ? installStep || "Updating..."
+                  : `Update to ${updateInfo.bundled_version}`}
+              </button>
+            </div>
+          )}
+
           {/* Microphone Routing Section */}
           <div className="pt-4 border-t border-discord-darker">
             <h4 className="text-sm font-medium text-discord-text mb-2">
@@ -383,9 +513,165 @@ export default function VbCableSettings({
             {isRoutingActive && (
               <p className="mt-2 text-xs text-discord-success">
                 Microphone is being routed to CABLE Input
+                {settings?.microphone_monitor_enabled && " and your monitor"}
+              </p>
+            )}
+            {routingStats && (
+              <p className="mt-1 text-xs text-discord-text-muted">
+                Buffer: {routingStats.buffer_ms} ms · Underruns:{" "}
+                {routingStats.underruns} · Overflows: {routingStats.overflows}
               </p>
             )}
 
+            {/* Mic Monitoring */}
+            <div className="mt-3 space-y-2">
+              <label className="flex items-center gap-2 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={settings?.microphone_monitor_enabled ?? false}
+                  onChange={(e) =>
+                    applyMicMonitoring(e.target.checked, cableGain, monitorGain)
+                  }
+                  className="rounded border-discord-dark bg-discord-darker
+                           text-discord-primary focus:ring-discord-primary cursor-pointer"
+                />
+                <span className="text-sm text-discord-text">
+                  Also play my microphone on the monitor device
+                </span>
+              </label>
+
+              <label className="block text-xs text-discord-text-muted">
+                CABLE Input gain: {Math.round(cableGain * 100)}%
+                <input
+                  type="range"
+                  min="0"
+                  max="2"
+                  step="0.05"
+                  value={cableGain}
+                  onChange={(e) => setCableGain(parseFloat(e.target.value))}
+                  onPointerUp={commitGains}
+                  onKeyUp={commitGains}
+                  className="w-full"
+                />
+              </label>
+
+              {settings?.microphone_monitor_enabled && (
+                <label className="block text-xs text-discord-text-muted">
+                  Monitor gain: {Math.round(monitorGain * 100)}%
+                  <input
+                    type="range"
+                    min="0"
+                    max="2"
+                    step="0.05"
+                    value={monitorGain}
+                    onChange={(e) => setMonitorGain(parseFloat(e.target.value))}
+                    onPointerUp={commitGains}
+                    onKeyUp={commitGains}
+                    className="w-full"
+                  />
+                </label>
+              )}
+            </div>
+
+            {/* Mic Ducking */}
+            <div className="mt-3 space-y-2">
+              <label className="flex items-center gap-2 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={settings?.microphone_duck_enabled ?? false}
+                  onChange={(e) => applyMicDucking(e.target.checked)}
+                  className="rounded border-discord-dark bg-discord-darker
+                           text-discord-primary focus:ring-discord-primary cursor-pointer"
+                />
+                <span className="text-sm text-discord-text">
+                  Lower my microphone while sounds play
+                </span>
+              </label>
+
+              {settings?.microphone_duck_enabled && (
+                <>
+                  <label className="block text-xs text-discord-text-muted">
+                    Lower by: {duckDb} dB
+                    <input
+                      type="range"
+                      min="0"
+                      max="60"
+                      step="1"
+                      value={duckDb}
+                      onChange={(e) => setDuckDb(parseFloat(e.target.value))}
+                      onPointerUp={commitDucking}
+                      onKeyUp={commitDucking}
+                      className="w-full"
+                    />
+                  </label>
+                  <label className="block text-xs text-discord-text-muted">
+                    Attack: {duckAttackMs} ms
+                    <input
+                      type="range"
+                      min="0"
+                      max="500"
+                      step="5"
+                      value={duckAttackMs}
+                      onChange={(e) =>
+                        setDuckAttackMs(parseInt(e.target.value))
+                      }
+                      onPointerUp={commitDucking}
+                      onKeyUp={commitDucking}
+                      className="w-full"
+                    />
+                  </label>
+                  <label className="block text-xs text-discord-text-muted">
+                    Release: {duckReleaseMs} ms
+                    <input
+                      type="range"
+                      min="0"
+                      max="5000"
+                      step="50"
+                      value={duckReleaseMs}
+                      onChange={(e) =>
+                        setDuckReleaseMs(parseInt(e.target.value))
+                      }
+                      onPointerUp={commitDucking}
+                      onKeyUp={commitDucking}
+                      className="w-full"
+                    />
+                  </label>
+                </>
+              )}
+            </div>
+
+            {/* Soundboard Mix */}
+            <div className="mt-3">
+              <label className="flex items-center gap-2 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={settings?.soundboard_mic_mix_enabled ?? false}
+                  onChange={(e) =>
+                    settings &&
+                    saveSettings({
+                      ...settings,
+                      soundboard_mic_mix_enabled: e.target.checked,
+                    })
+                  }
+                  className="rounded border-discord-dark bg-discord-darker
+                           text-discord-primary focus:ring-discord-primary cursor-pointer"
+                />
+                <span className="text-sm text-discord-text">
+                  Mix sounds into my microphone
+                </span>
+              </label>
+              <p className="text-xs text-discord-text-muted mt-1 ml-6">
+                Sounds go straight into the routed microphone instead of the
+                broadcast device, so CABLE Input doesn't need to be selected.
+              </p>
+            </div>
+
+            {/* Noise Reduction */}
+            <NoiseReductionSettings />
+
+            {/* Voice Changer */}
+            <VoiceChangerSettings />
+
             {/* Communication Apps Setup Tip */}
             <div className="mt-3 p-3 bg-discord-darker rounded text-xs text-discord-text-muted">
               <p className="font-medium text-discord-text mb-1">
@@ -0,0 +1,140 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { VoiceChanger } from "../../types";
+import { formatError } from "../../utils/errors";
+import { useSettings } from "../../contexts/SettingsContext";
+
+const DEFAULT_VOICE_CHANGER: VoiceChanger = {
+  enabled: false,
+  pitch_semitones: 0,
+  robot: false,
+  echo_delay_ms: 0,
+  echo_feedback: 0,
+};
+
+export default function VoiceChangerSettings() {
+  const { settings } = useSettings();
+  const saved = settings?.voice_changer ?? DEFAULT_VOICE_CHANGER;
+  const [draft, setDraft] = useState<VoiceChanger>(saved);
+  const [error, setError] = useState<string | null>(null);
+
+  // Keep the sliders in sync with saved settings
+  useEffect(() => {
+    setDraft(saved);
+  }, [
+    saved.enabled,
+    saved.pitch_semitones,
+    saved.robot,
+    saved.echo_delay_ms,
+    saved.echo_feedback,
+  ]);
+
+  const apply = async (voiceChanger: VoiceChanger) => {
+    setDraft(voiceChanger);
+    setError(null);
+    try {
+      await invoke("set_voice_changer", { voiceChanger });
+    } catch (e) {
+      setError(`Failed to update voice changer: ${formatError(e)}`);
+    }
+  };
+
+  const commit = () => apply(draft);
+
+  return (
+    <div className="mt-3 space-y-2">
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={draft.enabled}
+          onChange={(e) => apply({ ...draft, enabled: e.target.checked })}
+          className="rounded border-discord-dark bg-discord-darker
+                   text-discord-primary focus:ring-discord-primary cursor-pointer"
+        />
+        <span className="text-sm text-discord-text">Voice changer</span>
+      </label>
+
+      {draft.enabled && (
+        <>
+          <label className="block text-xs text-discord-text-muted">
+            Pitch: {draft.pitch_semitones > 0 ? "+" : ""}
+            {draft.pitch_semitones} semitones
+            <input
+              type="range"
+              min="-12"
+              max="12"
+              step="1"
+              value={draft.pitch_semitones}
+              onChange={(e) =>
+                setDraft({
+                  ...draft,
+                  pitch_semitones: parseFloat(e.target.value),
+                })
+              }
+              onPointerUp={commit}
+              onKeyUp={commit}
+              className="w-full"
+            />
+          </label>
+
+          <label className="flex items-center gap-2 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={draft.robot}
+              onChange={(e) => apply({ ...draft, robot: e.target.checked })}
+              className="rounded border-discord-dark bg-discord-darker
+                       text-discord-primary focus:ring-discord-primary cursor-pointer"
+            />
+            <span className="text-xs text-discord-text">Robot voice</span>
+          </label>
+
+          <label className="block text-xs text-discord-text-muted">
+            Echo delay:{" "}
+            {draft.echo_delay_ms > 0 ? `${draft.echo_delay_ms} ms` : "Off"}
+            <input
+              type="range"
+              min="0"
+              max="1000"
+              step="10"
+              value={draft.echo_delay_ms}
+              onChange={(e) =>
+                setDraft({ ...draft, echo_delay_ms: parseInt(e.target.value) })
+              }
+              onPointerUp={commit}
+              onKeyUp={commit}
+              className="w-full"
+            />
+          </label>
+
+          {draft.echo_delay_ms > 0 && (
+            <label className="block text-xs text-discord-text-muted">
+              Echo feedback: {Math.round(draft.echo_feedback * 100)}%
+              <input
+                type="range"
+                min="0"
+                max="0.9"
+                step="0.05"
+                value={draft.echo_feedback}
+                onChange={(e) =>
+                  setDraft({
+                    ...draft,
+                    echo_feedback: parseFloat(e.target.value),
+                  })
+                }
+                onPointerUp={commit}
+                onKeyUp={commit}
+                className="w-full"
+              />
+            </label>
+          )}
+
+          <p className="text-xs text-discord-text-muted">
+            Applies to the routed microphone; changes are heard right away.
+          </p>
+        </>
+      )}
+
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,112 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { AppSettings, WatchedFolder } from "../../types";
+import { useSoundLibrary } from "../../contexts/SoundLibraryContext";
+import { formatError } from "../../utils/errors";
+
+interface WatchedFolderSettingsProps {
+  settings: AppSettings;
+}
+
+export default function WatchedFolderSettings({
+  settings,
+}: WatchedFolderSettingsProps) {
+  const { soundLibrary, refreshSounds } = useSoundLibrary();
+  const [categoryId, setCategoryId] = useState<string>("");
+  const [error, setError] = useState<string>("");
+
+  const folders = settings.watched_folders ?? [];
+  const selectedCategoryId = categoryId || soundLibrary.categories[0]?.id || "";
+
+  const categoryName = (id: string) =>
+    soundLibrary.categories.find((c) => c.id === id)?.name ?? "Deleted category";
+
+  const handleAddFolder = async () => {
+    setError("");
+    try {
+      const selected = await open({ directory: true, multiple: false });
+      if (!selected || Array.isArray(selected)) return;
+      await invoke<WatchedFolder[]>("add_watched_folder", {
+        path: selected,
+        categoryId: selectedCategoryId,
+      });
+      // Files already in the folder are added in the background
+      await refreshSounds();
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const handleRemoveFolder = async (path: string) => {
+    setError("");
+    try {
+      await invoke<WatchedFolder[]>("remove_watched_folder", { path });
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className="bg-discord-dark rounded-lg p-6 space-y-4">
+      <h2 className="text-xl font-semibold text-discord-text mb-4">
+        Watched Folders
+      </h2>
+      <p className="text-xs text-discord-text-muted">
+        Audio files added to a watched folder appear in the library on their
+        own; deleting a file removes its sound.
+      </p>
+
+      {folders.length > 0 && (
+        <div className="space-y-2">
+          {folders.map((folder) => (
+            <div
+              key={folder.path}
+              className="bg-discord-darker rounded px-4 py-3 flex items-center justify-between gap-3"
+            >
+              <div className="min-w-0">
+                <p className="text-sm text-discord-text truncate">
+                  {folder.path}
+                </p>
+                <p className="text-xs text-discord-text-muted">
+                  → {categoryName(folder.category_id)}
+                </p>
+              </div>
+              <button
+                onClick={() => handleRemoveFolder(folder.path)}
+                className="px-2 py-1 bg-discord-dark hover:bg-discord-danger rounded
+                         text-xs text-discord-text transition-colors"
+              >
+                Remove
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="flex items-center gap-2">
+        <select
+          value={selectedCategoryId}
+          onChange={(e) => setCategoryId(e.target.value)}
+          className="bg-discord-darker border border-discord-dark rounded px-3 py-2
+                   text-sm text-discord-text focus:outline-none focus:ring-2 focus:ring-discord-primary"
+        >
+          {soundLibrary.categories.map((category) => (
+            <option key={category.id} value={category.id}>
+              {category.name}
+            </option>
+          ))}
+        </select>
+        <button
+          onClick={handleAddFolder}
+          disabled={!selectedCategoryId}
+          className="px-3 py-2 bg-discord-primary hover:bg-discord-primary/80 rounded
+                   text-sm text-white transition-colors disabled:opacity-50"
+        >
+          Add Folder
+        </button>
+      </div>
+      {error && <p className="text-xs text-discord-danger">{error}</p>}
+    </div>
+  );
+}
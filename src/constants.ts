@@ -8,8 +8,16 @@ export const ANIMATION_DURATIONS = {
   TOAST_EXIT_START: 600, // ms - Time before toast starts exit animation
   CLEANUP_DELAY: 100, // ms - Delay for audio cleanup after stop
   MODAL_TRANSITION: 100, // ms - Delay for modal state transitions
+  IGNORED_FLASH: 400, // ms - Button flash when a trigger is ignored
 } as const;
 
+// ============================================================================
+// Audio Devices
+// ============================================================================
+
+// Pseudo-device ID: play on whatever the system default output is
+export const SYSTEM_DEFAULT_DEVICE_ID = "system_default";
+
 // ============================================================================
 // Debug & Development
 // ============================================================================
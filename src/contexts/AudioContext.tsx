@@ -7,6 +7,7 @@ import {
   ReactNode,
 } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { AudioDevice } from "../types";
 
 interface AudioContextType {
@@ -19,9 +20,10 @@ const AudioContext = createContext<AudioContextType | undefined>(undefined);
 export function AudioProvider({ children }: { children: ReactNode }) {
   const [devices, setDevices] = useState<AudioDevice[]>([]);
 
+  // Rescan devices; saved device IDs that shifted are updated by the backend
   const refreshDevices = useCallback(async () => {
     try {
-      const deviceList = await invoke<AudioDevice[]>("list_audio_devices");
+      const deviceList = await invoke<AudioDevice[]>("refresh_audio_devices");
       setDevices(deviceList);
     } catch (error) {
       console.error("Failed to refresh devices:", error);
@@ -32,13 +34,24 @@ export function AudioProvider({ children }: { children: ReactNode }) {
   useEffect(() => {
     const loadDevices = async () => {
       try {
-        await refreshDevices();
+        const deviceList = await invoke<AudioDevice[]>("list_audio_devices");
+        setDevices(deviceList);
       } catch (error) {
         console.error("Failed to load devices:", error);
       }
     };
     loadDevices();
-  }, [refreshDevices]);
+    // Rescanned by the backend when a device is plugged in or removed
+    const unlisten = listen<AudioDevice[]>(
+      "audio-devices-changed",
+      (event) => {
+        setDevices(event.payload);
+      }
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
   return (
     <AudioContext.Provider value={{ devices, refreshDevices }}>
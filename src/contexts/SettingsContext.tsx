@@ -7,6 +7,7 @@ import {
   ReactNode,
 } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { AppSettings } from "../types";
 
 interface SettingsContextType {
@@ -57,6 +58,16 @@ export function SettingsProvider({ children }: { children: ReactNode }) {
     loadSettings();
   }, [reloadSettings]);
 
+  // Settings changed by the backend (e.g. normalization toggled via hotkey)
+  useEffect(() => {
+    const unlisten = listen<AppSettings>("settings-changed", (event) => {
+      setSettings(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   return (
     <SettingsContext.Provider
       value={{ settings, saveSettings, reloadSettings, isLoading }}
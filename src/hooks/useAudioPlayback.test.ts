@@ -38,6 +38,16 @@ describe("useAudioPlayback", () => {
     is_favorite: false,
     trim_start_ms: null,
     trim_end_ms: null,
+    play_policy: "restart",
+    resume_playback: false,
+    expires_at: null,
+    monitor_trim_db: null,
+    broadcast_trim_db: null,
+    group: null,
+    monitor_device_id: null,
+    broadcast_device_id: null,
+    preview: null,
+    fit_duration_ms: null,
     ...overrides,
   });
 
@@ -199,6 +209,22 @@ describe("useAudioPlayback", () => {
       );
     });
 
+    it("should pass playPolicy", async () => {
+      const { result } = renderHook(() => useAudioPlayback(defaultProps));
+      const mockSound = createMockSound({ play_policy: "extend" });
+
+      await act(async () => {
+        await result.current.playSound(mockSound);
+      });
+
+      expect(invoke).toHaveBeenCalledWith(
+        "play_dual_output",
+        expect.objectContaining({
+          playPolicy: "extend",
+        })
+      );
+    });
+
     it("should pass all parameters correctly in single invoke call", async () => {
       const props = {
         ...defaultProps,
@@ -227,6 +253,7 @@ describe("useAudioPlayback", () => {
         trimStartMs: 100,
         trimEndMs: 2000,
         soundId: "test-id",
+        playPolicy: "restart",
       });
     });
   });
@@ -347,6 +374,12 @@ describe("useAudioPlayback", () => {
       expect(result.current.playingSoundIds.size).toBe(0);
     });
 
+    it("should initialize with empty decodingSoundIds", () => {
+      const { result } = renderHook(() => useAudioPlayback(defaultProps));
+
+      expect(result.current.decodingSoundIds.size).toBe(0);
+    });
+
     it("should add sound to playingSoundIds on successful playback", async () => {
       const { result } = renderHook(() => useAudioPlayback(defaultProps));
       const mockSound = createMockSound({ id: "playing-sound-id" });
@@ -380,6 +413,7 @@ describe("useAudioPlayback", () => {
         durationMs: 0,
         trimStartMs: 500,
         trimEndMs: 3000,
+        volume: 0.8,
       });
     });
 
@@ -499,6 +533,18 @@ describe("useAudioPlayback", () => {
         "playback-progress",
         expect.any(Function)
       );
+      expect(listen).toHaveBeenCalledWith(
+        "playback-ignored",
+        expect.any(Function)
+      );
+      expect(listen).toHaveBeenCalledWith(
+        "device-fallback",
+        expect.any(Function)
+      );
+      expect(listen).toHaveBeenCalledWith(
+        "sound-state-changed",
+        expect.any(Function)
+      );
     });
 
     it("should return cleanup function", () => {
@@ -519,10 +565,13 @@ describe("useAudioPlayback", () => {
       const { result } = renderHook(() => useAudioPlayback(defaultProps));
 
       expect(result.current).toHaveProperty("playingSoundIds");
+      expect(result.current).toHaveProperty("decodingSoundIds");
+      expect(result.current).toHaveProperty("ignoredSoundIds");
       expect(result.current).toHaveProperty("activeWaveform");
       expect(result.current).toHaveProperty("isWaveformExiting");
       expect(result.current).toHaveProperty("playSound");
       expect(result.current).toHaveProperty("stopAllAudio");
+      expect(result.current).toHaveProperty("setActiveVolume");
       expect(result.current).toHaveProperty("setupAudioListeners");
     });
 
@@ -1,8 +1,15 @@
 import { useState, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { Sound, PlaybackResult } from "../types";
+import {
+  Sound,
+  PlaybackResult,
+  PlaybackIgnoredEvent,
+  DeviceFallback,
+  SoundStateChangedEvent,
+} from "../types";
 import { DEBUG, ANIMATION_DURATIONS } from "../constants";
+import { formatError } from "../utils/errors";
 
 // Playback progress event payload (matches Rust struct)
 interface PlaybackProgress {
@@ -21,6 +28,14 @@ export interface ActiveWaveform {
   durationMs: number;
   trimStartMs: number | null;
   trimEndMs: number | null;
+  volume: number; // Live volume of this playback (set_playback_volume)
+  paused?: boolean; // Paused via pause_playback
+}
+
+// Playback paused/resumed event payload (matches Rust struct)
+interface PlaybackPaused {
+  playback_id: string;
+  paused: boolean;
 }
 
 interface UseAudioPlaybackProps {
@@ -45,9 +60,19 @@ export function useAudioPlayback({
     null
   );
   const [isWaveformExiting, setIsWaveformExiting] = useState(false);
+  // Sounds whose last trigger was ignored (flashed briefly in the UI)
+  const [ignoredSoundIds, setIgnoredSoundIds] = useState<Set<string>>(
+    new Set()
+  );
+
+  // Sounds still decoding (spinner instead of playing indicator)
+  const [decodingSoundIds, setDecodingSoundIds] = useState<Set<string>>(
+    new Set()
+  );
 
   // Track playing sounds
   const playingSoundsRef = useRef<Map<string, string>>(new Map()); // sound_id -> playback_id
+  const playbackVolumesRef = useRef<Map<string, number>>(new Map()); // sound_id -> volume
 
   const playSound = useCallback(
     async (sound: Sound) => {
@@ -76,6 +101,7 @@ export function useAudioPlayback({
           trimStartMs: sound.trim_start_ms,
           trimEndMs: sound.trim_end_ms,
           soundId: sound.id,
+          playPolicy: sound.play_policy,
         });
 
         if (DEBUG) {
@@ -88,6 +114,18 @@ export function useAudioPlayback({
           return;
         }
 
+        // Extended: the current playback keeps going, nothing new to track
+        if (result.action === "extended") {
+          if (DEBUG) console.log(`[EXTEND] ${result.playback_id} plays again`);
+          return;
+        }
+
+        // Queued: plays again after the current playback, started by the backend
+        if (result.action === "queued") {
+          if (DEBUG) console.log(`[QUEUE] Plays after ${result.playback_id}`);
+          return;
+        }
+
         // If restarted, clean up old tracking first
         if (result.action === "restarted" && result.stopped_playback_id) {
           if (DEBUG) {
@@ -101,6 +139,7 @@ export function useAudioPlayback({
         if (result.playback_id) {
           setPlayingSoundIds((prev) => new Set(prev).add(sound.id));
           playingSoundsRef.current.set(sound.id, result.playback_id);
+          playbackVolumesRef.current.set(sound.id, playbackVolume);
 
           if (DEBUG) {
             console.log(
@@ -117,11 +156,12 @@ export function useAudioPlayback({
             durationMs: 0, // Will be updated by progress events
             trimStartMs: sound.trim_start_ms ?? null,
             trimEndMs: sound.trim_end_ms ?? null,
+            volume: playbackVolume,
           });
         }
       } catch (error) {
         console.error(`Playback error:`, error);
-        showToast(`Error: ${error}`);
+        showToast(`Error: ${formatError(error)}`);
         playingSoundsRef.current.delete(sound.id);
         setPlayingSoundIds((prev) => {
           const next = new Set(prev);
@@ -137,6 +177,7 @@ export function useAudioPlayback({
     try {
       await invoke("stop_all_audio");
       playingSoundsRef.current.clear();
+      playbackVolumesRef.current.clear();
       setPlayingSoundIds(new Set());
 
       // Trigger exit animation before removing waveform
@@ -148,10 +189,50 @@ export function useAudioPlayback({
 
       showToast("All audio stopped");
     } catch (error) {
-      showToast(`Stop Error: ${error}`);
+      showToast(`Stop Error: ${formatError(error)}`);
     }
   }, [showToast]);
 
+  // Change the volume of the sound shown in the header while it plays
+  const setActiveVolume = useCallback(
+    async (newVolume: number) => {
+      const soundId = activeWaveform?.soundId;
+      if (!soundId) return;
+      const playbackId = playingSoundsRef.current.get(soundId);
+      if (!playbackId) return;
+
+      playbackVolumesRef.current.set(soundId, newVolume);
+      setActiveWaveform((prev) =>
+        prev?.soundId === soundId ? { ...prev, volume: newVolume } : prev
+      );
+      try {
+        await invoke("set_playback_volume", { playbackId, volume: newVolume });
+      } catch (error) {
+        // The playback may have ended in the meantime
+        if (DEBUG) console.log(`[VOLUME] ${formatError(error)}`);
+      }
+    },
+    [activeWaveform?.soundId]
+  );
+
+  // Pause or resume the sound shown in the header
+  const toggleActivePause = useCallback(async () => {
+    const soundId = activeWaveform?.soundId;
+    if (!soundId) return;
+    const playbackId = playingSoundsRef.current.get(soundId);
+    if (!playbackId) return;
+
+    try {
+      await invoke(
+        activeWaveform?.paused ? "resume_playback" : "pause_playback",
+        { playbackId }
+      );
+    } catch (error) {
+      // The playback may have ended in the meantime
+      if (DEBUG) console.log(`[PAUSE] ${formatError(error)}`);
+    }
+  }, [activeWaveform?.soundId, activeWaveform?.paused]);
+
   // Setup audio event listeners
   const setupAudioListeners = useCallback(() => {
     const unlisten = listen<string>("audio-decode-complete", (event) => {
@@ -189,6 +270,7 @@ export function useAudioPlayback({
 
       // Clean up all tracking
       playingSoundsRef.current.delete(soundId);
+      playbackVolumesRef.current.delete(soundId);
       setPlayingSoundIds((prev) => {
         const next = new Set(prev);
         next.delete(soundId);
@@ -218,6 +300,7 @@ export function useAudioPlayback({
                 durationMs: 0,
                 trimStartMs: nextSound.trim_start_ms ?? null,
                 trimEndMs: nextSound.trim_end_ms ?? null,
+                volume: playbackVolumesRef.current.get(nextSound.id) ?? 1,
               };
             }
           }
@@ -232,6 +315,69 @@ export function useAudioPlayback({
       });
     });
 
+    // Flash buttons whose trigger was ignored (cooldown, already playing)
+    const unlistenIgnored = listen<PlaybackIgnoredEvent>(
+      "playback-ignored",
+      (event) => {
+        const { sound_id, reason, remaining_ms } = event.payload;
+        if (DEBUG)
+          console.log(
+            `[IGNORED] ${sound_id}: ${reason} (${remaining_ms}ms remaining)`
+          );
+
+        setIgnoredSoundIds((prev) => new Set(prev).add(sound_id));
+        setTimeout(() => {
+          setIgnoredSoundIds((prev) => {
+            const next = new Set(prev);
+            next.delete(sound_id);
+            return next;
+          });
+        }, ANIMATION_DURATIONS.IGNORED_FLASH);
+      }
+    );
+
+    // Track decode/playing state (also covers hotkey-triggered sounds)
+    const unlistenSoundState = listen<SoundStateChangedEvent>(
+      "sound-state-changed",
+      (event) => {
+        const { sound_id, state } = event.payload;
+        if (DEBUG) console.log(`[STATE] ${sound_id}: ${state}`);
+
+        setDecodingSoundIds((prev) => {
+          if ((state === "decoding") === prev.has(sound_id)) return prev;
+          const next = new Set(prev);
+          if (state === "decoding") {
+            next.add(sound_id);
+          } else {
+            next.delete(sound_id);
+          }
+          return next;
+        });
+      }
+    );
+
+    // Tell the user when a missing device is replaced by the system default
+    const unlistenFallback = listen<DeviceFallback>(
+      "device-fallback",
+      (event) => {
+        const { role, restored } = event.payload;
+        const label = role === "monitor" ? "Monitor" : "Broadcast";
+        showToast(
+          restored
+            ? `${label} device is back`
+            : `${label} device missing - using system default`
+        );
+      }
+    );
+
+    // Saved devices that matched no output device at startup
+    const unlistenMigration = listen<string[]>(
+      "device-migration-needed",
+      () => {
+        showToast("Saved output device not found - please select it again");
+      }
+    );
+
     // Listen for playback progress events
     const unlistenProgress = listen<PlaybackProgress>(
       "playback-progress",
@@ -257,20 +403,45 @@ export function useAudioPlayback({
       }
     );
 
+    // Listen for pause/resume of running playbacks
+    const unlistenPaused = listen<PlaybackPaused>(
+      "playback-paused",
+      (event) => {
+        const { playback_id, paused } = event.payload;
+        for (const [soundId, pid] of playingSoundsRef.current.entries()) {
+          if (pid === playback_id) {
+            setActiveWaveform((prev) =>
+              prev?.soundId === soundId ? { ...prev, paused } : prev
+            );
+            break;
+          }
+        }
+      }
+    );
+
     return () => {
       unlisten.then((fn: () => void) => fn());
       unlistenError.then((fn: () => void) => fn());
       unlistenComplete.then((fn: () => void) => fn());
       unlistenProgress.then((fn: () => void) => fn());
+      unlistenIgnored.then((fn: () => void) => fn());
+      unlistenFallback.then((fn: () => void) => fn());
+      unlistenMigration.then((fn: () => void) => fn());
+      unlistenSoundState.then((fn: () => void) => fn());
+      unlistenPaused.then((fn: () => void) => fn());
     };
   }, [showToast, soundLibrary.sounds]);
 
   return {
     playingSoundIds,
+    decodingSoundIds,
+    ignoredSoundIds,
     activeWaveform,
     isWaveformExiting,
     playSound,
     stopAllAudio,
+    setActiveVolume,
+    toggleActivePause,
     setupAudioListeners,
   };
 }
@@ -29,7 +29,7 @@ export function useFileDrop({ showToast, onFilesDropped }: UseFileDropProps) {
         }
 
         const audioFiles = paths.filter((path: string) =>
-          /\.(mp3|wav|ogg|m4a|flac)$/i.test(path)
+          /\.(mp3|wav|ogg|m4a|flac|opus)$/i.test(path)
         );
 
         if (audioFiles.length > 0) {
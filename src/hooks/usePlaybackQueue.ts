@@ -0,0 +1,62 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { QueueSnapshot } from "../types";
+import { formatError } from "../utils/errors";
+
+export function usePlaybackQueue(showToast: (message: string) => void) {
+  const [queue, setQueue] = useState<QueueSnapshot>({
+    current: null,
+    pending: [],
+  });
+
+  // Load the queue on mount, then follow queue-updated events
+  useEffect(() => {
+    invoke<QueueSnapshot>("get_queue")
+      .then(setQueue)
+      .catch((error) =>
+        console.error("Failed to load playback queue:", formatError(error))
+      );
+
+    const unlisten = listen<QueueSnapshot>("queue-updated", (event) => {
+      setQueue(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const queueSound = useCallback(
+    async (soundId: string) => {
+      try {
+        await invoke("queue_sound", { soundId });
+      } catch (error) {
+        showToast(`Queue Error: ${formatError(error)}`);
+      }
+    },
+    [showToast]
+  );
+
+  const skip = useCallback(async () => {
+    try {
+      await invoke("skip");
+    } catch (error) {
+      showToast(`Skip Error: ${formatError(error)}`);
+    }
+  }, [showToast]);
+
+  const clearQueue = useCallback(async () => {
+    try {
+      await invoke("clear_queue");
+    } catch (error) {
+      showToast(`Queue Error: ${formatError(error)}`);
+    }
+  }, [showToast]);
+
+  return {
+    queue,
+    queueSound,
+    skip,
+    clearQueue,
+  };
+}
@@ -1,10 +1,15 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import QuickSearch from "./components/search/QuickSearch";
 import "./index.css";
 
+// The quick search popup is a second window loading the same bundle
+const isQuickSearch = getCurrentWindow().label === "quick-search";
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isQuickSearch ? <QuickSearch /> : <App />}
   </React.StrictMode>
 );
@@ -1,7 +1,48 @@
+// ============================================================================
+// Error Types
+// ============================================================================
+
+/** Stable error codes returned by backend commands */
+export type ErrorCode =
+  | "device_missing"
+  | "device_error"
+  | "file_not_found"
+  | "unsupported_format"
+  | "drm_protected"
+  | "decode_failed"
+  | "hotkey_conflict"
+  | "hotkey_invalid"
+  | "hotkey_not_found"
+  | "sound_not_found"
+  | "category_not_found"
+  | "playback_not_found"
+  | "invalid_request"
+  | "cancelled"
+  | "timeout"
+  | "vb_cable"
+  | "internal";
+
+/** Structured error rejected by invoke() */
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  context?: Record<string, string>;
+}
+
 // ============================================================================
 // Audio Device Types
 // ============================================================================
 
+export type DeviceRole = "monitor" | "broadcast";
+
+/** Payload of `device-fallback` (and entries of get_device_fallbacks) */
+export interface DeviceFallback {
+  role: DeviceRole;
+  original_device_id: string; // Saved device that is missing (or has returned)
+  fallback_device_id: string | null; // Device used instead (null once restored)
+  restored: boolean;
+}
+
 export interface AudioDevice {
   id: string;
   name: string;
@@ -13,7 +54,7 @@ export interface AudioDevice {
 // ============================================================================
 
 export interface AppSettings {
-  monitor_device_id: string | null;
+  monitor_device_id: string | null; // "system_default" follows the system default output
   broadcast_device_id: string | null;
   default_volume: number;
   volume_multiplier: number; // Global volume scaling (0.1 - 1.0), default 0.2
@@ -23,6 +64,254 @@ export interface AppSettings {
   autostart_enabled: boolean; // Enable autostart on system boot
   microphone_routing_device_id: string | null; // Microphone device ID for VB-Cable routing
   microphone_routing_enabled: boolean; // Whether microphone routing is enabled
+  microphone_monitor_enabled: boolean; // Also play the routed mic on the monitor device
+  microphone_cable_gain: number; // Mic gain on CABLE Input (0.0 - 2.0)
+  microphone_monitor_gain: number; // Mic gain on the monitor device (0.0 - 2.0)
+  noise_reduction: NoiseReduction; // Noise suppression and gate of the routed microphone
+  voice_changer: VoiceChanger; // Effects on the routed microphone
+  microphone_duck_enabled: boolean; // Lower the mic on CABLE Input while sounds play
+  microphone_duck_db: number; // How far the mic is lowered (0 - 60 dB), default 12
+  microphone_duck_attack_ms: number; // Time to lower the mic (0 - 5000 ms), default 20
+  microphone_duck_release_ms: number; // Time to bring it back (0 - 5000 ms), default 400
+  soundboard_mic_mix_enabled: boolean; // Mix sounds into the routed mic instead of the broadcast device
+  normalization_enabled: boolean; // Normalize playback loudness to the target
+  normalization_target_lufs: number; // Loudness target (-40 to -5 LUFS), default -16
+  spectrum_analyzer_enabled: boolean; // Send spectrum-update events for the visualizer
+  discord_mute_awareness_enabled: boolean; // Silence broadcast while muted/deafened in Discord
+  discord_client_id: string | null; // Discord application client ID (RPC)
+  discord_client_secret: string | null; // Discord application client secret (RPC)
+  api_enabled: boolean; // Serve the local API on 127.0.0.1
+  api_port: number; // Local API port, default 7393
+  device_fallback_enabled: boolean; // Use the system default while a saved device is missing
+  setup_progress: SetupProgress; // First-run setup wizard progress
+  language: Language; // Language for tray menu, backend errors and notifications
+  autostart_profile: string | null; // Profile passed to autostart launches (--profile)
+  autostart_minimized: boolean; // Start hidden when launched by autostart (--start-minimized)
+  startup_actions: StartupAction[]; // Actions executed once at startup, in order
+  api_tokens: ApiToken[]; // Local API tokens (the API is closed while empty)
+  api_open_local_access: boolean; // Allow clients without a token while none exists
+  queue_mode_enabled: boolean; // Triggered sounds wait for the previous one
+  high_quality_resampling: boolean; // Band-limited resampling before playback
+  resample_cache_enabled: boolean; // Cache sounds converted to device rates
+  master_volume: number; // 0.0-1.0, applies to playing sounds too
+  monitor_volume: number; // 0.0-1.0, level of the monitor output
+  broadcast_volume: number; // 0.0-1.0, level of the broadcast output
+  max_concurrent_sounds: number; // 1-32, the oldest sound stops for a new one
+  restart_crossfade_ms: number; // 0-500, fade between old and new playback on restart
+  device_names?: Record<string, string>; // device ID -> device name, keeps saved IDs on their device
+  output_limiter_enabled: boolean; // Limit outputs to -1 dBFS instead of clipping
+  small_output_buffers: boolean; // Smallest output buffers the device accepts
+  identical_device_behavior: IdenticalDeviceBehavior; // Playback when monitor = broadcast
+  copy_sounds_to_library: boolean; // Copy added sounds into the app data folder
+  watched_folders: WatchedFolder[]; // Folders whose audio files are added automatically
+  app_rules_enabled: boolean; // Switch profile/category with the focused application
+  app_rules: AppRule[]; // Checked against the focused application, first match wins
+}
+
+export type IdenticalDeviceBehavior = "single_stream" | "both_streams";
+
+// Settings combination that likely isn't intended (validate_settings)
+export type SettingsIssue = {
+  kind: "identical_output_devices";
+  device_id: string;
+  behavior: IdenticalDeviceBehavior;
+};
+
+export type Language = "en" | "de";
+
+export type StartupAction =
+  | { type: "enable_mic_routing"; microphone_id?: string | null }
+  | { type: "apply_profile_devices"; profile: string };
+
+// ============================================================================
+// Setup Wizard Types
+// ============================================================================
+
+export type SetupStep = "devices" | "vb_cable" | "mic_routing" | "test_sound";
+
+export interface SetupProgress {
+  completed: SetupStep[];
+  skipped: SetupStep[];
+}
+
+/** Returned by get_setup_state / complete_setup_step / skip_setup_step */
+export interface SetupState {
+  current_step: SetupStep | null; // null once every step is done or skipped
+  completed: SetupStep[];
+  skipped: SetupStep[];
+  is_complete: boolean;
+}
+
+// ============================================================================
+// Local API Types
+// ============================================================================
+
+export interface ApiStatus {
+  enabled: boolean;
+  running_port: number | null;
+}
+
+export type ApiCapability =
+  | "events"
+  | "spectate" // Read-only: GET /spectate, GET /now-playing
+  | "play"
+  | "stop"
+  | "edit_library"
+  | "edit_settings";
+
+export interface ApiToken {
+  id: string;
+  name: string;
+  secret: string; // Sent as "Authorization: Bearer <secret>" (or ?token= on event streams)
+  capabilities: ApiCapability[];
+  created_at_ms: number;
+}
+
+export type ApiDenial = "missing_token" | "invalid_token" | "missing_capability";
+
+/** Entry of get_api_audit_log */
+export interface ApiAuditEntry {
+  timestamp_ms: number;
+  token_id: string | null;
+  token_name: string | null;
+  request: string; // e.g. "GET /events"
+  capability: ApiCapability;
+  allowed: boolean;
+  denial: ApiDenial | null;
+}
+
+/** Payload of `trigger-feedback` (also streamed from `GET /events`) */
+export type TriggerFeedback = { timestamp_ms: number } & (
+  | { type: "sound_started"; sound_id: string; playback_id: string }
+  | { type: "sound_stopped"; sound_id: string; playback_id: string }
+  | {
+      type: "hotkey_fired";
+      hotkey: string;
+      sound_id: string | null;
+      action: HotkeyAction | null;
+    }
+);
+
+// ============================================================================
+// Discord Types
+// ============================================================================
+
+export type DiscordConnection =
+  | "disabled"
+  | "connecting"
+  | "authorizing"
+  | "connected"
+  | "unavailable";
+
+/** Payload of `discord-voice-state` and `get_discord_status` */
+export interface DiscordStatus {
+  connection: DiscordConnection;
+  muted: boolean;
+  deafened: boolean;
+  broadcast_suppressed: boolean;
+  error: string | null;
+}
+
+// ============================================================================
+// Session Types
+// ============================================================================
+
+export interface WindowGeometry {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  maximized: boolean;
+}
+
+export interface ProfileDevices {
+  monitor_device_id: string | null;
+  broadcast_device_id: string | null;
+}
+
+export interface SessionState {
+  last_category_id: string | null; // Category selected when the app was last used
+  devices_by_profile: Record<string, ProfileDevices>; // Last used devices per profile
+  window: WindowGeometry | null; // Main window geometry (physical pixels)
+  last_recap?: SessionRecap | null; // Recap of the last session, until shown
+  active_profile?: string | null; // Profile switched to last
+}
+
+/** Profiles and the active one (list_profiles, payload of profile-changed) */
+export interface ProfileList {
+  active: string;
+  profiles: string[]; // "default" first, then by name
+}
+
+/** Where a clip is recorded from (start_recording) */
+export type RecordingSource =
+  | { type: "device"; device_id: string } // ID from list_microphones
+  | { type: "cable_mix" }; // CABLE Output: what is sent over VB-Cable
+
+export type RecordingFormat = "wav" | "ogg";
+
+/** The running recording (get_recording_status) */
+export interface RecordingStatus {
+  source: RecordingSource;
+  format: RecordingFormat;
+  elapsed_ms: number;
+  limit_reached: boolean; // Capture stopped at the length limit
+}
+
+/** Noise suppression and gate of the routed microphone */
+export interface NoiseReduction {
+  gate_enabled: boolean;
+  gate_threshold_db: number; // -90 to 0 dBFS, default -50
+  suppression_enabled: boolean;
+  suppression_db: number; // How far noise is lowered (0 - 40 dB), default 20
+}
+
+/** Effects applied to the routed microphone */
+export interface VoiceChanger {
+  enabled: boolean;
+  pitch_semitones: number; // -12 to 12
+  robot: boolean; // Ring-modulated robot voice
+  echo_delay_ms: number; // 0 (no echo) to 1000
+  echo_feedback: number; // 0.0 - 0.9
+}
+
+/** Profile/category to switch to while an application is focused */
+export interface AppRule {
+  process_name: string; // Executable, e.g. "obs64.exe" (case-insensitive)
+  profile: string | null; // null keeps the current profile
+  category_id: string | null; // null keeps the current category
+}
+
+/** Payload of `app-rule-changed` */
+export interface AppRuleChanged {
+  process: string; // Executable in the foreground
+  rule: AppRule | null; // null when the last rule stopped matching
+}
+
+/** Summary of the last session (take_session_recap) */
+export interface SessionRecap {
+  started_ms: number; // Unix time in milliseconds
+  ended_ms: number; // Unix time in milliseconds
+  sounds_played: number;
+  most_played: {
+    sound_id: string;
+    sound_name: string | null; // null if the sound was deleted
+    plays: number;
+  } | null;
+  playback_ms: number; // Audible time, overlapping playbacks counted separately
+  errors: number; // Failed playbacks and device stream errors
+}
+
+/** Plays of one sound (get_sound_stats) */
+export interface PlayStats {
+  play_count: number;
+  last_played_ms: number; // Unix time in milliseconds
+}
+
+/** A sound with its plays (get_most_played, get_recently_played) */
+export interface SoundPlayStats {
+  sound: Sound;
+  stats: PlayStats;
 }
 
 // ============================================================================
@@ -30,16 +319,290 @@ export interface AppSettings {
 // ============================================================================
 
 /** Result of play_dual_output indicating what action was taken */
+/** Grid card data of a sound (get_card_assets) */
+export interface CardAsset {
+  sound_id: string;
+  peaks: number[]; // 64 peaks (0.0-1.0), empty if the file can't be decoded
+  duration_ms: number | null;
+  icon: string | null;
+}
+
 export interface PlaybackResult {
   playback_id: string | null;
-  action: "started" | "restarted" | "ignored";
+  action: "started" | "restarted" | "extended" | "queued" | "ignored";
   stopped_playback_id: string | null;
 }
 
+/** Per-trigger routing passed to play_dual_output (default: both outputs) */
+export type OutputRouting =
+  | { mode: "both" }
+  | { mode: "monitor_only" }
+  | { mode: "broadcast_only" }
+  | { mode: "alternate"; segment_ms: number }; // A/B comparison, monitor first
+
+// Emitted when an A/B comparison switches outputs
+export interface RoutingSegmentEvent {
+  playback_id: string;
+  output: DeviceRole;
+}
+
+// Emitted as queue-updated whenever the playback queue changes
+export interface QueueSnapshot {
+  current: string | null; // Sound ID playing from the queue
+  pending: string[]; // Sound IDs waiting, next first
+}
+
+export type PlaybackIgnoreReason = "cooldown" | "already_playing";
+
+// Emitted when a trigger is dropped (e.g. retriggered during cooldown)
+export interface PlaybackIgnoredEvent {
+  sound_id: string;
+  reason: PlaybackIgnoreReason;
+  remaining_ms: number; // Time until the sound can be triggered again
+}
+
+export type DataFile = "sounds" | "hotkeys";
+
+// Emitted when sounds.json / hotkeys.json was edited outside the app
+export interface DataFileChangedEvent {
+  file: DataFile;
+  reloaded: boolean; // false: edit rejected, in-memory state kept
+  error: string | null;
+}
+
+/** Backup copy of a data file (list_backups) */
+export interface BackupEntry {
+  file_name: string; // Pass to restore_backup
+  source: string; // "sounds" | "hotkeys" | "settings"
+  created_ms: number; // Unix time in milliseconds
+  size_bytes: number;
+}
+
+export type SaveTarget =
+  | "hotkeys"
+  | "sounds"
+  | "settings"
+  | "session"
+  | "stats";
+
+// Emitted when the routed microphone or CABLE Input failed; routing retries
+export interface MicRoutingLost {
+  microphone_id: string;
+  error: string;
+  retry_in_ms: number; // Delay before the first retry (doubles, up to 30 s)
+}
+
+// Emitted when microphone routing runs again after it was lost
+export interface MicRoutingRestored {
+  microphone_id: string;
+  attempts: number; // Failed attempts before it was restored
+}
+
+/** Ring buffer counters of the active routing (get_mic_routing_stats) */
+export interface MicRoutingStats {
+  overflows: number; // Writes that overwrote unread samples
+  underruns: number; // Reads that ran out of samples
+  buffer_ms: number; // Current buffer length, grows from 100 after sustained underruns
+  resizes: number;
+}
+
+// Emitted when a background save of a data file failed (in-memory state is kept)
+export interface SaveFailedEvent {
+  file: SaveTarget;
+  error: string;
+}
+
+export type SoundStatus = "decoding" | "playing" | "stopped";
+
+// Emitted when a sound starts decoding, becomes audible, or stops
+export interface SoundStateChangedEvent {
+  sound_id: string;
+  playback_id: string;
+  state: SoundStatus;
+}
+
+// ============================================================================
+// Analysis Types
+// ============================================================================
+
+/** Quantized STFT magnitudes from get_spectrogram */
+export interface SpectrogramData {
+  width: number;
+  height: number;
+  data: number[]; // column-major: data[col * height + row], row 0 = lowest frequency
+  max_frequency_hz: number;
+  duration_ms: number;
+}
+
+// ============================================================================
+// Diagnostics Types
+// ============================================================================
+
+export interface CacheStats {
+  entries: number;
+  resampled_entries: number;
+  memory_bytes: number;
+  max_memory_bytes: number;
+  memory_mb: number;
+  max_memory_mb: number;
+}
+
+export interface DeviceStatus {
+  id: string;
+  name: string | null; // Device name if currently present
+  available: boolean;
+}
+
+export interface StreamErrorRecord {
+  device: string;
+  message: string;
+  timestamp_ms: number; // Unix time in milliseconds
+}
+
+export interface PoolStatus {
+  workers: number; // Worker threads spawned so far
+  max_workers: number;
+  busy: number;
+  queued: number; // Jobs waiting for a free worker
+}
+
+export interface EngineStatus {
+  host_api: string; // e.g. "WASAPI"
+  active_playbacks: number;
+  active_streams: number;
+  monitor_device: DeviceStatus | null;
+  broadcast_device: DeviceStatus | null;
+  cache: CacheStats;
+  decode_pool: PoolStatus;
+  playback_pool: PoolStatus;
+  mic_routing: {
+    enabled: boolean;
+    active_device_id: string | null;
+    healthy: boolean;
+  };
+  recent_stream_errors: StreamErrorRecord[];
+  vb_cable_temp_bytes: number;
+  output_latency: LatencyReport | null; // Last measure_output_latency result
+}
+
+export interface LatencyReport {
+  device_id: string;
+  device_name: string;
+  sample_rate: number;
+  buffer_size: string; // "Fixed(256)" or "Default"
+  callback_frames: number; // Frames per callback the device asked for
+  buffer_ms: number; // The app's buffering (one callback)
+  device_latency_ms: number | null; // Reported by the host, null if unknown
+  total_ms: number; // Roughly trigger to sound
+  measured_at_ms: number; // Unix time in milliseconds
+}
+
+export type SelfTestStageStatus = "passed" | "failed" | "skipped";
+
+export interface SelfTestStage {
+  name: "decode" | "monitor_stream" | "broadcast_stream";
+  status: SelfTestStageStatus;
+  detail: string | null; // Error or skip reason
+  duration_ms: number;
+}
+
+export interface SelfTestReport {
+  passed: boolean;
+  stages: SelfTestStage[];
+}
+
+// Simulated failure for inject_audio_fault (debug builds only)
+export type AudioFault = "device_loss" | "decode_failure" | "stream_build_failure";
+
+export type StartupPhase =
+  | "state_load"
+  | "device_enumeration"
+  | "hotkey_registration"
+  | "tray_init"
+  | "cache_warmup"
+  | "card_assets";
+
+export interface PhaseTiming {
+  phase: StartupPhase;
+  duration_ms: number;
+  deferred: boolean; // Ran in the background after setup
+}
+
+export interface StartupReport {
+  phases: PhaseTiming[]; // In the order they finished
+  setup_ms: number | null; // Launch until the window could show
+  deferred_ms: number | null; // Launch until background work finished
+}
+
+export type GainVerdict = "ok" | "clipping" | "inaudible";
+
+/** Gain per stage in dB (null = silence) */
+export interface GainChain {
+  volume: number; // Per-sound or default volume (0.0-1.0)
+  normalization_gain_db: number; // 0 when normalization is off
+  curve_gain_db: number | null; // Volume curve incl. base attenuation
+  master_gain_db: number;
+  total_gain_db: number | null;
+}
+
+export interface SoundGainReport {
+  sound_id: string;
+  name: string;
+  chain: GainChain;
+  measured_lufs: number | null;
+  source_peak_dbfs: number | null; // Peak of the file itself
+  output_peak_dbfs: number | null; // Expected peak after the whole chain
+  verdict: GainVerdict | null; // null if the file could not be decoded
+  error: string | null;
+}
+
+/** Returned by analyze_gain_staging */
+export interface GainStagingReport {
+  sounds: SoundGainReport[];
+  clipping: number;
+  inaudible: number;
+}
+
+/** Payload of `loudness-analysis-progress`, emitted after each sound */
+export interface LoudnessProgress {
+  sound_id: string;
+  lufs: number | null; // null if silent or not decodable
+  error: string | null;
+  done: number;
+  total: number;
+}
+
+/** Payload of `loudness-analysis-complete` */
+export interface LoudnessComplete {
+  measured: number;
+  failed: number;
+}
+
+/** Result of `migrate_sounds_to_library` */
+export interface LibraryMigration {
+  copied: number; // Sounds now pointing at a copy in the library
+  already_managed: number;
+  missing: string[]; // Sound IDs whose file doesn't exist
+  failed: string[]; // Sound IDs whose file couldn't be copied
+}
+
+/** Payload of `spectrum-update`: monitor output band levels, low to high (0-1) */
+export interface SpectrumFrame {
+  bands: number[];
+}
+
 // ============================================================================
 // Sound Library Types
 // ============================================================================
 
+/** What re-triggering a sound while it plays does */
+export type PlayPolicy =
+  | "restart"
+  | "extend"
+  | "overlap"
+  | "ignore_if_playing"
+  | "queue";
+
 export interface Sound {
   id: string;
   name: string;
@@ -50,6 +613,75 @@ export interface Sound {
   is_favorite: boolean;
   trim_start_ms: number | null;
   trim_end_ms: number | null;
+  play_policy: PlayPolicy;
+  /** Resume from the last stopped position on the next trigger */
+  resume_playback: boolean;
+  /** Unix ms after which the sound is removed at startup */
+  expires_at: number | null;
+  /** Level trim on the monitor output in dB (-30 to +6), null = none */
+  monitor_trim_db: number | null;
+  /** Level trim on the broadcast output in dB (-30 to +6), null = none */
+  broadcast_trim_db: number | null;
+  /** Exclusive group: starting the sound stops the others in the group */
+  group: string | null;
+  /** Monitor device used instead of the global one */
+  monitor_device_id: string | null;
+  /** Broadcast device used instead of the global one */
+  broadcast_device_id: string | null;
+  /** Region auditioned on hover (preview_sound), null = no hover preview */
+  preview: PreviewRegion | null;
+  /** Play time-stretched to last exactly this long (ms), null = normal speed */
+  fit_duration_ms: number | null;
+  /** Pitch shift in semitones, keeping the length, null = original pitch */
+  pitch_semitones?: number | null;
+  /** Playback speed (the pitch follows), null = normal speed */
+  speed?: number | null;
+  /** Problem found in the file by validate_sound_file, null = none */
+  import_warning?: ImportWarning | null;
+  /** Integrated loudness (LUFS) from analyze_library_loudness, null = not measured or silent */
+  lufs?: number | null;
+  /** Whether lufs was measured (it stays null for silent files) */
+  loudness_measured?: boolean;
+  /** Length read from the file's headers on bulk import, null = unknown */
+  duration_ms?: number | null;
+  /** Free-form labels for filtering beyond the category */
+  tags?: string[];
+}
+
+/** A tag and how many sounds carry it (list_tags) */
+export interface TagCount {
+  tag: string;
+  count: number;
+}
+
+/** Folder whose audio files are kept in the library */
+export interface WatchedFolder {
+  path: string;
+  category_id: string; // Category new files are added to
+}
+
+/** Payload of `watched-folder-changed` */
+export interface WatchedFolderChanged {
+  folder: string;
+  added: Sound[];
+  removed: string[]; // Sound IDs
+  failed: string[]; // Files that couldn't be read as audio
+}
+
+/** Outcome of one file passed to import_files */
+export interface FileImportResult {
+  path: string;
+  sound: Sound | null; // null if the file was skipped
+  error: AppError | null;
+}
+
+/** Why an imported file is useless as a sound */
+export type ImportWarning = "empty_file" | "no_audio" | "silent";
+
+/** Short region of a sound played by preview_sound (at most 10 seconds) */
+export interface PreviewRegion {
+  start_ms: number;
+  end_ms: number;
 }
 
 export interface Category {
@@ -64,6 +696,38 @@ export interface SoundLibrary {
   sounds: Sound[];
 }
 
+/** Part of a sound a search query matched */
+export type MatchField = "name" | "tag" | "file_name";
+
+/** Ranked hit returned by search_sounds */
+export interface SearchResult {
+  sound: Sound;
+  score: number;
+  matched: MatchField | null; // null when listed without a query
+}
+
+// ============================================================================
+// Soundpack Types
+// ============================================================================
+
+/** How import_soundpack handles a sound name already in the target category */
+export type SoundpackConflictResolution = "skip" | "rename" | "replace";
+
+export interface SoundpackExportSummary {
+  path: string;
+  sound_count: number;
+  missing_files: string[]; // Sounds left out because their file is missing
+}
+
+export interface SoundpackImportSummary {
+  category_ids: string[];
+  imported: string[];
+  replaced: string[];
+  skipped: string[]; // Names of sounds skipped due to conflicts
+  hotkeys_assigned: string[];
+  hotkey_conflicts: string[]; // Suggested hotkeys already in use
+}
+
 // ============================================================================
 // Component Props Types
 // ============================================================================
@@ -73,10 +737,36 @@ export interface SoundLibrary {
 // Hotkey Types
 // ============================================================================
 
+// Unit actions are plain strings, actions on a sound carry its ID and
+// duck carries its attenuation in dB (applied while the hotkey is held)
+export type HotkeyAction =
+  | "toggle_normalization"
+  | "open_quick_search"
+  | "stop_all"
+  | "toggle_mic_routing"
+  | "volume_up"
+  | "volume_down"
+  | { play_sound: string }
+  | { stop_sound: string }
+  | { duck: number };
+
 export interface HotkeyMapping {
   mappings: Record<string, string>; // hotkey -> sound_id
+  actions?: Record<string, HotkeyAction>; // hotkey -> app action
+  bank?: HotkeyBank; // NumPad1-9 -> selected category's first nine sounds
+  modes?: Record<string, HotkeyMode>; // hotkey -> trigger mode (default "toggle")
 }
 
+// "hold_to_play" plays the sound only while the key is held
+export type HotkeyMode = "toggle" | "hold_to_play";
+
+export interface HotkeyBank {
+  modifiers: string; // Canonical modifiers, e.g. "Ctrl+Alt"
+}
+
+// Output format of export_bindings
+export type BindingFormat = "json" | "csv";
+
 // ============================================================================
 // VB-Cable Types
 // ============================================================================
@@ -90,6 +780,13 @@ export type VbCableStatus =
   | { status: "installed"; info: VbCableInfo }
   | { status: "notInstalled" };
 
+/** Installed VB-Cable driver version compared with the bundled installer */
+export interface VbCableUpdateInfo {
+  installed_version: string | null; // null if it couldn't be read
+  bundled_version: string;
+  update_available: boolean;
+}
+
 /** All 4 Windows default audio device settings */
 export interface SavedDefaults {
   render_console: string | null;
@@ -0,0 +1,74 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { cardAssetLoader } from "./cardAssets";
+import { CardAsset } from "../types";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+const asset = (soundId: string): CardAsset => ({
+  sound_id: soundId,
+  peaks: [0.5, 1.0],
+  duration_ms: 1000,
+  icon: null,
+});
+
+describe("CardAssetLoader", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("should batch requests into one call", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce([asset("a"), asset("b")]);
+
+    const [a, b] = await Promise.all([
+      cardAssetLoader.load("a", "/a.mp3"),
+      cardAssetLoader.load("b", "/b.mp3"),
+    ]);
+
+    expect(invoke).toHaveBeenCalledTimes(1);
+    expect(invoke).toHaveBeenCalledWith("get_card_assets", {
+      soundIds: ["a", "b"],
+    });
+    expect(a.sound_id).toBe("a");
+    expect(b.sound_id).toBe("b");
+  });
+
+  it("should serve loaded assets from its cache", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce([asset("cached")]);
+    await cardAssetLoader.load("cached", "/cached.mp3");
+
+    const again = await cardAssetLoader.load("cached", "/cached.mp3");
+
+    expect(again.sound_id).toBe("cached");
+    expect(invoke).toHaveBeenCalledTimes(1);
+  });
+
+  it("should reload when the sound's file changed", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce([asset("changed")])
+      .mockResolvedValueOnce([asset("changed")]);
+    await cardAssetLoader.load("changed", "/old.mp3");
+
+    await cardAssetLoader.load("changed", "/new.mp3");
+
+    expect(invoke).toHaveBeenCalledTimes(2);
+  });
+
+  it("should reject sounds the backend skipped", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce([]);
+
+    await expect(cardAssetLoader.load("gone", "/gone.mp3")).rejects.toThrow(
+      "Sound not found: gone"
+    );
+  });
+
+  it("should reject the whole batch on error", async () => {
+    vi.mocked(invoke).mockRejectedValueOnce(new Error("IPC failed"));
+
+    await expect(cardAssetLoader.load("x", "/x.mp3")).rejects.toThrow(
+      "IPC failed"
+    );
+  });
+});
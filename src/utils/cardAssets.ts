@@ -0,0 +1,74 @@
+import { invoke } from "@tauri-apps/api/core";
+import { CardAsset } from "../types";
+
+/** Requests made within this window go out as one get_card_assets call */
+const BATCH_WINDOW_MS = 16;
+
+interface Waiter {
+  resolve: (asset: CardAsset) => void;
+  reject: (error: unknown) => void;
+}
+
+interface CachedAsset {
+  filePath: string;
+  asset: CardAsset;
+}
+
+/**
+ * Batches grid card requests (waveform, duration, icon) so a grid with
+ * hundreds of sounds loads them in a few IPC calls instead of one per card.
+ */
+class CardAssetLoader {
+  private cache = new Map<string, CachedAsset>();
+  private pending = new Map<string, { filePath: string; waiters: Waiter[] }>();
+  private timer: ReturnType<typeof setTimeout> | null = null;
+
+  /** Card assets of a sound; `filePath` detects sounds whose file changed */
+  load(soundId: string, filePath: string): Promise<CardAsset> {
+    const cached = this.cache.get(soundId);
+    if (cached && cached.filePath === filePath) {
+      return Promise.resolve(cached.asset);
+    }
+
+    return new Promise((resolve, reject) => {
+      const request = this.pending.get(soundId) ?? { filePath, waiters: [] };
+      request.waiters.push({ resolve, reject });
+      this.pending.set(soundId, request);
+      if (this.timer === null) {
+        this.timer = setTimeout(() => this.flush(), BATCH_WINDOW_MS);
+      }
+    });
+  }
+
+  private async flush() {
+    this.timer = null;
+    const batch = this.pending;
+    this.pending = new Map();
+
+    try {
+      const assets = await invoke<CardAsset[]>("get_card_assets", {
+        soundIds: Array.from(batch.keys()),
+      });
+      for (const asset of assets) {
+        const request = batch.get(asset.sound_id);
+        if (!request) continue;
+        this.cache.set(asset.sound_id, { filePath: request.filePath, asset });
+        request.waiters.forEach((w) => w.resolve(asset));
+        batch.delete(asset.sound_id);
+      }
+      // The backend skips sounds that no longer exist
+      batch.forEach((request, soundId) =>
+        request.waiters.forEach((w) =>
+          w.reject(new Error(`Sound not found: ${soundId}`))
+        )
+      );
+    } catch (error) {
+      batch.forEach((request) =>
+        request.waiters.forEach((w) => w.reject(error))
+      );
+    }
+  }
+}
+
+// Global singleton loader
+export const cardAssetLoader = new CardAssetLoader();
@@ -0,0 +1,27 @@
+import { describe, it, expect } from "vitest";
+import { formatError, isAppError } from "./errors";
+
+describe("formatError", () => {
+  it("should return the message of an AppError", () => {
+    const error = { code: "sound_not_found", message: "Sound not found: abc" };
+    expect(formatError(error)).toBe("Sound not found: abc");
+  });
+
+  it("should return the message of an Error", () => {
+    expect(formatError(new Error("boom"))).toBe("boom");
+  });
+
+  it("should stringify plain values", () => {
+    expect(formatError("plain string")).toBe("plain string");
+  });
+});
+
+describe("isAppError", () => {
+  it("should detect structured command errors", () => {
+    expect(isAppError({ code: "hotkey_conflict", message: "taken" })).toBe(
+      true
+    );
+    expect(isAppError("hotkey_conflict")).toBe(false);
+    expect(isAppError(null)).toBe(false);
+  });
+});
@@ -0,0 +1,35 @@
+import { AppError, ImportWarning } from "../types";
+
+/**
+ * Type guard for structured errors returned by Tauri commands
+ */
+export function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    "message" in error
+  );
+}
+
+/**
+ * Convert a caught error (AppError, Error or string) to a display message
+ */
+export function formatError(error: unknown): string {
+  if (isAppError(error)) {
+    return error.message;
+  }
+  if (error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}
+
+/**
+ * Display messages for a sound's import warning
+ */
+export const IMPORT_WARNING_LABELS: Record<ImportWarning, string> = {
+  empty_file: "The file is empty (0 bytes)",
+  no_audio: "The file contains no audio",
+  silent: "The file is completely silent",
+};
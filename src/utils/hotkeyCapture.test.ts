@@ -0,0 +1,44 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { captureHotkey } from "./hotkeyCapture";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+describe("captureHotkey", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("should return the captured hotkey", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce("Ctrl+F1");
+
+    await expect(captureHotkey()).resolves.toBe("Ctrl+F1");
+    expect(invoke).toHaveBeenCalledWith("start_hotkey_capture");
+  });
+
+  it("should cancel the capture on Escape", async () => {
+    let finish: (hotkey: string) => void = () => {};
+    vi.mocked(invoke).mockImplementation((command) =>
+      command === "start_hotkey_capture"
+        ? new Promise((resolve) => (finish = resolve))
+        : Promise.resolve()
+    );
+
+    const capture = captureHotkey();
+    window.dispatchEvent(new KeyboardEvent("keydown", { key: "Escape" }));
+    expect(invoke).toHaveBeenCalledWith("cancel_hotkey_capture");
+
+    finish("F1");
+    await capture;
+  });
+
+  it("should stop listening once the capture ends", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce("F1");
+    await captureHotkey();
+
+    window.dispatchEvent(new KeyboardEvent("keydown", { key: "Escape" }));
+    expect(invoke).not.toHaveBeenCalledWith("cancel_hotkey_capture");
+  });
+});
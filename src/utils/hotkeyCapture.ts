@@ -0,0 +1,23 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * Capture the next key combination pressed (see start_hotkey_capture)
+ *
+ * Escape isn't registered as a global shortcut, since that would swallow it
+ * in every other application; pressing it in the app window cancels instead.
+ */
+export async function captureHotkey(): Promise<string> {
+  const onKeyDown = (event: KeyboardEvent) => {
+    if (event.key === "Escape") {
+      event.preventDefault();
+      void invoke("cancel_hotkey_capture");
+    }
+  };
+
+  window.addEventListener("keydown", onKeyDown);
+  try {
+    return await invoke<string>("start_hotkey_capture");
+  } finally {
+    window.removeEventListener("keydown", onKeyDown);
+  }
+}